@@ -0,0 +1,30 @@
+// Build the OpenVINO worker (isolated mode) and place it where the bundler
+// expects sidecars: src-tauri/binaries/openvino-worker-<target triple>.
+// Run by `npm run tauri:build` before `tauri build`.
+
+import { execFileSync } from "node:child_process";
+import { copyFileSync, mkdirSync } from "node:fs";
+import { dirname, join } from "node:path";
+import { fileURLToPath } from "node:url";
+
+const tauriDir = join(dirname(fileURLToPath(import.meta.url)), "..", "src-tauri");
+const ext = process.platform === "win32" ? ".exe" : "";
+
+const rustInfo = execFileSync("rustc", ["-vV"], { encoding: "utf8" });
+const triple = rustInfo.match(/^host: (\S+)$/m)?.[1];
+if (!triple) {
+  throw new Error("Cannot find the target triple in `rustc -vV`");
+}
+
+execFileSync("cargo", ["build", "--release", "--bin", "openvino-worker"], {
+  cwd: tauriDir,
+  stdio: "inherit",
+});
+
+const binaries = join(tauriDir, "binaries");
+mkdirSync(binaries, { recursive: true });
+copyFileSync(
+  join(tauriDir, "target", "release", `openvino-worker${ext}`),
+  join(binaries, `openvino-worker-${triple}${ext}`),
+);
+console.log(`openvino-worker copied for ${triple}`);
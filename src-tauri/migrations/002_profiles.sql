@@ -0,0 +1,15 @@
+-- Profils utilisateur (poste partagé)
+
+-- Table des profils avec leur jeu de préférences sérialisé
+CREATE TABLE IF NOT EXISTS profiles (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL,
+    settings_json TEXT
+);
+
+-- Profil par défaut (propriétaire des transcriptions existantes)
+INSERT OR IGNORE INTO profiles (id, name, created_at, settings_json)
+VALUES ('default', 'Default', datetime('now'), NULL);
+
+INSERT OR IGNORE INTO settings (key, value) VALUES ('active_profile_id', 'default');
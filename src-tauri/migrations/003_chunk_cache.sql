@@ -0,0 +1,10 @@
+-- Cache des transcriptions par chunk (re-transcription de fichiers retouchés)
+
+-- Clé = hash de l'audio du chunk + backend + langue + config de décodage
+CREATE TABLE IF NOT EXISTS chunk_cache (
+    key TEXT PRIMARY KEY,
+    text TEXT NOT NULL,
+    last_used_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_chunk_cache_last_used ON chunk_cache(last_used_at);
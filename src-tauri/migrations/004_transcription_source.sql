@@ -0,0 +1,43 @@
+-- Source typée des transcriptions + métadonnées de la source
+--
+-- SQLite ne permet pas de modifier une contrainte CHECK : la table est
+-- reconstruite (exécuté une seule fois, tant que source_path n'existe pas).
+-- Les clés étrangères sont désactivées pendant la reconstruction : sinon le
+-- DROP TABLE supprimerait en cascade les segments (ON DELETE CASCADE).
+
+PRAGMA foreign_keys = OFF;
+
+BEGIN;
+
+CREATE TABLE transcriptions_new (
+    id TEXT PRIMARY KEY,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    source_type TEXT NOT NULL CHECK (source_type IN ('dictation', 'file', 'stream', 'import', 'meeting')),
+    source_name TEXT,
+    duration_ms INTEGER,
+    language TEXT DEFAULT 'fr',
+    raw_text TEXT,
+    edited_text TEXT,
+    is_edited INTEGER DEFAULT 0,
+    profile_id TEXT,
+    source_path TEXT,
+    original_sample_rate INTEGER
+);
+
+-- Valeurs inconnues (anciennes versions) : traitées comme des fichiers
+INSERT INTO transcriptions_new (id, created_at, updated_at, source_type, source_name, duration_ms, language, raw_text, edited_text, is_edited, profile_id)
+SELECT id, created_at, updated_at,
+       CASE WHEN lower(source_type) IN ('dictation', 'file', 'stream', 'import', 'meeting') THEN lower(source_type) ELSE 'file' END,
+       source_name, duration_ms, language, raw_text, edited_text, is_edited, profile_id
+FROM transcriptions;
+
+DROP TABLE transcriptions;
+ALTER TABLE transcriptions_new RENAME TO transcriptions;
+
+CREATE INDEX IF NOT EXISTS idx_transcriptions_created ON transcriptions(created_at);
+CREATE INDEX IF NOT EXISTS idx_transcriptions_source ON transcriptions(source_type);
+
+COMMIT;
+
+PRAGMA foreign_keys = ON;
@@ -0,0 +1,12 @@
+-- Échecs des tâches de fond (dossier surveillé, enregistrements programmés, ...)
+
+CREATE TABLE IF NOT EXISTS job_errors (
+    id TEXT PRIMARY KEY,
+    job TEXT NOT NULL,
+    code TEXT NOT NULL,
+    message TEXT NOT NULL,
+    context TEXT,
+    occurred_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_errors_occurred_at ON job_errors(occurred_at);
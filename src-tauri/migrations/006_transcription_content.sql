@@ -0,0 +1,55 @@
+-- Textes complets séparés des métadonnées
+--
+-- list_transcriptions ne lit plus que les métadonnées et un aperçu ; le texte
+-- brut et le texte édité sont dans transcription_content. La table est
+-- reconstruite sans les colonnes de texte (exécuté une seule fois, tant que
+-- preview n'existe pas). Les clés étrangères sont désactivées pendant la
+-- reconstruction : sinon le DROP TABLE supprimerait en cascade les segments et
+-- les textes copiés dans transcription_content.
+
+PRAGMA foreign_keys = OFF;
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS transcription_content (
+    transcription_id TEXT PRIMARY KEY,
+    raw_text TEXT NOT NULL DEFAULT '',
+    edited_text TEXT,
+    FOREIGN KEY (transcription_id) REFERENCES transcriptions(id) ON DELETE CASCADE
+);
+
+INSERT OR REPLACE INTO transcription_content (transcription_id, raw_text, edited_text)
+SELECT id, COALESCE(raw_text, ''), edited_text FROM transcriptions;
+
+CREATE TABLE transcriptions_new (
+    id TEXT PRIMARY KEY,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    source_type TEXT NOT NULL CHECK (source_type IN ('dictation', 'file', 'stream', 'import', 'meeting')),
+    source_name TEXT,
+    duration_ms INTEGER,
+    language TEXT DEFAULT 'fr',
+    preview TEXT NOT NULL DEFAULT '',
+    is_edited INTEGER DEFAULT 0,
+    profile_id TEXT,
+    source_path TEXT,
+    original_sample_rate INTEGER,
+    action_items TEXT,
+    markers TEXT
+);
+
+INSERT INTO transcriptions_new (id, created_at, updated_at, source_type, source_name, duration_ms, language, preview, is_edited, profile_id, source_path, original_sample_rate, action_items, markers)
+SELECT id, created_at, updated_at, source_type, source_name, duration_ms, language,
+       substr(COALESCE(edited_text, raw_text, ''), 1, 200),
+       is_edited, profile_id, source_path, original_sample_rate, action_items, markers
+FROM transcriptions;
+
+DROP TABLE transcriptions;
+ALTER TABLE transcriptions_new RENAME TO transcriptions;
+
+CREATE INDEX IF NOT EXISTS idx_transcriptions_created ON transcriptions(created_at);
+CREATE INDEX IF NOT EXISTS idx_transcriptions_source ON transcriptions(source_type);
+
+COMMIT;
+
+PRAGMA foreign_keys = ON;
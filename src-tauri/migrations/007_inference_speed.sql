@@ -0,0 +1,12 @@
+-- Vitesse mesurée des transcriptions, par backend (estimation de la durée des tâches)
+
+CREATE TABLE IF NOT EXISTS inference_speed (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    backend TEXT NOT NULL,
+    beam_width INTEGER NOT NULL,
+    audio_ms INTEGER NOT NULL,
+    elapsed_ms INTEGER NOT NULL,
+    recorded_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_inference_speed_backend ON inference_speed(backend, recorded_at);
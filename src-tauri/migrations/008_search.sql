@@ -0,0 +1,60 @@
+-- Recherche plein texte dans l'historique (FTS5)
+--
+-- Index des textes (brut et édité) et des segments, tenus à jour par des
+-- triggers. Les accents sont ignorés ("ete" trouve "été"). Exécuté une seule
+-- fois, tant que transcription_fts n'existe pas : les lignes existantes sont
+-- indexées à la fin.
+
+BEGIN;
+
+CREATE VIRTUAL TABLE transcription_fts USING fts5(
+    transcription_id UNINDEXED,
+    raw_text,
+    edited_text,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE VIRTUAL TABLE segment_fts USING fts5(
+    segment_id UNINDEXED,
+    transcription_id UNINDEXED,
+    text,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER transcription_content_fts_insert AFTER INSERT ON transcription_content BEGIN
+    INSERT INTO transcription_fts (transcription_id, raw_text, edited_text)
+    VALUES (new.transcription_id, new.raw_text, new.edited_text);
+END;
+
+CREATE TRIGGER transcription_content_fts_update AFTER UPDATE ON transcription_content BEGIN
+    DELETE FROM transcription_fts WHERE transcription_id = old.transcription_id;
+    INSERT INTO transcription_fts (transcription_id, raw_text, edited_text)
+    VALUES (new.transcription_id, new.raw_text, new.edited_text);
+END;
+
+CREATE TRIGGER transcription_content_fts_delete AFTER DELETE ON transcription_content BEGIN
+    DELETE FROM transcription_fts WHERE transcription_id = old.transcription_id;
+END;
+
+CREATE TRIGGER segments_fts_insert AFTER INSERT ON segments BEGIN
+    INSERT INTO segment_fts (segment_id, transcription_id, text)
+    VALUES (new.id, new.transcription_id, new.text);
+END;
+
+CREATE TRIGGER segments_fts_update AFTER UPDATE ON segments BEGIN
+    DELETE FROM segment_fts WHERE segment_id = old.id;
+    INSERT INTO segment_fts (segment_id, transcription_id, text)
+    VALUES (new.id, new.transcription_id, new.text);
+END;
+
+CREATE TRIGGER segments_fts_delete AFTER DELETE ON segments BEGIN
+    DELETE FROM segment_fts WHERE segment_id = old.id;
+END;
+
+INSERT INTO transcription_fts (transcription_id, raw_text, edited_text)
+SELECT transcription_id, raw_text, edited_text FROM transcription_content;
+
+INSERT INTO segment_fts (segment_id, transcription_id, text)
+SELECT id, transcription_id, text FROM segments;
+
+COMMIT;
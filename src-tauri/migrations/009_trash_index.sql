@@ -0,0 +1,3 @@
+-- Index de la corbeille (liste et purge des transcriptions supprimées)
+
+CREATE INDEX IF NOT EXISTS idx_transcriptions_deleted ON transcriptions(deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,3 @@
+-- Fichier audio de l'enregistrement, conservé pour la réécoute (si activé)
+
+ALTER TABLE transcriptions ADD COLUMN audio_path TEXT;
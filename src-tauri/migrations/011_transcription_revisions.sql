@@ -0,0 +1,20 @@
+-- Historique des modifications du texte
+--
+-- Chaque enregistrement du texte édité ajoute une révision numérotée ; la
+-- révision 0 (le texte transcrit) reste dans transcription_content. Les
+-- textes déjà édités deviennent la révision 1. Pas de clé étrangère : le
+-- remplacement d'une transcription (synchronisation) garde son historique.
+
+CREATE TABLE IF NOT EXISTS transcription_revisions (
+    transcription_id TEXT NOT NULL,
+    revision INTEGER NOT NULL,
+    text TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (transcription_id, revision)
+);
+
+INSERT OR IGNORE INTO transcription_revisions (transcription_id, revision, text, created_at)
+SELECT c.transcription_id, 1, c.edited_text, t.updated_at
+FROM transcription_content c
+JOIN transcriptions t ON t.id = c.transcription_id
+WHERE c.edited_text IS NOT NULL;
@@ -0,0 +1,3 @@
+-- Langue identifiée de chaque segment (mode Auto)
+
+ALTER TABLE segments ADD COLUMN language TEXT;
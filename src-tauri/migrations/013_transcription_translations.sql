@@ -0,0 +1,13 @@
+-- Traductions d'une transcription, en textes parallèles
+--
+-- Une traduction par langue cible ; la traduire à nouveau la remplace.
+-- Comme l'historique, elles sont gardées au remplacement d'une
+-- transcription (synchronisation).
+
+CREATE TABLE IF NOT EXISTS transcription_translations (
+    transcription_id TEXT NOT NULL,
+    language TEXT NOT NULL,
+    text TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (transcription_id, language)
+);
@@ -0,0 +1,15 @@
+-- Notes générées par un LLM (résumé, points clés, actions)
+--
+-- Une ligne par style de résumé ; le régénérer la remplace. Listes en JSON.
+-- Gardées au remplacement d'une transcription, comme les traductions.
+
+CREATE TABLE IF NOT EXISTS transcription_notes (
+    transcription_id TEXT NOT NULL,
+    style TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    key_points TEXT NOT NULL DEFAULT '[]',
+    action_items TEXT NOT NULL DEFAULT '[]',
+    model TEXT,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (transcription_id, style)
+);
@@ -0,0 +1,26 @@
+-- Étiquettes et dossiers pour ranger l'historique
+--
+-- Les étiquettes appartiennent à un profil (nom unique, sans tenir compte de
+-- la casse) ; une transcription peut en avoir plusieurs. Le dossier (client,
+-- projet) est un simple nom sur la transcription.
+
+CREATE TABLE IF NOT EXISTS tags (
+    id TEXT PRIMARY KEY,
+    profile_id TEXT NOT NULL DEFAULT 'default',
+    name TEXT NOT NULL COLLATE NOCASE,
+    color TEXT,
+    created_at TEXT NOT NULL,
+    UNIQUE (profile_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS transcription_tags (
+    transcription_id TEXT NOT NULL,
+    tag_id TEXT NOT NULL,
+    PRIMARY KEY (transcription_id, tag_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcription_tags_tag ON transcription_tags(tag_id);
+
+ALTER TABLE transcriptions ADD COLUMN folder TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_transcriptions_folder ON transcriptions(folder) WHERE folder IS NOT NULL;
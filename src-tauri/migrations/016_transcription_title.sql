@@ -0,0 +1,3 @@
+-- Titre donné par l'utilisateur (les dictées n'ont pas de nom de source)
+
+ALTER TABLE transcriptions ADD COLUMN title TEXT;
@@ -0,0 +1,3 @@
+-- Pauses d'un enregistrement (JSON : position dans l'audio enregistré, durée)
+
+ALTER TABLE transcriptions ADD COLUMN pauses TEXT;
@@ -0,0 +1,18 @@
+-- Transcriptions de fichiers en cours
+--
+-- Le résultat de chaque morceau est ajouté (JSON) dès qu'il est transcrit ;
+-- la ligne est supprimée une fois la transcription enregistrée. Celles qui
+-- restent ont été interrompues : elles peuvent être reprises ou finalisées.
+
+CREATE TABLE IF NOT EXISTS pending_transcriptions (
+    id TEXT PRIMARY KEY,
+    source_path TEXT NOT NULL,
+    source_name TEXT,
+    language TEXT,
+    decoding_config TEXT,
+    normalization TEXT,
+    chunk_count INTEGER NOT NULL DEFAULT 0,
+    chunks TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
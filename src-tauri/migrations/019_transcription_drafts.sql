@@ -0,0 +1,13 @@
+-- Brouillons du texte en cours d'édition
+--
+-- L'éditeur enregistre le texte modifié quelques secondes après la dernière
+-- frappe, sans créer de révision. Le brouillon est supprimé quand le texte
+-- est enregistré ; s'il reste, il est proposé à la réouverture.
+-- base_updated_at : updated_at de la transcription quand l'édition a commencé.
+
+CREATE TABLE IF NOT EXISTS transcription_drafts (
+    transcription_id TEXT PRIMARY KEY,
+    text TEXT NOT NULL,
+    base_updated_at TEXT NOT NULL,
+    saved_at TEXT NOT NULL
+);
@@ -0,0 +1,12 @@
+-- Transcriptions supprimées définitivement
+--
+-- La synchronisation envoie ces suppressions dans le manifeste distant, pour
+-- que les autres machines suppriment aussi leur copie au lieu de la renvoyer.
+-- Une transcription enregistrée à nouveau avec le même id (synchronisation,
+-- import d'une sauvegarde) n'est plus considérée comme supprimée.
+
+CREATE TABLE IF NOT EXISTS deleted_transcriptions (
+    id TEXT PRIMARY KEY,
+    profile_id TEXT NOT NULL,
+    deleted_at TEXT NOT NULL
+);
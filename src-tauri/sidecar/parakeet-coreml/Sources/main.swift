@@ -93,7 +93,7 @@ struct ParakeetCoreML {
         let args = CommandLine.arguments
 
         guard let cliArgs = CLIArguments.parse(args) else {
-            exitWithError("Usage: parakeet-coreml <audio.wav> [--models <path>] [--language <auto|french|english>] [--beam-width <N>] [--temperature <F>] [--blank-penalty <F>]")
+            exitWithError("Usage: parakeet-coreml <audio.wav> [--models <path>] [--language <auto|fr|en|de|...>] [--beam-width <N>] [--temperature <F>] [--blank-penalty <F>]")
         }
 
         log("Audio: \(cliArgs.audioPath)")
@@ -315,6 +315,11 @@ class Vocabulary {
         log("Loaded vocabulary with \(tokens.count) tokens")
     }
 
+    /// Id of a token, e.g. a language tag ("<|de|>")
+    func id(of token: String) -> Int? {
+        tokens.first { $0.value == token }?.key
+    }
+
     func decode(_ tokenId: Int) -> String {
         guard tokenId != 8192 else { return "" } // blank
         return tokens[tokenId] ?? ""
@@ -654,7 +659,9 @@ class TDTDecoder {
             cState = c2
 
             // Step 3: language token
-            let langToken = (language == "french" || language == "fr") ? tokenFrench : tokenEnglish
+            // ISO code ("de"), or the older "french"/"english" names
+            let code = language == "french" ? "fr" : language == "english" ? "en" : language
+            let langToken = vocabulary.id(of: "<|\(code)|>") ?? (code == "fr" ? tokenFrench : tokenEnglish)
             let (_, h3, c3) = try runDecoderStep(token: langToken, hState: hState, cState: cState)
             hState = h3
             cState = c3
@@ -819,7 +826,7 @@ class TDTDecoder {
 //         let args = CommandLine.arguments
 //
 //         guard let cliArgs = CLIArguments.parse(args) else {
-//             exitWithError("Usage: parakeet-coreml <audio.wav> [--models <path>] [--language <auto|french|english>] [--beam-width <N>] [--temperature <F>] [--blank-penalty <F>]")
+//             exitWithError("Usage: parakeet-coreml <audio.wav> [--models <path>] [--language <auto|fr|en|de|...>] [--beam-width <N>] [--temperature <F>] [--blank-penalty <F>]")
 //         }
 //
 //         log("Audio: \(cliArgs.audioPath)")
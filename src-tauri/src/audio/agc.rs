@@ -0,0 +1,111 @@
+//! Automatic gain control of the microphone while recording
+//!
+//! Level normalization only runs once the recording stops. Meanwhile a quiet
+//! speaker fills the buffer with speech below the VAD silence threshold, so
+//! rolling blocks and live captions treat it as pauses and drop it. The AGC
+//! raises the level in the capture callback, slowly, and only on buffers loud
+//! enough to be speech: pauses keep the gain they had, and noise is not
+//! pumped up to the speech level.
+
+use super::processor::soft_clip;
+
+/// Level the AGC brings speech to (below `TARGET_RMS`, which normalization
+/// reaches afterwards, to leave headroom for peaks)
+const TARGET_RMS: f32 = 0.1;
+
+/// Buffers quieter than this are pauses: the gain is held
+const SPEECH_RMS_FLOOR: f32 = 0.002;
+
+/// Gain range (+20 dB at most: beyond, it is mostly noise that gets raised)
+const MIN_GAIN: f32 = 0.5;
+const MAX_GAIN: f32 = 10.0;
+
+/// Time constants: the gain drops fast on loud speech and rises slowly
+const ATTACK_SECONDS: f32 = 0.05;
+const RELEASE_SECONDS: f32 = 2.0;
+
+/// Gain state of one capture stream
+pub struct Agc {
+    gain: f32,
+    /// Per-frame smoothing coefficients
+    attack: f32,
+    release: f32,
+    channels: usize,
+}
+
+impl Agc {
+    pub fn new(sample_rate: u32, channels: u16) -> Self {
+        let coefficient = |seconds: f32| 1.0 - (-1.0 / (seconds * sample_rate.max(1) as f32)).exp();
+        Self {
+            gain: 1.0,
+            attack: coefficient(ATTACK_SECONDS),
+            release: coefficient(RELEASE_SECONDS),
+            channels: channels.max(1) as usize,
+        }
+    }
+
+    /// Apply the gain to one callback buffer (interleaved), in place
+    pub fn process(&mut self, samples: &mut [f32]) {
+        if samples.is_empty() {
+            return;
+        }
+        let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+        let wanted = if rms >= SPEECH_RMS_FLOOR {
+            (TARGET_RMS / rms).clamp(MIN_GAIN, MAX_GAIN)
+        } else {
+            self.gain
+        };
+        let rate = if wanted < self.gain { self.attack } else { self.release };
+
+        for frame in samples.chunks_mut(self.channels) {
+            self.gain += (wanted - self.gain) * rate;
+            for s in frame {
+                *s = soft_clip(*s * self.gain);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(seconds: f32, amplitude: f32) -> Vec<f32> {
+        (0..(seconds * 16000.0) as usize)
+            .map(|i| (2.0 * std::f32::consts::PI * 220.0 * i as f32 / 16000.0).sin() * amplitude)
+            .collect()
+    }
+
+    fn run(agc: &mut Agc, mut samples: Vec<f32>) -> Vec<f32> {
+        // Callback-sized buffers (32ms)
+        for buffer in samples.chunks_mut(512) {
+            agc.process(buffer);
+        }
+        samples
+    }
+
+    #[test]
+    fn test_quiet_speech_raised_above_vad_threshold() {
+        let mut agc = Agc::new(16000, 1);
+        let out = run(&mut agc, tone(8.0, 0.008)); // RMS ~0.0057, below the VAD's 0.01
+        let tail_rms = (out[out.len() - 16000..].iter().map(|s| s * s).sum::<f32>() / 16000.0).sqrt();
+        assert!(tail_rms > 0.05, "rms {}", tail_rms);
+        assert!(agc.gain <= MAX_GAIN);
+    }
+
+    #[test]
+    fn test_gain_held_during_pauses_and_lowered_fast() {
+        let mut agc = Agc::new(16000, 1);
+        run(&mut agc, tone(8.0, 0.02));
+        let raised = agc.gain;
+        assert!(raised > 3.0);
+
+        // Near silence: not pumped up further
+        run(&mut agc, vec![0.0005; 32000]);
+        assert_eq!(agc.gain, raised);
+
+        // Loud speech: back down within a fraction of a second
+        run(&mut agc, tone(0.5, 0.5));
+        assert!(agc.gain < 0.6);
+    }
+}
@@ -1,12 +1,22 @@
+use crate::audio::agc::Agc;
+use crate::audio::dual::downmix;
+use crate::audio::ducking::{self, Ducked};
+use crate::audio::level::{LevelMeter, HISTORY_RATE_HZ};
+use crate::audio::processor::resample_to_16k;
+use crate::audio::spill::{SpillFile, SPILL_THRESHOLD};
+use crate::audio::vad::SilenceMonitor;
 use crate::error::{AppError, Result};
-use crate::storage::AudioDevice;
+use crate::storage::{AudioDevice, AudioReset, AudioWaveform, DeviceCapabilities, InputConfig, RecordingLimit, RecordingMarker, RecordingPause, RecordingStatus};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use cpal::{SampleFormat, StreamConfig};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::mpsc::{self, Receiver, Sender};
+use cpal::{
+    BufferSize, SampleFormat, SampleRate, StreamConfig, SupportedBufferSize, SupportedStreamConfig,
+    SupportedStreamConfigRange,
+};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use parking_lot::Mutex;
 use tracing::{debug, info, warn};
 
@@ -14,9 +24,17 @@ use tracing::{debug, info, warn};
 enum AudioCommand {
     Start {
         device_id: Option<String>,
+        /// System audio captured alongside the microphone (dual-source recording)
+        loopback_id: Option<String>,
         response: Sender<Result<()>>,
     },
+    /// Microphone samples, and those of the loopback source if any
     Stop {
+        response: Sender<Result<(Vec<f32>, Option<SourceAudio>)>>,
+    },
+    /// Copy of the buffer from `from`, recording continues
+    Snapshot {
+        from: usize,
         response: Sender<Result<Vec<f32>>>,
     },
     Pause,
@@ -24,49 +42,416 @@ enum AudioCommand {
     Shutdown,
 }
 
-/// Audio capture manager that handles threading internally
-pub struct AudioCapture {
-    command_tx: Sender<AudioCommand>,
-    _thread_handle: JoinHandle<()>,
+/// Audio of one source as captured (device rate, interleaved channels)
+#[derive(Debug, Clone)]
+pub struct SourceAudio {
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+/// How often the watchdog checks that the audio thread is alive
+const WATCHDOG_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How often the audio thread checks the buffer (silence, move to disk)
+const TICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Time left before the length limit when the warning is sent
+const LIMIT_WARNING: Duration = Duration::from_secs(5 * 60);
+
+const RESET_ERROR: &str = "Audio thread restarted, recording was reset";
+
+type ResetHandler = Box<dyn Fn(&AudioReset) + Send + Sync>;
+type LimitHandler = Box<dyn Fn(&RecordingLimit) + Send + Sync>;
+type AutoStopHandler = Box<dyn Fn(u64) + Send + Sync>;
+
+/// State shared by the capture handle, the audio thread and its stream callbacks
+#[derive(Clone)]
+struct SharedState {
     is_recording: Arc<AtomicBool>,
     is_paused: Arc<AtomicBool>,
-    audio_level: Arc<Mutex<f32>>,
+    /// Microphone level and its recent history
+    audio_level: Arc<Mutex<LevelMeter>>,
     sample_rate: Arc<Mutex<u32>>,
+    /// Samples in the recording buffer, updated by the stream callback
+    buffered_samples: Arc<AtomicU64>,
+    /// Part of `buffered_samples` moved to the spill file
+    spilled_samples: Arc<AtomicU64>,
+    /// Channel count of the current stream (buffer is interleaved)
+    channels: Arc<AtomicU32>,
+    /// Automatic gain control of the microphone, read when a recording starts
+    agc: Arc<AtomicBool>,
+    /// Format of the microphone stream, read when a recording starts
+    input_config: Arc<Mutex<InputConfig>>,
+    /// Recording length after which audio is no longer captured (0: no limit),
+    /// read when a recording starts
+    max_duration_ms: Arc<AtomicU64>,
+    /// Limit notice sent for the current recording (0: none, 1: warning, 2: reached)
+    limit_notice: Arc<AtomicU8>,
+    /// Silence after which the recording is stopped, in ms (0: never), read
+    /// when a recording starts
+    auto_stop_ms: Arc<AtomicU64>,
+    /// Silence found by the audio thread, waiting for the watchdog to stop the recording
+    auto_stop_pending: Arc<AtomicU64>,
+    /// Pauses of the current recording, kept by the audio thread
+    pauses: Arc<Mutex<Vec<RecordingPause>>>,
+}
+
+impl SharedState {
+    /// Recorded duration, derived from the buffer size
+    fn elapsed_ms(&self) -> u64 {
+        let buffered_samples = self.buffered_samples.load(Ordering::SeqCst);
+        let channels = self.channels.load(Ordering::SeqCst).max(1) as u64;
+        let sample_rate = *self.sample_rate.lock() as u64;
+        if sample_rate > 0 {
+            buffered_samples * 1000 / (sample_rate * channels)
+        } else {
+            0
+        }
+    }
+}
+
+/// A running audio thread
+struct Worker {
+    command_tx: Sender<AudioCommand>,
+    thread_handle: Option<JoinHandle<()>>,
+    /// Incremented on each restart, so a dead thread is only replaced once
+    epoch: u64,
+}
+
+impl Worker {
+    fn spawn(state: &SharedState, epoch: u64) -> Self {
+        let (command_tx, command_rx) = mpsc::channel();
+        let state = state.clone();
+        let thread_handle = thread::spawn(move || audio_thread(command_rx, state));
+        Self {
+            command_tx,
+            thread_handle: Some(thread_handle),
+            epoch,
+        }
+    }
+
+    /// Start recording (None if the thread is not responding)
+    fn start(&self, device_id: Option<&str>, loopback_id: Option<&str>) -> Option<Result<()>> {
+        let (response_tx, response_rx) = mpsc::channel();
+        self.command_tx
+            .send(AudioCommand::Start {
+                device_id: device_id.map(String::from),
+                loopback_id: loopback_id.map(String::from),
+                response: response_tx,
+            })
+            .ok()?;
+        response_rx.recv().ok()
+    }
+}
+
+/// Restarts the audio thread when it dies (panic, closed channel)
+#[derive(Clone)]
+struct Supervisor {
+    worker: Arc<Mutex<Worker>>,
+    state: SharedState,
+    /// Device of the last started recording, reused after a restart
+    device_id: Arc<Mutex<Option<String>>>,
+    /// Loopback source of the last started recording, if dual-source
+    loopback_id: Arc<Mutex<Option<String>>>,
+    on_reset: Arc<Mutex<Option<ResetHandler>>>,
+    on_limit: Arc<Mutex<Option<LimitHandler>>>,
+    on_auto_stop: Arc<Mutex<Option<AutoStopHandler>>>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl Supervisor {
+    fn epoch(&self) -> u64 {
+        self.worker.lock().epoch
+    }
+
+    fn send(&self, command: AudioCommand) -> Result<()> {
+        let (sent, epoch) = {
+            let worker = self.worker.lock();
+            (worker.command_tx.send(command).is_ok(), worker.epoch)
+        };
+        if !sent {
+            self.restart(epoch, "Audio thread not responding");
+            return Err(AppError::Audio(RESET_ERROR.into()));
+        }
+        Ok(())
+    }
+
+    /// Send a command and wait for its response
+    fn request<T>(&self, command: impl FnOnce(Sender<Result<T>>) -> AudioCommand) -> Result<T> {
+        let epoch = self.epoch();
+        let (response_tx, response_rx) = mpsc::channel();
+        self.send(command(response_tx))?;
+        match response_rx.recv() {
+            Ok(result) => result,
+            Err(_) => {
+                // The thread dropped the request: it died while handling it
+                self.restart(epoch, "Audio thread died while handling a command");
+                Err(AppError::Audio(RESET_ERROR.into()))
+            }
+        }
+    }
+
+    fn is_dead(&self) -> Option<u64> {
+        let worker = self.worker.lock();
+        let finished = worker
+            .thread_handle
+            .as_ref()
+            .map(|h| h.is_finished())
+            .unwrap_or(true);
+        finished.then_some(worker.epoch)
+    }
+
+    /// Replace the audio thread of `epoch` (no-op if already replaced)
+    fn restart(&self, epoch: u64, reason: &str) {
+        if self.shutdown.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let mut worker = self.worker.lock();
+        if worker.epoch != epoch {
+            return;
+        }
+        warn!("{}: restarting audio thread", reason);
+
+        // Its receiver is gone, so the thread is exiting: wait for the unwind to finish
+        if let Some(handle) = worker.thread_handle.take() {
+            if handle.join().is_err() {
+                warn!("Audio thread had panicked");
+            }
+        }
+
+        let was_recording = self.state.is_recording.swap(false, Ordering::SeqCst);
+        self.state.is_paused.store(false, Ordering::SeqCst);
+        self.state.buffered_samples.store(0, Ordering::SeqCst);
+        self.state.spilled_samples.store(0, Ordering::SeqCst);
+        self.state.limit_notice.store(0, Ordering::SeqCst);
+        self.state.auto_stop_pending.store(0, Ordering::SeqCst);
+        *self.state.audio_level.lock() = LevelMeter::new();
+
+        *worker = Worker::spawn(&self.state, epoch + 1);
+
+        // Resume recording on the same device (the audio captured so far is lost)
+        let device_id = self.device_id.lock().clone();
+        let loopback_id = self.loopback_id.lock().clone();
+        let recording_restarted = was_recording
+            && match worker.start(device_id.as_deref(), loopback_id.as_deref()) {
+                Some(Ok(())) => true,
+                Some(Err(e)) => {
+                    warn!("Failed to resume recording after restart: {}", e);
+                    false
+                }
+                None => false,
+            };
+        drop(worker);
+
+        info!(
+            "Audio thread restarted (was recording: {}, resumed: {})",
+            was_recording, recording_restarted
+        );
+
+        if let Some(handler) = self.on_reset.lock().as_ref() {
+            handler(&AudioReset {
+                reason: reason.to_string(),
+                was_recording,
+                recording_restarted,
+                device_id,
+            });
+        }
+    }
+
+    /// Notify when the recording nears, then reaches, its length limit (once each)
+    fn check_limit(&self) {
+        let max_ms = self.state.max_duration_ms.load(Ordering::SeqCst);
+        if max_ms == 0 || !self.state.is_recording.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let elapsed_ms = self.state.elapsed_ms();
+        let notice = if elapsed_ms >= max_ms {
+            2
+        } else if elapsed_ms + LIMIT_WARNING.as_millis() as u64 >= max_ms {
+            1
+        } else {
+            return;
+        };
+        if self.state.limit_notice.fetch_max(notice, Ordering::SeqCst) >= notice {
+            return;
+        }
+
+        let reached = notice == 2;
+        if reached {
+            warn!("Recording reached its {}ms limit, audio is no longer captured", max_ms);
+        } else {
+            info!("Recording at {}ms, limit is {}ms", elapsed_ms, max_ms);
+        }
+        if let Some(handler) = self.on_limit.lock().as_ref() {
+            handler(&RecordingLimit {
+                max_ms,
+                elapsed_ms,
+                reached,
+            });
+        }
+    }
+
+    /// Hand a recording left silent to the auto-stop handler
+    fn check_auto_stop(&self) {
+        let silence_ms = self.state.auto_stop_pending.swap(0, Ordering::SeqCst);
+        if silence_ms == 0 || !self.state.is_recording.load(Ordering::SeqCst) {
+            return;
+        }
+        info!("No speech for {}ms, stopping the recording", silence_ms);
+        if let Some(handler) = self.on_auto_stop.lock().as_ref() {
+            handler(silence_ms);
+        }
+    }
+}
+
+/// Audio capture manager that handles threading internally
+///
+/// The cpal stream lives on a dedicated thread. A watchdog thread restarts it
+/// if it dies, so audio commands don't fail for the rest of the session.
+pub struct AudioCapture {
+    supervisor: Supervisor,
+    state: SharedState,
+    /// Markers added during the current recording
+    markers: Mutex<Vec<RecordingMarker>>,
+    /// Loopback audio of the last stopped dual-source recording
+    loopback: Mutex<Option<SourceAudio>>,
+    /// Output volume while recording, in percent of its level (None: untouched)
+    duck_level: Mutex<Option<u8>>,
+    /// Output volume lowered for the running recording, restored when taken
+    ducked: Mutex<Option<Ducked>>,
 }
 
 impl AudioCapture {
     pub fn new() -> Self {
-        let (command_tx, command_rx) = mpsc::channel();
-        let is_recording = Arc::new(AtomicBool::new(false));
-        let is_paused = Arc::new(AtomicBool::new(false));
-        let audio_level = Arc::new(Mutex::new(0.0f32));
-        let sample_rate = Arc::new(Mutex::new(16000u32));
-
-        let is_recording_clone = Arc::clone(&is_recording);
-        let is_paused_clone = Arc::clone(&is_paused);
-        let audio_level_clone = Arc::clone(&audio_level);
-        let sample_rate_clone = Arc::clone(&sample_rate);
-
-        let thread_handle = thread::spawn(move || {
-            audio_thread(
-                command_rx,
-                is_recording_clone,
-                is_paused_clone,
-                audio_level_clone,
-                sample_rate_clone,
-            );
+        let state = SharedState {
+            is_recording: Arc::new(AtomicBool::new(false)),
+            is_paused: Arc::new(AtomicBool::new(false)),
+            audio_level: Arc::new(Mutex::new(LevelMeter::new())),
+            sample_rate: Arc::new(Mutex::new(16000u32)),
+            buffered_samples: Arc::new(AtomicU64::new(0)),
+            spilled_samples: Arc::new(AtomicU64::new(0)),
+            channels: Arc::new(AtomicU32::new(1)),
+            agc: Arc::new(AtomicBool::new(false)),
+            input_config: Arc::new(Mutex::new(InputConfig::default())),
+            max_duration_ms: Arc::new(AtomicU64::new(0)),
+            limit_notice: Arc::new(AtomicU8::new(0)),
+            auto_stop_ms: Arc::new(AtomicU64::new(0)),
+            auto_stop_pending: Arc::new(AtomicU64::new(0)),
+            pauses: Arc::new(Mutex::new(Vec::new())),
+        };
+
+        let supervisor = Supervisor {
+            worker: Arc::new(Mutex::new(Worker::spawn(&state, 0))),
+            state: state.clone(),
+            device_id: Arc::new(Mutex::new(None)),
+            loopback_id: Arc::new(Mutex::new(None)),
+            on_reset: Arc::new(Mutex::new(None)),
+            on_limit: Arc::new(Mutex::new(None)),
+            on_auto_stop: Arc::new(Mutex::new(None)),
+            shutdown: Arc::new(AtomicBool::new(false)),
+        };
+
+        let watchdog = supervisor.clone();
+        thread::spawn(move || {
+            while !watchdog.shutdown.load(Ordering::SeqCst) {
+                thread::sleep(WATCHDOG_INTERVAL);
+                if watchdog.shutdown.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Some(epoch) = watchdog.is_dead() {
+                    watchdog.restart(epoch, "Audio thread stopped unexpectedly");
+                }
+                watchdog.check_limit();
+                watchdog.check_auto_stop();
+            }
         });
 
         Self {
-            command_tx,
-            _thread_handle: thread_handle,
-            is_recording,
-            is_paused,
-            audio_level,
-            sample_rate,
+            supervisor,
+            state,
+            markers: Mutex::new(Vec::new()),
+            loopback: Mutex::new(None),
+            duck_level: Mutex::new(None),
+            ducked: Mutex::new(None),
+        }
+    }
+
+    /// Lower the system output to `level` percent while recording (None: never)
+    ///
+    /// Applies from the next start. Dual-source recordings are never ducked:
+    /// the system audio is part of what is recorded.
+    pub fn set_ducking(&self, level: Option<u8>) {
+        *self.duck_level.lock() = level;
+    }
+
+    /// Raise the level of quiet speakers while recording (see `agc`)
+    ///
+    /// Applies from the next start, to the microphone only.
+    pub fn set_agc(&self, enabled: bool) {
+        self.state.agc.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Sample rate, channel and buffer size of the microphone stream (unset: device default)
+    ///
+    /// Applies from the next start, to the microphone only.
+    pub fn set_input_config(&self, config: InputConfig) {
+        *self.state.input_config.lock() = config;
+    }
+
+    /// Stop capturing audio once a recording is `max` long (None: no limit)
+    ///
+    /// Applies from the next start. The recording stays open until stopped;
+    /// see `set_limit_handler` for the notices.
+    pub fn set_max_duration(&self, max: Option<Duration>) {
+        let max_ms = max.map_or(0, |d| d.as_millis() as u64);
+        self.state.max_duration_ms.store(max_ms, Ordering::SeqCst);
+    }
+
+    /// Stop a recording once nobody has spoken for `silence` (None: never)
+    ///
+    /// Applies from the next start. Speech is found by the VAD on the
+    /// microphone; the stop itself is left to `set_auto_stop_handler`.
+    pub fn set_auto_stop(&self, silence: Option<Duration>) {
+        let silence_ms = silence.map_or(0, |d| d.as_millis() as u64);
+        self.state.auto_stop_ms.store(silence_ms, Ordering::SeqCst);
+    }
+
+    fn duck_output(&self) {
+        let Some(level) = *self.duck_level.lock() else {
+            return;
+        };
+        if self.supervisor.loopback_id.lock().is_some() {
+            return;
+        }
+        let mut ducked = self.ducked.lock();
+        if ducked.is_none() {
+            *ducked = ducking::duck(level);
         }
     }
 
+    fn restore_output(&self) {
+        // Dropping restores the volume
+        self.ducked.lock().take();
+    }
+
+    /// Called after the audio thread was restarted (e.g. to notify the UI)
+    pub fn set_reset_handler(&self, handler: impl Fn(&AudioReset) + Send + Sync + 'static) {
+        *self.supervisor.on_reset.lock() = Some(Box::new(handler));
+    }
+
+    /// Called a few minutes before the length limit, then when it is reached
+    pub fn set_limit_handler(&self, handler: impl Fn(&RecordingLimit) + Send + Sync + 'static) {
+        *self.supervisor.on_limit.lock() = Some(Box::new(handler));
+    }
+
+    /// Called with the silence length when a recording should stop (see `set_auto_stop`)
+    pub fn set_auto_stop_handler(&self, handler: impl Fn(u64) + Send + Sync + 'static) {
+        *self.supervisor.on_auto_stop.lock() = Some(Box::new(handler));
+    }
+
     pub fn list_devices() -> Result<Vec<AudioDevice>> {
         let host = cpal::default_host();
         let default_device = host.default_input_device();
@@ -91,81 +476,244 @@ impl AudioCapture {
         Ok(devices)
     }
 
-    pub fn start(&self, device_id: Option<&str>) -> Result<()> {
-        if self.is_recording.load(Ordering::SeqCst) {
-            return Err(AppError::InvalidState("Already recording".into()));
+    /// Rates, channels, formats and buffer sizes an input device accepts
+    pub fn device_capabilities(device_id: Option<&str>) -> Result<DeviceCapabilities> {
+        let device = find_input(device_id)?;
+        let default = device
+            .default_input_config()
+            .map_err(|e| AppError::Audio(e.to_string()))?;
+        let ranges: Vec<SupportedStreamConfigRange> = device
+            .supported_input_configs()
+            .map_err(|e| AppError::Audio(e.to_string()))?
+            .collect();
+
+        let mut sample_rates: Vec<u32> = COMMON_SAMPLE_RATES
+            .iter()
+            .copied()
+            .filter(|&rate| ranges.iter().any(|r| (r.min_sample_rate().0..=r.max_sample_rate().0).contains(&rate)))
+            .chain([default.sample_rate().0])
+            .collect();
+        sample_rates.sort_unstable();
+        sample_rates.dedup();
+        let mut sample_formats: Vec<String> = ranges.iter().map(|r| r.sample_format().to_string()).collect();
+        sample_formats.sort();
+        sample_formats.dedup();
+        let buffer_sizes = ranges.iter().filter_map(|r| match r.buffer_size() {
+            SupportedBufferSize::Range { min, max } => Some((*min, *max)),
+            SupportedBufferSize::Unknown => None,
+        });
+
+        Ok(DeviceCapabilities {
+            id: device.name().unwrap_or_default(),
+            sample_rates,
+            default_sample_rate: default.sample_rate().0,
+            max_channels: ranges.iter().map(|r| r.channels()).max().unwrap_or(default.channels()),
+            default_channels: default.channels(),
+            sample_formats,
+            min_buffer_size: buffer_sizes.clone().map(|(min, _)| min).min(),
+            max_buffer_size: buffer_sizes.map(|(_, max)| max).max(),
+        })
+    }
+
+    /// Sources of system audio for dual-source recording
+    ///
+    /// Windows records output devices through WASAPI loopback; elsewhere
+    /// system audio comes from a monitor or virtual input (PulseAudio/PipeWire
+    /// monitor, BlackHole), so every input is offered.
+    pub fn list_loopback_devices() -> Result<Vec<AudioDevice>> {
+        if !cfg!(target_os = "windows") {
+            return Self::list_devices();
         }
 
-        let (response_tx, response_rx) = mpsc::channel();
-        self.command_tx
-            .send(AudioCommand::Start {
-                device_id: device_id.map(String::from),
-                response: response_tx,
+        let host = cpal::default_host();
+        let default_name = host
+            .default_output_device()
+            .and_then(|d| d.name().ok())
+            .unwrap_or_default();
+
+        Ok(host
+            .output_devices()
+            .map_err(|e| AppError::Audio(e.to_string()))?
+            .filter_map(|device| {
+                let name = device.name().ok()?;
+                Some(AudioDevice {
+                    id: name.clone(),
+                    name: name.clone(),
+                    is_default: name == default_name,
+                })
             })
-            .map_err(|_| AppError::Audio("Audio thread not responding".into()))?;
+            .collect())
+    }
 
-        response_rx
-            .recv()
-            .map_err(|_| AppError::Audio("Failed to get response from audio thread".into()))?
+    pub fn start(&self, device_id: Option<&str>) -> Result<()> {
+        self.start_dual(device_id, None)
+    }
+
+    /// Record the microphone and, with `loopback_id`, the system audio at the same time
+    ///
+    /// The loopback source is a monitor input (PulseAudio/PipeWire, BlackHole
+    /// on macOS) or, on Windows, an output device recorded through WASAPI
+    /// loopback. Its audio is kept apart: see `take_loopback`.
+    pub fn start_dual(&self, device_id: Option<&str>, loopback_id: Option<&str>) -> Result<()> {
+        if self.state.is_recording.load(Ordering::SeqCst) {
+            return Err(AppError::InvalidState("Already recording".into()));
+        }
+
+        *self.supervisor.device_id.lock() = device_id.map(String::from);
+        *self.supervisor.loopback_id.lock() = loopback_id.map(String::from);
+        self.markers.lock().clear();
+        *self.loopback.lock() = None;
+        let request = |response| AudioCommand::Start {
+            device_id: device_id.map(String::from),
+            loopback_id: loopback_id.map(String::from),
+            response,
+        };
+        let result = match self.supervisor.request(request) {
+            // Nothing was being recorded: retry once on the fresh thread
+            Err(AppError::Audio(msg)) if msg == RESET_ERROR => self.supervisor.request(request),
+            result => result,
+        };
+        if result.is_ok() {
+            self.duck_output();
+        }
+        result
     }
 
     pub fn stop(&self) -> Result<Vec<f32>> {
-        if !self.is_recording.load(Ordering::SeqCst) {
+        // Also after a reset that ended the recording
+        self.restore_output();
+        if !self.state.is_recording.load(Ordering::SeqCst) {
             return Err(AppError::InvalidState("Not recording".into()));
         }
 
-        let (response_tx, response_rx) = mpsc::channel();
-        self.command_tx
-            .send(AudioCommand::Stop {
-                response: response_tx,
-            })
-            .map_err(|_| AppError::Audio("Audio thread not responding".into()))?;
+        let (samples, loopback) = self
+            .supervisor
+            .request(|response| AudioCommand::Stop { response })?;
+        *self.loopback.lock() = loopback;
+        Ok(samples)
+    }
 
-        response_rx
-            .recv()
-            .map_err(|_| AppError::Audio("Failed to get response from audio thread".into()))?
+    /// Loopback audio of the last dual-source recording (empties it)
+    pub fn take_loopback(&self) -> Option<SourceAudio> {
+        self.loopback.lock().take()
+    }
+
+    /// Channel count of the microphone stream (samples are interleaved)
+    pub fn channels(&self) -> u16 {
+        self.state.channels.load(Ordering::SeqCst).max(1) as u16
+    }
+
+    /// Device of the last started recording (None: system default)
+    pub fn device_id(&self) -> Option<String> {
+        self.supervisor.device_id.lock().clone()
+    }
+
+    /// System audio source of the last started recording, if any
+    pub fn loopback_id(&self) -> Option<String> {
+        self.supervisor.loopback_id.lock().clone()
+    }
+
+    /// Samples recorded so far from index `from`, without stopping (live transcription)
+    pub fn snapshot(&self, from: usize) -> Result<Vec<f32>> {
+        if !self.state.is_recording.load(Ordering::SeqCst) {
+            return Err(AppError::InvalidState("Not recording".into()));
+        }
+
+        self.supervisor
+            .request(|response| AudioCommand::Snapshot { from, response })
     }
 
     pub fn pause(&self) -> Result<()> {
-        if !self.is_recording.load(Ordering::SeqCst) {
+        if !self.state.is_recording.load(Ordering::SeqCst) {
             return Err(AppError::InvalidState("Not recording".into()));
         }
-        self.command_tx
-            .send(AudioCommand::Pause)
-            .map_err(|_| AppError::Audio("Audio thread not responding".into()))?;
-        self.is_paused.store(true, Ordering::SeqCst);
+        self.supervisor.send(AudioCommand::Pause)?;
+        self.state.is_paused.store(true, Ordering::SeqCst);
+        self.restore_output();
         info!("Recording paused");
         Ok(())
     }
 
     pub fn resume(&self) -> Result<()> {
-        if !self.is_recording.load(Ordering::SeqCst) {
+        if !self.state.is_recording.load(Ordering::SeqCst) {
             return Err(AppError::InvalidState("Not recording".into()));
         }
-        self.command_tx
-            .send(AudioCommand::Resume)
-            .map_err(|_| AppError::Audio("Audio thread not responding".into()))?;
-        self.is_paused.store(false, Ordering::SeqCst);
+        self.supervisor.send(AudioCommand::Resume)?;
+        self.state.is_paused.store(false, Ordering::SeqCst);
+        self.duck_output();
         info!("Recording resumed");
         Ok(())
     }
 
+    /// Flag the current position of the recording (pauses excluded)
+    pub fn add_marker(&self, label: &str) -> Result<RecordingMarker> {
+        if !self.state.is_recording.load(Ordering::SeqCst) {
+            return Err(AppError::InvalidState("Not recording".into()));
+        }
+
+        let marker = RecordingMarker {
+            at_ms: self.status().elapsed_ms as i64,
+            label: label.trim().to_string(),
+        };
+        info!("Recording marker at {}ms: {}", marker.at_ms, marker.label);
+        self.markers.lock().push(marker.clone());
+        Ok(marker)
+    }
+
+    /// Markers of the last recording, in order (empties the list)
+    pub fn take_markers(&self) -> Vec<RecordingMarker> {
+        std::mem::take(&mut *self.markers.lock())
+    }
+
+    /// Pauses of the last recording after which it was resumed, in order (empties the list)
+    pub fn take_pauses(&self) -> Vec<RecordingPause> {
+        std::mem::take(&mut *self.state.pauses.lock())
+    }
+
     pub fn get_audio_level(&self) -> f32 {
-        *self.audio_level.lock()
+        self.state.audio_level.lock().level()
+    }
+
+    /// Microphone levels of the last minute of recording, for a scrolling waveform
+    pub fn waveform(&self) -> AudioWaveform {
+        let meter = self.state.audio_level.lock();
+        AudioWaveform {
+            level: meter.level(),
+            rate_hz: HISTORY_RATE_HZ,
+            levels: meter.history(),
+        }
     }
 
     pub fn sample_rate(&self) -> u32 {
-        *self.sample_rate.lock()
+        *self.state.sample_rate.lock()
     }
 
     pub fn is_recording(&self) -> bool {
-        self.is_recording.load(Ordering::SeqCst)
+        self.state.is_recording.load(Ordering::SeqCst)
+    }
+
+    /// Snapshot of the current recording, derived from the buffer itself
+    /// (no lock on the buffer, safe to poll from the UI timer)
+    pub fn status(&self) -> RecordingStatus {
+        let buffered_samples = self.state.buffered_samples.load(Ordering::SeqCst);
+        let spilled_samples = self.state.spilled_samples.load(Ordering::SeqCst).min(buffered_samples);
+        let sample_size = std::mem::size_of::<f32>() as u64;
+
+        RecordingStatus {
+            is_recording: self.is_recording(),
+            is_paused: self.state.is_paused.load(Ordering::SeqCst),
+            elapsed_ms: self.state.elapsed_ms(),
+            buffered_samples,
+            memory_bytes: (buffered_samples - spilled_samples) * sample_size,
+            disk_bytes: spilled_samples * sample_size,
+        }
     }
 }
 
 impl Drop for AudioCapture {
     fn drop(&mut self) {
-        let _ = self.command_tx.send(AudioCommand::Shutdown);
+        self.supervisor.shutdown.store(true, Ordering::SeqCst);
+        let _ = self.supervisor.worker.lock().command_tx.send(AudioCommand::Shutdown);
     }
 }
 
@@ -176,23 +724,51 @@ impl Default for AudioCapture {
 }
 
 /// Audio thread that owns the cpal Stream
-fn audio_thread(
-    command_rx: Receiver<AudioCommand>,
-    is_recording: Arc<AtomicBool>,
-    is_paused: Arc<AtomicBool>,
-    audio_level: Arc<Mutex<f32>>,
-    sample_rate: Arc<Mutex<u32>>,
-) {
+fn audio_thread(command_rx: Receiver<AudioCommand>, state: SharedState) {
+    let SharedState {
+        is_recording,
+        is_paused,
+        audio_level,
+        sample_rate,
+        buffered_samples,
+        spilled_samples,
+        channels,
+        agc,
+        input_config,
+        max_duration_ms,
+        limit_notice,
+        auto_stop_ms,
+        auto_stop_pending,
+        pauses,
+    } = state.clone();
+
     let mut current_stream: Option<cpal::Stream> = None;
     let buffer: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(Vec::new()));
+    // Start of a long recording, on disk (written and read on this thread only)
+    let mut spill: Option<SpillFile> = None;
+    let mut loopback_spill: Option<SpillFile> = None;
+    // Writing to disk failed: the rest of the recording stays in memory
+    let mut spill_failed = false;
+    // Silence at the end of the microphone audio (auto-stop), and the samples
+    // of the recording it has seen
+    let mut silence: Option<SilenceMonitor> = None;
+    let mut silence_checked = 0usize;
+    // Position in the recorded audio and time of the current pause
+    let mut paused_since: Option<(u64, Instant)> = None;
+
+    // Second source of a dual-source recording; its level and size are not reported
+    let mut loopback_stream: Option<cpal::Stream> = None;
+    let loopback_buffer: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(Vec::new()));
+    let loopback_rate = Arc::new(Mutex::new(0u32));
+    let loopback_channels = Arc::new(AtomicU32::new(1));
 
     // Generation counter to prevent stale callbacks from writing to buffer
     // Each new recording increments this counter
     let recording_generation: Arc<AtomicU64> = Arc::new(AtomicU64::new(0));
 
     loop {
-        match command_rx.recv() {
-            Ok(AudioCommand::Start { device_id, response }) => {
+        match command_rx.recv_timeout(TICK_INTERVAL) {
+            Ok(AudioCommand::Start { device_id, loopback_id, response }) => {
                 // 1. Increment generation FIRST to invalidate any in-flight callbacks
                 let new_generation = recording_generation.fetch_add(1, Ordering::SeqCst) + 1;
                 info!("Starting recording generation {}", new_generation);
@@ -206,22 +782,74 @@ fn audio_thread(
                     drop(stream);
                     std::thread::sleep(Duration::from_millis(50));
                 }
+                drop(loopback_stream.take());
 
                 // 3. Clear buffer BEFORE creating new stream (critical!)
                 buffer.lock().clear();
+                loopback_buffer.lock().clear();
+                *loopback_rate.lock() = 0;
+                buffered_samples.store(0, Ordering::SeqCst);
+                spill = None;
+                loopback_spill = None;
+                spill_failed = false;
+                spilled_samples.store(0, Ordering::SeqCst);
+                limit_notice.store(0, Ordering::SeqCst);
+                auto_stop_pending.store(0, Ordering::SeqCst);
+                silence = (auto_stop_ms.load(Ordering::SeqCst) > 0).then(SilenceMonitor::new);
+                silence_checked = 0;
+                pauses.lock().clear();
+                paused_since = None;
+                let max_ms = max_duration_ms.load(Ordering::SeqCst);
+                let input = *input_config.lock();
                 debug!("Buffer cleared for generation {}", new_generation);
 
-                // 4. Create and start stream with current generation
-                let result = start_stream(
-                    device_id.as_deref(),
-                    Arc::clone(&buffer),
-                    Arc::clone(&is_recording),
-                    Arc::clone(&is_paused),
-                    Arc::clone(&audio_level),
-                    Arc::clone(&sample_rate),
-                    Arc::clone(&recording_generation),
-                    new_generation,
-                );
+                // 4. Create and start streams with current generation
+                let result = open_input(device_id.as_deref(), &input).and_then(|(device, format)| {
+                    start_stream(
+                        &device,
+                        format,
+                        Arc::clone(&buffer),
+                        Arc::clone(&is_recording),
+                        Arc::clone(&is_paused),
+                        Arc::clone(&audio_level),
+                        Arc::clone(&sample_rate),
+                        Arc::clone(&buffered_samples),
+                        Arc::clone(&channels),
+                        agc.load(Ordering::SeqCst),
+                        max_ms,
+                        Arc::clone(&recording_generation),
+                        new_generation,
+                    )
+                });
+                let loopback = match (&result, loopback_id.as_deref()) {
+                    (Ok(_), Some(id)) => open_loopback(id).and_then(|(device, format)| {
+                        start_stream(
+                            &device,
+                            format,
+                            Arc::clone(&loopback_buffer),
+                            Arc::clone(&is_recording),
+                            Arc::clone(&is_paused),
+                            Arc::new(Mutex::new(LevelMeter::new())),
+                            Arc::clone(&loopback_rate),
+                            Arc::new(AtomicU64::new(0)),
+                            Arc::clone(&loopback_channels),
+                            false,
+                            max_ms,
+                            Arc::clone(&recording_generation),
+                            new_generation,
+                        )
+                    })
+                    .map(Some),
+                    _ => Ok(None),
+                };
+                // Both sources or none
+                let result = match (result, loopback) {
+                    (Ok(stream), Ok(loopback)) => {
+                        loopback_stream = loopback;
+                        Ok(stream)
+                    }
+                    (Err(e), _) | (_, Err(e)) => Err(e),
+                };
 
                 match result {
                     Ok(stream) => {
@@ -243,9 +871,11 @@ fn audio_thread(
 
                 // 1. Stop accepting new samples immediately
                 is_recording.store(false, Ordering::SeqCst);
+                // Nothing recorded after a pause still running
+                paused_since = None;
 
-                // 2. Properly stop stream: pause THEN drop
-                if let Some(stream) = current_stream.take() {
+                // 2. Properly stop streams: pause THEN drop
+                for stream in [current_stream.take(), loopback_stream.take()].into_iter().flatten() {
                     if let Err(e) = stream.pause() {
                         warn!("Failed to pause stream: {}", e);
                     }
@@ -255,25 +885,108 @@ fn audio_thread(
                 // 3. Small delay to let in-flight callbacks complete
                 std::thread::sleep(Duration::from_millis(50));
 
-                // 4. Take all samples from buffer
-                let samples = std::mem::take(&mut *buffer.lock());
-                info!("Recording generation {} stopped: {} samples ({:.2}s @ 16kHz)",
-                      gen,
-                      samples.len(),
-                      samples.len() as f32 / 16000.0);
-                let _ = response.send(Ok(samples));
+                // 4. Take all samples, from disk then from the buffer
+                let result = (|| -> Result<(Vec<f32>, Option<SourceAudio>)> {
+                    let samples = take_samples(spill.take(), &buffer)?;
+                    info!("Recording generation {} stopped: {} samples ({:.2}s @ 16kHz)",
+                          gen,
+                          samples.len(),
+                          samples.len() as f32 / 16000.0);
+                    let loopback = match std::mem::take(&mut *loopback_rate.lock()) {
+                        0 => None,
+                        rate => Some(SourceAudio {
+                            samples: take_samples(loopback_spill.take(), &loopback_buffer)?,
+                            sample_rate: rate,
+                            channels: loopback_channels.load(Ordering::SeqCst).max(1) as u16,
+                        }),
+                    };
+                    Ok((samples, loopback))
+                })();
+                buffered_samples.store(0, Ordering::SeqCst);
+                spilled_samples.store(0, Ordering::SeqCst);
+                let _ = response.send(result);
+            }
+            Ok(AudioCommand::Snapshot { from, response }) => {
+                let on_disk = spill.as_ref().map_or(0, SpillFile::len);
+                let result = match spill.as_mut() {
+                    Some(file) if from < on_disk => file.read_from(from).map(|mut samples| {
+                        samples.extend_from_slice(&buffer.lock());
+                        samples
+                    }),
+                    _ => Ok(buffer.lock().get(from - on_disk..).map(<[f32]>::to_vec).unwrap_or_default()),
+                };
+                let _ = response.send(result);
             }
             Ok(AudioCommand::Pause) => {
-                if let Some(ref stream) = current_stream {
+                for stream in current_stream.iter().chain(loopback_stream.iter()) {
                     let _ = stream.pause();
                 }
+                if current_stream.is_some() && paused_since.is_none() {
+                    paused_since = Some((state.elapsed_ms(), Instant::now()));
+                }
             }
             Ok(AudioCommand::Resume) => {
-                if let Some(ref stream) = current_stream {
+                for stream in current_stream.iter().chain(loopback_stream.iter()) {
                     let _ = stream.play();
                 }
+                if let Some((at_ms, since)) = paused_since.take() {
+                    let pause = RecordingPause {
+                        at_ms: at_ms as i64,
+                        duration_ms: since.elapsed().as_millis() as i64,
+                    };
+                    debug!("Recording resumed at {}ms after {}ms", pause.at_ms, pause.duration_ms);
+                    pauses.lock().push(pause);
+                }
             }
-            Ok(AudioCommand::Shutdown) | Err(_) => {
+            Err(RecvTimeoutError::Timeout) => {
+                if !is_recording.load(Ordering::SeqCst) {
+                    continue;
+                }
+                let rate = *sample_rate.lock();
+
+                // Before the buffer is moved to disk, so no block is missed
+                if let Some(monitor) = silence.as_mut() {
+                    let on_disk = spill.as_ref().map_or(0, SpillFile::len);
+                    let block = buffer
+                        .lock()
+                        .get(silence_checked.saturating_sub(on_disk)..)
+                        .map(<[f32]>::to_vec)
+                        .unwrap_or_default();
+                    silence_checked += block.len();
+                    let mono = downmix(&block, channels.load(Ordering::SeqCst) as u16);
+                    match resample_to_16k(&mono, rate) {
+                        Ok(block) if !block.is_empty() => {
+                            let silence_ms = monitor.feed(&block);
+                            if silence_ms >= auto_stop_ms.load(Ordering::SeqCst) {
+                                auto_stop_pending.store(silence_ms, Ordering::SeqCst);
+                                silence = None;
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(e) => warn!("Silence check failed: {}", e),
+                    }
+                }
+
+                if spill_failed {
+                    continue;
+                }
+                let loopback = *loopback_rate.lock();
+                let spilled = spill_buffer(&buffer, &mut spill, rate, channels.load(Ordering::SeqCst) as u16)
+                    .and_then(|()| {
+                        spill_buffer(
+                            &loopback_buffer,
+                            &mut loopback_spill,
+                            loopback,
+                            loopback_channels.load(Ordering::SeqCst) as u16,
+                        )
+                    });
+                if let Err(e) = spilled {
+                    warn!("Failed to move the recording buffer to disk, keeping it in memory: {}", e);
+                    spill_failed = true;
+                }
+                spilled_samples.store(spill.as_ref().map_or(0, |f| f.len() as u64), Ordering::SeqCst);
+            }
+            Ok(AudioCommand::Shutdown) | Err(RecvTimeoutError::Disconnected) => {
                 info!("Audio thread shutting down");
                 break;
             }
@@ -281,16 +994,67 @@ fn audio_thread(
     }
 }
 
-fn start_stream(
-    device_id: Option<&str>,
-    buffer: Arc<Mutex<Vec<f32>>>,
-    is_recording: Arc<AtomicBool>,
-    is_paused: Arc<AtomicBool>,
-    audio_level: Arc<Mutex<f32>>,
-    sample_rate: Arc<Mutex<u32>>,
-    recording_generation: Arc<AtomicU64>,
-    expected_generation: u64,
-) -> Result<cpal::Stream> {
+/// Move the samples of `buffer` to its spill file once there are enough of them
+fn spill_buffer(
+    buffer: &Mutex<Vec<f32>>,
+    spill: &mut Option<SpillFile>,
+    sample_rate: u32,
+    channels: u16,
+) -> Result<()> {
+    if buffer.lock().len() < SPILL_THRESHOLD {
+        return Ok(());
+    }
+    let file = match spill {
+        Some(file) => file,
+        None => spill.insert(SpillFile::create(sample_rate, channels)?),
+    };
+
+    // Taken whole so the stream callback never waits on the disk
+    let chunk = std::mem::take(&mut *buffer.lock());
+    if let Err(e) = file.append(&chunk) {
+        // Back in front of what was captured meanwhile
+        let mut buffer = buffer.lock();
+        let captured = std::mem::replace(&mut *buffer, chunk);
+        buffer.extend(captured);
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Samples on disk followed by those left in `buffer` (empties both)
+fn take_samples(spill: Option<SpillFile>, buffer: &Mutex<Vec<f32>>) -> Result<Vec<f32>> {
+    let memory = std::mem::take(&mut *buffer.lock());
+    let Some(mut file) = spill else {
+        return Ok(memory);
+    };
+    let mut samples = file.read_from(0)?;
+    samples.extend(memory);
+    Ok(samples)
+}
+
+/// Rates offered by `device_capabilities`, when the device accepts them
+const COMMON_SAMPLE_RATES: [u32; 7] = [8000, 16000, 22050, 32000, 44100, 48000, 96000];
+
+/// Stream to open on a device
+struct StreamFormat {
+    supported: SupportedStreamConfig,
+    config: StreamConfig,
+    /// Only this channel of the interleaved stream is kept
+    channel: Option<u16>,
+}
+
+impl From<SupportedStreamConfig> for StreamFormat {
+    fn from(supported: SupportedStreamConfig) -> Self {
+        Self {
+            config: supported.config(),
+            supported,
+            channel: None,
+        }
+    }
+}
+
+/// Input device by name, or the system default
+fn find_input(device_id: Option<&str>) -> Result<cpal::Device> {
     let host = cpal::default_host();
 
     let device = if let Some(id) = device_id {
@@ -302,41 +1066,165 @@ fn start_stream(
         host.default_input_device()
             .ok_or_else(|| AppError::Audio("No default input device".into()))?
     };
+    Ok(device)
+}
 
+/// Input device with the stream of `input` (its default config for what is unset)
+fn open_input(device_id: Option<&str>, input: &InputConfig) -> Result<(cpal::Device, StreamFormat)> {
+    let device = find_input(device_id)?;
     info!("Using audio device: {:?}", device.name());
 
-    let config = device
+    let default = device
         .default_input_config()
         .map_err(|e| AppError::Audio(e.to_string()))?;
+    let min_channels = input.channel.map_or(1, |c| c + 1);
+    let supported = if input.sample_rate.is_none() && default.channels() >= min_channels {
+        default
+    } else {
+        let rate = input.sample_rate.unwrap_or(default.sample_rate().0);
+        let ranges: Vec<SupportedStreamConfigRange> = device
+            .supported_input_configs()
+            .map_err(|e| AppError::Audio(e.to_string()))?
+            .collect();
+        choose_config(&ranges, rate, min_channels, &default).ok_or_else(|| {
+            AppError::Audio(format!(
+                "Device does not record {}Hz with {} channels",
+                rate, min_channels
+            ))
+        })?
+    };
+
+    let mut config = supported.config();
+    if let Some(frames) = input.buffer_size {
+        config.buffer_size = BufferSize::Fixed(match supported.buffer_size() {
+            SupportedBufferSize::Range { min, max } => frames.clamp(*min, *max),
+            SupportedBufferSize::Unknown => frames,
+        });
+    }
+    Ok((
+        device,
+        StreamFormat {
+            supported,
+            config,
+            channel: input.channel,
+        },
+    ))
+}
+
+/// Config at `rate` with at least `min_channels`, closest to the default
+/// (same sample format, same channels, then fewest channels)
+fn choose_config(
+    ranges: &[SupportedStreamConfigRange],
+    rate: u32,
+    min_channels: u16,
+    default: &SupportedStreamConfig,
+) -> Option<SupportedStreamConfig> {
+    ranges
+        .iter()
+        .filter(|r| (r.min_sample_rate().0..=r.max_sample_rate().0).contains(&rate))
+        .filter(|r| r.channels() >= min_channels)
+        .filter(|r| matches!(r.sample_format(), SampleFormat::F32 | SampleFormat::I16))
+        .min_by_key(|r| {
+            (
+                r.sample_format() != default.sample_format(),
+                r.channels() != default.channels(),
+                r.channels(),
+            )
+        })
+        .map(|r| r.clone().with_sample_rate(SampleRate(rate)))
+}
+
+/// System audio source: a monitor input, else an output device (WASAPI loopback)
+fn open_loopback(id: &str) -> Result<(cpal::Device, StreamFormat)> {
+    let host = cpal::default_host();
+    let named = |d: &cpal::Device| d.name().map(|n| n == id).unwrap_or(false);
+
+    if let Some(device) = host
+        .input_devices()
+        .map_err(|e| AppError::Audio(e.to_string()))?
+        .find(named)
+    {
+        info!("Using loopback input: {}", id);
+        let config = device
+            .default_input_config()
+            .map_err(|e| AppError::Audio(e.to_string()))?;
+        return Ok((device, config.into()));
+    }
+
+    let device = host
+        .output_devices()
+        .map_err(|e| AppError::Audio(e.to_string()))?
+        .find(named)
+        .ok_or_else(|| AppError::NotFound(format!("Loopback device not found: {}", id)))?;
+    info!("Using loopback of output: {}", id);
+    let config = device
+        .default_output_config()
+        .map_err(|e| AppError::Audio(e.to_string()))?;
+    Ok((device, config.into()))
+}
 
-    *sample_rate.lock() = config.sample_rate().0;
-    info!("Audio config: {}Hz, {} channels, {:?}",
-          config.sample_rate().0,
-          config.channels(),
-          config.sample_format());
+fn start_stream(
+    device: &cpal::Device,
+    format: StreamFormat,
+    buffer: Arc<Mutex<Vec<f32>>>,
+    is_recording: Arc<AtomicBool>,
+    is_paused: Arc<AtomicBool>,
+    audio_level: Arc<Mutex<LevelMeter>>,
+    sample_rate: Arc<Mutex<u32>>,
+    buffered_samples: Arc<AtomicU64>,
+    channels: Arc<AtomicU32>,
+    agc: bool,
+    max_duration_ms: u64,
+    recording_generation: Arc<AtomicU64>,
+    expected_generation: u64,
+) -> Result<cpal::Stream> {
+    let StreamFormat { supported, config, channel } = format;
+    let rate = config.sample_rate.0;
+    // One channel taken out of the interleaved stream, or all of them
+    let pick = channel.map(|c| (c as usize, config.channels as usize));
+    let recorded_channels = if pick.is_some() { 1 } else { config.channels };
+    *sample_rate.lock() = rate;
+    channels.store(recorded_channels as u32, Ordering::SeqCst);
+    audio_level.lock().reset(rate, recorded_channels);
+    // Rounded up, so the recorded duration reaches the limit (0: no limit)
+    let max_samples = (max_duration_ms * rate as u64 * recorded_channels as u64).div_ceil(1000);
+    let agc = agc.then(|| Agc::new(rate, recorded_channels));
+    info!("Audio config: {}Hz, {} channels (recording {}), {:?}, buffer {:?}",
+          rate,
+          config.channels,
+          channel.map_or("all".to_string(), |c| format!("channel {}", c)),
+          supported.sample_format(),
+          config.buffer_size);
 
     let err_fn = |err| warn!("Audio stream error: {}", err);
-    let config_clone: StreamConfig = config.clone().into();
 
-    let stream = match config.sample_format() {
+    let stream = match supported.sample_format() {
         SampleFormat::F32 => build_stream_f32(
-            &device,
-            &config_clone,
+            device,
+            &config,
             buffer,
             is_recording,
             is_paused,
             audio_level,
+            buffered_samples,
+            agc,
+            pick,
+            max_samples,
             recording_generation,
             expected_generation,
             err_fn,
         )?,
         SampleFormat::I16 => build_stream_i16(
-            &device,
-            &config_clone,
+            device,
+            &config,
             buffer,
             is_recording,
             is_paused,
             audio_level,
+            buffered_samples,
+            agc,
+            pick,
+            max_samples,
             recording_generation,
             expected_generation,
             err_fn,
@@ -358,7 +1246,11 @@ fn build_stream_f32<E>(
     buffer: Arc<Mutex<Vec<f32>>>,
     is_recording: Arc<AtomicBool>,
     is_paused: Arc<AtomicBool>,
-    audio_level: Arc<Mutex<f32>>,
+    audio_level: Arc<Mutex<LevelMeter>>,
+    buffered_samples: Arc<AtomicU64>,
+    mut agc: Option<Agc>,
+    pick: Option<(usize, usize)>,
+    max_samples: u64,
     recording_generation: Arc<AtomicU64>,
     expected_generation: u64,
     err_fn: E,
@@ -380,14 +1272,21 @@ where
                     return;
                 }
 
-                // Calculate audio level (RMS) with gain boost for visualization
-                let sum: f32 = data.iter().map(|s| s * s).sum();
-                let rms = (sum / data.len() as f32).sqrt();
-                // Apply gain (10x) and use sqrt for more visual range
-                let boosted = (rms * 10.0).sqrt().min(1.0);
-                *audio_level.lock() = boosted;
+                // Length limit reached: the rest is not recorded
+                if max_samples > 0 && buffered_samples.load(Ordering::SeqCst) >= max_samples {
+                    audio_level.lock().silence();
+                    return;
+                }
+
+                let mut samples = select_channel(data.to_vec(), pick);
+                if let Some(agc) = agc.as_mut() {
+                    agc.process(&mut samples);
+                }
+
+                audio_level.lock().push(&samples);
 
-                buffer.lock().extend_from_slice(data);
+                buffered_samples.fetch_add(samples.len() as u64, Ordering::SeqCst);
+                buffer.lock().extend(samples);
             },
             err_fn,
             None,
@@ -401,7 +1300,11 @@ fn build_stream_i16<E>(
     buffer: Arc<Mutex<Vec<f32>>>,
     is_recording: Arc<AtomicBool>,
     is_paused: Arc<AtomicBool>,
-    audio_level: Arc<Mutex<f32>>,
+    audio_level: Arc<Mutex<LevelMeter>>,
+    buffered_samples: Arc<AtomicU64>,
+    mut agc: Option<Agc>,
+    pick: Option<(usize, usize)>,
+    max_samples: u64,
     recording_generation: Arc<AtomicU64>,
     expected_generation: u64,
     err_fn: E,
@@ -423,15 +1326,20 @@ where
                     return;
                 }
 
-                let samples: Vec<f32> = data.iter().map(|&s| s as f32 / 32768.0).collect();
+                // Length limit reached: the rest is not recorded
+                if max_samples > 0 && buffered_samples.load(Ordering::SeqCst) >= max_samples {
+                    audio_level.lock().silence();
+                    return;
+                }
 
-                // Calculate audio level (RMS) with gain boost for visualization
-                let sum: f32 = samples.iter().map(|s| s * s).sum();
-                let rms = (sum / samples.len() as f32).sqrt();
-                // Apply gain (10x) and use sqrt for more visual range
-                let boosted = (rms * 10.0).sqrt().min(1.0);
-                *audio_level.lock() = boosted;
+                let mut samples = select_channel(data.iter().map(|&s| s as f32 / 32768.0).collect(), pick);
+                if let Some(agc) = agc.as_mut() {
+                    agc.process(&mut samples);
+                }
 
+                audio_level.lock().push(&samples);
+
+                buffered_samples.fetch_add(samples.len() as u64, Ordering::SeqCst);
                 buffer.lock().extend(samples);
             },
             err_fn,
@@ -439,3 +1347,25 @@ where
         )
         .map_err(|e| AppError::Audio(e.to_string()))
 }
+
+/// Channel `index` of interleaved samples of `channels` (all of them without a pick)
+fn select_channel(samples: Vec<f32>, pick: Option<(usize, usize)>) -> Vec<f32> {
+    match pick {
+        Some((index, channels)) if channels > 1 => samples.into_iter().skip(index).step_by(channels).collect(),
+        _ => samples,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_channel() {
+        let stereo = vec![0.1, -0.1, 0.2, -0.2, 0.3, -0.3];
+        assert_eq!(select_channel(stereo.clone(), Some((0, 2))), [0.1, 0.2, 0.3]);
+        assert_eq!(select_channel(stereo.clone(), Some((1, 2))), [-0.1, -0.2, -0.3]);
+        assert_eq!(select_channel(stereo.clone(), None), stereo);
+        assert_eq!(select_channel(vec![0.5, 0.6], Some((0, 1))), [0.5, 0.6]);
+    }
+}
@@ -0,0 +1,181 @@
+//! Speech / music / noise classification for audio chunks
+//!
+//! Cheap energy + spectral features, meant to catch *clear* non-speech
+//! (podcast jingles, background music, fan noise) before it reaches the model,
+//! which otherwise tends to hallucinate words or lyrics on it:
+//! - Noise is spectrally flat (high geometric/arithmetic power ratio)
+//! - Music is tonal and continuous: few low-energy frames, unlike speech which
+//!   pauses between syllables and words several times per second
+
+use rustfft::{num_complex::Complex, FftPlanner};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::vad::{analyze_audio, VadConfig};
+
+/// FFT size for spectral flatness (32ms at 16kHz)
+const FFT_SIZE: usize = 512;
+
+/// Spectral flatness above which a frame sounds like noise
+const NOISE_FLATNESS: f32 = 0.45;
+
+/// Below this fraction of low-energy frames, sound is too continuous for speech
+const MUSIC_MAX_LOW_ENERGY_RATIO: f32 = 0.08;
+
+/// Minimum fraction of non-silent frames for a chunk to be music/noise
+const MIN_ACTIVE_RATIO: f32 = 0.9;
+
+/// What a chunk of audio (mostly) contains
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioContent {
+    Speech,
+    Silence,
+    Music,
+    Noise,
+}
+
+impl AudioContent {
+    /// Marker inserted in the transcript instead of transcribing the chunk
+    pub fn marker(&self) -> Option<&'static str> {
+        match self {
+            AudioContent::Music => Some("[music]"),
+            AudioContent::Noise => Some("[noise]"),
+            AudioContent::Speech | AudioContent::Silence => None,
+        }
+    }
+}
+
+/// Classify audio samples (16kHz mono)
+///
+/// Anything ambiguous is reported as speech, so it still gets transcribed.
+pub fn classify_audio(samples: &[f32]) -> AudioContent {
+    // Energy only: music and noise are not speech, but not silence either
+    let vad_config = VadConfig::energy_only();
+    let frames = analyze_audio(samples, &vad_config);
+    if frames.is_empty() {
+        return AudioContent::Speech;
+    }
+
+    let active: Vec<f32> = frames.iter().filter(|f| !f.is_silence).map(|f| f.rms).collect();
+    let active_ratio = active.len() as f32 / frames.len() as f32;
+    if active.is_empty() {
+        return AudioContent::Silence;
+    }
+    if active_ratio < MIN_ACTIVE_RATIO {
+        // Pauses: speech (or at least not continuous music/noise)
+        return AudioContent::Speech;
+    }
+
+    // Low-energy frame ratio: speech drops well below its mean energy between syllables
+    let mean_rms = frames.iter().map(|f| f.rms).sum::<f32>() / frames.len() as f32;
+    let low_energy_ratio =
+        frames.iter().filter(|f| f.rms < 0.5 * mean_rms).count() as f32 / frames.len() as f32;
+
+    let flatness = mean_spectral_flatness(samples);
+
+    debug!(
+        "Audio content: active={:.2}, low_energy={:.2}, flatness={:.3}",
+        active_ratio, low_energy_ratio, flatness
+    );
+
+    if flatness >= NOISE_FLATNESS {
+        AudioContent::Noise
+    } else if low_energy_ratio < MUSIC_MAX_LOW_ENERGY_RATIO {
+        AudioContent::Music
+    } else {
+        AudioContent::Speech
+    }
+}
+
+/// Average spectral flatness (geometric / arithmetic mean of the power spectrum)
+fn mean_spectral_flatness(samples: &[f32]) -> f32 {
+    if samples.len() < FFT_SIZE {
+        return 0.0;
+    }
+
+    let mut planner = FftPlanner::<f32>::new();
+    let fft = planner.plan_fft_forward(FFT_SIZE);
+    let mut buffer = vec![Complex::new(0.0f32, 0.0); FFT_SIZE];
+
+    let mut total = 0.0f32;
+    let mut count = 0usize;
+
+    for frame in samples.chunks_exact(FFT_SIZE) {
+        // Remove DC offset (it would leak into the low bins through the window)
+        let dc = frame.iter().sum::<f32>() / FFT_SIZE as f32;
+        for (i, (b, &s)) in buffer.iter_mut().zip(frame).enumerate() {
+            // Hann window
+            let w = 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / FFT_SIZE as f32).cos();
+            *b = Complex::new((s - dc) * w, 0.0);
+        }
+        fft.process(&mut buffer);
+
+        // Skip DC, keep positive frequencies
+        let power: Vec<f32> = buffer[1..FFT_SIZE / 2].iter().map(|c| c.norm_sqr() + 1e-10).collect();
+        let log_mean = power.iter().map(|p| p.ln()).sum::<f32>() / power.len() as f32;
+        let mean = power.iter().sum::<f32>() / power.len() as f32;
+
+        total += log_mean.exp() / mean;
+        count += 1;
+    }
+
+    if count == 0 {
+        0.0
+    } else {
+        total / count as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 16000.0;
+
+    fn tone(freqs: &[f32], seconds: f32) -> Vec<f32> {
+        (0..(seconds * SR) as usize)
+            .map(|i| {
+                let t = i as f32 / SR;
+                freqs.iter().map(|f| (2.0 * std::f32::consts::PI * f * t).sin()).sum::<f32>() * 0.1
+            })
+            .collect()
+    }
+
+    fn white_noise(seconds: f32) -> Vec<f32> {
+        let mut state = 12345u64;
+        (0..(seconds * SR) as usize)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                ((state >> 33) as f32 / (1u64 << 30) as f32 - 1.0) * 0.2
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_silence() {
+        assert_eq!(classify_audio(&vec![0.0; 32000]), AudioContent::Silence);
+    }
+
+    #[test]
+    fn test_steady_chord_is_music() {
+        // A major chord held for 3s
+        assert_eq!(classify_audio(&tone(&[220.0, 277.2, 329.6], 3.0)), AudioContent::Music);
+    }
+
+    #[test]
+    fn test_white_noise_is_noise() {
+        assert_eq!(classify_audio(&white_noise(3.0)), AudioContent::Noise);
+    }
+
+    #[test]
+    fn test_syllabic_bursts_are_speech() {
+        // Harmonic bursts of 150ms with 100ms gaps (~4 syllables/s)
+        let mut samples = Vec::new();
+        for _ in 0..12 {
+            samples.extend(tone(&[150.0, 300.0, 450.0], 0.15));
+            samples.extend(vec![0.0; (0.1 * SR) as usize]);
+        }
+        assert_eq!(classify_audio(&samples), AudioContent::Speech);
+    }
+}
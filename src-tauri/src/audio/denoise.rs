@@ -0,0 +1,195 @@
+//! Noise reduction applied before level normalization
+//!
+//! Laptop microphones pick up fan and hum below the voice, and a steady hiss
+//! that normalization then amplifies during pauses. The high-pass filter
+//! removes the rumble; spectral gating also attenuates, frequency by
+//! frequency, whatever does not rise above the noise floor measured on the
+//! quietest frames of the recording.
+
+use rustfft::{num_complex::Complex, FftPlanner};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+const SAMPLE_RATE: f32 = 16000.0;
+
+/// Cutoff of the high-pass filter: below the fundamental of most voices
+const HIGH_PASS_HZ: f32 = 100.0;
+
+/// STFT frame (32ms at 16kHz), analysed every half frame
+const FRAME: usize = 512;
+const HOP: usize = FRAME / 2;
+
+/// Fraction of the frames, the quietest, averaged into the noise profile
+const NOISE_FRAMES_RATIO: f32 = 0.1;
+
+/// A bin passes when above the noise floor times this
+const GATE_OVER_NOISE: f32 = 1.5;
+
+/// Attenuation of gated bins (-20 dB): silencing them entirely sounds "musical"
+const GATE_FLOOR: f32 = 0.1;
+
+/// Noise reduction before transcription
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Denoise {
+    /// Keep the audio as recorded
+    #[default]
+    Off,
+    /// Remove the rumble below the voice (fans, hum, handling noise)
+    HighPass,
+    /// High-pass, then attenuate what stays under the noise floor
+    SpectralGate,
+}
+
+/// Denoise 16kHz mono samples
+pub fn denoise(samples: &[f32], mode: Denoise) -> Vec<f32> {
+    match mode {
+        Denoise::Off => samples.to_vec(),
+        Denoise::HighPass => high_pass(samples, HIGH_PASS_HZ),
+        Denoise::SpectralGate => spectral_gate(&high_pass(samples, HIGH_PASS_HZ)),
+    }
+}
+
+/// Second-order Butterworth high-pass (RBJ biquad)
+fn high_pass(samples: &[f32], cutoff_hz: f32) -> Vec<f32> {
+    let w0 = 2.0 * std::f32::consts::PI * cutoff_hz / SAMPLE_RATE;
+    let alpha = w0.sin() / std::f32::consts::SQRT_2; // Q = 1/sqrt(2)
+    let cos = w0.cos();
+    let a0 = 1.0 + alpha;
+    let (b0, b1, b2) = ((1.0 + cos) / 2.0 / a0, -(1.0 + cos) / a0, (1.0 + cos) / 2.0 / a0);
+    let (a1, a2) = (-2.0 * cos / a0, (1.0 - alpha) / a0);
+
+    let (mut x1, mut x2, mut y1, mut y2) = (0.0f32, 0.0f32, 0.0f32, 0.0f32);
+    samples
+        .iter()
+        .map(|&x| {
+            let y = b0 * x + b1 * x1 + b2 * x2 - a1 * y1 - a2 * y2;
+            (x2, x1, y2, y1) = (x1, x, y1, y);
+            y
+        })
+        .collect()
+}
+
+/// Spectral gating over a Hann-windowed STFT, resynthesized by overlap-add
+///
+/// The noise floor of each bin is its average magnitude over the quietest
+/// frames, so the recording needs a few pauses; too short, it is returned as is.
+fn spectral_gate(samples: &[f32]) -> Vec<f32> {
+    if samples.len() < FRAME * 8 {
+        return samples.to_vec();
+    }
+
+    // Half a frame of padding on each side: every sample is covered by two frames
+    let frame_count = samples.len().div_ceil(HOP) + 1;
+    let mut padded = vec![0.0f32; HOP];
+    padded.extend_from_slice(samples);
+    padded.resize((frame_count + 1) * HOP, 0.0);
+
+    // Periodic Hann: windows half a frame apart sum to 1
+    let window: Vec<f32> = (0..FRAME)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / FRAME as f32).cos())
+        .collect();
+
+    let mut planner = FftPlanner::<f32>::new();
+    let forward = planner.plan_fft_forward(FRAME);
+    let inverse = planner.plan_fft_inverse(FRAME);
+
+    let spectra: Vec<Vec<Complex<f32>>> = (0..frame_count)
+        .map(|f| {
+            let frame = &padded[f * HOP..f * HOP + FRAME];
+            let mut buffer: Vec<Complex<f32>> =
+                frame.iter().zip(&window).map(|(&s, &w)| Complex::new(s * w, 0.0)).collect();
+            forward.process(&mut buffer);
+            buffer
+        })
+        .collect();
+
+    let noise = noise_profile(&spectra);
+
+    let mut output = vec![0.0f32; padded.len()];
+    let mut previous_gain = vec![1.0f32; FRAME / 2 + 1];
+    for (f, spectrum) in spectra.into_iter().enumerate() {
+        let mut buffer = spectrum;
+        for bin in 0..=FRAME / 2 {
+            let magnitude = buffer[bin].norm();
+            let gain = ((magnitude - GATE_OVER_NOISE * noise[bin]) / (magnitude + 1e-10)).clamp(GATE_FLOOR, 1.0);
+            // Smoothed over time against isolated bins flickering on and off
+            let gain = 0.5 * (gain + previous_gain[bin]);
+            previous_gain[bin] = gain;
+            buffer[bin] *= gain;
+            if bin > 0 && bin < FRAME / 2 {
+                buffer[FRAME - bin] *= gain;
+            }
+        }
+        inverse.process(&mut buffer);
+        for (out, c) in output[f * HOP..f * HOP + FRAME].iter_mut().zip(&buffer) {
+            *out += c.re / FRAME as f32;
+        }
+    }
+
+    output.drain(..HOP);
+    output.truncate(samples.len());
+    info!("Spectral gating applied ({} frames)", frame_count);
+    output
+}
+
+/// Average magnitude per bin over the quietest frames
+fn noise_profile(spectra: &[Vec<Complex<f32>>]) -> Vec<f32> {
+    let bins = FRAME / 2 + 1;
+    let mut by_energy: Vec<(f32, usize)> = spectra
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s[..bins].iter().map(|c| c.norm_sqr()).sum::<f32>(), i))
+        .collect();
+    by_energy.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let quiet = ((spectra.len() as f32 * NOISE_FRAMES_RATIO) as usize).max(1);
+    let mut profile = vec![0.0f32; bins];
+    for &(_, i) in &by_energy[..quiet] {
+        for (p, c) in profile.iter_mut().zip(&spectra[i][..bins]) {
+            *p += c.norm() / quiet as f32;
+        }
+    }
+    profile
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::processor::calculate_rms;
+
+    fn tone(freq: f32, seconds: f32, amplitude: f32) -> Vec<f32> {
+        (0..(seconds * SAMPLE_RATE) as usize)
+            .map(|i| (2.0 * std::f32::consts::PI * freq * i as f32 / SAMPLE_RATE).sin() * amplitude)
+            .collect()
+    }
+
+    #[test]
+    fn test_high_pass_removes_hum_keeps_voice_band() {
+        let hum = high_pass(&tone(30.0, 1.0, 0.5), HIGH_PASS_HZ);
+        let voice = high_pass(&tone(1000.0, 1.0, 0.5), HIGH_PASS_HZ);
+        // Past the filter's settling time
+        assert!(calculate_rms(&hum[4000..]) < 0.15 * calculate_rms(&tone(30.0, 0.75, 0.5)));
+        assert!((calculate_rms(&voice[4000..]) / calculate_rms(&tone(1000.0, 0.75, 0.5)) - 1.0).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_spectral_gate_attenuates_noise_in_pauses() {
+        let mut state = 12345u64;
+        let mut noise = || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 33) as f32 / (1u64 << 30) as f32 - 1.0) * 0.01
+        };
+        // 1s of noise alone, then 1s of a tone over the same noise
+        let mut samples: Vec<f32> = (0..16000).map(|_| noise()).collect();
+        samples.extend(tone(440.0, 1.0, 0.2).into_iter().map(|s| s + noise()));
+
+        let gated = denoise(&samples, Denoise::SpectralGate);
+        assert_eq!(gated.len(), samples.len());
+        assert!(calculate_rms(&gated[2000..14000]) < 0.3 * calculate_rms(&samples[2000..14000]));
+        let kept = calculate_rms(&gated[18000..30000]) / calculate_rms(&samples[18000..30000]);
+        assert!(kept > 0.9, "tone kept at {}", kept);
+
+        assert_eq!(denoise(&samples, Denoise::Off), samples);
+    }
+}
@@ -0,0 +1,177 @@
+//! Dual-source recordings: microphone ("me") and system audio ("them")
+//!
+//! Both sources are brought to mono 16 kHz, then either mixed into one
+//! signal, or transcribed apart and merged with each segment labelled by
+//! its source.
+
+use crate::audio::capture::SourceAudio;
+use crate::audio::resample_to_16k;
+use crate::error::Result;
+use crate::storage::{Segment, Transcription};
+use serde::{Deserialize, Serialize};
+
+pub const SPEAKER_ME: &str = "me";
+pub const SPEAKER_THEM: &str = "them";
+
+/// What to do with the two sources of a recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DualSourceMode {
+    /// One signal, one transcription
+    #[default]
+    Mix,
+    /// One transcription per source, segments labelled me/them
+    Separate,
+}
+
+/// Average of the interleaved channels
+pub fn downmix(samples: &[f32], channels: u16) -> Vec<f32> {
+    if channels <= 1 {
+        return samples.to_vec();
+    }
+    samples
+        .chunks(channels as usize)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect()
+}
+
+/// Mono 16 kHz audio of a source
+pub fn to_mono_16k(audio: &SourceAudio) -> Result<Vec<f32>> {
+    let mono = downmix(&audio.samples, audio.channels);
+    let mut resampled = resample_to_16k(&mono, audio.sample_rate)?;
+    // The resampler pads its last block with zeros
+    resampled.truncate((mono.len() as u64 * 16000 / audio.sample_rate.max(1) as u64) as usize);
+    Ok(resampled)
+}
+
+/// Sum of both sources, the shorter one padded with silence
+pub fn mix(a: &[f32], b: &[f32]) -> Vec<f32> {
+    (0..a.len().max(b.len()))
+        .map(|i| {
+            let sum = a.get(i).unwrap_or(&0.0) + b.get(i).unwrap_or(&0.0);
+            sum.clamp(-1.0, 1.0)
+        })
+        .collect()
+}
+
+/// One transcription from those of each source, segments in time order
+///
+/// The text gives one line per speaker turn ("Moi :" / "Eux :").
+pub fn merge_sources(me: Transcription, them: Transcription) -> Transcription {
+    let label = |segments: Vec<Segment>, speaker: &'static str| {
+        segments.into_iter().map(move |s| Segment {
+            speaker: Some(speaker.to_string()),
+            ..s
+        })
+    };
+    let mut segments: Vec<Segment> = label(me.segments, SPEAKER_ME)
+        .chain(label(them.segments, SPEAKER_THEM))
+        .filter(|s| !s.text.trim().is_empty())
+        .collect();
+    segments.sort_by_key(|s| s.start_ms);
+
+    let mut turns: Vec<(&str, Vec<&str>)> = Vec::new();
+    for segment in &segments {
+        let speaker = segment.speaker.as_deref().unwrap_or(SPEAKER_ME);
+        match turns.last_mut() {
+            Some((last, texts)) if *last == speaker => texts.push(segment.text.trim()),
+            _ => turns.push((speaker, vec![segment.text.trim()])),
+        }
+    }
+    let raw_text = turns
+        .iter()
+        .map(|(speaker, texts)| {
+            let name = if *speaker == SPEAKER_ME { "Moi" } else { "Eux" };
+            format!("{} : {}", name, texts.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut warnings = me.warnings;
+    warnings.extend(them.warnings);
+    let confidence = match (me.confidence, them.confidence) {
+        (Some(a), Some(b)) => Some((a + b) / 2.0),
+        (a, b) => a.or(b),
+    };
+    Transcription {
+        duration_ms: me.duration_ms.max(them.duration_ms),
+        confidence,
+        segments,
+        raw_text,
+        warnings,
+        ..me
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::TranscriptionSource;
+
+    fn transcription(segments: &[(i64, &str)]) -> Transcription {
+        Transcription {
+            id: "t".into(),
+            created_at: String::new(),
+            updated_at: String::new(),
+            source_type: TranscriptionSource::Dictation,
+            source_name: None,
+            source_path: None,
+            original_sample_rate: None,
+            duration_ms: segments.last().map_or(0, |s| s.0 + 1000),
+            language: "fr".into(),
+            segments: segments
+                .iter()
+                .map(|&(start_ms, text)| Segment {
+                    id: String::new(),
+                    start_ms,
+                    end_ms: start_ms + 1000,
+                    text: text.into(),
+                    confidence: 1.0,
+                    speaker: None,
+                    language: None,
+                    words: Vec::new(),
+                })
+                .collect(),
+            raw_text: String::new(),
+            edited_text: None,
+            is_edited: false,
+            is_draft: false,
+            warnings: Vec::new(),
+            normalization_gain: None,
+            action_items: Vec::new(),
+            markers: Vec::new(),
+            pauses: Vec::new(),
+            capture: None,
+            confidence: None,
+            audio_path: None,
+            translations: Vec::new(),
+            notes: Vec::new(),
+            folder: None,
+            tags: Vec::new(),
+            title: None,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn test_downmix_and_mix() {
+        assert_eq!(downmix(&[0.2, 0.4, -1.0, 0.0], 2), vec![0.3, -0.5]);
+        assert_eq!(mix(&[0.5, 0.8], &[0.25]), vec![0.75, 0.8]);
+        assert_eq!(mix(&[0.9], &[0.9]), vec![1.0]);
+    }
+
+    #[test]
+    fn test_merge_labels_turns_in_time_order() {
+        let me = transcription(&[(0, "Bonjour."), (4000, "Tres bien, merci.")]);
+        let them = transcription(&[(1500, "Bonjour,"), (2500, "comment allez-vous ?"), (6000, " ")]);
+
+        let merged = merge_sources(me, them);
+        assert_eq!(
+            merged.raw_text,
+            "Moi : Bonjour.\nEux : Bonjour, comment allez-vous ?\nMoi : Tres bien, merci."
+        );
+        let speakers: Vec<_> = merged.segments.iter().map(|s| s.speaker.as_deref().unwrap()).collect();
+        assert_eq!(speakers, ["me", "them", "them", "me"]);
+        assert_eq!(merged.duration_ms, 7000);
+    }
+}
@@ -0,0 +1,124 @@
+//! Lowering of the system output volume while recording
+//!
+//! Sound from the speakers bleeds into the microphone. While a recording is
+//! active (not paused) the output volume is brought down to a share of its
+//! level, then restored on stop, pause or when the capture is dropped. A
+//! volume changed by the user in the meantime is left as is.
+//!
+//! Platform probes: `pactl` (PulseAudio/PipeWire) on Linux, `osascript` on
+//! macOS. Other platforms report no volume and are not ducked.
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use std::process::Command;
+use tracing::{debug, info, warn};
+
+/// Output volume while ducked, in percent of the volume before recording
+pub const DEFAULT_DUCK_LEVEL: u8 = 30;
+
+/// Volume lowered by `duck`, restored when dropped
+#[derive(Debug)]
+pub struct Ducked {
+    saved: u32,
+    ducked: u32,
+}
+
+/// Lower the output volume to `level` percent of its current value
+///
+/// None if the volume cannot be read or set, or is already silent.
+pub fn duck(level: u8) -> Option<Ducked> {
+    let saved = output_volume()?;
+    if saved == 0 {
+        return None;
+    }
+    let ducked = saved * level.min(100) as u32 / 100;
+    if !set_output_volume(ducked) {
+        warn!("Failed to lower the output volume");
+        return None;
+    }
+    info!("Output volume lowered from {}% to {}%", saved, ducked);
+    Some(Ducked { saved, ducked })
+}
+
+impl Drop for Ducked {
+    fn drop(&mut self) {
+        match output_volume() {
+            Some(current) if current != self.ducked => {
+                debug!("Output volume changed to {}% while recording, keeping it", current);
+            }
+            _ => {
+                if set_output_volume(self.saved) {
+                    info!("Output volume restored to {}%", self.saved);
+                } else {
+                    warn!("Failed to restore the output volume to {}%", self.saved);
+                }
+            }
+        }
+    }
+}
+
+/// First percentage in a volume report ("Volume: front-left: 32768 /  50% / ...")
+#[cfg(any(target_os = "linux", test))]
+fn parse_percent(text: &str) -> Option<u32> {
+    let end = text.find('%')?;
+    let digits = text[..end]
+        .trim_end()
+        .rsplit(|c: char| !c.is_ascii_digit())
+        .next()?;
+    digits.parse().ok()
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(target_os = "linux")]
+fn output_volume() -> Option<u32> {
+    parse_percent(&run("pactl", &["get-sink-volume", "@DEFAULT_SINK@"])?)
+}
+
+#[cfg(target_os = "linux")]
+fn set_output_volume(percent: u32) -> bool {
+    run("pactl", &["set-sink-volume", "@DEFAULT_SINK@", &format!("{}%", percent)]).is_some()
+}
+
+#[cfg(target_os = "macos")]
+fn output_volume() -> Option<u32> {
+    let text = run("osascript", &["-e", "output volume of (get volume settings)"])?;
+    text.trim().parse().ok()
+}
+
+#[cfg(target_os = "macos")]
+fn set_output_volume(percent: u32) -> bool {
+    run("osascript", &["-e", &format!("set volume output volume {}", percent)]).is_some()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn output_volume() -> Option<u32> {
+    debug!("Output volume control not supported on this platform");
+    None
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn set_output_volume(_percent: u32) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_percent() {
+        assert_eq!(
+            parse_percent("Volume: front-left: 32768 /  50% / -18.06 dB,   front-right: 32768 /  50% / -18.06 dB"),
+            Some(50)
+        );
+        assert_eq!(parse_percent("Volume: mono: 98304 / 150%"), Some(150));
+        assert_eq!(parse_percent("no volume here"), None);
+    }
+}
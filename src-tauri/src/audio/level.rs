@@ -0,0 +1,114 @@
+//! Microphone level while recording, and its recent history for the waveform
+//!
+//! The stream callback feeds every captured block; one RMS value is kept per
+//! tenth of a second, for the last minute.
+
+use std::collections::VecDeque;
+
+/// History values per second
+pub const HISTORY_RATE_HZ: u32 = 10;
+
+/// Length of the history
+const HISTORY_SECONDS: usize = 60;
+
+const HISTORY_LEN: usize = HISTORY_RATE_HZ as usize * HISTORY_SECONDS;
+
+/// RMS scaled for display: 10x gain, then sqrt for more visual range
+fn display_level(rms: f32) -> f32 {
+    (rms * 10.0).sqrt().min(1.0)
+}
+
+pub struct LevelMeter {
+    /// Level of the last captured block (0 to 1)
+    level: f32,
+    /// Oldest first
+    history: VecDeque<f32>,
+    /// Interleaved samples per history value
+    window: usize,
+    sum_squares: f64,
+    count: usize,
+}
+
+impl LevelMeter {
+    pub fn new() -> Self {
+        Self {
+            level: 0.0,
+            history: VecDeque::with_capacity(HISTORY_LEN),
+            window: (16000 / HISTORY_RATE_HZ) as usize,
+            sum_squares: 0.0,
+            count: 0,
+        }
+    }
+
+    /// Empty the history for a stream of this format
+    pub fn reset(&mut self, sample_rate: u32, channels: u16) {
+        *self = Self::new();
+        self.window = ((sample_rate / HISTORY_RATE_HZ) as usize * channels.max(1) as usize).max(1);
+    }
+
+    /// Level of the last block goes to zero (history untouched)
+    pub fn silence(&mut self) {
+        self.level = 0.0;
+    }
+
+    pub fn push(&mut self, samples: &[f32]) {
+        if samples.is_empty() {
+            return;
+        }
+        let sum: f32 = samples.iter().map(|s| s * s).sum();
+        self.level = display_level((sum / samples.len() as f32).sqrt());
+
+        for &sample in samples {
+            self.sum_squares += (sample * sample) as f64;
+            self.count += 1;
+            if self.count == self.window {
+                let rms = (self.sum_squares / self.count as f64).sqrt() as f32;
+                if self.history.len() == HISTORY_LEN {
+                    self.history.pop_front();
+                }
+                self.history.push_back(display_level(rms));
+                self.sum_squares = 0.0;
+                self.count = 0;
+            }
+        }
+    }
+
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    /// Levels of the last minute, oldest first
+    pub fn history(&self) -> Vec<f32> {
+        self.history.iter().copied().collect()
+    }
+}
+
+impl Default for LevelMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_window_and_length() {
+        let mut meter = LevelMeter::new();
+        meter.reset(1000, 2);
+        // 100 Hz stereo: 200 samples per value
+        meter.push(&[0.1; 150]);
+        assert!(meter.history().is_empty());
+        assert!(meter.level() > 0.0);
+        meter.push(&[0.1; 250]);
+        assert_eq!(meter.history().len(), 2);
+        assert!((meter.history()[0] - 1.0).abs() < 1e-6);
+
+        meter.push(&vec![0.0; 200 * (HISTORY_LEN + 5)]);
+        let history = meter.history();
+        assert_eq!(history.len(), HISTORY_LEN);
+        assert_eq!(history[HISTORY_LEN - 1], 0.0);
+        assert_eq!(meter.level(), 0.0);
+    }
+}
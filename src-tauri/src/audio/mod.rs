@@ -1,8 +1,27 @@
+pub mod agc;
 pub mod capture;
 pub mod chunker;
+pub mod classifier;
+pub mod denoise;
+pub mod dual;
+pub mod ducking;
+pub mod level;
+pub mod normalize;
+pub mod pauses;
+pub mod permission;
 pub mod processor;
+pub mod silero;
+pub mod skip_silence;
+pub mod spill;
 pub mod vad;
 
-pub use capture::AudioCapture;
+pub use capture::{AudioCapture, SourceAudio};
+pub use dual::DualSourceMode;
 pub use chunker::{split_audio_smart, SmartChunkConfig};
-pub use processor::{duration_ms, load_audio_file, normalize_audio, resample_to_16k, write_wav};
+pub use classifier::{classify_audio, AudioContent};
+pub use denoise::{denoise, Denoise};
+pub use normalize::{normalize, Normalization};
+pub use permission::MicrophonePermission;
+pub use skip_silence::skip_silence;
+pub use vad::{edge_silence_ms, VadConfig};
+pub use processor::{duration_ms, load_audio_file, normalize_audio, probe_audio_file, resample_to_16k, write_wav};
@@ -0,0 +1,286 @@
+//! Level normalization strategies applied before transcription
+//!
+//! Whole-file RMS normalization (the historical default) measures pauses too,
+//! so quiet recordings with many pauses get a large gain that also amplifies
+//! breathing and room noise. LUFS measures gated loudness (pauses ignored),
+//! per-chunk adjusts the gain window by window.
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use super::processor::{calculate_rms, normalize_rms, soft_clip, MAX_GAIN, MIN_RMS_THRESHOLD, TARGET_RMS};
+
+const SAMPLE_RATE: f32 = 16000.0;
+
+/// Window of the per-chunk strategy (5s at 16kHz)
+const CHUNK_WINDOW: usize = 80_000;
+
+/// Windows quieter than this fraction of the loudest one are pauses (left at gain 1)
+const PAUSE_RMS_RATIO: f32 = 0.25;
+
+/// Default loudness target of the LUFS strategy
+const TARGET_LUFS: f32 = -20.0;
+
+/// How the audio level is adjusted before transcription
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum Normalization {
+    /// Keep the audio as recorded
+    Off,
+    /// Whole-file RMS to `target` (linear, e.g. 0.15)
+    Rms {
+        #[serde(default = "default_target_rms")]
+        target: f32,
+    },
+    /// Gated integrated loudness (ITU-R BS.1770) to `target` LUFS
+    Lufs {
+        #[serde(default = "default_target_lufs")]
+        target: f32,
+    },
+    /// RMS to `target` per 5s window, gains interpolated between windows
+    PerChunk {
+        #[serde(default = "default_target_rms")]
+        target: f32,
+    },
+}
+
+fn default_target_rms() -> f32 {
+    TARGET_RMS
+}
+
+fn default_target_lufs() -> f32 {
+    TARGET_LUFS
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Normalization::Rms { target: TARGET_RMS }
+    }
+}
+
+/// Normalize 16kHz mono samples; returns them with the applied gain
+/// (average speech gain for per-chunk)
+pub fn normalize(samples: &[f32], strategy: Normalization) -> (Vec<f32>, f32) {
+    match strategy {
+        Normalization::Off => (samples.to_vec(), 1.0),
+        Normalization::Rms { target } => normalize_rms(samples, target),
+        Normalization::Lufs { target } => normalize_lufs(samples, target),
+        Normalization::PerChunk { target } => normalize_per_chunk(samples, target),
+    }
+}
+
+fn normalize_lufs(samples: &[f32], target: f32) -> (Vec<f32>, f32) {
+    let Some(loudness) = integrated_loudness(samples) else {
+        info!("Audio too quiet or short for loudness measurement, skipping normalization");
+        return (samples.to_vec(), 1.0);
+    };
+
+    let gain = 10f32.powf((target - loudness) / 20.0).min(MAX_GAIN);
+    info!(
+        "Normalizing audio: {:.1} LUFS → {:.1} LUFS (gain: {:.1}x)",
+        loudness,
+        loudness + 20.0 * gain.log10(),
+        gain
+    );
+
+    (samples.iter().map(|&s| soft_clip(s * gain)).collect(), gain)
+}
+
+fn normalize_per_chunk(samples: &[f32], target: f32) -> (Vec<f32>, f32) {
+    if samples.is_empty() {
+        return (Vec::new(), 1.0);
+    }
+
+    let rms: Vec<f32> = samples.chunks(CHUNK_WINDOW).map(calculate_rms).collect();
+    let loudest = rms.iter().cloned().fold(0.0f32, f32::max);
+    let pause_level = (loudest * PAUSE_RMS_RATIO).max(MIN_RMS_THRESHOLD);
+
+    let gains: Vec<f32> = rms
+        .iter()
+        .map(|&r| if r < pause_level { 1.0 } else { (target / r).min(MAX_GAIN) })
+        .collect();
+
+    // Interpolate between window centers so the gain never jumps
+    let normalized = samples
+        .iter()
+        .enumerate()
+        .map(|(i, &s)| {
+            let pos = (i as f32 - CHUNK_WINDOW as f32 / 2.0) / CHUNK_WINDOW as f32;
+            let gain = if pos <= 0.0 {
+                gains[0]
+            } else {
+                let w = pos.floor() as usize;
+                let frac = pos - w as f32;
+                match (gains.get(w), gains.get(w + 1)) {
+                    (Some(a), Some(b)) => a + (b - a) * frac,
+                    (Some(a), None) => *a,
+                    _ => gains[gains.len() - 1],
+                }
+            };
+            soft_clip(s * gain)
+        })
+        .collect();
+
+    let speech_gains: Vec<f32> = rms
+        .iter()
+        .zip(&gains)
+        .filter(|(r, _)| **r >= pause_level)
+        .map(|(_, g)| *g)
+        .collect();
+    let average_gain = if speech_gains.is_empty() {
+        1.0
+    } else {
+        speech_gains.iter().sum::<f32>() / speech_gains.len() as f32
+    };
+    info!(
+        "Normalizing audio per chunk: {} windows, average gain {:.1}x",
+        gains.len(),
+        average_gain
+    );
+
+    (normalized, average_gain)
+}
+
+/// Second-order IIR filter (direct form I)
+struct Biquad {
+    b: [f32; 3],
+    a: [f32; 2],
+}
+
+impl Biquad {
+    /// RBJ high shelf
+    fn high_shelf(gain_db: f32, q: f32, fc: f32) -> Self {
+        let a = 10f32.powf(gain_db / 40.0);
+        let w0 = 2.0 * std::f32::consts::PI * fc / SAMPLE_RATE;
+        let (sin, cos) = w0.sin_cos();
+        let alpha = sin / (2.0 * q);
+        let sqrt_a = a.sqrt();
+
+        let a0 = (a + 1.0) - (a - 1.0) * cos + 2.0 * sqrt_a * alpha;
+        Self {
+            b: [
+                a * ((a + 1.0) + (a - 1.0) * cos + 2.0 * sqrt_a * alpha) / a0,
+                -2.0 * a * ((a - 1.0) + (a + 1.0) * cos) / a0,
+                a * ((a + 1.0) + (a - 1.0) * cos - 2.0 * sqrt_a * alpha) / a0,
+            ],
+            a: [
+                2.0 * ((a - 1.0) - (a + 1.0) * cos) / a0,
+                ((a + 1.0) - (a - 1.0) * cos - 2.0 * sqrt_a * alpha) / a0,
+            ],
+        }
+    }
+
+    /// RBJ high pass
+    fn high_pass(q: f32, fc: f32) -> Self {
+        let w0 = 2.0 * std::f32::consts::PI * fc / SAMPLE_RATE;
+        let (sin, cos) = w0.sin_cos();
+        let alpha = sin / (2.0 * q);
+
+        let a0 = 1.0 + alpha;
+        Self {
+            b: [(1.0 + cos) / 2.0 / a0, -(1.0 + cos) / a0, (1.0 + cos) / 2.0 / a0],
+            a: [-2.0 * cos / a0, (1.0 - alpha) / a0],
+        }
+    }
+
+    fn apply(&self, input: &[f32]) -> Vec<f32> {
+        let (mut x1, mut x2, mut y1, mut y2) = (0.0f32, 0.0f32, 0.0f32, 0.0f32);
+        input
+            .iter()
+            .map(|&x| {
+                let y = self.b[0] * x + self.b[1] * x1 + self.b[2] * x2 - self.a[0] * y1 - self.a[1] * y2;
+                x2 = x1;
+                x1 = x;
+                y2 = y1;
+                y1 = y;
+                y
+            })
+            .collect()
+    }
+}
+
+/// Integrated loudness in LUFS (BS.1770 K-weighting, 400ms blocks, gated)
+fn integrated_loudness(samples: &[f32]) -> Option<f32> {
+    // K-weighting: head shelf + RLB high pass
+    let shelf = Biquad::high_shelf(4.0, 0.7072, 1681.97);
+    let high_pass = Biquad::high_pass(0.5003, 38.135);
+    let weighted = high_pass.apply(&shelf.apply(samples));
+
+    let block = (0.4 * SAMPLE_RATE) as usize;
+    let step = block / 4; // 75% overlap
+    if weighted.len() < block {
+        return None;
+    }
+
+    let powers: Vec<f32> = (0..=(weighted.len() - block) / step)
+        .map(|i| {
+            let b = &weighted[i * step..i * step + block];
+            b.iter().map(|s| s * s).sum::<f32>() / block as f32
+        })
+        .collect();
+    let loudness = |power: f32| -0.691 + 10.0 * power.log10();
+    let gated_mean = |threshold: f32| {
+        let kept: Vec<f32> = powers.iter().cloned().filter(|&p| p > 0.0 && loudness(p) > threshold).collect();
+        (!kept.is_empty()).then(|| kept.iter().sum::<f32>() / kept.len() as f32)
+    };
+
+    // Absolute gate at -70 LUFS, then relative gate 10 LU below
+    let absolute = gated_mean(-70.0)?;
+    let relative = gated_mean(loudness(absolute) - 10.0)?;
+    Some(loudness(relative))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq: f32, amplitude: f32, seconds: f32) -> Vec<f32> {
+        (0..(seconds * SAMPLE_RATE) as usize)
+            .map(|i| amplitude * (2.0 * std::f32::consts::PI * freq * i as f32 / SAMPLE_RATE).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_full_scale_sine_loudness() {
+        // BS.1770 reference: 1kHz sine at 0 dBFS reads about -3 LUFS
+        let loudness = integrated_loudness(&sine(1000.0, 1.0, 3.0)).unwrap();
+        assert!((loudness + 3.0).abs() < 0.5, "loudness = {}", loudness);
+    }
+
+    #[test]
+    fn test_lufs_ignores_pauses() {
+        let tone = sine(440.0, 0.05, 2.0);
+        let mut with_pauses = tone.clone();
+        with_pauses.extend(vec![0.0; 64000]);
+        with_pauses.extend(&tone);
+
+        let (_, continuous) = normalize(&tone, Normalization::Lufs { target: -20.0 });
+        let (_, paused) = normalize(&with_pauses, Normalization::Lufs { target: -20.0 });
+        assert!((continuous - paused).abs() < 0.05 * continuous);
+
+        // Whole-file RMS boosts more because of the pause
+        let (_, rms_gain) = normalize(&with_pauses, Normalization::default());
+        let (_, rms_continuous) = normalize(&tone, Normalization::default());
+        assert!(rms_gain > rms_continuous * 1.2);
+    }
+
+    #[test]
+    fn test_per_chunk_levels_quiet_and_loud_parts() {
+        let mut samples = sine(440.0, 0.08, 5.0);
+        samples.extend(sine(440.0, 0.2, 5.0));
+
+        let (normalized, _) = normalize(&samples, Normalization::PerChunk { target: 0.1 });
+        let quiet = calculate_rms(&normalized[..40000]);
+        let loud = calculate_rms(&normalized[120000..]);
+        assert!((quiet - 0.1).abs() < 0.02, "quiet = {}", quiet);
+        assert!((loud - 0.1).abs() < 0.02, "loud = {}", loud);
+    }
+
+    #[test]
+    fn test_off_keeps_samples() {
+        let samples = sine(440.0, 0.01, 1.0);
+        let (out, gain) = normalize(&samples, Normalization::Off);
+        assert_eq!(gain, 1.0);
+        assert_eq!(out, samples);
+    }
+}
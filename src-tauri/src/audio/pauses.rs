@@ -0,0 +1,135 @@
+//! Pauses of a recording in its transcript
+//!
+//! Paused time is not recorded, so the transcript timeline runs on as if
+//! there had been no pause. Each pause becomes a "[pause]" segment of no
+//! length where the audio resumes; a segment spanning that point is split
+//! at its first word after it.
+
+use crate::storage::{RecordingPause, Segment, SegmentWord};
+use uuid::Uuid;
+
+/// Text of the segment standing for a pause
+pub const PAUSE_MARKER: &str = "[pause]";
+
+/// `segments` (in time order) with a pause segment at each pause
+pub fn insert_pause_segments(segments: Vec<Segment>, pauses: &[RecordingPause]) -> Vec<Segment> {
+    let mut pending = pauses.iter().peekable();
+    let mut out = Vec::with_capacity(segments.len() + pauses.len());
+
+    for mut segment in segments {
+        while let Some(pause) = pending.peek() {
+            if pause.at_ms <= segment.start_ms {
+                out.push(pause_segment(pause));
+                pending.next();
+                continue;
+            }
+            if pause.at_ms >= segment.end_ms {
+                break;
+            }
+            // Within the segment: split before the first word said after the pause
+            match segment.words.iter().position(|w| w.start_ms >= pause.at_ms) {
+                Some(0) => {
+                    out.push(pause_segment(pause));
+                    pending.next();
+                }
+                Some(index) => {
+                    let rest = split_off(&mut segment, index);
+                    out.push(std::mem::replace(&mut segment, rest));
+                }
+                // No word timings, or none after the pause: it follows the segment
+                None => break,
+            }
+        }
+        out.push(segment);
+    }
+    out.extend(pending.map(pause_segment));
+    out
+}
+
+fn pause_segment(pause: &RecordingPause) -> Segment {
+    Segment {
+        id: Uuid::new_v4().to_string(),
+        start_ms: pause.at_ms,
+        end_ms: pause.at_ms,
+        text: PAUSE_MARKER.to_string(),
+        confidence: 1.0,
+        speaker: None,
+        language: None,
+        words: Vec::new(),
+    }
+}
+
+/// Words from `index` on moved to a new segment (texts rebuilt from the words)
+fn split_off(segment: &mut Segment, index: usize) -> Segment {
+    let words = segment.words.split_off(index);
+    let rest = Segment {
+        id: Uuid::new_v4().to_string(),
+        start_ms: words[0].start_ms,
+        text: join_words(&words),
+        words,
+        ..segment.clone()
+    };
+    segment.text = join_words(&segment.words);
+    segment.end_ms = segment.words.last().map_or(segment.end_ms, |w| w.end_ms);
+    rest
+}
+
+fn join_words(words: &[SegmentWord]) -> String {
+    words.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start_ms: i64, end_ms: i64, words: &[(&str, i64)]) -> Segment {
+        Segment {
+            id: format!("s{}", start_ms),
+            start_ms,
+            end_ms,
+            text: words.iter().map(|w| w.0).collect::<Vec<_>>().join(" "),
+            confidence: 0.9,
+            speaker: None,
+            language: None,
+            words: words
+                .iter()
+                .map(|&(text, start_ms)| SegmentWord {
+                    text: text.into(),
+                    start_ms,
+                    end_ms: start_ms + 300,
+                    confidence: 0.9,
+                })
+                .collect(),
+        }
+    }
+
+    fn pause(at_ms: i64) -> RecordingPause {
+        RecordingPause { at_ms, duration_ms: 60_000 }
+    }
+
+    #[test]
+    fn test_pauses_split_segments_at_words() {
+        let segments = vec![
+            segment(0, 2000, &[("Bonjour", 0), ("a", 500), ("tous", 1700)]),
+            segment(3000, 4000, &[("Merci.", 3000)]),
+        ];
+        let out = insert_pause_segments(segments, &[pause(1000), pause(2500), pause(5000)]);
+        let texts: Vec<_> = out.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, ["Bonjour a", "[pause]", "tous", "[pause]", "Merci.", "[pause]"]);
+        assert_eq!((out[0].start_ms, out[0].end_ms), (0, 800));
+        assert_eq!((out[1].start_ms, out[1].end_ms), (1000, 1000));
+        assert_eq!((out[2].start_ms, out[2].end_ms), (1700, 2000));
+        assert_eq!(out[2].words.len(), 1);
+        assert_eq!(out[0].id, "s0");
+        assert_ne!(out[2].id, "s0");
+    }
+
+    #[test]
+    fn test_pause_after_a_segment_without_words() {
+        let segments = vec![segment(0, 2000, &[]), segment(2000, 3000, &[])];
+        let out = insert_pause_segments(segments, &[pause(1500)]);
+        let texts: Vec<_> = out.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, ["", "[pause]", ""]);
+        assert_eq!(out[1].start_ms, 1500);
+    }
+}
@@ -0,0 +1,151 @@
+//! Microphone permission preflight
+//!
+//! On macOS a denied permission doesn't make cpal fail: the stream opens and
+//! records silence, so the first dictation is lost without any error. The UI
+//! checks the permission before recording and guides the user when it is
+//! missing.
+
+use serde::Serialize;
+
+/// Operating system decision about microphone access
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionState {
+    Granted,
+    /// Refused by the user or blocked by a policy
+    Denied,
+    /// The user hasn't been asked yet
+    Undetermined,
+}
+
+/// Permission state and, when access is missing, how to grant it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MicrophonePermission {
+    pub state: PermissionState,
+    pub instructions: Option<String>,
+}
+
+impl MicrophonePermission {
+    fn new(state: PermissionState) -> Self {
+        let instructions = match state {
+            PermissionState::Granted => None,
+            PermissionState::Denied => Some(DENIED_INSTRUCTIONS.to_string()),
+            PermissionState::Undetermined => Some(UNDETERMINED_INSTRUCTIONS.to_string()),
+        };
+        Self { state, instructions }
+    }
+}
+
+#[cfg(target_os = "macos")]
+const DENIED_INSTRUCTIONS: &str = "Autorisez WakaScribe dans Reglages Systeme > Confidentialite et securite > Microphone, puis relancez l'application.";
+#[cfg(target_os = "windows")]
+const DENIED_INSTRUCTIONS: &str = "Activez \"Autoriser les applications de bureau a acceder au microphone\" dans Parametres > Confidentialite et securite > Microphone.";
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const DENIED_INSTRUCTIONS: &str = "Verifiez que le microphone n'est pas coupe et que l'application y a acces.";
+
+const UNDETERMINED_INSTRUCTIONS: &str =
+    "Le systeme va demander l'acces au microphone au premier enregistrement : acceptez pour pouvoir dicter.";
+
+/// Current permission, without prompting the user
+pub fn check() -> MicrophonePermission {
+    MicrophonePermission::new(probe())
+}
+
+/// Ask the user for access when undetermined (blocks until they answer)
+///
+/// Platforms without a prompt return the current state.
+pub fn request() -> MicrophonePermission {
+    match probe() {
+        PermissionState::Undetermined => MicrophonePermission::new(prompt()),
+        state => MicrophonePermission::new(state),
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod avfoundation {
+    use block::ConcreteBlock;
+    use objc::runtime::{Object, BOOL, YES};
+    use objc::{class, msg_send, sel, sel_impl};
+    use std::sync::mpsc;
+
+    #[link(name = "AVFoundation", kind = "framework")]
+    extern "C" {
+        static AVMediaTypeAudio: *mut Object;
+    }
+
+    /// AVAuthorizationStatus: 0 not determined, 1 restricted, 2 denied, 3 authorized
+    pub fn authorization_status() -> i64 {
+        // SAFETY: class method taking a media type constant, returns an NSInteger
+        unsafe { msg_send![class!(AVCaptureDevice), authorizationStatusForMediaType: AVMediaTypeAudio] }
+    }
+
+    /// Show the system prompt and wait for the answer
+    pub fn request_access() -> bool {
+        let (tx, rx) = mpsc::channel();
+        let handler = ConcreteBlock::new(move |granted: BOOL| {
+            let _ = tx.send(granted == YES);
+        })
+        .copy();
+        // SAFETY: the block is copied to the heap and retained by AVFoundation until called
+        unsafe {
+            let _: () = msg_send![class!(AVCaptureDevice), requestAccessForMediaType: AVMediaTypeAudio completionHandler: &*handler];
+        }
+        rx.recv().unwrap_or(false)
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn probe() -> PermissionState {
+    match avfoundation::authorization_status() {
+        0 => PermissionState::Undetermined,
+        3 => PermissionState::Granted,
+        _ => PermissionState::Denied,
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn prompt() -> PermissionState {
+    if avfoundation::request_access() {
+        PermissionState::Granted
+    } else {
+        PermissionState::Denied
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn probe() -> PermissionState {
+    // Privacy switches: "Value" is "Allow" or "Deny" (machine-wide and per user)
+    let denied = |hive: &str| {
+        std::process::Command::new("reg")
+            .args([
+                "query",
+                &format!(
+                    r"{}\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone",
+                    hive
+                ),
+                "/v",
+                "Value",
+            ])
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).contains("Deny"))
+            .unwrap_or(false)
+    };
+
+    if denied("HKLM") || denied("HKCU") {
+        PermissionState::Denied
+    } else {
+        PermissionState::Granted
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn probe() -> PermissionState {
+    // No OS-level microphone permission
+    PermissionState::Granted
+}
+
+#[cfg(not(target_os = "macos"))]
+fn prompt() -> PermissionState {
+    probe()
+}
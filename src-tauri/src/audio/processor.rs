@@ -2,11 +2,19 @@ use crate::error::{AppError, Result};
 use hound::{SampleFormat, WavSpec, WavWriter};
 use rubato::{FftFixedInOut, Resampler};
 use std::path::Path;
-use tracing::info;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use tracing::{info, warn};
 
 const TARGET_SAMPLE_RATE: u32 = 16000;
-const TARGET_RMS: f32 = 0.15; // Target RMS for normalization (based on working test file)
-const MIN_RMS_THRESHOLD: f32 = 0.001; // Below this, audio is considered silence
+pub const TARGET_RMS: f32 = 0.15; // Target RMS for normalization (based on working test file)
+pub const MIN_RMS_THRESHOLD: f32 = 0.001; // Below this, audio is considered silence
+pub const MAX_GAIN: f32 = 20.0; // Avoid amplifying noise too much
 
 /// Resample audio to 16kHz mono
 pub fn resample_to_16k(samples: &[f32], source_rate: u32) -> Result<Vec<f32>> {
@@ -60,15 +68,132 @@ pub fn load_audio_file(path: &Path) -> Result<(Vec<f32>, u32)> {
 
     match extension.as_str() {
         "wav" => load_wav(path),
+        "mp3" | "m4a" | "ogg" | "flac" => load_compressed(path, &extension),
+        _ => Err(AppError::Audio(format!("Unknown audio format: {}", extension))),
+    }
+}
+
+/// Duration (ms) and sample rate of an audio file, from its header when possible
+pub fn probe_audio_file(path: &Path) -> Result<(i64, u32)> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|s| s.to_lowercase())
+        .unwrap_or_default();
+
+    let header = match extension.as_str() {
+        "wav" => {
+            let reader = hound::WavReader::open(path).map_err(|e| AppError::Audio(e.to_string()))?;
+            let rate = reader.spec().sample_rate;
+            Some((reader.duration() as u64, rate))
+        }
         "mp3" | "m4a" | "ogg" | "flac" => {
-            // For now, we only support WAV natively
-            // Other formats would need additional dependencies like symphonia
-            Err(AppError::Audio(format!(
-                "Format {} not yet supported. Please convert to WAV.",
-                extension
-            )))
+            let file = std::fs::File::open(path)?;
+            let stream = MediaSourceStream::new(Box::new(file), Default::default());
+            let mut hint = Hint::new();
+            hint.with_extension(&extension);
+            let probed = symphonia::default::get_probe()
+                .format(&hint, stream, &FormatOptions::default(), &MetadataOptions::default())
+                .map_err(|e| AppError::Audio(format!("Cannot read {} file: {}", extension, e)))?;
+            probed
+                .format
+                .tracks()
+                .iter()
+                .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+                .and_then(|t| Some((t.codec_params.n_frames?, t.codec_params.sample_rate?)))
+        }
+        _ => return Err(AppError::Audio(format!("Unknown audio format: {}", extension))),
+    };
+
+    match header {
+        Some((frames, rate)) if rate > 0 => Ok(((frames * 1000 / rate as u64) as i64, rate)),
+        // Length not in the header (some MP3s): decode it
+        _ => {
+            let (samples, rate) = load_audio_file(path)?;
+            Ok((duration_ms(&samples, rate), rate))
         }
-        _ => Err(AppError::Audio(format!("Unknown audio format: {}", extension))),
+    }
+}
+
+/// Decode MP3, M4A (AAC), OGG (Vorbis) and FLAC with symphonia
+fn load_compressed(path: &Path, extension: &str) -> Result<(Vec<f32>, u32)> {
+    let file = std::fs::File::open(path)?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+    let mut hint = Hint::new();
+    hint.with_extension(extension);
+
+    let probed = symphonia::default::get_probe()
+        .format(&hint, stream, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| AppError::Audio(format!("Cannot read {} file: {}", extension, e)))?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| AppError::Audio("No audio track in file".to_string()))?;
+    let track_id = track.id;
+    let codec_params = track.codec_params.clone();
+    let sample_rate = codec_params
+        .sample_rate
+        .ok_or_else(|| AppError::Audio("Unknown sample rate".to_string()))?;
+
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&codec_params, &DecoderOptions::default())
+        .map_err(|e| AppError::Audio(format!("Unsupported codec in {} file: {}", extension, e)))?;
+
+    info!("Decoding {}: {}Hz, codec {:?}", extension, sample_rate, codec_params.codec);
+
+    let mut mono_samples = Vec::new();
+    let mut buffer: Option<SampleBuffer<f32>> = None;
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            // End of stream
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(AppError::Audio(e.to_string())),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // Corrupted frame: skip it, as players do
+            Err(SymphoniaError::DecodeError(e)) => {
+                warn!("Skipping undecodable {} frame: {}", extension, e);
+                continue;
+            }
+            Err(e) => return Err(AppError::Audio(e.to_string())),
+        };
+
+        let spec = *decoded.spec();
+        let channels = spec.channels.count();
+        let needed = decoded.capacity() * channels;
+        if buffer.as_ref().map_or(0, |b| b.capacity()) < needed {
+            buffer = Some(SampleBuffer::new(decoded.capacity() as u64, spec));
+        }
+        let buffer = buffer.as_mut().unwrap();
+        buffer.copy_interleaved_ref(decoded);
+        mono_samples.extend(to_mono(buffer.samples(), channels));
+    }
+
+    if mono_samples.is_empty() {
+        return Err(AppError::Audio(format!("No audio decoded from {} file", extension)));
+    }
+
+    Ok((mono_samples, sample_rate))
+}
+
+/// Average interleaved channels
+fn to_mono(samples: &[f32], channels: usize) -> Vec<f32> {
+    if channels > 1 {
+        samples
+            .chunks(channels)
+            .map(|chunk| chunk.iter().sum::<f32>() / channels as f32)
+            .collect()
+    } else {
+        samples.to_vec()
     }
 }
 
@@ -101,16 +226,7 @@ fn load_wav(path: &Path) -> Result<(Vec<f32>, u32)> {
     };
 
     // Convert to mono by averaging channels
-    let mono_samples: Vec<f32> = if channels > 1 {
-        samples
-            .chunks(channels)
-            .map(|chunk| chunk.iter().sum::<f32>() / channels as f32)
-            .collect()
-    } else {
-        samples
-    };
-
-    Ok((mono_samples, sample_rate))
+    Ok((to_mono(&samples, channels), sample_rate))
 }
 
 /// Calculate the duration in milliseconds
@@ -130,6 +246,11 @@ pub fn calculate_rms(samples: &[f32]) -> f32 {
 /// Normalize audio to target RMS level
 /// Returns normalized samples and the gain applied
 pub fn normalize_audio(samples: &[f32]) -> (Vec<f32>, f32) {
+    normalize_rms(samples, TARGET_RMS)
+}
+
+/// Normalize audio to the given RMS level (gain capped at `MAX_GAIN`)
+pub fn normalize_rms(samples: &[f32], target_rms: f32) -> (Vec<f32>, f32) {
     let current_rms = calculate_rms(samples);
 
     // If audio is essentially silence, don't amplify noise
@@ -142,10 +263,10 @@ pub fn normalize_audio(samples: &[f32]) -> (Vec<f32>, f32) {
     }
 
     // Calculate gain needed to reach target RMS
-    let gain = TARGET_RMS / current_rms;
+    let gain = target_rms / current_rms;
 
     // Limit maximum gain to avoid amplifying noise too much (max 20x)
-    let gain = gain.min(20.0);
+    let gain = gain.min(MAX_GAIN);
 
     info!(
         "Normalizing audio: RMS {:.4} → {:.4} (gain: {:.1}x)",
@@ -154,21 +275,16 @@ pub fn normalize_audio(samples: &[f32]) -> (Vec<f32>, f32) {
         gain
     );
 
-    // Apply gain with soft clipping to prevent harsh distortion
-    let normalized: Vec<f32> = samples
-        .iter()
-        .map(|&s| {
-            let amplified = s * gain;
-            // Soft clipping using tanh for values approaching ±1
-            if amplified.abs() > 0.9 {
-                amplified.signum() * (0.9 + 0.1 * ((amplified.abs() - 0.9) / 0.1).tanh())
-            } else {
-                amplified
-            }
-        })
-        .collect();
+    (samples.iter().map(|&s| soft_clip(s * gain)).collect(), gain)
+}
 
-    (normalized, gain)
+/// Soft clipping using tanh for values approaching ±1
+pub fn soft_clip(amplified: f32) -> f32 {
+    if amplified.abs() > 0.9 {
+        amplified.signum() * (0.9 + 0.1 * ((amplified.abs() - 0.9) / 0.1).tanh())
+    } else {
+        amplified
+    }
 }
 
 /// Write audio samples to a WAV file (16kHz mono, 16-bit PCM)
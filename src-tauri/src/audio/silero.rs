@@ -0,0 +1,96 @@
+//! Silero VAD: speech probability from a small neural network
+//!
+//! The energy VAD takes breathy speech for silence and a steady hum for
+//! speech, and then cuts chunks in the middle of words. Silero (v5, a 2 MB
+//! ONNX model run on the CPU through ONNX Runtime) scores each 32ms frame
+//! instead. It is optional: without its model file, `speech_probabilities`
+//! returns None and `vad` falls back to the energy threshold.
+
+use crate::error::{AppError, Result};
+use once_cell::sync::OnceCell;
+use ort::session::{builder::GraphOptimizationLevel, Session};
+use ort::value::Tensor;
+use parking_lot::Mutex;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Model file, in the model directory or its `vad` subdirectory
+pub const MODEL_FILE: &str = "silero_vad.onnx";
+
+/// Samples scored at once (32ms at 16kHz)
+pub const FRAME_SAMPLES: usize = 512;
+
+/// End of the previous frame, fed along with each frame (v5 models)
+const CONTEXT_SAMPLES: usize = 64;
+
+/// Recurrent state: [2, 1, 128]
+const STATE_SHAPE: [usize; 3] = [2, 1, 128];
+
+static MODEL: OnceCell<Mutex<Session>> = OnceCell::new();
+
+fn model_path(model_base: &Path) -> Option<PathBuf> {
+    [model_base.join("vad").join(MODEL_FILE), model_base.join(MODEL_FILE)]
+        .into_iter()
+        .find(|p| p.exists())
+}
+
+/// Load the model from `model_base` (once; later calls do nothing)
+pub fn load(model_base: &Path) -> Result<()> {
+    if MODEL.get().is_some() {
+        return Ok(());
+    }
+    let path = model_path(model_base)
+        .ok_or_else(|| AppError::NotFound(format!("{} not found in {:?}", MODEL_FILE, model_base)))?;
+
+    let _ = ort::init().with_name("WakaScribe").commit();
+    let session = Session::builder()
+        .and_then(|b| b.with_optimization_level(GraphOptimizationLevel::Level3))
+        .and_then(|b| b.with_intra_threads(1))
+        .and_then(|b| b.commit_from_file(&path))
+        .map_err(|e| AppError::Audio(format!("Failed to load the Silero VAD model: {}", e)))?;
+    let _ = MODEL.set(Mutex::new(session));
+    info!("Silero VAD loaded from {:?}", path);
+    Ok(())
+}
+
+/// Speech probability of each `FRAME_SAMPLES` frame of 16kHz `samples`
+/// (the last one zero-padded); None without the model or when it fails
+pub fn speech_probabilities(samples: &[f32]) -> Option<Vec<f32>> {
+    let mut session = MODEL.get()?.lock();
+    match run(&mut session, samples) {
+        Ok(probabilities) => Some(probabilities),
+        Err(e) => {
+            warn!("Silero VAD failed, using the energy VAD: {}", e);
+            None
+        }
+    }
+}
+
+fn run(session: &mut Session, samples: &[f32]) -> Result<Vec<f32>> {
+    let err = |e: ort::Error| AppError::Audio(format!("Silero VAD: {}", e));
+
+    let mut state = vec![0.0f32; STATE_SHAPE.iter().product()];
+    let mut input = vec![0.0f32; CONTEXT_SAMPLES + FRAME_SAMPLES];
+    let mut probabilities = Vec::with_capacity(samples.len().div_ceil(FRAME_SAMPLES));
+
+    for frame in samples.chunks(FRAME_SAMPLES) {
+        // Context: the last samples of the previous frame
+        input.copy_within(FRAME_SAMPLES.., 0);
+        input[CONTEXT_SAMPLES..].fill(0.0);
+        input[CONTEXT_SAMPLES..CONTEXT_SAMPLES + frame.len()].copy_from_slice(frame);
+
+        let outputs = session
+            .run(ort::inputs![
+                "input" => Tensor::from_array(([1usize, input.len()], input.clone())).map_err(err)?,
+                "state" => Tensor::from_array((STATE_SHAPE, state)).map_err(err)?,
+                "sr" => Tensor::from_array(([0usize; 0], vec![16000i64])).map_err(err)?,
+            ])
+            .map_err(err)?;
+
+        let (_, probability) = outputs["output"].try_extract_tensor::<f32>().map_err(err)?;
+        probabilities.push(probability[0]);
+        let (_, next_state) = outputs["stateN"].try_extract_tensor::<f32>().map_err(err)?;
+        state = next_state.to_vec();
+    }
+    Ok(probabilities)
+}
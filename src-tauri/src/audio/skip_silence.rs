@@ -0,0 +1,154 @@
+//! Long pauses removed from files before transcription
+//!
+//! Hour-long recordings (meetings, lectures) can be 40% silence, and every
+//! second of it still goes through the encoder. Pauses longer than
+//! `MIN_SKIPPED_MS` are shortened to `KEPT_MS`, so the chunker and the
+//! sentence-gap punctuation still see a pause there; the `TimeMap` puts
+//! the timestamps of the transcription back on the file's timeline.
+
+use super::vad::{find_silence_regions, VadConfig};
+use crate::storage::Transcription;
+use tracing::info;
+
+const SAMPLES_PER_MS: usize = 16;
+
+/// Pauses shorter than this are left as they are
+pub const MIN_SKIPPED_MS: usize = 2000;
+
+/// What remains of a skipped pause (half on each side of the cut)
+const KEPT_MS: usize = 500;
+
+/// Position in the shortened audio -> position in the original one
+#[derive(Debug, Clone)]
+pub struct TimeMap {
+    /// Start of each kept piece (ms), in the shortened and in the original audio
+    pieces: Vec<(i64, i64)>,
+    original_ms: i64,
+}
+
+impl TimeMap {
+    /// Original time of `ms`; a time on a cut is the start of what follows
+    pub fn to_original_ms(&self, ms: i64) -> i64 {
+        self.project(self.pieces.partition_point(|&(start, _)| start <= ms), ms)
+    }
+
+    /// Same, but a time on a cut is the end of what precedes (segment ends)
+    fn end_to_original_ms(&self, ms: i64) -> i64 {
+        self.project(self.pieces.partition_point(|&(start, _)| start < ms), ms)
+    }
+
+    fn project(&self, after: usize, ms: i64) -> i64 {
+        match after.checked_sub(1).and_then(|i| self.pieces.get(i)) {
+            Some(&(start, original)) => original + ms - start,
+            None => ms,
+        }
+    }
+
+    /// Silence removed (ms)
+    pub fn skipped_ms(&self) -> i64 {
+        self.pieces.last().map_or(0, |&(start, original)| original - start)
+    }
+
+    /// Move the segments and words of `t`, transcribed from the shortened audio
+    pub fn apply(&self, t: &mut Transcription) {
+        for segment in &mut t.segments {
+            segment.start_ms = self.to_original_ms(segment.start_ms);
+            segment.end_ms = self.end_to_original_ms(segment.end_ms);
+            for word in &mut segment.words {
+                word.start_ms = self.to_original_ms(word.start_ms);
+                word.end_ms = self.end_to_original_ms(word.end_ms);
+            }
+        }
+        t.duration_ms = self.original_ms;
+    }
+}
+
+/// Shorten the long pauses of 16kHz `samples`
+pub fn skip_silence(samples: &[f32], config: &VadConfig) -> (Vec<f32>, TimeMap) {
+    let half_kept = KEPT_MS / 2 * SAMPLES_PER_MS;
+    let to_ms = |n: usize| (n / SAMPLES_PER_MS) as i64;
+
+    let mut output = Vec::with_capacity(samples.len());
+    let mut pieces = vec![(0, 0)];
+    let mut cursor = 0;
+    for (start, end) in find_silence_regions(samples, config) {
+        if end - start < MIN_SKIPPED_MS * SAMPLES_PER_MS {
+            continue;
+        }
+        output.extend_from_slice(&samples[cursor..start + half_kept]);
+        cursor = end - half_kept;
+        pieces.push((to_ms(output.len()), to_ms(cursor)));
+    }
+    output.extend_from_slice(&samples[cursor..]);
+
+    let map = TimeMap {
+        pieces,
+        original_ms: to_ms(samples.len()),
+    };
+    if map.skipped_ms() > 0 {
+        info!(
+            "Skipping {:.1}s of silence out of {:.1}s",
+            map.skipped_ms() as f32 / 1000.0,
+            map.original_ms as f32 / 1000.0
+        );
+    }
+    (output, map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{Segment, SegmentWord};
+
+    fn loud(ms: usize) -> Vec<f32> {
+        vec![0.5; ms * SAMPLES_PER_MS]
+    }
+
+    fn silent(ms: usize) -> Vec<f32> {
+        vec![0.0; ms * SAMPLES_PER_MS]
+    }
+
+    #[test]
+    fn test_long_pauses_skipped_and_times_mapped_back() {
+        // 1s speech, 5s pause, 1s speech, 1s pause (kept), 1s speech
+        let samples = [loud(1000), silent(5000), loud(1000), silent(1000), loud(1000)].concat();
+        let (short, map) = skip_silence(&samples, &VadConfig::energy_only());
+
+        let skipped = map.skipped_ms();
+        assert!((4400..=4600).contains(&skipped), "skipped {}", skipped);
+        assert_eq!((samples.len() - short.len()) as i64, skipped * SAMPLES_PER_MS as i64);
+
+        // Before the cut: unchanged; after: shifted by the skipped pause
+        assert_eq!(map.to_original_ms(500), 500);
+        assert_eq!(map.to_original_ms(2000), 2000 + skipped);
+
+        let mut t: Transcription = serde_json::from_str(
+            r#"{"id": "a", "createdAt": "", "updatedAt": "", "sourceType": "file", "sourceName": null,
+                "durationMs": 0, "language": "fr", "segments": [], "rawText": "", "editedText": null, "isEdited": false}"#,
+        )
+        .unwrap();
+        t.segments.push(Segment {
+            id: "s".into(),
+            start_ms: 1800,
+            end_ms: 2200,
+            text: "bonjour".into(),
+            confidence: 1.0,
+            speaker: None,
+            language: None,
+            words: vec![SegmentWord { text: "bonjour".into(), start_ms: 1800, end_ms: 2200, confidence: 1.0 }],
+        });
+        map.apply(&mut t);
+        assert_eq!(t.segments[0].start_ms, 1800 + skipped);
+        assert_eq!(t.segments[0].words[0].end_ms, 2200 + skipped);
+        assert_eq!(t.duration_ms, 9000);
+    }
+
+    #[test]
+    fn test_nothing_to_skip() {
+        let samples = [loud(1000), silent(1000), loud(1000)].concat();
+        let (short, map) = skip_silence(&samples, &VadConfig::energy_only());
+        assert_eq!(short, samples);
+        assert_eq!(map.skipped_ms(), 0);
+        assert_eq!(map.to_original_ms(2500), 2500);
+    }
+}
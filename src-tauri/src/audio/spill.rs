@@ -0,0 +1,129 @@
+//! Recording buffer moved to disk
+//!
+//! Samples of a long recording are taken from the in-memory buffer by the
+//! audio thread and appended to a temporary WAV file (32-bit float, as
+//! captured), so memory use stays bounded. The file is removed with the
+//! `SpillFile`.
+
+use crate::error::{AppError, Result};
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::{info, warn};
+
+/// Samples kept in memory before they are moved to disk (64 MB; about 5
+/// minutes of 48kHz stereo)
+pub const SPILL_THRESHOLD: usize = 16 * 1024 * 1024;
+
+static NEXT_FILE: AtomicU64 = AtomicU64::new(0);
+
+/// Start of a recording, written to a temporary WAV file
+pub struct SpillFile {
+    path: PathBuf,
+    writer: Option<WavWriter<BufWriter<File>>>,
+    channels: u16,
+    len: usize,
+}
+
+impl SpillFile {
+    pub fn create(sample_rate: u32, channels: u16) -> Result<Self> {
+        let path = std::env::temp_dir().join(format!(
+            "wakascribe-capture-{}-{}.wav",
+            std::process::id(),
+            NEXT_FILE.fetch_add(1, Ordering::SeqCst)
+        ));
+        let spec = WavSpec {
+            channels: channels.max(1),
+            sample_rate,
+            bits_per_sample: 32,
+            sample_format: SampleFormat::Float,
+        };
+        let writer = WavWriter::create(&path, spec).map_err(|e| AppError::Audio(e.to_string()))?;
+        info!("Recording buffer moved to {}", path.display());
+        Ok(Self {
+            path,
+            writer: Some(writer),
+            channels: channels.max(1),
+            len: 0,
+        })
+    }
+
+    /// Samples on disk (interleaved)
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn append(&mut self, samples: &[f32]) -> Result<()> {
+        let writer = self
+            .writer
+            .as_mut()
+            .ok_or_else(|| AppError::InvalidState("Spill file closed".into()))?;
+        for &sample in samples {
+            writer.write_sample(sample).map_err(|e| AppError::Audio(e.to_string()))?;
+        }
+        self.len += samples.len();
+        Ok(())
+    }
+
+    /// Samples from index `from` (interleaved) to the end
+    pub fn read_from(&mut self, from: usize) -> Result<Vec<f32>> {
+        if from >= self.len {
+            return Ok(Vec::new());
+        }
+        // Header and buffered samples must be on disk before reading
+        if let Some(writer) = self.writer.as_mut() {
+            writer.flush().map_err(|e| AppError::Audio(e.to_string()))?;
+        }
+
+        let channels = self.channels as usize;
+        let mut reader = WavReader::open(&self.path).map_err(|e| AppError::Audio(e.to_string()))?;
+        reader.seek((from / channels) as u32)?;
+        let mut samples = reader
+            .samples::<f32>()
+            .skip(from % channels)
+            .take(self.len - from)
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Audio(e.to_string()))?;
+        samples.shrink_to_fit();
+        Ok(samples)
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        drop(self.writer.take());
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            warn!("Failed to remove {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spill_round_trip() {
+        let mut file = SpillFile::create(48000, 2).unwrap();
+        file.append(&[0.1, -0.1, 0.2, -0.2]).unwrap();
+        file.append(&[0.3, -0.3]).unwrap();
+        assert_eq!(file.len(), 6);
+        assert_eq!(file.read_from(0).unwrap(), [0.1, -0.1, 0.2, -0.2, 0.3, -0.3]);
+        assert_eq!(file.read_from(3).unwrap(), [-0.2, 0.3, -0.3]);
+        assert!(file.read_from(6).unwrap().is_empty());
+
+        // Still writable after a read
+        file.append(&[0.4, -0.4]).unwrap();
+        assert_eq!(file.read_from(6).unwrap(), [0.4, -0.4]);
+
+        let path = file.path.clone();
+        drop(file);
+        assert!(!path.exists());
+    }
+}
@@ -1,8 +1,10 @@
 //! Voice Activity Detection (VAD) module
 //!
-//! Simple energy-based VAD for finding silence points in audio.
-//! Used to split audio at natural pauses instead of mid-word.
+//! Finds silence points in audio, to split it at natural pauses instead of
+//! mid-word. Speech is told apart by the Silero model when it is loaded (see
+//! `silero`), by an energy threshold otherwise.
 
+use super::silero::{self, FRAME_SAMPLES};
 use tracing::debug;
 
 /// Sample rate (fixed at 16kHz for Parakeet)
@@ -15,8 +17,12 @@ pub struct VadConfig {
     pub window_samples: usize,
     /// Step size between windows in samples
     pub step_samples: usize,
-    /// RMS threshold below which audio is considered silence
+    /// RMS threshold below which audio is considered silence (energy VAD)
     pub silence_threshold: f32,
+    /// Use the Silero model when loaded
+    pub speech_model: bool,
+    /// Speech probability below which audio is considered silence (Silero)
+    pub speech_threshold: f32,
 }
 
 impl Default for VadConfig {
@@ -25,6 +31,8 @@ impl Default for VadConfig {
             window_samples: (0.1 * SAMPLE_RATE as f32) as usize, // 100ms window
             step_samples: (0.05 * SAMPLE_RATE as f32) as usize,  // 50ms step
             silence_threshold: 0.01,                              // RMS < 0.01 = silence
+            speech_model: true,
+            speech_threshold: 0.5,
         }
     }
 }
@@ -37,6 +45,42 @@ impl VadConfig {
             ..Default::default()
         }
     }
+
+    /// Energy threshold only, even with the Silero model loaded
+    pub fn energy_only() -> Self {
+        Self {
+            speech_model: false,
+            ..Default::default()
+        }
+    }
+
+    /// Speech probability per Silero frame, when the model is used
+    fn speech(&self, samples: &[f32]) -> Option<Vec<f32>> {
+        if self.speech_model {
+            silero::speech_probabilities(samples)
+        } else {
+            None
+        }
+    }
+
+    fn is_silence(&self, rms: f32, speech: Option<f32>) -> bool {
+        match speech {
+            Some(probability) => probability < self.speech_threshold,
+            None => rms < self.silence_threshold,
+        }
+    }
+}
+
+/// Highest speech probability of the Silero frames overlapping a window
+fn window_speech(probabilities: &[f32], start: usize, len: usize) -> f32 {
+    let first = start / FRAME_SAMPLES;
+    let last = (start + len).div_ceil(FRAME_SAMPLES).min(probabilities.len());
+    probabilities
+        .get(first..last)
+        .unwrap_or_default()
+        .iter()
+        .copied()
+        .fold(0.0, f32::max)
 }
 
 /// Result of VAD analysis for a segment
@@ -63,11 +107,13 @@ pub struct VadFrame {
 pub fn analyze_audio(samples: &[f32], config: &VadConfig) -> Vec<VadFrame> {
     let mut frames = Vec::new();
     let mut pos = 0;
+    let speech = config.speech(samples);
 
     while pos + config.window_samples <= samples.len() {
         let window = &samples[pos..pos + config.window_samples];
         let rms = compute_rms(window);
-        let is_silence = rms < config.silence_threshold;
+        let probability = speech.as_ref().map(|p| window_speech(p, pos, config.window_samples));
+        let is_silence = config.is_silence(rms, probability);
 
         frames.push(VadFrame {
             start_sample: pos,
@@ -85,7 +131,8 @@ pub fn analyze_audio(samples: &[f32], config: &VadConfig) -> Vec<VadFrame> {
 /// Find the best silence point in a range of samples
 ///
 /// Returns the sample position with minimum energy (best cut point).
-/// If no silence is found, returns the position with minimum energy.
+/// If no silence is found, returns the position least likely to be speech
+/// (minimum energy without the Silero model).
 ///
 /// # Arguments
 /// * `samples` - Audio samples at 16kHz
@@ -110,24 +157,30 @@ pub fn find_best_cut_point(
 
     let mut best_pos = search_start;
     let mut best_rms = f32::MAX;
+    let mut best_score = f32::MAX;
     let mut found_silence = false;
+    let speech = config.speech(&samples[search_start..search_end]);
 
     let mut pos = search_start;
     while pos + config.window_samples <= search_end {
         let window = &samples[pos..pos + config.window_samples];
         let rms = compute_rms(window);
+        let probability = speech
+            .as_ref()
+            .map(|p| window_speech(p, pos - search_start, config.window_samples));
 
         // Prefer silence points
-        if rms < config.silence_threshold {
+        if config.is_silence(rms, probability) {
             if !found_silence || rms < best_rms {
                 best_pos = pos + config.window_samples / 2; // Center of window
                 best_rms = rms;
                 found_silence = true;
             }
-        } else if !found_silence && rms < best_rms {
-            // No silence found yet, track minimum energy
+        } else if !found_silence && probability.unwrap_or(rms) < best_score {
+            // No silence found yet, track minimum speech probability (or energy)
             best_pos = pos + config.window_samples / 2;
             best_rms = rms;
+            best_score = probability.unwrap_or(rms);
         }
 
         pos += config.step_samples;
@@ -177,6 +230,39 @@ pub fn find_silence_regions(samples: &[f32], config: &VadConfig) -> Vec<(usize,
     regions
 }
 
+/// Silence (ms) before the first and after the last speech frame
+///
+/// Returns `None` when `samples` contain no speech at all.
+pub fn edge_silence_ms(samples: &[f32], config: &VadConfig) -> Option<(i64, i64)> {
+    let frames = analyze_audio(samples, config);
+    let first = frames.iter().find(|f| !f.is_silence)?;
+    let last = frames.iter().rev().find(|f| !f.is_silence)?;
+    let to_ms = |n: usize| (n * 1000 / SAMPLE_RATE) as i64;
+    Some((to_ms(first.start_sample), to_ms(samples.len() - last.end_sample)))
+}
+
+/// Silence at the end of a recording in progress, fed block by block (16kHz)
+#[derive(Debug, Default)]
+pub struct SilenceMonitor {
+    config: VadConfig,
+    silence_ms: u64,
+}
+
+impl SilenceMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the next block; returns how long there has been no speech
+    pub fn feed(&mut self, samples: &[f32]) -> u64 {
+        match edge_silence_ms(samples, &self.config) {
+            Some((_, trailing)) => self.silence_ms = trailing as u64,
+            None => self.silence_ms += (samples.len() * 1000 / SAMPLE_RATE) as u64,
+        }
+        self.silence_ms
+    }
+}
+
 /// Compute RMS (Root Mean Square) energy of audio samples
 fn compute_rms(samples: &[f32]) -> f32 {
     if samples.is_empty() {
@@ -205,6 +291,45 @@ mod tests {
         assert!(silence_count > 0, "Should detect silence frames");
     }
 
+    #[test]
+    fn test_silence_monitor() {
+        let mut monitor = SilenceMonitor {
+            config: VadConfig::energy_only(),
+            silence_ms: 0,
+        };
+        assert_eq!(monitor.feed(&vec![0.001f32; 8000]), 500);
+        assert_eq!(monitor.feed(&vec![0.001f32; 8000]), 1000);
+
+        // Speech resets it to the silence after the speech
+        let mut block = vec![0.5f32; 4000];
+        block.extend(vec![0.001f32; 4000]);
+        let after_speech = monitor.feed(&block);
+        assert!((200..=250).contains(&after_speech), "after_speech={}", after_speech);
+        assert_eq!(monitor.feed(&vec![0.001f32; 8000]), after_speech + 500);
+    }
+
+    #[test]
+    fn test_edge_silence() {
+        let mut samples = vec![0.001f32; 8000]; // 0.5s of silence
+        samples.extend(vec![0.5f32; 16000]); // 1s of loud audio
+        samples.extend(vec![0.001f32; 16000]); // 1s of silence
+
+        let (leading, trailing) = edge_silence_ms(&samples, &VadConfig::default()).unwrap();
+        assert!((450..=500).contains(&leading), "leading={}", leading);
+        assert!((950..=1000).contains(&trailing), "trailing={}", trailing);
+        assert!(edge_silence_ms(&vec![0.0f32; 16000], &VadConfig::default()).is_none());
+    }
+
+    #[test]
+    fn test_window_speech() {
+        let probabilities = [0.1, 0.9, 0.2, 0.05];
+        // 100ms window from 0.5 frame: frames 0 to 3 overlap it
+        assert_eq!(window_speech(&probabilities, 256, 1600), 0.9);
+        assert_eq!(window_speech(&probabilities, 1024, 512), 0.2);
+        // Past the scored audio
+        assert_eq!(window_speech(&probabilities, 4096, 512), 0.0);
+    }
+
     #[test]
     fn test_find_best_cut_point() {
         // Create audio with a clear silence point
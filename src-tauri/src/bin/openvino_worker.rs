@@ -0,0 +1,77 @@
+//! OpenVINO inference worker (isolated mode, see `engine::isolated`)
+//!
+//! Started by the app with the model directory and the CPU settings (JSON) as
+//! arguments. A crash of OpenVINO only takes down this process.
+
+use std::io::{BufReader, Write};
+use std::path::PathBuf;
+use wakascribe_lib::engine::isolated::{read_request, write_reply, WorkerReply};
+use wakascribe_lib::engine::{ASREngine, OpenVinoPerformance, ParakeetEngine, ProgressSink};
+
+fn reply(reply: &WorkerReply) {
+    let mut stdout = std::io::stdout().lock();
+    if write_reply(&mut stdout, reply).is_err() {
+        // Parent gone
+        std::process::exit(0);
+    }
+}
+
+fn main() {
+    // stdout carries the protocol: logs go to stderr
+    tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::INFO)
+        .with_target(false)
+        .with_writer(std::io::stderr)
+        .init();
+
+    let Some(model_dir) = std::env::args_os().nth(1).map(PathBuf::from) else {
+        eprintln!("Usage: openvino-worker <model_dir> [performance]");
+        std::process::exit(2);
+    };
+    let performance: OpenVinoPerformance = std::env::args()
+        .nth(2)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+
+    let mut engine = ParakeetEngine::new().with_performance(performance);
+    if let Err(e) = engine.load_model(&model_dir) {
+        reply(&WorkerReply::Error { message: e.to_string() });
+        std::process::exit(1);
+    }
+    reply(&WorkerReply::Ready);
+
+    let mut stdin = BufReader::new(std::io::stdin().lock());
+    loop {
+        let (request, samples) = match read_request(&mut stdin) {
+            Ok(Some(request)) => request,
+            Ok(None) => break,
+            Err(e) => {
+                reply(&WorkerReply::Error { message: e.to_string() });
+                break;
+            }
+        };
+
+        let mut config = request.decoding_config(|chunk_count, chunk| {
+            reply(&WorkerReply::Chunk { chunk_count, chunk })
+        });
+        config.progress = Some(ProgressSink::new(|progress| reply(&WorkerReply::Progress(progress))));
+        let first_dumped = config.debug_dump.as_ref().map(|dump| dump.next_inference());
+
+        let result = ASREngine::run_inference(&engine, &samples, request.language, &config);
+        let dumped = config
+            .debug_dump
+            .as_ref()
+            .zip(first_dumped)
+            .map_or(0, |(dump, first)| dump.next_inference() - first);
+        reply(&match result {
+            Ok(text) => WorkerReply::Done {
+                text,
+                words: engine.last_word_timings(),
+                telemetry: engine.last_telemetry(),
+                dumped,
+            },
+            Err(e) => WorkerReply::Error { message: e.to_string() },
+        });
+    }
+    let _ = std::io::stdout().flush();
+}
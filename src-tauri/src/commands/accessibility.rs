@@ -0,0 +1,15 @@
+use crate::dictation;
+use crate::error::Result;
+use crate::speech;
+
+/// Speak a short status announcement (spoken feedback setting)
+#[tauri::command]
+pub fn announce(text: String) -> Result<()> {
+    speech::speak(&text)
+}
+
+/// Type a push-to-talk dictation into the focused application
+#[tauri::command]
+pub fn type_dictation(text: String) -> Result<()> {
+    dictation::type_text(&text)
+}
@@ -1,7 +1,9 @@
-use crate::audio::{resample_to_16k, write_wav, AudioCapture};
+use crate::audio::{permission, resample_to_16k, write_wav, AudioCapture, MicrophonePermission};
+use crate::commands::transcription::{finish_rolling, EngineJob};
 use crate::error::Result;
-use crate::storage::AudioDevice;
+use crate::storage::{self, AudioDevice, AudioWaveform, DeviceCapabilities, RecordingMarker, RecordingStatus};
 use std::path::PathBuf;
+use std::time::Duration;
 use tauri::{AppHandle, Manager, State};
 use tracing::info;
 
@@ -12,12 +14,58 @@ pub fn list_audio_devices() -> Result<Vec<AudioDevice>> {
     AudioCapture::list_devices()
 }
 
+/// Microphone access as decided by the OS, with guidance when it is missing
+#[tauri::command]
+pub fn check_microphone_permission() -> MicrophonePermission {
+    permission::check()
+}
+
+/// Show the system prompt if the user was never asked (waits for the answer)
+#[tauri::command]
+pub async fn request_microphone_permission() -> MicrophonePermission {
+    permission::request()
+}
+
+/// Sample rates, channels and buffer sizes of an input device (the default one without `id`)
+#[tauri::command]
+pub fn get_device_capabilities(id: Option<String>) -> Result<DeviceCapabilities> {
+    AudioCapture::device_capabilities(id.as_deref())
+}
+
+/// Devices whose audio can be recorded along with the microphone (call transcription)
+#[tauri::command]
+pub fn list_loopback_devices() -> Result<Vec<AudioDevice>> {
+    AudioCapture::list_loopback_devices()
+}
+
+/// Start recording; with `loopback_device_id`, the system audio too
 #[tauri::command]
 pub fn start_recording(
     state: State<'_, AudioState>,
     device_id: Option<String>,
+    loopback_device_id: Option<String>,
 ) -> Result<()> {
-    state.0.start(device_id.as_deref())
+    storage::ensure_writable()?;
+    let settings = storage::store()?.get_settings()?;
+    state
+        .0
+        .set_ducking(settings.duck_output.then_some(settings.duck_output_level));
+    state.0.set_agc(settings.capture_agc);
+    state.0.set_input_config(
+        settings
+            .input_configs
+            .get(device_id.as_deref().unwrap_or(""))
+            .copied()
+            .unwrap_or_default(),
+    );
+    state.0.set_max_duration(
+        (settings.max_recording_minutes > 0)
+            .then(|| Duration::from_secs(settings.max_recording_minutes as u64 * 60)),
+    );
+    state.0.set_auto_stop(
+        (settings.auto_stop_silence_secs > 0).then(|| Duration::from_secs(settings.auto_stop_silence_secs as u64)),
+    );
+    state.0.start_dual(device_id.as_deref(), loopback_device_id.as_deref())
 }
 
 #[tauri::command]
@@ -35,14 +83,35 @@ pub fn get_audio_level(state: State<'_, AudioState>) -> f32 {
     state.0.get_audio_level()
 }
 
+/// Level history of the current recording (one call per frame of a scrolling waveform)
+#[tauri::command]
+pub fn get_audio_waveform(state: State<'_, AudioState>) -> AudioWaveform {
+    state.0.waveform()
+}
+
+/// Elapsed time, pause state and buffer size of the current recording
+#[tauri::command]
+pub fn get_recording_status(state: State<'_, AudioState>) -> RecordingStatus {
+    state.0.status()
+}
+
+/// Flag the current moment of the recording; kept with the resulting transcription
+#[tauri::command]
+pub fn add_recording_marker(state: State<'_, AudioState>, label: String) -> Result<RecordingMarker> {
+    state.0.add_marker(&label)
+}
+
 #[tauri::command]
 pub fn stop_recording_to_wav(
     app: AppHandle,
     state: State<'_, AudioState>,
 ) -> Result<String> {
     // Stop recording and get samples
+    let _job = EngineJob::begin(&app);
     let samples = state.0.stop()?;
     let sample_rate = state.0.sample_rate();
+    // Saved as is: blocks transcribed while recording are not needed
+    let _ = finish_rolling();
 
     info!(
         "Recording stopped: {} samples at {}Hz",
@@ -1,27 +1,95 @@
 use crate::error::{AppError, Result};
-use crate::export;
-use crate::storage;
+use crate::export::{self, SubtitleOptions, TranscriptFormat};
+use crate::provenance::{self, ExportVerification, Provenance};
+use crate::storage::{self, Transcription};
 use std::path::PathBuf;
 use tauri_plugin_clipboard_manager::ClipboardExt;
 
 #[tauri::command]
 pub fn export_to_txt(id: String, path: String) -> Result<()> {
-    let transcription = storage::with_db(|conn| {
-        storage::get_transcription(conn, &id)?
-            .ok_or_else(|| AppError::NotFound(format!("Transcription not found: {}", id)))
-    })?;
+    let transcription = load(&id)?;
 
-    export::export_to_txt(&transcription, &PathBuf::from(path))
+    let provenance = provenance_of(&transcription)?;
+    export::export_to_txt(&transcription, &PathBuf::from(path), provenance.as_ref())
 }
 
 #[tauri::command]
 pub fn export_to_docx(id: String, path: String) -> Result<()> {
-    let transcription = storage::with_db(|conn| {
-        storage::get_transcription(conn, &id)?
-            .ok_or_else(|| AppError::NotFound(format!("Transcription not found: {}", id)))
-    })?;
+    let transcription = load(&id)?;
 
-    export::export_to_docx(&transcription, &PathBuf::from(path))
+    let provenance = provenance_of(&transcription)?;
+    export::export_to_docx(&transcription, &PathBuf::from(path), provenance.as_ref())
+}
+
+fn load(id: &str) -> Result<Transcription> {
+    storage::store()?
+        .get_transcription(id)?
+        .ok_or_else(|| AppError::NotFound(format!("Transcription not found: {}", id)))
+}
+
+/// Provenance to embed, when the setting is on
+fn provenance_of(transcription: &Transcription) -> Result<Option<Provenance>> {
+    let settings = storage::store()?.get_settings()?;
+    Ok(settings.export_provenance.then(|| Provenance::of(transcription)))
+}
+
+/// Markdown for note-taking apps: metadata table, text, blockquoted segments
+#[tauri::command]
+pub fn export_to_markdown(id: String, path: String) -> Result<()> {
+    let transcription = load(&id)?;
+
+    let provenance = provenance_of(&transcription)?;
+    export::export_to_markdown(&transcription, &PathBuf::from(path), provenance.as_ref())
+}
+
+/// The whole transcription as stored (segments, words, confidences), for scripts
+#[tauri::command]
+pub fn export_to_json(id: String, path: String) -> Result<()> {
+    let transcription = load(&id)?;
+
+    export::export_to_json(&transcription, &PathBuf::from(path))
+}
+
+/// Check the provenance metadata of an exported TXT or DOCX against the stored audio hash
+#[tauri::command]
+pub fn verify_export(path: String) -> Result<ExportVerification> {
+    provenance::verify_export(&PathBuf::from(path))
+}
+
+/// Subtitles from the segments (default layout: 42 characters per line, 6 s per cue)
+#[tauri::command]
+pub fn export_to_srt(id: String, path: String, options: Option<SubtitleOptions>) -> Result<()> {
+    let transcription = load(&id)?;
+
+    export::export_to_srt(&transcription, &PathBuf::from(path), &options.unwrap_or_default())
+}
+
+#[tauri::command]
+pub fn export_to_vtt(id: String, path: String, options: Option<SubtitleOptions>) -> Result<()> {
+    let transcription = load(&id)?;
+
+    export::export_to_vtt(&transcription, &PathBuf::from(path), &options.unwrap_or_default())
+}
+
+/// Transcript JSON read by other tools (Whisper verbose_json, word list)
+#[tauri::command]
+pub fn export_transcript_json(id: String, path: String, format: TranscriptFormat) -> Result<()> {
+    let transcription = load(&id)?;
+
+    export::export_transcript_json(&transcription, &PathBuf::from(path), format)
+}
+
+/// Static HTML site of the whole history; returns the number of transcriptions
+#[tauri::command]
+pub fn export_archive_html(dir: String) -> Result<usize> {
+    let store = storage::store()?;
+    let transcriptions = store
+        .list_transcriptions(None, None)?
+        .into_iter()
+        .filter_map(|summary| store.get_transcription(&summary.id).transpose())
+        .collect::<Result<Vec<_>>>()?;
+
+    export::export_archive_html(&transcriptions, &PathBuf::from(dir))
 }
 
 #[tauri::command]
@@ -1,22 +1,274 @@
-use crate::error::Result;
-use crate::storage::{self, Transcription};
+use crate::engine::TranscriptionLanguage;
+use crate::error::{AppError, Result};
+use crate::export::{self, TranscriptFormat};
+use crate::postprocess;
+use crate::recordings;
+use crate::storage::{
+    self, ActivityDay, HistoryCursor, SearchFilters, SearchHit, Segment, SummaryStyle, TextDraft, TextUpdate, Transcription,
+    TranscriptionNotes, TranscriptionRevision, TranscriptionSummary, TranscriptionText, Translation,
+};
+use std::path::Path;
+use tracing::warn;
 
+/// Metadata and preview of each transcription, newest first; full content via
+/// `get_transcription_text`, segments via `get_transcription_segments`
+///
+/// With `limit`, one page; the next one starts `after` its last transcription.
 #[tauri::command]
-pub fn list_transcriptions() -> Result<Vec<Transcription>> {
-    storage::with_db(|conn| storage::list_transcriptions(conn))
+pub fn list_transcriptions(
+    limit: Option<usize>,
+    after: Option<HistoryCursor>,
+) -> Result<Vec<TranscriptionSummary>> {
+    storage::store()?.list_transcriptions(limit, after.as_ref())
 }
 
+#[tauri::command]
+pub fn get_transcription_segments(id: String) -> Result<Vec<Segment>> {
+    storage::store()?.get_segments(&id)
+}
+
+/// Transcriptions and minutes per day of `year`, for the history heat map
+#[tauri::command]
+pub fn get_activity_calendar(year: i32) -> Result<Vec<ActivityDay>> {
+    storage::store()?.get_activity_calendar(year)
+}
+
+/// Full-text search over the history, best matches first
+#[tauri::command]
+pub fn search_transcriptions(query: String, filters: Option<SearchFilters>) -> Result<Vec<SearchHit>> {
+    storage::store()?.search_transcriptions(&query, &filters.unwrap_or_default())
+}
+
+#[tauri::command]
+pub fn get_transcription_text(id: String) -> Result<TranscriptionText> {
+    storage::store()?
+        .get_transcription_text(&id)?
+        .ok_or_else(|| AppError::NotFound(format!("Transcription not found: {}", id)))
+}
+
+/// Add a transcript exported by another tool to the history (format guessed when None)
+#[tauri::command]
+pub fn import_transcript_json(path: String, format: Option<TranscriptFormat>) -> Result<Transcription> {
+    storage::ensure_writable()?;
+    let transcription = export::import_transcript_json(Path::new(&path), format)?;
+    storage::store()?.insert_transcription(&transcription)?;
+    Ok(transcription)
+}
+
+/// Move a transcription to the trash (purged after `trash_retention_days`)
+#[tauri::command]
+pub fn trash_transcription(id: String) -> Result<()> {
+    storage::ensure_writable()?;
+    storage::store()?.trash_transcription(&id)
+}
+
+#[tauri::command]
+pub fn restore_transcription(id: String) -> Result<()> {
+    storage::ensure_writable()?;
+    storage::store()?.restore_transcription(&id)
+}
+
+#[tauri::command]
+pub fn list_trash() -> Result<Vec<TranscriptionSummary>> {
+    storage::store()?.list_trash()
+}
+
+/// Delete the trashed transcriptions for good; returns how many were deleted
+#[tauri::command]
+pub fn purge_trash() -> Result<usize> {
+    storage::ensure_writable()?;
+    let purged = storage::store()?.purge_trash()?;
+    remove_orphan_recordings();
+    Ok(purged)
+}
+
+/// Delete a transcription for good, without going through the trash
 #[tauri::command]
 pub fn delete_transcription(id: String) -> Result<()> {
-    storage::with_db(|conn| storage::delete_transcription(conn, &id))
+    storage::ensure_writable()?;
+    storage::store()?.delete_transcription(&id)?;
+    remove_orphan_recordings();
+    Ok(())
+}
+
+/// Delete the kept recordings of the transcriptions just deleted
+fn remove_orphan_recordings() {
+    if let Err(e) = storage::with_db(recordings::remove_orphans) {
+        warn!("Failed to delete recordings: {}", e);
+    }
+}
+
+/// Kept recording of a transcription, None when there is none (or its file is gone)
+#[tauri::command]
+pub fn get_recording_audio_path(id: String) -> Result<Option<String>> {
+    let transcription = storage::store()?
+        .get_transcription(&id)?
+        .ok_or_else(|| AppError::NotFound(format!("Transcription not found: {}", id)))?;
+    Ok(transcription.audio_path.filter(|p| Path::new(p).is_file()))
 }
 
+/// Delete the kept recording of a transcription, keeping its text
 #[tauri::command]
-pub fn update_transcription_text(id: String, edited_text: String) -> Result<()> {
-    storage::with_db(|conn| storage::update_transcription_text(conn, &id, &edited_text))
+pub fn delete_recording_audio(id: String) -> Result<()> {
+    storage::ensure_writable()?;
+    let store = storage::store()?;
+    let transcription = store
+        .get_transcription(&id)?
+        .ok_or_else(|| AppError::NotFound(format!("Transcription not found: {}", id)))?;
+    if let Some(path) = transcription.audio_path {
+        store.set_audio_path(&id, None)?;
+        recordings::remove(Path::new(&path));
+    }
+    Ok(())
+}
+
+/// Save the edited text; actions and decisions are extracted again from it
+///
+/// `expected_updated_at` is the `updated_at` the editor loaded: when the
+/// transcription was saved since (another window, sync), the save fails
+/// with a conflict error unless `force` is set.
+#[tauri::command]
+pub fn update_transcription_text(
+    id: String,
+    edited_text: String,
+    expected_updated_at: String,
+    force: Option<bool>,
+) -> Result<TextUpdate> {
+    storage::ensure_writable()?;
+    let action_items = postprocess::extract_action_items(&edited_text);
+    let expected = (!force.unwrap_or(false)).then_some(expected_updated_at.as_str());
+    let updated_at = storage::store()?.update_transcription_text(&id, &edited_text, &action_items, expected)?;
+    Ok(TextUpdate {
+        action_items,
+        updated_at,
+    })
+}
+
+/// Keep the text being edited until it is saved (called by the editor a few
+/// seconds after the last change; no revision is created). `base_updated_at`
+/// is the version the edit started from, so a restored draft can tell whether
+/// the transcription changed in between
+#[tauri::command]
+pub fn save_draft(id: String, text: String, base_updated_at: String) -> Result<TextDraft> {
+    storage::ensure_writable()?;
+    storage::store()?.save_text_draft(&id, &text, &base_updated_at)
+}
+
+/// Draft left by an editing session that did not save, to restore when the
+/// transcription is opened; a draft matching the saved text is dropped
+#[tauri::command]
+pub fn get_text_draft(id: String) -> Result<Option<TextDraft>> {
+    let store = storage::store()?;
+    let Some(draft) = store.get_text_draft(&id)? else {
+        return Ok(None);
+    };
+    let saved = store
+        .get_transcription_text(&id)?
+        .map(|t| t.edited_text.unwrap_or(t.raw_text));
+    if saved.is_some_and(|text| text != draft.text) {
+        return Ok(Some(draft));
+    }
+    if !storage::is_read_only() {
+        store.delete_text_draft(&id)?;
+    }
+    Ok(None)
+}
+
+#[tauri::command]
+pub fn discard_text_draft(id: String) -> Result<()> {
+    storage::ensure_writable()?;
+    storage::store()?.delete_text_draft(&id)
+}
+
+/// Saved texts of a transcription, newest first, ending with revision 0 (the transcribed text)
+#[tauri::command]
+pub fn get_revision_history(id: String) -> Result<Vec<TranscriptionRevision>> {
+    storage::store()?.get_revision_history(&id)
+}
+
+/// Save the text of an earlier revision as a new one (so the revert can be undone too)
+#[tauri::command]
+pub fn revert_to_revision(id: String, revision: i64) -> Result<TextUpdate> {
+    storage::ensure_writable()?;
+    let store = storage::store()?;
+    let text = store
+        .get_revision_history(&id)?
+        .into_iter()
+        .find(|r| r.revision == revision)
+        .map(|r| r.text)
+        .ok_or_else(|| AppError::NotFound(format!("Revision {} of {} not found", revision, id)))?;
+    let action_items = postprocess::extract_action_items(&text);
+    let updated_at = store.update_transcription_text(&id, &text, &action_items, None)?;
+    Ok(TextUpdate {
+        action_items,
+        updated_at,
+    })
+}
+
+/// Rename a transcription and correct its source name and language (code of a
+/// supported language, e.g. "fr"); a blank title or source name clears it.
+/// Returns the new `updated_at`, the base of the next text save
+#[tauri::command]
+pub fn update_transcription_metadata(
+    id: String,
+    title: Option<String>,
+    source_name: Option<String>,
+    language: String,
+) -> Result<String> {
+    storage::ensure_writable()?;
+    let language = TranscriptionLanguage::from_locale(&language)
+        .and_then(|l| l.code())
+        .ok_or_else(|| AppError::InvalidInput(format!("Unsupported language: {}", language)))?;
+    storage::store()?.update_metadata(&id, title.as_deref(), source_name.as_deref(), language)
+}
+
+/// Translate a transcription into `target_lang` and keep the translation next
+/// to the original text (replacing an earlier one in the same language)
+#[tauri::command]
+pub async fn translate_transcription(id: String, target_lang: String) -> Result<Translation> {
+    storage::ensure_writable()?;
+    let store = storage::store()?;
+    let transcription = store
+        .get_transcription(&id)?
+        .ok_or_else(|| AppError::NotFound(format!("Transcription not found: {}", id)))?;
+    let settings = store.get_settings()?.translation;
+
+    let translation = tauri::async_runtime::spawn_blocking(move || {
+        postprocess::translation::translate(&transcription, &target_lang, &settings)
+    })
+    .await
+    .map_err(|e| AppError::Network(e.to_string()))??;
+
+    store.save_translation(&id, &translation)?;
+    Ok(translation)
+}
+
+/// Have the configured LLM write a summary, key points and actions in `style`,
+/// kept with the transcription (replacing earlier notes in the same style)
+#[tauri::command]
+pub async fn summarize_transcription(id: String, style: Option<SummaryStyle>) -> Result<TranscriptionNotes> {
+    storage::ensure_writable()?;
+    let store = storage::store()?;
+    let transcription = store
+        .get_transcription(&id)?
+        .ok_or_else(|| AppError::NotFound(format!("Transcription not found: {}", id)))?;
+    let settings = store.get_settings()?.llm;
+    let style = style.unwrap_or_default();
+
+    let notes = tauri::async_runtime::spawn_blocking(move || {
+        postprocess::summary::summarize(&transcription, style, &settings)
+    })
+    .await
+    .map_err(|e| AppError::Network(e.to_string()))??;
+
+    store.save_notes(&id, &notes)?;
+    Ok(notes)
 }
 
 #[tauri::command]
 pub fn delete_all_transcriptions() -> Result<()> {
-    storage::with_db(|conn| storage::delete_all_transcriptions(conn))
+    storage::ensure_writable()?;
+    storage::store()?.delete_all_transcriptions()?;
+    remove_orphan_recordings();
+    Ok(())
 }
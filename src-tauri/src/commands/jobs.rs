@@ -0,0 +1,8 @@
+use crate::error::Result;
+use crate::storage::{self, JobError};
+
+/// Failures of background jobs, most recent first
+#[tauri::command]
+pub fn get_recent_errors(limit: Option<usize>) -> Result<Vec<JobError>> {
+    storage::with_db(|conn| storage::list_recent_job_errors(conn, limit.unwrap_or(50)))
+}
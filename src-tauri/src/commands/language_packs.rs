@@ -0,0 +1,47 @@
+use crate::error::{AppError, Result};
+use crate::postprocess::language_pack::{self, LanguagePackInfo};
+use crate::storage;
+
+#[tauri::command]
+pub fn list_language_packs() -> Result<Vec<LanguagePackInfo>> {
+    let settings = storage::store()?.get_settings()?;
+    Ok(language_pack::list_packs(&settings.enabled_language_packs))
+}
+
+/// Download (or update) a language pack from the URL of its `pack.json`
+#[tauri::command]
+pub async fn download_language_pack(url: String) -> Result<LanguagePackInfo> {
+    storage::ensure_writable()?;
+
+    let pack = tauri::async_runtime::spawn_blocking(move || language_pack::download_pack(&url))
+        .await
+        .map_err(|e| AppError::Network(e.to_string()))??;
+
+    let settings = storage::store()?.get_settings()?;
+    Ok(LanguagePackInfo {
+        enabled: settings.enabled_language_packs.contains(&pack.language),
+        language: pack.language,
+        name: pack.name,
+        version: pack.version,
+        assets: pack.assets,
+    })
+}
+
+/// Enable or disable an installed pack for the active profile
+#[tauri::command]
+pub fn set_language_pack_enabled(language: String, enabled: bool) -> Result<()> {
+    storage::ensure_writable()?;
+
+    if enabled {
+        // Refuse to enable a pack that isn't installed (or is unreadable)
+        language_pack::load_pack(&language)?;
+    }
+
+    let store = storage::store()?;
+    let mut settings = store.get_settings()?;
+    settings.enabled_language_packs.retain(|l| l != &language);
+    if enabled {
+        settings.enabled_language_packs.push(language);
+    }
+    store.update_settings(&settings)
+}
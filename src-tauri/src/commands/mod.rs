@@ -1,17 +1,35 @@
+pub mod accessibility;
 pub mod audio;
 pub mod export;
 pub mod history;
+pub mod jobs;
+pub mod language_packs;
+pub mod models;
+pub mod profiles;
 pub mod settings;
+pub mod support;
+pub mod sync;
+pub mod tags;
 pub mod transcription;
 
 // Module de test - commenter cette ligne pour désactiver
 pub mod test_transcription;
+pub mod test_audio;
 
+pub use accessibility::*;
 pub use audio::*;
 pub use export::*;
 pub use history::*;
+pub use jobs::*;
+pub use language_packs::*;
+pub use models::*;
+pub use profiles::*;
 pub use settings::*;
+pub use support::*;
+pub use sync::*;
+pub use tags::*;
 pub use transcription::*;
 
 // Export test - commenter cette ligne pour désactiver
 pub use test_transcription::*;
+pub use test_audio::*;
@@ -0,0 +1,74 @@
+use crate::engine::model_cache::{self, ModelCacheInfo};
+use crate::error::{AppError, Result};
+use crate::models::{self, AvailableModel, ModelStatus};
+use crate::storage;
+use tauri::AppHandle;
+
+/// Models of the catalog set in the settings, with their download status
+#[tauri::command]
+pub async fn list_available_models() -> Result<Vec<AvailableModel>> {
+    let url = catalog_url()?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let catalog = models::catalog(&url, true)?;
+        Ok(catalog
+            .models
+            .into_iter()
+            .map(|entry| AvailableModel {
+                size: entry.size(),
+                status: models::status(&entry),
+                entry,
+            })
+            .collect())
+    })
+    .await
+    .map_err(|e| AppError::Network(e.to_string()))?
+}
+
+/// Download (or resume) a model and install it; progress arrives as `model-download-progress`
+#[tauri::command]
+pub async fn download_model(app: AppHandle, model_id: String) -> Result<ModelStatus> {
+    let url = catalog_url()?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let entry = models::find_model(&url, &model_id)?;
+        models::download_model(&app, &entry)
+    })
+    .await
+    .map_err(|e| AppError::Network(e.to_string()))?
+}
+
+/// Stop a running download; the next `download_model` resumes it
+#[tauri::command]
+pub fn cancel_download(model_id: String) -> Result<()> {
+    models::cancel_download(&model_id)
+}
+
+#[tauri::command]
+pub async fn get_model_status(model_id: String) -> Result<ModelStatus> {
+    let url = catalog_url()?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let entry = models::find_model(&url, &model_id)?;
+        Ok(models::status(&entry))
+    })
+    .await
+    .map_err(|e| AppError::Network(e.to_string()))?
+}
+
+/// Location and size of the OpenVINO compiled-model cache
+#[tauri::command]
+pub fn get_model_cache_info() -> ModelCacheInfo {
+    model_cache::info()
+}
+
+/// Delete the compiled OpenVINO models; the next load compiles them again
+/// (slower start once). Returns the bytes freed
+#[tauri::command]
+pub fn clear_model_cache() -> Result<u64> {
+    model_cache::clear()
+}
+
+fn catalog_url() -> Result<String> {
+    storage::store()?.get_settings()?
+        .model_catalog_url
+        .filter(|url| !url.trim().is_empty())
+        .ok_or_else(|| AppError::InvalidInput("No model catalog URL configured".into()))
+}
@@ -0,0 +1,20 @@
+use crate::error::Result;
+use crate::storage::{self, Profile, Settings};
+
+#[tauri::command]
+pub fn list_profiles() -> Result<Vec<Profile>> {
+    storage::with_db(|conn| storage::list_profiles(conn))
+}
+
+#[tauri::command]
+pub fn create_profile(name: String) -> Result<Profile> {
+    storage::ensure_writable()?;
+    storage::with_db(|conn| storage::create_profile(conn, &name))
+}
+
+/// Switch the active profile and return its settings
+#[tauri::command]
+pub fn switch_profile(id: String) -> Result<Settings> {
+    storage::ensure_writable()?;
+    storage::with_db(|conn| storage::switch_profile(conn, &id))
+}
@@ -1,12 +1,103 @@
+use crate::dictation;
+use crate::engine::profiles::{self, DecodingProfile};
 use crate::error::Result;
-use crate::storage::{self, Settings};
+use crate::power::{self, PowerSource};
+use crate::storage::{self, AppMode, BackupImport, Settings};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
 
 #[tauri::command]
 pub fn get_settings() -> Result<Settings> {
-    storage::with_db(|conn| storage::get_settings(conn))
+    let mut settings = storage::store()?.get_settings()?;
+    settings.database_path = storage::database_path().map(|p| p.to_string_lossy().into_owned());
+    Ok(settings)
 }
 
 #[tauri::command]
-pub fn update_settings(settings: Settings) -> Result<()> {
-    storage::with_db(|conn| storage::update_settings(conn, &settings))
+pub fn update_settings(app: AppHandle, settings: Settings) -> Result<()> {
+    storage::ensure_writable()?;
+    storage::store()?.update_settings(&settings)?;
+    dictation::register_push_to_talk(&app, &settings.shortcuts.push_to_talk)
+}
+
+/// Report how the app was started (read-only review mode or normal)
+#[tauri::command]
+pub fn get_app_mode() -> AppMode {
+    AppMode {
+        read_only: storage::is_read_only(),
+    }
+}
+
+/// Move the database to another folder or file (`None`: back to the app data directory)
+///
+/// Returns the new database file.
+#[tauri::command]
+pub fn move_database(path: Option<String>) -> Result<String> {
+    let moved = storage::move_database(path.as_deref().map(Path::new))?;
+    Ok(moved.to_string_lossy().into_owned())
+}
+
+/// Whether the machine is on AC or battery (for the low-power mode setting)
+#[tauri::command]
+pub fn get_power_source() -> PowerSource {
+    power::power_source()
+}
+
+/// Save settings, substitution rules, vocabulary and shortcuts to a preset file
+#[tauri::command]
+pub fn export_preset(path: String) -> Result<()> {
+    storage::with_db(|conn| storage::export_preset(conn, &PathBuf::from(path)))
+}
+
+/// Load a preset file, replacing the current settings
+#[tauri::command]
+pub fn import_preset(app: AppHandle, path: String) -> Result<Settings> {
+    storage::ensure_writable()?;
+    let settings = storage::with_db(|conn| storage::import_preset(conn, &PathBuf::from(path)))?;
+    dictation::register_push_to_talk(&app, &settings.shortcuts.push_to_talk)?;
+    Ok(settings)
+}
+
+/// Back up the settings and history to one file; returns the number of transcriptions saved
+#[tauri::command]
+pub fn export_database(path: String) -> Result<usize> {
+    storage::with_db(|conn| storage::export_database(conn, &PathBuf::from(path)))
+}
+
+/// Restore a backup: its transcriptions are added to the history, its settings replace the current ones
+#[tauri::command]
+pub fn import_database(app: AppHandle, path: String) -> Result<BackupImport> {
+    storage::ensure_writable()?;
+    let report = storage::with_db(|conn| storage::import_database(conn, &PathBuf::from(path)))?;
+    dictation::register_push_to_talk(&app, &report.settings.shortcuts.push_to_talk)?;
+    Ok(report)
+}
+
+/// Decoding profiles of `backend` (default: the configured one), built-in first
+#[tauri::command]
+pub fn list_decoding_profiles(backend: Option<String>) -> Result<Vec<DecodingProfile>> {
+    let settings = storage::store()?.get_settings()?;
+    let backend = backend.unwrap_or(settings.engine_backend);
+    Ok(profiles::profiles_for(&backend, &settings.decoding_profiles))
+}
+
+/// Save a decoding profile and select it for its backend; on the configured
+/// backend, its parameters become the default ones. Returns the settings.
+#[tauri::command]
+pub fn save_decoding_profile(profile: DecodingProfile) -> Result<Settings> {
+    storage::ensure_writable()?;
+    let store = storage::store()?;
+    let mut settings = store.get_settings()?;
+
+    let profile = profiles::save_profile(&mut settings.decoding_profiles, profile)?;
+    settings
+        .selected_decoding_profiles
+        .insert(profile.backend.clone(), profile.name.clone());
+    if profile.backend == settings.engine_backend {
+        settings.transcription.beam_width = profile.beam_width;
+        settings.transcription.temperature = profile.temperature;
+        settings.transcription.blank_penalty = profile.blank_penalty;
+    }
+    store.update_settings(&settings)?;
+    Ok(settings)
 }
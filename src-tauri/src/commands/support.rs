@@ -0,0 +1,75 @@
+use crate::commands::{EngineState, EngineStatusState, ModelPathState};
+use crate::error::{AppError, Result};
+use crate::logs::{self, LogEntry, LogLevel};
+use crate::storage;
+use crate::support::{self, AppStatus, ModelManifest};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, State};
+
+/// Log events kept in memory, at `level` (default info) or more severe, oldest first
+#[tauri::command]
+pub fn get_recent_logs(level: Option<LogLevel>, limit: Option<usize>) -> Vec<LogEntry> {
+    logs::recent(level.unwrap_or(LogLevel::Info), limit.unwrap_or(200))
+}
+
+/// Support bundle in the downloads folder (or `path`); returns its path
+#[tauri::command]
+pub async fn export_diagnostics_bundle(
+    app: AppHandle,
+    engine_state: State<'_, EngineState>,
+    status_state: State<'_, EngineStatusState>,
+    model_path_state: State<'_, ModelPathState>,
+    path: Option<String>,
+) -> Result<String> {
+    let path = match path {
+        Some(path) => path,
+        None => {
+            let dir = app.path().download_dir().unwrap_or_else(|_| std::env::temp_dir());
+            let name = format!("wakascribe-diagnostics-{}.zip", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+            dir.join(name).to_string_lossy().into_owned()
+        }
+    };
+    create_support_bundle(engine_state, status_state, model_path_state, path).await
+}
+
+/// Zip logs, status, redacted settings, model manifest and last failure into `path`
+// async: the engine lock may be held by a running transcription
+#[tauri::command]
+pub async fn create_support_bundle(
+    engine_state: State<'_, EngineState>,
+    status_state: State<'_, EngineStatusState>,
+    model_path_state: State<'_, ModelPathState>,
+    path: String,
+) -> Result<String> {
+    let (status, manifest) = {
+        let engine = engine_state.0.lock();
+        let backend = engine.backend();
+        let status = AppStatus {
+            created_at: chrono::Utc::now().to_rfc3339(),
+            app_version: env!("CARGO_PKG_VERSION"),
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            read_only: storage::is_read_only(),
+            engine_backend: backend.display_name().to_string(),
+            engine_status: status_state.0.status(),
+            engine_loaded: engine.is_loaded(),
+            last_telemetry: engine.last_telemetry(),
+        };
+        let manifest = ModelManifest::scan(
+            &model_path_state.0.join(backend.model_subdir()),
+            engine.is_loaded(),
+        );
+        (status, manifest)
+    };
+    let settings = storage::store().and_then(|store| store.get_settings()).ok();
+
+    let path = PathBuf::from(path);
+    let written = path.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        support::create_bundle(&path, &status, settings.as_ref(), &manifest)
+    })
+    .await
+    .map_err(|e| AppError::Export(e.to_string()))??;
+
+    Ok(written.to_string_lossy().into_owned())
+}
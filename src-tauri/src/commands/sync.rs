@@ -0,0 +1,16 @@
+use crate::error::{AppError, Result};
+use crate::sync::{self, SyncStatus};
+use tauri::AppHandle;
+
+/// Sync the history with the configured remote; progress is sent as `sync-status` events
+#[tauri::command]
+pub async fn sync_now(app: AppHandle) -> Result<SyncStatus> {
+    tauri::async_runtime::spawn_blocking(move || sync::sync_now(&app))
+        .await
+        .map_err(|e| AppError::InvalidState(e.to_string()))?
+}
+
+#[tauri::command]
+pub fn get_sync_status() -> SyncStatus {
+    sync::status()
+}
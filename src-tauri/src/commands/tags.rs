@@ -0,0 +1,68 @@
+use crate::error::Result;
+use crate::storage::{self, Folder, HistoryCursor, Tag, TranscriptionSummary};
+
+/// Tags of the active profile by name, with their number of transcriptions
+#[tauri::command]
+pub fn list_tags() -> Result<Vec<Tag>> {
+    storage::store()?.list_tags()
+}
+
+/// Create a tag, or return the existing one with that name
+#[tauri::command]
+pub fn add_tag(name: String, color: Option<String>) -> Result<Tag> {
+    storage::ensure_writable()?;
+    storage::store()?.add_tag(&name, color.as_deref())
+}
+
+#[tauri::command]
+pub fn delete_tag(id: String) -> Result<()> {
+    storage::ensure_writable()?;
+    storage::store()?.delete_tag(&id)
+}
+
+/// Returns the new `updated_at` of the transcription (tags are synced with it)
+#[tauri::command]
+pub fn tag_transcription(id: String, tag_id: String) -> Result<String> {
+    storage::ensure_writable()?;
+    storage::store()?.tag_transcription(&id, &tag_id)
+}
+
+/// Returns the new `updated_at` of the transcription
+#[tauri::command]
+pub fn untag_transcription(id: String, tag_id: String) -> Result<String> {
+    storage::ensure_writable()?;
+    storage::store()?.untag_transcription(&id, &tag_id)
+}
+
+/// Transcriptions with a tag, newest first, paged like `list_transcriptions`
+#[tauri::command]
+pub fn list_by_tag(
+    tag_id: String,
+    limit: Option<usize>,
+    after: Option<HistoryCursor>,
+) -> Result<Vec<TranscriptionSummary>> {
+    storage::store()?.list_by_tag(&tag_id, limit, after.as_ref())
+}
+
+/// File a transcription in a folder (client, project); None takes it out.
+/// Returns the new `updated_at` of the transcription
+#[tauri::command]
+pub fn set_transcription_folder(id: String, folder: Option<String>) -> Result<String> {
+    storage::ensure_writable()?;
+    storage::store()?.set_folder(&id, folder.as_deref())
+}
+
+#[tauri::command]
+pub fn list_folders() -> Result<Vec<Folder>> {
+    storage::store()?.list_folders()
+}
+
+/// Transcriptions of a folder, newest first, paged like `list_transcriptions`
+#[tauri::command]
+pub fn list_by_folder(
+    folder: String,
+    limit: Option<usize>,
+    after: Option<HistoryCursor>,
+) -> Result<Vec<TranscriptionSummary>> {
+    storage::store()?.list_by_folder(&folder, limit, after.as_ref())
+}
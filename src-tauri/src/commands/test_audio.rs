@@ -0,0 +1,277 @@
+//! Module d'autotest de la chaîne audio (diagnostic matériel)
+//!
+//! Joue un bip de 1 kHz sur la sortie par défaut pendant l'enregistrement
+//! (boucle acoustique haut-parleur -> micro, ou périphérique de loopback
+//! s'il est choisi comme entrée), puis passe la capture par le
+//! rééchantillonnage et la normalisation comme un vrai enregistrement.
+//! Rapporte la latence et le niveau mesurés: de quoi trancher les tickets
+//! "mes enregistrements sont silencieux".
+//!
+//! Pour désactiver ce module:
+//! 1. Commenter la ligne `pub mod test_audio;` dans commands/mod.rs
+//! 2. Commenter l'enregistrement de la commande dans lib.rs
+
+use crate::audio::{normalize_audio, resample_to_16k};
+use crate::commands::AudioState;
+use crate::error::{AppError, Result};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{SampleFormat, StreamConfig};
+use serde::Serialize;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tracing::info;
+
+/// Fréquence du bip de test
+const TONE_HZ: f32 = 1000.0;
+/// Amplitude du bip (assez fort pour le micro, sans saturer les enceintes)
+const TONE_AMPLITUDE: f32 = 0.3;
+/// Durée du bip
+const TONE_MS: u64 = 1000;
+/// Silence enregistré avant le bip, pour mesurer le bruit de fond
+const LEAD_IN_MS: u64 = 400;
+/// Enregistrement après la fin du bip, pour absorber la latence
+const TAIL_MS: u64 = 600;
+/// Fenêtre d'analyse du niveau
+const FRAME_MS: usize = 10;
+/// Niveau en dessous duquel la capture est considérée silencieuse
+const SILENCE_RMS: f32 = 0.002;
+
+/// Résultat de l'autotest audio
+#[derive(Debug, Serialize)]
+pub struct AudioLoopbackResult {
+    /// Sortie utilisée pour jouer le bip
+    pub output_device: String,
+    /// Sample rate de l'entrée
+    pub input_sample_rate: u32,
+    /// Le bip a été retrouvé dans la capture
+    pub tone_detected: bool,
+    /// Délai entre l'envoi du bip et son arrivée dans la capture (approximatif)
+    pub latency_ms: Option<i64>,
+    /// RMS du bruit de fond avant le bip
+    pub noise_rms: f32,
+    /// RMS de la capture pendant le bip
+    pub captured_rms: f32,
+    /// Crête de la capture
+    pub captured_peak: f32,
+    /// RMS après rééchantillonnage à 16 kHz et normalisation
+    pub normalized_rms: f32,
+    /// Gain appliqué par la normalisation
+    pub normalization_gain: f32,
+    /// Fréquence mesurée après rééchantillonnage (1000 Hz attendus)
+    pub measured_frequency_hz: Option<f32>,
+    /// Piste de résolution quand quelque chose cloche
+    pub advice: Option<String>,
+}
+
+/// Commande Tauri d'autotest de la chaîne audio
+///
+/// Cette commande:
+/// 1. Démarre l'enregistrement sur l'entrée choisie
+/// 2. Joue un bip de 1 kHz sur la sortie par défaut
+/// 3. Arrête, rééchantillonne et normalise la capture
+/// 4. Retourne latence, niveaux et fréquence mesurés
+#[tauri::command]
+pub async fn test_audio_loopback(app: AppHandle, device_id: Option<String>) -> Result<AudioLoopbackResult> {
+    // Attentes et lecture du bip bloquantes: hors du runtime async
+    tauri::async_runtime::spawn_blocking(move || run_loopback(&app.state::<AudioState>(), device_id.as_deref()))
+        .await
+        .map_err(|e| AppError::Audio(e.to_string()))?
+}
+
+fn run_loopback(audio_state: &AudioState, device_id: Option<&str>) -> Result<AudioLoopbackResult> {
+    info!("=== TEST AUDIO LOOPBACK START ===");
+
+    audio_state.0.start(device_id)?;
+    let record_start = Instant::now();
+    thread::sleep(Duration::from_millis(LEAD_IN_MS));
+
+    let played = play_tone();
+    let tone_sent_ms = record_start.elapsed().as_millis() as usize;
+    let samples = audio_state.0.stop()?;
+    let output_device = played?;
+
+    let sample_rate = audio_state.0.sample_rate();
+    info!("Loopback capture: {} samples @ {}Hz", samples.len(), sample_rate);
+
+    let rate_per_ms = sample_rate as usize / 1000;
+    let lead_in = (LEAD_IN_MS as usize * rate_per_ms).min(samples.len());
+    let noise_rms = compute_rms(&samples[..lead_in]);
+    let onset = tone_onset(&samples, sample_rate, lead_in, noise_rms);
+
+    let tone_len = TONE_MS as usize * rate_per_ms;
+    let tone = onset.map_or(&[][..], |start| &samples[start..(start + tone_len).min(samples.len())]);
+    let captured_rms = compute_rms(tone);
+    let captured_peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    let latency_ms = onset.map(|start| (start / rate_per_ms) as i64 - tone_sent_ms as i64);
+
+    // Même chemin qu'un enregistrement: 16 kHz puis normalisation
+    let resampled = if tone.is_empty() {
+        Vec::new()
+    } else {
+        resample_to_16k(tone, sample_rate)?
+    };
+    let (normalized, normalization_gain) = normalize_audio(&resampled);
+    let normalized_rms = compute_rms(&normalized);
+    let measured_frequency_hz = onset.and(zero_crossing_frequency(&resampled, 16000));
+
+    let advice = if compute_rms(&samples) < SILENCE_RMS {
+        Some("Capture silencieuse: verifier le micro choisi, son volume et l'autorisation d'acces".to_string())
+    } else if onset.is_none() {
+        Some("Bip non entendu: monter le volume de sortie ou choisir un peripherique de loopback".to_string())
+    } else if measured_frequency_hz
+        .filter(|hz| (hz - TONE_HZ).abs() <= TONE_HZ * 0.1)
+        .is_none()
+    {
+        Some("Frequence deformee: verifier le sample rate du peripherique d'entree".to_string())
+    } else {
+        None
+    };
+
+    info!(
+        "Loopback: detected={}, latency={:?}ms, rms={:.4}, peak={:.4}, gain={:.1}x, freq={:?}",
+        onset.is_some(),
+        latency_ms,
+        captured_rms,
+        captured_peak,
+        normalization_gain,
+        measured_frequency_hz
+    );
+    info!("=== TEST AUDIO LOOPBACK END ===");
+
+    Ok(AudioLoopbackResult {
+        output_device,
+        input_sample_rate: sample_rate,
+        tone_detected: onset.is_some(),
+        latency_ms,
+        noise_rms,
+        captured_rms,
+        captured_peak,
+        normalized_rms,
+        normalization_gain,
+        measured_frequency_hz,
+        advice,
+    })
+}
+
+/// Joue le bip sur la sortie par défaut (bloque jusqu'à la fin de la traîne)
+///
+/// Retourne le nom de la sortie utilisée.
+fn play_tone() -> Result<String> {
+    let host = cpal::default_host();
+    let device = host
+        .default_output_device()
+        .ok_or_else(|| AppError::Audio("No default output device".into()))?;
+    let name = device.name().unwrap_or_else(|_| "Unknown".to_string());
+
+    let config = device
+        .default_output_config()
+        .map_err(|e| AppError::Audio(e.to_string()))?;
+    let sample_rate = config.sample_rate().0 as f32;
+    let channels = config.channels() as usize;
+    let stream_config: StreamConfig = config.clone().into();
+    let total = (sample_rate * TONE_MS as f32 / 1000.0) as usize;
+
+    let mut position = 0usize;
+    let mut next_sample = move || {
+        let value = if position < total {
+            TONE_AMPLITUDE * (2.0 * std::f32::consts::PI * TONE_HZ * position as f32 / sample_rate).sin()
+        } else {
+            0.0
+        };
+        position += 1;
+        value
+    };
+
+    let err_fn = |err| tracing::warn!("Audio output error: {}", err);
+    let stream = match config.sample_format() {
+        SampleFormat::F32 => device.build_output_stream(
+            &stream_config,
+            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                for frame in data.chunks_mut(channels) {
+                    frame.fill(next_sample());
+                }
+            },
+            err_fn,
+            None,
+        ),
+        SampleFormat::I16 => device.build_output_stream(
+            &stream_config,
+            move |data: &mut [i16], _: &cpal::OutputCallbackInfo| {
+                for frame in data.chunks_mut(channels) {
+                    frame.fill((next_sample() * i16::MAX as f32) as i16);
+                }
+            },
+            err_fn,
+            None,
+        ),
+        format => {
+            return Err(AppError::Audio(format!("Unsupported output format: {:?}", format)));
+        }
+    }
+    .map_err(|e| AppError::Audio(e.to_string()))?;
+
+    info!("Playing {}Hz test tone on {}", TONE_HZ, name);
+    stream.play().map_err(|e| AppError::Audio(e.to_string()))?;
+    thread::sleep(Duration::from_millis(TONE_MS + TAIL_MS));
+    drop(stream);
+
+    Ok(name)
+}
+
+/// Calcule le RMS d'un signal audio
+fn compute_rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum_sq: f64 = samples.iter().map(|&s| (s as f64) * (s as f64)).sum();
+    (sum_sq / samples.len() as f64).sqrt() as f32
+}
+
+/// Début du bip: première fenêtre après `from` nettement au-dessus du bruit de fond
+fn tone_onset(samples: &[f32], sample_rate: u32, from: usize, noise_rms: f32) -> Option<usize> {
+    let frame = (sample_rate as usize * FRAME_MS / 1000).max(1);
+    let threshold = (noise_rms * 4.0).max(SILENCE_RMS);
+
+    (from..samples.len().saturating_sub(frame))
+        .step_by(frame)
+        .find(|&start| compute_rms(&samples[start..start + frame]) > threshold)
+}
+
+/// Fréquence estimée par comptage des passages à zéro
+fn zero_crossing_frequency(samples: &[f32], sample_rate: u32) -> Option<f32> {
+    if samples.len() < 2 {
+        return None;
+    }
+    let crossings = samples
+        .windows(2)
+        .filter(|pair| (pair[0] < 0.0) != (pair[1] < 0.0))
+        .count();
+    if crossings == 0 {
+        return None;
+    }
+    Some(crossings as f32 * sample_rate as f32 / (2.0 * samples.len() as f32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(hz: f32, sample_rate: u32, ms: usize) -> Vec<f32> {
+        (0..sample_rate as usize * ms / 1000)
+            .map(|i| 0.3 * (2.0 * std::f32::consts::PI * hz * i as f32 / sample_rate as f32).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_onset_and_frequency_of_delayed_tone() {
+        let mut samples = vec![0.0001; 48 * 500];
+        samples.extend(sine(TONE_HZ, 48000, 500));
+
+        let onset = tone_onset(&samples, 48000, 48 * 100, 0.0001).unwrap();
+        assert!((48 * 490..=48 * 510).contains(&onset));
+
+        let hz = zero_crossing_frequency(&sine(TONE_HZ, 16000, 500), 16000).unwrap();
+        assert!((hz - TONE_HZ).abs() < 10.0);
+    }
+}
@@ -7,15 +7,18 @@
 //! 1. Commenter la ligne `pub mod test_transcription;` dans commands/mod.rs
 //! 2. Commenter l'enregistrement de la commande dans lib.rs
 
-use crate::audio::{load_audio_file, normalize_audio, resample_to_16k};
+use crate::audio::{duration_ms, load_audio_file, normalize_audio, resample_to_16k};
 use crate::commands::EngineState;
-use crate::engine::TranscriptionLanguage;
+use crate::engine::accuracy::{self, AccuracyReport, TextNormalization};
+use crate::engine::{DecodeTelemetry, DecodingConfig, TranscriptionLanguage};
 use crate::error::{AppError, Result};
+use crate::logs::TRANSCRIPT_TARGET;
+use crate::storage::TranscriptionSource;
 use serde::Serialize;
 use std::path::PathBuf;
 use std::time::Instant;
 use tauri::State;
-use tracing::info;
+use tracing::{debug, info};
 
 /// Résultat du test de transcription avec métriques détaillées
 #[derive(Debug, Serialize)]
@@ -45,6 +48,10 @@ pub struct TestDiagnostics {
     pub original_sample_rate: u32,
     /// Nombre de tokens générés
     pub tokens_count: usize,
+    /// Statistiques du décodeur (ratio de blank, tokens/s) si le backend les fournit
+    pub decode_telemetry: Option<DecodeTelemetry>,
+    /// Le décodeur n'a produit (presque) que des blanks
+    pub blank_collapse: bool,
 }
 
 /// Trouve le fichier audio de test
@@ -137,12 +144,13 @@ pub fn test_transcription(
     let engine = engine_state.0.lock();
     let transcription = engine.transcribe(
         &normalized,
-        "test",
+        TranscriptionSource::File,
         Some("test_audio.wav".to_string()),
         TranscriptionLanguage::Auto,
         None, // Use default DecodingConfig (greedy)
     )?;
     let transcribe_time = transcribe_start.elapsed();
+    let decode_telemetry = engine.last_telemetry();
 
     let transcription_time_ms = transcribe_time.as_millis() as u64;
     let realtime_factor = transcription_time_ms as f64 / audio_duration_ms as f64;
@@ -150,7 +158,7 @@ pub fn test_transcription(
     info!("Transcription completed in {:?}", transcribe_time);
     info!("Realtime factor: {:.2}x ({}ms audio / {}ms processing)",
           1.0 / realtime_factor, audio_duration_ms, transcription_time_ms);
-    info!("Result: '{}'", transcription.raw_text);
+    debug!(target: TRANSCRIPT_TARGET, "Result: {:?}", transcription.raw_text);
 
     // Compter les tokens (approximation basée sur les espaces)
     let tokens_count = transcription.raw_text.split_whitespace().count();
@@ -168,6 +176,8 @@ pub fn test_transcription(
             audio_samples: samples.len(),
             original_sample_rate: sample_rate,
             tokens_count,
+            blank_collapse: decode_telemetry.as_ref().is_some_and(|t| t.is_blank_collapse()),
+            decode_telemetry,
         },
     })
 }
@@ -178,3 +188,62 @@ pub fn check_test_audio() -> Result<String> {
     let path = find_test_audio()?;
     Ok(path.to_string_lossy().to_string())
 }
+
+/// Résultat de `evaluate_accuracy`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccuracyEvaluation {
+    #[serde(flatten)]
+    pub accuracy: AccuracyReport,
+    /// Texte brut du moteur
+    pub text: String,
+    pub audio_duration_ms: i64,
+    pub transcription_time_ms: u64,
+}
+
+/// Transcrit un fichier (sans l'enregistrer) et calcule WER/CER par rapport
+/// à la transcription de référence, pour comparer des paramètres de décodage
+#[tauri::command]
+pub async fn evaluate_accuracy(
+    engine_state: State<'_, EngineState>,
+    audio_path: String,
+    reference_text: String,
+    config: Option<DecodingConfig>,
+    language: Option<TranscriptionLanguage>,
+    normalization: Option<TextNormalization>,
+) -> Result<AccuracyEvaluation> {
+    let path = PathBuf::from(&audio_path);
+    if !path.exists() {
+        return Err(AppError::NotFound(format!("File not found: {}", audio_path)));
+    }
+
+    let (samples, sample_rate) = load_audio_file(&path)?;
+    let audio_duration_ms = duration_ms(&samples, sample_rate);
+    let resampled = resample_to_16k(&samples, sample_rate)?;
+    let (normalized, _) = normalize_audio(&resampled);
+
+    let start = Instant::now();
+    let transcription = engine_state.0.lock().transcribe(
+        &normalized,
+        TranscriptionSource::File,
+        None,
+        language.unwrap_or(TranscriptionLanguage::Auto),
+        config,
+    )?;
+    let transcription_time_ms = start.elapsed().as_millis() as u64;
+
+    let accuracy = accuracy::evaluate(&reference_text, &transcription.raw_text, &normalization.unwrap_or_default());
+    info!(
+        "Accuracy of {:?}: WER {:.1}%, CER {:.1}%",
+        path,
+        accuracy.wer * 100.0,
+        accuracy.cer * 100.0
+    );
+
+    Ok(AccuracyEvaluation {
+        accuracy,
+        text: transcription.raw_text,
+        audio_duration_ms,
+        transcription_time_ms,
+    })
+}
@@ -0,0 +1,318 @@
+use super::queue::{engine_busy, PENDING_SWITCH};
+use crate::engine::{
+    chunk_cache, profiles, DecodingProfile, DynamicEngine, EngineBackend, EngineReadiness, EngineStatus,
+    EngineStatusReport, ExecutionProviderChoice, SupportedLanguage, TranscriptionLanguage,
+};
+use crate::error::{AppError, Result};
+use crate::storage;
+use parking_lot::{Mutex, MutexGuard};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tracing::{info, warn};
+
+/// State wrapper for the ASR engine (supports dynamic backend switching)
+pub struct EngineState(pub Mutex<DynamicEngine>);
+
+/// Second engine, for file transcriptions with the `engine_pool` setting
+/// (loaded on the first file, with the backend and model of the main one)
+pub struct BatchEngineState(pub Mutex<DynamicEngine>);
+
+/// State for the background model loading status
+pub struct EngineStatusState(pub EngineReadiness);
+
+/// State for the model base path (needed for backend switching)
+pub struct ModelPathState(pub PathBuf);
+
+/// Engine for a file transcription: the second engine with the pool (brought
+/// to the backend and model of the main one first), else the main engine
+pub(super) fn lock_file_engine(app: &AppHandle, pool: bool) -> Result<MutexGuard<'_, DynamicEngine>> {
+    let main = &app.state::<EngineState>().inner().0;
+    let batch = &app.state::<BatchEngineState>().inner().0;
+    if !pool {
+        let mut engine = batch.lock();
+        if engine.is_loaded() {
+            engine.unload();
+        }
+        return Ok(main.lock());
+    }
+
+    let (backend, model_dir) = {
+        let engine = main.lock();
+        (engine.backend(), engine.model_dir().map(Path::to_path_buf))
+    };
+    // Main engine not loaded: the transcription fails as without the pool
+    let Some(model_dir) = model_dir else {
+        return Ok(main.lock());
+    };
+    let mut engine = batch.lock();
+    if engine.backend() != backend || engine.model_dir() != Some(model_dir.as_path()) {
+        if engine.is_loaded() {
+            engine.unload();
+        }
+        info!("Loading a second {} engine for file transcriptions", backend.display_name());
+        engine.switch_backend(backend, &model_dir)?;
+    }
+    Ok(engine)
+}
+
+/// Result of `switch_engine_backend`
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendSwitch {
+    /// Display name of the requested backend
+    pub backend: String,
+    /// Recording or transcription in progress: applied when it ends ("engine-backend-switched" event)
+    pub deferred: bool,
+    /// Decoding profile chosen for this backend, whose parameters become the defaults
+    pub decoding_profile: DecodingProfile,
+}
+
+pub(super) fn model_dir_for(model_path: &Path, backend: EngineBackend) -> Result<PathBuf> {
+    let model_dir = model_path.join(backend.model_subdir());
+    if !model_dir.exists() {
+        return Err(AppError::NotFound(format!(
+            "Model directory not found for {}: {:?}",
+            backend.display_name(),
+            model_dir
+        )));
+    }
+    Ok(model_dir)
+}
+
+/// Switch to a different inference backend
+///
+/// During a recording or a transcription the switch is deferred until it
+/// ends, so the running job keeps the engine it started with.
+#[tauri::command]
+pub fn switch_engine_backend(
+    app: AppHandle,
+    engine_state: State<'_, EngineState>,
+    status_state: State<'_, EngineStatusState>,
+    model_path_state: State<'_, ModelPathState>,
+    backend: String,
+) -> Result<BackendSwitch> {
+    let backend = match backend.as_str() {
+        "openvino" => EngineBackend::OpenVINO,
+        "onnxruntime" => EngineBackend::OnnxRuntime,
+        #[cfg(target_os = "macos")]
+        "coreml" => EngineBackend::CoreML,
+        _ => return Err(AppError::InvalidInput(format!("Unknown backend: {}", backend))),
+    };
+    let model_dir = model_dir_for(&model_path_state.0, backend)?;
+    let settings = storage::store()?.get_settings()?;
+    let decoding_profile = profiles::selected_profile(
+        backend.model_subdir(),
+        &settings.decoding_profiles,
+        &settings.selected_decoding_profiles,
+    );
+
+    // Don't race the startup loader
+    if status_state.0.status() == EngineStatus::Loading {
+        return Err(AppError::InvalidState("Engine is still loading".into()));
+    }
+
+    let mut pending = PENDING_SWITCH.lock();
+    let engine = if engine_busy(&app) { None } else { engine_state.0.try_lock() };
+    let Some(mut engine) = engine else {
+        info!("Engine busy, switch to {} deferred", backend.display_name());
+        *pending = Some(backend);
+        return Ok(BackendSwitch {
+            backend: backend.display_name().to_string(),
+            deferred: true,
+            decoding_profile,
+        });
+    };
+    // A later request replaces the deferred one
+    *pending = None;
+    drop(pending);
+
+    engine.switch_backend(backend, &model_dir)?;
+    status_state.0.set_ready(&engine);
+
+    info!("Switched to {} backend", backend.display_name());
+    Ok(BackendSwitch {
+        backend: backend.display_name().to_string(),
+        deferred: false,
+        decoding_profile,
+    })
+}
+
+/// Reload the current backend's model files (e.g. after a model download or update)
+///
+/// The new engine is loaded next to the running one and swapped in once ready,
+/// so transcriptions keep working during the load and recording is not touched.
+/// An unloaded engine (`unload_engine`) is loaded in place.
+#[tauri::command]
+pub async fn reload_engine(app: AppHandle) -> Result<String> {
+    // Loading a model takes seconds: off the async runtime
+    tauri::async_runtime::spawn_blocking(move || reload(&app))
+        .await
+        .map_err(|e| AppError::InvalidState(e.to_string()))?
+}
+
+fn reload(app: &AppHandle) -> Result<String> {
+    let engine_state = app.state::<EngineState>();
+    let status_state = app.state::<EngineStatusState>();
+    if status_state.0.status() == EngineStatus::Loading {
+        return Err(AppError::InvalidState("Engine is still loading".into()));
+    }
+
+    // Unloaded: loaded in place, transcriptions wait for it meanwhile
+    if status_state.0.start_reload() {
+        let backend = engine_state.0.lock().backend();
+        if let Err(e) = load_unloaded(app) {
+            status_state.0.set(EngineStatus::Unloaded);
+            return Err(e);
+        }
+        return Ok(backend.display_name().to_string());
+    }
+
+    let backend = engine_state.0.lock().backend();
+    let model_dir = model_dir_for(&app.state::<ModelPathState>().0, backend)?;
+
+    info!("Reloading {} model from {:?}", backend.display_name(), model_dir);
+    let mut fresh = DynamicEngine::new(backend);
+    fresh.load_model(&model_dir)?;
+    status_state.0.set_ready(&fresh);
+
+    // Swap under the lock, drop the old engine after releasing it
+    let old = std::mem::replace(&mut *engine_state.0.lock(), fresh);
+    drop(old);
+    // Model files may have changed: cached chunk results are stale
+    chunk_cache::clear();
+    // The second engine loads again with the new files and settings (unless a file is running on it)
+    if let Some(mut batch) = app.state::<BatchEngineState>().0.try_lock() {
+        if batch.is_loaded() {
+            batch.unload();
+        }
+    }
+
+    let _ = app.emit("engine-ready", EngineStatus::Ready);
+
+    info!("{} engine reloaded", backend.display_name());
+    Ok(backend.display_name().to_string())
+}
+
+/// Release the model to free memory (app idle in the tray)
+///
+/// The status becomes "unloaded"; `reload_engine`, or the next transcription,
+/// loads the model again.
+#[tauri::command]
+pub fn unload_engine(
+    app: AppHandle,
+    engine_state: State<'_, EngineState>,
+    status_state: State<'_, EngineStatusState>,
+) -> Result<()> {
+    match status_state.0.status() {
+        EngineStatus::Ready => {}
+        EngineStatus::Unloaded => return Ok(()),
+        EngineStatus::Loading => return Err(AppError::InvalidState("Engine is still loading".into())),
+        EngineStatus::Unavailable => return Err(AppError::InvalidState("No model loaded".into())),
+    }
+    let engine = if engine_busy(&app) { None } else { engine_state.0.try_lock() };
+    let Some(mut engine) = engine else {
+        return Err(AppError::InvalidState("Engine is in use".into()));
+    };
+    engine.unload();
+    let mut batch = app.state::<BatchEngineState>().0.lock();
+    if batch.is_loaded() {
+        batch.unload();
+    }
+    status_state.0.set(EngineStatus::Unloaded);
+    Ok(())
+}
+
+/// Status of the engine once loaded, loading it first if it was unloaded
+/// (the first caller loads it, the others wait). Blocks: for worker threads
+/// or `spawn_blocking`, never an async command itself
+pub(super) fn wait_engine(app: &AppHandle) -> EngineStatus {
+    let status_state = app.state::<EngineStatusState>();
+    if status_state.0.start_reload() {
+        if let Err(e) = load_unloaded(app) {
+            warn!("Failed to load the unloaded engine: {}", e);
+            status_state.0.set(EngineStatus::Unloaded);
+        }
+    }
+    status_state.0.wait_loaded()
+}
+
+/// Load the model of the unloaded engine (status set to loading by `start_reload`)
+fn load_unloaded(app: &AppHandle) -> Result<()> {
+    let engine_state = app.state::<EngineState>();
+    let mut engine = engine_state.0.lock();
+    let backend = engine.backend();
+    let model_dir = model_dir_for(&app.state::<ModelPathState>().0, backend)?;
+    info!("Loading unloaded {} model from {:?}", backend.display_name(), model_dir);
+    engine.load_model(&model_dir)?;
+    app.state::<EngineStatusState>().0.set_ready(&engine);
+    let _ = app.emit("engine-ready", EngineStatus::Ready);
+    Ok(())
+}
+
+/// Get the current engine backend name
+#[tauri::command]
+pub fn get_engine_backend(engine_state: State<'_, EngineState>) -> String {
+    let engine = engine_state.0.lock();
+    engine.backend().display_name().to_string()
+}
+
+/// Execution provider of the ONNX Runtime backend, as shown in the settings
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ExecutionProviderInfo {
+    pub provider: ExecutionProviderChoice,
+    /// Whether the ONNX Runtime library includes it; unknown until that backend is loaded
+    pub available: Option<bool>,
+    /// Provider the loaded model runs on
+    pub active: bool,
+}
+
+/// Execution providers selectable on this platform
+#[tauri::command]
+pub fn get_execution_providers(engine_state: State<'_, EngineState>) -> Vec<ExecutionProviderInfo> {
+    let engine = engine_state.0.lock();
+    // Probing needs the ONNX Runtime library, only loaded with that backend
+    let loaded = engine.backend() == EngineBackend::OnnxRuntime && engine.is_loaded();
+    let active = engine.execution_provider();
+
+    let mut providers = vec![ExecutionProviderChoice::Cpu, ExecutionProviderChoice::Auto];
+    providers.extend(ExecutionProviderChoice::platform_providers());
+    providers
+        .into_iter()
+        .map(|provider| ExecutionProviderInfo {
+            provider,
+            available: loaded.then(|| provider.is_available()),
+            active: active == Some(provider),
+        })
+        .collect()
+}
+
+/// Languages the model can be forced to (besides Auto)
+#[tauri::command]
+pub fn list_supported_languages() -> Vec<SupportedLanguage> {
+    TranscriptionLanguage::supported()
+}
+
+/// Model status ("loading", "ready", "unavailable" or "unloaded") with the
+/// load time, warm-up and memory of the loaded model
+#[tauri::command]
+pub fn get_engine_status(status_state: State<'_, EngineStatusState>) -> EngineStatusReport {
+    status_state.0.report()
+}
+
+/// Run a short inference on the loaded model (waits for the startup load),
+/// so that the next transcription starts at full speed
+#[tauri::command]
+pub async fn warmup_engine(app: AppHandle) -> Result<EngineStatusReport> {
+    // Waits for (or does) the model load, then runs the model: off the async runtime
+    tauri::async_runtime::spawn_blocking(move || {
+        if wait_engine(&app) != EngineStatus::Ready {
+            return Err(AppError::InvalidState("No model loaded".into()));
+        }
+        let warmup_ms = app.state::<EngineState>().0.lock().warm_up()?;
+        let status_state = app.state::<EngineStatusState>();
+        status_state.0.set_warmed_up(warmup_ms);
+        Ok(status_state.0.report())
+    })
+    .await
+    .map_err(|e| AppError::InvalidState(e.to_string()))?
+}
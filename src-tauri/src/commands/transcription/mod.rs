@@ -0,0 +1,606 @@
+mod engine;
+mod pending;
+mod queue;
+mod rolling;
+
+pub use engine::*;
+pub use pending::*;
+pub use queue::*;
+pub use rolling::*;
+
+use crate::audio::{
+    denoise, dual, duration_ms, load_audio_file, normalize, pauses, resample_to_16k, skip_silence, DualSourceMode,
+    Normalization, SourceAudio, VadConfig,
+};
+use crate::commands::audio::AudioState;
+use crate::drafts::{self, DraftWriter};
+use crate::engine::{
+    partial::ChunkJournal, ChunkJoin, DebugDump, DecodingConfig, EngineStatus, LanguageDecoding, ProgressSink,
+    StreamingConfig, StreamingSession, TranscriptionLanguage,
+};
+use crate::error::{AppError, Result};
+use crate::hooks;
+use crate::job_queue;
+use crate::live_paste::LivePaste;
+use crate::postprocess::{self, translation::LiveTranslator};
+use crate::provenance;
+use crate::recordings::{self, PendingRecording};
+use crate::storage::{
+    self, PendingTranscription, RecordingAutoStopped, RecordingMarker, RecordingPause, Settings, Transcription,
+    TranscriptionProgress, TranscriptionSource,
+};
+use crate::support::{self, FailedJob};
+use pending::queue_file;
+use rolling::{finish_session, Rolled, RollingSession, ROLLING};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State, Window};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Complete the decoding config with the app settings: in Auto mode, favor the
+/// UI language (short French utterances are otherwise often detected as
+/// English); pace chunks on battery in low-power mode; join chunks with the
+/// configured separator and sentence gap; slow every inference down in the
+/// low-resource simulation; use the blank penalty and temperature of the language.
+/// Without a config from the caller, starts from the stored default parameters.
+fn with_settings(
+    config: Option<DecodingConfig>,
+    language: TranscriptionLanguage,
+    settings: &Settings,
+) -> DecodingConfig {
+    let mut config = config
+        .unwrap_or_else(|| settings.transcription.decoding_config())
+        .with_power_mode(settings.power_mode)
+        .with_chunk_join(ChunkJoin {
+            separator: settings.chunk_separator.clone(),
+            sentence_gap_ms: settings.sentence_gap_ms,
+        });
+    if language == TranscriptionLanguage::Auto && settings.auto_language_prior {
+        config = config.with_language_prior(TranscriptionLanguage::from_locale(&settings.language));
+    }
+    if settings.simulated_inference_delay_ms > 0 {
+        config = config.with_simulated_delay(Duration::from_millis(settings.simulated_inference_delay_ms));
+    }
+    if settings.per_language_decoding {
+        // Forced language, or the one Auto mode is biased towards
+        let effective = match language {
+            TranscriptionLanguage::Auto => config.language_prior,
+            forced => Some(forced),
+        };
+        let params = effective.and_then(|l| {
+            settings
+                .language_decoding_overrides
+                .get(&l)
+                .copied()
+                .or_else(|| LanguageDecoding::default_for(l))
+        });
+        if let Some(params) = params {
+            config = config.with_language_decoding(params);
+        }
+    }
+    config
+}
+
+/// Dump the model I/O of this transcription when the debug setting is on
+fn with_debug_dump(config: DecodingConfig, settings: &Settings) -> DecodingConfig {
+    if !settings.debug_dump {
+        return config;
+    }
+    match DebugDump::create() {
+        Ok(dump) => config.with_debug_dump(dump),
+        Err(e) => {
+            warn!("Model I/O dump disabled: {}", e);
+            config
+        }
+    }
+}
+
+/// A stopped recording, waiting for its transcription job
+struct StoppedRecording {
+    samples: Vec<f32>,
+    sample_rate: u32,
+    channels: u16,
+    loopback: Option<SourceAudio>,
+    /// Rolling transcription of the recording, joined by the job
+    rolling: Option<RollingSession>,
+    draft_id: Option<String>,
+    markers: Vec<RecordingMarker>,
+    pauses: Vec<RecordingPause>,
+    device: Option<String>,
+}
+
+/// Stop the recording and queue its transcription
+///
+/// Returns the job id at once; the transcription comes as a
+/// "transcription-job" event, or from `get_job_status`.
+#[tauri::command]
+pub async fn stop_recording(
+    app: AppHandle,
+    language: Option<TranscriptionLanguage>,
+    decoding_config: Option<DecodingConfig>,
+    normalization: Option<Normalization>,
+) -> Result<String> {
+    queue_recording(&app, language, decoding_config, normalization, None)
+}
+
+/// Stop the capture now and leave the rest to a job; `finished` gets its result
+fn queue_recording(
+    app: &AppHandle,
+    language: Option<TranscriptionLanguage>,
+    decoding_config: Option<DecodingConfig>,
+    normalization: Option<Normalization>,
+    finished: Option<Box<dyn FnOnce(&Result<Transcription>) + Send>>,
+) -> Result<String> {
+    let job = EngineJob::begin(app);
+    let audio_state = app.state::<AudioState>();
+    let samples = audio_state.0.stop()?;
+    let loopback = audio_state.0.take_loopback();
+    let device = match (audio_state.0.device_id(), loopback.as_ref().and(audio_state.0.loopback_id())) {
+        (mic, Some(system)) => Some(format!("{} + {}", mic.as_deref().unwrap_or("default"), system)),
+        (mic, None) => mic,
+    };
+    let recording = StoppedRecording {
+        samples,
+        sample_rate: audio_state.0.sample_rate(),
+        channels: audio_state.0.channels(),
+        loopback,
+        rolling: ROLLING.lock().take(),
+        // Live drafting ends here; a failed job leaves the draft in the history
+        draft_id: drafts::finish(),
+        markers: audio_state.0.take_markers(),
+        pauses: audio_state.0.take_pauses(),
+        device,
+    };
+
+    let handle = app.clone();
+    job_queue::submit(
+        app,
+        TranscriptionSource::Dictation,
+        None,
+        move || {
+            let _job = job;
+            transcribe_recording(&handle, recording, language, decoding_config, normalization)
+        },
+        finished,
+    )
+}
+
+/// Transcription job of a stopped recording
+fn transcribe_recording(
+    app: &AppHandle,
+    recording: StoppedRecording,
+    language: Option<TranscriptionLanguage>,
+    decoding_config: Option<DecodingConfig>,
+    normalization: Option<Normalization>,
+) -> Result<Transcription> {
+    let StoppedRecording { samples, sample_rate, channels, loopback, rolling, draft_id, markers, pauses, device } =
+        recording;
+    // Blocks already transcribed while recording: only the rest is left
+    let rolled = rolling.and_then(finish_session);
+    let tail_from = rolled.as_ref().map_or(0, Rolled::tail_from).min(samples.len());
+
+    // Resample to 16kHz; with system audio, both sources are brought to mono first
+    let (resampled, them) = match &loopback {
+        Some(loopback) => {
+            let mic = SourceAudio {
+                samples: samples.clone(),
+                sample_rate,
+                channels,
+            };
+            (dual::to_mono_16k(&mic)?, Some(dual::to_mono_16k(loopback)?))
+        }
+        None => (resample_to_16k(&samples[tail_from..], sample_rate)?, None),
+    };
+
+    // Use provided language or the stored default
+    let settings = storage::store()?.get_settings()?;
+    let lang = language.unwrap_or(settings.transcription.language);
+    let decoding_config = Some(with_debug_dump(with_settings(decoding_config, lang, &settings), &settings));
+    let normalization = normalization.unwrap_or(settings.transcription.normalization);
+
+    // Mixed: one signal, transcribed as usual
+    let (resampled, them) = match them {
+        Some(them) if settings.dual_source_mode == DualSourceMode::Mix => (dual::mix(&resampled, &them), None),
+        other => (resampled, other),
+    };
+
+    // Kept for playback: the whole recording, sources mixed
+    let kept = if settings.keep_recordings {
+        let saved = match (&them, tail_from) {
+            (Some(them), _) => recordings::save(&dual::mix(&resampled, them)),
+            (None, 0) => recordings::save(&resampled),
+            (None, _) => resample_to_16k(&samples, sample_rate).and_then(|all| recordings::save(&all)),
+        };
+        saved.map_err(|e| warn!("Failed to keep the recording: {}", e)).ok()
+    } else {
+        None
+    };
+
+    // Reduce noise, then normalize audio level (RMS by default)
+    let denoising = settings.transcription.denoise;
+    let (normalized, gain) = normalize(&denoise(&resampled, denoising), normalization);
+
+    // Transcribe (waits for the engine if models are still loading or unloaded)
+    wait_engine(app);
+    let engine_state = app.state::<EngineState>();
+    let engine = engine_state.0.lock();
+    let transcribe = |audio: &[f32]| {
+        engine
+            .transcribe(audio, TranscriptionSource::Dictation, None, lang, decoding_config.clone())
+            .map_err(|e| {
+                support::record_failure(FailedJob::new(
+                    TranscriptionSource::Dictation,
+                    None,
+                    &e,
+                    &engine,
+                    lang,
+                    decoding_config.as_ref(),
+                    audio.len(),
+                ));
+                e
+            })
+    };
+    let mut transcription = match rolled {
+        Some(rolled) => rolled.join(&normalized, &transcribe)?,
+        None => transcribe(&normalized)?,
+    };
+
+    // Separate: the system audio on its own, segments labelled me/them
+    if let Some(them) = them {
+        let (them, _) = normalize(&denoise(&them, denoising), normalization);
+        transcription = dual::merge_sources(transcription, transcribe(&them)?);
+    }
+    drop(engine);
+
+    transcription.original_sample_rate = Some(sample_rate);
+    transcription.normalization_gain = Some(gain);
+    transcription.markers = markers;
+    transcription.segments = pauses::insert_pause_segments(std::mem::take(&mut transcription.segments), &pauses);
+    transcription.pauses = pauses;
+    let loopback_samples = loopback.as_ref().map(|l| l.samples.as_slice());
+    transcription.capture = Some(provenance::capture_of_samples(&samples, loopback_samples, device));
+
+    // Apply user post-processing (substitution rules)
+    postprocess::apply(&mut transcription, &settings);
+
+    transcription.audio_path = kept.as_ref().map(PendingRecording::path);
+
+    // Save to database, in place of the live draft
+    storage::with_db(|conn| drafts::finalize(conn, draft_id.as_deref(), &transcription))?;
+    if let Some(kept) = kept {
+        kept.keep();
+    }
+    hooks::run_after_transcription(app, &transcription, &settings.post_transcription_hooks);
+
+    Ok(transcription)
+}
+
+/// Stop a recording left silent (auto-stop setting) and transcribe it as
+/// `stop_recording` does with the stored settings, then tell the UI
+pub async fn auto_stop_recording(app: AppHandle, silence_ms: u64) {
+    let handle = app.clone();
+    let tell = move |result: &Result<Transcription>| {
+        let (transcription, error) = match result {
+            Ok(transcription) => (Some(transcription.clone()), None),
+            Err(e) => {
+                warn!("Auto-stopped recording not transcribed: {}", e);
+                (None, Some(e.to_string()))
+            }
+        };
+        let _ = handle.emit(
+            "recording-auto-stopped",
+            RecordingAutoStopped {
+                silence_ms,
+                transcription,
+                error,
+            },
+        );
+    };
+    if let Err(e) = queue_recording(&app, None, None, None, Some(Box::new(tell))) {
+        warn!("Auto-stopped recording not transcribed: {}", e);
+        let _ = app.emit(
+            "recording-auto-stopped",
+            RecordingAutoStopped {
+                silence_ms,
+                transcription: None,
+                error: Some(e.to_string()),
+            },
+        );
+    }
+}
+
+/// Set while a live transcription runs
+static STREAMING: AtomicBool = AtomicBool::new(false);
+
+/// Live captions of the current recording, sent as "transcription-segment" events
+///
+/// Returns at once; the session ends with the recording. The saved
+/// transcription still comes from `stop_recording`, over the whole audio;
+/// meanwhile the live text is kept as a draft (see `drafts`). With a live
+/// translation language, final captions are also sent translated, as
+/// "transcription-caption" events (see `LiveTranslator`).
+#[tauri::command]
+pub fn start_streaming_transcription(
+    app: AppHandle,
+    audio_state: State<'_, AudioState>,
+    language: Option<TranscriptionLanguage>,
+    decoding_config: Option<DecodingConfig>,
+    normalization: Option<Normalization>,
+    streaming_config: Option<StreamingConfig>,
+) -> Result<()> {
+    // Live text is saved as a draft
+    storage::ensure_writable()?;
+    if !audio_state.0.is_recording() {
+        return Err(AppError::InvalidState("Not recording".into()));
+    }
+
+    let settings = storage::store()?.get_settings()?;
+    let lang = language.unwrap_or(settings.transcription.language);
+    let decoding_config = with_settings(decoding_config, lang, &settings);
+    let session = StreamingSession::new(streaming_config.unwrap_or_default());
+    let normalization = normalization.unwrap_or(settings.transcription.normalization);
+
+    if STREAMING.swap(true, Ordering::SeqCst) {
+        return Err(AppError::InvalidState("Live transcription already running".into()));
+    }
+    thread::spawn(move || {
+        if let Err(e) = run_streaming(&app, session, lang, &decoding_config, normalization, &settings) {
+            warn!("Live transcription stopped: {}", e);
+        }
+        STREAMING.store(false, Ordering::SeqCst);
+    });
+    Ok(())
+}
+
+/// Decode the pending audio every step until the recording stops
+fn run_streaming(
+    app: &AppHandle,
+    mut session: StreamingSession,
+    language: TranscriptionLanguage,
+    config: &DecodingConfig,
+    normalization: Normalization,
+    settings: &Settings,
+) -> Result<()> {
+    let audio_state = app.state::<AudioState>();
+    let engine_state = app.state::<EngineState>();
+    if wait_engine(app) != EngineStatus::Ready {
+        info!("No model loaded, live transcription disabled");
+        return Ok(());
+    }
+
+    let sample_rate = audio_state.0.sample_rate();
+    let step = Duration::from_millis(session.config().step_ms);
+    info!("Live transcription started ({}Hz capture)", sample_rate);
+    let mut draft = DraftWriter::begin(language, config.language_prior);
+    let mut live_paste = settings.live_paste.then(LivePaste::new);
+    let denoising = settings.transcription.denoise;
+    let handle = app.clone();
+    let translator = LiveTranslator::start(&settings.translation, language.code().unwrap_or("auto"), move |caption| {
+        let _ = handle.emit("transcription-caption", &caption);
+    })
+    .unwrap_or_else(|e| {
+        warn!("Live captions not translated: {}", e);
+        None
+    });
+    let mut recorded_ms = 0;
+
+    while audio_state.0.is_recording() {
+        thread::sleep(step);
+
+        // Capture samples before the committed point are never read again
+        let from = (session.committed_samples() as u64 * sample_rate as u64 / 16000) as usize;
+        let Ok(recorded) = audio_state.0.snapshot(from) else {
+            break; // Stopped meanwhile
+        };
+        let mut pending = resample_to_16k(&recorded, sample_rate)?;
+        // The resampler pads its last block with zeros
+        pending.truncate((recorded.len() as u64 * 16000 / sample_rate as u64) as usize);
+        recorded_ms = ((session.committed_samples() + pending.len()) / 16) as i64;
+        let (pending, _) = normalize(&denoise(&pending, denoising), normalization);
+
+        // The recording job has the engine: the final transcription is on its way
+        let Some(engine) = engine_state.0.try_lock() else {
+            continue;
+        };
+        if let Some(segment) = session.decode(&engine, &pending, language, config)? {
+            let _ = app.emit("transcription-segment", &segment);
+            if let Some(translator) = &translator {
+                translator.push(&segment);
+            }
+            draft.push(&segment, recorded_ms);
+            if let Some(text) = live_paste.as_mut().and_then(|p| p.push(&segment, Instant::now())) {
+                write_clipboard(app, text);
+            }
+        }
+    }
+    draft.flush(recorded_ms);
+    if let Some(text) = live_paste.as_mut().and_then(|p| p.flush(Instant::now())) {
+        write_clipboard(app, text);
+    }
+    if let Some(translator) = translator {
+        translator.finish();
+    }
+
+    info!("Live transcription ended");
+    Ok(())
+}
+
+fn write_clipboard(app: &AppHandle, text: String) {
+    if let Err(e) = app.clipboard().write_text(text) {
+        warn!("Live paste: failed to write the clipboard: {}", e);
+    }
+}
+
+/// Queue the transcription of an audio file; returns the job id (see `stop_recording`)
+#[tauri::command]
+pub async fn transcribe_file(
+    window: Window,
+    file_path: String,
+    language: Option<TranscriptionLanguage>,
+    decoding_config: Option<DecodingConfig>,
+    normalization: Option<Normalization>,
+) -> Result<String> {
+    storage::ensure_writable()?;
+    let job = EngineJob::begin(window.app_handle());
+
+    let path = PathBuf::from(&file_path);
+
+    if !path.exists() {
+        return Err(AppError::NotFound(format!("File not found: {}", file_path)));
+    }
+
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(String::from);
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let pending = PendingTranscription {
+        id: Uuid::new_v4().to_string(),
+        source_path: file_path,
+        source_name: file_name,
+        language,
+        decoding_config,
+        normalization,
+        chunk_count: 0,
+        chunks: Vec::new(),
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    queue_file(window, pending, job)
+}
+
+/// Transcription job of an audio file (chunks already saved in `pending` are not decoded again)
+fn transcribe_path(window: &Window, pending: PendingTranscription) -> Result<Transcription> {
+    let path = PathBuf::from(&pending.source_path);
+    let file_name = pending.source_name;
+    let journal = ChunkJournal::new(&pending.id, pending.chunks);
+
+    // Load and process audio
+    let (samples, sample_rate) = load_audio_file(&path)?;
+    let total_ms = duration_ms(&samples, sample_rate);
+
+    let settings = storage::store()?.get_settings()?;
+    let simulated = settings.simulated_inference_delay_ms > 0;
+
+    // Emit initial progress
+    let _ = window.emit(
+        "transcription-progress",
+        TranscriptionProgress {
+            current_ms: 0,
+            total_ms,
+            speed_factor: 0.0,
+            chunk_index: 0,
+            chunk_count: 0,
+            simulated,
+        },
+    );
+
+    // Use provided language or the stored default
+    let lang = pending.language.unwrap_or(settings.transcription.language);
+    // Long files: the engine reports each chunk as it gets there
+    let progress_window = window.clone();
+    let progress = ProgressSink::new(move |p| {
+        let _ = progress_window.emit(
+            "transcription-progress",
+            TranscriptionProgress {
+                current_ms: p.processed_ms,
+                total_ms: p.total_ms,
+                speed_factor: p.realtime_factor,
+                chunk_index: p.chunk_index,
+                chunk_count: p.chunk_count,
+                simulated,
+            },
+        );
+    });
+    let decoding_config = Some(with_debug_dump(
+        with_settings(pending.decoding_config, lang, &settings)
+            .with_progress(progress)
+            .with_journal(journal),
+        &settings,
+    ));
+    let config = decoding_config.clone();
+    info!(
+        "Transcribing file: {:?} with language: {:?}, decoding_config: {:?}",
+        path, lang, config
+    );
+
+    // Resample to 16kHz
+    let resampled = resample_to_16k(&samples, sample_rate)?;
+
+    // Reduce noise, then normalize audio level (RMS by default)
+    let denoised = denoise(&resampled, settings.transcription.denoise);
+    let (normalized, gain) = normalize(&denoised, pending.normalization.unwrap_or_default());
+
+    // Long pauses left out, timestamps put back on the file's timeline afterwards
+    let (normalized, time_map) = if settings.skip_silence {
+        let (shortened, map) = skip_silence(&normalized, &VadConfig::default());
+        (shortened, Some(map))
+    } else {
+        (normalized, None)
+    };
+
+    // Transcribe (waits for the engine if models are still loading or unloaded)
+    wait_engine(window.app_handle());
+    let engine = lock_file_engine(window.app_handle(), settings.engine_pool)?;
+    let started = Instant::now();
+    let mut transcription = engine
+        .transcribe(&normalized, TranscriptionSource::File, file_name.clone(), lang, decoding_config.clone())
+        .map_err(|e| {
+            support::record_failure(FailedJob::new(
+                TranscriptionSource::File,
+                file_name.as_deref(),
+                &e,
+                &engine,
+                lang,
+                decoding_config.as_ref(),
+                normalized.len(),
+            ));
+            e
+        })?;
+    drop(engine);
+
+    if let Some(map) = time_map {
+        map.apply(&mut transcription);
+    }
+    transcription.source_path = Some(path.to_string_lossy().into_owned());
+    transcription.original_sample_rate = Some(sample_rate);
+    transcription.normalization_gain = Some(gain);
+    transcription.capture = provenance::capture_of_file(&path)
+        .map_err(|e| warn!("Could not hash {:?}: {}", path, e))
+        .ok();
+
+    // Apply user post-processing (substitution rules)
+    postprocess::apply(&mut transcription, &settings);
+
+    // Final progress, speed measured over the whole inference
+    let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+    let _ = window.emit(
+        "transcription-progress",
+        TranscriptionProgress {
+            current_ms: total_ms,
+            total_ms,
+            speed_factor: if elapsed_ms > 0.0 { total_ms as f64 / elapsed_ms } else { 0.0 },
+            chunk_index: 0,
+            chunk_count: 0,
+            simulated,
+        },
+    );
+
+    // Save to database
+    storage::store()?.insert_transcription(&transcription)?;
+    hooks::run_after_transcription(window.app_handle(), &transcription, &settings.post_transcription_hooks);
+
+    Ok(transcription)
+}
+
+#[tauri::command]
+pub fn get_transcription(id: String) -> Result<Transcription> {
+    storage::store()?
+        .get_transcription(&id)?
+        .ok_or_else(|| AppError::NotFound(format!("Transcription not found: {}", id)))
+}
@@ -0,0 +1,110 @@
+use super::queue::EngineJob;
+use super::transcribe_path;
+use crate::engine::partial::partial_transcription;
+use crate::error::{AppError, Result};
+use crate::job_queue;
+use crate::postprocess;
+use crate::storage::{self, PendingTranscription, Transcription, TranscriptionSource};
+use parking_lot::Mutex;
+use std::path::Path;
+use tauri::{Manager, Window};
+use tracing::warn;
+
+/// Pending transcriptions whose job runs in this session
+static ACTIVE_PENDING: Mutex<Vec<String>> = parking_lot::const_mutex(Vec::new());
+
+/// Queue a file job whose chunk results are saved in `pending` as they come:
+/// the row is removed once the transcription is saved, or after a failure
+/// that left nothing to recover
+pub(super) fn queue_file(window: Window, pending: PendingTranscription, job: EngineJob) -> Result<String> {
+    storage::with_db(|conn| storage::insert_pending_transcription(conn, &pending))?;
+    ACTIVE_PENDING.lock().push(pending.id.clone());
+
+    let app = window.app_handle().clone();
+    let id = pending.id.clone();
+    let submitted = job_queue::submit(
+        &app,
+        TranscriptionSource::File,
+        pending.source_name.clone(),
+        move || {
+            let _job = job;
+            let id = pending.id.clone();
+            let result = transcribe_path(&window, pending);
+            let keep = result.is_err()
+                && storage::with_db(|conn| storage::get_pending_transcription(conn, &id))
+                    .ok()
+                    .flatten()
+                    .is_some_and(|p| !p.chunks.is_empty());
+            if !keep {
+                if let Err(e) = storage::with_db(|conn| storage::delete_pending_transcription(conn, &id)) {
+                    warn!("Failed to remove pending transcription {}: {}", id, e);
+                }
+            }
+            ACTIVE_PENDING.lock().retain(|active| *active != id);
+            result
+        },
+        None,
+    );
+    if submitted.is_err() {
+        ACTIVE_PENDING.lock().retain(|active| *active != id);
+    }
+    submitted
+}
+
+/// File transcriptions interrupted by a crash or a failure (not those running),
+/// oldest first
+#[tauri::command]
+pub fn recover_pending_transcriptions() -> Result<Vec<PendingTranscription>> {
+    let active = ACTIVE_PENDING.lock().clone();
+    let mut pending = storage::with_db(|conn| storage::list_pending_transcriptions(conn))?;
+    pending.retain(|p| !active.contains(&p.id));
+    Ok(pending)
+}
+
+fn interrupted_transcription(id: &str) -> Result<PendingTranscription> {
+    if ACTIVE_PENDING.lock().iter().any(|active| active == id) {
+        return Err(AppError::InvalidState(format!("Transcription {} is running", id)));
+    }
+    storage::with_db(|conn| storage::get_pending_transcription(conn, id))?
+        .ok_or_else(|| AppError::NotFound(format!("Pending transcription not found: {}", id)))
+}
+
+/// Queue an interrupted file transcription again: the saved chunks are reused,
+/// the others decoded; returns the job id
+#[tauri::command]
+pub fn resume_pending_transcription(window: Window, id: String) -> Result<String> {
+    storage::ensure_writable()?;
+    let pending = interrupted_transcription(&id)?;
+    if !Path::new(&pending.source_path).exists() {
+        return Err(AppError::NotFound(format!("File not found: {}", pending.source_path)));
+    }
+    let job = EngineJob::begin(window.app_handle());
+    queue_file(window, pending, job)
+}
+
+/// Save the chunks done of an interrupted transcription as they are
+#[tauri::command]
+pub fn finalize_pending_transcription(id: String) -> Result<Transcription> {
+    storage::ensure_writable()?;
+    let pending = interrupted_transcription(&id)?;
+    if pending.chunks.iter().all(|c| c.text.is_empty()) {
+        return Err(AppError::InvalidState("No transcribed chunk to keep".into()));
+    }
+    let mut transcription = partial_transcription(&pending);
+    if let Some(language) = pending.language.and_then(|l| l.code()) {
+        transcription.language = language.to_string();
+    }
+    let store = storage::store()?;
+    postprocess::apply(&mut transcription, &store.get_settings()?);
+    store.insert_transcription(&transcription)?;
+    storage::with_db(|conn| storage::delete_pending_transcription(conn, &id))?;
+    Ok(transcription)
+}
+
+/// Forget an interrupted transcription and its saved chunks
+#[tauri::command]
+pub fn discard_pending_transcription(id: String) -> Result<()> {
+    storage::ensure_writable()?;
+    interrupted_transcription(&id)?;
+    storage::with_db(|conn| storage::delete_pending_transcription(conn, &id))
+}
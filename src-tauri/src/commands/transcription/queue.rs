@@ -0,0 +1,138 @@
+use super::engine::{model_dir_for, EngineState, EngineStatusState, ModelPathState};
+use crate::audio::probe_audio_file;
+use crate::commands::audio::AudioState;
+use crate::engine::estimate::{self, JobEstimate};
+use crate::engine::{DecodingConfig, EngineBackend};
+use crate::error::{AppError, Result};
+use crate::job_queue::{self, TranscriptionJob};
+use crate::storage;
+use parking_lot::Mutex;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tracing::{info, warn};
+
+/// Backend switch requested while the engine was busy, applied once it is free
+pub(super) static PENDING_SWITCH: Mutex<Option<EngineBackend>> = parking_lot::const_mutex(None);
+
+/// Recordings being stopped and transcriptions running
+static ENGINE_JOBS: AtomicUsize = AtomicUsize::new(0);
+
+/// Held from the end of a recording until its transcription is done, and for
+/// file transcriptions: the engine is not swapped meanwhile. The last job to
+/// end applies a deferred backend switch.
+pub(crate) struct EngineJob {
+    app: AppHandle,
+}
+
+impl EngineJob {
+    pub(crate) fn begin(app: &AppHandle) -> Self {
+        ENGINE_JOBS.fetch_add(1, Ordering::SeqCst);
+        Self { app: app.clone() }
+    }
+}
+
+impl Drop for EngineJob {
+    fn drop(&mut self) {
+        if ENGINE_JOBS.fetch_sub(1, Ordering::SeqCst) == 1 && PENDING_SWITCH.lock().is_some() {
+            // Loading the model takes a while: don't hold up the job's result
+            let app = self.app.clone();
+            thread::spawn(move || apply_pending_switch(&app));
+        }
+    }
+}
+
+/// Sent as "engine-backend-switched" when a deferred switch has been applied (or failed)
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeferredSwitch {
+    pub backend: String,
+    pub error: Option<String>,
+}
+
+/// Whether a recording or transcription needs the current engine
+pub(super) fn engine_busy(app: &AppHandle) -> bool {
+    app.state::<AudioState>().0.is_recording() || ENGINE_JOBS.load(Ordering::SeqCst) > 0
+}
+
+/// Apply the deferred switch, unless a new recording or job started meanwhile
+/// (its end applies it then)
+fn apply_pending_switch(app: &AppHandle) {
+    if engine_busy(app) {
+        return;
+    }
+    let Some(backend) = PENDING_SWITCH.lock().take() else {
+        return;
+    };
+
+    let result = model_dir_for(&app.state::<ModelPathState>().0, backend).and_then(|model_dir| {
+        app.state::<EngineState>().0.lock().switch_backend(backend, &model_dir)
+    });
+    let error = match result {
+        Ok(()) => {
+            app.state::<EngineStatusState>().0.set_ready(&app.state::<EngineState>().0.lock());
+            info!("Switched to {} backend (deferred)", backend.display_name());
+            None
+        }
+        Err(e) => {
+            warn!("Deferred switch to {} failed: {}", backend.display_name(), e);
+            Some(e.to_string())
+        }
+    };
+    let _ = app.emit(
+        "engine-backend-switched",
+        DeferredSwitch {
+            backend: backend.display_name().to_string(),
+            error,
+        },
+    );
+}
+
+/// State of a transcription job started by `stop_recording` or `transcribe_file`
+#[tauri::command]
+pub fn get_job_status(id: String) -> Result<TranscriptionJob> {
+    job_queue::status(&id)
+}
+
+/// Audio of a job to estimate: a file, or a duration
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+pub enum JobInput {
+    DurationMs(i64),
+    Path(PathBuf),
+}
+
+/// Predict the processing time and memory of a job before running it
+/// (`backend`: the current one if not given)
+#[tauri::command]
+pub fn estimate_job(
+    engine_state: State<'_, EngineState>,
+    model_path_state: State<'_, ModelPathState>,
+    input: JobInput,
+    backend: Option<EngineBackend>,
+    config: Option<DecodingConfig>,
+) -> Result<JobEstimate> {
+    let (audio_ms, sample_rate) = match input {
+        JobInput::DurationMs(ms) if ms >= 0 => (ms, 16000),
+        JobInput::DurationMs(ms) => {
+            return Err(AppError::InvalidInput(format!("Invalid duration: {} ms", ms)))
+        }
+        JobInput::Path(path) => probe_audio_file(&path)?,
+    };
+    let backend = backend.unwrap_or_else(|| engine_state.0.lock().backend());
+    let beam_width = match config {
+        Some(config) => config.beam_width,
+        None => storage::store()?.get_settings()?.transcription.beam_width,
+    };
+    let model_bytes = estimate::dir_size(&model_path_state.0.join(backend.model_subdir()));
+
+    Ok(estimate::estimate(
+        audio_ms,
+        sample_rate,
+        backend,
+        beam_width,
+        &estimate::history(backend),
+        model_bytes,
+    ))
+}
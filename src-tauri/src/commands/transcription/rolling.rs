@@ -0,0 +1,221 @@
+use super::engine::{wait_engine, EngineState};
+use super::with_settings;
+use crate::audio::{denoise, normalize, resample_to_16k, Denoise, Normalization, VadConfig};
+use crate::commands::audio::AudioState;
+use crate::engine::rolling::{self, Block};
+use crate::engine::{DecodingConfig, EngineStatus, TranscriptionLanguage};
+use crate::error::{AppError, Result};
+use crate::storage::{self, Transcription, TranscriptionSource};
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+use tracing::{info, warn};
+
+/// Rolling transcription of the current recording, if started
+pub(super) static ROLLING: Mutex<Option<RollingSession>> = parking_lot::const_mutex(None);
+
+/// Pause between two looks for a block end in the recording
+const ROLLING_STEP: Duration = Duration::from_secs(5);
+
+/// Shorter audio after the last block is not worth a transcription (0.5 s)
+const MIN_TAIL_SAMPLES: usize = 8000;
+
+pub(super) struct RollingSession {
+    stop: Arc<AtomicBool>,
+    sample_rate: u32,
+    config: DecodingConfig,
+    /// Cuts and queues the blocks; returns the 16 kHz samples queued
+    feeder: thread::JoinHandle<Result<usize>>,
+    /// Transcribes the queued blocks
+    worker: thread::JoinHandle<Result<Vec<Block>>>,
+}
+
+/// Blocks of a finished rolling transcription, and where the rest starts
+pub(crate) struct Rolled {
+    blocks: Vec<Block>,
+    /// In capture samples
+    tail_from: usize,
+    tail_start_ms: i64,
+    config: DecodingConfig,
+}
+
+impl Rolled {
+    /// Where the audio after the last block starts, in capture samples
+    pub(super) fn tail_from(&self) -> usize {
+        self.tail_from
+    }
+
+    /// Join the blocks with the transcription of the audio after the last one
+    pub(super) fn join(
+        self,
+        tail: &[f32],
+        transcribe: impl Fn(&[f32]) -> Result<Transcription>,
+    ) -> Result<Transcription> {
+        let Rolled { mut blocks, tail_start_ms, config, .. } = self;
+        if tail.len() >= MIN_TAIL_SAMPLES {
+            blocks.push(Block {
+                start_ms: tail_start_ms,
+                transcription: transcribe(tail)?,
+            });
+        }
+        rolling::join_blocks(blocks, &config)
+            .ok_or_else(|| AppError::InvalidState("No rolling transcription block".into()))
+    }
+}
+
+/// Transcribe a long recording block by block while it goes on
+///
+/// Returns at once. The recording is cut at pauses into 30-60 s blocks,
+/// transcribed in the background; `stop_recording` then only transcribes the
+/// audio after the last block and joins the results. Not available with
+/// system audio (the sources are merged over the whole recording).
+#[tauri::command]
+pub fn start_rolling_transcription(
+    app: AppHandle,
+    audio_state: State<'_, AudioState>,
+    language: Option<TranscriptionLanguage>,
+    decoding_config: Option<DecodingConfig>,
+    normalization: Option<Normalization>,
+) -> Result<()> {
+    storage::ensure_writable()?;
+    if !audio_state.0.is_recording() {
+        return Err(AppError::InvalidState("Not recording".into()));
+    }
+    if audio_state.0.loopback_id().is_some() {
+        return Err(AppError::InvalidState(
+            "Rolling transcription is not available with system audio".into(),
+        ));
+    }
+
+    let settings = storage::store()?.get_settings()?;
+    let lang = language.unwrap_or(settings.transcription.language);
+    let config = with_settings(decoding_config, lang, &settings);
+    let denoising = settings.transcription.denoise;
+    let normalization = normalization.unwrap_or(settings.transcription.normalization);
+
+    let mut rolling = ROLLING.lock();
+    if rolling.as_ref().is_some_and(|r| !r.feeder.is_finished()) {
+        return Err(AppError::InvalidState("Rolling transcription already running".into()));
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let (queue, blocks) = mpsc::sync_channel(rolling::QUEUE_BLOCKS);
+    let worker = {
+        let (app, config) = (app.clone(), config.clone());
+        thread::spawn(move || transcribe_blocks(&app, blocks, lang, &config, denoising, normalization))
+    };
+    let feeder = {
+        let stop = stop.clone();
+        thread::spawn(move || feed_blocks(&app, &stop, queue))
+    };
+    // A session left by a recording that was not transcribed is dropped here
+    *rolling = Some(RollingSession {
+        stop,
+        sample_rate: audio_state.0.sample_rate(),
+        config,
+        feeder,
+        worker,
+    });
+    info!("Rolling transcription started");
+    Ok(())
+}
+
+/// Cut the recording into blocks at pauses and queue them, until it stops
+fn feed_blocks(app: &AppHandle, stop: &AtomicBool, queue: SyncSender<(i64, Vec<f32>)>) -> Result<usize> {
+    let audio_state = app.state::<AudioState>();
+    let sample_rate = audio_state.0.sample_rate();
+    let vad = VadConfig::default();
+    let mut queued = 0;
+
+    while audio_state.0.is_recording() && !stop.load(Ordering::SeqCst) {
+        // Woken early by `finish_rolling`
+        thread::park_timeout(ROLLING_STEP);
+
+        let from = (queued as u64 * sample_rate as u64 / 16000) as usize;
+        let Ok(recorded) = audio_state.0.snapshot(from) else {
+            break; // Stopped meanwhile
+        };
+        let mut pending = resample_to_16k(&recorded, sample_rate)?;
+        // The resampler pads its last block with zeros
+        pending.truncate((recorded.len() as u64 * 16000 / sample_rate as u64) as usize);
+
+        let mut offset = 0;
+        while let Some(end) = rolling::next_block_end(&pending[offset..], &vad) {
+            let block = pending[offset..offset + end].to_vec();
+            // Waits while the engine is behind by a full queue
+            if queue.send(((queued / 16) as i64, block)).is_err() {
+                return Ok(queued); // The worker gave up
+            }
+            offset += end;
+            queued += end;
+        }
+    }
+    Ok(queued)
+}
+
+/// Transcribe the queued blocks until the feeder is done
+fn transcribe_blocks(
+    app: &AppHandle,
+    blocks: Receiver<(i64, Vec<f32>)>,
+    language: TranscriptionLanguage,
+    config: &DecodingConfig,
+    denoising: Denoise,
+    normalization: Normalization,
+) -> Result<Vec<Block>> {
+    if wait_engine(app) != EngineStatus::Ready {
+        info!("No model loaded, rolling transcription disabled");
+        return Ok(Vec::new());
+    }
+    let engine_state = app.state::<EngineState>();
+    let mut done = Vec::new();
+    for (start_ms, audio) in blocks {
+        let (audio, _) = normalize(&denoise(&audio, denoising), normalization);
+        let transcription = engine_state.0.lock().transcribe(
+            &audio,
+            TranscriptionSource::Dictation,
+            None,
+            language,
+            Some(config.clone()),
+        )?;
+        info!("Rolling block at {}s transcribed", start_ms / 1000);
+        done.push(Block { start_ms, transcription });
+    }
+    Ok(done)
+}
+
+/// End the rolling transcription, if any, once its queued blocks are done
+///
+/// Must be called before locking the engine. None without blocks (recording
+/// too short) or when a block failed: the whole recording is transcribed then.
+pub(crate) fn finish_rolling() -> Option<Rolled> {
+    finish_session(ROLLING.lock().take()?)
+}
+
+pub(super) fn finish_session(session: RollingSession) -> Option<Rolled> {
+    session.stop.store(true, Ordering::SeqCst);
+    session.feeder.thread().unpark();
+    let queued = session.feeder.join();
+    let blocks = session.worker.join();
+
+    match (queued, blocks) {
+        (Ok(Ok(queued)), Ok(Ok(blocks))) if !blocks.is_empty() => Some(Rolled {
+            blocks,
+            tail_from: (queued as u64 * session.sample_rate as u64 / 16000) as usize,
+            tail_start_ms: (queued / 16) as i64,
+            config: session.config,
+        }),
+        (Ok(Err(e)), _) | (_, Ok(Err(e))) => {
+            warn!("Rolling transcription failed, transcribing the whole recording: {}", e);
+            None
+        }
+        (Err(_), _) | (_, Err(_)) => {
+            warn!("Rolling transcription thread panicked");
+            None
+        }
+        _ => None,
+    }
+}
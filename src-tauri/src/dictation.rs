@@ -0,0 +1,65 @@
+//! Push-to-talk dictation into the focused application
+//!
+//! The push-to-talk shortcut is registered system-wide. Pressing and releasing
+//! it sends "push-to-talk" events; the UI records while it is held, then
+//! transcribes with its own settings and hands the text back to `type_text`,
+//! which types it into whatever application has the keyboard focus.
+
+use crate::error::{AppError, Result};
+use enigo::{Enigo, Keyboard};
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+use tracing::{info, warn};
+
+/// Shortcut currently registered for push-to-talk
+static REGISTERED: Mutex<Option<String>> = parking_lot::const_mutex(None);
+
+/// Sent when the push-to-talk shortcut goes down or up
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushToTalk {
+    pub pressed: bool,
+}
+
+/// Register `shortcut` for push-to-talk in place of the previous one (empty: none)
+pub fn register_push_to_talk(app: &AppHandle, shortcut: &str) -> Result<()> {
+    let shortcut = shortcut.trim();
+    let mut registered = REGISTERED.lock();
+    if registered.as_deref().unwrap_or("") == shortcut {
+        return Ok(());
+    }
+
+    if let Some(previous) = registered.take() {
+        if let Err(e) = app.global_shortcut().unregister(previous.as_str()) {
+            warn!("Failed to unregister push-to-talk shortcut {}: {}", previous, e);
+        }
+    }
+    if shortcut.is_empty() {
+        return Ok(());
+    }
+
+    app.global_shortcut()
+        .on_shortcut(shortcut, |app, _shortcut, event| {
+            let pressed = event.state() == ShortcutState::Pressed;
+            let _ = app.emit("push-to-talk", PushToTalk { pressed });
+        })
+        .map_err(|e| AppError::InvalidInput(format!("Cannot register shortcut {}: {}", shortcut, e)))?;
+    info!("Push-to-talk shortcut: {}", shortcut);
+    *registered = Some(shortcut.to_string());
+    Ok(())
+}
+
+/// Type `text` into the focused application, as if from the keyboard
+pub fn type_text(text: &str) -> Result<()> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(());
+    }
+    let mut enigo = Enigo::new(&enigo::Settings::default())
+        .map_err(|e| AppError::InvalidState(format!("Keyboard input unavailable: {}", e)))?;
+    enigo
+        .text(text)
+        .map_err(|e| AppError::InvalidState(format!("Failed to type the dictation: {}", e)))
+}
@@ -0,0 +1,165 @@
+//! Drafts of the recording in progress
+//!
+//! While live transcription runs, its text (committed sentences and the
+//! current partial) is saved now and then as a transcription flagged
+//! `is_draft`. When `stop_recording` succeeds the draft is replaced by the
+//! final transcription; when it fails, or the app is closed meanwhile, the
+//! draft stays in the history so the dictated text is not lost.
+
+use crate::engine::{language_id, TranscriptionLanguage};
+use crate::error::Result;
+use crate::storage::{self, StreamingSegment, Transcription, TranscriptionSource};
+use parking_lot::Mutex;
+use rusqlite::Connection;
+use std::time::{Duration, Instant};
+use tracing::warn;
+use uuid::Uuid;
+
+/// Shortest delay between two saves of a partial text (committed sentences are saved at once)
+const SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Draft of the ongoing recording; cleared by `finish`, after which no save goes through
+static CURRENT: Mutex<Option<String>> = parking_lot::const_mutex(None);
+
+/// Accumulates the live text of a recording and saves it as a draft
+pub struct DraftWriter {
+    id: String,
+    created_at: String,
+    /// Forced language, or Auto with the favored one
+    language: TranscriptionLanguage,
+    prior: Option<TranscriptionLanguage>,
+    committed: Vec<String>,
+    partial: String,
+    last_saved: Option<Instant>,
+    dirty: bool,
+}
+
+impl DraftWriter {
+    /// Start the draft of a new recording (replaces any previous one as current)
+    pub fn begin(language: TranscriptionLanguage, prior: Option<TranscriptionLanguage>) -> Self {
+        let id = Uuid::new_v4().to_string();
+        *CURRENT.lock() = Some(id.clone());
+        Self {
+            id,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            language,
+            prior,
+            committed: Vec::new(),
+            partial: String::new(),
+            last_saved: None,
+            dirty: false,
+        }
+    }
+
+    /// Add a live segment; saves when a sentence was committed or the partial is old enough
+    pub fn push(&mut self, segment: &StreamingSegment, duration_ms: i64) {
+        if segment.is_final {
+            self.committed.push(segment.text.clone());
+            self.partial.clear();
+        } else {
+            self.partial = segment.text.clone();
+        }
+        self.dirty = true;
+
+        let due = self.last_saved.is_none_or(|t| t.elapsed() >= SAVE_INTERVAL);
+        if segment.is_final || due {
+            self.save(duration_ms);
+        }
+    }
+
+    /// Save the pending text, if any
+    pub fn flush(&mut self, duration_ms: i64) {
+        if self.dirty {
+            self.save(duration_ms);
+        }
+    }
+
+    fn text(&self) -> String {
+        self.committed
+            .iter()
+            .chain(std::iter::once(&self.partial))
+            .map(|t| t.trim())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Code of the forced language, or of the one identified in `text` so
+    /// far (Auto), as `language_id::label` does for the final transcription
+    fn language_code(&self, text: &str) -> &'static str {
+        let language = match self.language {
+            TranscriptionLanguage::Auto => language_id::detect(text)
+                .or(self.prior)
+                .unwrap_or(TranscriptionLanguage::French),
+            forced => forced,
+        };
+        language.code().unwrap_or("fr")
+    }
+
+    fn save(&mut self, duration_ms: i64) {
+        let text = self.text();
+        if text.is_empty() {
+            return;
+        }
+
+        // Held while writing, so `finish` can't slip in between the check and the save
+        let current = CURRENT.lock();
+        if current.as_deref() != Some(self.id.as_str()) {
+            return;
+        }
+        let language = self.language_code(&text).to_string();
+        let draft = Transcription {
+            id: self.id.clone(),
+            created_at: self.created_at.clone(),
+            updated_at: chrono::Utc::now().to_rfc3339(),
+            source_type: TranscriptionSource::Dictation,
+            source_name: None,
+            source_path: None,
+            original_sample_rate: None,
+            duration_ms,
+            language,
+            segments: Vec::new(),
+            raw_text: text,
+            edited_text: None,
+            is_edited: false,
+            is_draft: true,
+            warnings: Vec::new(),
+            normalization_gain: None,
+            action_items: Vec::new(),
+            markers: Vec::new(),
+            pauses: Vec::new(),
+            capture: None,
+            confidence: None,
+            audio_path: None,
+            translations: Vec::new(),
+            notes: Vec::new(),
+            folder: None,
+            tags: Vec::new(),
+            title: None,
+            deleted_at: None,
+        };
+        match storage::with_db(|conn| storage::upsert_transcription(conn, &draft)) {
+            Ok(()) => {
+                self.last_saved = Some(Instant::now());
+                self.dirty = false;
+            }
+            Err(e) => warn!("Failed to save draft: {}", e),
+        }
+    }
+}
+
+/// End drafting for the current recording; returns the draft to finalize or discard
+pub fn finish() -> Option<String> {
+    CURRENT.lock().take()
+}
+
+/// Save the final transcription in place of its draft
+pub fn finalize(conn: &Connection, draft_id: Option<&str>, transcription: &Transcription) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    if let Some(id) = draft_id {
+        storage::delete_transcription(&tx, id)?;
+    }
+    storage::insert_transcription(&tx, transcription)?;
+    tx.commit()?;
+    Ok(())
+}
@@ -0,0 +1,223 @@
+//! Word and character error rates against a reference transcript
+//!
+//! Both texts are normalized the same way (case, punctuation, accents as
+//! chosen), then aligned by edit distance: substitutions, deletions and
+//! insertions are counted on the cheapest alignment. WER and CER are the
+//! errors over the length of the reference.
+
+use serde::{Deserialize, Serialize};
+
+/// What the comparison ignores
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TextNormalization {
+    pub lowercase: bool,
+    /// Apostrophes inside words are kept, hyphens split words
+    pub strip_punctuation: bool,
+    /// "é" counts as "e"
+    pub strip_accents: bool,
+}
+
+impl Default for TextNormalization {
+    fn default() -> Self {
+        Self {
+            lowercase: true,
+            strip_punctuation: true,
+            strip_accents: false,
+        }
+    }
+}
+
+/// Edits turning the reference into the hypothesis
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditCounts {
+    pub substitutions: usize,
+    pub deletions: usize,
+    pub insertions: usize,
+    /// Words or characters of the reference
+    pub reference_len: usize,
+}
+
+impl EditCounts {
+    pub fn errors(&self) -> usize {
+        self.substitutions + self.deletions + self.insertions
+    }
+
+    /// Errors over the reference length (can exceed 1 with many insertions)
+    pub fn rate(&self) -> f64 {
+        self.errors() as f64 / self.reference_len.max(1) as f64
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccuracyReport {
+    pub wer: f64,
+    pub cer: f64,
+    pub words: EditCounts,
+    pub chars: EditCounts,
+    /// Texts as compared, after normalization
+    pub reference: String,
+    pub hypothesis: String,
+}
+
+/// Compare the engine output to the reference
+pub fn evaluate(reference: &str, hypothesis: &str, options: &TextNormalization) -> AccuracyReport {
+    let reference = normalize_text(reference, options);
+    let hypothesis = normalize_text(hypothesis, options);
+
+    let ref_words: Vec<&str> = reference.split(' ').filter(|w| !w.is_empty()).collect();
+    let hyp_words: Vec<&str> = hypothesis.split(' ').filter(|w| !w.is_empty()).collect();
+    let words = align(&ref_words, &hyp_words);
+
+    let ref_chars: Vec<char> = reference.chars().collect();
+    let hyp_chars: Vec<char> = hypothesis.chars().collect();
+    let chars = align(&ref_chars, &hyp_chars);
+
+    AccuracyReport {
+        wer: words.rate(),
+        cer: chars.rate(),
+        words,
+        chars,
+        reference,
+        hypothesis,
+    }
+}
+
+/// Text as compared: words separated by single spaces
+pub fn normalize_text(text: &str, options: &TextNormalization) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if options.strip_punctuation && !c.is_alphanumeric() && c != '\'' && c != '’' {
+            out.push(' ');
+            continue;
+        }
+        let c = if c == '’' { '\'' } else { c };
+        let folded = if options.strip_accents { fold_accent(c) } else { None };
+        match folded {
+            Some(s) => out.push_str(s),
+            None => out.push(c),
+        }
+    }
+    if options.lowercase {
+        out = out.to_lowercase();
+    }
+
+    let words = out.split_whitespace().map(|w| {
+        if options.strip_punctuation {
+            w.trim_matches('\'')
+        } else {
+            w
+        }
+    });
+    words.filter(|w| !w.is_empty()).collect::<Vec<_>>().join(" ")
+}
+
+/// Letter without its accent (Latin letters of French and neighbours)
+fn fold_accent(c: char) -> Option<&'static str> {
+    Some(match c {
+        'à' | 'â' | 'ä' | 'á' | 'ã' => "a",
+        'À' | 'Â' | 'Ä' | 'Á' | 'Ã' => "A",
+        'é' | 'è' | 'ê' | 'ë' => "e",
+        'É' | 'È' | 'Ê' | 'Ë' => "E",
+        'î' | 'ï' | 'í' | 'ì' => "i",
+        'Î' | 'Ï' | 'Í' | 'Ì' => "I",
+        'ô' | 'ö' | 'ó' | 'ò' | 'õ' => "o",
+        'Ô' | 'Ö' | 'Ó' | 'Ò' | 'Õ' => "O",
+        'ù' | 'û' | 'ü' | 'ú' => "u",
+        'Ù' | 'Û' | 'Ü' | 'Ú' => "U",
+        'ç' => "c",
+        'Ç' => "C",
+        'ñ' => "n",
+        'Ñ' => "N",
+        'ÿ' => "y",
+        'œ' => "oe",
+        'Œ' => "OE",
+        'æ' => "ae",
+        'Æ' => "AE",
+        _ => return None,
+    })
+}
+
+/// Edit counts of the cheapest alignment (two rows of the distance table)
+fn align<T: PartialEq>(reference: &[T], hypothesis: &[T]) -> EditCounts {
+    let cost = |e: &EditCounts| e.errors();
+    // Row 0: everything in the hypothesis inserted
+    let mut prev: Vec<EditCounts> = (0..=hypothesis.len())
+        .map(|j| EditCounts {
+            insertions: j,
+            ..EditCounts::default()
+        })
+        .collect();
+    let mut curr = vec![EditCounts::default(); hypothesis.len() + 1];
+
+    for (i, r) in reference.iter().enumerate() {
+        curr[0] = EditCounts {
+            deletions: i + 1,
+            ..EditCounts::default()
+        };
+        for (j, h) in hypothesis.iter().enumerate() {
+            let mut matched = prev[j];
+            if r != h {
+                matched.substitutions += 1;
+            }
+            let mut deleted = prev[j + 1];
+            deleted.deletions += 1;
+            let mut inserted = curr[j];
+            inserted.insertions += 1;
+
+            curr[j + 1] = [deleted, inserted]
+                .into_iter()
+                .fold(matched, |best, e| if cost(&e) < cost(&best) { e } else { best });
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    EditCounts {
+        reference_len: reference.len(),
+        ..prev[hypothesis.len()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_errors() {
+        let report = evaluate(
+            "Bonjour à tous, merci d'être venus.",
+            "bonjour a tous merci d'être venu aujourd'hui",
+            &TextNormalization::default(),
+        );
+        assert_eq!(report.reference, "bonjour à tous merci d'être venus");
+        assert_eq!(
+            report.words,
+            EditCounts {
+                substitutions: 2,
+                deletions: 0,
+                insertions: 1,
+                reference_len: 6,
+            }
+        );
+        assert!((report.wer - 0.5).abs() < 1e-9);
+
+        let folded = TextNormalization {
+            strip_accents: true,
+            ..TextNormalization::default()
+        };
+        let report = evaluate("Ça a été.", "ca a ete", &folded);
+        assert_eq!(report.wer, 0.0);
+        assert_eq!(report.cer, 0.0);
+    }
+
+    #[test]
+    fn test_empty_texts() {
+        let options = TextNormalization::default();
+        assert_eq!(evaluate("", "", &options).wer, 0.0);
+        assert_eq!(evaluate("un deux", "", &options).words.deletions, 2);
+        assert_eq!(evaluate("un deux", "", &options).wer, 1.0);
+        assert_eq!(evaluate("", "bruit", &options).wer, 1.0);
+    }
+}
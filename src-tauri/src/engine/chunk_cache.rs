@@ -0,0 +1,72 @@
+//! Per-chunk transcription cache
+//!
+//! Re-transcribing a slightly edited file (trimmed end, re-exported) produces
+//! mostly the same smart chunks. Their results are cached in the database,
+//! keyed by a hash of the chunk samples, the backend, the language and the
+//! decoding parameters that affect the model output, so unchanged regions are
+//! not decoded again. The cache is cleared when the model is reloaded.
+
+use crate::engine::config::DecodingConfig;
+use crate::engine::TranscriptionLanguage;
+use crate::storage::{self, with_db};
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+
+/// Most recently used chunks kept (~a few hours of audio)
+const MAX_ENTRIES: usize = 5000;
+
+/// Cache key of a chunk
+pub fn chunk_key(
+    samples: &[f32],
+    backend: &str,
+    language: TranscriptionLanguage,
+    config: &DecodingConfig,
+) -> String {
+    let mut hasher = Sha256::new();
+    for s in samples {
+        hasher.update(s.to_le_bytes());
+    }
+    hasher.update(backend.as_bytes());
+    hasher.update(language.display_name().as_bytes());
+    hasher.update((config.beam_width as u64).to_le_bytes());
+    hasher.update(config.temperature.to_le_bytes());
+    hasher.update(config.blank_penalty.to_le_bytes());
+    hasher.update((config.max_symbols_per_frame as u64).to_le_bytes());
+    if let Some(prior) = config.language_prior {
+        hasher.update(prior.display_name().as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Cached text for a chunk, if any
+pub fn lookup(key: &str) -> Option<String> {
+    match with_db(|conn| storage::get_cached_chunk(conn, key)) {
+        Ok(text) => text,
+        Err(e) => {
+            debug!("Chunk cache lookup failed: {}", e);
+            None
+        }
+    }
+}
+
+/// Remember the (filtered) text of a chunk
+pub fn store(key: &str, text: &str) {
+    if storage::is_read_only() {
+        return;
+    }
+    if let Err(e) = with_db(|conn| storage::put_cached_chunk(conn, key, text, MAX_ENTRIES)) {
+        warn!("Failed to cache chunk result: {}", e);
+    }
+}
+
+/// Drop every cached chunk (model changed)
+pub fn clear() {
+    if storage::is_read_only() {
+        return;
+    }
+    match with_db(storage::clear_chunk_cache) {
+        Ok(n) if n > 0 => debug!("Cleared {} cached chunks", n),
+        Ok(_) => {}
+        Err(e) => warn!("Failed to clear chunk cache: {}", e),
+    }
+}
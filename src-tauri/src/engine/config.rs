@@ -1,4 +1,12 @@
+use crate::engine::debug_dump::DebugDump;
+use crate::engine::merger::{ChunkJoin, DEFAULT_BOUNDARY_SIMILARITY};
+use crate::engine::parakeet::TranscriptionLanguage;
+use crate::engine::partial::ChunkJournal;
+use crate::engine::progress::ProgressSink;
+use crate::power::PowerMode;
 use serde::{Deserialize, Serialize};
+use std::thread;
+use std::time::Duration;
 
 /// Configuration for the TDT decoding process
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +17,83 @@ pub struct DecodingConfig {
     pub temperature: f32,
     /// Blank penalty: value subtracted from blank token logit (0-15, higher = more tokens)
     pub blank_penalty: f32,
+    /// Maximum decode steps on a single encoder frame before forcing time forward
+    #[serde(default = "default_max_symbols_per_frame")]
+    pub max_symbols_per_frame: usize,
+    /// Similarity (0-1) above which a phrase repeated across a chunk boundary is dropped (0 = off)
+    #[serde(default = "default_boundary_similarity")]
+    pub boundary_similarity: f32,
+    /// Separator and sentence-boundary heuristics used when joining chunks
+    #[serde(default)]
+    pub chunk_join: ChunkJoin,
+    /// Skip chunks that are clearly music or noise, inserting a marker instead
+    #[serde(default)]
+    pub skip_non_speech: bool,
+    /// Reuse cached results for chunks already transcribed with the same audio and settings
+    #[serde(default)]
+    pub chunk_cache: bool,
+    /// Greedy decoding of long audio carries the decoder state from one chunk
+    /// to the next instead of decoding each chunk from scratch (no chunk cache then)
+    #[serde(default)]
+    pub carry_decoder_state: bool,
+    /// Language favored in Auto mode (soft prior, the model can still switch)
+    #[serde(default)]
+    pub language_prior: Option<TranscriptionLanguage>,
+    /// Pacing of chunked transcription on battery
+    #[serde(default)]
+    pub power_mode: PowerMode,
+    /// Context or custom spellings for engines conditioned on a text prompt
+    /// (Whisper-style); ignored by backends without that capability (Parakeet)
+    #[serde(default)]
+    pub initial_prompt: Option<String>,
+    /// Receives the progress of chunked transcription (set by the caller, never serialized)
+    #[serde(skip)]
+    pub progress: Option<ProgressSink>,
+    /// Saves each chunk's result of a file transcription, and gives back those
+    /// of an interrupted run (set by the caller, never serialized)
+    #[serde(skip)]
+    pub journal: Option<ChunkJournal>,
+    /// Folder receiving the model inputs and outputs (debug setting, never serialized)
+    #[serde(skip)]
+    pub debug_dump: Option<DebugDump>,
+    /// Extra time spent on each inference to mimic a slow machine (dev setting, never serialized)
+    #[serde(skip)]
+    pub simulated_delay: Option<Duration>,
+}
+
+/// Blank penalty and temperature tuned for one language
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageDecoding {
+    pub blank_penalty: f32,
+    pub temperature: f32,
+}
+
+impl LanguageDecoding {
+    /// Built-in defaults: French output drops short function words ("de", "à",
+    /// "et") at the global 6.0, so it gets a lower blank penalty
+    pub fn default_for(language: TranscriptionLanguage) -> Option<Self> {
+        match language {
+            TranscriptionLanguage::Auto => None,
+            TranscriptionLanguage::French => Some(Self {
+                blank_penalty: 5.0,
+                temperature: 0.9,
+            }),
+            // Not tuned yet: the global defaults
+            _ => Some(Self {
+                blank_penalty: 6.0,
+                temperature: 1.0,
+            }),
+        }
+    }
+}
+
+fn default_max_symbols_per_frame() -> usize {
+    10
+}
+
+fn default_boundary_similarity() -> f32 {
+    DEFAULT_BOUNDARY_SIMILARITY
 }
 
 impl Default for DecodingConfig {
@@ -17,6 +102,19 @@ impl Default for DecodingConfig {
             beam_width: 1,      // Greedy decoding by default (fastest)
             temperature: 1.0,   // No scaling by default
             blank_penalty: 6.0, // Default blank penalty
+            max_symbols_per_frame: default_max_symbols_per_frame(),
+            boundary_similarity: default_boundary_similarity(),
+            chunk_join: ChunkJoin::default(),
+            skip_non_speech: false,
+            chunk_cache: false,
+            carry_decoder_state: false,
+            language_prior: None,
+            power_mode: PowerMode::default(),
+            initial_prompt: None,
+            progress: None,
+            journal: None,
+            debug_dump: None,
+            simulated_delay: None,
         }
     }
 }
@@ -33,6 +131,19 @@ impl DecodingConfig {
             beam_width: beam_width.max(1),
             temperature: 1.0,
             blank_penalty: 6.0,
+            max_symbols_per_frame: default_max_symbols_per_frame(),
+            boundary_similarity: default_boundary_similarity(),
+            chunk_join: ChunkJoin::default(),
+            skip_non_speech: false,
+            chunk_cache: false,
+            carry_decoder_state: false,
+            language_prior: None,
+            power_mode: PowerMode::default(),
+            initial_prompt: None,
+            progress: None,
+            journal: None,
+            debug_dump: None,
+            simulated_delay: None,
         }
     }
 
@@ -47,4 +158,115 @@ impl DecodingConfig {
         self.blank_penalty = blank_penalty.max(0.0).min(15.0);
         self
     }
+
+    /// Create a config with the blank penalty and temperature of a language
+    pub fn with_language_decoding(self, params: LanguageDecoding) -> Self {
+        self.with_blank_penalty(params.blank_penalty)
+            .with_temperature(params.temperature)
+    }
+
+    /// Create a config with custom max symbols per frame
+    pub fn with_max_symbols_per_frame(mut self, max_symbols_per_frame: usize) -> Self {
+        self.max_symbols_per_frame = max_symbols_per_frame.max(1);
+        self
+    }
+
+    /// Create a config with custom chunk boundary dedup similarity
+    pub fn with_boundary_similarity(mut self, boundary_similarity: f32) -> Self {
+        self.boundary_similarity = boundary_similarity.max(0.0).min(1.0);
+        self
+    }
+
+    /// Create a config joining chunks with `chunk_join`
+    pub fn with_chunk_join(mut self, chunk_join: ChunkJoin) -> Self {
+        self.chunk_join = chunk_join;
+        self
+    }
+
+    /// Create a config that skips music/noise chunks
+    pub fn with_skip_non_speech(mut self, skip_non_speech: bool) -> Self {
+        self.skip_non_speech = skip_non_speech;
+        self
+    }
+
+    /// Create a config that reuses cached chunk results
+    pub fn with_chunk_cache(mut self, chunk_cache: bool) -> Self {
+        self.chunk_cache = chunk_cache;
+        self
+    }
+
+    /// Create a config decoding long audio with the state carried across chunks
+    pub fn with_carry_decoder_state(mut self, carry_decoder_state: bool) -> Self {
+        self.carry_decoder_state = carry_decoder_state;
+        self
+    }
+
+    /// Create a config favoring `language` when the language is Auto
+    pub fn with_language_prior(mut self, language_prior: Option<TranscriptionLanguage>) -> Self {
+        self.language_prior = language_prior;
+        self
+    }
+
+    /// Create a config with the given battery behavior
+    pub fn with_power_mode(mut self, power_mode: PowerMode) -> Self {
+        self.power_mode = power_mode;
+        self
+    }
+
+    /// Create a config passing `initial_prompt` to the engines that take one
+    pub fn with_initial_prompt(mut self, initial_prompt: Option<String>) -> Self {
+        self.initial_prompt = initial_prompt.filter(|p| !p.trim().is_empty());
+        self
+    }
+
+    /// Create a config reporting chunk progress to `progress`
+    pub fn with_progress(mut self, progress: ProgressSink) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Create a config saving chunk results in `journal`
+    pub fn with_journal(mut self, journal: ChunkJournal) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Create a config dumping model I/O into `debug_dump`
+    pub fn with_debug_dump(mut self, debug_dump: DebugDump) -> Self {
+        self.debug_dump = Some(debug_dump);
+        self
+    }
+
+    /// Create a config sleeping `delay` per inference (per chunk for long audio)
+    pub fn with_simulated_delay(mut self, delay: Duration) -> Self {
+        self.simulated_delay = Some(delay);
+        self
+    }
+
+    /// Low-resource simulation: wait before an inference, when configured
+    pub fn simulate_slow_inference(&self) {
+        if let Some(delay) = self.simulated_delay {
+            thread::sleep(delay);
+        }
+    }
+
+    /// Frames to advance after a decode step predicting `duration`
+    ///
+    /// TDT can predict duration 0 (stay on the same frame). After
+    /// `max_symbols_per_frame` such steps on one frame, time is forced forward
+    /// by one frame. Returns the advance and whether the guard triggered.
+    pub fn frame_advance(&self, duration: usize, symbols_at_frame: &mut usize) -> (usize, bool) {
+        if duration > 0 {
+            *symbols_at_frame = 0;
+            return (duration, false);
+        }
+
+        *symbols_at_frame += 1;
+        if *symbols_at_frame >= self.max_symbols_per_frame.max(1) {
+            *symbols_at_frame = 0;
+            (1, true)
+        } else {
+            (0, false)
+        }
+    }
 }
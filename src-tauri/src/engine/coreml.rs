@@ -115,11 +115,7 @@ impl CoreMLEngine {
             .ok_or_else(|| AppError::Transcription("Model directory not set".to_string()))?;
 
         // Convert language to CLI argument
-        let language_str = match language {
-            TranscriptionLanguage::Auto => "auto",
-            TranscriptionLanguage::French => "french",
-            TranscriptionLanguage::English => "english",
-        };
+        let language_str = language.code().unwrap_or("auto");
 
         debug!(
             "Calling sidecar: {:?} {:?} --models {:?} --language {} --beam-width {} --temperature {} --blank-penalty {}",
@@ -221,6 +217,7 @@ impl ASREngine for CoreMLEngine {
             config.temperature,
             config.blank_penalty
         );
+        config.simulate_slow_inference();
 
         // Write audio to temp file
         let temp_wav = self.write_temp_wav(samples)?;
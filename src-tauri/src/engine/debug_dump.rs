@@ -0,0 +1,134 @@
+//! Model I/O dumps, to reproduce model-level bugs outside the app
+//!
+//! With the `debug_dump` setting, each transcription writes into its own
+//! timestamped folder under `<app data>/com.wakascribe.app/debug_dumps`:
+//! - `audio.wav`: the normalized 16 kHz audio given to the engine
+//! - per inference (one per chunk) `NNN_audio.wav`, `NNN_mel.f32` (raw
+//!   little-endian f32, in the layout fed to the encoder) and `NNN.json`
+//!   (mel shape, encoder lengths, token/duration sequence)
+//!
+//! Writing a dump never fails the transcription: errors are only logged.
+
+use crate::audio::write_wav;
+use crate::engine::timing::TimedToken;
+use crate::error::{AppError, Result};
+use serde_json::json;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+fn dumps_dir() -> Option<PathBuf> {
+    crate::app_data_dir().map(|p| p.join("com.wakascribe.app").join("debug_dumps"))
+}
+
+/// Intermediate values of one inference
+pub struct InferenceArtifacts<'a> {
+    pub audio: &'a [f32],
+    pub mel: &'a [f32],
+    /// Frames of the mel tensor, and those backed by real audio
+    pub mel_frames: usize,
+    pub valid_mel_frames: usize,
+    /// Time steps of the encoder output, and the valid ones
+    pub encoder_frames: usize,
+    pub valid_encoder_frames: usize,
+    pub tokens: &'a [TimedToken],
+}
+
+/// Folder receiving the artifacts of one transcription
+#[derive(Clone)]
+pub struct DebugDump(Arc<DumpDir>);
+
+struct DumpDir {
+    dir: PathBuf,
+    inferences: AtomicUsize,
+}
+
+impl DebugDump {
+    /// Create a new timestamped folder
+    pub fn create() -> Result<Self> {
+        let root = dumps_dir()
+            .ok_or_else(|| AppError::NotFound("App data directory not found".into()))?;
+        let dir = root.join(chrono::Local::now().format("%Y%m%d-%H%M%S-%3f").to_string());
+        fs::create_dir_all(&dir)?;
+        info!("Dumping model I/O to {:?}", dir);
+        Ok(Self(Arc::new(DumpDir {
+            dir,
+            inferences: AtomicUsize::new(0),
+        })))
+    }
+
+    /// Folder created by the app, filled by the worker process (isolated
+    /// mode) from inference `next_inference` on
+    pub fn open(dir: PathBuf, next_inference: usize) -> Self {
+        Self(Arc::new(DumpDir {
+            dir,
+            inferences: AtomicUsize::new(next_inference),
+        }))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.0.dir
+    }
+
+    /// Index of the next inference
+    pub fn next_inference(&self) -> usize {
+        self.0.inferences.load(Ordering::SeqCst)
+    }
+
+    /// Count the inferences dumped by the worker process
+    pub fn advance(&self, inferences: usize) {
+        self.0.inferences.fetch_add(inferences, Ordering::SeqCst);
+    }
+
+    /// Audio of the whole transcription, after normalization
+    pub fn audio(&self, samples: &[f32]) {
+        if let Err(e) = write_wav(samples, &self.dir().join("audio.wav")) {
+            warn!("Failed to dump audio: {}", e);
+        }
+    }
+
+    /// Artifacts of the next inference
+    pub fn inference(&self, artifacts: &InferenceArtifacts) {
+        let index = self.0.inferences.fetch_add(1, Ordering::SeqCst);
+        if let Err(e) = self.write_inference(index, artifacts) {
+            warn!("Failed to dump inference {}: {}", index, e);
+        }
+    }
+
+    fn write_inference(&self, index: usize, artifacts: &InferenceArtifacts) -> Result<()> {
+        let prefix = format!("{:03}", index);
+        write_wav(artifacts.audio, &self.dir().join(format!("{}_audio.wav", prefix)))?;
+
+        let mel: Vec<u8> = artifacts.mel.iter().flat_map(|v| v.to_le_bytes()).collect();
+        fs::write(self.dir().join(format!("{}_mel.f32", prefix)), mel)?;
+
+        let mel_bins = artifacts.mel.len() / artifacts.mel_frames.max(1);
+        let tokens: Vec<_> = artifacts
+            .tokens
+            .iter()
+            .map(|t| json!({ "id": t.id, "frame": t.frame, "duration": t.duration }))
+            .collect();
+        let metadata = json!({
+            "audioSamples": artifacts.audio.len(),
+            "melShape": [mel_bins, artifacts.mel_frames],
+            "validMelFrames": artifacts.valid_mel_frames,
+            "encoderFrames": artifacts.encoder_frames,
+            "validEncoderFrames": artifacts.valid_encoder_frames,
+            "tokens": tokens,
+        });
+        fs::write(
+            self.dir().join(format!("{}.json", prefix)),
+            serde_json::to_string_pretty(&metadata)?,
+        )?;
+        Ok(())
+    }
+}
+
+impl fmt::Debug for DebugDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DebugDump").field(&self.0.dir).finish()
+    }
+}
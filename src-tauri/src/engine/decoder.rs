@@ -1,3 +1,4 @@
+use crate::engine::timing::ENCODER_FRAME_MS;
 use crate::error::{AppError, Result};
 use crate::storage::Segment;
 use std::collections::HashMap;
@@ -114,6 +115,11 @@ impl Vocabulary {
         self.tokens.get(id).map(|s| s.as_str()).unwrap_or("<unk>")
     }
 
+    /// Id of a token, e.g. a language tag ("<|fr|>")
+    pub fn id_of(&self, token: &str) -> Option<usize> {
+        self.token_to_id.get(token).copied()
+    }
+
     pub fn vocab_size(&self) -> usize {
         self.tokens.len()
     }
@@ -124,6 +130,30 @@ impl Vocabulary {
     }
 }
 
+/// Probability of `token` under the softmax of the token logits, with the
+/// temperature scaling and blank penalty of the decoding step
+pub fn token_posterior(
+    token_logits: &[f32],
+    token: usize,
+    temperature: f32,
+    blank_id: usize,
+    blank_penalty: f32,
+) -> f32 {
+    let temp = if temperature > 0.0 { temperature } else { 1.0 };
+    let adjusted = |i: usize| {
+        let scaled = token_logits[i] / temp;
+        if i == blank_id {
+            scaled - blank_penalty
+        } else {
+            scaled
+        }
+    };
+    // Shifted by the max so exp() can't overflow
+    let max = (0..token_logits.len()).map(adjusted).fold(f32::NEG_INFINITY, f32::max);
+    let sum: f32 = (0..token_logits.len()).map(|i| (adjusted(i) - max).exp()).sum();
+    (adjusted(token) - max).exp() / sum
+}
+
 /// TDT Decoder for converting model outputs to text
 pub struct TDTDecoder {
     vocab: Vocabulary,
@@ -139,10 +169,13 @@ impl TDTDecoder {
     }
 
     /// Decode token IDs to text segments with timestamps
+    ///
+    /// `posteriors` gives the probability of each token (see `token_posterior`).
     pub fn decode(
         &self,
         token_ids: &[i64],
         durations: &[i64],
+        posteriors: &[f32],
         sample_rate: u32,
         hop_length: usize,
         subsampling_factor: usize,
@@ -177,7 +210,7 @@ impl TDTDecoder {
                 current_text.push_str(token_text);
             }
 
-            confidence_sum += 0.9;
+            confidence_sum += posteriors.get(i).copied().unwrap_or(0.0) as f64;
             token_count += 1;
             current_frame += duration;
         }
@@ -193,8 +226,11 @@ impl TDTDecoder {
                 confidence: if token_count > 0 {
                     confidence_sum / token_count as f64
                 } else {
-                    0.9
+                    0.0
                 },
+                speaker: None,
+                language: None,
+                words: Vec::new(),
             });
         }
 
@@ -202,15 +238,17 @@ impl TDTDecoder {
     }
 
     /// Simple greedy decode without durations
-    pub fn greedy_decode(&self, token_ids: &[i64], duration_ms: i64) -> Vec<Segment> {
+    pub fn greedy_decode(&self, token_ids: &[i64], posteriors: &[f32], duration_ms: i64) -> Vec<Segment> {
         let mut text = String::new();
+        let mut scores = Vec::new();
 
-        for &token_id in token_ids {
+        for (i, &token_id) in token_ids.iter().enumerate() {
             let token_id = token_id as usize;
 
             if self.vocab.is_special_token(token_id) {
                 continue;
             }
+            scores.push(posteriors.get(i).copied().unwrap_or(0.0) as f64);
 
             let token_text = self.vocab.decode_token(token_id);
 
@@ -232,7 +270,10 @@ impl TDTDecoder {
             start_ms: 0,
             end_ms: duration_ms,
             text,
-            confidence: 0.9,
+            confidence: scores.iter().sum::<f64>() / scores.len().max(1) as f64,
+            speaker: None,
+            language: None,
+            words: Vec::new(),
         }]
     }
 
@@ -246,3 +287,61 @@ impl TDTDecoder {
         token.replace("▁", " ")
     }
 }
+
+/// LSTM state where a chunk stopped decoding
+#[derive(Debug, Clone)]
+pub struct DecoderState {
+    pub h: Vec<f32>,
+    pub c: Vec<f32>,
+    pub last_token: i64,
+    /// Frames at the start of the next chunk already covered by the last token
+    pub skip_frames: usize,
+}
+
+/// Decoding handed over from one chunk to the next (`DecodingConfig::carry_decoder_state`)
+///
+/// Each chunk stops where the next one starts, so the overlap is decoded
+/// once, and leaves its decoder state: the next chunk resumes mid-sentence,
+/// or mid-word, instead of starting from silence.
+#[derive(Debug, Default)]
+pub struct ChunkCarry {
+    /// State left by the previous chunk (None: start fresh, with the language tokens)
+    pub state: Option<DecoderState>,
+    /// Encoder frame where the next chunk starts (None on the last chunk)
+    pub stop_frame: Option<usize>,
+    /// The chunk's first token continues the last word of the previous one
+    pub continues_word: bool,
+}
+
+impl ChunkCarry {
+    /// Stop the chunk `next_start_ms` after its start, where the next one begins
+    pub fn stop_at(&mut self, next_start_ms: Option<i64>) {
+        self.stop_frame = next_start_ms.map(|ms| (ms.max(0) / ENCODER_FRAME_MS) as usize);
+    }
+
+    /// Start the next chunk fresh (previous one skipped, cached or failed)
+    pub fn reset(&mut self) {
+        self.state = None;
+        self.continues_word = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_posterior() {
+        let logits = [2.0, 1.0, 0.0];
+        let p0 = token_posterior(&logits, 0, 1.0, 2, 0.0);
+        let total: f32 = (0..3).map(|i| token_posterior(&logits, i, 1.0, 2, 0.0)).sum();
+        assert!((total - 1.0).abs() < 1e-6);
+        assert!((p0 - 0.665).abs() < 1e-3);
+
+        // Penalizing the blank raises the others' share, a higher temperature flattens
+        assert!(token_posterior(&logits, 0, 1.0, 2, 5.0) > p0);
+        assert!(token_posterior(&logits, 0, 2.0, 2, 0.0) < p0);
+        // Large logits don't overflow
+        assert!((token_posterior(&[1000.0, 0.0], 0, 1.0, 1, 0.0) - 1.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,163 @@
+//! Processing time and memory of a job, predicted before running it
+//!
+//! Each transcription of a few seconds or more records its speed (audio
+//! duration / processing time) for the backend and beam width used. The
+//! estimate takes the median speed of the most recent runs with the same beam
+//! width, or of any recent run on the backend when there are too few, and a
+//! conservative default before the first run. Memory is the model files plus
+//! the audio buffers of a file job (decoded, resampled, normalized) and the
+//! working set of one chunk.
+
+use crate::engine::EngineBackend;
+use crate::storage::{self, with_db, InferenceSpeed};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::{debug, warn};
+
+/// Runs shorter than this are dominated by fixed costs and not recorded
+const MIN_RECORDED_AUDIO_MS: i64 = 5_000;
+
+/// Runs kept per backend
+const MAX_ENTRIES: usize = 50;
+
+/// Most recent runs the estimate is based on
+const RUNS_USED: usize = 20;
+
+/// Runs with the job's beam width needed before ignoring the others
+const MIN_MATCHING_RUNS: usize = 3;
+
+/// Encoder and decoder working set of one chunk (order of magnitude)
+const CHUNK_WORKING_SET_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Speed assumed before any run on the backend, on the slow side
+fn default_realtime_factor(backend: EngineBackend) -> f64 {
+    match backend {
+        EngineBackend::OpenVINO => 10.0,
+        EngineBackend::OnnxRuntime => 5.0,
+        #[cfg(target_os = "macos")]
+        EngineBackend::CoreML => 10.0,
+    }
+}
+
+/// Predicted cost of a job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobEstimate {
+    pub audio_ms: i64,
+    pub processing_ms: i64,
+    /// Audio processed per wall-clock time (2.0 = twice faster than realtime)
+    pub realtime_factor: f64,
+    pub memory_bytes: u64,
+    /// Past runs the speed comes from (0: default speed, no history yet)
+    pub based_on_runs: usize,
+}
+
+/// Record the speed of a finished transcription
+pub fn record(backend: EngineBackend, beam_width: usize, audio_ms: i64, elapsed_ms: i64) {
+    if audio_ms < MIN_RECORDED_AUDIO_MS || elapsed_ms <= 0 || storage::is_read_only() {
+        return;
+    }
+    let speed = InferenceSpeed { beam_width, audio_ms, elapsed_ms };
+    if let Err(e) = with_db(|conn| {
+        storage::insert_inference_speed(conn, backend.model_subdir(), &speed, MAX_ENTRIES)
+    }) {
+        warn!("Failed to record inference speed: {}", e);
+    }
+}
+
+/// Recorded speeds of `backend`, most recent first (empty if unreadable)
+pub fn history(backend: EngineBackend) -> Vec<InferenceSpeed> {
+    with_db(|conn| storage::list_inference_speeds(conn, backend.model_subdir())).unwrap_or_else(|e| {
+        debug!("Inference speed history unavailable: {}", e);
+        Vec::new()
+    })
+}
+
+/// Estimate a job of `audio_ms` (read at `sample_rate`) from the backend's `history`
+pub fn estimate(
+    audio_ms: i64,
+    sample_rate: u32,
+    backend: EngineBackend,
+    beam_width: usize,
+    history: &[InferenceSpeed],
+    model_bytes: u64,
+) -> JobEstimate {
+    let recent = &history[..history.len().min(RUNS_USED)];
+    let matching: Vec<InferenceSpeed> =
+        recent.iter().filter(|s| s.beam_width == beam_width).copied().collect();
+    let runs = if matching.len() >= MIN_MATCHING_RUNS { &matching[..] } else { recent };
+
+    let realtime_factor = median(runs.iter().map(|s| s.audio_ms as f64 / s.elapsed_ms.max(1) as f64))
+        .unwrap_or_else(|| default_realtime_factor(backend));
+
+    // Decoded audio at its own rate, then resampled and normalized at 16kHz, as f32
+    let audio_seconds = audio_ms.max(0) as f64 / 1000.0;
+    let audio_bytes = (audio_seconds * (sample_rate as f64 + 2.0 * 16_000.0) * 4.0) as u64;
+
+    JobEstimate {
+        audio_ms,
+        processing_ms: (audio_ms.max(0) as f64 / realtime_factor).round() as i64,
+        realtime_factor,
+        memory_bytes: model_bytes + audio_bytes + CHUNK_WORKING_SET_BYTES,
+        based_on_runs: runs.len(),
+    }
+}
+
+fn median(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let mut values: Vec<f64> = values.filter(|v| v.is_finite() && *v > 0.0).collect();
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    Some(if values.len() % 2 == 1 {
+        values[mid]
+    } else {
+        (values[mid - 1] + values[mid]) / 2.0
+    })
+}
+
+/// Total size of the files under `dir` (model files loaded in memory)
+pub fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(beam_width: usize, realtime_factor: i64) -> InferenceSpeed {
+        InferenceSpeed { beam_width, audio_ms: 60_000 * realtime_factor, elapsed_ms: 60_000 }
+    }
+
+    #[test]
+    fn test_estimate_prefers_runs_with_same_beam_width() {
+        let two_hours = 2 * 3600 * 1000;
+        let history = [run(1, 30), run(4, 10), run(4, 12), run(4, 100), run(1, 30)];
+
+        // Three beam-4 runs: median 12x, the fast outlier and greedy runs are ignored
+        let job = estimate(two_hours, 16_000, EngineBackend::OpenVINO, 4, &history, 0);
+        assert_eq!(job.based_on_runs, 3);
+        assert_eq!(job.processing_ms, two_hours / 12);
+
+        // Too few greedy runs: all recent runs count, median 30x
+        let job = estimate(two_hours, 16_000, EngineBackend::OpenVINO, 1, &history[..4], 0);
+        assert_eq!(job.based_on_runs, 4);
+        assert_eq!(job.processing_ms, two_hours / 21);
+
+        let job = estimate(60_000, 48_000, EngineBackend::OnnxRuntime, 1, &[], 1000);
+        assert_eq!(job.based_on_runs, 0);
+        assert_eq!(job.processing_ms, 12_000);
+        assert_eq!(job.memory_bytes, 1000 + 60 * 80_000 * 4 + CHUNK_WORKING_SET_BYTES);
+    }
+}
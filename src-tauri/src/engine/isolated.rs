@@ -0,0 +1,407 @@
+//! OpenVINO inference in a separate process
+//!
+//! Some driver stacks make OpenVINO abort the whole process. In isolated
+//! mode the pipeline runs in the `openvino-worker` binary, next to the app
+//! executable: a crash of the worker becomes an `AppError::EngineCrashed`
+//! for the current job, and the worker is started again on the next call.
+//!
+//! The worker gets the model directory and the `OpenVinoPerformance` (JSON)
+//! as arguments. Release builds bundle it as a sidecar (`externalBin` in
+//! `tauri.bundle.conf.json`, built by `scripts/build-worker.mjs`). Protocol over the worker's stdin/stdout: each request is a
+//! JSON line followed by the samples as little-endian f32; each reply is a
+//! JSON line.
+//! The worker answers `ready` (or `error`) once the model is loaded, then
+//! any number of `progress` and `chunk` lines (results for the chunk journal)
+//! and a final `done` or `error` per request.
+//! Its logs go to stderr.
+
+use crate::engine::config::DecodingConfig;
+use crate::engine::debug_dump::DebugDump;
+use crate::engine::partial::ChunkJournal;
+use crate::engine::parakeet::OpenVinoPerformance;
+use crate::engine::progress::ChunkProgress;
+use crate::engine::telemetry::DecodeTelemetry;
+use crate::engine::timing::WordTiming;
+use crate::engine::{ASREngine, TranscriptionLanguage};
+use crate::error::{AppError, Result};
+use crate::storage::PendingChunk;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// File name of the worker binary, without the platform suffix
+pub const WORKER_NAME: &str = "openvino-worker";
+
+/// Header of a request, followed by `samples` f32 values
+///
+/// The fields of `DecodingConfig` set by the caller are not serialized with
+/// it: the progress and the journaled chunks come back as replies, the debug
+/// dump, the journal's saved chunks and the simulated delay travel in their
+/// own fields.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkerRequest {
+    pub language: TranscriptionLanguage,
+    pub config: DecodingConfig,
+    pub samples: usize,
+    /// Dump folder and index of the next inference
+    #[serde(default)]
+    pub debug_dump: Option<(PathBuf, usize)>,
+    #[serde(default)]
+    pub simulated_delay_ms: Option<u64>,
+    /// Pending transcription id and chunks saved by an interrupted run
+    #[serde(default)]
+    pub journal: Option<(String, Vec<PendingChunk>)>,
+}
+
+impl WorkerRequest {
+    pub fn new(language: TranscriptionLanguage, config: &DecodingConfig, samples: usize) -> Self {
+        Self {
+            language,
+            config: config.clone(),
+            samples,
+            debug_dump: config
+                .debug_dump
+                .as_ref()
+                .map(|dump| (dump.dir().to_path_buf(), dump.next_inference())),
+            simulated_delay_ms: config.simulated_delay.map(|d| d.as_millis() as u64),
+            journal: config
+                .journal
+                .as_ref()
+                .map(|journal| (journal.pending_id().to_string(), journal.saved_chunks().to_vec())),
+        }
+    }
+
+    /// The decoding config with the debug dump and simulated delay restored;
+    /// with a journal, new chunk results go to `forward`
+    pub fn decoding_config(&self, forward: impl Fn(usize, PendingChunk) + Send + Sync + 'static) -> DecodingConfig {
+        let mut config = self.config.clone();
+        config.debug_dump = self
+            .debug_dump
+            .clone()
+            .map(|(dir, next_inference)| DebugDump::open(dir, next_inference));
+        config.simulated_delay = self.simulated_delay_ms.map(Duration::from_millis);
+        config.journal = self
+            .journal
+            .clone()
+            .map(|(pending_id, saved)| ChunkJournal::forwarding(&pending_id, saved, forward));
+        config
+    }
+}
+
+/// Line written by the worker
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum WorkerReply {
+    Ready,
+    Progress(ChunkProgress),
+    /// Result of one chunk, for the journal
+    Chunk { chunk_count: usize, chunk: PendingChunk },
+    Done {
+        text: String,
+        words: Option<Vec<WordTiming>>,
+        telemetry: Option<DecodeTelemetry>,
+        /// Inferences written to the debug dump
+        #[serde(default)]
+        dumped: usize,
+    },
+    Error { message: String },
+}
+
+/// Write a request: its header line, then the raw samples
+pub fn write_request(out: &mut impl Write, request: &WorkerRequest, samples: &[f32]) -> Result<()> {
+    serde_json::to_writer(&mut *out, request)?;
+    out.write_all(b"\n")?;
+    let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+    out.write_all(&bytes)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Read a request, None once the parent closed the pipe
+pub fn read_request(input: &mut impl BufRead) -> Result<Option<(WorkerRequest, Vec<f32>)>> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    let request: WorkerRequest = serde_json::from_str(&line)?;
+    let mut bytes = vec![0u8; request.samples * 4];
+    input.read_exact(&mut bytes)?;
+    let samples = bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+    Ok(Some((request, samples)))
+}
+
+/// Write one reply line
+pub fn write_reply(out: &mut impl Write, reply: &WorkerReply) -> Result<()> {
+    serde_json::to_writer(&mut *out, reply)?;
+    out.write_all(b"\n")?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Running worker process
+struct Worker {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Worker {
+    fn spawn(binary: &Path, model_dir: &Path, performance: &OpenVinoPerformance) -> Result<Self> {
+        let mut child = Command::new(binary)
+            .arg(model_dir)
+            .arg(serde_json::to_string(performance)?)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| AppError::Transcription(format!("Failed to start OpenVINO worker: {}", e)))?;
+
+        let stdin = child.stdin.take().expect("piped stdin");
+        let stdout = BufReader::new(child.stdout.take().expect("piped stdout"));
+        let mut worker = Self { child, stdin, stdout };
+
+        match worker.next_reply()? {
+            WorkerReply::Ready => Ok(worker),
+            WorkerReply::Error { message } => Err(AppError::Transcription(message)),
+            other => Err(AppError::Transcription(format!("Unexpected worker reply: {:?}", other))),
+        }
+    }
+
+    /// Next reply line; a closed pipe means the worker died
+    fn next_reply(&mut self) -> Result<WorkerReply> {
+        let mut line = String::new();
+        let read = self.stdout.read_line(&mut line);
+        if !matches!(read, Ok(n) if n > 0) {
+            return Err(self.crashed());
+        }
+        serde_json::from_str(&line)
+            .map_err(|e| AppError::Transcription(format!("Invalid worker reply: {} - raw: {}", e, line.trim())))
+    }
+
+    fn crashed(&mut self) -> AppError {
+        let _ = self.child.kill();
+        let status = match self.child.wait() {
+            Ok(status) => status.to_string(),
+            Err(e) => e.to_string(),
+        };
+        AppError::EngineCrashed(format!("OpenVINO worker stopped ({})", status))
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// OpenVINO engine running in the `openvino-worker` process
+pub struct IsolatedOpenVinoEngine {
+    model_dir: Option<PathBuf>,
+    performance: OpenVinoPerformance,
+    worker: Mutex<Option<Worker>>,
+    last_words: Mutex<Option<Vec<WordTiming>>>,
+    last_telemetry: Mutex<Option<DecodeTelemetry>>,
+}
+
+impl IsolatedOpenVinoEngine {
+    pub fn new() -> Self {
+        Self {
+            model_dir: None,
+            performance: OpenVinoPerformance::default(),
+            worker: Mutex::new(None),
+            last_words: Mutex::new(None),
+            last_telemetry: Mutex::new(None),
+        }
+    }
+
+    /// Compile the models in the worker with these CPU settings
+    pub fn with_performance(mut self, performance: OpenVinoPerformance) -> Self {
+        self.performance = performance;
+        self
+    }
+
+    /// Worker binary next to the app executable
+    fn worker_binary() -> Result<PathBuf> {
+        let exe = std::env::current_exe()?;
+        let path = exe
+            .with_file_name(format!("{}{}", WORKER_NAME, std::env::consts::EXE_SUFFIX));
+        if path.exists() {
+            Ok(path)
+        } else {
+            Err(AppError::NotFound(format!("OpenVINO worker not found at {:?}", path)))
+        }
+    }
+
+    fn infer(
+        worker: &mut Worker,
+        samples: &[f32],
+        language: TranscriptionLanguage,
+        config: &DecodingConfig,
+    ) -> Result<(String, Option<Vec<WordTiming>>, Option<DecodeTelemetry>)> {
+        let request = WorkerRequest::new(language, config, samples.len());
+        if write_request(&mut worker.stdin, &request, samples).is_err() {
+            return Err(worker.crashed());
+        }
+
+        loop {
+            match worker.next_reply()? {
+                WorkerReply::Progress(progress) => {
+                    if let Some(sink) = &config.progress {
+                        sink.report(progress);
+                    }
+                }
+                WorkerReply::Chunk { chunk_count, chunk } => {
+                    if let Some(journal) = &config.journal {
+                        journal.record(chunk_count, chunk);
+                    }
+                }
+                WorkerReply::Done {
+                    text,
+                    words,
+                    telemetry,
+                    dumped,
+                } => {
+                    if let Some(dump) = &config.debug_dump {
+                        dump.advance(dumped);
+                    }
+                    return Ok((text, words, telemetry));
+                }
+                WorkerReply::Error { message } => return Err(AppError::Transcription(message)),
+                WorkerReply::Ready => {
+                    return Err(AppError::Transcription("Unexpected worker reply: ready".to_string()))
+                }
+            }
+        }
+    }
+}
+
+impl Default for IsolatedOpenVinoEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ASREngine for IsolatedOpenVinoEngine {
+    fn name(&self) -> &str {
+        "OpenVINO (isolated)"
+    }
+
+    fn is_loaded(&self) -> bool {
+        self.model_dir.is_some()
+    }
+
+    fn load_model(&mut self, model_dir: &Path) -> Result<()> {
+        info!("Starting OpenVINO worker, model_dir: {:?}", model_dir);
+        let worker = Worker::spawn(&Self::worker_binary()?, model_dir, &self.performance)?;
+        *self.worker.lock().unwrap() = Some(worker);
+        self.model_dir = Some(model_dir.to_path_buf());
+        info!("OpenVINO worker ready");
+        Ok(())
+    }
+
+    fn run_inference(
+        &self,
+        samples: &[f32],
+        language: TranscriptionLanguage,
+        config: &DecodingConfig,
+    ) -> Result<String> {
+        let model_dir = self
+            .model_dir
+            .as_ref()
+            .ok_or_else(|| AppError::Transcription("Model directory not set".to_string()))?;
+
+        let mut guard = self.worker.lock().unwrap();
+        if guard.is_none() {
+            info!("Restarting OpenVINO worker");
+            *guard = Some(Worker::spawn(&Self::worker_binary()?, model_dir, &self.performance)?);
+        }
+        let worker = guard.as_mut().expect("worker started above");
+
+        match Self::infer(worker, samples, language, config) {
+            Ok((text, words, telemetry)) => {
+                *self.last_words.lock().unwrap() = words;
+                *self.last_telemetry.lock().unwrap() = telemetry;
+                Ok(text)
+            }
+            Err(e) => {
+                if matches!(e, AppError::EngineCrashed(_)) {
+                    warn!("{}; it will be restarted on the next inference", e);
+                    *guard = None;
+                }
+                *self.last_words.lock().unwrap() = None;
+                *self.last_telemetry.lock().unwrap() = None;
+                Err(e)
+            }
+        }
+    }
+
+    fn last_telemetry(&self) -> Option<DecodeTelemetry> {
+        self.last_telemetry.lock().unwrap().clone()
+    }
+
+    fn last_word_timings(&self) -> Option<Vec<WordTiming>> {
+        self.last_words.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_round_trip() {
+        let request = |samples: usize| WorkerRequest::new(TranscriptionLanguage::French, &DecodingConfig::default(), samples);
+        let samples = [0.0, -0.5, 0.25, 1.0];
+        let mut pipe = Vec::new();
+        write_request(&mut pipe, &request(4), &samples).unwrap();
+        write_request(&mut pipe, &request(1), &samples[..1]).unwrap();
+
+        let mut input = std::io::Cursor::new(pipe);
+        let (first, read) = read_request(&mut input).unwrap().unwrap();
+        assert_eq!(first.language, TranscriptionLanguage::French);
+        assert_eq!(read, samples);
+        let (_, read) = read_request(&mut input).unwrap().unwrap();
+        assert_eq!(read, [0.0]);
+        assert!(read_request(&mut input).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_request_carries_caller_settings() {
+        let chunk = |index: usize, text: &str| PendingChunk {
+            index,
+            start_ms: index as i64 * 1000,
+            end_ms: (index as i64 + 1) * 1000,
+            text: text.to_string(),
+            continues_word: false,
+        };
+        let config = DecodingConfig::default()
+            .with_debug_dump(DebugDump::open(PathBuf::from("/tmp/dump"), 3))
+            .with_simulated_delay(Duration::from_millis(250))
+            .with_journal(ChunkJournal::new("p1", vec![chunk(0, "bonjour")]));
+        let mut pipe = Vec::new();
+        write_request(&mut pipe, &WorkerRequest::new(TranscriptionLanguage::Auto, &config, 0), &[]).unwrap();
+
+        let (request, _) = read_request(&mut std::io::Cursor::new(pipe)).unwrap().unwrap();
+        let forwarded = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let sink = forwarded.clone();
+        let restored = request.decoding_config(move |count, chunk| sink.lock().unwrap().push((count, chunk)));
+
+        let dump = restored.debug_dump.expect("debug dump forwarded");
+        assert_eq!((dump.dir(), dump.next_inference()), (Path::new("/tmp/dump"), 3));
+        assert_eq!(restored.simulated_delay, Some(Duration::from_millis(250)));
+
+        // The worker's journal resumes the saved chunks and sends the new ones back
+        let journal = restored.journal.expect("journal forwarded");
+        assert_eq!(journal.saved(0, 0, 1000), Some(&chunk(0, "bonjour")));
+        journal.record(2, chunk(1, "monde"));
+        assert_eq!(*forwarded.lock().unwrap(), [(2, chunk(1, "monde"))]);
+    }
+}
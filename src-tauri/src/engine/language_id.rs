@@ -0,0 +1,119 @@
+//! Language of a transcribed text
+//!
+//! In Auto mode Parakeet picks the language by itself but doesn't say which
+//! one. The language of the transcription (search filter, language packs,
+//! exports) and of each segment is then identified from the text: function
+//! words ("le", "the") make up a large part of any sentence and hardly
+//! overlap between French and English; accented letters count for French.
+
+use crate::engine::TranscriptionLanguage;
+use crate::storage::Transcription;
+use tracing::info;
+
+const FRENCH_WORDS: &[&str] = &[
+    "le", "la", "les", "de", "des", "du", "un", "une", "et", "est", "que", "qui", "pas", "je", "vous",
+    "nous", "il", "elle", "ils", "ce", "cette", "dans", "pour", "sur", "avec", "mais", "au", "aux",
+    "ses", "ne", "tout", "sont", "ça", "suis", "mon", "ma", "mes", "leur", "donc", "alors", "aussi",
+];
+
+const ENGLISH_WORDS: &[&str] = &[
+    "the", "an", "and", "is", "are", "was", "were", "to", "of", "in", "that", "it", "this", "you",
+    "we", "they", "he", "she", "not", "for", "with", "but", "have", "has", "be", "at", "from", "by",
+    "or", "what", "there", "my", "your", "so", "do", "will", "would", "can",
+];
+
+/// French elisions ("l'", "c'est") and English contractions ("don't", "it's")
+const FRENCH_ELISIONS: &[&str] = &["l", "d", "j", "qu", "c", "n", "s", "m", "t", "jusqu", "lorsqu"];
+const ENGLISH_CONTRACTIONS: &[&str] = &["t", "s", "re", "ll", "ve", "m", "d"];
+
+/// Fewer hints than this: too short to tell
+const MIN_HINTS: usize = 2;
+
+/// Share of the hints the winning language needs
+const MIN_SHARE: f32 = 0.65;
+
+/// Language of `text`, None when too short or mixed
+pub fn detect(text: &str) -> Option<TranscriptionLanguage> {
+    let (mut french, mut english) = (0usize, 0usize);
+    for word in text
+        .split(|c: char| !c.is_alphabetic() && c != '\'' && c != '’')
+        .filter(|w| !w.is_empty())
+    {
+        let word = word.to_lowercase().replace('’', "'");
+        if let Some((head, tail)) = word.split_once('\'') {
+            if FRENCH_ELISIONS.contains(&head) {
+                french += 1;
+            } else if ENGLISH_CONTRACTIONS.contains(&tail) {
+                english += 1;
+            }
+        } else if FRENCH_WORDS.contains(&word.as_str()) {
+            french += 1;
+        } else if ENGLISH_WORDS.contains(&word.as_str()) {
+            english += 1;
+        }
+        if word.contains(['é', 'è', 'ê', 'à', 'ç', 'ù', 'û', 'î', 'ô', 'œ']) {
+            french += 1;
+        }
+    }
+
+    let total = french + english;
+    if total < MIN_HINTS {
+        return None;
+    }
+    if french as f32 >= MIN_SHARE * total as f32 {
+        Some(TranscriptionLanguage::French)
+    } else if english as f32 >= MIN_SHARE * total as f32 {
+        Some(TranscriptionLanguage::English)
+    } else {
+        None
+    }
+}
+
+/// Set the language of `t` and, in Auto mode, of each of its segments
+///
+/// A forced language is taken as is. In Auto mode, a text that can't be
+/// told is given `prior` (the favored language), else French.
+pub fn label(t: &mut Transcription, language: TranscriptionLanguage, prior: Option<TranscriptionLanguage>) {
+    if let Some(code) = language.code() {
+        t.language = code.to_string();
+        return;
+    }
+
+    for segment in &mut t.segments {
+        segment.language = detect(&segment.text).and_then(|l| l.code()).map(str::to_string);
+    }
+    let detected = detect(&t.raw_text);
+    let language = detected.or(prior).unwrap_or(TranscriptionLanguage::French);
+    t.language = language.code().unwrap_or("fr").to_string();
+    info!(
+        "Language {} ({})",
+        t.language,
+        if detected.is_some() { "detected" } else { "not detected, default" }
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_french_and_english() {
+        assert_eq!(
+            detect("Bonjour à tous, on commence la réunion par le budget de l'année."),
+            Some(TranscriptionLanguage::French)
+        );
+        assert_eq!(
+            detect("Hi everyone, let's start the meeting with the budget for this year."),
+            Some(TranscriptionLanguage::English)
+        );
+        assert_eq!(detect("C’est parti, j’arrive."), Some(TranscriptionLanguage::French));
+        assert_eq!(detect("I don't know, it's fine."), Some(TranscriptionLanguage::English));
+    }
+
+    #[test]
+    fn test_too_short_or_mixed_is_undetected() {
+        assert_eq!(detect("OK"), None);
+        assert_eq!(detect(""), None);
+        assert_eq!(detect("le the la and"), None);
+    }
+}
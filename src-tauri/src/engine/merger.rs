@@ -2,7 +2,15 @@
 //!
 //! Merges transcription results from overlapping audio chunks,
 //! handling deduplication in overlap regions.
+//!
+//! `join_chunk_texts` is used by the backends: smart chunks overlap by
+//! ~0.5s, so the last words of a chunk are often repeated (sometimes with
+//! slightly different spelling) at the start of the next one. It also
+//! restores sentence boundaries: a long silence between two chunks ends the
+//! sentence, and a chunk following a sentence end starts with a capital.
 
+use crate::logs::TRANSCRIPT_TARGET;
+use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 
 /// A transcribed chunk with timing information
@@ -58,12 +66,12 @@ pub fn merge_transcriptions(chunks: &[ChunkTranscription], overlap_ms: i64) -> S
         if i == 0 {
             // First chunk: take all text, but may need to trim end for overlap
             let trimmed = trim_overlap_end(text, overlap_ms, chunk.end_ms - chunk.start_ms);
-            debug!("Chunk {}: using '{}' (trimmed end)", i, trimmed);
+            debug!(target: TRANSCRIPT_TARGET, "Chunk {}: using {:?} (trimmed end)", i, trimmed);
             merged_parts.push(trimmed);
         } else if i == chunks.len() - 1 {
             // Last chunk: skip beginning overlap, take rest
             let trimmed = trim_overlap_start(text, overlap_ms, chunk.end_ms - chunk.start_ms);
-            debug!("Chunk {}: using '{}' (trimmed start)", i, trimmed);
+            debug!(target: TRANSCRIPT_TARGET, "Chunk {}: using {:?} (trimmed start)", i, trimmed);
             merged_parts.push(trimmed);
         } else {
             // Middle chunks: trim both ends
@@ -74,7 +82,7 @@ pub fn merge_transcriptions(chunks: &[ChunkTranscription], overlap_ms: i64) -> S
                 overlap_ms / 2,
                 chunk.end_ms - chunk.start_ms,
             );
-            debug!("Chunk {}: using '{}' (trimmed both)", i, trimmed);
+            debug!(target: TRANSCRIPT_TARGET, "Chunk {}: using {:?} (trimmed both)", i, trimmed);
             merged_parts.push(trimmed);
         }
     }
@@ -133,6 +141,183 @@ fn trim_overlap_start(text: &str, overlap_ms: i64, chunk_duration_ms: i64) -> St
     words[words_to_trim..].join(" ")
 }
 
+/// Default similarity for boundary dedup (0 = disabled, 1 = exact match only)
+pub const DEFAULT_BOUNDARY_SIMILARITY: f32 = 0.8;
+
+/// Longest boundary phrase (in words) compared between consecutive chunks
+const MAX_BOUNDARY_WORDS: usize = 8;
+
+/// Minimum length of a single repeated word to be considered a duplicate
+/// (avoids dropping legitimate short repeats like "de de" or "the the")
+const MIN_SINGLE_WORD_CHARS: usize = 4;
+
+/// Default silence between chunks (ms) after which a sentence end is assumed
+pub const DEFAULT_SENTENCE_GAP_MS: i64 = 1000;
+
+/// How consecutive chunk transcriptions are concatenated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkJoin {
+    /// Inserted between chunks
+    #[serde(default = "default_separator")]
+    pub separator: String,
+    /// Silence (ms) between chunks after which a period is added if missing (0 = off)
+    #[serde(default = "default_sentence_gap_ms")]
+    pub sentence_gap_ms: i64,
+}
+
+fn default_separator() -> String {
+    " ".to_string()
+}
+
+fn default_sentence_gap_ms() -> i64 {
+    DEFAULT_SENTENCE_GAP_MS
+}
+
+impl Default for ChunkJoin {
+    fn default() -> Self {
+        Self {
+            separator: default_separator(),
+            sentence_gap_ms: default_sentence_gap_ms(),
+        }
+    }
+}
+
+/// Transcription of one chunk and the silence preceding its speech
+#[derive(Debug, Clone)]
+pub struct ChunkText {
+    pub text: String,
+    /// Silence since the previous chunk's speech (ms)
+    pub silence_before_ms: i64,
+}
+
+/// Join chunk transcriptions, dropping near-duplicate phrases at each boundary
+///
+/// # Arguments
+/// * `chunks` - Chunk transcriptions in order
+/// * `similarity_threshold` - Minimum normalized Levenshtein similarity (0-1)
+///   between the end of a chunk and the start of the next to drop the repeat
+/// * `join` - Separator and sentence-boundary heuristics
+pub fn join_chunk_texts(chunks: &[ChunkText], similarity_threshold: f32, join: &ChunkJoin) -> String {
+    let mut merged = String::new();
+
+    for chunk in chunks {
+        let text = chunk.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        if merged.is_empty() {
+            merged.push_str(text);
+            continue;
+        }
+
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let skip = duplicated_prefix_words(&merged, &words, similarity_threshold);
+        if skip > 0 {
+            debug!(target: TRANSCRIPT_TARGET, "Boundary dedup: dropping {:?}", words[..skip].join(" "));
+        }
+        if skip < words.len() {
+            // Long pause without punctuation: the speaker most likely ended a sentence
+            let long_pause = join.sentence_gap_ms > 0 && chunk.silence_before_ms >= join.sentence_gap_ms;
+            if long_pause && merged.ends_with(char::is_alphanumeric) {
+                debug!("Sentence end inserted after {}ms of silence", chunk.silence_before_ms);
+                merged.push('.');
+            }
+
+            let rest = words[skip..].join(" ");
+            merged.push_str(&join.separator);
+            if ends_sentence(&merged) {
+                merged.push_str(&capitalize_first(&rest));
+            } else {
+                merged.push_str(&rest);
+            }
+        }
+    }
+
+    merged
+}
+
+/// Whether `text` ends with sentence-final punctuation (separator whitespace ignored)
+fn ends_sentence(text: &str) -> bool {
+    text.trim_end().ends_with(['.', '!', '?', '…'])
+}
+
+/// `text` with its first character uppercased
+fn capitalize_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Number of words at the start of `next` that repeat the end of `prev`
+fn duplicated_prefix_words(prev: &str, next: &[&str], similarity_threshold: f32) -> usize {
+    if similarity_threshold <= 0.0 {
+        return 0;
+    }
+
+    let prev_words: Vec<&str> = prev.split_whitespace().collect();
+    let max_words = MAX_BOUNDARY_WORDS.min(prev_words.len()).min(next.len());
+
+    // Prefer the longest matching phrase
+    for n in (1..=max_words).rev() {
+        let tail = normalize_phrase(&prev_words[prev_words.len() - n..]);
+        let head = normalize_phrase(&next[..n]);
+
+        if head.is_empty() || (n == 1 && head.chars().count() < MIN_SINGLE_WORD_CHARS) {
+            continue;
+        }
+        if similarity(&tail, &head) >= similarity_threshold {
+            return n;
+        }
+    }
+
+    0
+}
+
+/// Lowercase words without punctuation, joined by single spaces
+fn normalize_phrase(words: &[&str]) -> String {
+    words
+        .iter()
+        .map(|w| {
+            w.chars()
+                .filter(|c| c.is_alphanumeric() || *c == '\'')
+                .flat_map(|c| c.to_lowercase())
+                .collect::<String>()
+        })
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Normalized Levenshtein similarity (1.0 = identical)
+fn similarity(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(&a, &b) as f32 / max_len as f32
+}
+
+/// Character edit distance
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
 /// Clean up merged text
 fn merge_cleanup(text: &str) -> String {
     // Remove double spaces
@@ -156,6 +341,16 @@ fn merge_cleanup(text: &str) -> String {
 mod tests {
     use super::*;
 
+    fn chunks(texts: &[&str]) -> Vec<ChunkText> {
+        texts
+            .iter()
+            .map(|t| ChunkText {
+                text: t.to_string(),
+                silence_before_ms: 0,
+            })
+            .collect()
+    }
+
     #[test]
     fn test_single_chunk() {
         let chunks = vec![ChunkTranscription {
@@ -190,6 +385,78 @@ mod tests {
         assert!(result.contains("continues"));
     }
 
+    #[test]
+    fn test_join_drops_exact_boundary_repeat() {
+        let texts = chunks(&[
+            "La réunion commence à neuf heures du matin",
+            "neuf heures du matin avec toute l'équipe",
+        ]);
+        assert_eq!(
+            join_chunk_texts(&texts, DEFAULT_BOUNDARY_SIMILARITY, &ChunkJoin::default()),
+            "La réunion commence à neuf heures du matin avec toute l'équipe"
+        );
+    }
+
+    #[test]
+    fn test_join_drops_fuzzy_boundary_repeat() {
+        // Same phrase, different punctuation/casing and a misspelled word
+        let texts = chunks(&[
+            "We reviewed the quarterly budget.",
+            "the quartely budget and the hiring plan",
+        ]);
+        assert_eq!(
+            join_chunk_texts(&texts, DEFAULT_BOUNDARY_SIMILARITY, &ChunkJoin::default()),
+            "We reviewed the quarterly budget. And the hiring plan"
+        );
+    }
+
+    #[test]
+    fn test_join_keeps_distinct_text() {
+        let texts = chunks(&[
+            "Le patient se plaint de douleurs",
+            "de de la jambe gauche depuis hier",
+        ]);
+        // The boundary words differ, and "de" alone is too short to be a duplicate
+        assert_eq!(
+            join_chunk_texts(&texts, DEFAULT_BOUNDARY_SIMILARITY, &ChunkJoin::default()),
+            "Le patient se plaint de douleurs de de la jambe gauche depuis hier"
+        );
+    }
+
+    #[test]
+    fn test_join_threshold_zero_disables_dedup() {
+        let texts = chunks(&["bonjour à tous", "à tous merci"]);
+        assert_eq!(
+            join_chunk_texts(&texts, 0.0, &ChunkJoin::default()),
+            "bonjour à tous à tous merci"
+        );
+    }
+
+    #[test]
+    fn test_join_long_silence_ends_sentence() {
+        let mut texts = chunks(&["fin du premier point", "le chapitre suivant", "porte sur le budget"]);
+        texts[1].silence_before_ms = 1500;
+        texts[2].silence_before_ms = 200;
+        assert_eq!(
+            join_chunk_texts(&texts, DEFAULT_BOUNDARY_SIMILARITY, &ChunkJoin::default()),
+            "fin du premier point. Le chapitre suivant porte sur le budget"
+        );
+    }
+
+    #[test]
+    fn test_join_custom_separator() {
+        let mut texts = chunks(&["Premier paragraphe", "second paragraphe"]);
+        texts[1].silence_before_ms = 3000;
+        let join = ChunkJoin {
+            separator: "\n".to_string(),
+            sentence_gap_ms: 0,
+        };
+        assert_eq!(
+            join_chunk_texts(&texts, DEFAULT_BOUNDARY_SIMILARITY, &join),
+            "Premier paragraphe\nsecond paragraphe"
+        );
+    }
+
     #[test]
     fn test_cleanup() {
         assert_eq!(merge_cleanup("hello  world"), "hello world");
@@ -1,18 +1,36 @@
+pub mod accuracy;
+pub mod chunk_cache;
 pub mod config;
 #[cfg(target_os = "macos")]
 pub mod coreml;
+pub mod debug_dump;
 pub mod decoder;
+pub mod estimate;
+pub mod isolated;
+pub mod language_id;
 pub mod mel;
-pub mod merger; // Kept for potential future use (LCS-based merge)
+pub mod merger;
+pub mod model_cache;
 pub mod onnxruntime;
+pub mod ov_tensor;
 pub mod parakeet;
-
-use crate::error::Result;
-use crate::storage::{Segment, Transcription};
+pub mod partial;
+pub mod profiles;
+pub mod progress;
+pub mod rolling;
+pub mod streaming;
+pub mod telemetry;
+pub mod timing;
+
+use crate::error::{AppError, Result};
+use crate::logs::TRANSCRIPT_TARGET;
+use crate::storage::{Segment, SegmentWord, Transcription, TranscriptionSource, TranscriptionWarning};
 use once_cell::sync::Lazy;
+use parking_lot::{Condvar, Mutex};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
@@ -57,7 +75,7 @@ pub fn filter_chunk_hallucinations(text: &str) -> String {
     let result = cleaned.trim().to_string();
 
     if result != text {
-        debug!("Filtered hallucination: '{}' -> '{}'", text, result);
+        debug!(target: TRANSCRIPT_TARGET, "Filtered hallucination: {:?} -> {:?}", text, result);
     }
 
     result
@@ -66,11 +84,22 @@ pub fn filter_chunk_hallucinations(text: &str) -> String {
 /// Maximum audio samples per chunk (15 seconds at 16kHz)
 pub const MAX_AUDIO_SAMPLES: usize = 240000;
 
-pub use config::DecodingConfig;
+/// Segment confidence when the backend gives no token scores (CoreML sidecar, cached chunks)
+const UNSCORED_CONFIDENCE: f64 = 0.95;
+
+pub use config::{DecodingConfig, LanguageDecoding};
+pub use debug_dump::DebugDump;
+pub use isolated::IsolatedOpenVinoEngine;
+pub use merger::ChunkJoin;
 #[cfg(target_os = "macos")]
 pub use coreml::CoreMLEngine;
-pub use onnxruntime::OnnxRuntimeEngine;
-pub use parakeet::{ParakeetEngine, TranscriptionLanguage};
+pub use onnxruntime::{ExecutionProviderChoice, OnnxRuntimeEngine};
+pub use parakeet::{OpenVinoPerformance, ParakeetEngine, PerformanceHint, SupportedLanguage, TranscriptionLanguage};
+pub use profiles::DecodingProfile;
+pub use progress::{ChunkProgress, ProgressSink};
+pub use streaming::{StreamingConfig, StreamingSession};
+pub use telemetry::DecodeTelemetry;
+pub use timing::WordTiming;
 
 // Re-export for use in commands
 
@@ -110,6 +139,126 @@ impl EngineBackend {
     }
 }
 
+/// Model loading state (models are loaded in the background after startup)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EngineStatus {
+    /// Models are being loaded
+    Loading,
+    /// A backend is loaded and ready
+    Ready,
+    /// No model could be loaded (mock transcription)
+    Unavailable,
+    /// Model released to free memory, loaded again on demand
+    Unloaded,
+}
+
+/// Engine status with the cost of its model (`get_engine_status`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineStatusReport {
+    pub status: EngineStatus,
+    /// Backend of the loaded model (None while loading)
+    pub backend: Option<String>,
+    pub load_time_ms: Option<u64>,
+    /// Duration of the warm-up inference (None: not warmed up)
+    pub warmup_ms: Option<u64>,
+    /// Size of the model files, held in memory once loaded
+    pub model_bytes: Option<u64>,
+    /// Resident memory of the whole process, where the OS reports it
+    pub process_bytes: Option<u64>,
+}
+
+/// Loaded engine as last reported ready
+#[derive(Debug, Clone)]
+struct LoadedEngine {
+    backend: EngineBackend,
+    load_time_ms: Option<u64>,
+    warmup_ms: Option<u64>,
+    model_bytes: Option<u64>,
+}
+
+/// Engine status shared between the loader thread and commands
+pub struct EngineReadiness {
+    status: Mutex<EngineStatus>,
+    changed: Condvar,
+    /// Kept apart from the engine, whose lock is held during transcriptions
+    loaded: Mutex<Option<LoadedEngine>>,
+}
+
+impl EngineReadiness {
+    pub fn new(status: EngineStatus) -> Self {
+        Self {
+            status: Mutex::new(status),
+            changed: Condvar::new(),
+            loaded: Mutex::new(None),
+        }
+    }
+
+    /// Mark `engine` ready, keeping its load time and model size for the report
+    pub fn set_ready(&self, engine: &DynamicEngine) {
+        *self.loaded.lock() = Some(LoadedEngine {
+            backend: engine.backend(),
+            load_time_ms: engine.load_time_ms(),
+            warmup_ms: engine.warmup_ms(),
+            model_bytes: engine.model_dir().map(estimate::dir_size),
+        });
+        self.set(EngineStatus::Ready);
+    }
+
+    /// Status, with the details of the engine last set ready
+    pub fn report(&self) -> EngineStatusReport {
+        let status = self.status();
+        let loaded = self.loaded.lock().clone().filter(|_| status == EngineStatus::Ready);
+        EngineStatusReport {
+            status,
+            backend: loaded.as_ref().map(|l| l.backend.display_name().to_string()),
+            load_time_ms: loaded.as_ref().and_then(|l| l.load_time_ms),
+            warmup_ms: loaded.as_ref().and_then(|l| l.warmup_ms),
+            model_bytes: loaded.as_ref().and_then(|l| l.model_bytes),
+            process_bytes: process_resident_bytes(),
+        }
+    }
+
+    /// Record the warm-up of the engine set ready
+    pub fn set_warmed_up(&self, warmup_ms: u64) {
+        if let Some(loaded) = self.loaded.lock().as_mut() {
+            loaded.warmup_ms = Some(warmup_ms);
+        }
+    }
+
+    /// Current status
+    pub fn status(&self) -> EngineStatus {
+        *self.status.lock()
+    }
+
+    /// Update the status and wake up waiting commands
+    pub fn set(&self, status: EngineStatus) {
+        *self.status.lock() = status;
+        self.changed.notify_all();
+    }
+
+    /// Take on the loading of an unloaded engine: false if it is not
+    /// unloaded, or already being loaded by another caller
+    pub fn start_reload(&self) -> bool {
+        let mut status = self.status.lock();
+        if *status != EngineStatus::Unloaded {
+            return false;
+        }
+        *status = EngineStatus::Loading;
+        true
+    }
+
+    /// Block until loading is finished (commands queued during startup)
+    pub fn wait_loaded(&self) -> EngineStatus {
+        let mut status = self.status.lock();
+        while *status == EngineStatus::Loading {
+            self.changed.wait(&mut status);
+        }
+        *status
+    }
+}
+
 /// Trait for ASR inference engines
 ///
 /// This allows swapping between different backends (OpenVINO, ONNX Runtime)
@@ -139,24 +288,97 @@ pub trait ASREngine: Send + Sync {
         language: TranscriptionLanguage,
         config: &DecodingConfig,
     ) -> Result<String>;
+
+    /// Decode statistics of the last inference, if the backend collects them
+    fn last_telemetry(&self) -> Option<DecodeTelemetry> {
+        None
+    }
+
+    /// Word timestamps of the last inference, if the backend decodes token positions
+    fn last_word_timings(&self) -> Option<Vec<WordTiming>> {
+        None
+    }
+
+    /// Whether `DecodingConfig::initial_prompt` conditions the decoding
+    ///
+    /// Parakeet TDT has no text conditioning: only prompt-driven backends
+    /// (Whisper-style, remote APIs) return true.
+    fn supports_initial_prompt(&self) -> bool {
+        false
+    }
+
+    /// Hardware the model runs on, for backends with selectable execution providers
+    fn execution_provider(&self) -> Option<ExecutionProviderChoice> {
+        None
+    }
+}
+
+/// Resident memory of this process (Linux only: VmRSS of /proc/self/status)
+fn process_resident_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmRSS:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+/// Audio of the warm-up inference: one second of faint noise (pure silence
+/// could be cut short by a backend)
+fn warmup_audio() -> Vec<f32> {
+    let mut state = 0x2545_f491_u32;
+    (0..16000)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state as f32 / u32::MAX as f32 - 0.5) * 0.002
+        })
+        .collect()
+}
+
+fn create_engine(backend: EngineBackend) -> Box<dyn ASREngine> {
+    match backend {
+        EngineBackend::OpenVINO => {
+            let (isolated, performance) = crate::storage::with_db(crate::storage::get_settings)
+                .map(|s| (s.openvino_isolated, s.openvino_performance))
+                .unwrap_or_default();
+            if isolated {
+                Box::new(IsolatedOpenVinoEngine::new().with_performance(performance))
+            } else {
+                Box::new(ParakeetEngine::new().with_performance(performance))
+            }
+        }
+        EngineBackend::OnnxRuntime => {
+            // Settings may not be readable yet (early startup): CPU then
+            let provider = crate::storage::with_db(crate::storage::get_settings)
+                .map(|s| s.onnx_execution_provider)
+                .unwrap_or_default();
+            Box::new(OnnxRuntimeEngine::new().with_execution_provider(provider))
+        }
+        #[cfg(target_os = "macos")]
+        EngineBackend::CoreML => Box::new(CoreMLEngine::new()),
+    }
 }
 
 /// Dynamic engine wrapper that can switch between backends at runtime
 pub struct DynamicEngine {
     engine: Box<dyn ASREngine>,
     backend: EngineBackend,
+    /// Directory and load time of the model, once loaded
+    model_dir: Option<PathBuf>,
+    load_time_ms: Option<u64>,
+    warmup_ms: Option<u64>,
 }
 
 impl DynamicEngine {
     /// Create a new dynamic engine with the specified backend
     pub fn new(backend: EngineBackend) -> Self {
-        let engine: Box<dyn ASREngine> = match backend {
-            EngineBackend::OpenVINO => Box::new(ParakeetEngine::new()),
-            EngineBackend::OnnxRuntime => Box::new(OnnxRuntimeEngine::new()),
-            #[cfg(target_os = "macos")]
-            EngineBackend::CoreML => Box::new(CoreMLEngine::new()),
-        };
-        Self { engine, backend }
+        Self {
+            engine: create_engine(backend),
+            backend,
+            model_dir: None,
+            load_time_ms: None,
+            warmup_ms: None,
+        }
     }
 
     /// Get the current backend type
@@ -174,39 +396,160 @@ impl DynamicEngine {
         self.engine.is_loaded()
     }
 
+    /// Decode statistics of the last inference (blank-collapse detection)
+    pub fn last_telemetry(&self) -> Option<DecodeTelemetry> {
+        self.engine.last_telemetry()
+    }
+
+    /// Execution provider of the ONNX Runtime backend, once loaded (None for other backends)
+    pub fn execution_provider(&self) -> Option<ExecutionProviderChoice> {
+        self.engine.execution_provider()
+    }
+
+    /// Whether the current backend uses the initial prompt
+    pub fn supports_initial_prompt(&self) -> bool {
+        self.engine.supports_initial_prompt()
+    }
+
     /// Load the model from the given directory
     pub fn load_model(&mut self, model_dir: &Path) -> Result<()> {
-        self.engine.load_model(model_dir)
+        let start = Instant::now();
+        self.engine.load_model(model_dir)?;
+        self.loaded_from(model_dir, start);
+        Ok(())
+    }
+
+    fn loaded_from(&mut self, model_dir: &Path, start: Instant) {
+        self.model_dir = Some(model_dir.to_path_buf());
+        self.load_time_ms = Some(start.elapsed().as_millis() as u64);
+        self.warmup_ms = None;
+    }
+
+    /// Directory of the loaded model
+    pub fn model_dir(&self) -> Option<&Path> {
+        self.model_dir.as_deref()
+    }
+
+    /// Time the model took to load
+    pub fn load_time_ms(&self) -> Option<u64> {
+        self.load_time_ms
+    }
+
+    /// Duration of the last warm-up, since the model was loaded
+    pub fn warmup_ms(&self) -> Option<u64> {
+        self.warmup_ms
+    }
+
+    /// Release the model, keeping the backend (`load_model` brings it back)
+    pub fn unload(&mut self) {
+        self.engine = create_engine(self.backend);
+        self.model_dir = None;
+        self.load_time_ms = None;
+        self.warmup_ms = None;
+        info!("{} engine unloaded", self.backend.display_name());
+    }
+
+    /// Run a short inference so that the first transcription does not pay
+    /// for lazy compilation and weights paged in from disk; returns its duration
+    pub fn warm_up(&mut self) -> Result<u64> {
+        if !self.is_loaded() {
+            return Err(AppError::InvalidState("No model loaded".into()));
+        }
+        let start = Instant::now();
+        self.engine
+            .run_inference(&warmup_audio(), TranscriptionLanguage::default(), &DecodingConfig::default())?;
+        let elapsed = start.elapsed().as_millis() as u64;
+        info!("{} engine warmed up in {}ms", self.backend.display_name(), elapsed);
+        self.warmup_ms = Some(elapsed);
+        Ok(elapsed)
     }
 
     /// Switch to a different backend (requires reloading model)
     pub fn switch_backend(&mut self, backend: EngineBackend, model_dir: &Path) -> Result<()> {
-        if backend == self.backend {
+        if backend == self.backend && self.is_loaded() {
             return Ok(());
         }
 
         info!("Switching engine from {} to {}", self.backend.display_name(), backend.display_name());
 
-        let mut new_engine: Box<dyn ASREngine> = match backend {
-            EngineBackend::OpenVINO => Box::new(ParakeetEngine::new()),
-            EngineBackend::OnnxRuntime => Box::new(OnnxRuntimeEngine::new()),
-            #[cfg(target_os = "macos")]
-            EngineBackend::CoreML => Box::new(CoreMLEngine::new()),
-        };
+        let mut new_engine = create_engine(backend);
 
+        let start = Instant::now();
         new_engine.load_model(model_dir)?;
         self.engine = new_engine;
         self.backend = backend;
+        self.loaded_from(model_dir, start);
 
         info!("Switched to {} backend successfully", backend.display_name());
         Ok(())
     }
 
+    /// Text of a short window (live transcription), empty while no model is loaded
+    pub fn transcribe_window(
+        &self,
+        samples: &[f32],
+        language: TranscriptionLanguage,
+        config: &DecodingConfig,
+    ) -> Result<String> {
+        if !self.is_loaded() {
+            return Ok(String::new());
+        }
+        self.engine.run_inference(samples, language, config)
+    }
+
+    /// One segment per phrase when the backend has word timings,
+    /// otherwise a single segment spanning the audio
+    ///
+    /// Phrase texts come from the decoded words: the chunk joining fixes
+    /// (boundary dedup, restored sentence ends) only apply to `raw_text`.
+    /// A segment's confidence is the mean of its scored words.
+    fn timed_segments(&self, text: &str, words: &[WordTiming], duration_ms: i64) -> Vec<Segment> {
+        let phrases = timing::group_phrases(words);
+
+        if phrases.is_empty() {
+            return vec![Segment {
+                id: Uuid::new_v4().to_string(),
+                start_ms: 0,
+                end_ms: duration_ms,
+                text: text.to_string(),
+                confidence: UNSCORED_CONFIDENCE,
+                speaker: None,
+                language: None,
+                words: Vec::new(),
+            }];
+        }
+
+        phrases
+            .into_iter()
+            .map(|phrase| Segment {
+                id: Uuid::new_v4().to_string(),
+                start_ms: phrase.start_ms,
+                end_ms: phrase.end_ms.min(duration_ms),
+                confidence: phrase.confidence().unwrap_or(UNSCORED_CONFIDENCE),
+                words: phrase
+                    .words
+                    .iter()
+                    .filter_map(|w| {
+                        Some(SegmentWord {
+                            text: w.text.clone(),
+                            start_ms: w.start_ms,
+                            end_ms: w.end_ms,
+                            confidence: w.confidence?,
+                        })
+                    })
+                    .collect(),
+                text: phrase.text,
+                speaker: None,
+                language: None,
+            })
+            .collect()
+    }
+
     /// Transcribe audio samples (16kHz mono f32)
     pub fn transcribe(
         &self,
         samples: &[f32],
-        source_type: &str,
+        source_type: TranscriptionSource,
         source_name: Option<String>,
         language: TranscriptionLanguage,
         decoding_config: Option<DecodingConfig>,
@@ -218,6 +561,12 @@ impl DynamicEngine {
             info!("Engine not loaded, returning mock transcription");
             return Self::mock_transcribe(samples, source_type, source_name);
         }
+        if config.initial_prompt.is_some() && !self.supports_initial_prompt() {
+            debug!("{} takes no initial prompt, ignoring it", self.name());
+        }
+        if let Some(dump) = &config.debug_dump {
+            dump.audio(samples);
+        }
 
         info!(
             "Transcribing {} samples ({} ms) with {}, language: {:?}, beam_width: {}, temperature: {:.2}",
@@ -229,31 +578,55 @@ impl DynamicEngine {
             config.temperature
         );
 
+        let started = std::time::Instant::now();
         match self.engine.run_inference(samples, language, &config) {
             Ok(text) => {
+                // A simulated delay doesn't reflect the backend's speed (re-runs served
+                // from the chunk cache are outliers the estimate's median leaves out)
+                if config.simulated_delay.is_none() {
+                    let elapsed_ms = started.elapsed().as_millis() as i64;
+                    estimate::record(self.backend, config.beam_width, duration_ms, elapsed_ms);
+                }
+
                 let now = chrono::Utc::now().to_rfc3339();
-                let segments = vec![Segment {
-                    id: Uuid::new_v4().to_string(),
-                    start_ms: 0,
-                    end_ms: duration_ms,
-                    text: text.clone(),
-                    confidence: 0.95,
-                }];
+                let words = self.engine.last_word_timings().unwrap_or_default();
+                let segments = self.timed_segments(&text, &words, duration_ms);
 
-                Ok(Transcription {
+                let mut transcription = Transcription {
                     id: Uuid::new_v4().to_string(),
                     created_at: now.clone(),
                     updated_at: now,
-                    source_type: source_type.to_string(),
+                    source_type,
                     source_name,
+                    source_path: None,
+                    original_sample_rate: None,
                     duration_ms,
                     language: "fr".to_string(),
                     segments,
                     raw_text: text,
                     edited_text: None,
                     is_edited: false,
-                })
+                    is_draft: false,
+                    warnings: self.decode_warnings(&config),
+                    normalization_gain: None,
+                    action_items: Vec::new(),
+                    markers: Vec::new(),
+                    pauses: Vec::new(),
+                    capture: None,
+                    confidence: timing::mean_confidence(&words),
+                    audio_path: None,
+                    translations: Vec::new(),
+                    notes: Vec::new(),
+                    folder: None,
+                    tags: Vec::new(),
+                    title: None,
+                    deleted_at: None,
+                };
+                language_id::label(&mut transcription, language, config.language_prior);
+                Ok(transcription)
             }
+            // A crashed worker fails the job instead of producing a fake text
+            Err(e @ AppError::EngineCrashed(_)) => Err(e),
             Err(e) => {
                 warn!("Inference failed: {}. Falling back to mock transcription.", e);
                 Self::mock_transcribe(samples, source_type, source_name)
@@ -261,10 +634,38 @@ impl DynamicEngine {
         }
     }
 
+    /// Structured warnings from the last inference's telemetry
+    fn decode_warnings(&self, config: &DecodingConfig) -> Vec<TranscriptionWarning> {
+        let Some(telemetry) = self.last_telemetry() else {
+            return Vec::new();
+        };
+
+        let mut warnings = Vec::new();
+        if telemetry.max_symbols_guard_hits > 0 {
+            warnings.push(TranscriptionWarning {
+                code: "max_symbols_per_frame".to_string(),
+                message: format!(
+                    "Decoder hit the limit of {} symbols per frame {} times; output may be truncated",
+                    config.max_symbols_per_frame, telemetry.max_symbols_guard_hits
+                ),
+            });
+        }
+        if telemetry.is_blank_collapse() {
+            warnings.push(TranscriptionWarning {
+                code: "blank_collapse".to_string(),
+                message: format!(
+                    "Decoder predicted blank on {:.0}% of frames; the model or backend may be misbehaving",
+                    telemetry.blank_ratio * 100.0
+                ),
+            });
+        }
+        warnings
+    }
+
     /// Generate mock transcription when model isn't loaded
     fn mock_transcribe(
         samples: &[f32],
-        source_type: &str,
+        source_type: TranscriptionSource,
         source_name: Option<String>,
     ) -> Result<Transcription> {
         let duration_ms = (samples.len() as f64 / 16000.0 * 1000.0) as i64;
@@ -276,8 +677,10 @@ impl DynamicEngine {
             id: Uuid::new_v4().to_string(),
             created_at: now.clone(),
             updated_at: now,
-            source_type: source_type.to_string(),
+            source_type,
             source_name,
+            source_path: None,
+            original_sample_rate: None,
             duration_ms,
             language: "fr".to_string(),
             segments: vec![Segment {
@@ -286,10 +689,28 @@ impl DynamicEngine {
                 end_ms: duration_ms,
                 text: mock_text.clone(),
                 confidence: 0.0,
+                speaker: None,
+                language: None,
+                words: Vec::new(),
             }],
             raw_text: mock_text,
             edited_text: None,
             is_edited: false,
+            is_draft: false,
+            warnings: Vec::new(),
+            normalization_gain: None,
+            action_items: Vec::new(),
+            markers: Vec::new(),
+            pauses: Vec::new(),
+            capture: None,
+            confidence: None,
+            audio_path: None,
+            translations: Vec::new(),
+            notes: Vec::new(),
+            folder: None,
+            tags: Vec::new(),
+            title: None,
+            deleted_at: None,
         })
     }
 }
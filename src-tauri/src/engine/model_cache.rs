@@ -0,0 +1,218 @@
+//! OpenVINO compiled-model cache
+//!
+//! OpenVINO can store compiled blobs in a cache directory (CACHE_DIR property)
+//! so later launches skip the slow IR compilation. The cache is wiped whenever
+//! the model files change, detected through a SHA-256 of each IR file. Hashes
+//! are recorded with the size and modification time they were computed for,
+//! so a file is only hashed again when one of them changes.
+
+use crate::engine::estimate;
+use crate::error::Result;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// File recording the hashes of the models the cache was built from
+const FINGERPRINT_FILE: &str = "models.fingerprint";
+
+/// Cache directory for compiled OpenVINO models (under app data)
+pub fn cache_dir() -> Option<PathBuf> {
+    crate::app_data_dir().map(|p| p.join("com.wakascribe.app").join("openvino_cache"))
+}
+
+/// An IR file as recorded in the fingerprint: one `name size mtime sha256` line
+#[derive(Debug, Clone, PartialEq)]
+struct FileHash {
+    name: String,
+    size: u64,
+    mtime: u64,
+    sha256: String,
+}
+
+impl FileHash {
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        let hash = Self {
+            name: fields.next()?.to_string(),
+            size: fields.next()?.parse().ok()?,
+            mtime: fields.next()?.parse().ok()?,
+            sha256: fields.next()?.to_string(),
+        };
+        fields.next().is_none().then_some(hash)
+    }
+
+    fn line(&self) -> String {
+        format!("{}\t{}\t{}\t{}", self.name, self.size, self.mtime, self.sha256)
+    }
+}
+
+/// Hashes of the IR files in a model directory, by name; those whose size and
+/// mtime match `known` keep their recorded hash
+fn fingerprint(model_dir: &Path, known: &[FileHash]) -> Vec<FileHash> {
+    let Ok(dir) = fs::read_dir(model_dir) else {
+        return Vec::new();
+    };
+    let mut hashes: Vec<FileHash> = dir
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let path = e.path();
+            matches!(
+                path.extension().and_then(|ext| ext.to_str()),
+                Some("xml") | Some("bin")
+            )
+        })
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            let mtime = meta
+                .modified()
+                .ok()?
+                .duration_since(std::time::UNIX_EPOCH)
+                .ok()?
+                .as_secs();
+            let name = e.file_name().to_string_lossy().into_owned();
+            let size = meta.len();
+            let sha256 = match known.iter().find(|k| k.name == name && k.size == size && k.mtime == mtime) {
+                Some(k) => k.sha256.clone(),
+                None => hash_file(&e.path())
+                    .map_err(|err| warn!("Failed to hash {:?}: {}", e.path(), err))
+                    .ok()?,
+            };
+            Some(FileHash { name, size, mtime, sha256 })
+        })
+        .collect();
+
+    hashes.sort_by(|a, b| a.name.cmp(&b.name));
+    hashes
+}
+
+fn hash_file(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Same files with the same contents (copied again or touched: still the same models)
+fn same_models(a: &[FileHash], b: &[FileHash]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.name == b.name && a.sha256 == b.sha256)
+}
+
+/// Prepare the cache directory for the given models
+///
+/// Clears stale compiled blobs when the model files changed since the cache
+/// was written. Returns the directory and whether existing blobs can be reused.
+pub fn prepare(model_dir: &Path) -> Option<(PathBuf, bool)> {
+    let dir = cache_dir()?;
+    let fingerprint_path = dir.join(FINGERPRINT_FILE);
+
+    let stored: Option<Vec<FileHash>> = fs::read_to_string(&fingerprint_path)
+        .ok()
+        .map(|text| text.lines().filter_map(FileHash::parse).collect());
+    let current = fingerprint(model_dir, stored.as_deref().unwrap_or_default());
+
+    let warm = match &stored {
+        Some(stored) if same_models(stored, &current) => true,
+        Some(_) => {
+            info!("Model files changed, clearing OpenVINO cache at {:?}", dir);
+            if let Err(e) = fs::remove_dir_all(&dir) {
+                warn!("Failed to clear OpenVINO cache: {}", e);
+            }
+            false
+        }
+        None => false,
+    };
+
+    if let Err(e) = fs::create_dir_all(&dir) {
+        warn!("Failed to create OpenVINO cache directory {:?}: {}", dir, e);
+        return None;
+    }
+    // Also rewritten when only sizes or mtimes moved, so they are not hashed again
+    if stored.as_ref() != Some(&current) {
+        let lines: Vec<String> = current.iter().map(FileHash::line).collect();
+        if let Err(e) = fs::write(&fingerprint_path, lines.join("\n")) {
+            warn!("Failed to write OpenVINO cache fingerprint: {}", e);
+        }
+    }
+
+    Some((dir, warm))
+}
+
+/// Size of the compiled-model cache, for the settings
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelCacheInfo {
+    /// None when the app data directory is unknown
+    pub path: Option<String>,
+    pub size_bytes: u64,
+    /// Compiled blobs (one per model and device)
+    pub blob_count: usize,
+}
+
+pub fn info() -> ModelCacheInfo {
+    let Some(dir) = cache_dir() else {
+        return ModelCacheInfo {
+            path: None,
+            size_bytes: 0,
+            blob_count: 0,
+        };
+    };
+    let blob_count = fs::read_dir(&dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "blob"))
+                .count()
+        })
+        .unwrap_or(0);
+    ModelCacheInfo {
+        path: Some(dir.to_string_lossy().into_owned()),
+        size_bytes: estimate::dir_size(&dir),
+        blob_count,
+    }
+}
+
+/// Delete the compiled blobs; the next model load compiles again. Returns the bytes freed
+pub fn clear() -> Result<u64> {
+    let Some(dir) = cache_dir() else {
+        return Ok(0);
+    };
+    let freed = estimate::dir_size(&dir);
+    match fs::remove_dir_all(&dir) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    }
+    info!("OpenVINO cache cleared ({} bytes)", freed);
+    Ok(freed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_hashes_contents() {
+        let dir = std::env::temp_dir().join(format!("wakascribe-model-cache-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("encoder.xml"), "<net/>").unwrap();
+        fs::write(dir.join("encoder.bin"), [1u8, 2, 3]).unwrap();
+        fs::write(dir.join("vocab.json"), "{}").unwrap();
+
+        let first = fingerprint(&dir, &[]);
+        assert_eq!(first.iter().map(|h| h.name.as_str()).collect::<Vec<_>>(), ["encoder.bin", "encoder.xml"]);
+        let parsed: Vec<FileHash> = first.iter().filter_map(|h| FileHash::parse(&h.line())).collect();
+        assert_eq!(parsed, first);
+
+        // A recorded hash is trusted while size and mtime match
+        let mut known = first.clone();
+        known[0].sha256 = "recorded".to_string();
+        assert_eq!(fingerprint(&dir, &known)[0].sha256, "recorded");
+
+        fs::write(dir.join("encoder.bin"), [1u8, 2, 3, 4]).unwrap();
+        let changed = fingerprint(&dir, &first);
+        assert!(!same_models(&first, &changed));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
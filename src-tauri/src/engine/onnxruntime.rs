@@ -5,16 +5,28 @@
 //! - encoder-model.int8.onnx: FastConformer encoder
 //! - decoder_joint-model.onnx: Combined decoder + joint network
 
-use crate::audio::{split_audio_smart, SmartChunkConfig};
+use crate::audio::{classify_audio, edge_silence_ms, split_audio_smart, SmartChunkConfig, VadConfig};
+use crate::engine::chunk_cache;
 use crate::engine::config::DecodingConfig;
-use crate::engine::decoder::{TDTDecoder, Vocabulary};
+use crate::engine::debug_dump::InferenceArtifacts;
+use crate::engine::decoder::{self, ChunkCarry, DecoderState, TDTDecoder, Vocabulary};
+use crate::engine::merger::{join_chunk_texts, ChunkText};
+use crate::engine::progress::ChunkProgressTracker;
+use crate::engine::telemetry::DecodeTelemetry;
+use crate::engine::timing::{self, TimedToken, WordTiming};
 use crate::engine::{filter_chunk_hallucinations, ASREngine, MAX_AUDIO_SAMPLES};
 use crate::engine::TranscriptionLanguage;
 use crate::error::{AppError, Result};
+use crate::logs::TRANSCRIPT_TARGET;
+use crate::power;
+use crate::storage::PendingChunk;
+use ort::ep::{ExecutionProvider, ExecutionProviderDispatch};
 use ort::session::{builder::GraphOptimizationLevel, Session};
 use ort::value::Tensor;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
 /// Token spécial pour le blank (pas de sortie)
@@ -24,7 +36,7 @@ const BLANK_TOKEN: u32 = 8192;
 const TOKEN_START_OF_TRANSCRIPT: i32 = 4;   // <|startoftranscript|>
 const TOKEN_NO_PREDICT_LANG: i32 = 23;      // <|nopredict_lang|>
 
-/// Nombre de classes de durée TDT (1, 2, 3, 4, 5 frames)
+/// Nombre de classes de durée TDT (0, 1, 2, 3, 4 frames)
 const NUM_DURATION_CLASSES: usize = 5;
 
 /// Taille totale du vocabulaire (tokens + blank)
@@ -42,6 +54,75 @@ const ENCODER_OUTPUT_DIM: usize = 1024;
 /// Mel features dimension
 const MEL_FEATURES: usize = 128;
 
+/// Hardware the ONNX Runtime sessions run on (setting)
+///
+/// GPU providers need an ONNX Runtime build that includes them (and, for
+/// CUDA, the CUDA libraries): an unavailable or failing provider falls back
+/// to the next candidate, and to the CPU in the end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionProviderChoice {
+    #[default]
+    Cpu,
+    /// First available GPU provider of the platform
+    Auto,
+    /// NVIDIA GPUs (Linux, Windows)
+    Cuda,
+    /// Any DirectX 12 GPU (Windows)
+    DirectMl,
+    /// Apple Neural Engine / GPU (macOS)
+    CoreMl,
+}
+
+impl ExecutionProviderChoice {
+    /// GPU providers usable on this platform, in order of preference
+    pub fn platform_providers() -> Vec<Self> {
+        let mut providers = Vec::new();
+        if cfg!(any(target_os = "linux", target_os = "windows")) {
+            providers.push(Self::Cuda);
+        }
+        if cfg!(target_os = "windows") {
+            providers.push(Self::DirectMl);
+        }
+        if cfg!(target_os = "macos") {
+            providers.push(Self::CoreMl);
+        }
+        providers
+    }
+
+    /// Providers to try for this choice, CPU excluded (always the last resort)
+    fn candidates(self) -> Vec<Self> {
+        match self {
+            Self::Cpu => Vec::new(),
+            Self::Auto => Self::platform_providers(),
+            provider => vec![provider],
+        }
+    }
+
+    fn dispatch(self) -> Option<ExecutionProviderDispatch> {
+        match self {
+            Self::Cpu | Self::Auto => None,
+            Self::Cuda => Some(ort::ep::CUDA::default().build().error_on_failure()),
+            Self::DirectMl => Some(ort::ep::DirectML::default().build().error_on_failure()),
+            Self::CoreMl => Some(ort::ep::CoreML::default().build().error_on_failure()),
+        }
+    }
+
+    /// Whether the loaded ONNX Runtime library includes this provider
+    ///
+    /// ONNX Runtime must already be initialized (a model loaded).
+    pub fn is_available(self) -> bool {
+        let available = match self {
+            Self::Cpu => Ok(true),
+            Self::Auto => Ok(Self::platform_providers().into_iter().any(Self::is_available)),
+            Self::Cuda => ort::ep::CUDA::default().is_available(),
+            Self::DirectMl => ort::ep::DirectML::default().is_available(),
+            Self::CoreMl => ort::ep::CoreML::default().is_available(),
+        };
+        available.unwrap_or(false)
+    }
+}
+
 /// LSTM states for decoder
 struct LSTMStates {
     h: Vec<f32>, // [2, 1, 640] flattened
@@ -62,7 +143,7 @@ impl LSTMStates {
 #[derive(Clone)]
 struct BeamHypothesis {
     /// Emitted tokens so far
-    tokens: Vec<u32>,
+    tokens: Vec<TimedToken>,
     /// Cumulative log probability score
     score: f32,
     /// LSTM hidden state
@@ -73,6 +154,8 @@ struct BeamHypothesis {
     last_token: i32,
     /// Current time position in encoder output
     current_time: usize,
+    /// Decode steps spent on the current frame (max-symbols-per-frame guard)
+    frame_symbols: usize,
 }
 
 /// ONNX Runtime engine for Parakeet TDT
@@ -81,6 +164,14 @@ pub struct OnnxRuntimeEngine {
     encoder_session: Option<Mutex<Session>>,
     decoder_joint_session: Option<Mutex<Session>>,
     tdt_decoder: Option<TDTDecoder>,
+    /// Decode statistics of the last inference
+    last_telemetry: Mutex<Option<DecodeTelemetry>>,
+    /// Word timestamps of the last inference
+    last_word_timings: Mutex<Option<Vec<WordTiming>>>,
+    /// Providers to try when loading, from the settings
+    execution_provider: ExecutionProviderChoice,
+    /// Provider the encoder session ended up on
+    active_provider: Option<ExecutionProviderChoice>,
 }
 
 // Implement Send + Sync
@@ -94,9 +185,49 @@ impl OnnxRuntimeEngine {
             encoder_session: None,
             decoder_joint_session: None,
             tdt_decoder: None,
+            last_telemetry: Mutex::new(None),
+            last_word_timings: Mutex::new(None),
+            execution_provider: ExecutionProviderChoice::default(),
+            active_provider: None,
         }
     }
 
+    /// Run the sessions on `execution_provider` when available (applied at the next `load_model`)
+    pub fn with_execution_provider(mut self, execution_provider: ExecutionProviderChoice) -> Self {
+        self.execution_provider = execution_provider;
+        self
+    }
+
+    /// Create a session for `path`, on the first candidate provider that works
+    fn load_session(&self, path: &Path, what: &str) -> Result<(Session, ExecutionProviderChoice)> {
+        let candidates = self.execution_provider.candidates();
+        for provider in candidates.iter().copied() {
+            if !provider.is_available() {
+                info!("{:?} not available in this ONNX Runtime build", provider);
+                continue;
+            }
+            let Some(dispatch) = provider.dispatch() else {
+                continue;
+            };
+            let session = Session::builder()
+                .and_then(|b| b.with_optimization_level(GraphOptimizationLevel::Level3))
+                .and_then(|b| b.with_execution_providers([dispatch]))
+                .and_then(|b| b.commit_from_file(path));
+            match session {
+                Ok(session) => return Ok((session, provider)),
+                Err(e) => warn!("{:?} failed for the {} model, trying the next provider: {}", provider, what, e),
+            }
+        }
+
+        let session = Session::builder()
+            .map_err(|e| AppError::Transcription(format!("Failed to create session builder: {}", e)))?
+            .with_optimization_level(GraphOptimizationLevel::Level3)
+            .map_err(|e| AppError::Transcription(format!("Failed to set optimization level: {}", e)))?
+            .commit_from_file(path)
+            .map_err(|e| AppError::Transcription(format!("Failed to load {} model: {}", what, e)))?;
+        Ok((session, ExecutionProviderChoice::Cpu))
+    }
+
     /// Compute mel spectrogram from audio
     fn compute_mel(&self, audio: &[f32]) -> Result<(Vec<f32>, usize, i64)> {
         let session = self.mel_session.as_ref()
@@ -250,8 +381,10 @@ impl OnnxRuntimeEngine {
         Ok(logits_data.to_vec())
     }
 
-    /// Decode TDT output (token + duration) from joint logits
-    fn decode_tdt_output(&self, logits: &[f32], config: &DecodingConfig) -> (u32, usize) {
+    /// Decode TDT output (token + duration + token probability) from joint logits
+    ///
+    /// The probability is only computed for an emitted token (0.0 for blank).
+    fn decode_tdt_output(&self, logits: &[f32], config: &DecodingConfig) -> (u32, usize, f32) {
         // Split logits into token and duration parts
         let token_logits = &logits[..VOCAB_SIZE];
         let duration_logits = &logits[VOCAB_SIZE..VOCAB_SIZE + NUM_DURATION_CLASSES];
@@ -281,13 +414,32 @@ impl OnnxRuntimeEngine {
             .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
             .unwrap();
 
-        // Duration is 1-indexed (dur_idx 0 = 1 frame, dur_idx 4 = 5 frames)
-        let duration = best_dur_idx + 1;
+        // Duration bins are [0, 1, 2, 3, 4]: the index is the duration, and
+        // only a token may stay on its frame (blank always moves forward)
+        let duration = if best_token == BLANK_TOKEN as usize {
+            best_dur_idx.max(1)
+        } else {
+            best_dur_idx
+        };
 
-        (best_token as u32, duration)
+        let confidence = if best_token == BLANK_TOKEN as usize {
+            0.0
+        } else {
+            decoder::token_posterior(
+                token_logits,
+                best_token,
+                config.temperature,
+                BLANK_TOKEN as usize,
+                config.blank_penalty,
+            )
+        };
+        (best_token as u32, duration, confidence)
     }
 
     /// TDT greedy decoding
+    ///
+    /// With a `carry`, decoding resumes from the previous chunk's state and
+    /// stops where the next chunk starts, leaving its own state there.
     fn tdt_greedy_decode(
         &self,
         encoder_data: &[f32],
@@ -295,21 +447,35 @@ impl OnnxRuntimeEngine {
         valid_time: usize,
         language: TranscriptionLanguage,
         config: &DecodingConfig,
-    ) -> Result<Vec<u32>> {
-        let mut states = LSTMStates::zeros();
+        telemetry: &mut DecodeTelemetry,
+        mut carry: Option<&mut ChunkCarry>,
+    ) -> Result<Vec<TimedToken>> {
+        let resumed = carry.as_mut().and_then(|c| c.state.take());
+        let is_resumed = resumed.is_some();
+        let (mut states, mut last_token, mut t) = match resumed {
+            Some(state) => (LSTMStates { h: state.h, c: state.c }, state.last_token as i32, state.skip_frames),
+            None => (LSTMStates::zeros(), BLANK_TOKEN as i32, 0),
+        };
+        let end = carry
+            .as_ref()
+            .and_then(|c| c.stop_frame)
+            .map_or(valid_time, |frame| frame.min(valid_time));
         let mut tokens = Vec::new();
-        let mut t = 0;
         let mut iterations = 0;
-        const MAX_ITERATIONS: usize = 1000;
+        // Tokens emitted on the current frame (duration 0), see `DecodingConfig::frame_advance`
+        let mut frame_symbols = 0;
 
         info!(
             "TDT decode config: beam=1, temp={:.2}, blank_penalty={:.1}, language={:?}",
             config.temperature, config.blank_penalty, language
         );
 
+        // Resumed: the state already holds the language and the previous words
         // If a language is forced, condition the decoder with the token sequence
         // Sequence: <|startoftranscript|> → <|nopredict_lang|> → <|lang|>
-        if let Some(lang_token) = language.token_id() {
+        if is_resumed {
+            debug!("Decoder resumed from the previous chunk (last token {})", last_token);
+        } else if let Some(lang_token) = language.token_id(self.vocab()) {
             info!(
                 "Forcing language with token sequence: startoftranscript(4) → nopredict_lang(23) → {}({})",
                 language.display_name(),
@@ -347,26 +513,41 @@ impl OnnxRuntimeEngine {
             debug!("Decoder step 3: <|{}|>", language.display_name());
 
             info!("Decoder conditioned with full language sequence");
+        } else if let Some(prior_token) = language.prior_token_id(config.language_prior, self.vocab()) {
+            // Auto with a soft prior: no <|nopredict_lang|>, the model may still switch
+            info!("Auto language with soft prior token {}", prior_token);
+            for token in [TOKEN_START_OF_TRANSCRIPT, prior_token as i32] {
+                let _ = self.run_decoder_joint(encoder_data, encoder_time, 0, token, &mut states)?;
+            }
         }
 
-        while t < valid_time && iterations < MAX_ITERATIONS {
+        // Time moves forward at least every max_symbols_per_frame steps, so this terminates
+        while t < end {
             iterations += 1;
 
-            // Get last token (or blank for start)
-            let last_token = tokens.last().copied().unwrap_or(BLANK_TOKEN) as i32;
-
-            // Run decoder+joint
+            // Run decoder+joint (last token, or blank for start)
             let logits = self.run_decoder_joint(encoder_data, encoder_time, t, last_token, &mut states)?;
 
             // Decode token and duration
-            let (token, duration) = self.decode_tdt_output(&logits, config);
+            let (token, duration, confidence) = self.decode_tdt_output(&logits, config);
+            telemetry.record_step(&logits[..VOCAB_SIZE], BLANK_TOKEN as usize, token == BLANK_TOKEN);
 
             if token != BLANK_TOKEN {
-                tokens.push(token);
+                tokens.push(TimedToken {
+                    id: token,
+                    frame: t,
+                    duration,
+                    confidence,
+                });
+                last_token = token as i32;
             }
 
             // Advance time by duration
-            t += duration;
+            let (advance, guarded) = config.frame_advance(duration, &mut frame_symbols);
+            if guarded {
+                telemetry.max_symbols_guard_hits += 1;
+            }
+            t += advance;
 
             if iterations <= 5 {
                 debug!(
@@ -376,17 +557,38 @@ impl OnnxRuntimeEngine {
             }
         }
 
+        if telemetry.max_symbols_guard_hits > 0 {
+            warn!(
+                "TDT decoding hit max_symbols_per_frame={} {} times, output may be truncated",
+                config.max_symbols_per_frame, telemetry.max_symbols_guard_hits
+            );
+        }
+
         info!(
             "TDT decoded {} tokens in {} iterations",
             tokens.len(),
             iterations
         );
 
+        if let Some(carry) = carry {
+            carry.state = Some(DecoderState {
+                h: states.h,
+                c: states.c,
+                last_token: last_token as i64,
+                skip_frames: t.saturating_sub(end),
+            });
+        }
+
         Ok(tokens)
     }
 
+    /// Vocabulary of the loaded model (language tokens)
+    fn vocab(&self) -> Option<&Vocabulary> {
+        self.tdt_decoder.as_ref().map(|d| d.vocab())
+    }
+
     /// Convert tokens to text
-    fn tokens_to_text(&self, tokens: &[u32]) -> String {
+    fn tokens_to_text(&self, tokens: &[TimedToken]) -> String {
         let decoder = self.tdt_decoder.as_ref();
         if decoder.is_none() {
             return String::new();
@@ -395,12 +597,12 @@ impl OnnxRuntimeEngine {
         let decoder = decoder.unwrap();
         let mut text = String::new();
 
-        for &token in tokens {
-            if token == BLANK_TOKEN || token as usize >= VOCAB_SIZE {
+        for token in tokens {
+            if token.id == BLANK_TOKEN || token.id as usize >= VOCAB_SIZE {
                 continue;
             }
 
-            let token_text = decoder.decode_single(token as usize);
+            let token_text = decoder.decode_single(token.id as usize);
             text.push_str(&token_text);
         }
 
@@ -454,14 +656,9 @@ impl ASREngine for OnnxRuntimeEngine {
         // Load mel spectrogram model
         info!("Loading mel spectrogram model (nemo128.onnx)...");
         let mel_path = model_dir.join("nemo128.onnx");
-        let mel_session = Session::builder()
-            .map_err(|e| AppError::Transcription(format!("Failed to create session builder: {}", e)))?
-            .with_optimization_level(GraphOptimizationLevel::Level3)
-            .map_err(|e| AppError::Transcription(format!("Failed to set optimization level: {}", e)))?
-            .commit_from_file(&mel_path)
-            .map_err(|e| AppError::Transcription(format!("Failed to load mel model: {}", e)))?;
+        let (mel_session, provider) = self.load_session(&mel_path, "mel")?;
         self.mel_session = Some(Mutex::new(mel_session));
-        info!("Mel spectrogram model loaded");
+        info!("Mel spectrogram model loaded ({:?})", provider);
 
         // Load encoder model (prefer float32 for quality)
         let encoder_path = if model_dir.join("encoder-model.onnx").exists() {
@@ -471,26 +668,18 @@ impl ASREngine for OnnxRuntimeEngine {
             info!("Loading encoder model (encoder-model.int8.onnx - quantized)...");
             model_dir.join("encoder-model.int8.onnx")
         };
-        let encoder_session = Session::builder()
-            .map_err(|e| AppError::Transcription(format!("Failed to create session builder: {}", e)))?
-            .with_optimization_level(GraphOptimizationLevel::Level3)
-            .map_err(|e| AppError::Transcription(format!("Failed to set optimization level: {}", e)))?
-            .commit_from_file(&encoder_path)
-            .map_err(|e| AppError::Transcription(format!("Failed to load encoder model: {}", e)))?;
+        let (encoder_session, provider) = self.load_session(&encoder_path, "encoder")?;
         self.encoder_session = Some(Mutex::new(encoder_session));
-        info!("Encoder model loaded");
+        // The encoder is where nearly all the time goes
+        self.active_provider = Some(provider);
+        info!("Encoder model loaded ({:?})", provider);
 
         // Load decoder+joint model
         info!("Loading decoder+joint model (decoder_joint-model.onnx)...");
         let decoder_joint_path = model_dir.join("decoder_joint-model.onnx");
-        let decoder_joint_session = Session::builder()
-            .map_err(|e| AppError::Transcription(format!("Failed to create session builder: {}", e)))?
-            .with_optimization_level(GraphOptimizationLevel::Level3)
-            .map_err(|e| AppError::Transcription(format!("Failed to set optimization level: {}", e)))?
-            .commit_from_file(&decoder_joint_path)
-            .map_err(|e| AppError::Transcription(format!("Failed to load decoder_joint model: {}", e)))?;
+        let (decoder_joint_session, provider) = self.load_session(&decoder_joint_path, "decoder_joint")?;
         self.decoder_joint_session = Some(Mutex::new(decoder_joint_session));
-        info!("Decoder+Joint model loaded");
+        info!("Decoder+Joint model loaded ({:?})", provider);
 
         info!("All ONNX Runtime models loaded successfully");
         Ok(())
@@ -508,18 +697,45 @@ impl ASREngine for OnnxRuntimeEngine {
             samples.len() as f32 / 16000.0
         );
 
+        let mut telemetry = DecodeTelemetry::default();
+
         // Check if audio needs chunking
-        if samples.len() > MAX_AUDIO_SAMPLES {
+        let result = if samples.len() > MAX_AUDIO_SAMPLES {
             info!(
                 "Audio too long ({} samples = {:.1}s), using chunked transcription",
                 samples.len(),
                 samples.len() as f32 / 16000.0
             );
-            return self.run_chunked_inference(samples, language, config);
+            self.run_chunked_inference(samples, language, config, &mut telemetry)
+        } else {
+            // Single chunk inference
+            self.run_single_inference(samples, language, config, &mut telemetry, None)
+        };
+
+        info!(
+            "Decode telemetry: blank_ratio={:.3}, avg_blank_margin={:.2}, tokens/s={:.1}",
+            telemetry.blank_ratio, telemetry.avg_blank_margin, telemetry.tokens_per_second
+        );
+        if telemetry.is_blank_collapse() {
+            warn!("Decoder collapsed to blank: model or backend is misbehaving");
         }
+        *self.last_telemetry.lock().unwrap() = Some(telemetry);
+
+        let (text, words) = result?;
+        *self.last_word_timings.lock().unwrap() = Some(words);
+        Ok(text)
+    }
+
+    fn last_telemetry(&self) -> Option<DecodeTelemetry> {
+        self.last_telemetry.lock().unwrap().clone()
+    }
 
-        // Single chunk inference
-        self.run_single_inference(samples, language, config)
+    fn last_word_timings(&self) -> Option<Vec<WordTiming>> {
+        self.last_word_timings.lock().unwrap().clone()
+    }
+
+    fn execution_provider(&self) -> Option<ExecutionProviderChoice> {
+        self.active_provider
     }
 }
 
@@ -531,13 +747,20 @@ impl OnnxRuntimeEngine {
         audio: &[f32],
         language: TranscriptionLanguage,
         config: &DecodingConfig,
-    ) -> Result<String> {
-        // Limit to max audio samples
-        let audio = if audio.len() > MAX_AUDIO_SAMPLES {
-            &audio[..MAX_AUDIO_SAMPLES]
-        } else {
-            audio
-        };
+        telemetry: &mut DecodeTelemetry,
+        mut carry: Option<&mut ChunkCarry>,
+    ) -> Result<(String, Vec<WordTiming>)> {
+        // Never truncate mid-word: chunks from split_audio_smart are at most 14s,
+        // so this can't recurse
+        if audio.len() > MAX_AUDIO_SAMPLES {
+            warn!(
+                "run_single_inference called with {} samples (> {}), routing through chunking",
+                audio.len(),
+                MAX_AUDIO_SAMPLES
+            );
+            return self.run_chunked_inference(audio, language, config, telemetry);
+        }
+        config.simulate_slow_inference();
 
         // Step 1: Compute mel spectrogram
         debug!("Computing mel spectrogram...");
@@ -553,19 +776,51 @@ impl OnnxRuntimeEngine {
         );
 
         // Step 3: TDT decode (greedy or beam search based on config)
+        let resumed = carry.as_ref().is_some_and(|c| c.state.is_some());
         let tokens = if config.beam_width <= 1 {
             debug!("Running TDT greedy decode...");
-            self.tdt_greedy_decode(&encoder_data, encoder_time, valid_time, language, config)?
+            self.tdt_greedy_decode(
+                &encoder_data,
+                encoder_time,
+                valid_time,
+                language,
+                config,
+                telemetry,
+                carry.as_deref_mut(),
+            )?
         } else {
             debug!("Running TDT beam search (beam_width={})...", config.beam_width);
-            self.tdt_beam_decode(&encoder_data, encoder_time, valid_time, language, config)?
+            self.tdt_beam_decode(&encoder_data, encoder_time, valid_time, language, config, telemetry)?
         };
+        telemetry.finish(tokens.len(), audio.len());
         debug!("Decoded {} tokens", tokens.len());
+        if let Some(dump) = &config.debug_dump {
+            dump.inference(&InferenceArtifacts {
+                audio,
+                mel: &mel_data,
+                mel_frames: mel_time,
+                valid_mel_frames: mel_len.max(0) as usize,
+                encoder_frames: encoder_time,
+                valid_encoder_frames: valid_time,
+                tokens: &tokens,
+            });
+        }
 
-        // Step 4: Convert to text
+        // Step 4: Convert to text and timestamped words
         let text = self.tokens_to_text(&tokens);
+        let words = match self.tdt_decoder.as_ref() {
+            Some(decoder) => timing::words_from_tokens(&tokens, |id| decoder.decode_single(id as usize), 0),
+            None => Vec::new(),
+        };
+        if let (Some(carry), Some(decoder)) = (carry, self.tdt_decoder.as_ref()) {
+            let first_piece = tokens
+                .iter()
+                .map(|t| decoder.decode_single(t.id as usize))
+                .find(|piece| !piece.is_empty());
+            carry.continues_word = resumed && first_piece.is_some_and(|piece| !piece.starts_with(' '));
+        }
 
-        Ok(text)
+        Ok((text, words))
     }
 
     /// Run chunked inference for long audio using VAD-based smart chunking
@@ -574,7 +829,8 @@ impl OnnxRuntimeEngine {
         audio: &[f32],
         language: TranscriptionLanguage,
         config: &DecodingConfig,
-    ) -> Result<String> {
+        telemetry: &mut DecodeTelemetry,
+    ) -> Result<(String, Vec<WordTiming>)> {
         // Use smart VAD-based chunking (cuts at silence points)
         let chunk_config = SmartChunkConfig::default(); // 8-14s, cuts at silence
         let chunks = split_audio_smart(audio, &chunk_config);
@@ -585,9 +841,22 @@ impl OnnxRuntimeEngine {
             audio.len() as f32 / 16000.0
         );
 
-        let mut transcriptions: Vec<String> = Vec::new();
+        let mut transcriptions: Vec<ChunkText> = Vec::new();
+        let mut words: Vec<WordTiming> = Vec::new();
+        // Silence since the last speech, for sentence boundaries between chunks
+        let mut silence_ms: i64 = 0;
+        let mut previous_inference: Option<Duration> = None;
+        // Stateful decoding: each chunk resumes where the previous one stopped
+        let carrying = config.carry_decoder_state && config.beam_width <= 1;
+        let mut carry = ChunkCarry::default();
+        let progress = ChunkProgressTracker::new(
+            config.progress.as_ref(),
+            chunks.len(),
+            (audio.len() / 16) as i64,
+        );
 
         for (i, chunk) in chunks.iter().enumerate() {
+            progress.chunk_started(i, chunk.start_ms);
             let chunk_duration = chunk.samples.len() as f32 / 16000.0;
             info!(
                 "Processing chunk {}/{} ({:.1}s - {:.1}s, duration={:.1}s)",
@@ -598,28 +867,136 @@ impl OnnxRuntimeEngine {
                 chunk_duration
             );
 
-            match self.run_single_inference(&chunk.samples, language, config) {
-                Ok(text) => {
+            let silence_before_ms = match edge_silence_ms(&chunk.samples, &VadConfig::default()) {
+                Some((leading, trailing)) => {
+                    let gap = silence_ms + leading;
+                    silence_ms = trailing;
+                    gap
+                }
+                None => {
+                    silence_ms += chunk.end_ms - chunk.start_ms;
+                    silence_ms
+                }
+            };
+
+            // Transcribed before an interruption: the saved result
+            if let Some(saved) = config.journal.as_ref().and_then(|j| j.saved(i, chunk.start_ms, chunk.end_ms)) {
+                debug!(target: TRANSCRIPT_TARGET, "Chunk {} resumed: {:?}", i + 1, saved.text);
+                carry.reset();
+                if !saved.text.is_empty() {
+                    timing::append_chunk_words(&mut words, vec![WordTiming::spanning(&saved.text, chunk.start_ms, chunk.end_ms)]);
+                    match transcriptions.last_mut() {
+                        Some(previous) if saved.continues_word => previous.text.push_str(&saved.text),
+                        _ => transcriptions.push(ChunkText {
+                            text: saved.text.clone(),
+                            silence_before_ms,
+                        }),
+                    }
+                }
+                continue;
+            }
+            let save = |text: &str, continues_word: bool| {
+                if let Some(journal) = &config.journal {
+                    journal.record(
+                        chunks.len(),
+                        PendingChunk {
+                            index: i,
+                            start_ms: chunk.start_ms,
+                            end_ms: chunk.end_ms,
+                            text: text.to_string(),
+                            continues_word,
+                        },
+                    );
+                }
+            };
+
+            // Music/noise: insert a marker instead of letting the model hallucinate
+            if config.skip_non_speech {
+                let content = classify_audio(&chunk.samples);
+                if let Some(marker) = content.marker() {
+                    info!("Chunk {} skipped: {:?}", i + 1, content);
+                    carry.reset();
+                    save(marker, false);
+                    timing::append_chunk_words(&mut words, vec![WordTiming::spanning(marker, chunk.start_ms, chunk.end_ms)]);
+                    transcriptions.push(ChunkText {
+                        text: marker.to_string(),
+                        silence_before_ms,
+                    });
+                    continue;
+                }
+            }
+
+            // Same audio and settings as a previous run: reuse the result
+            // (not with a carried state, which the result depends on)
+            let cache_key = (config.chunk_cache && !carrying)
+                .then(|| chunk_cache::chunk_key(&chunk.samples, self.name(), language, config));
+            if let Some(text) = cache_key.as_deref().and_then(chunk_cache::lookup) {
+                debug!(target: TRANSCRIPT_TARGET, "Chunk {} from cache: {:?}", i + 1, text);
+                save(&text, false);
+                if !text.is_empty() {
+                    timing::append_chunk_words(&mut words, vec![WordTiming::spanning(&text, chunk.start_ms, chunk.end_ms)]);
+                    transcriptions.push(ChunkText { text, silence_before_ms });
+                }
+                continue;
+            }
+
+            // Low-power mode: idle or wait for AC between chunks when on battery
+            power::pace_chunk(config.power_mode, previous_inference);
+            let inference_start = Instant::now();
+
+            let resumed = carry.state.is_some();
+            carry.stop_at(chunks.get(i + 1).map(|next| next.start_ms - chunk.start_ms));
+            let mut chunk_telemetry = DecodeTelemetry::default();
+            let result = self.run_single_inference(
+                &chunk.samples,
+                language,
+                config,
+                &mut chunk_telemetry,
+                carrying.then_some(&mut carry),
+            );
+            previous_inference = Some(inference_start.elapsed());
+            match result {
+                Ok((text, mut chunk_words)) => {
+                    telemetry.merge(&chunk_telemetry);
                     let raw_text = text.trim().to_string();
-                    // Filter hallucinations at chunk start
-                    let text = filter_chunk_hallucinations(&raw_text);
+                    // Filter hallucinations at chunk start (a resumed chunk doesn't start from silence)
+                    let text = if resumed { raw_text.clone() } else { filter_chunk_hallucinations(&raw_text) };
+                    if let Some(key) = &cache_key {
+                        chunk_cache::store(key, &text);
+                    }
+                    save(&text, carry.continues_word && !transcriptions.is_empty());
                     if !text.is_empty() {
                         if text != raw_text {
-                            info!("Chunk {} transcription (filtered): '{}' -> '{}'", i + 1, raw_text, text);
+                            debug!(target: TRANSCRIPT_TARGET, "Chunk {} transcription (filtered): {:?} -> {:?}", i + 1, raw_text, text);
                         } else {
-                            info!("Chunk {} transcription: '{}'", i + 1, text);
+                            debug!(target: TRANSCRIPT_TARGET, "Chunk {} transcription: {:?}", i + 1, text);
                         }
-                        transcriptions.push(text);
+                        for word in &mut chunk_words {
+                            word.start_ms += chunk.start_ms;
+                            word.end_ms += chunk.start_ms;
+                        }
+                        timing::keep_last_words(&mut chunk_words, text.split_whitespace().count());
+                        match transcriptions.last_mut() {
+                            // Cut inside a word: glue it back together
+                            Some(previous) if carry.continues_word => {
+                                previous.text.push_str(&text);
+                                timing::continue_last_word(&mut words, &mut chunk_words);
+                            }
+                            _ => transcriptions.push(ChunkText { text, silence_before_ms }),
+                        }
+                        timing::append_chunk_words(&mut words, chunk_words);
                     } else {
                         debug!("Chunk {} produced empty transcription after filtering (silence?)", i + 1);
                     }
                 }
                 Err(e) => {
                     warn!("Chunk {} transcription failed: {}", i + 1, e);
+                    carry.reset();
                     // Continue with other chunks
                 }
             }
         }
+        progress.finished();
 
         if transcriptions.is_empty() {
             return Err(AppError::Transcription(
@@ -627,11 +1004,13 @@ impl OnnxRuntimeEngine {
             ));
         }
 
-        // Simple concatenation - no complex merge needed since we cut at silence
-        let merged_text = transcriptions.join(" ");
+        // Cuts are at silence, but chunks overlap slightly: drop repeated boundary phrases
+        // and restore sentence ends lost at long pauses
+        let merged_text = join_chunk_texts(&transcriptions, config.boundary_similarity, &config.chunk_join);
 
-        info!("Final transcription ({} chunks): '{}'", transcriptions.len(), merged_text);
-        Ok(merged_text)
+        info!("Final transcription: {} chunks, {} characters", transcriptions.len(), merged_text.chars().count());
+        debug!(target: TRANSCRIPT_TARGET, "Final transcription: {:?}", merged_text);
+        Ok((merged_text, words))
     }
 
     /// TDT beam search decoding
@@ -642,7 +1021,8 @@ impl OnnxRuntimeEngine {
         valid_time: usize,
         language: TranscriptionLanguage,
         config: &DecodingConfig,
-    ) -> Result<Vec<u32>> {
+        telemetry: &mut DecodeTelemetry,
+    ) -> Result<Vec<TimedToken>> {
         let beam_width = config.beam_width.max(1);
         let temperature = config.temperature;
 
@@ -659,10 +1039,11 @@ impl OnnxRuntimeEngine {
             c_state: vec![0.0f32; DECODER_NUM_LAYERS * DECODER_HIDDEN_DIM],
             last_token: BLANK_TOKEN as i32,
             current_time: 0,
+            frame_symbols: 0,
         }];
 
         // If language is forced, condition all beams
-        if let Some(lang_token) = language.token_id() {
+        if let Some(lang_token) = language.token_id(self.vocab()) {
             info!(
                 "Conditioning beams with language: {} (token {})",
                 language.display_name(),
@@ -709,14 +1090,25 @@ impl OnnxRuntimeEngine {
             beams[0].last_token = BLANK_TOKEN as i32;
 
             info!("Beams conditioned with full language sequence");
+        } else if let Some(prior_token) = language.prior_token_id(config.language_prior, self.vocab()) {
+            info!("Conditioning beams with soft language prior (token {})", prior_token);
+            let mut states = LSTMStates {
+                h: beams[0].h_state.clone(),
+                c: beams[0].c_state.clone(),
+            };
+            for token in [TOKEN_START_OF_TRANSCRIPT, prior_token as i32] {
+                let _ = self.run_decoder_joint(encoder_data, encoder_time, 0, token, &mut states)?;
+            }
+            beams[0].h_state = states.h;
+            beams[0].c_state = states.c;
+            beams[0].last_token = BLANK_TOKEN as i32;
         }
 
-        // Safety limit
-        let max_iterations = valid_time * 10;
         let mut iterations = 0;
 
-        // Main beam search loop
-        while iterations < max_iterations {
+        // Main beam search loop (each beam advances at least one frame every
+        // max_symbols_per_frame steps, so this terminates)
+        loop {
             iterations += 1;
 
             // Check if all beams have finished (reached end of encoder)
@@ -757,6 +1149,8 @@ impl OnnxRuntimeEngine {
 
                 // Get top-k tokens with their scores
                 let top_k = self.get_top_k_tokens(&logits, beam_width, temperature, config.blank_penalty);
+                let best_is_blank = top_k.first().is_some_and(|&(token, _)| token == BLANK_TOKEN);
+                telemetry.record_step(&logits[..VOCAB_SIZE], BLANK_TOKEN as usize, best_is_blank);
                 let duration = self.get_best_duration(&logits, temperature);
 
                 // Expand beam with top-k tokens
@@ -768,20 +1162,36 @@ impl OnnxRuntimeEngine {
                         c_state: beam.c_state.clone(),
                         last_token: beam.last_token,
                         current_time: beam.current_time,
+                        frame_symbols: beam.frame_symbols,
                     };
 
-                    if token == BLANK_TOKEN {
-                        // Blank: advance time, keep states unchanged
-                        new_beam.current_time += duration as usize;
-                    } else {
-                        // Token emitted: update states and advance time
-                        new_beam.tokens.push(token);
+                    if token != BLANK_TOKEN {
+                        // Token emitted: update states
+                        new_beam.tokens.push(TimedToken {
+                            id: token,
+                            frame: t,
+                            duration: duration as usize,
+                            confidence: decoder::token_posterior(
+                                &logits[..VOCAB_SIZE],
+                                token as usize,
+                                temperature,
+                                BLANK_TOKEN as usize,
+                                config.blank_penalty,
+                            ),
+                        });
                         new_beam.last_token = token as i32;
                         new_beam.h_state = states.h.clone();
                         new_beam.c_state = states.c.clone();
-                        new_beam.current_time += duration as usize;
                     }
 
+                    // Advance time (blank keeps states unchanged)
+                    let (advance, guarded) =
+                        config.frame_advance(duration as usize, &mut new_beam.frame_symbols);
+                    if guarded {
+                        telemetry.max_symbols_guard_hits += 1;
+                    }
+                    new_beam.current_time += advance;
+
                     new_beams.push(new_beam);
                 }
             }
@@ -803,8 +1213,11 @@ impl OnnxRuntimeEngine {
             }
         }
 
-        if iterations >= max_iterations {
-            warn!("Beam search reached max iterations limit");
+        if telemetry.max_symbols_guard_hits > 0 {
+            warn!(
+                "Beam search hit max_symbols_per_frame={} {} times, output may be truncated",
+                config.max_symbols_per_frame, telemetry.max_symbols_guard_hits
+            );
         }
 
         // Return tokens from best beam
@@ -0,0 +1,105 @@
+//! Helpers for OpenVINO infer request buffers
+//!
+//! Models are compiled with static shapes, so inputs are written into the
+//! pre-allocated tensors (zero-padded) and outputs copied out. Every access
+//! checks sizes and reports the stage and tensor name, so a model exported
+//! with different shapes fails with a clear error instead of a panic or a
+//! silently truncated buffer.
+
+use crate::error::{AppError, Result};
+use openvino::{InferRequest, Tensor};
+
+fn error(stage: &str, name: &str, what: &str, e: impl std::fmt::Debug) -> AppError {
+    AppError::Transcription(format!("{} {} {}: {:?}", stage, name, what, e))
+}
+
+fn tensor(request: &mut InferRequest, stage: &str, name: &str) -> Result<Tensor> {
+    request
+        .get_tensor(name)
+        .map_err(|e| error(stage, name, "get tensor", e))
+}
+
+/// Write `values` at the start of input `name`, zeroing the rest
+pub fn write_f32(request: &mut InferRequest, stage: &str, name: &str, values: &[f32]) -> Result<()> {
+    let mut tensor = tensor(request, stage, name)?;
+    let data = tensor
+        .get_data_mut::<f32>()
+        .map_err(|e| error(stage, name, "data", e))?;
+    if values.len() > data.len() {
+        return Err(AppError::Transcription(format!(
+            "{} {}: {} values for a tensor of {}",
+            stage,
+            name,
+            values.len(),
+            data.len()
+        )));
+    }
+    data.fill(0.0);
+    data[..values.len()].copy_from_slice(values);
+    Ok(())
+}
+
+/// Set the first element of i64 input `name` (lengths, token ids)
+pub fn set_i64(request: &mut InferRequest, stage: &str, name: &str, value: i64) -> Result<()> {
+    let mut tensor = tensor(request, stage, name)?;
+    let data = tensor
+        .get_data_mut::<i64>()
+        .map_err(|e| error(stage, name, "data", e))?;
+    let first = data
+        .first_mut()
+        .ok_or_else(|| error(stage, name, "data", "empty tensor"))?;
+    *first = value;
+    Ok(())
+}
+
+/// Set the first element of i32 input `name`
+pub fn set_i32(request: &mut InferRequest, stage: &str, name: &str, value: i32) -> Result<()> {
+    let mut tensor = tensor(request, stage, name)?;
+    let data = tensor
+        .get_data_mut::<i32>()
+        .map_err(|e| error(stage, name, "data", e))?;
+    let first = data
+        .first_mut()
+        .ok_or_else(|| error(stage, name, "data", "empty tensor"))?;
+    *first = value;
+    Ok(())
+}
+
+/// Copy of f32 tensor `name`
+pub fn read_f32(request: &mut InferRequest, stage: &str, name: &str) -> Result<Vec<f32>> {
+    let tensor = tensor(request, stage, name)?;
+    let data = tensor
+        .get_data::<f32>()
+        .map_err(|e| error(stage, name, "data", e))?;
+    Ok(data.to_vec())
+}
+
+/// First element of i64 tensor `name`
+pub fn read_i64(request: &mut InferRequest, stage: &str, name: &str) -> Result<i64> {
+    let tensor = tensor(request, stage, name)?;
+    let data = tensor
+        .get_data::<i64>()
+        .map_err(|e| error(stage, name, "data", e))?;
+    data.first()
+        .copied()
+        .ok_or_else(|| error(stage, name, "data", "empty tensor"))
+}
+
+/// Copy of the f32 output at `index` (for models with unnamed outputs)
+pub fn read_output_f32(request: &mut InferRequest, stage: &str, index: usize) -> Result<Vec<f32>> {
+    let name = format!("output #{}", index);
+    let tensor = request
+        .get_output_tensor_by_index(index)
+        .map_err(|e| error(stage, &name, "get tensor", e))?;
+    let data = tensor
+        .get_data::<f32>()
+        .map_err(|e| error(stage, &name, "data", e))?;
+    Ok(data.to_vec())
+}
+
+/// Run inference
+pub fn infer(request: &mut InferRequest, stage: &str) -> Result<()> {
+    request
+        .infer()
+        .map_err(|e| AppError::Transcription(format!("{} infer: {:?}", stage, e)))
+}
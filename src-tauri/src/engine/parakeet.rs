@@ -1,47 +1,147 @@
-use crate::audio::{split_audio_smart, SmartChunkConfig};
+use crate::audio::{classify_audio, edge_silence_ms, split_audio_smart, SmartChunkConfig, VadConfig};
+use crate::engine::chunk_cache;
 use crate::engine::config::DecodingConfig;
-use crate::engine::decoder::{TDTDecoder, Vocabulary};
+use crate::engine::debug_dump::InferenceArtifacts;
+use crate::engine::decoder::{self, ChunkCarry, DecoderState, TDTDecoder, Vocabulary};
+use crate::engine::language_id;
+use crate::engine::merger::{join_chunk_texts, ChunkText};
+use crate::engine::progress::ChunkProgressTracker;
+use crate::engine::model_cache;
+use crate::engine::ov_tensor;
+use crate::engine::telemetry::DecodeTelemetry;
+use crate::engine::timing::{self, TimedToken, WordTiming};
 use crate::engine::{filter_chunk_hallucinations, ASREngine, MAX_AUDIO_SAMPLES};
 use crate::error::{AppError, Result};
-use crate::storage::{Segment, Transcription};
-use openvino::{CompiledModel, Core, DeviceType, InferRequest};
+use crate::logs::TRANSCRIPT_TARGET;
+use crate::power;
+use crate::storage::{PendingChunk, Segment, Transcription, TranscriptionSource};
+use openvino::{CompiledModel, Core, DeviceType, InferRequest, PropertyKey, RwPropertyKey};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 /// Language selection for transcription
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TranscriptionLanguage {
     /// Auto-detect language (default)
     #[default]
     Auto,
-    /// Force French
-    French,
-    /// Force English
+    Bulgarian,
+    Croatian,
+    Czech,
+    Danish,
+    Dutch,
     English,
+    Estonian,
+    Finnish,
+    French,
+    German,
+    Greek,
+    Hungarian,
+    Italian,
+    Latvian,
+    Lithuanian,
+    Maltese,
+    Polish,
+    Portuguese,
+    Romanian,
+    Russian,
+    Slovak,
+    Slovenian,
+    Spanish,
+    Swedish,
+    Ukrainian,
+}
+
+/// Languages of Parakeet TDT v3: ISO 639-1 code (its `<|xx|>` token) and native name
+const LANGUAGES: &[(TranscriptionLanguage, &str, &str)] = &[
+    (TranscriptionLanguage::Bulgarian, "bg", "Български"),
+    (TranscriptionLanguage::Croatian, "hr", "Hrvatski"),
+    (TranscriptionLanguage::Czech, "cs", "Čeština"),
+    (TranscriptionLanguage::Danish, "da", "Dansk"),
+    (TranscriptionLanguage::Dutch, "nl", "Nederlands"),
+    (TranscriptionLanguage::English, "en", "English"),
+    (TranscriptionLanguage::Estonian, "et", "Eesti"),
+    (TranscriptionLanguage::Finnish, "fi", "Suomi"),
+    (TranscriptionLanguage::French, "fr", "Français"),
+    (TranscriptionLanguage::German, "de", "Deutsch"),
+    (TranscriptionLanguage::Greek, "el", "Ελληνικά"),
+    (TranscriptionLanguage::Hungarian, "hu", "Magyar"),
+    (TranscriptionLanguage::Italian, "it", "Italiano"),
+    (TranscriptionLanguage::Latvian, "lv", "Latviešu"),
+    (TranscriptionLanguage::Lithuanian, "lt", "Lietuvių"),
+    (TranscriptionLanguage::Maltese, "mt", "Malti"),
+    (TranscriptionLanguage::Polish, "pl", "Polski"),
+    (TranscriptionLanguage::Portuguese, "pt", "Português"),
+    (TranscriptionLanguage::Romanian, "ro", "Română"),
+    (TranscriptionLanguage::Russian, "ru", "Русский"),
+    (TranscriptionLanguage::Slovak, "sk", "Slovenčina"),
+    (TranscriptionLanguage::Slovenian, "sl", "Slovenščina"),
+    (TranscriptionLanguage::Spanish, "es", "Español"),
+    (TranscriptionLanguage::Swedish, "sv", "Svenska"),
+    (TranscriptionLanguage::Ukrainian, "uk", "Українська"),
+];
+
+/// Language offered for transcription (`list_supported_languages`)
+#[derive(Debug, Clone, Serialize)]
+pub struct SupportedLanguage {
+    pub id: TranscriptionLanguage,
+    pub code: &'static str,
+    pub name: &'static str,
 }
 
 impl TranscriptionLanguage {
-    /// Get the token ID to inject for this language
+    /// Languages the multilingual model transcribes, Auto excluded
+    pub fn supported() -> Vec<SupportedLanguage> {
+        LANGUAGES
+            .iter()
+            .map(|&(id, code, name)| SupportedLanguage { id, code, name })
+            .collect()
+    }
+
+    /// Token ID to inject for this language: its `<|xx|>` token in `vocab`
+    /// (the ids of French and English are also built in)
     /// Returns None for Auto (let the model decide)
-    pub fn token_id(&self) -> Option<i64> {
-        match self {
-            TranscriptionLanguage::Auto => None,
+    pub fn token_id(&self, vocab: Option<&Vocabulary>) -> Option<i64> {
+        let code = self.code()?;
+        let built_in = match self {
             TranscriptionLanguage::French => Some(71),  // <|fr|>
             TranscriptionLanguage::English => Some(64), // <|en|>
+            _ => None,
+        };
+        vocab
+            .and_then(|v| v.id_of(&format!("<|{}|>", code)))
+            .map(|id| id as i64)
+            .or(built_in)
+    }
+
+    /// Token injected without <|nopredict_lang|> in Auto mode, biasing the
+    /// model towards `prior` while still letting it detect another language
+    pub fn prior_token_id(&self, prior: Option<TranscriptionLanguage>, vocab: Option<&Vocabulary>) -> Option<i64> {
+        match self {
+            TranscriptionLanguage::Auto => prior.and_then(|p| p.token_id(vocab)),
+            _ => None,
         }
     }
 
+    /// ISO 639-1 code ("fr", "en"); None for Auto
+    pub fn code(&self) -> Option<&'static str> {
+        LANGUAGES.iter().find(|(l, _, _)| l == self).map(|&(_, code, _)| code)
+    }
+
+    /// Language of an ISO code or a UI locale code ("fr", "en-US")
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let code = locale.split(['-', '_']).next()?.to_lowercase();
+        LANGUAGES.iter().find(|(_, c, _)| *c == code).map(|&(l, _, _)| l)
+    }
+
     /// Get display name
     pub fn display_name(&self) -> &'static str {
-        match self {
-            TranscriptionLanguage::Auto => "Auto",
-            TranscriptionLanguage::French => "Français",
-            TranscriptionLanguage::English => "English",
-        }
+        LANGUAGES.iter().find(|(l, _, _)| l == self).map_or("Auto", |&(_, _, name)| name)
     }
 }
 
@@ -87,7 +187,7 @@ const DEFAULT_BLANK_PENALTY: f32 = 6.0;
 #[derive(Clone)]
 struct BeamHypothesis {
     /// Emitted tokens so far
-    tokens: Vec<u32>,
+    tokens: Vec<TimedToken>,
     /// Cumulative log probability score
     score: f32,
     /// LSTM hidden state [2 * 640]
@@ -98,6 +198,72 @@ struct BeamHypothesis {
     last_token: i64,
     /// Current time position in encoder output
     current_time: usize,
+    /// Decode steps spent on the current frame (max-symbols-per-frame guard)
+    frame_symbols: usize,
+}
+
+/// What the OpenVINO CPU plugin optimizes for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PerformanceHint {
+    /// One inference as fast as possible
+    Latency,
+    /// Several inference streams: more work overall, each one slower
+    Throughput,
+}
+
+impl PerformanceHint {
+    fn property_value(self) -> &'static str {
+        match self {
+            PerformanceHint::Latency => "LATENCY",
+            PerformanceHint::Throughput => "THROUGHPUT",
+        }
+    }
+}
+
+/// CPU use of the OpenVINO backend, set before the models are compiled
+/// (None: OpenVINO's choice for the machine)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OpenVinoPerformance {
+    /// Inference threads (fewer leave cores to the rest of the machine)
+    pub num_threads: Option<u32>,
+    pub hint: Option<PerformanceHint>,
+    /// Pin inference threads to cores
+    pub cpu_pinning: Option<bool>,
+    /// Run inference threads on both hardware threads of a core
+    pub hyper_threading: Option<bool>,
+}
+
+impl OpenVinoPerformance {
+    /// CPU plugin properties to set, with their OpenVINO names
+    fn properties(&self) -> Vec<(&'static str, RwPropertyKey, String)> {
+        let yes_no = |on: bool| if on { "YES" } else { "NO" }.to_string();
+        let mut properties = Vec::new();
+        if let Some(threads) = self.num_threads.filter(|&n| n > 0) {
+            properties.push(("INFERENCE_NUM_THREADS", RwPropertyKey::InferenceNumThreads, threads.to_string()));
+        }
+        if let Some(hint) = self.hint {
+            properties.push(("PERFORMANCE_HINT", RwPropertyKey::HintPerformanceMode, hint.property_value().to_string()));
+        }
+        if let Some(pinning) = self.cpu_pinning {
+            properties.push(("ENABLE_CPU_PINNING", RwPropertyKey::HintEnableCpuPinning, yes_no(pinning)));
+        }
+        if let Some(hyper_threading) = self.hyper_threading {
+            properties.push(("ENABLE_HYPER_THREADING", RwPropertyKey::HintEnableHyperThreading, yes_no(hyper_threading)));
+        }
+        properties
+    }
+
+    /// Set on the CPU device of `core`; a property the plugin refuses is skipped
+    fn apply(&self, core: &mut Core) {
+        for (name, key, value) in self.properties() {
+            match core.set_property(&DeviceType::CPU, &PropertyKey::Rw(key), &value) {
+                Ok(()) => info!("OpenVINO {} = {}", name, value),
+                Err(e) => warn!("Failed to set OpenVINO {} to {}: {}", name, value, e),
+            }
+        }
+    }
 }
 
 /// Parakeet STT Engine using OpenVINO with 4 separate models
@@ -114,6 +280,16 @@ pub struct ParakeetEngine {
     encoder_model: Option<Mutex<CompiledModel>>,
     decoder_model: Option<Mutex<CompiledModel>>,
     joint_model: Option<Mutex<CompiledModel>>,
+    /// Time taken by the last load_model call
+    load_time_ms: u64,
+    /// Whether the last load reused compiled models from the cache
+    cache_warm: bool,
+    /// Threading and hint, applied at the next load
+    performance: OpenVinoPerformance,
+    /// Decode statistics of the last inference
+    last_telemetry: Mutex<Option<DecodeTelemetry>>,
+    /// Word timestamps of the last inference
+    last_word_timings: Mutex<Option<Vec<WordTiming>>>,
 }
 
 // Implement Send + Sync manually since InferRequest might not be Sync
@@ -133,18 +309,67 @@ impl ParakeetEngine {
             encoder_model: None,
             decoder_model: None,
             joint_model: None,
+            load_time_ms: 0,
+            cache_warm: false,
+            performance: OpenVinoPerformance::default(),
+            last_telemetry: Mutex::new(None),
+            last_word_timings: Mutex::new(None),
         }
     }
 
+    /// Compile the models with these CPU settings
+    pub fn with_performance(mut self, performance: OpenVinoPerformance) -> Self {
+        self.performance = performance;
+        self
+    }
+
+    /// Decode statistics of the last inference (blank ratio, tokens per second...)
+    pub fn last_telemetry(&self) -> Option<DecodeTelemetry> {
+        self.last_telemetry.lock().unwrap().clone()
+    }
+
+    /// Word timestamps of the last inference
+    pub fn last_word_timings(&self) -> Option<Vec<WordTiming>> {
+        self.last_word_timings.lock().unwrap().clone()
+    }
+
+    /// Load time of the models and whether the compiled-model cache was used
+    pub fn load_stats(&self) -> (u64, bool) {
+        (self.load_time_ms, self.cache_warm)
+    }
+
     /// Load the OpenVINO IR models from the model directory
     pub fn load_model(&mut self, model_dir: &Path) -> Result<()> {
         info!("Loading Parakeet models from {:?}", model_dir);
+        let load_start = Instant::now();
 
         // Initialize OpenVINO Core
         let mut core = Core::new().map_err(|e| {
             AppError::Transcription(format!("Failed to initialize OpenVINO: {}", e))
         })?;
 
+        // Enable the compiled-model cache (skips IR compilation on later launches)
+        let cache_warm = match model_cache::prepare(model_dir) {
+            Some((cache_dir, warm)) => {
+                match core.set_property(
+                    &DeviceType::CPU,
+                    &PropertyKey::Rw(RwPropertyKey::CacheDir),
+                    &cache_dir.to_string_lossy(),
+                ) {
+                    Ok(()) => {
+                        info!("OpenVINO model cache at {:?} ({})", cache_dir, if warm { "warm" } else { "cold" });
+                        warm
+                    }
+                    Err(e) => {
+                        warn!("Failed to enable OpenVINO model cache: {}", e);
+                        false
+                    }
+                }
+            }
+            None => false,
+        };
+        self.performance.apply(&mut core);
+
         // Load vocabulary from JSON
         let vocab_path = model_dir.join("parakeet_v3_vocab.json");
         let vocab_path = if vocab_path.exists() {
@@ -204,7 +429,13 @@ impl ParakeetEngine {
         self.decoder_model = Some(Mutex::new(decoder_model));
         self.joint_model = Some(Mutex::new(joint_model));
 
-        info!("All models loaded successfully");
+        self.load_time_ms = load_start.elapsed().as_millis() as u64;
+        self.cache_warm = cache_warm;
+        info!(
+            "All models loaded successfully in {} ms (model cache {})",
+            self.load_time_ms,
+            if cache_warm { "hit" } else { "miss, next launch will be faster" }
+        );
         Ok(())
     }
 
@@ -292,7 +523,7 @@ impl ParakeetEngine {
     pub fn transcribe(
         &self,
         samples: &[f32],
-        source_type: &str,
+        source_type: TranscriptionSource,
         source_name: Option<String>,
         language: TranscriptionLanguage,
         decoding_config: Option<DecodingConfig>,
@@ -317,27 +548,50 @@ impl ParakeetEngine {
         match self.run_inference(samples, language, &config) {
             Ok(text) => {
                 let now = chrono::Utc::now().to_rfc3339();
+                let confidence = timing::mean_confidence(&self.last_word_timings().unwrap_or_default());
                 let segments = vec![Segment {
                     id: Uuid::new_v4().to_string(),
                     start_ms: 0,
                     end_ms: duration_ms,
                     text: text.clone(),
-                    confidence: 0.95,
+                    confidence: confidence.unwrap_or(0.95),
+                    speaker: None,
+                    language: None,
+                    words: Vec::new(),
                 }];
 
-                Ok(Transcription {
+                let mut transcription = Transcription {
                     id: Uuid::new_v4().to_string(),
                     created_at: now.clone(),
                     updated_at: now,
-                    source_type: source_type.to_string(),
+                    source_type,
                     source_name,
+                    source_path: None,
+                    original_sample_rate: None,
                     duration_ms,
-                    language: "en".to_string(),
+                    language: "fr".to_string(),
                     segments,
                     raw_text: text,
                     edited_text: None,
                     is_edited: false,
-                })
+                    is_draft: false,
+                    warnings: Vec::new(),
+                    normalization_gain: None,
+                    action_items: Vec::new(),
+                    markers: Vec::new(),
+                    pauses: Vec::new(),
+                    capture: None,
+                    confidence,
+                    audio_path: None,
+                    translations: Vec::new(),
+                    notes: Vec::new(),
+                    folder: None,
+                    tags: Vec::new(),
+                    title: None,
+                    deleted_at: None,
+                };
+                language_id::label(&mut transcription, language, config.language_prior);
+                Ok(transcription)
             }
             Err(e) => {
                 warn!("Inference failed: {}. Falling back to mock transcription.", e);
@@ -355,18 +609,33 @@ impl ParakeetEngine {
     ) -> Result<String> {
         info!("Starting TDT inference on {} audio samples", audio.len());
 
+        let mut telemetry = DecodeTelemetry::default();
+
         // Check if audio needs chunking
-        if audio.len() > MAX_AUDIO_SAMPLES {
+        let result = if audio.len() > MAX_AUDIO_SAMPLES {
             info!(
                 "Audio too long ({} samples = {:.1}s), using chunked transcription",
                 audio.len(),
                 audio.len() as f32 / 16000.0
             );
-            return self.run_chunked_inference(audio, language, config);
+            self.run_chunked_inference(audio, language, config, &mut telemetry)
+        } else {
+            // Single chunk inference
+            self.run_single_inference(audio, language, config, &mut telemetry, None)
+        };
+
+        info!(
+            "Decode telemetry: blank_ratio={:.3}, avg_blank_margin={:.2}, tokens/s={:.1}",
+            telemetry.blank_ratio, telemetry.avg_blank_margin, telemetry.tokens_per_second
+        );
+        if telemetry.is_blank_collapse() {
+            warn!("Decoder collapsed to blank: model or backend is misbehaving");
         }
+        *self.last_telemetry.lock().unwrap() = Some(telemetry);
 
-        // Single chunk inference
-        self.run_single_inference(audio, language, config)
+        let (text, words) = result?;
+        *self.last_word_timings.lock().unwrap() = Some(words);
+        Ok(text)
     }
 
     /// Run inference on a single chunk (max 15s)
@@ -375,7 +644,21 @@ impl ParakeetEngine {
         audio: &[f32],
         language: TranscriptionLanguage,
         config: &DecodingConfig,
-    ) -> Result<String> {
+        telemetry: &mut DecodeTelemetry,
+        mut carry: Option<&mut ChunkCarry>,
+    ) -> Result<(String, Vec<WordTiming>)> {
+        // Jamais de troncature en plein mot : les chunks de split_audio_smart font
+        // au plus 14s, pas de récursion
+        if audio.len() > MAX_AUDIO_SAMPLES {
+            warn!(
+                "run_single_inference called with {} samples (> {}), routing through chunking",
+                audio.len(),
+                MAX_AUDIO_SAMPLES
+            );
+            return self.run_chunked_inference(audio, language, config, telemetry);
+        }
+        config.simulate_slow_inference();
+
         // Reset all InferRequests to ensure clean state
         self.reset_all_requests()?;
 
@@ -442,26 +725,55 @@ impl ParakeetEngine {
             "TDT decode config: beam={}, temp={:.2}, blank_penalty={:.1}",
             config.beam_width, config.temperature, config.blank_penalty
         );
+        let resumed = carry.as_ref().is_some_and(|c| c.state.is_some());
         let tokens = if config.beam_width <= 1 {
             // Greedy decoding (fastest)
-            self.tdt_greedy_decode(&encoder_output, valid_encoder_time, language, config)?
+            self.tdt_greedy_decode(
+                &encoder_output,
+                valid_encoder_time,
+                language,
+                config,
+                telemetry,
+                carry.as_deref_mut(),
+            )?
         } else {
             // Beam search decoding (higher quality)
-            self.tdt_beam_decode(&encoder_output, valid_encoder_time, language, config)?
+            self.tdt_beam_decode(&encoder_output, valid_encoder_time, language, config, telemetry)?
         };
+        telemetry.finish(tokens.len(), actual_audio_len);
         info!("TDT decoding produced {} tokens", tokens.len());
+        if let Some(dump) = &config.debug_dump {
+            dump.inference(&InferenceArtifacts {
+                audio,
+                mel: &mel_features,
+                mel_frames: time_frames,
+                valid_mel_frames: actual_mel_frames,
+                encoder_frames: encoder_tensor_time,
+                valid_encoder_frames: valid_encoder_time,
+                tokens: &tokens,
+            });
+        }
 
-        // Étape 4: Convertir tokens en texte
+        // Étape 4: Convertir tokens en texte (et en mots horodatés)
         let decoder = self.tdt_decoder.as_ref().unwrap();
         let text: String = tokens
             .iter()
-            .map(|&t| decoder.decode_single(t as usize))
+            .map(|t| decoder.decode_single(t.id as usize))
             .collect::<Vec<_>>()
             .join("");
         let text = text.trim().to_string();
-        info!("Decoded text: '{}'", text);
+        debug!(target: TRANSCRIPT_TARGET, "Decoded text: {:?}", text);
+        let words = timing::words_from_tokens(&tokens, |id| decoder.decode_single(id as usize), 0);
+        if let Some(carry) = carry {
+            // Premier morceau sans "▁" : suite du dernier mot du chunk précédent
+            let first_piece = tokens
+                .iter()
+                .map(|t| decoder.decode_single(t.id as usize))
+                .find(|piece| !piece.is_empty());
+            carry.continues_word = resumed && first_piece.is_some_and(|piece| !piece.starts_with(' '));
+        }
 
-        Ok(text)
+        Ok((text, words))
     }
 
     /// Run chunked inference for long audio using VAD-based smart chunking
@@ -473,7 +785,8 @@ impl ParakeetEngine {
         audio: &[f32],
         language: TranscriptionLanguage,
         decoding_config: &DecodingConfig,
-    ) -> Result<String> {
+        telemetry: &mut DecodeTelemetry,
+    ) -> Result<(String, Vec<WordTiming>)> {
         // Use smart VAD-based chunking (cuts at silence points)
         let chunk_config = SmartChunkConfig::default(); // 8-14s, cuts at silence
         let chunks = split_audio_smart(audio, &chunk_config);
@@ -484,9 +797,22 @@ impl ParakeetEngine {
             audio.len() as f32 / 16000.0
         );
 
-        let mut transcriptions: Vec<String> = Vec::new();
+        let mut transcriptions: Vec<ChunkText> = Vec::new();
+        let mut words: Vec<WordTiming> = Vec::new();
+        // Silence since the last speech, for sentence boundaries between chunks
+        let mut silence_ms: i64 = 0;
+        let mut previous_inference: Option<Duration> = None;
+        // Stateful decoding: each chunk resumes where the previous one stopped
+        let carrying = decoding_config.carry_decoder_state && decoding_config.beam_width <= 1;
+        let mut carry = ChunkCarry::default();
+        let progress = ChunkProgressTracker::new(
+            decoding_config.progress.as_ref(),
+            chunks.len(),
+            (audio.len() / 16) as i64,
+        );
 
         for (i, chunk) in chunks.iter().enumerate() {
+            progress.chunk_started(i, chunk.start_ms);
             let chunk_duration = chunk.samples.len() as f32 / 16000.0;
             info!(
                 "Processing chunk {}/{} ({:.1}s - {:.1}s, duration={:.1}s)",
@@ -497,28 +823,137 @@ impl ParakeetEngine {
                 chunk_duration
             );
 
-            match self.run_single_inference(&chunk.samples, language, decoding_config) {
-                Ok(text) => {
+            let silence_before_ms = match edge_silence_ms(&chunk.samples, &VadConfig::default()) {
+                Some((leading, trailing)) => {
+                    let gap = silence_ms + leading;
+                    silence_ms = trailing;
+                    gap
+                }
+                None => {
+                    silence_ms += chunk.end_ms - chunk.start_ms;
+                    silence_ms
+                }
+            };
+
+            // Transcribed before an interruption: the saved result
+            if let Some(saved) = decoding_config.journal.as_ref().and_then(|j| j.saved(i, chunk.start_ms, chunk.end_ms)) {
+                debug!(target: TRANSCRIPT_TARGET, "Chunk {} resumed: {:?}", i + 1, saved.text);
+                carry.reset();
+                if !saved.text.is_empty() {
+                    timing::append_chunk_words(&mut words, vec![WordTiming::spanning(&saved.text, chunk.start_ms, chunk.end_ms)]);
+                    match transcriptions.last_mut() {
+                        Some(previous) if saved.continues_word => previous.text.push_str(&saved.text),
+                        _ => transcriptions.push(ChunkText {
+                            text: saved.text.clone(),
+                            silence_before_ms,
+                        }),
+                    }
+                }
+                continue;
+            }
+            let save = |text: &str, continues_word: bool| {
+                if let Some(journal) = &decoding_config.journal {
+                    journal.record(
+                        chunks.len(),
+                        PendingChunk {
+                            index: i,
+                            start_ms: chunk.start_ms,
+                            end_ms: chunk.end_ms,
+                            text: text.to_string(),
+                            continues_word,
+                        },
+                    );
+                }
+            };
+
+            // Music/noise: insert a marker instead of letting the model hallucinate
+            if decoding_config.skip_non_speech {
+                let content = classify_audio(&chunk.samples);
+                if let Some(marker) = content.marker() {
+                    info!("Chunk {} skipped: {:?}", i + 1, content);
+                    carry.reset();
+                    save(marker, false);
+                    timing::append_chunk_words(&mut words, vec![WordTiming::spanning(marker, chunk.start_ms, chunk.end_ms)]);
+                    transcriptions.push(ChunkText {
+                        text: marker.to_string(),
+                        silence_before_ms,
+                    });
+                    continue;
+                }
+            }
+
+            // Same audio and settings as a previous run: reuse the result
+            // (not with a carried state, which the result depends on)
+            let cache_key = (decoding_config.chunk_cache && !carrying)
+                .then(|| chunk_cache::chunk_key(&chunk.samples, self.name(), language, decoding_config));
+            if let Some(text) = cache_key.as_deref().and_then(chunk_cache::lookup) {
+                debug!(target: TRANSCRIPT_TARGET, "Chunk {} from cache: {:?}", i + 1, text);
+                save(&text, false);
+                if !text.is_empty() {
+                    timing::append_chunk_words(&mut words, vec![WordTiming::spanning(&text, chunk.start_ms, chunk.end_ms)]);
+                    transcriptions.push(ChunkText { text, silence_before_ms });
+                }
+                continue;
+            }
+
+            // Low-power mode: idle or wait for AC between chunks when on battery
+            power::pace_chunk(decoding_config.power_mode, previous_inference);
+            let inference_start = Instant::now();
+
+            let mut chunk_telemetry = DecodeTelemetry::default();
+            let resumed = carry.state.is_some();
+            carry.stop_at(chunks.get(i + 1).map(|next| next.start_ms - chunk.start_ms));
+            let result = self.run_single_inference(
+                &chunk.samples,
+                language,
+                decoding_config,
+                &mut chunk_telemetry,
+                carrying.then_some(&mut carry),
+            );
+            previous_inference = Some(inference_start.elapsed());
+            match result {
+                Ok((text, mut chunk_words)) => {
+                    telemetry.merge(&chunk_telemetry);
                     let raw_text = text.trim().to_string();
-                    // Filter hallucinations at chunk start (punctuation, short nonsense words)
-                    let text = filter_chunk_hallucinations(&raw_text);
+                    // Filter hallucinations at chunk start (punctuation, short nonsense words);
+                    // a resumed chunk doesn't start from silence
+                    let text = if resumed { raw_text.clone() } else { filter_chunk_hallucinations(&raw_text) };
+                    if let Some(key) = &cache_key {
+                        chunk_cache::store(key, &text);
+                    }
+                    save(&text, carry.continues_word && !transcriptions.is_empty());
                     if !text.is_empty() {
                         if text != raw_text {
-                            info!("Chunk {} transcription (filtered): '{}' -> '{}'", i + 1, raw_text, text);
+                            debug!(target: TRANSCRIPT_TARGET, "Chunk {} transcription (filtered): {:?} -> {:?}", i + 1, raw_text, text);
                         } else {
-                            info!("Chunk {} transcription: '{}'", i + 1, text);
+                            debug!(target: TRANSCRIPT_TARGET, "Chunk {} transcription: {:?}", i + 1, text);
                         }
-                        transcriptions.push(text);
+                        for word in &mut chunk_words {
+                            word.start_ms += chunk.start_ms;
+                            word.end_ms += chunk.start_ms;
+                        }
+                        timing::keep_last_words(&mut chunk_words, text.split_whitespace().count());
+                        match transcriptions.last_mut() {
+                            // Cut inside a word: glue it back together
+                            Some(previous) if carry.continues_word => {
+                                previous.text.push_str(&text);
+                                timing::continue_last_word(&mut words, &mut chunk_words);
+                            }
+                            _ => transcriptions.push(ChunkText { text, silence_before_ms }),
+                        }
+                        timing::append_chunk_words(&mut words, chunk_words);
                     } else {
                         debug!("Chunk {} produced empty transcription after filtering (silence?)", i + 1);
                     }
                 }
                 Err(e) => {
                     warn!("Chunk {} transcription failed: {}", i + 1, e);
+                    carry.reset();
                     // Continue with other chunks
                 }
             }
         }
+        progress.finished();
 
         if transcriptions.is_empty() {
             return Err(AppError::Transcription(
@@ -526,11 +961,13 @@ impl ParakeetEngine {
             ));
         }
 
-        // Simple concatenation - no complex merge needed since we cut at silence
-        let merged_text = transcriptions.join(" ");
+        // Cuts are at silence, but chunks overlap slightly: drop repeated boundary phrases
+        // and restore sentence ends lost at long pauses
+        let merged_text = join_chunk_texts(&transcriptions, decoding_config.boundary_similarity, &decoding_config.chunk_join);
 
-        info!("Final transcription ({} chunks): '{}'", transcriptions.len(), merged_text);
-        Ok(merged_text)
+        info!("Final transcription: {} chunks, {} characters", transcriptions.len(), merged_text.chars().count());
+        debug!(target: TRANSCRIPT_TARGET, "Final transcription: {:?}", merged_text);
+        Ok((merged_text, words))
     }
 
     /// Calcule le mel spectrogram à partir de l'audio brut
@@ -538,44 +975,27 @@ impl ParakeetEngine {
         let mel_request = self.mel_request.as_ref().unwrap();
         let mut mel_request = mel_request.lock().unwrap();
 
-        // Préparer l'audio: padder ou tronquer à MAX_AUDIO_SAMPLES
+        // Préparer l'audio: tronquer à MAX_AUDIO_SAMPLES (le reste du tensor est mis à zéro)
         let actual_len = audio.len().min(MAX_AUDIO_SAMPLES);
-        let mut padded_audio = vec![0.0f32; MAX_AUDIO_SAMPLES];
-        padded_audio[..actual_len].copy_from_slice(&audio[..actual_len]);
 
         debug!("Mel input: {} actual samples, padded to {}", actual_len, MAX_AUDIO_SAMPLES);
 
-        // Récupérer le tensor d'entrée pré-alloué par le modèle
-        let mut input_tensor = mel_request.get_tensor("input_signals")
-            .map_err(|e| AppError::Transcription(format!("mel get input tensor: {:?}", e)))?;
-        {
-            let data = input_tensor.get_data_mut::<f32>()
-                .map_err(|e| AppError::Transcription(format!("mel input data: {:?}", e)))?;
-            data.fill(0.0);
-            data[..MAX_AUDIO_SAMPLES].copy_from_slice(&padded_audio);
-        }
+        // Remplir le tensor d'entrée pré-alloué par le modèle
+        ov_tensor::write_f32(&mut mel_request, "mel", "input_signals", &audio[..actual_len])?;
 
         // Input length: [1]
-        let mut length_tensor = mel_request.get_tensor("input_length")
-            .map_err(|e| AppError::Transcription(format!("mel get length tensor: {:?}", e)))?;
-        length_tensor.get_data_mut::<i64>()
-            .map_err(|e| AppError::Transcription(format!("mel length data: {:?}", e)))?[0] = actual_len as i64;
+        ov_tensor::set_i64(&mut mel_request, "mel", "input_length", actual_len as i64)?;
 
         // Inférence
         info!("Running mel spectrogram model...");
-        mel_request.infer()
-            .map_err(|e| AppError::Transcription(format!("mel infer: {:?}", e)))?;
+        ov_tensor::infer(&mut mel_request, "mel")?;
 
         // Récupérer la sortie
-        let output_tensor = mel_request.get_output_tensor_by_index(0)
-            .map_err(|e| AppError::Transcription(format!("mel get output: {:?}", e)))?;
-
-        let output_data = output_tensor.get_data::<f32>()
-            .map_err(|e| AppError::Transcription(format!("mel output data: {:?}", e)))?;
+        let output_data = ov_tensor::read_output_f32(&mut mel_request, "mel", 0)?;
 
         info!("Mel output size: {} elements", output_data.len());
 
-        Ok(output_data.to_vec())
+        Ok(output_data)
     }
 
     /// Encode les features mel avec l'encoder FastConformer
@@ -602,71 +1022,73 @@ impl ParakeetEngine {
 
         debug!("Encoder input: {} valid frames (of {} tensor frames)", frames_to_copy, mel_tensor_stride);
 
-        // Récupérer le tensor d'entrée pré-alloué
-        let mut input_tensor = encoder_request.get_tensor("melspectogram")
-            .map_err(|e| AppError::Transcription(format!("encoder get input tensor: {:?}", e)))?;
-        {
-            let data = input_tensor.get_data_mut::<f32>()
-                .map_err(|e| AppError::Transcription(format!("encoder input data: {:?}", e)))?;
-            data.fill(0.0);
-            data[..padded_mel.len()].copy_from_slice(&padded_mel);
-        }
+        // Remplir le tensor d'entrée pré-alloué
+        ov_tensor::write_f32(&mut encoder_request, "encoder", "melspectogram", &padded_mel)?;
 
         // Input length: [1] - passer le nombre réel de frames valides
-        let mut length_tensor = encoder_request.get_tensor("melspectogram_length")
-            .map_err(|e| AppError::Transcription(format!("encoder get length tensor: {:?}", e)))?;
-        length_tensor.get_data_mut::<i32>()
-            .map_err(|e| AppError::Transcription(format!("encoder length data: {:?}", e)))?[0] = frames_to_copy as i32;
+        ov_tensor::set_i32(&mut encoder_request, "encoder", "melspectogram_length", frames_to_copy as i32)?;
 
         // Inférence
         info!("Running encoder inference...");
-        encoder_request.infer()
-            .map_err(|e| AppError::Transcription(format!("encoder infer: {:?}", e)))?;
+        ov_tensor::infer(&mut encoder_request, "encoder")?;
 
         // Récupérer la sortie des features
-        let output_tensor = encoder_request.get_tensor("encoder_output")
-            .map_err(|e| AppError::Transcription(format!("encoder get output: {:?}", e)))?;
-
-        let output_data = output_tensor.get_data::<f32>()
-            .map_err(|e| AppError::Transcription(format!("encoder output data: {:?}", e)))?;
+        let output_data = ov_tensor::read_f32(&mut encoder_request, "encoder", "encoder_output")?;
 
         // FIX: Récupérer encoder_output_length pour savoir combien de time steps sont valides
-        let length_output = encoder_request.get_tensor("encoder_output_length")
-            .map_err(|e| AppError::Transcription(format!("encoder get output_length: {:?}", e)))?;
-
-        let valid_time_steps = length_output.get_data::<i64>()
-            .map_err(|e| AppError::Transcription(format!("encoder output_length data: {:?}", e)))?[0] as usize;
+        let valid_time_steps =
+            ov_tensor::read_i64(&mut encoder_request, "encoder", "encoder_output_length")? as usize;
 
         info!("Encoder output size: {} elements, valid time steps: {}", output_data.len(), valid_time_steps);
 
-        Ok((output_data.to_vec(), valid_time_steps))
+        Ok((output_data, valid_time_steps))
     }
 
     /// Décodage TDT greedy avec le decoder LSTM et le joint network
+    ///
+    /// Avec un `carry`, reprend l'état laissé par le chunk précédent et
+    /// s'arrête au début du chunk suivant, en y laissant son propre état.
     fn tdt_greedy_decode(
         &self,
         encoder_output: &[f32],
         encoder_time: usize,
         language: TranscriptionLanguage,
         config: &DecodingConfig,
-    ) -> Result<Vec<u32>> {
+        telemetry: &mut DecodeTelemetry,
+        mut carry: Option<&mut ChunkCarry>,
+    ) -> Result<Vec<TimedToken>> {
         let decoder_request = self.decoder_request.as_ref().unwrap();
         let joint_request = self.joint_request.as_ref().unwrap();
         let mut decoder_request = decoder_request.lock().unwrap();
         let mut joint_request = joint_request.lock().unwrap();
 
-        // États LSTM initiaux (zeros)
-        let mut h_state = vec![0.0f32; DECODER_NUM_LAYERS * DECODER_HIDDEN_DIM];
-        let mut c_state = vec![0.0f32; DECODER_NUM_LAYERS * DECODER_HIDDEN_DIM];
-
-        // Token actuel (commence avec blank ou token de langue)
-        let mut last_token: i64 = BLANK_TOKEN as i64;
+        // États LSTM initiaux (zeros, ou ceux du chunk précédent)
+        // et token actuel (commence avec blank ou token de langue)
+        let resumed = carry.as_mut().and_then(|c| c.state.take());
+        let is_resumed = resumed.is_some();
+        let (mut h_state, mut c_state, mut last_token, mut t) = match resumed {
+            Some(state) => (state.h, state.c, state.last_token, state.skip_frames),
+            None => (
+                vec![0.0f32; DECODER_NUM_LAYERS * DECODER_HIDDEN_DIM],
+                vec![0.0f32; DECODER_NUM_LAYERS * DECODER_HIDDEN_DIM],
+                BLANK_TOKEN as i64,
+                0,
+            ),
+        };
+        // Fin du décodage : début du chunk suivant en mode stateful
+        let end = carry
+            .as_ref()
+            .and_then(|c| c.stop_frame)
+            .map_or(encoder_time, |frame| frame.min(encoder_time));
 
-        let mut tokens: Vec<u32> = Vec::new();
+        let mut tokens: Vec<TimedToken> = Vec::new();
 
+        // Reprise : l'état contient déjà la langue et les mots précédents
         // Si une langue est forcée, initialiser le decoder avec la séquence de tokens correcte
         // Séquence: <|startoftranscript|> → <|nopredict_lang|> → <|lang|>
-        if let Some(lang_token) = language.token_id() {
+        if is_resumed {
+            debug!("Decoder resumed from the previous chunk (last token {})", last_token);
+        } else if let Some(lang_token) = language.token_id(self.vocab()) {
             info!(
                 "Forcing language with token sequence: startoftranscript(4) → nopredict_lang(23) → {}({}) ",
                 language.display_name(),
@@ -711,17 +1133,25 @@ impl ParakeetEngine {
             last_token = BLANK_TOKEN as i64;
 
             info!("Decoder conditioned with full language sequence, starting with BLANK");
+        } else if let Some(prior_token) = language.prior_token_id(config.language_prior, self.vocab()) {
+            // Auto avec préférence: <|startoftranscript|> → <|lang|> sans <|nopredict_lang|>,
+            // le modèle peut encore détecter une autre langue
+            info!("Auto language with soft prior token {}", prior_token);
+            for token in [TOKEN_START_OF_TRANSCRIPT, prior_token] {
+                let (_, new_h, new_c) =
+                    self.run_decoder_step(&mut decoder_request, token, &h_state, &c_state)?;
+                h_state = new_h;
+                c_state = new_c;
+            }
+            last_token = BLANK_TOKEN as i64;
         }
-        let mut t: usize = 0;
-
-        // Limite de sécurité
-        let max_iterations = encoder_time * 10;
+        let mut frame_symbols: usize = 0;
         let mut iterations = 0;
 
         // Buffer pour extraire une frame temporelle
         let mut encoder_frame = vec![0.0f32; ENCODER_OUTPUT_DIM];
 
-        while t < encoder_time && iterations < max_iterations {
+        while t < end {
             iterations += 1;
 
             // Extraire la frame temporelle t de l'encoder output
@@ -758,7 +1188,9 @@ impl ParakeetEngine {
             )?;
 
             // Étape 3: Decode TDT output
-            let (token, duration) = self.decode_tdt_output(&logits, config.temperature, config.blank_penalty);
+            let (token, duration, confidence) =
+                self.decode_tdt_output(&logits, config.temperature, config.blank_penalty);
+            telemetry.record_step(&logits[..VOCAB_SIZE], BLANK_TOKEN as usize, token == BLANK_TOKEN);
 
             // Debug log for first few iterations - with logits analysis
             if iterations <= 5 || tokens.len() < 10 {
@@ -780,21 +1212,22 @@ impl ParakeetEngine {
                     .unwrap_or((0, f32::NEG_INFINITY));
                 let best_nonblank_text = vocab.map(|v| v.decode_token(best_nonblank)).unwrap_or("?");
 
-                debug!("t={}, token={} ('{}'), dur={} | blank_logit={:.4}, best_nonblank={}('{}')={:.4}, margin={:.4}",
+                debug!(target: TRANSCRIPT_TARGET, "t={}, token={} ('{}'), dur={} | blank_logit={:.4}, best_nonblank={}('{}')={:.4}, margin={:.4}",
                     t, token, token_text, duration,
                     blank_logit, best_nonblank, best_nonblank_text, best_nonblank_logit,
                     blank_logit - best_nonblank_logit);
             }
 
             if token == BLANK_TOKEN {
-                // Blank: avancer dans le temps
+                // Blank: avancer dans le temps (durée >= 1)
                 t += duration as usize;
+                frame_symbols = 0;
 
                 // OPTIMIZATION: Inner loop for consecutive blanks
                 // Since blank doesn't update decoder state, we can reuse dec_out
                 // and just run joint network with new encoder frames
                 // (Same optimization as FluidAudio TdtDecoderV3.swift)
-                while t < encoder_time && iterations < max_iterations {
+                while t < end {
                     iterations += 1;
 
                     // Extract next encoder frame
@@ -809,18 +1242,28 @@ impl ParakeetEngine {
                         &dec_out,
                     )?;
 
-                    let (inner_token, inner_duration) = self.decode_tdt_output(
+                    let (inner_token, inner_duration, inner_confidence) = self.decode_tdt_output(
                         &inner_logits,
                         config.temperature,
                         config.blank_penalty,
                     );
+                    telemetry.record_step(
+                        &inner_logits[..VOCAB_SIZE],
+                        BLANK_TOKEN as usize,
+                        inner_token == BLANK_TOKEN,
+                    );
 
                     if inner_token == BLANK_TOKEN {
                         // Still blank, keep advancing
                         t += inner_duration as usize;
                     } else {
                         // Non-blank token found, emit it and exit inner loop
-                        tokens.push(inner_token);
+                        tokens.push(TimedToken {
+                            id: inner_token,
+                            frame: t,
+                            duration: inner_duration as usize,
+                            confidence: inner_confidence,
+                        });
                         last_token = inner_token as i64;
                         // Need to update decoder state for this new token
                         let (_, inner_h, inner_c) = self.run_decoder_step(
@@ -831,28 +1274,58 @@ impl ParakeetEngine {
                         )?;
                         h_state = inner_h;
                         c_state = inner_c;
-                        t += inner_duration as usize;
+                        let (advance, guarded) =
+                            config.frame_advance(inner_duration as usize, &mut frame_symbols);
+                        if guarded {
+                            telemetry.max_symbols_guard_hits += 1;
+                        }
+                        t += advance;
                         break;
                     }
                 }
             } else {
                 // Token émis
-                tokens.push(token);
+                tokens.push(TimedToken {
+                    id: token,
+                    frame: t,
+                    duration: duration as usize,
+                    confidence,
+                });
                 last_token = token as i64;
                 h_state = new_h;
                 c_state = new_c;
-                t += duration as usize;
+                let (advance, guarded) = config.frame_advance(duration as usize, &mut frame_symbols);
+                if guarded {
+                    telemetry.max_symbols_guard_hits += 1;
+                }
+                t += advance;
             }
         }
 
-        if iterations >= max_iterations {
-            warn!("TDT decoding reached max iterations limit");
+        if telemetry.max_symbols_guard_hits > 0 {
+            warn!(
+                "TDT decoding hit max_symbols_per_frame={} {} times, output may be truncated",
+                config.max_symbols_per_frame, telemetry.max_symbols_guard_hits
+            );
         }
 
         info!("Decoded {} tokens in {} iterations", tokens.len(), iterations);
+        if let Some(carry) = carry {
+            carry.state = Some(DecoderState {
+                h: h_state,
+                c: c_state,
+                last_token,
+                skip_frames: t.saturating_sub(end),
+            });
+        }
         Ok(tokens)
     }
 
+    /// Vocabulaire du modèle chargé (tokens de langue)
+    fn vocab(&self) -> Option<&Vocabulary> {
+        self.tdt_decoder.as_ref().map(|d| d.vocab())
+    }
+
     /// Exécute une étape du decoder LSTM
     fn run_decoder_step(
         &self,
@@ -862,52 +1335,19 @@ impl ParakeetEngine {
         c_in: &[f32],
     ) -> Result<(Vec<f32>, Vec<f32>, Vec<f32>)> {
         // Target: [1, 1]
-        let mut target_tensor = request.get_tensor("targets")
-            .map_err(|e| AppError::Transcription(format!("decoder get targets tensor: {:?}", e)))?;
-        target_tensor.get_data_mut::<i64>()
-            .map_err(|e| AppError::Transcription(format!("decoder target data: {:?}", e)))?[0] = target;
-
-        // H_in: [2, 1, 640]
-        let mut h_tensor = request.get_tensor("h_in")
-            .map_err(|e| AppError::Transcription(format!("decoder get h_in tensor: {:?}", e)))?;
-        {
-            let data = h_tensor.get_data_mut::<f32>()
-                .map_err(|e| AppError::Transcription(format!("decoder h_in data: {:?}", e)))?;
-            data.fill(0.0);
-            data[..h_in.len()].copy_from_slice(h_in);
-        }
+        ov_tensor::set_i64(request, "decoder", "targets", target)?;
 
-        // C_in: [2, 1, 640]
-        let mut c_tensor = request.get_tensor("c_in")
-            .map_err(|e| AppError::Transcription(format!("decoder get c_in tensor: {:?}", e)))?;
-        {
-            let data = c_tensor.get_data_mut::<f32>()
-                .map_err(|e| AppError::Transcription(format!("decoder c_in data: {:?}", e)))?;
-            data.fill(0.0);
-            data[..c_in.len()].copy_from_slice(c_in);
-        }
+        // H_in, C_in: [2, 1, 640]
+        ov_tensor::write_f32(request, "decoder", "h_in", h_in)?;
+        ov_tensor::write_f32(request, "decoder", "c_in", c_in)?;
 
         // Inférence
-        request.infer()
-            .map_err(|e| AppError::Transcription(format!("decoder infer: {:?}", e)))?;
+        ov_tensor::infer(request, "decoder")?;
 
         // Récupérer les sorties
-        let dec_output = request.get_tensor("decoder_output")
-            .map_err(|e| AppError::Transcription(format!("decoder get output: {:?}", e)))?;
-        let h_out = request.get_tensor("h_out")
-            .map_err(|e| AppError::Transcription(format!("decoder get h_out: {:?}", e)))?;
-        let c_out = request.get_tensor("c_out")
-            .map_err(|e| AppError::Transcription(format!("decoder get c_out: {:?}", e)))?;
-
-        let dec_data = dec_output.get_data::<f32>()
-            .map_err(|e| AppError::Transcription(format!("decoder output data: {:?}", e)))?
-            .to_vec();
-        let h_data = h_out.get_data::<f32>()
-            .map_err(|e| AppError::Transcription(format!("decoder h_out data: {:?}", e)))?
-            .to_vec();
-        let c_data = c_out.get_data::<f32>()
-            .map_err(|e| AppError::Transcription(format!("decoder c_out data: {:?}", e)))?
-            .to_vec();
+        let dec_data = ov_tensor::read_f32(request, "decoder", "decoder_output")?;
+        let h_data = ov_tensor::read_f32(request, "decoder", "h_out")?;
+        let c_data = ov_tensor::read_f32(request, "decoder", "c_out")?;
 
         Ok((dec_data, h_data, c_data))
     }
@@ -919,45 +1359,24 @@ impl ParakeetEngine {
         encoder_frame: &[f32],
         decoder_output: &[f32],
     ) -> Result<Vec<f32>> {
-        // Encoder output: [1, 1, 1024]
-        let mut enc_tensor = request.get_tensor("encoder_outputs")
-            .map_err(|e| AppError::Transcription(format!("joint get encoder tensor: {:?}", e)))?;
-        {
-            let data = enc_tensor.get_data_mut::<f32>()
-                .map_err(|e| AppError::Transcription(format!("joint enc data: {:?}", e)))?;
-            data.fill(0.0);
-            data[..encoder_frame.len()].copy_from_slice(encoder_frame);
-        }
-
-        // Decoder output: [1, 1, 640]
-        let mut dec_tensor = request.get_tensor("decoder_outputs")
-            .map_err(|e| AppError::Transcription(format!("joint get decoder tensor: {:?}", e)))?;
-        {
-            let data = dec_tensor.get_data_mut::<f32>()
-                .map_err(|e| AppError::Transcription(format!("joint dec data: {:?}", e)))?;
-            data.fill(0.0);
-            data[..decoder_output.len()].copy_from_slice(decoder_output);
-        }
+        // Encoder output: [1, 1, 1024], decoder output: [1, 1, 640]
+        ov_tensor::write_f32(request, "joint", "encoder_outputs", encoder_frame)?;
+        ov_tensor::write_f32(request, "joint", "decoder_outputs", decoder_output)?;
 
         // Inférence
-        request.infer()
-            .map_err(|e| AppError::Transcription(format!("joint infer: {:?}", e)))?;
+        ov_tensor::infer(request, "joint")?;
 
         // Récupérer les logits
-        let output = request.get_output_tensor()
-            .map_err(|e| AppError::Transcription(format!("joint get output: {:?}", e)))?;
-
-        let logits = output.get_data::<f32>()
-            .map_err(|e| AppError::Transcription(format!("joint output data: {:?}", e)))?
-            .to_vec();
+        let logits = ov_tensor::read_output_f32(request, "joint", 0)?;
 
         Ok(logits)
     }
 
-    /// Décode la sortie TDT: token + durée
+    /// Décode la sortie TDT: token + durée + probabilité du token (softmax)
     /// temperature: scaling factor for logits (1.0 = no scaling, <1.0 = more conservative)
     /// blank_penalty: value to subtract from blank token logit
-    fn decode_tdt_output(&self, logits: &[f32], temperature: f32, blank_penalty: f32) -> (u32, u32) {
+    /// La probabilité n'est calculée que pour un token émis (0.0 pour blank)
+    fn decode_tdt_output(&self, logits: &[f32], temperature: f32, blank_penalty: f32) -> (u32, u32, f32) {
         // Apply temperature scaling if needed
         let temp = if temperature > 0.0 { temperature } else { 1.0 };
 
@@ -1004,7 +1423,12 @@ impl ParakeetEngine {
             duration = 1;
         }
 
-        (max_token, duration)
+        let confidence = if max_token == BLANK_TOKEN {
+            0.0
+        } else {
+            decoder::token_posterior(token_logits, max_token as usize, temperature, BLANK_TOKEN as usize, blank_penalty)
+        };
+        (max_token, duration, confidence)
     }
 
     /// Get top-k tokens with their log probabilities from logits
@@ -1062,7 +1486,8 @@ impl ParakeetEngine {
         encoder_time: usize,
         language: TranscriptionLanguage,
         config: &DecodingConfig,
-    ) -> Result<Vec<u32>> {
+        telemetry: &mut DecodeTelemetry,
+    ) -> Result<Vec<TimedToken>> {
         let decoder_request = self.decoder_request.as_ref().unwrap();
         let joint_request = self.joint_request.as_ref().unwrap();
         let mut decoder_request = decoder_request.lock().unwrap();
@@ -1084,10 +1509,11 @@ impl ParakeetEngine {
             c_state: vec![0.0f32; DECODER_NUM_LAYERS * DECODER_HIDDEN_DIM],
             last_token: BLANK_TOKEN as i64,
             current_time: 0,
+            frame_symbols: 0,
         }];
 
         // If language is forced, condition all beams
-        if let Some(lang_token) = language.token_id() {
+        if let Some(lang_token) = language.token_id(self.vocab()) {
             info!(
                 "Conditioning beams with language: {} (token {})",
                 language.display_name(),
@@ -1129,17 +1555,27 @@ impl ParakeetEngine {
 
             // Reset last_token to BLANK for normal decoding
             beam.last_token = BLANK_TOKEN as i64;
+        } else if let Some(prior_token) = language.prior_token_id(config.language_prior, self.vocab()) {
+            // Auto with a soft prior: no <|nopredict_lang|>, the model may still switch
+            info!("Conditioning beams with soft language prior (token {})", prior_token);
+            let beam = &mut beams[0];
+            for token in [TOKEN_START_OF_TRANSCRIPT, prior_token] {
+                let (_, new_h, new_c) =
+                    self.run_decoder_step(&mut decoder_request, token, &beam.h_state, &beam.c_state)?;
+                beam.h_state = new_h;
+                beam.c_state = new_c;
+            }
+            beam.last_token = BLANK_TOKEN as i64;
         }
 
         // Buffer for encoder frame
         let mut encoder_frame = vec![0.0f32; ENCODER_OUTPUT_DIM];
 
-        // Safety limit
-        let max_iterations = encoder_time * 10;
         let mut iterations = 0;
 
-        // Main beam search loop
-        while iterations < max_iterations {
+        // Main beam search loop (each beam advances at least one frame every
+        // max_symbols_per_frame steps, so this terminates)
+        loop {
             iterations += 1;
 
             // Check if all beams have finished (reached end of encoder)
@@ -1181,6 +1617,8 @@ impl ParakeetEngine {
 
                 // Get top-k tokens
                 let top_k = self.get_top_k_tokens(&logits, beam_width, temperature, config.blank_penalty);
+                let best_is_blank = top_k.first().is_some_and(|&(token, _)| token == BLANK_TOKEN);
+                telemetry.record_step(&logits[..VOCAB_SIZE], BLANK_TOKEN as usize, best_is_blank);
                 let raw_duration = self.get_best_duration(&logits, temperature);
 
                 // Expand beam with top-k tokens
@@ -1192,6 +1630,7 @@ impl ParakeetEngine {
                         c_state: beam.c_state.clone(),
                         last_token: beam.last_token,
                         current_time: beam.current_time,
+                        frame_symbols: beam.frame_symbols,
                     };
 
                     if token == BLANK_TOKEN {
@@ -1199,15 +1638,32 @@ impl ParakeetEngine {
                         // CRITICAL: If duration=0 for blank, force to 1 to avoid infinite loop
                         let duration = if raw_duration == 0 { 1 } else { raw_duration };
                         new_beam.current_time += duration as usize;
+                        new_beam.frame_symbols = 0;
                     } else {
                         // Token emitted: update states and advance time
-                        new_beam.tokens.push(token);
+                        new_beam.tokens.push(TimedToken {
+                            id: token,
+                            frame: t,
+                            duration: raw_duration as usize,
+                            confidence: decoder::token_posterior(
+                                &logits[..VOCAB_SIZE],
+                                token as usize,
+                                temperature,
+                                BLANK_TOKEN as usize,
+                                config.blank_penalty,
+                            ),
+                        });
                         new_beam.last_token = token as i64;
                         new_beam.h_state = new_h.clone();
                         new_beam.c_state = new_c.clone();
                         // For non-blank tokens, duration=0 means "don't advance time"
-                        // This allows multiple tokens at the same timestep
-                        new_beam.current_time += raw_duration as usize;
+                        // This allows multiple tokens at the same timestep (up to max_symbols_per_frame)
+                        let (advance, guarded) =
+                            config.frame_advance(raw_duration as usize, &mut new_beam.frame_symbols);
+                        if guarded {
+                            telemetry.max_symbols_guard_hits += 1;
+                        }
+                        new_beam.current_time += advance;
                     }
 
                     new_beams.push(new_beam);
@@ -1231,8 +1687,11 @@ impl ParakeetEngine {
             }
         }
 
-        if iterations >= max_iterations {
-            warn!("Beam search reached max iterations limit");
+        if telemetry.max_symbols_guard_hits > 0 {
+            warn!(
+                "Beam search hit max_symbols_per_frame={} {} times, output may be truncated",
+                config.max_symbols_per_frame, telemetry.max_symbols_guard_hits
+            );
         }
 
         // Return tokens from best beam
@@ -1255,7 +1714,7 @@ impl ParakeetEngine {
     fn mock_transcribe(
         &self,
         samples: &[f32],
-        source_type: &str,
+        source_type: TranscriptionSource,
         source_name: Option<String>,
     ) -> Result<Transcription> {
         let now = chrono::Utc::now().to_rfc3339();
@@ -1270,20 +1729,40 @@ impl ParakeetEngine {
             end_ms: duration_ms,
             text: mock_text.to_string(),
             confidence: 0.85,
+            speaker: None,
+            language: None,
+            words: Vec::new(),
         }];
 
         Ok(Transcription {
             id: Uuid::new_v4().to_string(),
             created_at: now.clone(),
             updated_at: now,
-            source_type: source_type.to_string(),
+            source_type,
             source_name,
+            source_path: None,
+            original_sample_rate: None,
             duration_ms,
             language: "fr".to_string(),
             segments,
             raw_text: mock_text.to_string(),
             edited_text: None,
             is_edited: false,
+            is_draft: false,
+            warnings: Vec::new(),
+            normalization_gain: None,
+            action_items: Vec::new(),
+            markers: Vec::new(),
+            pauses: Vec::new(),
+            capture: None,
+            confidence: None,
+            audio_path: None,
+            translations: Vec::new(),
+            notes: Vec::new(),
+            folder: None,
+            tags: Vec::new(),
+            title: None,
+            deleted_at: None,
         })
     }
 }
@@ -1351,4 +1830,12 @@ impl ASREngine for ParakeetEngine {
         // Delegate to the existing run_inference method
         ParakeetEngine::run_inference(self, samples, language, config)
     }
+
+    fn last_telemetry(&self) -> Option<DecodeTelemetry> {
+        ParakeetEngine::last_telemetry(self)
+    }
+
+    fn last_word_timings(&self) -> Option<Vec<WordTiming>> {
+        ParakeetEngine::last_word_timings(self)
+    }
 }
@@ -0,0 +1,203 @@
+//! Chunk results of a file transcription, saved as they come
+//!
+//! The chunked loops of the backends save each chunk's text in
+//! `pending_transcriptions` through the `ChunkJournal` of the decoding config.
+//! After a crash, the same audio and settings give the same chunks: a resumed
+//! run takes the saved ones instead of decoding them again. The chunks done
+//! can also be kept as they are, as a partial transcription.
+//!
+//! In isolated mode the worker process has no database: its journal forwards
+//! each result to the app, which saves it. The CoreML backend transcribes a
+//! file in one sidecar call, without chunks: nothing is saved during its
+//! runs, and an interrupted one is transcribed again from the start.
+
+use crate::storage::{self, with_db, PendingChunk, PendingTranscription, Segment, Transcription, TranscriptionSource, TranscriptionWarning};
+use std::fmt;
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Where the chunks of one pending transcription are saved
+#[derive(Clone)]
+pub struct ChunkJournal(Arc<Journal>);
+
+type ForwardFn = dyn Fn(usize, PendingChunk) + Send + Sync;
+
+struct Journal {
+    pending_id: String,
+    /// Saved by an interrupted run
+    saved: Vec<PendingChunk>,
+    /// Receives the new results instead of the database (worker process)
+    forward: Option<Box<ForwardFn>>,
+}
+
+impl ChunkJournal {
+    pub fn new(pending_id: &str, saved: Vec<PendingChunk>) -> Self {
+        Self(Arc::new(Journal {
+            pending_id: pending_id.to_string(),
+            saved,
+            forward: None,
+        }))
+    }
+
+    /// Journal of the worker process: `forward` gets each new result with the
+    /// number of chunks, for the app to record
+    pub fn forwarding(
+        pending_id: &str,
+        saved: Vec<PendingChunk>,
+        forward: impl Fn(usize, PendingChunk) + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(Journal {
+            pending_id: pending_id.to_string(),
+            saved,
+            forward: Some(Box::new(forward)),
+        }))
+    }
+
+    pub fn pending_id(&self) -> &str {
+        &self.0.pending_id
+    }
+
+    /// All the results saved by an interrupted run
+    pub fn saved_chunks(&self) -> &[PendingChunk] {
+        &self.0.saved
+    }
+
+    /// Result of this chunk saved by an interrupted run (same boundaries only)
+    pub fn saved(&self, index: usize, start_ms: i64, end_ms: i64) -> Option<&PendingChunk> {
+        self.0
+            .saved
+            .iter()
+            .find(|c| c.index == index && c.start_ms == start_ms && c.end_ms == end_ms)
+    }
+
+    /// Save the result of a chunk (a failure only costs the chunk on resume)
+    pub fn record(&self, chunk_count: usize, chunk: PendingChunk) {
+        if let Some(forward) = &self.0.forward {
+            forward(chunk_count, chunk);
+            return;
+        }
+        if storage::is_read_only() {
+            return;
+        }
+        if let Err(e) = with_db(|conn| storage::append_pending_chunk(conn, &self.0.pending_id, chunk_count, &chunk)) {
+            warn!("Failed to save chunk {} of {}: {}", chunk.index + 1, self.0.pending_id, e);
+        }
+    }
+}
+
+impl fmt::Debug for ChunkJournal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ChunkJournal({})", self.0.pending_id)
+    }
+}
+
+/// The chunks done of an interrupted transcription, one segment each
+pub fn partial_transcription(pending: &PendingTranscription) -> Transcription {
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut segments: Vec<Segment> = Vec::new();
+    for chunk in pending.chunks.iter().filter(|c| !c.text.is_empty()) {
+        match segments.last_mut() {
+            Some(previous) if chunk.continues_word => {
+                previous.text.push_str(&chunk.text);
+                previous.end_ms = chunk.end_ms;
+            }
+            _ => segments.push(Segment {
+                id: Uuid::new_v4().to_string(),
+                start_ms: chunk.start_ms,
+                end_ms: chunk.end_ms,
+                text: chunk.text.clone(),
+                confidence: 0.0,
+                speaker: None,
+                language: None,
+                words: Vec::new(),
+            }),
+        }
+    }
+    let raw_text = segments.iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join(" ");
+    let done = pending.chunks.len();
+
+    Transcription {
+        id: Uuid::new_v4().to_string(),
+        created_at: now.clone(),
+        updated_at: now,
+        source_type: TranscriptionSource::File,
+        source_name: pending.source_name.clone(),
+        source_path: Some(pending.source_path.clone()),
+        original_sample_rate: None,
+        duration_ms: pending.chunks.last().map_or(0, |c| c.end_ms),
+        language: "fr".to_string(),
+        segments,
+        raw_text,
+        edited_text: None,
+        is_edited: false,
+        is_draft: false,
+        warnings: vec![TranscriptionWarning {
+            code: "partial".to_string(),
+            message: format!(
+                "Transcription interrupted: {} of {} chunks transcribed",
+                done,
+                pending.chunk_count.max(done)
+            ),
+        }],
+        normalization_gain: None,
+        action_items: Vec::new(),
+        markers: Vec::new(),
+        pauses: Vec::new(),
+        capture: None,
+        confidence: None,
+        audio_path: None,
+        translations: Vec::new(),
+        notes: Vec::new(),
+        folder: None,
+        tags: Vec::new(),
+        title: None,
+        deleted_at: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(index: usize, text: &str, continues_word: bool) -> PendingChunk {
+        PendingChunk {
+            index,
+            start_ms: index as i64 * 10_000,
+            end_ms: (index as i64 + 1) * 10_000,
+            text: text.to_string(),
+            continues_word,
+        }
+    }
+
+    #[test]
+    fn test_partial_transcription_of_saved_chunks() {
+        let pending = PendingTranscription {
+            id: "p".to_string(),
+            source_path: "/tmp/reunion.wav".to_string(),
+            source_name: Some("reunion.wav".to_string()),
+            language: None,
+            decoding_config: None,
+            normalization: None,
+            chunk_count: 10,
+            chunks: vec![
+                chunk(0, "Bonjour a tous. Nous commen", false),
+                chunk(1, "cons la reunion.", true),
+                chunk(2, "", false),
+                chunk(3, "Premier point.", false),
+            ],
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let transcription = partial_transcription(&pending);
+        assert_eq!(transcription.raw_text, "Bonjour a tous. Nous commencons la reunion. Premier point.");
+        assert_eq!(transcription.segments.len(), 2);
+        assert_eq!((transcription.segments[0].start_ms, transcription.segments[0].end_ms), (0, 20_000));
+        assert_eq!(transcription.duration_ms, 40_000);
+        assert!(transcription.warnings[0].message.contains("4 of 10"));
+
+        let journal = ChunkJournal::new("p", pending.chunks.clone());
+        assert!(journal.saved(1, 10_000, 20_000).is_some());
+        assert!(journal.saved(1, 10_000, 21_000).is_none());
+    }
+}
@@ -0,0 +1,189 @@
+//! Named decoding parameters per backend
+//!
+//! The best temperature and blank penalty are not the same for every backend
+//! (the OpenVINO and ONNX Runtime models were tuned on different sweeps), so
+//! each backend has its own "fast" and "accurate" profiles, plus the ones the
+//! user saves. Switching backends selects the profile last chosen for it.
+
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Greedy decoding, the default of every backend
+pub const FAST: &str = "fast";
+
+/// Beam search with the parameters tuned for the backend
+pub const ACCURATE: &str = "accurate";
+
+/// Beam width, temperature and blank penalty saved under a name for one backend
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodingProfile {
+    pub name: String,
+    /// Backend key, as in the settings: "openvino", "onnxruntime", "coreml"
+    pub backend: String,
+    pub beam_width: usize,
+    pub temperature: f32,
+    pub blank_penalty: f32,
+    /// Shipped with the app (saving one under its name overrides it)
+    #[serde(default)]
+    pub built_in: bool,
+}
+
+impl DecodingProfile {
+    fn built_in(backend: &str, name: &str, beam_width: usize, temperature: f32, blank_penalty: f32) -> Self {
+        Self {
+            name: name.to_string(),
+            backend: backend.to_string(),
+            beam_width,
+            temperature,
+            blank_penalty,
+            built_in: true,
+        }
+    }
+
+    /// Same parameters (name and backend aside)
+    fn same_parameters(&self, other: &Self) -> bool {
+        self.beam_width == other.beam_width
+            && self.temperature == other.temperature
+            && self.blank_penalty == other.blank_penalty
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(AppError::InvalidInput("Profile name is empty".into()));
+        }
+        if !matches!(self.backend.as_str(), "openvino" | "onnxruntime" | "coreml") {
+            return Err(AppError::InvalidInput(format!("Unknown backend: {}", self.backend)));
+        }
+        if self.beam_width == 0 || self.beam_width > 16 {
+            return Err(AppError::InvalidInput(format!("Invalid beam width: {}", self.beam_width)));
+        }
+        if !(0.05..=2.0).contains(&self.temperature) {
+            return Err(AppError::InvalidInput(format!("Invalid temperature: {}", self.temperature)));
+        }
+        if !(0.0..=15.0).contains(&self.blank_penalty) {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid blank penalty: {}",
+                self.blank_penalty
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Profiles shipped for `backend`
+///
+/// CoreML decodes greedily only: no accurate profile.
+pub fn built_in_profiles(backend: &str) -> Vec<DecodingProfile> {
+    let fast = DecodingProfile::built_in(backend, FAST, 1, 1.0, 6.0);
+    match backend {
+        "openvino" => vec![fast, DecodingProfile::built_in(backend, ACCURATE, 5, 0.7, 6.0)],
+        "onnxruntime" => vec![fast, DecodingProfile::built_in(backend, ACCURATE, 5, 0.7, 7.0)],
+        _ => vec![fast],
+    }
+}
+
+/// Profiles of `backend`: the built-in ones (overridden by a saved profile of
+/// the same name), then the saved ones by name
+pub fn profiles_for(backend: &str, saved: &[DecodingProfile]) -> Vec<DecodingProfile> {
+    let mut own: Vec<&DecodingProfile> = saved.iter().filter(|p| p.backend == backend).collect();
+    own.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut profiles: Vec<DecodingProfile> = built_in_profiles(backend)
+        .into_iter()
+        .map(|b| own.iter().find(|p| p.name == b.name).map_or(b, |p| (*p).clone()))
+        .collect();
+    for p in own {
+        if !profiles.iter().any(|q| q.name == p.name) {
+            profiles.push(p.clone());
+        }
+    }
+    profiles
+}
+
+/// Profile selected for `backend`, or its fast profile
+pub fn selected_profile(
+    backend: &str,
+    saved: &[DecodingProfile],
+    selected: &HashMap<String, String>,
+) -> DecodingProfile {
+    let profiles = profiles_for(backend, saved);
+    let name = selected.get(backend).map_or(FAST, String::as_str);
+    profiles
+        .iter()
+        .find(|p| p.name == name)
+        .or_else(|| profiles.iter().find(|p| p.name == FAST))
+        .cloned()
+        .unwrap_or_else(|| DecodingProfile::built_in(backend, FAST, 1, 1.0, 6.0))
+}
+
+/// Store `profile` among the saved ones, replacing the one with its name
+///
+/// A built-in profile saved unchanged is not stored (that undoes an
+/// override). Returns the profile as listed afterwards.
+pub fn save_profile(saved: &mut Vec<DecodingProfile>, mut profile: DecodingProfile) -> Result<DecodingProfile> {
+    profile.validate()?;
+    profile.name = profile.name.trim().to_string();
+    saved.retain(|p| !(p.backend == profile.backend && p.name == profile.name));
+
+    let shipped = built_in_profiles(&profile.backend)
+        .into_iter()
+        .find(|b| b.name == profile.name);
+    match shipped {
+        Some(shipped) if shipped.same_parameters(&profile) => Ok(shipped),
+        _ => {
+            profile.built_in = false;
+            saved.push(profile.clone());
+            Ok(profile)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(backend: &str, name: &str, beam_width: usize) -> DecodingProfile {
+        DecodingProfile {
+            name: name.to_string(),
+            backend: backend.to_string(),
+            beam_width,
+            temperature: 0.8,
+            blank_penalty: 6.5,
+            built_in: false,
+        }
+    }
+
+    #[test]
+    fn test_saved_profiles_override_and_select() {
+        let mut saved = Vec::new();
+        save_profile(&mut saved, profile("onnxruntime", "accurate", 8)).unwrap();
+        save_profile(&mut saved, profile("onnxruntime", " reunions ", 3)).unwrap();
+        save_profile(&mut saved, profile("openvino", "custom", 4)).unwrap();
+
+        let listed = profiles_for("onnxruntime", &saved);
+        let names: Vec<&str> = listed.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["fast", "accurate", "reunions"]);
+        assert!(listed[0].built_in);
+        assert_eq!(listed[1].beam_width, 8);
+        assert!(!listed[1].built_in);
+
+        // Saving the built-in values again removes the override
+        let restored = save_profile(&mut saved, built_in_profiles("onnxruntime")[1].clone()).unwrap();
+        assert!(restored.built_in);
+        assert_eq!(profiles_for("onnxruntime", &saved)[1].beam_width, 5);
+
+        let mut selected = HashMap::new();
+        assert_eq!(selected_profile("openvino", &saved, &selected).name, FAST);
+        selected.insert("openvino".to_string(), "custom".to_string());
+        assert_eq!(selected_profile("openvino", &saved, &selected).beam_width, 4);
+        // Selection of a profile deleted since: back to fast
+        selected.insert("onnxruntime".to_string(), "gone".to_string());
+        assert_eq!(selected_profile("onnxruntime", &saved, &selected).name, FAST);
+
+        assert!(save_profile(&mut saved, profile("openvino", "", 1)).is_err());
+        assert!(save_profile(&mut saved, profile("whisper", "x", 1)).is_err());
+        assert!(save_profile(&mut saved, profile("openvino", "x", 0)).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+//! Progress of chunked transcription
+//!
+//! The caller puts a `ProgressSink` in the `DecodingConfig`; the chunked
+//! inference loops of the backends report through a `ChunkProgressTracker`
+//! before each chunk and once all are done. Audio short enough for a single
+//! inference reports nothing.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Where the chunked loop stands
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChunkProgress {
+    /// Chunks processed so far (`chunk_count` once done)
+    pub chunk_index: usize,
+    pub chunk_count: usize,
+    /// Audio processed so far
+    pub processed_ms: i64,
+    pub total_ms: i64,
+    /// Audio processed per wall-clock time since the start (2.0 = twice faster than realtime)
+    pub realtime_factor: f64,
+}
+
+/// Callback receiving the progress of a transcription
+#[derive(Clone)]
+pub struct ProgressSink(Arc<dyn Fn(ChunkProgress) + Send + Sync>);
+
+impl ProgressSink {
+    pub fn new(report: impl Fn(ChunkProgress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(report))
+    }
+
+    pub fn report(&self, progress: ChunkProgress) {
+        (self.0)(progress)
+    }
+}
+
+impl fmt::Debug for ProgressSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressSink")
+    }
+}
+
+/// Measures and reports the progress of one chunked inference
+pub struct ChunkProgressTracker<'a> {
+    sink: Option<&'a ProgressSink>,
+    chunk_count: usize,
+    total_ms: i64,
+    started: Instant,
+}
+
+impl<'a> ChunkProgressTracker<'a> {
+    pub fn new(sink: Option<&'a ProgressSink>, chunk_count: usize, total_ms: i64) -> Self {
+        Self {
+            sink,
+            chunk_count,
+            total_ms,
+            started: Instant::now(),
+        }
+    }
+
+    /// Chunk `index` starts at `start_ms`: everything before it is processed
+    pub fn chunk_started(&self, index: usize, start_ms: i64) {
+        self.report(index, start_ms);
+    }
+
+    /// All chunks are processed
+    pub fn finished(&self) {
+        self.report(self.chunk_count, self.total_ms);
+    }
+
+    fn report(&self, chunk_index: usize, processed_ms: i64) {
+        if let Some(sink) = self.sink {
+            let elapsed_ms = self.started.elapsed().as_secs_f64() * 1000.0;
+            sink.report(progress(
+                chunk_index,
+                self.chunk_count,
+                processed_ms,
+                self.total_ms,
+                elapsed_ms,
+            ));
+        }
+    }
+}
+
+fn progress(
+    chunk_index: usize,
+    chunk_count: usize,
+    processed_ms: i64,
+    total_ms: i64,
+    elapsed_ms: f64,
+) -> ChunkProgress {
+    let processed_ms = processed_ms.clamp(0, total_ms);
+    ChunkProgress {
+        chunk_index,
+        chunk_count,
+        processed_ms,
+        total_ms,
+        realtime_factor: if elapsed_ms > 0.0 {
+            processed_ms as f64 / elapsed_ms
+        } else {
+            0.0
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_is_clamped_and_measured() {
+        let p = progress(2, 4, 30_000, 60_000, 10_000.0);
+        assert_eq!(p.processed_ms, 30_000);
+        assert!((p.realtime_factor - 3.0).abs() < 1e-9);
+
+        // Chunk ends may overshoot the resampled length by a few ms
+        assert_eq!(progress(4, 4, 60_010, 60_000, 1.0).processed_ms, 60_000);
+        assert_eq!(progress(0, 4, 0, 60_000, 0.0).realtime_factor, 0.0);
+    }
+}
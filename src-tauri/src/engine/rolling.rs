@@ -0,0 +1,191 @@
+//! Rolling transcription of long recordings
+//!
+//! While a long recording goes on, the audio is cut into blocks at pauses
+//! (between 30 s and 60 s long) and each block is transcribed in the
+//! background, through a bounded queue between the capture and the engine.
+//! On stop, only the audio after the last block is left to transcribe: the
+//! block transcriptions are then joined into one, their segment times
+//! shifted by the start of each block.
+
+use crate::audio::vad::{find_best_cut_point, VadConfig};
+use crate::engine::merger::{join_chunk_texts, ChunkText};
+use crate::engine::DecodingConfig;
+use crate::storage::{Segment, SegmentWord, Transcription, TranscriptionWarning};
+
+const SAMPLES_PER_MS: usize = 16;
+
+/// Shortest block: below, the queue would mostly hold model overhead
+pub const MIN_BLOCK_MS: usize = 30_000;
+
+/// Longest block, cut at its quietest point between the two bounds
+pub const MAX_BLOCK_MS: usize = 60_000;
+
+/// Blocks waiting for the engine before the capture side waits in turn
+pub const QUEUE_BLOCKS: usize = 4;
+
+/// Transcription of one block of the recording
+#[derive(Debug, Clone)]
+pub struct Block {
+    /// Start of the block in the recording
+    pub start_ms: i64,
+    pub transcription: Transcription,
+}
+
+/// End of the next block in `pending` (16 kHz audio after the last block),
+/// None until there is enough audio to choose a pause
+pub fn next_block_end(pending: &[f32], vad: &VadConfig) -> Option<usize> {
+    let max_len = MAX_BLOCK_MS * SAMPLES_PER_MS;
+    if pending.len() < max_len {
+        return None;
+    }
+    let (cut, _, _) = find_best_cut_point(pending, MIN_BLOCK_MS * SAMPLES_PER_MS, max_len, vad);
+    Some(cut)
+}
+
+/// One transcription from the blocks, in order; None without blocks
+///
+/// Texts are joined as chunks are (boundary dedup, sentence end after a
+/// long pause), confidence is the duration-weighted mean of the blocks.
+pub fn join_blocks(blocks: Vec<Block>, config: &DecodingConfig) -> Option<Transcription> {
+    let last = blocks.last()?;
+    let duration_ms = last.start_ms + last.transcription.duration_ms;
+
+    let mut chunks = Vec::new();
+    let mut segments = Vec::new();
+    let mut warnings: Vec<TranscriptionWarning> = Vec::new();
+    let (mut weighted, mut scored_ms) = (0.0, 0);
+    let mut previous_end_ms = 0;
+    let mut base: Option<Transcription> = None;
+
+    for block in blocks {
+        let offset = block.start_ms;
+        let t = block.transcription;
+
+        let speech_start = t.segments.first().map_or(0, |s| s.start_ms) + offset;
+        chunks.push(ChunkText {
+            text: t.raw_text.clone(),
+            silence_before_ms: if base.is_some() { speech_start - previous_end_ms } else { 0 },
+        });
+        if let Some(last) = t.segments.last() {
+            previous_end_ms = last.end_ms + offset;
+        }
+
+        segments.extend(t.segments.iter().map(|s| Segment {
+            start_ms: s.start_ms + offset,
+            end_ms: s.end_ms + offset,
+            words: s
+                .words
+                .iter()
+                .map(|w| SegmentWord {
+                    start_ms: w.start_ms + offset,
+                    end_ms: w.end_ms + offset,
+                    ..w.clone()
+                })
+                .collect(),
+            ..s.clone()
+        }));
+        for warning in &t.warnings {
+            if !warnings.iter().any(|w| w.code == warning.code) {
+                warnings.push(warning.clone());
+            }
+        }
+        if let Some(confidence) = t.confidence {
+            weighted += confidence * t.duration_ms as f64;
+            scored_ms += t.duration_ms;
+        }
+        base.get_or_insert(t);
+    }
+
+    let raw_text = join_chunk_texts(&chunks, config.boundary_similarity, &config.chunk_join);
+    Some(Transcription {
+        duration_ms,
+        raw_text,
+        segments,
+        warnings,
+        confidence: (scored_ms > 0).then(|| weighted / scored_ms as f64),
+        ..base?
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::TranscriptionSource;
+
+    fn block(start_ms: i64, duration_ms: i64, text: &str, confidence: Option<f64>) -> Block {
+        Block {
+            start_ms,
+            transcription: Transcription {
+                id: format!("t{}", start_ms),
+                created_at: String::new(),
+                updated_at: String::new(),
+                source_type: TranscriptionSource::Dictation,
+                source_name: None,
+                source_path: None,
+                original_sample_rate: None,
+                duration_ms,
+                language: "fr".to_string(),
+                segments: vec![Segment {
+                    id: format!("s{}", start_ms),
+                    start_ms: 1000,
+                    end_ms: duration_ms - 2000,
+                    text: text.to_string(),
+                    confidence: 0.9,
+                    speaker: None,
+                    language: None,
+                    words: vec![SegmentWord {
+                        text: text.split(' ').next().unwrap().to_string(),
+                        start_ms: 1000,
+                        end_ms: 1400,
+                        confidence: 0.9,
+                    }],
+                }],
+                raw_text: text.to_string(),
+                edited_text: None,
+                is_edited: false,
+                is_draft: false,
+                warnings: Vec::new(),
+                normalization_gain: None,
+                action_items: Vec::new(),
+                markers: Vec::new(),
+                pauses: Vec::new(),
+                capture: None,
+                confidence,
+                audio_path: None,
+                translations: Vec::new(),
+                notes: Vec::new(),
+                folder: None,
+                tags: Vec::new(),
+                title: None,
+                deleted_at: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_join_blocks_shifts_segments() {
+        let joined = join_blocks(
+            vec![
+                block(0, 40_000, "on commence la reunion", Some(0.8)),
+                block(40_000, 10_000, "premier point le budget", None),
+                block(50_000, 30_000, "Ensuite le planning.", Some(0.5)),
+            ],
+            &DecodingConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(joined.id, "t0");
+        assert_eq!(joined.duration_ms, 80_000);
+        // 3 s of silence between blocks ends a sentence
+        assert_eq!(
+            joined.raw_text,
+            "on commence la reunion. Premier point le budget. Ensuite le planning."
+        );
+        let starts: Vec<i64> = joined.segments.iter().map(|s| s.start_ms).collect();
+        assert_eq!(starts, [1000, 41_000, 51_000]);
+        assert_eq!(joined.segments[2].words[0].start_ms, 51_000);
+        assert!((joined.confidence.unwrap() - (0.8 * 40.0 + 0.5 * 30.0) / 70.0).abs() < 1e-9);
+
+        assert!(join_blocks(Vec::new(), &DecodingConfig::default()).is_none());
+    }
+}
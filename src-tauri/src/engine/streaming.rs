@@ -0,0 +1,229 @@
+//! Live transcription while recording
+//!
+//! The audio recorded after the last committed point is decoded again in
+//! rolling windows, each result sent as a partial caption. Once a pause is
+//! found in that audio (or the window reaches its limit), the part before the
+//! pause is committed: its text is sent as final and never decoded again.
+//! Backends decode every call from a fresh state, so what carries over from
+//! one window to the next is the committed offset, not the LSTM state.
+
+use crate::audio::vad::{edge_silence_ms, find_best_cut_point, find_silence_regions, VadConfig};
+use crate::engine::{filter_chunk_hallucinations, DecodingConfig, DynamicEngine, TranscriptionLanguage};
+use crate::error::Result;
+use crate::storage::StreamingSegment;
+use serde::{Deserialize, Serialize};
+
+const SAMPLES_PER_MS: usize = 16;
+
+/// Latency and window bounds of a live session
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamingConfig {
+    /// Delay between two decodes
+    #[serde(default = "default_step_ms")]
+    pub step_ms: u64,
+    /// Pending audio below this is not decoded yet
+    #[serde(default = "default_min_window_ms")]
+    pub min_window_ms: u64,
+    /// Pending audio is committed at its quietest point beyond this
+    /// (kept under the 15 s a single inference accepts)
+    #[serde(default = "default_max_window_ms")]
+    pub max_window_ms: u64,
+    /// Pause that ends a sentence and commits what precedes it
+    #[serde(default = "default_commit_silence_ms")]
+    pub commit_silence_ms: u64,
+}
+
+fn default_step_ms() -> u64 {
+    1000
+}
+
+fn default_min_window_ms() -> u64 {
+    1000
+}
+
+fn default_max_window_ms() -> u64 {
+    12_000
+}
+
+fn default_commit_silence_ms() -> u64 {
+    600
+}
+
+impl Default for StreamingConfig {
+    fn default() -> Self {
+        Self {
+            step_ms: default_step_ms(),
+            min_window_ms: default_min_window_ms(),
+            max_window_ms: default_max_window_ms(),
+            commit_silence_ms: default_commit_silence_ms(),
+        }
+    }
+}
+
+/// Part of the pending audio to decode next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window {
+    /// End of the window in the pending audio (16 kHz samples)
+    pub end: usize,
+    /// The window is committed once decoded
+    pub is_final: bool,
+}
+
+/// State kept between the windows of one recording
+pub struct StreamingSession {
+    config: StreamingConfig,
+    vad: VadConfig,
+    /// 16 kHz samples already committed
+    committed: usize,
+    /// Length of the pending audio at the last partial, to skip unchanged audio (pause)
+    last_partial_len: usize,
+}
+
+impl StreamingSession {
+    pub fn new(config: StreamingConfig) -> Self {
+        Self {
+            config,
+            vad: VadConfig::default(),
+            committed: 0,
+            last_partial_len: 0,
+        }
+    }
+
+    pub fn config(&self) -> &StreamingConfig {
+        &self.config
+    }
+
+    /// 16 kHz samples of the recording already committed
+    pub fn committed_samples(&self) -> usize {
+        self.committed
+    }
+
+    /// Choose the window to decode in `pending` (audio after the committed point)
+    ///
+    /// Final up to the middle of the last long enough pause, or of the
+    /// quietest point of the last third once the window is too long; partial
+    /// over everything otherwise. `None` when there is nothing new to decode.
+    pub fn next_window(&self, pending: &[f32]) -> Option<Window> {
+        let min_len = self.config.min_window_ms as usize * SAMPLES_PER_MS;
+        let max_len = self.config.max_window_ms as usize * SAMPLES_PER_MS;
+        let pause_len = self.config.commit_silence_ms as usize * SAMPLES_PER_MS;
+
+        if pending.len() < min_len {
+            return None;
+        }
+
+        let pause = find_silence_regions(pending, &self.vad)
+            .into_iter()
+            .rev()
+            .filter(|&(start, end)| end - start >= pause_len)
+            .map(|(start, end)| (start + end) / 2)
+            .find(|&middle| middle <= max_len);
+        if let Some(end) = pause {
+            return Some(Window { end, is_final: true });
+        }
+
+        if pending.len() >= max_len {
+            let (cut, _, _) = find_best_cut_point(pending, max_len * 2 / 3, max_len, &self.vad);
+            return Some(Window { end: cut, is_final: true });
+        }
+
+        if pending.len() == self.last_partial_len {
+            return None;
+        }
+        Some(Window {
+            end: pending.len(),
+            is_final: false,
+        })
+    }
+
+    /// Decode the next window of `pending`; a final one advances the committed point
+    ///
+    /// Final windows without speech are committed silently (`None`).
+    pub fn decode(
+        &mut self,
+        engine: &DynamicEngine,
+        pending: &[f32],
+        language: TranscriptionLanguage,
+        config: &DecodingConfig,
+    ) -> Result<Option<StreamingSegment>> {
+        let Some(window) = self.next_window(pending) else {
+            return Ok(None);
+        };
+
+        let audio = &pending[..window.end];
+        // Pauses are committed without running the model
+        let text = if window.is_final && edge_silence_ms(audio, &self.vad).is_none() {
+            String::new()
+        } else {
+            filter_chunk_hallucinations(&engine.transcribe_window(audio, language, config)?)
+        };
+
+        if window.is_final {
+            self.committed += window.end;
+            self.last_partial_len = 0;
+        } else {
+            self.last_partial_len = pending.len();
+        }
+
+        if text.is_empty() && window.is_final {
+            return Ok(None);
+        }
+        Ok(Some(StreamingSegment {
+            text,
+            is_final: window.is_final,
+            confidence: None,
+            // Filled by the live translator, off the decoding path
+            translated_text: None,
+            target_language: None,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(ms: usize) -> Vec<f32> {
+        (0..ms * SAMPLES_PER_MS)
+            .map(|i| 0.3 * (i as f32 * 0.05).sin())
+            .collect()
+    }
+
+    fn silence(ms: usize) -> Vec<f32> {
+        vec![0.0; ms * SAMPLES_PER_MS]
+    }
+
+    #[test]
+    fn test_short_audio_is_not_decoded() {
+        let session = StreamingSession::new(StreamingConfig::default());
+        assert_eq!(session.next_window(&tone(500)), None);
+    }
+
+    #[test]
+    fn test_partial_then_final_after_pause() {
+        let session = StreamingSession::new(StreamingConfig::default());
+
+        let speech = tone(2000);
+        assert_eq!(
+            session.next_window(&speech),
+            Some(Window { end: speech.len(), is_final: false })
+        );
+
+        let mut audio = speech;
+        audio.extend(silence(1000));
+        audio.extend(tone(500));
+        let window = session.next_window(&audio).unwrap();
+        assert!(window.is_final);
+        assert!(window.end > 2000 * SAMPLES_PER_MS && window.end < 3000 * SAMPLES_PER_MS);
+    }
+
+    #[test]
+    fn test_long_speech_is_cut_within_limit() {
+        let session = StreamingSession::new(StreamingConfig::default());
+        let audio = tone(14_000);
+        let window = session.next_window(&audio).unwrap();
+        assert!(window.is_final);
+        assert!(window.end <= 12_000 * SAMPLES_PER_MS);
+    }
+}
@@ -0,0 +1,129 @@
+//! Decode telemetry: detect when a model/backend collapses to blank
+//!
+//! The TDT decoder picks blank or a token at each joint step. A healthy model
+//! emits a few tokens per second of speech; a misbehaving one (wrong model
+//! files, broken backend) picks blank almost everywhere and returns empty text.
+
+use serde::{Deserialize, Serialize};
+
+/// Blank ratio above which a decode is considered collapsed
+const BLANK_COLLAPSE_RATIO: f32 = 0.98;
+
+/// Per-inference decode statistics
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodeTelemetry {
+    /// Joint network evaluations
+    pub steps: usize,
+    /// Steps where blank won (after blank penalty)
+    pub blank_steps: usize,
+    /// blank_steps / steps
+    pub blank_ratio: f32,
+    /// Average raw blank logit minus best non-blank logit (> 0 = blank dominates)
+    pub avg_blank_margin: f32,
+    /// Emitted tokens
+    pub tokens: usize,
+    /// Decoded audio duration
+    pub audio_seconds: f32,
+    /// Emitted tokens per second of audio
+    pub tokens_per_second: f32,
+    /// Times the max-symbols-per-frame guard forced time forward (output may be truncated)
+    pub max_symbols_guard_hits: usize,
+    #[serde(skip)]
+    margin_sum: f64,
+}
+
+impl DecodeTelemetry {
+    /// Record one joint step from its raw token logits (blank included)
+    pub fn record_step(&mut self, token_logits: &[f32], blank_id: usize, chose_blank: bool) {
+        self.steps += 1;
+        if chose_blank {
+            self.blank_steps += 1;
+        }
+
+        if let Some(&blank_logit) = token_logits.get(blank_id) {
+            let best_nonblank = token_logits
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != blank_id)
+                .map(|(_, &v)| v)
+                .fold(f32::NEG_INFINITY, f32::max);
+            if best_nonblank.is_finite() {
+                self.margin_sum += (blank_logit - best_nonblank) as f64;
+            }
+        }
+
+        self.update_derived();
+    }
+
+    /// Record the decode result (emitted tokens for `audio_samples` at 16kHz)
+    pub fn finish(&mut self, tokens: usize, audio_samples: usize) {
+        self.tokens += tokens;
+        self.audio_seconds += audio_samples as f32 / 16000.0;
+        self.update_derived();
+    }
+
+    /// Accumulate the telemetry of another chunk
+    pub fn merge(&mut self, other: &DecodeTelemetry) {
+        self.steps += other.steps;
+        self.blank_steps += other.blank_steps;
+        self.tokens += other.tokens;
+        self.audio_seconds += other.audio_seconds;
+        self.margin_sum += other.margin_sum;
+        self.max_symbols_guard_hits += other.max_symbols_guard_hits;
+        self.update_derived();
+    }
+
+    /// Model picked blank (nearly) everywhere: output is empty or truncated
+    pub fn is_blank_collapse(&self) -> bool {
+        self.steps > 0 && self.blank_ratio >= BLANK_COLLAPSE_RATIO && self.tokens == 0
+    }
+
+    fn update_derived(&mut self) {
+        if self.steps > 0 {
+            self.blank_ratio = self.blank_steps as f32 / self.steps as f32;
+            self.avg_blank_margin = (self.margin_sum / self.steps as f64) as f32;
+        }
+        if self.audio_seconds > 0.0 {
+            self.tokens_per_second = self.tokens as f32 / self.audio_seconds;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ratio_and_margin() {
+        let mut t = DecodeTelemetry::default();
+        // blank (id 2) wins by 2.0
+        t.record_step(&[1.0, 0.0, 3.0], 2, true);
+        // token 0 wins, blank behind by 1.0
+        t.record_step(&[4.0, 0.0, 3.0], 2, false);
+        t.finish(1, 16000);
+
+        assert_eq!(t.steps, 2);
+        assert!((t.blank_ratio - 0.5).abs() < 1e-6);
+        assert!((t.avg_blank_margin - 0.5).abs() < 1e-6);
+        assert!((t.tokens_per_second - 1.0).abs() < 1e-6);
+        assert!(!t.is_blank_collapse());
+    }
+
+    #[test]
+    fn test_blank_collapse_across_chunks() {
+        let mut a = DecodeTelemetry::default();
+        let mut b = DecodeTelemetry::default();
+        for _ in 0..50 {
+            a.record_step(&[0.0, 5.0], 1, true);
+            b.record_step(&[0.0, 5.0], 1, true);
+        }
+        a.finish(0, 32000);
+        b.finish(0, 32000);
+        a.merge(&b);
+
+        assert_eq!(a.steps, 100);
+        assert!((a.audio_seconds - 4.0).abs() < 1e-6);
+        assert!(a.is_blank_collapse());
+    }
+}
@@ -0,0 +1,272 @@
+//! Word and phrase timestamps from TDT token positions
+//!
+//! Each token is emitted at an encoder frame (80 ms: 10 ms mel hop, 8x
+//! subsampling) and predicts how many frames it covers. A word spans from its
+//! first piece (SentencePiece "▁" prefix) to the end of its last one; words
+//! are then grouped into phrases at sentence ends and long pauses. A word's
+//! confidence is that of its least probable piece.
+
+use serde::{Deserialize, Serialize};
+
+/// Duration of one encoder frame
+pub const ENCODER_FRAME_MS: i64 = 80;
+
+/// Pause between two words that starts a new phrase
+const PHRASE_GAP_MS: i64 = 800;
+
+/// Longest phrase, so subtitles and exports stay readable
+const MAX_PHRASE_WORDS: usize = 25;
+
+/// Token emitted by a TDT decoder
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimedToken {
+    pub id: u32,
+    /// Encoder frame the token was emitted at
+    pub frame: usize,
+    /// Frames covered according to the duration head (at least 1)
+    pub duration: usize,
+    /// Posterior probability of the token (see `decoder::token_posterior`)
+    pub confidence: f32,
+}
+
+/// Word with its position in the audio
+///
+/// Chunks reused from the cache or replaced by a marker have no token
+/// timings: they appear as a single entry spanning the chunk.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordTiming {
+    pub text: String,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    /// None without token scores (cached text, marker)
+    pub confidence: Option<f32>,
+}
+
+impl WordTiming {
+    /// Whole chunk as a single entry (cached text, non-speech marker)
+    pub fn spanning(text: &str, start_ms: i64, end_ms: i64) -> Self {
+        Self {
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+            confidence: None,
+        }
+    }
+}
+
+/// Phrase built from consecutive words
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedPhrase {
+    pub text: String,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub words: Vec<WordTiming>,
+}
+
+impl TimedPhrase {
+    /// Mean confidence of the scored words
+    pub fn confidence(&self) -> Option<f64> {
+        mean_confidence(&self.words)
+    }
+}
+
+/// Mean confidence of the words that have one
+pub fn mean_confidence(words: &[WordTiming]) -> Option<f64> {
+    let scores: Vec<f64> = words.iter().filter_map(|w| w.confidence).map(f64::from).collect();
+    (!scores.is_empty()).then(|| scores.iter().sum::<f64>() / scores.len() as f64)
+}
+
+/// Words of decoded tokens; `piece` gives the text of a token (" " for "▁")
+pub fn words_from_tokens(
+    tokens: &[TimedToken],
+    piece: impl Fn(u32) -> String,
+    offset_ms: i64,
+) -> Vec<WordTiming> {
+    let mut words: Vec<WordTiming> = Vec::new();
+    for token in tokens {
+        let text = piece(token.id);
+        if text.is_empty() {
+            continue;
+        }
+        let start_ms = offset_ms + token.frame as i64 * ENCODER_FRAME_MS;
+        let end_ms = start_ms + token.duration.max(1) as i64 * ENCODER_FRAME_MS;
+
+        match words.last_mut() {
+            Some(word) if !text.starts_with(' ') => {
+                word.text.push_str(&text);
+                word.end_ms = end_ms;
+                word.confidence = word.confidence.map(|c| c.min(token.confidence));
+            }
+            _ => words.push(WordTiming {
+                text: text.trim_start().to_string(),
+                start_ms,
+                end_ms,
+                confidence: Some(token.confidence),
+            }),
+        }
+    }
+    words.retain(|w| !w.text.is_empty());
+    words
+}
+
+/// Keep the last `count` words (those left after hallucination filtering)
+pub fn keep_last_words(words: &mut Vec<WordTiming>, count: usize) {
+    let drop = words.len().saturating_sub(count);
+    words.drain(..drop);
+}
+
+/// Append the words of the next chunk, skipping those already heard in the
+/// overlap with the previous chunk
+pub fn append_chunk_words(words: &mut Vec<WordTiming>, chunk: Vec<WordTiming>) {
+    let heard_until = words.last().map(|w| w.end_ms).unwrap_or(i64::MIN);
+    // Half a frame of tolerance: the same word may be placed slightly earlier
+    words.extend(
+        chunk
+            .into_iter()
+            .filter(|w| w.start_ms >= heard_until - ENCODER_FRAME_MS / 2),
+    );
+}
+
+/// Glue the first word of a chunk that starts mid-word (decoder state
+/// carried over from the previous chunk) to the last word already heard
+pub fn continue_last_word(words: &mut [WordTiming], chunk: &mut Vec<WordTiming>) {
+    let Some(word) = words.last_mut() else {
+        return;
+    };
+    if chunk.is_empty() {
+        return;
+    }
+    let rest = chunk.remove(0);
+    word.text.push_str(&rest.text);
+    word.end_ms = rest.end_ms;
+    word.confidence = match (word.confidence, rest.confidence) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    };
+}
+
+/// Group words into phrases at sentence ends, long pauses and `MAX_PHRASE_WORDS`
+pub fn group_phrases(words: &[WordTiming]) -> Vec<TimedPhrase> {
+    let mut phrases: Vec<TimedPhrase> = Vec::new();
+    let mut count = 0;
+    let mut previous: Option<&WordTiming> = None;
+
+    for word in words {
+        let new_phrase = match previous {
+            None => true,
+            Some(prev) => {
+                prev.text.ends_with(['.', '?', '!'])
+                    || word.start_ms - prev.end_ms > PHRASE_GAP_MS
+                    || count >= MAX_PHRASE_WORDS
+            }
+        };
+
+        if new_phrase {
+            phrases.push(TimedPhrase {
+                text: word.text.clone(),
+                start_ms: word.start_ms,
+                end_ms: word.end_ms,
+                words: vec![word.clone()],
+            });
+            count = 1;
+        } else if let Some(phrase) = phrases.last_mut() {
+            phrase.text.push(' ');
+            phrase.text.push_str(&word.text);
+            phrase.end_ms = word.end_ms;
+            phrase.words.push(word.clone());
+            count += 1;
+        }
+        previous = Some(word);
+    }
+
+    phrases
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(id: u32, frame: usize, duration: usize) -> TimedToken {
+        TimedToken {
+            id,
+            frame,
+            duration,
+            confidence: 0.9,
+        }
+    }
+
+    fn word(text: &str, start_ms: i64, end_ms: i64) -> WordTiming {
+        WordTiming {
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+            confidence: Some(0.9),
+        }
+    }
+
+    #[test]
+    fn test_words_from_pieces() {
+        let pieces = [" Bon", "jour", " à", " tous", "."];
+        let tokens = [token(0, 2, 1), token(1, 3, 2), token(2, 10, 1), token(3, 11, 2), token(4, 13, 1)];
+        let words = words_from_tokens(&tokens, |id| pieces[id as usize].to_string(), 1000);
+
+        assert_eq!(
+            words,
+            vec![
+                word("Bonjour", 1160, 1400),
+                word("à", 1800, 1880),
+                word("tous.", 1880, 2120),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_word_confidence_is_its_weakest_piece() {
+        let pieces = [" Bon", "jour", " Paris"];
+        let mut tokens = [token(0, 0, 1), token(1, 1, 1), token(2, 2, 1)];
+        tokens[1].confidence = 0.4;
+        let words = words_from_tokens(&tokens, |id| pieces[id as usize].to_string(), 0);
+
+        assert_eq!(words[0].confidence, Some(0.4));
+        assert_eq!(words[1].confidence, Some(0.9));
+        let phrases = group_phrases(&words);
+        assert!((phrases[0].confidence().unwrap() - 0.65).abs() < 1e-6);
+        assert_eq!(mean_confidence(&[WordTiming::spanning("[musique]", 0, 1000)]), None);
+    }
+
+    #[test]
+    fn test_overlap_words_are_skipped() {
+        let mut words = vec![word("le", 0, 200), word("budget", 200, 800)];
+        append_chunk_words(&mut words, vec![word("budget", 240, 780), word("annuel", 790, 1500)]);
+        assert_eq!(words.len(), 3);
+        assert_eq!(words[2].text, "annuel");
+    }
+
+    #[test]
+    fn test_word_continued_across_chunks() {
+        let mut words = vec![word("le", 0, 200), word("bud", 200, 480)];
+        let mut chunk = vec![word("get", 480, 800), word("annuel", 800, 1500)];
+        continue_last_word(&mut words, &mut chunk);
+        append_chunk_words(&mut words, chunk);
+
+        assert_eq!(words.len(), 3);
+        assert_eq!(words[1], word("budget", 200, 800));
+        assert_eq!(words[2].text, "annuel");
+    }
+
+    #[test]
+    fn test_phrases_split_at_sentence_end_and_pause() {
+        let words = vec![
+            word("Bonjour.", 0, 500),
+            word("On", 600, 700),
+            word("commence", 700, 1200),
+            word("maintenant", 2500, 3000),
+        ];
+        let phrases = group_phrases(&words);
+
+        assert_eq!(phrases.len(), 3);
+        assert_eq!(phrases[1].text, "On commence");
+        assert_eq!((phrases[1].start_ms, phrases[1].end_ms), (600, 1200));
+        assert_eq!(phrases[2].start_ms, 2500);
+    }
+}
@@ -1,3 +1,5 @@
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use serde::Deserialize;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -11,12 +13,26 @@ pub enum AppError {
     #[error("Transcription error: {0}")]
     Transcription(String),
 
+    /// The inference process died (isolated OpenVINO worker); it is restarted on the next job
+    #[error("Engine crashed: {0}")]
+    EngineCrashed(String),
+
     #[error("Export error: {0}")]
     Export(String),
 
+    /// Export failed while writing; the destination file was left untouched
+    #[error("Export error: {message} (temporary file {cleanup})")]
+    ExportWrite {
+        message: String,
+        cleanup: PartialCleanup,
+    },
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[error("Network error: {0}")]
+    Network(String),
+
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
@@ -28,6 +44,119 @@ pub enum AppError {
 
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+
+    /// Saved by someone else since it was loaded (optimistic concurrency)
+    #[error("Conflict: {id} was modified at {current_updated_at}")]
+    Conflict { id: String, current_updated_at: String },
+}
+
+/// What became of the temporary file of a failed export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartialCleanup {
+    /// The failure happened before the temporary file was created
+    NotCreated,
+    Removed,
+    /// Could not be deleted (hidden `.tmp` file next to the destination)
+    LeftBehind,
+}
+
+impl std::fmt::Display for PartialCleanup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PartialCleanup::NotCreated => "not created",
+            PartialCleanup::Removed => "removed",
+            PartialCleanup::LeftBehind => "left behind",
+        })
+    }
+}
+
+/// Stable identifier of an error kind, for events and stored job errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    Database,
+    Audio,
+    Transcription,
+    EngineCrashed,
+    Export,
+    Io,
+    Network,
+    Serialization,
+    NotFound,
+    InvalidState,
+    InvalidInput,
+    Conflict,
+}
+
+impl ErrorCode {
+    /// Value stored in the database (same as the serialized form)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::Database => "database",
+            ErrorCode::Audio => "audio",
+            ErrorCode::Transcription => "transcription",
+            ErrorCode::EngineCrashed => "engine_crashed",
+            ErrorCode::Export => "export",
+            ErrorCode::Io => "io",
+            ErrorCode::Network => "network",
+            ErrorCode::Serialization => "serialization",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::InvalidState => "invalid_state",
+            ErrorCode::InvalidInput => "invalid_input",
+            ErrorCode::Conflict => "conflict",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "database" => Some(ErrorCode::Database),
+            "audio" => Some(ErrorCode::Audio),
+            "transcription" => Some(ErrorCode::Transcription),
+            "engine_crashed" => Some(ErrorCode::EngineCrashed),
+            "export" => Some(ErrorCode::Export),
+            "io" => Some(ErrorCode::Io),
+            "network" => Some(ErrorCode::Network),
+            "serialization" => Some(ErrorCode::Serialization),
+            "not_found" => Some(ErrorCode::NotFound),
+            "invalid_state" => Some(ErrorCode::InvalidState),
+            "invalid_input" => Some(ErrorCode::InvalidInput),
+            "conflict" => Some(ErrorCode::Conflict),
+            _ => None,
+        }
+    }
+}
+
+impl ToSql for ErrorCode {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for ErrorCode {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let s = value.as_str()?;
+        ErrorCode::parse(s).ok_or_else(|| FromSqlError::Other(format!("Unknown error code: {}", s).into()))
+    }
+}
+
+impl AppError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::Database(_) => ErrorCode::Database,
+            AppError::Audio(_) => ErrorCode::Audio,
+            AppError::Transcription(_) => ErrorCode::Transcription,
+            AppError::EngineCrashed(_) => ErrorCode::EngineCrashed,
+            AppError::Export(_) | AppError::ExportWrite { .. } => ErrorCode::Export,
+            AppError::Io(_) => ErrorCode::Io,
+            AppError::Network(_) => ErrorCode::Network,
+            AppError::Serialization(_) => ErrorCode::Serialization,
+            AppError::NotFound(_) => ErrorCode::NotFound,
+            AppError::InvalidState(_) => ErrorCode::InvalidState,
+            AppError::InvalidInput(_) => ErrorCode::InvalidInput,
+            AppError::Conflict { .. } => ErrorCode::Conflict,
+        }
+    }
 }
 
 impl serde::Serialize for AppError {
@@ -1,11 +1,15 @@
 use crate::error::{AppError, Result};
+use crate::export::{action_item_line, notes_title, write_atomic};
+use crate::provenance::Provenance;
 use crate::storage::Transcription;
 use docx_rs::*;
-use std::fs::File;
-use std::io::BufWriter;
+use std::io::{BufWriter, Cursor};
 use std::path::Path;
 
-pub fn export_to_docx(transcription: &Transcription, path: &Path) -> Result<()> {
+/// Numbering definition used for bulleted lists
+const BULLET_NUMBERING_ID: usize = 1;
+
+pub fn export_to_docx(transcription: &Transcription, path: &Path, provenance: Option<&Provenance>) -> Result<()> {
     let text = transcription
         .edited_text
         .as_ref()
@@ -21,6 +25,9 @@ pub fn export_to_docx(transcription: &Transcription, path: &Path) -> Result<()>
 
     // Metadata
     docx = docx.add_paragraph(Paragraph::new());
+    if let Some(ref title) = transcription.title {
+        docx = docx.add_paragraph(Paragraph::new().add_run(Run::new().add_text(format!("Titre: {}", title))));
+    }
     docx = docx.add_paragraph(
         Paragraph::new().add_run(Run::new().add_text(format!("Date: {}", transcription.created_at))),
     );
@@ -47,6 +54,87 @@ pub fn export_to_docx(transcription: &Transcription, path: &Path) -> Result<()>
         }
     }
 
+    // Translations, in parallel with the text
+    for translation in &transcription.translations {
+        docx = docx.add_paragraph(Paragraph::new());
+        docx = docx.add_paragraph(Paragraph::new().add_run(
+            Run::new()
+                .add_text(format!("Traduction ({}):", translation.language.to_uppercase()))
+                .bold(),
+        ));
+        for paragraph in translation.text.split('\n') {
+            if !paragraph.is_empty() {
+                docx = docx.add_paragraph(Paragraph::new().add_run(Run::new().add_text(paragraph)));
+            }
+        }
+    }
+
+    // Notes written by the LLM
+    for notes in &transcription.notes {
+        docx = docx.add_paragraph(Paragraph::new());
+        docx = docx.add_paragraph(
+            Paragraph::new().add_run(Run::new().add_text(format!("{}:", notes_title(notes.style))).bold()),
+        );
+        for paragraph in notes.summary.split('\n') {
+            if !paragraph.is_empty() {
+                docx = docx.add_paragraph(Paragraph::new().add_run(Run::new().add_text(paragraph)));
+            }
+        }
+        for (title, items) in [("Points cles", &notes.key_points), ("Actions", &notes.action_items)] {
+            if !items.is_empty() {
+                docx = docx.add_paragraph(
+                    Paragraph::new().add_run(Run::new().add_text(format!("{}:", title)).bold()),
+                );
+                for item in items {
+                    docx = docx.add_paragraph(Paragraph::new().add_run(Run::new().add_text(format!("- {}", item))));
+                }
+            }
+        }
+    }
+
+    // Markers flagged while recording
+    if !transcription.markers.is_empty() {
+        docx = docx.add_paragraph(Paragraph::new());
+        docx = docx.add_paragraph(
+            Paragraph::new().add_run(Run::new().add_text("Marqueurs:").bold()),
+        );
+
+        for marker in &transcription.markers {
+            docx = docx.add_paragraph(
+                Paragraph::new()
+                    .add_run(Run::new().add_text(format!("[{}] ", format_timestamp(marker.at_ms))).bold())
+                    .add_run(Run::new().add_text(&marker.label)),
+            );
+        }
+    }
+
+    // Actions and decisions, as a bulleted list
+    if !transcription.action_items.is_empty() {
+        docx = docx.add_paragraph(Paragraph::new());
+        docx = docx.add_paragraph(
+            Paragraph::new().add_run(Run::new().add_text("Actions et decisions:").bold()),
+        );
+
+        docx = docx.add_abstract_numbering(
+            AbstractNumbering::new(BULLET_NUMBERING_ID).add_level(Level::new(
+                0,
+                Start::new(1),
+                NumberFormat::new("bullet"),
+                LevelText::new("•"),
+                LevelJc::new("left"),
+            )),
+        );
+        docx = docx.add_numbering(Numbering::new(BULLET_NUMBERING_ID, BULLET_NUMBERING_ID));
+
+        for item in &transcription.action_items {
+            docx = docx.add_paragraph(
+                Paragraph::new()
+                    .add_run(Run::new().add_text(action_item_line(item)))
+                    .numbering(NumberingId::new(BULLET_NUMBERING_ID), IndentLevel::new(0)),
+            );
+        }
+    }
+
     // Segments
     if !transcription.segments.is_empty() {
         docx = docx.add_paragraph(Paragraph::new());
@@ -67,14 +155,28 @@ pub fn export_to_docx(transcription: &Transcription, path: &Path) -> Result<()>
         }
     }
 
-    // Write to file
-    let file = File::create(path)?;
-    let writer = BufWriter::new(file);
-    docx.build()
-        .pack(writer)
-        .map_err(|e| AppError::Export(format!("Failed to create docx: {:?}", e)))?;
+    // Provenance, last paragraph in small print, sealed with the text of the
+    // runs before it as they read back from the packed document
+    if let Some(provenance) = provenance {
+        let mut packed = Cursor::new(Vec::new());
+        docx.clone()
+            .build()
+            .pack(&mut packed)
+            .map_err(|e| AppError::Export(format!("Failed to create docx: {:?}", e)))?;
+        let sealed = provenance.sealing(&crate::provenance::docx_text(packed)?);
+        docx = docx.add_paragraph(Paragraph::new());
+        docx = docx.add_paragraph(
+            Paragraph::new().add_run(Run::new().add_text(sealed.line()?).size(14)),
+        );
+    }
 
-    Ok(())
+    // Write to file
+    write_atomic(path, |file| {
+        docx.build()
+            .pack(BufWriter::new(file))
+            .map_err(|e| AppError::Export(format!("Failed to create docx: {:?}", e)))?;
+        Ok(())
+    })
 }
 
 fn format_duration(ms: i64) -> String {
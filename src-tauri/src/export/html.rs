@@ -0,0 +1,218 @@
+//! Static HTML archive of the history
+//!
+//! Self-contained site readable in any browser without the app: `index.html`
+//! lists every transcription with a search box (plain embedded JS, no
+//! network), and `transcriptions/<id>.html` shows the text, markers, actions
+//! and timestamped segments of each one.
+
+use crate::error::Result;
+use crate::export::{action_item_line, notes_title, write_atomic};
+use crate::storage::Transcription;
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+const PAGES_DIR: &str = "transcriptions";
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:50rem;margin:2rem auto;padding:0 1rem;color:#222;line-height:1.5}\
+a{color:#2563eb;text-decoration:none}a:hover{text-decoration:underline}\
+.meta{color:#666;font-size:.9rem}.item{border-bottom:1px solid #ddd;padding:.75rem 0}\
+.ts{font-family:monospace;color:#666;margin-right:.5rem}\
+input{width:100%;padding:.5rem;font-size:1rem;box-sizing:border-box}";
+
+/// Search entry embedded in the index
+#[derive(Serialize)]
+struct IndexEntry<'a> {
+    id: &'a str,
+    title: String,
+    date: &'a str,
+    duration: String,
+    preview: String,
+    /// Lowercased full text, matched by the search box
+    search: String,
+}
+
+/// Write the archive into `dir` (created if needed); returns the number of pages
+pub fn export_archive_html(transcriptions: &[Transcription], dir: &Path) -> Result<usize> {
+    fs::create_dir_all(dir.join(PAGES_DIR))?;
+
+    let mut entries = Vec::with_capacity(transcriptions.len());
+    for t in transcriptions {
+        let page = dir.join(PAGES_DIR).join(format!("{}.html", t.id));
+        write_atomic(&page, |file| Ok(file.write_all(transcription_page(t).as_bytes())?))?;
+
+        let text = text_of(t);
+        entries.push(IndexEntry {
+            id: &t.id,
+            title: title_of(t),
+            date: &t.created_at,
+            duration: format_duration(t.duration_ms),
+            preview: text.chars().take(200).collect(),
+            search: format!("{} {}", title_of(t), text).to_lowercase(),
+        });
+    }
+
+    let index = index_page(&entries)?;
+    write_atomic(&dir.join("index.html"), |file| Ok(file.write_all(index.as_bytes())?))?;
+
+    Ok(transcriptions.len())
+}
+
+fn index_page(entries: &[IndexEntry]) -> Result<String> {
+    // "</" would close the script element
+    let data = serde_json::to_string(entries)?.replace("</", "<\\/");
+
+    Ok(format!(
+        r#"<!DOCTYPE html>
+<html lang="fr">
+<head>
+<meta charset="utf-8">
+<title>Archive WakaScribe</title>
+<style>{style}</style>
+</head>
+<body>
+<h1>Archive WakaScribe</h1>
+<p class="meta">{count} transcription(s)</p>
+<input id="q" type="search" placeholder="Rechercher..." autofocus>
+<div id="list"></div>
+<script>
+const entries = {data};
+const list = document.getElementById("list");
+function esc(s) {{
+  return s.replace(/[&<>"]/g, c => ({{"&": "&amp;", "<": "&lt;", ">": "&gt;", '"': "&quot;"}})[c]);
+}}
+function render(query) {{
+  const words = query.toLowerCase().split(/\s+/).filter(Boolean);
+  list.innerHTML = entries
+    .filter(e => words.every(w => e.search.includes(w)))
+    .map(e => `<div class="item"><a href="{pages}/${{e.id}}.html">${{esc(e.title)}}</a>
+      <div class="meta">${{esc(e.date)}} - ${{esc(e.duration)}}</div><div>${{esc(e.preview)}}</div></div>`)
+    .join("");
+}}
+document.getElementById("q").addEventListener("input", ev => render(ev.target.value));
+render("");
+</script>
+</body>
+</html>
+"#,
+        style = STYLE,
+        count = entries.len(),
+        data = data,
+        pages = PAGES_DIR,
+    ))
+}
+
+fn transcription_page(t: &Transcription) -> String {
+    let mut body = String::new();
+    for paragraph in text_of(t).split("\n\n").filter(|p| !p.trim().is_empty()) {
+        body.push_str(&format!("<p>{}</p>\n", escape(paragraph).replace('\n', "<br>")));
+    }
+
+    for translation in &t.translations {
+        body.push_str(&format!("<h2>Traduction ({})</h2>\n", escape(&translation.language.to_uppercase())));
+        for paragraph in translation.text.split("\n\n").filter(|p| !p.trim().is_empty()) {
+            body.push_str(&format!("<p>{}</p>\n", escape(paragraph).replace('\n', "<br>")));
+        }
+    }
+
+    for notes in &t.notes {
+        body.push_str(&format!("<h2>{}</h2>\n", notes_title(notes.style)));
+        body.push_str(&format!("<p>{}</p>\n", escape(notes.summary.trim()).replace('\n', "<br>")));
+        for (title, items) in [("Points cles", &notes.key_points), ("Actions", &notes.action_items)] {
+            if !items.is_empty() {
+                body.push_str(&format!("<h3>{}</h3>\n<ul>\n", title));
+                for item in items {
+                    body.push_str(&format!("<li>{}</li>\n", escape(item)));
+                }
+                body.push_str("</ul>\n");
+            }
+        }
+    }
+
+    if !t.markers.is_empty() {
+        body.push_str("<h2>Marqueurs</h2>\n<ul>\n");
+        for marker in &t.markers {
+            body.push_str(&format!(
+                "<li><span class=\"ts\">{}</span>{}</li>\n",
+                format_timestamp(marker.at_ms),
+                escape(&marker.label)
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    if !t.action_items.is_empty() {
+        body.push_str("<h2>Actions et decisions</h2>\n<ul>\n");
+        for item in &t.action_items {
+            body.push_str(&format!("<li>{}</li>\n", escape(&action_item_line(item))));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    if !t.segments.is_empty() {
+        body.push_str("<h2>Segments</h2>\n");
+        for segment in &t.segments {
+            body.push_str(&format!(
+                "<div><span class=\"ts\">[{} - {}]</span>{}</div>\n",
+                format_timestamp(segment.start_ms),
+                format_timestamp(segment.end_ms),
+                escape(&segment.text)
+            ));
+        }
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="fr">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>{style}</style>
+</head>
+<body>
+<p><a href="../index.html">&larr; Archive</a></p>
+<h1>{title}</h1>
+<p class="meta">{date} - Duree: {duration}</p>
+{body}</body>
+</html>
+"#,
+        title = escape(&title_of(t)),
+        style = STYLE,
+        date = escape(&t.created_at),
+        duration = format_duration(t.duration_ms),
+        body = body,
+    )
+}
+
+fn text_of(t: &Transcription) -> &str {
+    t.edited_text.as_deref().unwrap_or(&t.raw_text)
+}
+
+fn title_of(t: &Transcription) -> String {
+    t.title
+        .clone()
+        .or_else(|| t.source_name.clone())
+        .unwrap_or_else(|| format!("Transcription du {}", t.created_at.get(..10).unwrap_or(&t.created_at)))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn format_duration(ms: i64) -> String {
+    let total_seconds = ms / 1000;
+    let minutes = total_seconds / 60;
+    let seconds = total_seconds % 60;
+    format!("{}:{:02}", minutes, seconds)
+}
+
+fn format_timestamp(ms: i64) -> String {
+    let total_seconds = ms / 1000;
+    let minutes = total_seconds / 60;
+    let seconds = total_seconds % 60;
+    format!("{:02}:{:02}", minutes, seconds)
+}
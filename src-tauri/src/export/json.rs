@@ -0,0 +1,13 @@
+//! JSON export of the full transcription (segments, words and confidences),
+//! in the app's own schema, for scripts
+//!
+//! The Whisper and word-list schemas other tools read are in `transcript_json`.
+
+use crate::error::Result;
+use crate::export::write_atomic;
+use crate::storage::Transcription;
+use std::path::Path;
+
+pub fn export_to_json(transcription: &Transcription, path: &Path) -> Result<()> {
+    write_atomic(path, |file| Ok(serde_json::to_writer_pretty(file, transcription)?))
+}
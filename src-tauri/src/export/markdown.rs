@@ -0,0 +1,163 @@
+//! Markdown export, for note-taking apps
+//!
+//! Title, a metadata table, the text, then markers, actions and one
+//! blockquote per timestamped segment.
+
+use crate::error::Result;
+use crate::export::{action_item_line, notes_title, write_atomic};
+use crate::provenance::Provenance;
+use crate::storage::Transcription;
+use std::io::Write;
+use std::path::Path;
+
+pub fn export_to_markdown(transcription: &Transcription, path: &Path, provenance: Option<&Provenance>) -> Result<()> {
+    let markdown = render(transcription, provenance)?;
+    write_atomic(path, |file| Ok(file.write_all(markdown.as_bytes())?))
+}
+
+fn render(t: &Transcription, provenance: Option<&Provenance>) -> Result<String> {
+    let mut out = String::new();
+    let heading = t.title.as_deref().or(t.source_name.as_deref()).unwrap_or("Dictee");
+    out.push_str(&format!("# {}\n\n", heading));
+
+    out.push_str("| | |\n|---|---|\n");
+    let mut row = |key: &str, value: &str| out.push_str(&format!("| {} | {} |\n", key, table_cell(value)));
+    row("Date", &t.created_at);
+    if let (Some(_), Some(name)) = (&t.title, &t.source_name) {
+        row("Source", name);
+    }
+    if let Some(ref path) = t.source_path {
+        row("Fichier", path);
+    }
+    row("Duree", &format_duration(t.duration_ms));
+    row("Langue", &t.language);
+    if let Some(confidence) = t.confidence {
+        row("Confiance", &format!("{:.0}%", confidence * 100.0));
+    }
+    if t.is_edited {
+        row("Modifiee", &t.updated_at);
+    }
+
+    out.push_str("\n## Texte\n\n");
+    out.push_str(t.edited_text.as_deref().unwrap_or(&t.raw_text).trim());
+    out.push('\n');
+
+    for translation in &t.translations {
+        out.push_str(&format!("\n## Traduction ({})\n\n", translation.language.to_uppercase()));
+        out.push_str(translation.text.trim());
+        out.push('\n');
+    }
+
+    for notes in &t.notes {
+        out.push_str(&format!("\n## {}\n\n", notes_title(notes.style)));
+        out.push_str(notes.summary.trim());
+        out.push('\n');
+        for (title, items) in [("Points cles", &notes.key_points), ("Actions", &notes.action_items)] {
+            if !items.is_empty() {
+                out.push_str(&format!("\n### {}\n\n", title));
+                for item in items {
+                    out.push_str(&format!("- {}\n", item));
+                }
+            }
+        }
+    }
+
+    if !t.markers.is_empty() {
+        out.push_str("\n## Marqueurs\n\n");
+        for marker in &t.markers {
+            out.push_str(&format!("- **[{}]** {}\n", format_timestamp(marker.at_ms), marker.label));
+        }
+    }
+
+    if !t.action_items.is_empty() {
+        out.push_str("\n## Actions et decisions\n\n");
+        for item in &t.action_items {
+            out.push_str(&format!("- {}\n", action_item_line(item)));
+        }
+    }
+
+    if !t.segments.is_empty() {
+        out.push_str("\n## Segments\n");
+        for segment in &t.segments {
+            let speaker = segment.speaker.as_deref().map(|s| format!(" {} :", s)).unwrap_or_default();
+            out.push_str(&format!(
+                "\n> **[{}]**{} {}\n",
+                format_timestamp(segment.start_ms),
+                speaker,
+                segment.text.trim().replace('\n', "\n> ")
+            ));
+        }
+    }
+
+    if let Some(provenance) = provenance {
+        out.push_str("\n---\n\n");
+        // Sealed with the text it follows
+        let sealed = provenance.sealing(&out);
+        out.push_str(&format!("{}\n", sealed.line()?));
+    }
+    Ok(out)
+}
+
+/// Cell text that keeps the table on one row
+fn table_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+fn format_duration(ms: i64) -> String {
+    let total_seconds = ms / 1000;
+    let minutes = total_seconds / 60;
+    let seconds = total_seconds % 60;
+    format!("{}:{:02}", minutes, seconds)
+}
+
+fn format_timestamp(ms: i64) -> String {
+    let total_seconds = ms / 1000;
+    let minutes = total_seconds / 60;
+    let seconds = total_seconds % 60;
+    format!("{:02}:{:02}", minutes, seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{SummaryStyle, TranscriptionNotes, Translation};
+
+    #[test]
+    fn test_markdown_layout() {
+        let mut t: Transcription = serde_json::from_str(
+            r#"{
+                "id": "a", "createdAt": "2026-03-01T10:00:00+00:00", "updatedAt": "2026-03-01T10:00:00+00:00",
+                "sourceType": "meeting", "sourceName": "Point | equipe", "durationMs": 65500, "language": "fr",
+                "rawText": "Bonjour. Salut", "isEdited": false,
+                "segments": [
+                    {"id": "s0", "startMs": 0, "endMs": 600, "text": "Bonjour.", "confidence": 0.9, "speaker": "me"},
+                    {"id": "s1", "startMs": 65000, "endMs": 65500, "text": "Salut", "confidence": 0.8, "speaker": "them"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        t.confidence = Some(0.85);
+        t.translations = vec![Translation {
+            language: "en".to_string(),
+            text: "Hello. Hi".to_string(),
+            created_at: "2026-03-02T10:00:00+00:00".to_string(),
+        }];
+        t.notes = vec![TranscriptionNotes {
+            style: SummaryStyle::Minutes,
+            summary: "Salutations.".to_string(),
+            key_points: Vec::new(),
+            action_items: vec!["Rappeler".to_string()],
+            model: None,
+            created_at: "2026-03-02T10:00:00+00:00".to_string(),
+        }];
+
+        let md = render(&t, None).unwrap();
+        assert!(md.starts_with("# Point | equipe\n\n| | |\n|---|---|\n| Date |"));
+        assert!(md.contains("| Duree | 1:05 |\n| Langue | fr |\n| Confiance | 85% |\n"));
+        assert!(md.contains("\n## Texte\n\nBonjour. Salut\n\n## Traduction (EN)\n\nHello. Hi\n"));
+        assert!(md.contains("\n## Compte rendu\n\nSalutations.\n\n### Actions\n\n- Rappeler\n"));
+        assert!(md.contains("\n> **[00:00]** me : Bonjour.\n"));
+        assert!(md.ends_with("\n> **[01:05]** them : Salut\n"));
+        assert_eq!(table_cell("a | b\nc"), "a \\| b c");
+    }
+}
@@ -1,5 +1,93 @@
 pub mod docx;
+pub mod html;
+pub mod json;
+pub mod markdown;
+pub mod subtitles;
+pub mod transcript_json;
 pub mod txt;
 
 pub use self::docx::export_to_docx;
+pub use html::export_archive_html;
+pub use json::export_to_json;
+pub use markdown::export_to_markdown;
+pub use subtitles::{export_to_srt, export_to_vtt, SubtitleOptions};
+pub use transcript_json::{export_transcript_json, import_transcript_json, TranscriptFormat};
 pub use txt::export_to_txt;
+
+use crate::error::{AppError, PartialCleanup, Result};
+use crate::storage::{ActionItem, ActionItemKind, SummaryStyle};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Write `path` through a temporary file in the same directory, renamed on success
+///
+/// A crash or an error mid-write never leaves a truncated file at `path`:
+/// the previous file, if any, stays intact until the rename.
+pub(crate) fn write_atomic<F>(path: &Path, write: F) -> Result<()>
+where
+    F: FnOnce(&mut File) -> Result<()>,
+{
+    let temp_path = temp_path_for(path);
+    let mut file = File::create(&temp_path).map_err(|e| AppError::ExportWrite {
+        message: format!("cannot create {:?}: {}", temp_path, e),
+        cleanup: PartialCleanup::NotCreated,
+    })?;
+
+    let written = write(&mut file).and_then(|_| Ok(file.sync_all()?));
+    // Closed before the rename or the removal (required on Windows)
+    drop(file);
+
+    written.and_then(|_| Ok(fs::rename(&temp_path, path)?)).map_err(|e| {
+        let cleanup = match fs::remove_file(&temp_path) {
+            Ok(()) => PartialCleanup::Removed,
+            Err(remove_err) => {
+                warn!("Could not remove partial export {:?}: {}", temp_path, remove_err);
+                PartialCleanup::LeftBehind
+            }
+        };
+        AppError::ExportWrite {
+            message: e.to_string(),
+            cleanup,
+        }
+    })
+}
+
+/// Hidden sibling of `path`, so the rename stays on the same filesystem
+fn temp_path_for(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "export".to_string());
+    path.with_file_name(format!(".{}.tmp", name))
+}
+
+/// One bullet of the "Actions et decisions" section
+fn action_item_line(item: &ActionItem) -> String {
+    let label = match item.kind {
+        ActionItemKind::Action => "Action",
+        ActionItemKind::Decision => "Decision",
+    };
+    let mut details = Vec::new();
+    if let Some(ref owner) = item.owner {
+        details.push(format!("responsable: {}", owner));
+    }
+    if let Some(ref due) = item.due {
+        details.push(format!("echeance: {}", due));
+    }
+
+    if details.is_empty() {
+        format!("{}: {}", label, item.text)
+    } else {
+        format!("{}: {} ({})", label, item.text, details.join(", "))
+    }
+}
+
+/// Heading of the LLM notes in `style`
+fn notes_title(style: SummaryStyle) -> &'static str {
+    match style {
+        SummaryStyle::Brief => "Resume",
+        SummaryStyle::Detailed => "Resume detaille",
+        SummaryStyle::Minutes => "Compte rendu",
+    }
+}
@@ -0,0 +1,266 @@
+//! SRT and WebVTT subtitles from the timestamped segments
+//!
+//! Segments longer than a cue allows (duration or two lines of text) are
+//! split between words; the time of a split is interpolated from its
+//! character position in the segment, since segments carry no word timings.
+//! Cues get minimal punctuation unless the options ask for the full one.
+
+use crate::error::Result;
+use crate::export::write_atomic;
+use crate::postprocess::punctuation::{punctuate, PunctuationMode};
+use crate::storage::{Segment, Transcription};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+
+/// Lines per cue, the usual limit for readability
+const MAX_LINES: usize = 2;
+
+/// Layout limits and punctuation of the cues
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubtitleOptions {
+    /// Characters per line
+    #[serde(default = "default_max_line_chars")]
+    pub max_line_chars: usize,
+    /// Longest time a cue stays on screen
+    #[serde(default = "default_max_cue_ms")]
+    pub max_cue_ms: i64,
+    #[serde(default = "default_punctuation")]
+    pub punctuation: PunctuationMode,
+}
+
+fn default_max_line_chars() -> usize {
+    42
+}
+
+fn default_max_cue_ms() -> i64 {
+    6000
+}
+
+fn default_punctuation() -> PunctuationMode {
+    PunctuationMode::Minimal
+}
+
+impl Default for SubtitleOptions {
+    fn default() -> Self {
+        Self {
+            max_line_chars: default_max_line_chars(),
+            max_cue_ms: default_max_cue_ms(),
+            punctuation: default_punctuation(),
+        }
+    }
+}
+
+/// Subtitle shown from `start_ms` to `end_ms`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cue {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub lines: Vec<String>,
+}
+
+pub fn export_to_srt(transcription: &Transcription, path: &Path, options: &SubtitleOptions) -> Result<()> {
+    let cues = cues_of(transcription, options);
+    write_atomic(path, |file| {
+        for (index, cue) in cues.iter().enumerate() {
+            writeln!(file, "{}", index + 1)?;
+            writeln!(file, "{} --> {}", timestamp(cue.start_ms, ','), timestamp(cue.end_ms, ','))?;
+            writeln!(file, "{}", cue.lines.join("\n"))?;
+            writeln!(file)?;
+        }
+        Ok(())
+    })
+}
+
+pub fn export_to_vtt(transcription: &Transcription, path: &Path, options: &SubtitleOptions) -> Result<()> {
+    let cues = cues_of(transcription, options);
+    write_atomic(path, |file| {
+        writeln!(file, "WEBVTT")?;
+        writeln!(file)?;
+        for cue in &cues {
+            writeln!(file, "{} --> {}", timestamp(cue.start_ms, '.'), timestamp(cue.end_ms, '.'))?;
+            writeln!(file, "{}", cue.lines.join("\n"))?;
+            writeln!(file)?;
+        }
+        Ok(())
+    })
+}
+
+/// Cues of a transcription; without segments the whole text spans the recording
+fn cues_of(transcription: &Transcription, options: &SubtitleOptions) -> Vec<Cue> {
+    if transcription.segments.is_empty() {
+        let whole = Segment {
+            id: String::new(),
+            start_ms: 0,
+            end_ms: transcription.duration_ms,
+            text: transcription.edited_text.clone().unwrap_or_else(|| transcription.raw_text.clone()),
+            confidence: 1.0,
+            speaker: None,
+            language: None,
+            words: Vec::new(),
+        };
+        return cues(&[whole], options);
+    }
+    cues(&transcription.segments, options)
+}
+
+/// Split segments into cues within the layout limits
+pub fn cues(segments: &[Segment], options: &SubtitleOptions) -> Vec<Cue> {
+    let mut cues = Vec::new();
+
+    for segment in segments {
+        let words: Vec<&str> = segment.text.split_whitespace().collect();
+        if words.is_empty() {
+            continue;
+        }
+        let total_chars = segment.text.chars().count().max(1);
+        let duration = (segment.end_ms - segment.start_ms).max(0);
+        // Time of a character offset, assuming a steady speaking rate
+        let time_at = |offset: usize| {
+            segment.start_ms + duration * offset.min(total_chars) as i64 / total_chars as i64
+        };
+
+        let mut start_offset = 0;
+        let mut offset = 0;
+        let mut current: Vec<&str> = Vec::new();
+        for word in words {
+            let length = word.chars().count();
+            let mut candidate = current.clone();
+            candidate.push(word);
+            let too_long = !fits(&candidate.join(" "), options.max_line_chars)
+                || time_at(offset + length + 1) - time_at(start_offset) > options.max_cue_ms;
+            if !current.is_empty() && too_long {
+                cues.push(Cue {
+                    start_ms: time_at(start_offset),
+                    end_ms: time_at(offset),
+                    lines: cue_lines(&current, options),
+                });
+                current.clear();
+                start_offset = offset;
+            }
+            current.push(word);
+            offset += length + 1;
+        }
+        cues.push(Cue {
+            start_ms: time_at(start_offset),
+            end_ms: segment.end_ms,
+            lines: cue_lines(&current, options),
+        });
+    }
+
+    cues
+}
+
+/// Lines of a cue, punctuated for the options
+fn cue_lines(words: &[&str], options: &SubtitleOptions) -> Vec<String> {
+    wrap(&punctuate(&words.join(" "), options.punctuation), options.max_line_chars)
+}
+
+/// Break `text` into lines of at most `max_chars`, balanced when it takes two
+fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let length = text.chars().count();
+    if length <= max_chars {
+        return vec![text.to_string()];
+    }
+
+    // Space closest to the middle, preferring those leaving both lines short enough
+    let middle = length / 2;
+    let split = text
+        .char_indices()
+        .filter(|&(_, c)| c == ' ')
+        .map(|(byte, _)| {
+            let before = text[..byte].chars().count();
+            let fits = before <= max_chars && length - before - 1 <= max_chars;
+            (byte, !fits, before.abs_diff(middle))
+        })
+        .min_by_key(|&(_, overflows, distance)| (overflows, distance))
+        .map(|(byte, _, _)| byte);
+
+    match split {
+        Some(byte) => vec![text[..byte].to_string(), text[byte + 1..].to_string()],
+        None => vec![text.to_string()],
+    }
+}
+
+/// `text` holds in `MAX_LINES` lines of `max_chars`
+fn fits(text: &str, max_chars: usize) -> bool {
+    let lines = wrap(text, max_chars);
+    lines.len() <= MAX_LINES && lines.iter().all(|l| l.chars().count() <= max_chars)
+}
+
+/// `HH:MM:SS,mmm` (SRT) or `HH:MM:SS.mmm` (WebVTT)
+fn timestamp(ms: i64, separator: char) -> String {
+    let ms = ms.max(0);
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        separator,
+        ms % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str, start_ms: i64, end_ms: i64) -> Segment {
+        Segment {
+            id: String::new(),
+            start_ms,
+            end_ms,
+            text: text.to_string(),
+            confidence: 1.0,
+            speaker: None,
+            language: None,
+            words: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_timestamps() {
+        assert_eq!(timestamp(3_723_045, ','), "01:02:03,045");
+        assert_eq!(timestamp(500, '.'), "00:00:00.500");
+    }
+
+    #[test]
+    fn test_long_segment_is_split_within_limits() {
+        let options = SubtitleOptions {
+            max_line_chars: 20,
+            max_cue_ms: 4000,
+            punctuation: PunctuationMode::Minimal,
+        };
+        let text = "le budget annuel a ete vote hier soir apres une longue discussion entre les membres du conseil";
+        let cues = cues(&[segment(text, 1000, 11_000)], &options);
+
+        assert!(cues.len() > 1);
+        assert_eq!(cues[0].start_ms, 1000);
+        assert_eq!(cues.last().unwrap().end_ms, 11_000);
+        for pair in cues.windows(2) {
+            assert_eq!(pair[0].end_ms, pair[1].start_ms);
+        }
+        for cue in &cues {
+            assert!(cue.lines.len() <= MAX_LINES);
+            assert!(cue.lines.iter().all(|l| l.chars().count() <= 20));
+            assert!(cue.end_ms - cue.start_ms <= 4000);
+        }
+        let rejoined: Vec<String> = cues.iter().map(|c| c.lines.join(" ")).collect();
+        assert_eq!(rejoined.join(" "), text);
+    }
+
+    #[test]
+    fn test_cue_punctuation_follows_the_options() {
+        let segments = [segment("Bon, on y va.", 0, 2000)];
+        let lines = |punctuation| {
+            let options = SubtitleOptions {
+                punctuation,
+                ..SubtitleOptions::default()
+            };
+            cues(&segments, &options)[0].lines.clone()
+        };
+        assert_eq!(lines(PunctuationMode::Minimal), ["Bon on y va"]);
+        assert_eq!(lines(PunctuationMode::Full), ["Bon, on y va."]);
+    }
+}
@@ -0,0 +1,476 @@
+//! Transcript JSON of other tools, to move archives in and out
+//!
+//! Two schemas, with times in seconds:
+//! - OpenAI Whisper `verbose_json`: segments (with their words, as written
+//!   by the `whisper` CLI) or top-level words (API responses).
+//! - A generic word-level list, as written by Otter-style exports and
+//!   alignment tools: `{"words": [{"word", "start", "end"}]}` or a bare array;
+//!   `text` may replace `word`, and `confidence`/`probability` and `speaker`
+//!   are optional.
+//!
+//! What does not survive the trip:
+//! - Whisper export: speakers, markers and action items. `text` is the
+//!   edited text when there is one, the segments keep the transcribed one.
+//!   `avg_logprob` is the log of the segment confidence; `tokens`,
+//!   `temperature`, `compression_ratio` and `no_speech_prob` are neutral.
+//! - Word-level export: segment boundaries. A segment without word timings
+//!   becomes one entry spanning the segment.
+//! - Import: token data and decoding statistics. Whisper segments keep their
+//!   boundaries; a word list is cut into phrases at speaker changes, sentence
+//!   ends and long pauses, like decoded words. Transcripts without scores get
+//!   a confidence of 1 (nothing flagged as doubtful).
+
+use crate::engine::timing::{self, WordTiming};
+use crate::error::{AppError, Result};
+use crate::export::write_atomic;
+use crate::storage::{Segment, SegmentWord, Transcription, TranscriptionSource};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Schema of a transcript JSON file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptFormat {
+    WhisperVerbose,
+    WordLevel,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WhisperTranscript {
+    #[serde(default = "default_task")]
+    task: String,
+    #[serde(default)]
+    language: String,
+    #[serde(default)]
+    duration: f64,
+    text: String,
+    #[serde(default)]
+    segments: Vec<WhisperSegment>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    words: Vec<WhisperWord>,
+}
+
+fn default_task() -> String {
+    "transcribe".to_string()
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WhisperSegment {
+    #[serde(default)]
+    id: usize,
+    #[serde(default)]
+    seek: u64,
+    start: f64,
+    end: f64,
+    text: String,
+    #[serde(default)]
+    tokens: Vec<u32>,
+    #[serde(default)]
+    temperature: f64,
+    #[serde(default)]
+    avg_logprob: Option<f64>,
+    #[serde(default)]
+    compression_ratio: f64,
+    #[serde(default)]
+    no_speech_prob: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    words: Vec<WhisperWord>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WhisperWord {
+    word: String,
+    start: f64,
+    end: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    probability: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WordLevelTranscript {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    duration: Option<f64>,
+    words: Vec<WordEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WordEntry {
+    #[serde(alias = "text")]
+    word: String,
+    start: f64,
+    end: f64,
+    #[serde(default, alias = "probability", skip_serializing_if = "Option::is_none")]
+    confidence: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    speaker: Option<String>,
+}
+
+/// Word-level file: a document or a bare array of words
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum WordLevelInput {
+    Document(WordLevelTranscript),
+    Words(Vec<WordEntry>),
+}
+
+fn seconds(ms: i64) -> f64 {
+    ms as f64 / 1000.0
+}
+
+fn millis(seconds: f64) -> i64 {
+    (seconds * 1000.0).round() as i64
+}
+
+/// Write `transcription` in the schema of `format`
+pub fn export_transcript_json(transcription: &Transcription, path: &Path, format: TranscriptFormat) -> Result<()> {
+    write_atomic(path, |file| {
+        match format {
+            TranscriptFormat::WhisperVerbose => serde_json::to_writer_pretty(&mut *file, &to_whisper(transcription))?,
+            TranscriptFormat::WordLevel => serde_json::to_writer_pretty(&mut *file, &to_word_level(transcription))?,
+        }
+        Ok(())
+    })
+}
+
+fn to_whisper(transcription: &Transcription) -> WhisperTranscript {
+    let segments = transcription
+        .segments
+        .iter()
+        .enumerate()
+        .map(|(id, segment)| WhisperSegment {
+            id,
+            seek: 0,
+            start: seconds(segment.start_ms),
+            end: seconds(segment.end_ms),
+            text: segment.text.clone(),
+            tokens: Vec::new(),
+            temperature: 0.0,
+            avg_logprob: Some(segment.confidence.max(1e-6).ln()),
+            compression_ratio: 1.0,
+            no_speech_prob: 0.0,
+            words: segment
+                .words
+                .iter()
+                .map(|w| WhisperWord {
+                    word: w.text.clone(),
+                    start: seconds(w.start_ms),
+                    end: seconds(w.end_ms),
+                    probability: Some(w.confidence as f64),
+                })
+                .collect(),
+        })
+        .collect();
+
+    WhisperTranscript {
+        task: default_task(),
+        language: transcription.language.clone(),
+        duration: seconds(transcription.duration_ms),
+        text: transcription.edited_text.clone().unwrap_or_else(|| transcription.raw_text.clone()),
+        segments,
+        words: Vec::new(),
+    }
+}
+
+fn to_word_level(transcription: &Transcription) -> WordLevelTranscript {
+    let words = transcription
+        .segments
+        .iter()
+        .flat_map(|segment| {
+            if segment.words.is_empty() {
+                return vec![WordEntry {
+                    word: segment.text.clone(),
+                    start: seconds(segment.start_ms),
+                    end: seconds(segment.end_ms),
+                    confidence: None,
+                    speaker: segment.speaker.clone(),
+                }];
+            }
+            segment
+                .words
+                .iter()
+                .map(|w| WordEntry {
+                    word: w.text.clone(),
+                    start: seconds(w.start_ms),
+                    end: seconds(w.end_ms),
+                    confidence: Some(w.confidence as f64),
+                    speaker: segment.speaker.clone(),
+                })
+                .collect()
+        })
+        .collect();
+
+    WordLevelTranscript {
+        text: Some(transcription.edited_text.clone().unwrap_or_else(|| transcription.raw_text.clone())),
+        language: Some(transcription.language.clone()),
+        duration: Some(seconds(transcription.duration_ms)),
+        words,
+    }
+}
+
+/// Read a transcript JSON file as a new imported transcription (not saved)
+///
+/// Without `format`, a document with `segments` is read as Whisper output,
+/// anything else as a word list.
+pub fn import_transcript_json(path: &Path, format: Option<TranscriptFormat>) -> Result<Transcription> {
+    let json = std::fs::read_to_string(path)?;
+    let mut transcription = parse_transcript(&json, format)?;
+    transcription.source_name = path.file_name().map(|n| n.to_string_lossy().into_owned());
+    transcription.source_path = Some(path.to_string_lossy().into_owned());
+    Ok(transcription)
+}
+
+fn parse_transcript(json: &str, format: Option<TranscriptFormat>) -> Result<Transcription> {
+    let format = match format {
+        Some(format) => format,
+        None => {
+            let value: serde_json::Value = serde_json::from_str(json)
+                .map_err(|e| AppError::InvalidInput(format!("Not a JSON file: {}", e)))?;
+            if value.get("segments").is_some() {
+                TranscriptFormat::WhisperVerbose
+            } else {
+                TranscriptFormat::WordLevel
+            }
+        }
+    };
+
+    match format {
+        TranscriptFormat::WhisperVerbose => serde_json::from_str(json)
+            .map(from_whisper)
+            .map_err(|e| AppError::InvalidInput(format!("Not a Whisper verbose_json transcript: {}", e))),
+        TranscriptFormat::WordLevel => {
+            let document = match serde_json::from_str(json)
+                .map_err(|e| AppError::InvalidInput(format!("Not a word-level transcript: {}", e)))?
+            {
+                WordLevelInput::Document(document) => document,
+                WordLevelInput::Words(words) => WordLevelTranscript {
+                    text: None,
+                    language: None,
+                    duration: None,
+                    words,
+                },
+            };
+            Ok(from_word_level(document))
+        }
+    }
+}
+
+/// Language code from Whisper's names ("french") or codes ("fr")
+fn language_code(language: &str) -> String {
+    match language.trim().to_lowercase().as_str() {
+        "" => "fr".to_string(),
+        "french" => "fr".to_string(),
+        "english" => "en".to_string(),
+        "spanish" => "es".to_string(),
+        "german" => "de".to_string(),
+        "italian" => "it".to_string(),
+        "portuguese" => "pt".to_string(),
+        "dutch" => "nl".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn segment_word(word: &WhisperWord) -> SegmentWord {
+    SegmentWord {
+        text: word.word.trim().to_string(),
+        start_ms: millis(word.start),
+        end_ms: millis(word.end),
+        confidence: word.probability.unwrap_or(1.0) as f32,
+    }
+}
+
+fn from_whisper(whisper: WhisperTranscript) -> Transcription {
+    let mut segments: Vec<Segment> = whisper
+        .segments
+        .iter()
+        .map(|segment| {
+            let start_ms = millis(segment.start);
+            let end_ms = millis(segment.end);
+            // API responses list the words at the top level
+            let words: Vec<SegmentWord> = if segment.words.is_empty() {
+                whisper
+                    .words
+                    .iter()
+                    .map(segment_word)
+                    .filter(|w| w.start_ms >= start_ms && w.start_ms < end_ms)
+                    .collect()
+            } else {
+                segment.words.iter().map(segment_word).collect()
+            };
+            Segment {
+                id: Uuid::new_v4().to_string(),
+                start_ms,
+                end_ms,
+                text: segment.text.trim().to_string(),
+                confidence: segment.avg_logprob.map(|p| p.exp().clamp(0.0, 1.0)).unwrap_or(1.0),
+                speaker: None,
+                language: None,
+                words,
+            }
+        })
+        .collect();
+    segments.retain(|s| !s.text.is_empty());
+
+    let end_ms = segments.last().map(|s| s.end_ms).unwrap_or(0);
+    imported(
+        whisper.text.trim().to_string(),
+        &whisper.language,
+        millis(whisper.duration).max(end_ms),
+        segments,
+    )
+}
+
+fn from_word_level(document: WordLevelTranscript) -> Transcription {
+    let mut segments = Vec::new();
+    // Runs of words of the same speaker, each cut into phrases
+    let mut start = 0;
+    while start < document.words.len() {
+        let speaker = &document.words[start].speaker;
+        let run = document.words[start..]
+            .iter()
+            .take_while(|w| &w.speaker == speaker)
+            .count();
+        let words: Vec<WordTiming> = document.words[start..start + run]
+            .iter()
+            .filter(|w| !w.word.trim().is_empty())
+            .map(|w| WordTiming {
+                text: w.word.trim().to_string(),
+                start_ms: millis(w.start),
+                end_ms: millis(w.end),
+                confidence: w.confidence.map(|c| c as f32),
+            })
+            .collect();
+
+        for phrase in timing::group_phrases(&words) {
+            segments.push(Segment {
+                id: Uuid::new_v4().to_string(),
+                start_ms: phrase.start_ms,
+                end_ms: phrase.end_ms,
+                confidence: phrase.confidence().unwrap_or(1.0),
+                words: phrase
+                    .words
+                    .iter()
+                    .map(|w| SegmentWord {
+                        text: w.text.clone(),
+                        start_ms: w.start_ms,
+                        end_ms: w.end_ms,
+                        confidence: w.confidence.unwrap_or(1.0),
+                    })
+                    .collect(),
+                text: phrase.text,
+                speaker: speaker.clone(),
+                language: None,
+            });
+        }
+        start += run;
+    }
+
+    let text = document.text.unwrap_or_else(|| {
+        segments.iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join(" ")
+    });
+    let end_ms = segments.last().map(|s| s.end_ms).unwrap_or(0);
+    imported(
+        text,
+        document.language.as_deref().unwrap_or(""),
+        document.duration.map(millis).unwrap_or(0).max(end_ms),
+        segments,
+    )
+}
+
+fn imported(text: String, language: &str, duration_ms: i64, segments: Vec<Segment>) -> Transcription {
+    let now = chrono::Utc::now().to_rfc3339();
+    Transcription {
+        id: Uuid::new_v4().to_string(),
+        created_at: now.clone(),
+        updated_at: now,
+        source_type: TranscriptionSource::Import,
+        source_name: None,
+        source_path: None,
+        original_sample_rate: None,
+        duration_ms,
+        language: language_code(language),
+        segments,
+        raw_text: text,
+        edited_text: None,
+        is_edited: false,
+        is_draft: false,
+        warnings: Vec::new(),
+        normalization_gain: None,
+        action_items: Vec::new(),
+        markers: Vec::new(),
+        pauses: Vec::new(),
+        capture: None,
+        // Not decoded here
+        confidence: None,
+        audio_path: None,
+        translations: Vec::new(),
+        notes: Vec::new(),
+        folder: None,
+        tags: Vec::new(),
+        title: None,
+        deleted_at: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_whisper_api_response() {
+        let json = r#"{
+            "task": "transcribe", "language": "french", "duration": 4.2,
+            "text": " Bonjour a tous. On commence.",
+            "segments": [
+                {"id": 0, "seek": 0, "start": 0.0, "end": 1.8, "text": " Bonjour a tous.", "avg_logprob": -0.1},
+                {"id": 1, "seek": 0, "start": 2.0, "end": 3.9, "text": " On commence."}
+            ],
+            "words": [
+                {"word": "Bonjour", "start": 0.0, "end": 0.6},
+                {"word": "On", "start": 2.0, "end": 2.3}
+            ]
+        }"#;
+        let t = parse_transcript(json, None).unwrap();
+        assert_eq!(t.language, "fr");
+        assert_eq!(t.duration_ms, 4200);
+        assert_eq!(t.raw_text, "Bonjour a tous. On commence.");
+        assert_eq!(t.segments.len(), 2);
+        assert_eq!((t.segments[1].start_ms, t.segments[1].end_ms), (2000, 3900));
+        assert!((t.segments[0].confidence - (-0.1f64).exp()).abs() < 1e-9);
+        assert_eq!(t.segments[0].words[0].text, "Bonjour");
+        assert_eq!(t.segments[1].words[0].text, "On");
+
+        // Back to Whisper: same boundaries and text
+        let whisper = to_whisper(&t);
+        assert_eq!(whisper.segments[1].text, "On commence.");
+        assert_eq!(whisper.segments[1].start, 2.0);
+    }
+
+    #[test]
+    fn test_word_list_is_cut_at_speakers_and_sentences() {
+        let json = r#"[
+            {"text": "Salut.", "start": 0.0, "end": 0.4, "speaker": "A"},
+            {"text": "Ca", "start": 0.6, "end": 0.8, "speaker": "A"},
+            {"text": "va", "start": 0.8, "end": 1.0, "speaker": "A", "confidence": 0.5},
+            {"text": "Oui", "start": 1.2, "end": 1.5, "speaker": "B"}
+        ]"#;
+        let t = parse_transcript(json, None).unwrap();
+        let texts: Vec<(&str, Option<&str>)> = t
+            .segments
+            .iter()
+            .map(|s| (s.text.as_str(), s.speaker.as_deref()))
+            .collect();
+        assert_eq!(texts, [("Salut.", Some("A")), ("Ca va", Some("A")), ("Oui", Some("B"))]);
+        assert_eq!(t.segments[1].confidence, 0.5);
+        assert_eq!(t.raw_text, "Salut. Ca va Oui");
+        assert_eq!(t.duration_ms, 1500);
+
+        let exported = to_word_level(&t);
+        assert_eq!(exported.words.len(), 4);
+        assert_eq!(exported.words[3].speaker.as_deref(), Some("B"));
+    }
+}
@@ -1,16 +1,31 @@
 use crate::error::Result;
+use crate::export::{action_item_line, notes_title, write_atomic};
+use crate::provenance::Provenance;
 use crate::storage::Transcription;
-use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 
-pub fn export_to_txt(transcription: &Transcription, path: &Path) -> Result<()> {
-    let mut file = File::create(path)?;
+pub fn export_to_txt(transcription: &Transcription, path: &Path, provenance: Option<&Provenance>) -> Result<()> {
+    let mut body = Vec::new();
+    write_txt(transcription, &mut body)?;
+    if let Some(provenance) = provenance {
+        writeln!(body)?;
+        writeln!(body, "---")?;
+        // Sealed with the text it follows
+        let sealed = provenance.sealing(&String::from_utf8_lossy(&body));
+        writeln!(body, "{}", sealed.line()?)?;
+    }
+    write_atomic(path, |file| Ok(file.write_all(&body)?))
+}
 
+fn write_txt(transcription: &Transcription, file: &mut impl Write) -> Result<()> {
     // Header
     writeln!(file, "Transcription WakaScribe")?;
     writeln!(file, "========================")?;
     writeln!(file)?;
+    if let Some(ref title) = transcription.title {
+        writeln!(file, "Titre: {}", title)?;
+    }
     writeln!(file, "Date: {}", transcription.created_at)?;
     if let Some(ref name) = transcription.source_name {
         writeln!(file, "Source: {}", name)?;
@@ -31,6 +46,61 @@ pub fn export_to_txt(transcription: &Transcription, path: &Path) -> Result<()> {
         .unwrap_or(&transcription.raw_text);
     writeln!(file, "{}", text)?;
 
+    // Translations, in parallel with the text
+    for translation in &transcription.translations {
+        writeln!(file)?;
+        writeln!(file, "---")?;
+        writeln!(file)?;
+        writeln!(file, "Traduction ({}):", translation.language.to_uppercase())?;
+        writeln!(file)?;
+        writeln!(file, "{}", translation.text)?;
+    }
+
+    // Notes written by the LLM
+    for notes in &transcription.notes {
+        writeln!(file)?;
+        writeln!(file, "---")?;
+        writeln!(file)?;
+        writeln!(file, "{}:", notes_title(notes.style))?;
+        writeln!(file)?;
+        writeln!(file, "{}", notes.summary)?;
+        for (title, items) in [("Points cles", &notes.key_points), ("Actions", &notes.action_items)] {
+            if !items.is_empty() {
+                writeln!(file)?;
+                writeln!(file, "{}:", title)?;
+                for item in items {
+                    writeln!(file, "- {}", item)?;
+                }
+            }
+        }
+    }
+
+    // Markers flagged while recording
+    if !transcription.markers.is_empty() {
+        writeln!(file)?;
+        writeln!(file, "---")?;
+        writeln!(file)?;
+        writeln!(file, "Marqueurs:")?;
+        writeln!(file)?;
+
+        for marker in &transcription.markers {
+            writeln!(file, "[{}] {}", format_timestamp(marker.at_ms), marker.label)?;
+        }
+    }
+
+    // Actions and decisions
+    if !transcription.action_items.is_empty() {
+        writeln!(file)?;
+        writeln!(file, "---")?;
+        writeln!(file)?;
+        writeln!(file, "Actions et decisions:")?;
+        writeln!(file)?;
+
+        for item in &transcription.action_items {
+            writeln!(file, "- {}", action_item_line(item))?;
+        }
+    }
+
     // Segments with timestamps
     if !transcription.segments.is_empty() {
         writeln!(file)?;
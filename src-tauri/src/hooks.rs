@@ -0,0 +1,214 @@
+//! User commands run after each transcription
+//!
+//! A hook is a program with arguments, configured in the settings, started
+//! once a transcription is saved: the text arrives on stdin and in a file,
+//! so workflows (push to a ticketing system, custom formatter) need no
+//! change to the app. Hooks run one after the other on a background thread
+//! with a restricted setup: no shell, an environment reduced to a few
+//! variables, a throwaway working directory, a timeout and capped output.
+//! This limits accidents, not a malicious program, which still runs with
+//! the user's rights.
+
+use crate::error::{AppError, Result};
+use crate::jobs;
+use crate::storage::{JobKind, Transcription};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Output kept from each stream of a hook (the rest is discarded)
+const MAX_OUTPUT_BYTES: u64 = 64 * 1024;
+
+/// Delay between two checks of a running hook
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Variables passed through from the app environment
+const INHERITED_VARS: [&str; 8] = [
+    "PATH", "HOME", "USER", "LANG", "TMPDIR", "SystemRoot", "TEMP", "USERPROFILE",
+];
+
+/// Program started after each transcription
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostTranscriptionHook {
+    pub name: String,
+    /// Absolute path or name found in PATH; never interpreted by a shell
+    pub program: String,
+    /// Arguments (empty ones skipped); `{id}`, `{text_file}`, `{source_path}` and `{language}` are replaced
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// The hook is killed past this delay
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+/// Run the enabled hooks on a background thread; failures are reported as `job-failed`
+pub fn run_after_transcription(app: &AppHandle, transcription: &Transcription, hooks: &[PostTranscriptionHook]) {
+    let hooks: Vec<PostTranscriptionHook> = hooks.iter().filter(|h| h.enabled).cloned().collect();
+    if hooks.is_empty() {
+        return;
+    }
+
+    let app = app.clone();
+    let transcription = transcription.clone();
+    thread::spawn(move || {
+        for hook in &hooks {
+            if let Err(e) = run_hook(hook, &transcription) {
+                jobs::report_failure(&app, JobKind::Hook, &e, Some(&hook.name));
+            }
+        }
+    });
+}
+
+fn run_hook(hook: &PostTranscriptionHook, transcription: &Transcription) -> Result<()> {
+    let workdir = std::env::temp_dir().join(format!("wakascribe-hook-{}", Uuid::new_v4()));
+    fs::create_dir_all(&workdir)?;
+    let result = run_in(&workdir, hook, transcription);
+    if let Err(e) = fs::remove_dir_all(&workdir) {
+        warn!("Failed to remove hook directory {:?}: {}", workdir, e);
+    }
+    result
+}
+
+fn run_in(workdir: &Path, hook: &PostTranscriptionHook, transcription: &Transcription) -> Result<()> {
+    let text = transcription.edited_text.as_deref().unwrap_or(&transcription.raw_text);
+    let text_file = workdir.join("transcription.txt");
+    fs::write(&text_file, text)?;
+
+    let values = HookValues {
+        id: &transcription.id,
+        text_file: &text_file.to_string_lossy(),
+        source_path: transcription.source_path.as_deref().unwrap_or(""),
+        language: &transcription.language,
+    };
+
+    let mut command = Command::new(&hook.program);
+    command
+        .args(hook.args.iter().filter(|a| !a.is_empty()).map(|a| values.expand(a)))
+        .current_dir(workdir)
+        .env_clear()
+        .envs(INHERITED_VARS.iter().filter_map(|k| std::env::var_os(k).map(|v| (k, v))))
+        .env("WAKASCRIBE_ID", values.id)
+        .env("WAKASCRIBE_TEXT_FILE", values.text_file)
+        .env("WAKASCRIBE_SOURCE_PATH", values.source_path)
+        .env("WAKASCRIBE_LANGUAGE", values.language)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    info!("Running hook '{}': {}", hook.name, hook.program);
+    let mut child = command
+        .spawn()
+        .map_err(|e| AppError::InvalidInput(format!("Hook '{}' could not start: {}", hook.name, e)))?;
+
+    // Pipes are served on their own threads so a chatty hook never blocks on them
+    let stdin = child.stdin.take().map(|mut stdin| {
+        let text = text.to_string();
+        thread::spawn(move || {
+            let _ = stdin.write_all(text.as_bytes());
+        })
+    });
+    let stdout = child.stdout.take().map(|out| thread::spawn(move || read_capped(out)));
+    let stderr = child.stderr.take().map(|err| thread::spawn(move || read_capped(err)));
+
+    let status = wait_with_timeout(&mut child, Duration::from_secs(hook.timeout_secs));
+
+    let _ = stdin.map(|t| t.join());
+    let stdout = stdout.and_then(|t| t.join().ok()).unwrap_or_default();
+    let stderr = stderr.and_then(|t| t.join().ok()).unwrap_or_default();
+    if !stdout.trim().is_empty() {
+        info!("Hook '{}' output: {}", hook.name, stdout.trim());
+    }
+
+    match status {
+        Some(status) if status.success() => Ok(()),
+        Some(status) => Err(AppError::InvalidState(format!(
+            "Hook '{}' failed ({}): {}",
+            hook.name,
+            status,
+            stderr.trim()
+        ))),
+        None => Err(AppError::InvalidState(format!(
+            "Hook '{}' killed after {} s",
+            hook.name, hook.timeout_secs
+        ))),
+    }
+}
+
+/// Exit status, or `None` when the child had to be killed
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Option<std::process::ExitStatus> {
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Some(status),
+            Ok(None) if started.elapsed() < timeout => thread::sleep(POLL_INTERVAL),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+}
+
+fn read_capped(stream: impl Read) -> String {
+    let mut bytes = Vec::new();
+    let mut limited = stream.take(MAX_OUTPUT_BYTES);
+    let _ = limited.read_to_end(&mut bytes);
+    // Drain the rest so the hook doesn't block on a full pipe
+    let _ = std::io::copy(&mut limited.into_inner(), &mut std::io::sink());
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Values substituted in the arguments
+struct HookValues<'a> {
+    id: &'a str,
+    text_file: &'a str,
+    source_path: &'a str,
+    language: &'a str,
+}
+
+impl HookValues<'_> {
+    fn expand(&self, arg: &str) -> String {
+        arg.replace("{id}", self.id)
+            .replace("{text_file}", self.text_file)
+            .replace("{source_path}", self.source_path)
+            .replace("{language}", self.language)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholders_are_expanded_in_place() {
+        let values = HookValues {
+            id: "abc",
+            text_file: "/tmp/t.txt",
+            source_path: "",
+            language: "fr",
+        };
+        assert_eq!(values.expand("--file={text_file}"), "--file=/tmp/t.txt");
+        assert_eq!(values.expand("{id}-{language}"), "abc-fr");
+        assert_eq!(values.expand("{source_path}"), "");
+        assert_eq!(values.expand("plain"), "plain");
+    }
+}
@@ -0,0 +1,243 @@
+//! Transcription jobs, run in order on worker threads
+//!
+//! `stop_recording` and `transcribe_file` only do what cannot wait (stop the
+//! capture, check the file) and queue the rest: the command returns the job
+//! id at once, so the engine lock is never held on a command thread. Each
+//! state change is sent as a `transcription-job` event; the queued and
+//! running jobs, and the last finished ones, answer `get_job_status`.
+//!
+//! Dictations have their own worker: they never wait for queued files. With
+//! the `engine_pool` setting files run on a second engine, so a dictation
+//! does not wait for the one being transcribed either.
+
+use crate::error::{AppError, Result};
+use crate::storage::{Transcription, TranscriptionSource};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Finished jobs kept for `get_job_status`
+const MAX_FINISHED: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobState {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+/// A transcription job as reported to the UI
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionJob {
+    pub id: String,
+    pub source: TranscriptionSource,
+    /// File name of a file job
+    pub source_name: Option<String>,
+    pub state: JobState,
+    pub created_at: String,
+    /// Saved transcription, once done
+    pub transcription: Option<Transcription>,
+    pub error: Option<String>,
+}
+
+type Work = Box<dyn FnOnce() -> Result<Transcription> + Send>;
+type Finished = Box<dyn FnOnce(&Result<Transcription>) + Send>;
+
+struct QueuedJob {
+    id: String,
+    app: AppHandle,
+    work: Work,
+    finished: Option<Finished>,
+}
+
+/// Worker of a kind of job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lane {
+    /// Dictations: someone waits for the text
+    Interactive,
+    /// Files
+    Batch,
+}
+
+impl Lane {
+    fn of(source: TranscriptionSource) -> Self {
+        match source {
+            TranscriptionSource::Dictation => Lane::Interactive,
+            _ => Lane::Batch,
+        }
+    }
+
+    fn thread_name(self) -> &'static str {
+        match self {
+            Lane::Interactive => "transcription-dictations",
+            Lane::Batch => "transcription-files",
+        }
+    }
+}
+
+/// Senders to the worker of each lane, started with its first job
+static WORKERS: Mutex<[Option<Sender<QueuedJob>>; 2]> = parking_lot::const_mutex([None, None]);
+
+/// Queued and running jobs, then the finished ones, oldest first
+static JOBS: Mutex<VecDeque<TranscriptionJob>> = parking_lot::const_mutex(VecDeque::new());
+
+/// Queue `work` and return the job id; `finished` runs on the worker with its result
+pub fn submit(
+    app: &AppHandle,
+    source: TranscriptionSource,
+    source_name: Option<String>,
+    work: impl FnOnce() -> Result<Transcription> + Send + 'static,
+    finished: Option<Finished>,
+) -> Result<String> {
+    let job = TranscriptionJob {
+        id: Uuid::new_v4().to_string(),
+        source,
+        source_name,
+        state: JobState::Queued,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        transcription: None,
+        error: None,
+    };
+    let id = job.id.clone();
+    JOBS.lock().push_back(job.clone());
+    let _ = app.emit("transcription-job", &job);
+
+    let queued = QueuedJob {
+        id: id.clone(),
+        app: app.clone(),
+        work: Box::new(work),
+        finished,
+    };
+    let sent = worker(Lane::of(source)).and_then(|sender| {
+        sender
+            .send(queued)
+            .map_err(|_| AppError::InvalidState("Transcription worker stopped".into()))
+    });
+    if let Err(e) = sent {
+        update(app, &id, |j| {
+            j.state = JobState::Failed;
+            j.error = Some(e.to_string());
+        });
+        return Err(e);
+    }
+    info!("Transcription job {} queued", id);
+    Ok(id)
+}
+
+fn worker(lane: Lane) -> Result<Sender<QueuedJob>> {
+    let mut workers = WORKERS.lock();
+    let worker = &mut workers[lane as usize];
+    if let Some(sender) = worker.as_ref() {
+        return Ok(sender.clone());
+    }
+    let (sender, receiver) = mpsc::channel::<QueuedJob>();
+    thread::Builder::new()
+        .name(lane.thread_name().into())
+        .spawn(move || receiver.into_iter().for_each(run))?;
+    Ok(worker.insert(sender).clone())
+}
+
+/// State of a job still queued or running, or among the last finished
+pub fn status(id: &str) -> Result<TranscriptionJob> {
+    JOBS.lock()
+        .iter()
+        .find(|job| job.id == id)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound(format!("Transcription job {}", id)))
+}
+
+fn run(job: QueuedJob) {
+    update(&job.app, &job.id, |j| j.state = JobState::Running);
+
+    // A panic fails the job, not the worker and the jobs after it
+    let result = panic::catch_unwind(AssertUnwindSafe(job.work))
+        .unwrap_or_else(|_| Err(AppError::Transcription("job panicked".into())));
+    if let Some(finished) = job.finished {
+        finished(&result);
+    }
+    match result {
+        Ok(transcription) => update(&job.app, &job.id, |j| {
+            j.state = JobState::Done;
+            j.transcription = Some(transcription);
+        }),
+        Err(e) => {
+            warn!("Transcription job {} failed: {}", job.id, e);
+            update(&job.app, &job.id, |j| {
+                j.state = JobState::Failed;
+                j.error = Some(e.to_string());
+            })
+        }
+    }
+    prune(&mut JOBS.lock());
+}
+
+fn update(app: &AppHandle, id: &str, change: impl FnOnce(&mut TranscriptionJob)) {
+    let job = {
+        let mut jobs = JOBS.lock();
+        let Some(job) = jobs.iter_mut().find(|job| job.id == id) else {
+            return;
+        };
+        change(job);
+        job.clone()
+    };
+    let _ = app.emit("transcription-job", &job);
+}
+
+/// Forget the oldest finished jobs past `MAX_FINISHED`
+fn prune(jobs: &mut VecDeque<TranscriptionJob>) {
+    let finished = |job: &TranscriptionJob| matches!(job.state, JobState::Done | JobState::Failed);
+    let mut excess = jobs.iter().filter(|job| finished(job)).count().saturating_sub(MAX_FINISHED);
+    jobs.retain(|job| {
+        if excess > 0 && finished(job) {
+            excess -= 1;
+            return false;
+        }
+        true
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: usize, state: JobState) -> TranscriptionJob {
+        TranscriptionJob {
+            id: id.to_string(),
+            source: TranscriptionSource::File,
+            source_name: None,
+            state,
+            created_at: String::new(),
+            transcription: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_dictations_have_their_own_lane() {
+        assert_eq!(Lane::of(TranscriptionSource::Dictation), Lane::Interactive);
+        assert_eq!(Lane::of(TranscriptionSource::File), Lane::Batch);
+    }
+
+    #[test]
+    fn test_prune_keeps_unfinished_and_last_finished() {
+        let mut jobs: VecDeque<_> = (0..MAX_FINISHED + 3).map(|i| job(i, JobState::Done)).collect();
+        jobs.push_front(job(100, JobState::Running));
+        jobs.push_back(job(101, JobState::Queued));
+
+        prune(&mut jobs);
+        let ids: Vec<_> = jobs.iter().map(|j| j.id.as_str()).collect();
+        assert_eq!(jobs.len(), MAX_FINISHED + 2);
+        assert_eq!(ids[0], "100");
+        assert_eq!(ids[1], "3");
+        assert_eq!(ids[ids.len() - 1], "101");
+    }
+}
@@ -0,0 +1,37 @@
+//! Failures of background jobs
+//!
+//! Jobs like the watch folder or scheduled recordings run without a command
+//! waiting for their result. Their failures are stored in `job_errors` (for
+//! `get_recent_errors`) and sent to the UI as a `job-failed` event carrying a
+//! typed error code.
+
+use crate::error::AppError;
+use crate::storage::{self, with_db, JobError, JobKind};
+use tauri::{AppHandle, Emitter};
+use tracing::warn;
+use uuid::Uuid;
+
+/// Most recent failures kept in the database
+const MAX_ENTRIES: usize = 500;
+
+/// Record and broadcast the failure of a background job
+pub fn report_failure(app: &AppHandle, job: JobKind, error: &AppError, context: Option<&str>) -> JobError {
+    let job_error = JobError {
+        id: Uuid::new_v4().to_string(),
+        job,
+        code: error.code(),
+        message: error.to_string(),
+        context: context.map(String::from),
+        occurred_at: chrono::Utc::now().to_rfc3339(),
+    };
+    warn!("Background job {} failed: {}", job.as_str(), job_error.message);
+
+    if !storage::is_read_only() {
+        if let Err(e) = with_db(|conn| storage::insert_job_error(conn, &job_error, MAX_ENTRIES)) {
+            warn!("Failed to store job error: {}", e);
+        }
+    }
+    let _ = app.emit("job-failed", &job_error);
+
+    job_error
+}
@@ -1,14 +1,30 @@
 mod audio;
 mod commands;
+mod dictation;
+mod drafts;
 pub mod engine;
 mod error;
 mod export;
+mod hooks;
+mod job_queue;
+mod jobs;
+mod live_paste;
+mod logs;
+mod models;
+mod postprocess;
+mod power;
+mod provenance;
+mod recordings;
+mod speech;
 mod storage;
+mod support;
+mod sync;
 
-use commands::{AudioState, EngineState, ModelPathState};
+use commands::{AudioState, BatchEngineState, EngineState, EngineStatusState, ModelPathState};
 use parking_lot::Mutex;
 use std::path::PathBuf;
 use std::fs::File;
+use tauri::{Emitter, Manager};
 use tracing::{info, warn};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
@@ -77,6 +93,11 @@ fn init_openvino() -> bool {
     false
 }
 
+/// Log file written alongside console output
+fn log_path() -> PathBuf {
+    std::env::temp_dir().join("wakascribe.log")
+}
+
 /// Cross-platform app data directory
 fn app_data_dir() -> Option<PathBuf> {
     #[cfg(target_os = "macos")]
@@ -109,7 +130,7 @@ fn get_model_base_path() -> Option<PathBuf> {
     // macOS: ~/Library/Application Support/com.wakascribe.app/models/
     // Windows: %LOCALAPPDATA%/com.wakascribe.app/models/
     // Linux: ~/.local/share/com.wakascribe.app/models/
-    if let Some(app_models) = app_data_dir().map(|p| p.join("com.wakascribe.app").join("models")) {
+    if let Some(app_models) = models::models_dir() {
         if app_models.exists() {
             info!("Found models in app data directory: {:?}", app_models);
             return Some(app_models);
@@ -153,6 +174,17 @@ fn get_model_base_path() -> Option<PathBuf> {
     None
 }
 
+/// Database path passed as `--read-only <db path>` (review mode on a copied database)
+fn read_only_db_arg() -> Option<PathBuf> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--read-only" {
+            return args.next().map(PathBuf::from);
+        }
+    }
+    None
+}
+
 /// Get model path for specific backend
 fn get_model_path(backend: engine::EngineBackend) -> Option<PathBuf> {
     let base_path = get_model_base_path()?;
@@ -164,11 +196,93 @@ fn get_model_path(backend: engine::EngineBackend) -> Option<PathBuf> {
     }
 }
 
+/// Load the saved backend preference, falling back to other backends
+fn load_saved_backend(saved_backend: &str, openvino_ok: bool) -> (engine::DynamicEngine, bool) {
+    let (backend, engine_loaded) = match saved_backend {
+        "onnxruntime" => {
+            info!("Loading saved preference: ONNX Runtime");
+            try_load_backend(engine::EngineBackend::OnnxRuntime, openvino_ok)
+        }
+        #[cfg(target_os = "macos")]
+        "coreml" => {
+            info!("Loading saved preference: CoreML");
+            try_load_backend(engine::EngineBackend::CoreML, openvino_ok)
+        }
+        _ => {
+            // Default to OpenVINO
+            info!("Loading saved preference: OpenVINO");
+            try_load_backend(engine::EngineBackend::OpenVINO, openvino_ok)
+        }
+    };
+
+    if !engine_loaded {
+        warn!("No model loaded. Using mock transcription.");
+    } else {
+        info!("Using {} backend", backend.name());
+    }
+
+    (backend, engine_loaded)
+}
+
+fn try_load_backend(preferred: engine::EngineBackend, openvino_ok: bool) -> (engine::DynamicEngine, bool) {
+    // Try preferred backend first
+    if let Some(model_path) = get_model_path(preferred) {
+        // For OpenVINO, check if library is available
+        if matches!(preferred, engine::EngineBackend::OpenVINO) && !openvino_ok {
+            info!("OpenVINO library not available, trying fallback");
+        } else {
+            info!("Found {} model at {:?}", preferred.display_name(), model_path);
+            let mut engine = engine::DynamicEngine::new(preferred);
+            match engine.load_model(&model_path) {
+                Ok(_) => {
+                    info!("{} engine loaded successfully", preferred.display_name());
+                    return (engine, true);
+                }
+                Err(e) => {
+                    warn!("Failed to load {} model: {}", preferred.display_name(), e);
+                }
+            }
+        }
+    } else {
+        info!("No {} model found", preferred.display_name());
+    }
+
+    // Fallback: try other backends
+    let fallbacks = [
+        engine::EngineBackend::OnnxRuntime,
+        engine::EngineBackend::OpenVINO,
+    ];
+
+    for fallback in fallbacks {
+        if fallback == preferred {
+            continue;
+        }
+        if matches!(fallback, engine::EngineBackend::OpenVINO) && !openvino_ok {
+            continue;
+        }
+        if let Some(model_path) = get_model_path(fallback) {
+            info!("Trying fallback: {} from {:?}", fallback.display_name(), model_path);
+            let mut engine = engine::DynamicEngine::new(fallback);
+            match engine.load_model(&model_path) {
+                Ok(_) => {
+                    info!("{} engine loaded successfully (fallback)", fallback.display_name());
+                    return (engine, true);
+                }
+                Err(e) => {
+                    warn!("Failed to load {} model: {}", fallback.display_name(), e);
+                }
+            }
+        }
+    }
+
+    // Nothing worked
+    (engine::DynamicEngine::new(engine::EngineBackend::OpenVINO), false)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Initialize logging to both console and file
-    let log_path = std::env::temp_dir().join("wakascribe.log");
-    let log_file = File::create(&log_path).expect("Failed to create log file");
+    let log_file = File::create(log_path()).expect("Failed to create log file");
 
     tracing_subscriber::registry()
         .with(fmt::layer()) // Console output
@@ -177,16 +291,40 @@ pub fn run() {
                 .with_ansi(false)
                 .with_writer(std::sync::Mutex::new(log_file))
         ) // File output
+        .with(logs::RecentLogs) // Kept in memory for get_recent_logs
         .with(EnvFilter::from_default_env().add_directive("wakascribe=debug".parse().unwrap()))
         .init();
 
     info!("Starting WakaScribe...");
 
-    // Initialize database
-    if let Err(e) = storage::init_database() {
+    // Initialize database (read-only review mode when started with `--read-only <db path>`)
+    let db_result = match read_only_db_arg() {
+        Some(db_path) => {
+            info!("Starting in read-only mode on {:?}", db_path);
+            storage::init_database_read_only(&db_path)
+        }
+        None => storage::init_database(),
+    };
+    if let Err(e) = db_result {
         eprintln!("Failed to initialize database: {}", e);
     }
 
+    // Transcriptions left in the trash past the retention period
+    if !storage::is_read_only() {
+        let purged = storage::with_db(|conn| {
+            let settings = storage::get_settings(conn)?;
+            storage::purge_expired_trash(conn, settings.trash_retention_days)
+        });
+        match purged {
+            Ok(0) => {}
+            Ok(count) => info!("Purged {} transcriptions from the trash", count),
+            Err(e) => warn!("Failed to purge the trash: {}", e),
+        }
+        if let Err(e) = storage::with_db(recordings::remove_orphans) {
+            warn!("Failed to delete orphan recordings: {}", e);
+        }
+    }
+
     // Read saved engine backend preference from database
     let saved_backend = storage::with_db(|conn| storage::get_settings(conn))
         .ok()
@@ -201,84 +339,9 @@ pub fn run() {
     let model_base_path = get_model_base_path().unwrap_or_else(|| PathBuf::from("model"));
     info!("Model base path: {:?}", model_base_path);
 
-    // Determine which backend to use based on saved preference
-    let (backend, engine_loaded) = match saved_backend.as_str() {
-        "onnxruntime" => {
-            info!("Loading saved preference: ONNX Runtime");
-            try_load_backend(engine::EngineBackend::OnnxRuntime, openvino_ok)
-        }
-        #[cfg(target_os = "macos")]
-        "coreml" => {
-            info!("Loading saved preference: CoreML");
-            try_load_backend(engine::EngineBackend::CoreML, openvino_ok)
-        }
-        _ => {
-            // Default to OpenVINO
-            info!("Loading saved preference: OpenVINO");
-            try_load_backend(engine::EngineBackend::OpenVINO, openvino_ok)
-        }
-    };
-
-    fn try_load_backend(preferred: engine::EngineBackend, openvino_ok: bool) -> (engine::DynamicEngine, bool) {
-        // Try preferred backend first
-        if let Some(model_path) = get_model_path(preferred) {
-            // For OpenVINO, check if library is available
-            if matches!(preferred, engine::EngineBackend::OpenVINO) && !openvino_ok {
-                info!("OpenVINO library not available, trying fallback");
-            } else {
-                info!("Found {} model at {:?}", preferred.display_name(), model_path);
-                let mut engine = engine::DynamicEngine::new(preferred);
-                match engine.load_model(&model_path) {
-                    Ok(_) => {
-                        info!("{} engine loaded successfully", preferred.display_name());
-                        return (engine, true);
-                    }
-                    Err(e) => {
-                        warn!("Failed to load {} model: {}", preferred.display_name(), e);
-                    }
-                }
-            }
-        } else {
-            info!("No {} model found", preferred.display_name());
-        }
-
-        // Fallback: try other backends
-        let fallbacks = [
-            engine::EngineBackend::OnnxRuntime,
-            engine::EngineBackend::OpenVINO,
-        ];
-
-        for fallback in fallbacks {
-            if fallback == preferred {
-                continue;
-            }
-            if matches!(fallback, engine::EngineBackend::OpenVINO) && !openvino_ok {
-                continue;
-            }
-            if let Some(model_path) = get_model_path(fallback) {
-                info!("Trying fallback: {} from {:?}", fallback.display_name(), model_path);
-                let mut engine = engine::DynamicEngine::new(fallback);
-                match engine.load_model(&model_path) {
-                    Ok(_) => {
-                        info!("{} engine loaded successfully (fallback)", fallback.display_name());
-                        return (engine, true);
-                    }
-                    Err(e) => {
-                        warn!("Failed to load {} model: {}", fallback.display_name(), e);
-                    }
-                }
-            }
-        }
-
-        // Nothing worked
-        (engine::DynamicEngine::new(engine::EngineBackend::OpenVINO), false)
-    }
-
-    if !engine_loaded {
-        warn!("No model loaded. Using mock transcription.");
-    } else {
-        info!("Using {} backend", backend.name());
-    }
+    // Models are loaded in the background once the window is up
+    // (no engine in read-only mode: nothing can be transcribed)
+    let load_in_background = !storage::is_read_only();
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
@@ -287,38 +350,204 @@ pub fn run() {
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .plugin(tauri_plugin_clipboard_manager::init())
         .manage(AudioState(audio::AudioCapture::new()))
-        .manage(EngineState(Mutex::new(backend)))
+        .manage(EngineState(Mutex::new(engine::DynamicEngine::new(
+            engine::EngineBackend::OpenVINO,
+        ))))
+        .manage(BatchEngineState(Mutex::new(engine::DynamicEngine::new(
+            engine::EngineBackend::OpenVINO,
+        ))))
+        .manage(EngineStatusState(engine::EngineReadiness::new(if load_in_background {
+            engine::EngineStatus::Loading
+        } else {
+            engine::EngineStatus::Unavailable
+        })))
         .manage(ModelPathState(model_base_path))
+        .setup(move |app| {
+            // Tell the UI when the audio thread had to be restarted
+            let handle = app.handle().clone();
+            app.state::<AudioState>().0.set_reset_handler(move |reset| {
+                let _ = handle.emit("audio-reset", reset);
+            });
+            let handle = app.handle().clone();
+            app.state::<AudioState>().0.set_limit_handler(move |limit| {
+                let _ = handle.emit("recording-limit", limit);
+            });
+            // Recordings left silent are stopped and transcribed without the UI
+            let handle = app.handle().clone();
+            app.state::<AudioState>().0.set_auto_stop_handler(move |silence_ms| {
+                tauri::async_runtime::spawn(commands::auto_stop_recording(handle.clone(), silence_ms));
+            });
+
+            if let Ok(settings) = storage::with_db(storage::get_settings) {
+                if let Err(e) = dictation::register_push_to_talk(app.handle(), &settings.shortcuts.push_to_talk) {
+                    warn!("Push-to-talk disabled: {}", e);
+                }
+            }
+
+            if load_in_background {
+                let handle = app.handle().clone();
+                std::thread::spawn(move || {
+                    // Model-based VAD when its model is installed
+                    if let Err(e) = audio::silero::load(&handle.state::<ModelPathState>().0) {
+                        info!("Energy VAD in use: {}", e);
+                    }
+                    let (backend, engine_loaded) = load_saved_backend(&saved_backend, openvino_ok);
+                    let engine_state = handle.state::<EngineState>();
+                    let mut engine = engine_state.0.lock();
+                    *engine = backend;
+
+                    let status = if engine_loaded {
+                        handle.state::<EngineStatusState>().0.set_ready(&engine);
+                        engine::EngineStatus::Ready
+                    } else {
+                        jobs::report_failure(
+                            &handle,
+                            storage::JobKind::ModelLoad,
+                            &error::AppError::NotFound("No transcription model could be loaded".into()),
+                            Some(&saved_backend),
+                        );
+                        handle.state::<EngineStatusState>().0.set(engine::EngineStatus::Unavailable);
+                        engine::EngineStatus::Unavailable
+                    };
+                    let _ = handle.emit("engine-ready", status);
+
+                    // First transcription without the lazy compilation and page-in cost
+                    // (one queued meanwhile waits for the engine lock)
+                    let warmup = storage::with_db(storage::get_settings).map_or(true, |s| s.warmup_engine);
+                    if engine_loaded && warmup {
+                        match engine.warm_up() {
+                            Ok(ms) => handle.state::<EngineStatusState>().0.set_warmed_up(ms),
+                            Err(e) => warn!("Engine warm-up failed: {}", e),
+                        }
+                    }
+                });
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // Audio commands
             commands::list_audio_devices,
+            commands::get_device_capabilities,
+            commands::list_loopback_devices,
+            commands::check_microphone_permission,
+            commands::request_microphone_permission,
             commands::start_recording,
             commands::stop_recording,
+            commands::start_streaming_transcription,
+            commands::start_rolling_transcription,
             commands::stop_recording_to_wav,
             commands::pause_recording,
             commands::resume_recording,
             commands::get_audio_level,
+            commands::get_audio_waveform,
+            commands::get_recording_status,
+            commands::add_recording_marker,
+            // Accessibility commands
+            commands::announce,
+            commands::type_dictation,
             // Transcription commands
             commands::transcribe_file,
             commands::get_transcription,
+            commands::get_job_status,
+            commands::recover_pending_transcriptions,
+            commands::resume_pending_transcription,
+            commands::finalize_pending_transcription,
+            commands::discard_pending_transcription,
+            commands::estimate_job,
             // Engine commands
             commands::switch_engine_backend,
             commands::get_engine_backend,
+            commands::get_execution_providers,
+            commands::get_engine_status,
+            commands::warmup_engine,
+            commands::unload_engine,
+            commands::list_supported_languages,
+            commands::reload_engine,
             // History commands
             commands::list_transcriptions,
+            commands::get_activity_calendar,
+            commands::search_transcriptions,
+            commands::get_transcription_segments,
+            commands::get_transcription_text,
+            commands::import_transcript_json,
             commands::delete_transcription,
+            commands::get_recording_audio_path,
+            commands::get_revision_history,
+            commands::revert_to_revision,
+            commands::save_draft,
+            commands::get_text_draft,
+            commands::discard_text_draft,
+            commands::update_transcription_metadata,
+            commands::translate_transcription,
+            commands::summarize_transcription,
+            commands::delete_recording_audio,
+            commands::trash_transcription,
+            commands::restore_transcription,
+            commands::list_trash,
+            commands::purge_trash,
             commands::delete_all_transcriptions,
             commands::update_transcription_text,
+            // Tag and folder commands
+            commands::list_tags,
+            commands::add_tag,
+            commands::delete_tag,
+            commands::tag_transcription,
+            commands::untag_transcription,
+            commands::list_by_tag,
+            commands::set_transcription_folder,
+            commands::list_folders,
+            commands::list_by_folder,
+            // Profile commands
+            commands::list_profiles,
+            commands::create_profile,
+            commands::switch_profile,
             // Settings commands
             commands::get_settings,
             commands::update_settings,
+            commands::get_app_mode,
+            commands::move_database,
+            commands::get_power_source,
+            commands::export_preset,
+            commands::import_preset,
+            commands::export_database,
+            commands::import_database,
+            commands::list_decoding_profiles,
+            commands::save_decoding_profile,
+            // Language pack commands
+            commands::list_language_packs,
+            commands::download_language_pack,
+            commands::set_language_pack_enabled,
+            // Model download commands
+            commands::list_available_models,
+            commands::download_model,
+            commands::cancel_download,
+            commands::get_model_status,
+            commands::get_model_cache_info,
+            commands::clear_model_cache,
+            // Support commands
+            commands::create_support_bundle,
+            commands::export_diagnostics_bundle,
+            commands::get_recent_logs,
+            // Background job commands
+            commands::get_recent_errors,
+            commands::sync_now,
+            commands::get_sync_status,
             // Export commands
             commands::export_to_txt,
             commands::export_to_docx,
+            commands::export_to_srt,
+            commands::export_to_vtt,
+            commands::export_to_markdown,
+            commands::export_to_json,
+            commands::verify_export,
+            commands::export_transcript_json,
+            commands::export_archive_html,
             commands::copy_to_clipboard,
             // Test commands - commenter pour désactiver
             commands::test_transcription,
             commands::check_test_audio,
+            commands::evaluate_accuracy,
+            commands::test_audio_loopback,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,159 @@
+//! Live paste (experimental): the dictated text reaches the clipboard as it is confirmed
+//!
+//! For fields WakaScribe can't type into, the clipboard holds the live text
+//! of the recording so far and the user pastes it whenever they like.
+//! Committed sentences go in at once; of the current partial, only the words
+//! two successive decodes agree on, and no more often than `DEBOUNCE`. When
+//! a revision changes words already written (a partial corrected by the
+//! next decode, or by the committed sentence), the clipboard is rewritten
+//! with the corrected text.
+
+use crate::storage::StreamingSegment;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Shortest delay between two clipboard writes from partials
+const DEBOUNCE: Duration = Duration::from_millis(800);
+
+/// Text of the live recording to keep in the clipboard
+#[derive(Debug, Default)]
+pub struct LivePaste {
+    committed: Vec<String>,
+    partial: String,
+    /// Words of `partial` confirmed by the previous decode
+    stable: String,
+    /// Clipboard content as last written
+    written: String,
+    last_write: Option<Instant>,
+}
+
+impl LivePaste {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a live segment; returns the text to put in the clipboard, if it's time
+    pub fn push(&mut self, segment: &StreamingSegment, now: Instant) -> Option<String> {
+        if segment.is_final {
+            self.committed.push(segment.text.clone());
+            self.partial.clear();
+            self.stable.clear();
+        } else {
+            self.stable = stable_prefix(&self.partial, &segment.text);
+            self.partial = segment.text.clone();
+        }
+
+        let due = self.last_write.is_none_or(|t| now.duration_since(t) >= DEBOUNCE);
+        if !segment.is_final && !due {
+            return None;
+        }
+        let text = join(&self.committed, &self.stable);
+        self.write(text, now)
+    }
+
+    /// Recording over: the whole text, last partial included, if not written yet
+    pub fn flush(&mut self, now: Instant) -> Option<String> {
+        let text = join(&self.committed, &self.partial);
+        self.write(text, now)
+    }
+
+    fn write(&mut self, text: String, now: Instant) -> Option<String> {
+        if text == self.written || text.is_empty() {
+            return None;
+        }
+        if !text.starts_with(&self.written) {
+            debug!("Live paste: correcting revised text");
+        }
+        self.written = text.clone();
+        self.last_write = Some(now);
+        Some(text)
+    }
+}
+
+/// Leading words shared by two successive partials
+fn stable_prefix(previous: &str, current: &str) -> String {
+    previous
+        .split_whitespace()
+        .zip(current.split_whitespace())
+        .take_while(|(a, b)| a == b)
+        .map(|(_, b)| b)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn join(committed: &[String], partial: &str) -> String {
+    committed
+        .iter()
+        .map(String::as_str)
+        .chain(std::iter::once(partial))
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str, is_final: bool) -> StreamingSegment {
+        StreamingSegment {
+            text: text.to_string(),
+            is_final,
+            confidence: None,
+            translated_text: None,
+            target_language: None,
+        }
+    }
+
+    #[test]
+    fn test_only_confirmed_words_are_written() {
+        let mut paste = LivePaste::new();
+        let t0 = Instant::now();
+        let later = |ms| t0 + Duration::from_millis(ms);
+
+        // A lone partial confirms nothing
+        assert_eq!(paste.push(&segment("bonjour a", false), t0), None);
+        assert_eq!(
+            paste.push(&segment("bonjour a tous", false), later(1000)),
+            Some("bonjour a".to_string())
+        );
+        // Debounced
+        assert_eq!(paste.push(&segment("bonjour a tous les", false), later(1200)), None);
+        assert_eq!(
+            paste.push(&segment("Bonjour a tous.", true), later(1300)),
+            Some("Bonjour a tous.".to_string())
+        );
+        assert_eq!(
+            paste.flush(later(1400)),
+            None,
+            "nothing new since the committed sentence"
+        );
+    }
+
+    #[test]
+    fn test_revised_partial_is_corrected() {
+        let mut paste = LivePaste::new();
+        let t0 = Instant::now();
+        let later = |ms| t0 + Duration::from_millis(ms);
+
+        paste.push(&segment("il fait", false), t0);
+        assert_eq!(
+            paste.push(&segment("il fait beau", false), later(1000)),
+            Some("il fait".to_string())
+        );
+        // The next decode revises "fait": the clipboard loses it
+        assert_eq!(
+            paste.push(&segment("il faut", false), later(2000)),
+            Some("il".to_string())
+        );
+        assert_eq!(paste.flush(later(2100)), Some("il faut".to_string()));
+    }
+
+    #[test]
+    fn test_stable_prefix() {
+        assert_eq!(stable_prefix("un deux trois", "un deux quatre"), "un deux");
+        assert_eq!(stable_prefix("", "un deux"), "");
+        assert_eq!(stable_prefix("un  deux", "un deux trois"), "un deux");
+    }
+}
@@ -0,0 +1,149 @@
+//! Recent log events kept in memory for the UI
+//!
+//! A tracing layer next to the console and file outputs: each event the
+//! filter lets through is kept with its level, target and fields, the last
+//! `CAPACITY` of them, for `get_recent_logs` and the diagnostics bundle.
+//!
+//! Events quoting what was dictated use `TRANSCRIPT_TARGET`, on a single line
+//! (`{:?}`), so the support bundle can leave them out.
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Events kept
+const CAPACITY: usize = 2000;
+
+/// Target of the events carrying transcript text
+pub const TRANSCRIPT_TARGET: &str = "wakascribe::transcript";
+
+static ENTRIES: Mutex<VecDeque<LogEntry>> = parking_lot::const_mutex(VecDeque::new());
+
+/// Ordered from the most to the least severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<&Level> for LogLevel {
+    fn from(level: &Level) -> Self {
+        match *level {
+            Level::ERROR => LogLevel::Error,
+            Level::WARN => LogLevel::Warn,
+            Level::INFO => LogLevel::Info,
+            Level::DEBUG => LogLevel::Debug,
+            Level::TRACE => LogLevel::Trace,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: LogLevel,
+    /// Module the event comes from
+    pub target: String,
+    pub message: String,
+    /// Other fields of the event
+    pub fields: BTreeMap<String, String>,
+}
+
+/// Layer keeping the events in memory
+pub struct RecentLogs;
+
+impl<S: Subscriber> Layer<S> for RecentLogs {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = EntryVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        push(LogEntry {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            level: metadata.level().into(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+        });
+    }
+}
+
+#[derive(Default)]
+struct EntryVisitor {
+    message: String,
+    fields: BTreeMap<String, String>,
+}
+
+impl Visit for EntryVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record(field, format!("{:?}", value));
+    }
+}
+
+impl EntryVisitor {
+    fn record(&mut self, field: &Field, value: String) {
+        if field.name() == "message" {
+            self.message = value;
+        } else {
+            self.fields.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+fn push(entry: LogEntry) {
+    let mut entries = ENTRIES.lock();
+    if entries.len() == CAPACITY {
+        entries.pop_front();
+    }
+    entries.push_back(entry);
+}
+
+/// The last `limit` events at `level` or more severe, oldest first
+pub fn recent(level: LogLevel, limit: usize) -> Vec<LogEntry> {
+    let entries = ENTRIES.lock();
+    let mut recent: Vec<_> = entries.iter().rev().filter(|e| e.level <= level).take(limit).cloned().collect();
+    recent.reverse();
+    recent
+}
+
+/// Whether a line of the log file comes from a `TRANSCRIPT_TARGET` event
+pub fn is_transcript_line(line: &str) -> bool {
+    line.contains(" wakascribe::transcript:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::prelude::*;
+
+    #[test]
+    fn test_events_kept_and_filtered() {
+        let subscriber = tracing_subscriber::registry().with(RecentLogs);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(job = "file", "Transcription started");
+            tracing::warn!("Disk almost full: {}%", 95);
+            tracing::debug!("Chunk 1/3");
+        });
+
+        let warnings = recent(LogLevel::Warn, 10);
+        let warning = warnings.iter().find(|e| e.message == "Disk almost full: 95%").unwrap();
+        assert_eq!(warning.level, LogLevel::Warn);
+
+        let all = recent(LogLevel::Trace, CAPACITY);
+        let started = all.iter().find(|e| e.message == "Transcription started").unwrap();
+        assert_eq!(started.fields.get("job").map(String::as_str), Some("file"));
+        assert!(!warnings.iter().any(|e| e.level == LogLevel::Info));
+    }
+}
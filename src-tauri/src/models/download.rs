@@ -0,0 +1,287 @@
+//! Resumable, verified download of model files
+//!
+//! A file is written as `<name>.part` and continued with an HTTP range
+//! request when the transfer stopped. Once complete its SHA-256 is checked
+//! and it is renamed to `<name>`: files present under their final name are
+//! verified and skipped by the next attempt.
+
+use super::ModelFile;
+use crate::error::{AppError, Result};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{info, warn};
+
+/// Read buffer of a transfer
+const BUFFER_SIZE: usize = 256 * 1024;
+
+/// Progress is reported every this many bytes
+const PROGRESS_STEP: u64 = 1024 * 1024;
+
+/// Bytes already in `staging` for `files` (complete and partial)
+pub(super) fn staged_bytes(staging: &Path, files: &[ModelFile]) -> u64 {
+    files
+        .iter()
+        .map(|file| {
+            let done = staging.join(&file.name);
+            fs::metadata(&done)
+                .or_else(|_| fs::metadata(part_path(&done)))
+                .map(|m| m.len().min(file.size))
+                .unwrap_or(0)
+        })
+        .sum()
+}
+
+/// Download every file of a model into `staging`
+///
+/// `on_progress` receives the total bytes present for the model so far.
+pub(super) fn download_files(
+    staging: &Path,
+    files: &[ModelFile],
+    cancel: &AtomicBool,
+    mut on_progress: impl FnMut(u64),
+) -> Result<()> {
+    let mut done_bytes = 0u64;
+    for file in files {
+        let target = staging.join(&file.name);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        if target.exists() {
+            if sha256_of(&target)? == file.sha256.to_ascii_lowercase() {
+                done_bytes += file.size;
+                on_progress(done_bytes);
+                continue;
+            }
+            warn!("Staged {} is corrupt, downloading it again", file.name);
+            fs::remove_file(&target)?;
+        }
+
+        let part = part_path(&target);
+        download_file(&file.url, &part, cancel, |written| on_progress(done_bytes + written))?;
+
+        let digest = sha256_of(&part)?;
+        if digest != file.sha256.to_ascii_lowercase() {
+            // A resumed file may have been changed on the server: start it over next time
+            fs::remove_file(&part)?;
+            return Err(AppError::Network(format!(
+                "Checksum mismatch for {} (expected {}, got {})",
+                file.name, file.sha256, digest
+            )));
+        }
+        fs::rename(&part, &target)?;
+        done_bytes += file.size;
+        on_progress(done_bytes);
+    }
+    Ok(())
+}
+
+/// Download `url` into `part`, continuing after the bytes already there
+///
+/// `on_progress` receives the size of `part` as it grows.
+fn download_file(
+    url: &str,
+    part: &Path,
+    cancel: &AtomicBool,
+    mut on_progress: impl FnMut(u64),
+) -> Result<()> {
+    let mut written = fs::metadata(part).map(|m| m.len()).unwrap_or(0);
+
+    let mut request = ureq::get(url);
+    if written > 0 {
+        request = request.set("Range", &format!("bytes={}-", written));
+    }
+    let response = match request.call() {
+        Ok(response) => response,
+        // The part already holds the whole file
+        Err(ureq::Error::Status(416, _)) if written > 0 => return Ok(()),
+        Err(e) => return Err(AppError::Network(format!("{}: {}", url, e))),
+    };
+
+    let mut output = if response.status() == 206 {
+        info!("Resuming {} at {} bytes", url, written);
+        OpenOptions::new().append(true).open(part)?
+    } else {
+        // No range support: start over
+        written = 0;
+        File::create(part)?
+    };
+
+    let mut reader = response.into_reader();
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+    let mut reported = written;
+    loop {
+        if cancel.load(Ordering::SeqCst) {
+            return Err(AppError::InvalidState("Download cancelled".into()));
+        }
+        let read = reader
+            .read(&mut buffer)
+            .map_err(|e| AppError::Network(format!("{}: {}", url, e)))?;
+        if read == 0 {
+            break;
+        }
+        output.write_all(&buffer[..read])?;
+        written += read as u64;
+        if written - reported >= PROGRESS_STEP {
+            reported = written;
+            on_progress(written);
+        }
+    }
+    output.sync_all()?;
+    on_progress(written);
+    Ok(())
+}
+
+fn part_path(target: &Path) -> PathBuf {
+    let mut name = target.as_os_str().to_owned();
+    name.push(".part");
+    PathBuf::from(name)
+}
+
+fn sha256_of(path: &Path) -> Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::thread;
+
+    const BODY: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    /// Serve `BODY` to `connections` requests, honoring `Range: bytes=N-`;
+    /// the handle returns the range start of each request
+    fn serve(connections: usize) -> (String, thread::JoinHandle<Vec<Option<usize>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/model.bin", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut ranges = Vec::new();
+            for stream in listener.incoming().take(connections) {
+                let mut stream = stream.unwrap();
+                let mut range = None;
+                for line in BufReader::new(&stream).lines() {
+                    let line = line.unwrap();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some(from) = line.to_ascii_lowercase().strip_prefix("range: bytes=") {
+                        range = from.trim_end_matches('-').parse().ok();
+                    }
+                }
+                ranges.push(range);
+                let head = match range {
+                    Some(from) => format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/{}\r\nContent-Length: {}\r\n",
+                        from,
+                        BODY.len() - 1,
+                        BODY.len(),
+                        BODY.len() - from
+                    ),
+                    None => format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n", BODY.len()),
+                };
+                // The client may hang up early (cancelled)
+                let _ = stream.write_all(format!("{}Connection: close\r\n\r\n", head).as_bytes());
+                let _ = stream.write_all(&BODY[range.unwrap_or(0)..]);
+            }
+            ranges
+        });
+        (url, handle)
+    }
+
+    fn staging(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("wakascribe-download-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn file(url: String, sha256: String) -> ModelFile {
+        ModelFile {
+            name: "model.bin".into(),
+            url,
+            sha256,
+            size: BODY.len() as u64,
+        }
+    }
+
+    fn body_sha256() -> String {
+        format!("{:x}", Sha256::digest(BODY))
+    }
+
+    #[test]
+    fn test_resumes_a_partial_file() {
+        let dir = staging("resume");
+        fs::write(dir.join("model.bin.part"), &BODY[..10]).unwrap();
+        assert_eq!(staged_bytes(&dir, &[file(String::new(), body_sha256())]), 10);
+
+        let (url, server) = serve(1);
+        let mut progress = Vec::new();
+        download_files(&dir, &[file(url, body_sha256())], &AtomicBool::new(false), |b| progress.push(b)).unwrap();
+
+        assert_eq!(server.join().unwrap(), [Some(10)]);
+        assert_eq!(fs::read(dir.join("model.bin")).unwrap(), BODY);
+        assert!(!dir.join("model.bin.part").exists());
+        assert_eq!(progress.last(), Some(&(BODY.len() as u64)));
+
+        // Verified and skipped by the next attempt: no request
+        download_files(&dir, &[file("http://127.0.0.1:9/".into(), body_sha256())], &AtomicBool::new(false), |_| {})
+            .unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_checksum_mismatch_drops_the_partial_file() {
+        let dir = staging("mismatch");
+        fs::write(dir.join("model.bin.part"), b"0123XXXXXX").unwrap();
+
+        let (url, server) = serve(1);
+        let result = download_files(&dir, &[file(url, body_sha256())], &AtomicBool::new(false), |_| {});
+        assert!(matches!(result, Err(AppError::Network(_))));
+        server.join().unwrap();
+        // Started over next time, never installed
+        assert!(!dir.join("model.bin.part").exists());
+        assert!(!dir.join("model.bin").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_staged_file_is_downloaded_again() {
+        let dir = staging("corrupt");
+        fs::write(dir.join("model.bin"), b"corrupt").unwrap();
+
+        let (url, server) = serve(1);
+        download_files(&dir, &[file(url, body_sha256())], &AtomicBool::new(false), |_| {}).unwrap();
+        assert_eq!(server.join().unwrap(), [None]);
+        assert_eq!(fs::read(dir.join("model.bin")).unwrap(), BODY);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cancel_keeps_the_partial_file() {
+        let dir = staging("cancel");
+        fs::write(dir.join("model.bin.part"), &BODY[..5]).unwrap();
+
+        let (url, server) = serve(1);
+        let result = download_files(&dir, &[file(url, body_sha256())], &AtomicBool::new(true), |_| {});
+        assert!(matches!(result, Err(AppError::InvalidState(_))));
+        server.join().unwrap();
+        // Resumed by the next download_model
+        assert_eq!(fs::read(dir.join("model.bin.part")).unwrap(), &BODY[..5]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
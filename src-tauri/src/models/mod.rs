@@ -0,0 +1,369 @@
+//! Download of speech models into the app data models directory
+//!
+//! Models are listed in a catalog (`models.json`, URL set in the settings):
+//! each entry targets one backend (OpenVINO IR, ONNX, CoreML) and lists its
+//! files with their size and SHA-256. Files are downloaded into
+//! `models/.downloads/<id>/`, resumed from where they stopped after a
+//! cancellation or a network error, and verified before the model replaces
+//! the backend's directory (`models/<backend>/`), where the engine loads it.
+
+mod download;
+
+use crate::error::{AppError, Result};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+use tracing::info;
+
+/// Highest catalog format this build understands
+const CATALOG_FORMAT_VERSION: u32 = 1;
+
+/// Largest catalog accepted
+const MAX_CATALOG_BYTES: u64 = 1024 * 1024;
+
+/// Written next to the model files once installed
+const INSTALLED_FILE: &str = "model.json";
+
+/// Staging area of downloads in progress or paused
+const DOWNLOADS_DIR: &str = ".downloads";
+
+/// Backends a catalog entry may target (`EngineBackend::model_subdir`)
+const BACKENDS: [&str; 3] = ["openvino", "onnxruntime", "coreml"];
+
+/// Model catalog (`models.json`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelCatalog {
+    /// Catalog format version
+    pub format: u32,
+    pub models: Vec<ModelEntry>,
+}
+
+/// Downloadable model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelEntry {
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Backend directory the model is installed into ("openvino", "onnxruntime", "coreml")
+    pub backend: String,
+    /// Model version, increased by the publisher on each update
+    pub version: u32,
+    pub files: Vec<ModelFile>,
+}
+
+/// File of a model, relative to the backend directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelFile {
+    /// Relative path ("encoder-model.onnx", "Encoder.mlmodelc/weights/weight.bin")
+    pub name: String,
+    pub url: String,
+    /// Lowercase hex SHA-256 of the whole file
+    pub sha256: String,
+    pub size: u64,
+}
+
+impl ModelEntry {
+    pub fn size(&self) -> u64 {
+        self.files.iter().map(|f| f.size).sum()
+    }
+}
+
+/// Where a model stands on this machine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelState {
+    NotInstalled,
+    Downloading,
+    /// Cancelled or interrupted; the next download resumes it
+    Paused,
+    Installed,
+    /// An older version is installed
+    UpdateAvailable,
+    Failed,
+}
+
+/// Download and installation status of a model (also the progress event payload)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelStatus {
+    pub model_id: String,
+    pub state: ModelState,
+    pub downloaded_bytes: u64,
+    pub total_bytes: u64,
+    pub installed_version: Option<u32>,
+    pub error: Option<String>,
+}
+
+/// Catalog entry with its status, as shown in the UI
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailableModel {
+    #[serde(flatten)]
+    pub entry: ModelEntry,
+    pub size: u64,
+    pub status: ModelStatus,
+}
+
+/// Download running (or last failed) for a model
+struct Download {
+    cancel: Arc<AtomicBool>,
+    status: ModelStatus,
+}
+
+static DOWNLOADS: Lazy<Mutex<HashMap<String, Download>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Last catalog fetched, with its URL
+static CATALOG: Lazy<Mutex<Option<(String, ModelCatalog)>>> = Lazy::new(|| Mutex::new(None));
+
+/// Root of the installed models (`<app data>/com.wakascribe.app/models`)
+pub fn models_dir() -> Option<PathBuf> {
+    crate::app_data_dir().map(|p| p.join("com.wakascribe.app").join("models"))
+}
+
+fn require_models_dir() -> Result<PathBuf> {
+    models_dir().ok_or_else(|| AppError::NotFound("App data directory not found".into()))
+}
+
+/// Catalog at `url`, fetched again when `refresh` or when the URL changed
+pub fn catalog(url: &str, refresh: bool) -> Result<ModelCatalog> {
+    if !refresh {
+        if let Some((cached_url, catalog)) = CATALOG.lock().as_ref() {
+            if cached_url == url {
+                return Ok(catalog.clone());
+            }
+        }
+    }
+    let catalog = fetch_catalog(url)?;
+    *CATALOG.lock() = Some((url.to_string(), catalog.clone()));
+    Ok(catalog)
+}
+
+/// Entry `model_id` of the catalog at `url`
+pub fn find_model(url: &str, model_id: &str) -> Result<ModelEntry> {
+    catalog(url, false)?
+        .models
+        .into_iter()
+        .find(|m| m.id == model_id)
+        .ok_or_else(|| AppError::NotFound(format!("Model not in catalog: {}", model_id)))
+}
+
+/// Download and validate the catalog
+fn fetch_catalog(url: &str) -> Result<ModelCatalog> {
+    let response = ureq::get(url)
+        .call()
+        .map_err(|e| AppError::Network(format!("{}: {}", url, e)))?;
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_CATALOG_BYTES + 1)
+        .read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > MAX_CATALOG_BYTES {
+        return Err(AppError::Network(format!("{}: catalog too large", url)));
+    }
+
+    let catalog: ModelCatalog = serde_json::from_slice(&bytes)?;
+    if catalog.format > CATALOG_FORMAT_VERSION {
+        return Err(AppError::InvalidInput(format!(
+            "Model catalog format {} is newer than supported ({})",
+            catalog.format, CATALOG_FORMAT_VERSION
+        )));
+    }
+    for entry in &catalog.models {
+        validate(entry)?;
+    }
+    Ok(catalog)
+}
+
+/// Status of `entry`: running or failed download first, then what is on disk
+pub fn status(entry: &ModelEntry) -> ModelStatus {
+    if let Some(download) = DOWNLOADS.lock().get(&entry.id) {
+        return download.status.clone();
+    }
+    disk_status(entry)
+}
+
+fn disk_status(entry: &ModelEntry) -> ModelStatus {
+    let mut status = ModelStatus {
+        model_id: entry.id.clone(),
+        state: ModelState::NotInstalled,
+        downloaded_bytes: 0,
+        total_bytes: entry.size(),
+        installed_version: None,
+        error: None,
+    };
+    let Some(dir) = models_dir() else {
+        return status;
+    };
+
+    if let Some(installed) = read_installed(&dir.join(&entry.backend)).filter(|i| i.id == entry.id) {
+        status.installed_version = Some(installed.version);
+        status.downloaded_bytes = status.total_bytes;
+        status.state = if installed.version < entry.version {
+            ModelState::UpdateAvailable
+        } else {
+            ModelState::Installed
+        };
+        return status;
+    }
+
+    let staging = dir.join(DOWNLOADS_DIR).join(&entry.id);
+    if staging.exists() {
+        status.downloaded_bytes = download::staged_bytes(&staging, &entry.files);
+        status.state = ModelState::Paused;
+    }
+    status
+}
+
+/// Download `entry` (resuming a previous attempt) and install it
+///
+/// Emits `model-download-progress` with the status as bytes arrive. The
+/// engine picks the new files up on its next reload.
+pub fn download_model(app: &AppHandle, entry: &ModelEntry) -> Result<ModelStatus> {
+    validate(entry)?;
+    let dir = require_models_dir()?;
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+        let mut downloads = DOWNLOADS.lock();
+        if downloads
+            .get(&entry.id)
+            .is_some_and(|d| d.status.state == ModelState::Downloading)
+        {
+            return Err(AppError::InvalidState(format!("{} is already downloading", entry.id)));
+        }
+        // A past failure is forgotten when a new attempt starts
+        let mut status = disk_status(entry);
+        status.state = ModelState::Downloading;
+        status.error = None;
+        let _ = app.emit("model-download-progress", &status);
+        downloads.insert(
+            entry.id.clone(),
+            Download {
+                cancel: Arc::clone(&cancel),
+                status,
+            },
+        );
+    }
+
+    info!("Downloading model {} v{} ({} bytes)", entry.id, entry.version, entry.size());
+    let staging = dir.join(DOWNLOADS_DIR).join(&entry.id);
+    let result = download::download_files(&staging, &entry.files, &cancel, |downloaded| {
+        update(app, &entry.id, |s| s.downloaded_bytes = downloaded);
+    })
+    .and_then(|_| install(&dir, &staging, entry));
+
+    match result {
+        Ok(()) => {
+            info!("Installed model {} into {:?}", entry.id, dir.join(&entry.backend));
+            DOWNLOADS.lock().remove(&entry.id);
+            let status = disk_status(entry);
+            let _ = app.emit("model-download-progress", &status);
+            Ok(status)
+        }
+        Err(_) if cancel.load(Ordering::SeqCst) => {
+            info!("Download of {} cancelled", entry.id);
+            // The paused download is read back from disk from now on
+            DOWNLOADS.lock().remove(&entry.id);
+            let status = disk_status(entry);
+            let _ = app.emit("model-download-progress", &status);
+            Ok(status)
+        }
+        Err(e) => {
+            update(app, &entry.id, |s| {
+                s.state = ModelState::Failed;
+                s.error = Some(e.to_string());
+            });
+            Err(e)
+        }
+    }
+}
+
+/// Ask a running download to stop; the downloaded part is kept for a resume
+pub fn cancel_download(model_id: &str) -> Result<()> {
+    let downloads = DOWNLOADS.lock();
+    let download = downloads
+        .get(model_id)
+        .filter(|d| d.status.state == ModelState::Downloading)
+        .ok_or_else(|| AppError::InvalidState(format!("{} is not downloading", model_id)))?;
+    download.cancel.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+fn update(app: &AppHandle, model_id: &str, change: impl FnOnce(&mut ModelStatus)) {
+    let status = {
+        let mut downloads = DOWNLOADS.lock();
+        let Some(download) = downloads.get_mut(model_id) else {
+            return;
+        };
+        change(&mut download.status);
+        download.status.clone()
+    };
+    let _ = app.emit("model-download-progress", &status);
+}
+
+/// Replace the backend directory with the verified staging directory
+fn install(dir: &Path, staging: &Path, entry: &ModelEntry) -> Result<()> {
+    fs::write(staging.join(INSTALLED_FILE), serde_json::to_vec_pretty(entry)?)?;
+
+    let target = dir.join(&entry.backend);
+    let previous = dir.join(format!("{}.previous", entry.backend));
+    if previous.exists() {
+        fs::remove_dir_all(&previous)?;
+    }
+    if target.exists() {
+        fs::rename(&target, &previous)?;
+    }
+    if let Err(e) = fs::rename(staging, &target) {
+        // Put the old model back rather than leave the backend without one
+        if previous.exists() {
+            let _ = fs::rename(&previous, &target);
+        }
+        return Err(e.into());
+    }
+    if previous.exists() {
+        fs::remove_dir_all(&previous)?;
+    }
+    Ok(())
+}
+
+fn read_installed(backend_dir: &Path) -> Option<ModelEntry> {
+    let text = fs::read_to_string(backend_dir.join(INSTALLED_FILE)).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+fn validate(entry: &ModelEntry) -> Result<()> {
+    let is_plain_name = |s: &str| {
+        !s.is_empty()
+            && !s.starts_with('.')
+            && s.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+
+    if !is_plain_name(&entry.id) {
+        return Err(AppError::InvalidInput(format!("Invalid model id: {:?}", entry.id)));
+    }
+    if !BACKENDS.contains(&entry.backend.as_str()) {
+        return Err(AppError::InvalidInput(format!("Unknown backend: {:?}", entry.backend)));
+    }
+    if entry.files.is_empty() {
+        return Err(AppError::InvalidInput(format!("Model {} has no files", entry.id)));
+    }
+    for file in &entry.files {
+        if !file.name.split('/').all(is_plain_name) || file.name == INSTALLED_FILE {
+            return Err(AppError::InvalidInput(format!("Invalid model file name: {:?}", file.name)));
+        }
+        if file.sha256.len() != 64 || !file.sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(AppError::InvalidInput(format!("Invalid SHA-256 for {}", file.name)));
+        }
+    }
+    Ok(())
+}
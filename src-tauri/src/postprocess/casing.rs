@@ -0,0 +1,109 @@
+//! Casing of acronyms and proper nouns from the user's vocabulary
+//!
+//! The tokenizer emits lowercase pieces: "sncf", "s n c f", "waka scribe".
+//! Each vocabulary entry with capitals (SNCF, GPU, WakaScribe) is matched
+//! case-insensitively, whole words only, also when split at its capitals
+//! or, for an acronym, letter by letter; the match is replaced by the
+//! entry as written.
+
+use regex::{NoExpand, Regex};
+use tracing::warn;
+
+/// Longest all-caps entry spelled letter by letter ("s n c f")
+const MAX_SPELLED_ACRONYM: usize = 6;
+
+/// Render the vocabulary entries found in `text` with their own casing
+pub fn apply_casing(text: &str, vocabulary: &[String]) -> String {
+    let mut terms: Vec<&str> = vocabulary
+        .iter()
+        .map(|t| t.trim())
+        .filter(|t| t.chars().any(char::is_uppercase))
+        .collect();
+    // "Visual Studio Code" before "Visual Studio"
+    terms.sort_by_key(|t| std::cmp::Reverse(t.chars().count()));
+
+    let mut result = text.to_string();
+    for term in terms {
+        match Regex::new(&pattern(term)) {
+            Ok(re) => result = re.replace_all(&result, NoExpand(term)).into_owned(),
+            Err(e) => warn!("Invalid vocabulary entry '{}': {}", term, e),
+        }
+    }
+    result
+}
+
+/// Case-insensitive pattern of a term, its pieces optionally split
+fn pattern(term: &str) -> String {
+    let words: Vec<String> = term
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|w| !w.is_empty())
+        .map(|word| {
+            pieces(word)
+                .iter()
+                .map(|p| regex::escape(p))
+                .collect::<Vec<_>>()
+                .join(r"[\s.]?")
+        })
+        .collect();
+    format!(r"(?i)\b{}\b", words.join(r"[\s\-]+"))
+}
+
+/// Where the tokenizer may split a word: every letter of a short acronym,
+/// else before each capital that follows a lowercase letter
+fn pieces(word: &str) -> Vec<String> {
+    let letters = word.chars().count();
+    if letters > 1
+        && letters <= MAX_SPELLED_ACRONYM
+        && word.chars().all(|c| c.is_alphabetic() && c.is_uppercase())
+    {
+        return word.chars().map(String::from).collect();
+    }
+
+    let mut pieces: Vec<String> = Vec::new();
+    let mut previous_lower = false;
+    for c in word.chars() {
+        match pieces.last_mut() {
+            Some(piece) if !(c.is_uppercase() && previous_lower) => piece.push(c),
+            _ => pieces.push(c.to_string()),
+        }
+        previous_lower = c.is_lowercase();
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vocabulary(terms: &[&str]) -> Vec<String> {
+        terms.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_acronyms_and_names_in_mixed_sentences() {
+        let vocab = vocabulary(&["SNCF", "GPU", "WakaScribe", "Visual Studio Code", "Dupont"]);
+        assert_eq!(
+            apply_casing("le train de la sncf est en retard, said mr dupont", &vocab),
+            "le train de la SNCF est en retard, said mr Dupont"
+        );
+        assert_eq!(
+            apply_casing("j'ai lance waka scribe sur le gpu, it runs on the Gpu now", &vocab),
+            "j'ai lance WakaScribe sur le GPU, it runs on the GPU now"
+        );
+        assert_eq!(
+            apply_casing("la s n c f et la s.n.c.f. ouvrent visual studio code", &vocab),
+            "la SNCF et la SNCF. ouvrent Visual Studio Code"
+        );
+    }
+
+    #[test]
+    fn test_whole_words_only() {
+        let vocab = vocabulary(&["GPU", "Eva", "api"]);
+        assert_eq!(
+            apply_casing("les gpus et le gpu, l'evaluation d'eva", &vocab),
+            "les gpus et le GPU, l'evaluation d'Eva"
+        );
+        // Lowercase entries carry no casing to restore
+        assert_eq!(apply_casing("une API", &vocab), "une API");
+    }
+}
@@ -0,0 +1,93 @@
+//! Interview mode: format a transcript as Q:/A: pairs
+//!
+//! Pitch is not available at this stage, so questions are detected from the
+//! punctuation and the opening words of each sentence. The sentences that
+//! follow a question, up to the next one, form its answer. Speaker turns will
+//! refine the split once diarization is available.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static SENTENCE: Lazy<Regex> = Lazy::new(|| Regex::new(r"[^.!?\n]+[.!?]*").unwrap());
+
+/// Sentence openings that make a question even when the "?" was not transcribed
+static INTERROGATIVE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^(est-ce que|est-ce qu'|qu'est-ce|pourquoi|comment|quand|où|combien|quel|quelle|quels|quelles|lequel|laquelle|pouvez-vous|pourriez-vous|avez-vous|êtes-vous|what|why|how|when|where|who|which|do you|did you|can you|could you|would you|have you|are you|is it|is there)\b",
+    )
+    .unwrap()
+});
+
+/// Whether `sentence` reads as a question
+pub fn is_question(sentence: &str) -> bool {
+    let sentence = sentence.trim();
+    sentence.ends_with('?') || INTERROGATIVE.is_match(sentence)
+}
+
+/// Rewrite `text` as "Q: ..." / "A: ..." paragraphs
+///
+/// Text before the first question is kept as an introduction. Returns the
+/// text unchanged when it contains no question.
+pub fn format_interview(text: &str) -> String {
+    let sentences: Vec<&str> = SENTENCE
+        .find_iter(text)
+        .map(|m| m.as_str().trim())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if !sentences.iter().any(|s| is_question(s)) {
+        return text.to_string();
+    }
+
+    let mut blocks: Vec<(Option<&str>, Vec<String>)> = Vec::new();
+    let mut seen_question = false;
+    for sentence in sentences {
+        let question = is_question(sentence);
+        let sentence = if question && !sentence.ends_with('?') {
+            format!("{}?", sentence.trim_end_matches(['.', '!']))
+        } else {
+            sentence.to_string()
+        };
+
+        seen_question |= question;
+        let label = match (question, seen_question) {
+            (true, _) => Some("Q"),
+            (false, true) => Some("A"),
+            (false, false) => None,
+        };
+        // Consecutive questions (or answers) stay in the same block
+        match blocks.last_mut() {
+            Some((last, sentences)) if *last == label => sentences.push(sentence),
+            _ => blocks.push((label, vec![sentence])),
+        }
+    }
+
+    blocks
+        .into_iter()
+        .map(|(label, sentences)| match label {
+            Some(label) => format!("{}: {}", label, sentences.join(" ")),
+            None => sentences.join(" "),
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_question_answer_pairs() {
+        assert_eq!(
+            format_interview(
+                "Bonjour. Merci d'être là. Comment avez-vous commencé ? J'ai commencé en 2010. C'était difficile. Pourquoi ce métier. Par passion."
+            ),
+            "Bonjour. Merci d'être là.\n\nQ: Comment avez-vous commencé ?\n\nA: J'ai commencé en 2010. C'était difficile.\n\nQ: Pourquoi ce métier?\n\nA: Par passion."
+        );
+    }
+
+    #[test]
+    fn test_text_without_questions_is_unchanged() {
+        let text = "Une simple note. Rien à structurer.";
+        assert_eq!(format_interview(text), text);
+    }
+}
@@ -0,0 +1,199 @@
+//! Downloadable per-language post-processing resources
+//!
+//! A language pack is a small manifest (`pack.json`) plus optional assets
+//! (JSON tables, ONNX punctuation models) stored under
+//! `<app data>/com.wakascribe.app/language_packs/<language>/`. Packs are
+//! downloaded from a URL pointing at the manifest; assets are fetched from the
+//! same base URL. Adding a language then only needs a new pack, not a new build.
+
+use crate::error::{AppError, Result};
+use crate::storage::SubstitutionRule;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+/// Manifest file name inside a pack directory
+const MANIFEST_FILE: &str = "pack.json";
+
+/// Highest manifest format this build understands
+const PACK_FORMAT_VERSION: u32 = 1;
+
+/// Maximum size of a downloaded file (packs are meant to stay small)
+const MAX_DOWNLOAD_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Language pack manifest (`pack.json`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguagePack {
+    /// Manifest format version
+    pub format: u32,
+    /// Language code matching `Transcription::language` ("fr", "es"...)
+    pub language: String,
+    /// Display name
+    pub name: String,
+    /// Pack version, increased by the publisher on each update
+    pub version: u32,
+    /// Replacements applied before the user's own rules
+    #[serde(default)]
+    pub substitution_rules: Vec<SubstitutionRule>,
+    /// Extra files next to the manifest (relative names)
+    #[serde(default)]
+    pub assets: Vec<String>,
+}
+
+/// Installed pack as shown in the UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguagePackInfo {
+    pub language: String,
+    pub name: String,
+    pub version: u32,
+    pub assets: Vec<String>,
+    pub enabled: bool,
+}
+
+/// Root directory of installed packs (under app data)
+pub fn packs_dir() -> Option<PathBuf> {
+    crate::app_data_dir().map(|p| p.join("com.wakascribe.app").join("language_packs"))
+}
+
+fn pack_dir(language: &str) -> Result<PathBuf> {
+    packs_dir()
+        .map(|p| p.join(language))
+        .ok_or_else(|| AppError::NotFound("App data directory not found".into()))
+}
+
+/// Installed packs, with their enabled state
+pub fn list_packs(enabled: &[String]) -> Vec<LanguagePackInfo> {
+    let Some(dir) = packs_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut packs: Vec<LanguagePackInfo> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| read_manifest(&e.path().join(MANIFEST_FILE)).ok())
+        .map(|pack| LanguagePackInfo {
+            enabled: enabled.contains(&pack.language),
+            language: pack.language,
+            name: pack.name,
+            version: pack.version,
+            assets: pack.assets,
+        })
+        .collect();
+    packs.sort_by(|a, b| a.language.cmp(&b.language));
+    packs
+}
+
+/// Load an installed pack
+pub fn load_pack(language: &str) -> Result<LanguagePack> {
+    read_manifest(&pack_dir(language)?.join(MANIFEST_FILE))
+}
+
+/// Download a pack from the URL of its manifest and install it
+///
+/// Files are written to a temporary directory first, so a failed download
+/// leaves the previously installed version untouched.
+pub fn download_pack(manifest_url: &str) -> Result<LanguagePack> {
+    info!("Downloading language pack from {}", manifest_url);
+
+    let manifest_bytes = fetch(manifest_url)?;
+    let pack: LanguagePack = serde_json::from_slice(&manifest_bytes)?;
+    validate(&pack)?;
+
+    let target = pack_dir(&pack.language)?;
+    let staging = target.with_extension("download");
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    fs::create_dir_all(&staging)?;
+
+    let base_url = manifest_url
+        .rsplit_once('/')
+        .map(|(base, _)| base)
+        .unwrap_or(manifest_url);
+    for asset in &pack.assets {
+        let bytes = fetch(&format!("{}/{}", base_url, asset))?;
+        fs::write(staging.join(asset), bytes)?;
+    }
+    fs::write(staging.join(MANIFEST_FILE), &manifest_bytes)?;
+
+    if target.exists() {
+        fs::remove_dir_all(&target)?;
+    }
+    fs::rename(&staging, &target)?;
+
+    info!(
+        "Installed language pack '{}' v{} ({} assets)",
+        pack.language,
+        pack.version,
+        pack.assets.len()
+    );
+    Ok(pack)
+}
+
+/// Substitution rules of the enabled pack for `language`, if any
+pub fn enabled_rules(language: &str, enabled: &[String]) -> Vec<SubstitutionRule> {
+    if !enabled.iter().any(|l| l == language) {
+        return Vec::new();
+    }
+    match load_pack(language) {
+        Ok(pack) => pack.substitution_rules,
+        Err(e) => {
+            warn!("Language pack '{}' enabled but not loadable: {}", language, e);
+            Vec::new()
+        }
+    }
+}
+
+fn read_manifest(path: &std::path::Path) -> Result<LanguagePack> {
+    let pack: LanguagePack = serde_json::from_str(&fs::read_to_string(path)?)?;
+    validate(&pack)?;
+    Ok(pack)
+}
+
+fn validate(pack: &LanguagePack) -> Result<()> {
+    if pack.format > PACK_FORMAT_VERSION {
+        return Err(AppError::InvalidInput(format!(
+            "Language pack format {} is newer than supported ({})",
+            pack.format, PACK_FORMAT_VERSION
+        )));
+    }
+
+    let is_plain_name = |s: &str| {
+        !s.is_empty()
+            && !s.starts_with('.')
+            && s.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    if !is_plain_name(&pack.language) {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid language code: {:?}",
+            pack.language
+        )));
+    }
+    if let Some(asset) = pack.assets.iter().find(|a| !is_plain_name(a) || a.as_str() == MANIFEST_FILE) {
+        return Err(AppError::InvalidInput(format!("Invalid asset name: {:?}", asset)));
+    }
+
+    Ok(())
+}
+
+fn fetch(url: &str) -> Result<Vec<u8>> {
+    let response = ureq::get(url)
+        .call()
+        .map_err(|e| AppError::Network(format!("{}: {}", url, e)))?;
+
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_DOWNLOAD_BYTES + 1)
+        .read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > MAX_DOWNLOAD_BYTES {
+        return Err(AppError::Network(format!("{}: file too large", url)));
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,114 @@
+//! Minute-taker output: actions and decisions found in a transcription
+//!
+//! Rule-based: a sentence is kept when it contains an actionable phrase
+//! ("il faut", "we will", "deadline"...), a decision phrase ("on a décidé",
+//! "we agreed"...) or a name followed by a commitment verb ("Marie va",
+//! "John will").
+
+use crate::storage::{ActionItem, ActionItemKind};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static SENTENCE: Lazy<Regex> = Lazy::new(|| Regex::new(r"[^.!?\n]+[.!?]*").unwrap());
+
+static DECISION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(on a décidé|nous avons décidé|il a été décidé|il est décidé|c'est acté|c'est validé|on valide|on part sur|décision|we decided|we have decided|it was decided|we agreed|agreed to|let's go with|decision)\b",
+    )
+    .unwrap()
+});
+
+static ACTION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(il faut|il faudra|il faudrait|on doit|on devra|nous devons|on va|nous allons|je vais|je m'en occupe|à faire|se charge de|s'occupe de|deadline|échéance|we will|we'll|i will|i'll|we need to|need to|needs to|has to|have to|action item|to do|follow up)\b",
+    )
+    .unwrap()
+});
+
+/// Capitalized name followed by a commitment verb: group 1 is the owner
+static OWNER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\b(\p{Lu}[\p{Ll}'-]+)\s+(va|vont|doit|doivent|devra|s'occupe|se charge|will|should|needs to|has to|is going to)\b",
+    )
+    .unwrap()
+});
+
+static DUE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b((?:avant|d'ici|pour|by|before|until)\s+(?:le\s+|la\s+|the\s+)?(?:lundi|mardi|mercredi|jeudi|vendredi|samedi|dimanche|demain|monday|tuesday|wednesday|thursday|friday|saturday|sunday|tomorrow|la semaine prochaine|next week|fin (?:du|de la|de) \w+|end of (?:the )?\w+|\d{1,2}(?:/\d{1,2})?(?:\s+\p{L}+)?))",
+    )
+    .unwrap()
+});
+
+/// Capitalized words that start sentences but are not names
+const NOT_NAMES: &[&str] = &[
+    "Il", "Elle", "On", "Je", "Tu", "Nous", "Vous", "Ils", "Elles", "Ça", "Ce", "Cela", "Qui",
+    "Le", "La", "Les", "Et", "Mais", "Donc", "Alors", "I", "We", "You", "He", "She", "It", "They",
+    "This", "That", "Who", "The", "And", "But", "So", "Someone", "Quelqu'un",
+];
+
+/// Find actions and decisions in `text`, in order of appearance
+pub fn extract_action_items(text: &str) -> Vec<ActionItem> {
+    SENTENCE
+        .find_iter(text)
+        .filter_map(|m| {
+            let sentence = m.as_str().trim();
+            if sentence.is_empty() {
+                return None;
+            }
+
+            let owner = OWNER
+                .captures_iter(sentence)
+                .map(|c| c[1].to_string())
+                .find(|name| !NOT_NAMES.contains(&name.as_str()));
+
+            let kind = if DECISION.is_match(sentence) {
+                ActionItemKind::Decision
+            } else if ACTION.is_match(sentence) || owner.is_some() {
+                ActionItemKind::Action
+            } else {
+                return None;
+            };
+
+            Some(ActionItem {
+                kind,
+                text: sentence.to_string(),
+                owner,
+                due: DUE.captures(sentence).map(|c| c[1].to_string()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_french_actions_and_decisions() {
+        let items = extract_action_items(
+            "Bonjour à tous. On a décidé de garder le format actuel. Marie va envoyer le devis avant vendredi. Il faut relancer le fournisseur.",
+        );
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].kind, ActionItemKind::Decision);
+        assert_eq!(items[1].kind, ActionItemKind::Action);
+        assert_eq!(items[1].owner.as_deref(), Some("Marie"));
+        assert_eq!(items[1].due.as_deref(), Some("avant vendredi"));
+        assert_eq!(items[2].owner, None);
+    }
+
+    #[test]
+    fn test_english_actions() {
+        let items = extract_action_items("The weather is nice. John will update the roadmap by Monday. We agreed to ship it.");
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].owner.as_deref(), Some("John"));
+        assert_eq!(items[0].due.as_deref(), Some("by Monday"));
+        assert_eq!(items[1].kind, ActionItemKind::Decision);
+    }
+
+    #[test]
+    fn test_pronouns_are_not_owners() {
+        let items = extract_action_items("Il va pleuvoir demain");
+        assert!(items.is_empty());
+    }
+}
@@ -0,0 +1,50 @@
+//! Text post-processing applied after inference
+//!
+//! Runs on the engine output before it is stored, using the user's settings.
+
+pub mod casing;
+pub mod interview;
+pub mod language_pack;
+pub mod minutes;
+pub mod punctuation;
+pub mod substitution;
+pub mod summary;
+pub mod translation;
+
+use crate::storage::{Settings, Transcription};
+
+pub use casing::apply_casing;
+pub use minutes::extract_action_items;
+pub use substitution::apply_substitutions;
+
+/// Apply the configured post-processing steps to a fresh transcription
+///
+/// Vocabulary casing comes first (SNCF, WakaScribe), then the rules from the
+/// enabled language pack, so the user's own substitution rules can override
+/// both. Actions and decisions are then
+/// extracted from the corrected text, before interview formatting.
+pub fn apply(transcription: &mut Transcription, settings: &Settings) {
+    if !settings.vocabulary.is_empty() {
+        transcription.raw_text = apply_casing(&transcription.raw_text, &settings.vocabulary);
+        for segment in &mut transcription.segments {
+            segment.text = apply_casing(&segment.text, &settings.vocabulary);
+        }
+    }
+
+    let mut rules =
+        language_pack::enabled_rules(&transcription.language, &settings.enabled_language_packs);
+    rules.extend(settings.substitution_rules.iter().cloned());
+
+    if !rules.is_empty() {
+        transcription.raw_text = apply_substitutions(&transcription.raw_text, &rules);
+        for segment in &mut transcription.segments {
+            segment.text = apply_substitutions(&segment.text, &rules);
+        }
+    }
+
+    transcription.action_items = extract_action_items(&transcription.raw_text);
+
+    if settings.interview_mode {
+        transcription.raw_text = interview::format_interview(&transcription.raw_text);
+    }
+}
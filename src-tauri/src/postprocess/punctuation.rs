@@ -0,0 +1,70 @@
+//! Punctuation of the output, depending on where the text is shown
+//!
+//! The model punctuates for prose. Subtitles read better with less: pauses
+//! are already marked by the cue breaks, so commas, semicolons and colons
+//! between words go, and so does the period closing a cue. Question and
+//! exclamation marks, ellipses and marks inside a word (3,5 - 10:30 - l'eau)
+//! are kept.
+
+use serde::{Deserialize, Serialize};
+
+/// How much punctuation the output keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PunctuationMode {
+    /// Prose: the punctuation as transcribed
+    #[default]
+    Full,
+    /// Subtitle cues: only what changes the meaning
+    Minimal,
+}
+
+/// Marks dropped between words in minimal mode
+const PAUSE_MARKS: &[char] = &[',', ';', ':'];
+
+/// Punctuate `text` (one cue, or one block of prose) for `mode`
+pub fn punctuate(text: &str, mode: PunctuationMode) -> String {
+    match mode {
+        PunctuationMode::Full => text.to_string(),
+        PunctuationMode::Minimal => minimal(text),
+    }
+}
+
+fn minimal(text: &str) -> String {
+    let mut words: Vec<&str> = text
+        .split_whitespace()
+        .map(|w| w.trim_end_matches(PAUSE_MARKS))
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    if let Some(last) = words.last_mut() {
+        if last.ends_with('.') && !last.ends_with("..") {
+            *last = &last[..last.len() - 1];
+        }
+        if last.is_empty() {
+            words.pop();
+        }
+    }
+    words.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimal_keeps_meaningful_marks() {
+        let minimal = |text| punctuate(text, PunctuationMode::Minimal);
+        assert_eq!(
+            minimal("Bon, on commence a 10:30 ; le taux est de 3,5 %. D'accord ?"),
+            "Bon on commence a 10:30 le taux est de 3,5 %. D'accord ?"
+        );
+        assert_eq!(minimal("Note : il faut voir ."), "Note il faut voir");
+        assert_eq!(minimal("Et puis..."), "Et puis...");
+        assert_eq!(minimal("C'est fini."), "C'est fini");
+        assert_eq!(
+            punctuate("Bon, c'est fini.", PunctuationMode::Full),
+            "Bon, c'est fini."
+        );
+    }
+}
@@ -0,0 +1,58 @@
+//! User-defined text substitutions ("wakascribe" -> "WakaScribe")
+
+use crate::storage::SubstitutionRule;
+use regex::{NoExpand, Regex};
+use tracing::warn;
+
+/// Replace every rule's `from` (case-insensitive, whole words only) with its `to`
+pub fn apply_substitutions(text: &str, rules: &[SubstitutionRule]) -> String {
+    let mut result = text.to_string();
+
+    for rule in rules {
+        let from = rule.from.trim();
+        if from.is_empty() {
+            continue;
+        }
+
+        let pattern = format!(r"(?i)\b{}\b", regex::escape(from));
+        match Regex::new(&pattern) {
+            Ok(re) => result = re.replace_all(&result, NoExpand(&rule.to)).into_owned(),
+            Err(e) => warn!("Invalid substitution rule '{}': {}", from, e),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(from: &str, to: &str) -> SubstitutionRule {
+        SubstitutionRule {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_whole_word_case_insensitive() {
+        let rules = vec![rule("waka scribe", "WakaScribe")];
+        assert_eq!(
+            apply_substitutions("J'utilise Waka Scribe tous les jours", &rules),
+            "J'utilise WakaScribe tous les jours"
+        );
+    }
+
+    #[test]
+    fn test_does_not_replace_inside_words() {
+        let rules = vec![rule("gpu", "GPU")];
+        assert_eq!(apply_substitutions("gpus et gpu", &rules), "gpus et GPU");
+    }
+
+    #[test]
+    fn test_replacement_is_literal() {
+        let rules = vec![rule("euros", "$1 €")];
+        assert_eq!(apply_substitutions("dix euros", &rules), "dix $1 €");
+    }
+}
@@ -0,0 +1,193 @@
+//! Summary, key points and actions written by an LLM
+//!
+//! Unlike the rule-based extraction of `minutes`, this sends the transcript
+//! to a chat completion endpoint compatible with OpenAI (`POST
+//! {url}/chat/completions`), which the llama.cpp server also exposes: with a
+//! local server the text never leaves the machine. The model is asked for a
+//! JSON object; a reply that isn't one is kept whole as the summary.
+
+use crate::error::{AppError, Result};
+use crate::storage::{SummaryStyle, Transcription, TranscriptionNotes};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Characters of transcript sent; the rest is left out of the summary
+const MAX_TRANSCRIPT_CHARS: usize = 60_000;
+
+/// Local models on a CPU can take minutes
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// LLM endpoint (part of the settings)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LlmSettings {
+    /// Base URL, e.g. http://localhost:8080/v1 (empty: summaries off)
+    pub api_url: String,
+    /// Sent as a bearer token when set
+    pub api_key: String,
+    /// Model name sent with each request (llama.cpp ignores it)
+    pub model: String,
+}
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: [ChatMessage<'a>; 2],
+    temperature: f32,
+}
+
+#[derive(Serialize)]
+struct ChatMessage<'a> {
+    role: &'static str,
+    content: &'a str,
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    #[serde(default)]
+    model: Option<String>,
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatReply,
+}
+
+#[derive(Deserialize)]
+struct ChatReply {
+    content: String,
+}
+
+/// What the model is asked to answer with
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Notes {
+    summary: String,
+    #[serde(default)]
+    key_points: Vec<String>,
+    #[serde(default)]
+    action_items: Vec<String>,
+}
+
+fn instructions(style: SummaryStyle) -> String {
+    let summary = match style {
+        SummaryStyle::Brief => "a summary of 3 to 5 sentences",
+        SummaryStyle::Detailed => "a detailed summary, one paragraph per topic discussed",
+        SummaryStyle::Minutes => "meeting minutes: participants if known, topics, decisions taken",
+    };
+    format!(
+        "You summarize transcripts of recordings (dictation, meetings, interviews). \
+         Write {} in the language of the transcript. \
+         Reply with a JSON object only, without code fences: \
+         {{\"summary\": string, \"keyPoints\": [string], \"actionItems\": [string]}}. \
+         keyPoints lists the main points; actionItems the tasks to do, with who and when \
+         if said (empty when there are none). Do not invent anything absent from the transcript.",
+        summary
+    )
+}
+
+/// Notes in `style` on the text (edited if any) of `t`
+pub fn summarize(t: &Transcription, style: SummaryStyle, settings: &LlmSettings) -> Result<TranscriptionNotes> {
+    let url = settings.api_url.trim().trim_end_matches('/');
+    if url.is_empty() {
+        return Err(AppError::InvalidState("No LLM endpoint configured".to_string()));
+    }
+
+    let text = t.edited_text.as_deref().unwrap_or(&t.raw_text).trim();
+    if text.is_empty() {
+        return Err(AppError::InvalidInput("Nothing to summarize".to_string()));
+    }
+    let text = match text.char_indices().nth(MAX_TRANSCRIPT_CHARS) {
+        Some((end, _)) => {
+            warn!("Transcript of {} cut to {} characters for the summary", t.id, MAX_TRANSCRIPT_CHARS);
+            &text[..end]
+        }
+        None => text,
+    };
+
+    let system = instructions(style);
+    let body = ChatRequest {
+        model: &settings.model,
+        messages: [
+            ChatMessage {
+                role: "system",
+                content: &system,
+            },
+            ChatMessage {
+                role: "user",
+                content: text,
+            },
+        ],
+        temperature: 0.2,
+    };
+    let endpoint = format!("{}/chat/completions", url);
+    let mut request = ureq::post(&endpoint)
+        .timeout(REQUEST_TIMEOUT)
+        .set("Content-Type", "application/json");
+    if !settings.api_key.is_empty() {
+        request = request.set("Authorization", &format!("Bearer {}", settings.api_key));
+    }
+    let response = request
+        .send_string(&serde_json::to_string(&body)?)
+        .map_err(|e| AppError::Network(format!("{}: {}", endpoint, e)))?
+        .into_string()?;
+    let response: ChatResponse = serde_json::from_str(&response)?;
+    let reply = response
+        .choices
+        .into_iter()
+        .next()
+        .map(|c| c.message.content)
+        .ok_or_else(|| AppError::Network(format!("{}: empty reply", endpoint)))?;
+
+    let notes = parse_reply(&reply);
+    info!(
+        "Summarized {} ({}, {} key points, {} actions)",
+        t.id,
+        style.as_str(),
+        notes.key_points.len(),
+        notes.action_items.len()
+    );
+    Ok(TranscriptionNotes {
+        style,
+        summary: notes.summary,
+        key_points: notes.key_points,
+        action_items: notes.action_items,
+        model: response.model.or_else(|| (!settings.model.is_empty()).then(|| settings.model.clone())),
+        created_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// JSON object of the reply (possibly in a code fence or after some text),
+/// else the whole reply as the summary
+fn parse_reply(reply: &str) -> Notes {
+    let json = match (reply.find('{'), reply.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &reply[start..=end],
+        _ => "",
+    };
+    serde_json::from_str(json).unwrap_or_else(|_| Notes {
+        summary: reply.trim().to_string(),
+        key_points: Vec::new(),
+        action_items: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reply() {
+        let notes = parse_reply(
+            "Voici :\n```json\n{\"summary\": \"Budget valide.\", \"keyPoints\": [\"budget\"], \"actionItems\": []}\n```",
+        );
+        assert_eq!(notes.summary, "Budget valide.");
+        assert_eq!(notes.key_points, ["budget"]);
+        assert!(notes.action_items.is_empty());
+
+        let notes = parse_reply("  Juste un resume. ");
+        assert_eq!(notes.summary, "Juste un resume.");
+        assert!(notes.key_points.is_empty());
+    }
+}
@@ -0,0 +1,272 @@
+//! Translation of a transcription into another language
+//!
+//! Done on request, not on every transcription: the translated text is stored
+//! next to the original (`Transcription::translations`) and exported with it.
+//! Translation goes through an HTTP API compatible with LibreTranslate
+//! (`POST {url}/translate`); pointing it at a LibreTranslate server running
+//! on the machine keeps the text local.
+//!
+//! Live captions can also be translated as they come (`LiveTranslator`, with
+//! the `live_target` setting). Latency budget: partial captions are never
+//! translated, as they are rewritten at every step; decoding never waits for
+//! the service; captions finalized while a request runs go out together in
+//! the next one, so translations lag by one request at most, itself bounded
+//! by `LIVE_REQUEST_TIMEOUT`. A failed request drops its captions, whose
+//! source text is already shown.
+
+use crate::error::{AppError, Result};
+use crate::storage::{StreamingSegment, Transcription, Translation};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Characters sent per request; longer texts are split between paragraphs
+const MAX_REQUEST_CHARS: usize = 4000;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// A live caption translated later than this is not worth showing
+const LIVE_REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Translation service (part of the settings)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TranslationSettings {
+    /// Base URL of the service, e.g. http://localhost:5000 (empty: translation off)
+    pub api_url: String,
+    /// Sent as `api_key` when the service requires one
+    pub api_key: String,
+    /// Language live captions are translated into (empty: not translated)
+    pub live_target: String,
+}
+
+#[derive(Serialize)]
+struct TranslateRequest<'a> {
+    q: &'a str,
+    source: &'a str,
+    target: &'a str,
+    format: &'static str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    api_key: &'a str,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TranslateResponse {
+    translated_text: String,
+}
+
+/// Translate the text (edited if any) of `t` into `target`
+pub fn translate(t: &Transcription, target: &str, settings: &TranslationSettings) -> Result<Translation> {
+    let url = service_url(settings)?;
+    let target = target_code(target)?;
+    let source = if t.language.is_empty() { "auto" } else { t.language.as_str() };
+
+    let text = t.edited_text.as_deref().unwrap_or(&t.raw_text);
+    let mut translated = Vec::new();
+    for part in split_text(text, MAX_REQUEST_CHARS) {
+        translated.push(request(url, &part, source, &target, &settings.api_key, REQUEST_TIMEOUT)?);
+    }
+    info!("Translated {} ({} -> {}, {} requests)", t.id, source, target, translated.len());
+
+    Ok(Translation {
+        language: target,
+        text: translated.join("\n\n"),
+        created_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+fn service_url(settings: &TranslationSettings) -> Result<&str> {
+    let url = settings.api_url.trim().trim_end_matches('/');
+    if url.is_empty() {
+        return Err(AppError::InvalidState("No translation service configured".to_string()));
+    }
+    Ok(url)
+}
+
+/// Lowercase language code ("en", "zh-hans"...)
+fn target_code(target: &str) -> Result<String> {
+    let target = target.trim().to_lowercase();
+    if target.is_empty() || target.len() > 8 || !target.chars().all(|c| c.is_ascii_alphabetic() || c == '-') {
+        return Err(AppError::InvalidInput(format!("Invalid target language: {}", target)));
+    }
+    Ok(target)
+}
+
+fn request(url: &str, text: &str, source: &str, target: &str, api_key: &str, timeout: Duration) -> Result<String> {
+    let endpoint = format!("{}/translate", url);
+    let body = TranslateRequest {
+        q: text,
+        source,
+        target,
+        format: "text",
+        api_key,
+    };
+    let response = ureq::post(&endpoint)
+        .timeout(timeout)
+        .set("Content-Type", "application/json")
+        .send_string(&serde_json::to_string(&body)?)
+        .map_err(|e| AppError::Network(format!("{}: {}", endpoint, e)))?
+        .into_string()?;
+    let response: TranslateResponse = serde_json::from_str(&response)?;
+    Ok(response.translated_text)
+}
+
+/// Translator of the final captions of a live session, on its own thread
+pub struct LiveTranslator {
+    captions: mpsc::Sender<String>,
+    worker: thread::JoinHandle<()>,
+}
+
+impl LiveTranslator {
+    /// None when live translation is off or no service is configured;
+    /// `on_caption` gets each source text along with its translation
+    pub fn start(
+        settings: &TranslationSettings,
+        source: &str,
+        on_caption: impl FnMut(StreamingSegment) + Send + 'static,
+    ) -> Result<Option<Self>> {
+        if settings.live_target.trim().is_empty() || settings.api_url.trim().is_empty() {
+            return Ok(None);
+        }
+        let url = service_url(settings)?.to_string();
+        let target = target_code(&settings.live_target)?;
+        let (source, api_key) = (source.to_string(), settings.api_key.clone());
+        info!("Live captions translated ({} -> {})", source, target);
+        let language = target.clone();
+        let translate = move |text: &str| request(&url, text, &source, &language, &api_key, LIVE_REQUEST_TIMEOUT);
+        Ok(Some(Self::with(target, translate, on_caption)))
+    }
+
+    fn with(
+        target: String,
+        translate: impl Fn(&str) -> Result<String> + Send + 'static,
+        mut on_caption: impl FnMut(StreamingSegment) + Send + 'static,
+    ) -> Self {
+        let (captions, queued) = mpsc::channel::<String>();
+        let worker = thread::spawn(move || {
+            while let Ok(first) = queued.recv() {
+                // Captions finalized during the last request: one request for all
+                let text = std::iter::once(first).chain(queued.try_iter()).collect::<Vec<_>>().join(" ");
+                match translate(&text) {
+                    Ok(translated) => on_caption(StreamingSegment {
+                        text,
+                        is_final: true,
+                        confidence: None,
+                        translated_text: Some(translated),
+                        target_language: Some(target.clone()),
+                    }),
+                    Err(e) => warn!("Live caption not translated: {}", e),
+                }
+            }
+        });
+        Self { captions, worker }
+    }
+
+    /// Queue a caption; only final ones are translated
+    pub fn push(&self, segment: &StreamingSegment) {
+        if segment.is_final && !segment.text.trim().is_empty() {
+            let _ = self.captions.send(segment.text.trim().to_string());
+        }
+    }
+
+    /// Translate what is still queued, then stop
+    pub fn finish(self) {
+        drop(self.captions);
+        let _ = self.worker.join();
+    }
+}
+
+/// Paragraphs grouped into parts of at most `max_chars` characters (a longer
+/// paragraph is cut between sentences, or words)
+fn split_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut parts: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for paragraph in text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        let mut rest = paragraph;
+        while rest.chars().count() > max_chars {
+            let limit = rest.char_indices().nth(max_chars).map_or(rest.len(), |(i, _)| i);
+            let cut = rest[..limit]
+                .rfind(['.', '!', '?'])
+                .map(|i| i + 1)
+                .or_else(|| rest[..limit].rfind(' '))
+                .filter(|&i| i > 0)
+                .unwrap_or(limit);
+            parts.extend((!current.is_empty()).then(|| std::mem::take(&mut current)));
+            parts.push(rest[..cut].trim().to_string());
+            rest = rest[cut..].trim_start();
+        }
+        if rest.is_empty() {
+            continue;
+        }
+        if !current.is_empty() && current.chars().count() + 2 + rest.chars().count() > max_chars {
+            parts.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(rest);
+    }
+    parts.extend((!current.is_empty()).then_some(current));
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_text_between_paragraphs_and_sentences() {
+        assert_eq!(split_text("Un.\n\nDeux.", 100), ["Un.\n\nDeux."]);
+        assert_eq!(split_text("Un.\n\nDeux.", 6), ["Un.", "Deux."]);
+        assert_eq!(
+            split_text("Première phrase. Deuxième phrase.", 20),
+            ["Première phrase.", "Deuxième phrase."]
+        );
+        assert_eq!(split_text("motsansespace", 5), ["motsa", "nsesp", "ace"]);
+        assert!(split_text("  \n\n ", 10).is_empty());
+    }
+
+    fn caption(text: &str, is_final: bool) -> StreamingSegment {
+        StreamingSegment {
+            text: text.into(),
+            is_final,
+            confidence: None,
+            translated_text: None,
+            target_language: None,
+        }
+    }
+
+    #[test]
+    fn test_live_translator_batches_final_captions() {
+        let (gate, wait) = mpsc::channel::<()>();
+        let (sent, received) = mpsc::channel();
+        let translator = LiveTranslator::with(
+            "en".into(),
+            move |text: &str| {
+                // The first request is held until the other captions are queued
+                let _ = wait.recv();
+                Ok(text.to_uppercase())
+            },
+            move |segment| sent.send(segment).unwrap(),
+        );
+
+        translator.push(&caption("bonjour", true));
+        translator.push(&caption("a to", false));
+        translator.push(&caption("a tous", true));
+        translator.push(&caption("  ", true));
+        translator.push(&caption("merci", true));
+        gate.send(()).unwrap();
+        gate.send(()).unwrap();
+        translator.finish();
+
+        let captions: Vec<StreamingSegment> = received.try_iter().collect();
+        let texts: Vec<&str> = captions.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts.join(" "), "bonjour a tous merci");
+        assert!(captions.len() <= 2);
+        assert_eq!(captions[0].translated_text.as_deref(), Some(captions[0].text.to_uppercase().as_str()));
+        assert!(captions.iter().all(|c| c.is_final && c.target_language.as_deref() == Some("en")));
+    }
+}
@@ -0,0 +1,149 @@
+//! Power state probe and low-power pacing of chunked transcription
+//!
+//! Long transcriptions at full speed drain laptop batteries and trigger
+//! thermal throttling. In low-power modes the chunk loop checks the power
+//! source before each chunk and, on battery, either idles between chunks or
+//! waits for AC power.
+
+use serde::{Deserialize, Serialize};
+use std::thread;
+use std::time::Duration;
+use tracing::info;
+
+/// How often `WaitForAc` checks the power source again
+const AC_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How chunked transcription behaves on battery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerMode {
+    /// Full speed, whatever the power source
+    #[default]
+    Performance,
+    /// On battery, idle as long as the previous chunk took (about half the CPU load)
+    BatterySaver,
+    /// On battery, wait for AC power before each chunk
+    WaitForAc,
+}
+
+/// Where the machine currently draws power from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerSource {
+    Ac,
+    Battery,
+    /// No battery found or the platform probe failed (treated as AC)
+    Unknown,
+}
+
+/// Current power source
+pub fn power_source() -> PowerSource {
+    probe()
+}
+
+/// Called before each chunk; `previous_chunk` is the inference time of the last one
+pub fn pace_chunk(mode: PowerMode, previous_chunk: Option<Duration>) {
+    if mode == PowerMode::Performance || power_source() != PowerSource::Battery {
+        return;
+    }
+
+    match mode {
+        PowerMode::Performance => {}
+        PowerMode::BatterySaver => {
+            if let Some(pause) = previous_chunk {
+                thread::sleep(pause);
+            }
+        }
+        PowerMode::WaitForAc => {
+            info!("On battery power, waiting for AC before the next chunk");
+            while power_source() == PowerSource::Battery {
+                thread::sleep(AC_POLL_INTERVAL);
+            }
+            info!("AC power restored, resuming transcription");
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn probe() -> PowerSource {
+    let Ok(supplies) = std::fs::read_dir("/sys/class/power_supply") else {
+        return PowerSource::Unknown;
+    };
+
+    let mut has_battery = false;
+    for supply in supplies.filter_map(|e| e.ok()).map(|e| e.path()) {
+        let read = |name: &str| std::fs::read_to_string(supply.join(name)).unwrap_or_default();
+        match read("type").trim() {
+            "Mains" | "USB" if read("online").trim() == "1" => return PowerSource::Ac,
+            "Battery" => has_battery = true,
+            _ => {}
+        }
+    }
+
+    if has_battery {
+        PowerSource::Battery
+    } else {
+        PowerSource::Unknown
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn probe() -> PowerSource {
+    // "Now drawing from 'AC Power'" / "Now drawing from 'Battery Power'"
+    match std::process::Command::new("pmset").args(["-g", "batt"]).output() {
+        Ok(output) => {
+            let text = String::from_utf8_lossy(&output.stdout);
+            if text.contains("'Battery Power'") {
+                PowerSource::Battery
+            } else if text.contains("'AC Power'") {
+                PowerSource::Ac
+            } else {
+                PowerSource::Unknown
+            }
+        }
+        Err(_) => PowerSource::Unknown,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn probe() -> PowerSource {
+    #[repr(C)]
+    #[allow(dead_code)]
+    struct SystemPowerStatus {
+        ac_line_status: u8,
+        battery_flag: u8,
+        battery_life_percent: u8,
+        system_status_flag: u8,
+        battery_life_time: u32,
+        battery_full_life_time: u32,
+    }
+
+    extern "system" {
+        fn GetSystemPowerStatus(status: *mut SystemPowerStatus) -> i32;
+    }
+
+    let mut status = SystemPowerStatus {
+        ac_line_status: 255,
+        battery_flag: 255,
+        battery_life_percent: 255,
+        system_status_flag: 0,
+        battery_life_time: 0,
+        battery_full_life_time: 0,
+    };
+    // SAFETY: the struct matches SYSTEM_POWER_STATUS and outlives the call
+    if unsafe { GetSystemPowerStatus(&mut status) } == 0 {
+        return PowerSource::Unknown;
+    }
+
+    // battery_flag 128: no system battery
+    match status.ac_line_status {
+        1 => PowerSource::Ac,
+        0 if status.battery_flag != 128 => PowerSource::Battery,
+        _ => PowerSource::Unknown,
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn probe() -> PowerSource {
+    PowerSource::Unknown
+}
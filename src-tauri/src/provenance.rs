@@ -0,0 +1,287 @@
+//! Provenance of exported documents (basic chain of custody)
+//!
+//! With the `export_provenance` setting, TXT and DOCX exports end with a
+//! `WakaScribe-Provenance: {json}` line: transcription, capture device,
+//! recording and export times, app version, SHA-256 of the audio and of the
+//! document text before the line (whitespace-normalized, so it reads the same
+//! from a TXT or the runs of a DOCX). `verify_export` reads it back, hashes the
+//! text again and compares the audio hash with the one stored with the
+//! transcription and, for files still on disk, with the source file and the
+//! kept recording. Dictation audio is otherwise not kept: its hash is only
+//! checked against the database.
+
+use crate::error::{AppError, Result};
+use crate::storage::{self, CaptureInfo, Transcription};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{self, Read, Seek};
+use std::path::Path;
+
+const MARKER: &str = "WakaScribe-Provenance: ";
+
+/// Metadata embedded in an export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Provenance {
+    pub transcription_id: String,
+    pub source_name: Option<String>,
+    pub device: Option<String>,
+    pub recorded_at: String,
+    pub exported_at: String,
+    pub app_version: String,
+    /// None for transcriptions made before hashes were recorded
+    pub audio_sha256: Option<String>,
+    /// Document text before the provenance line, see `content_digest`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_sha256: Option<String>,
+    /// WAV file kept with the transcription (None: recordings not kept)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recording_sha256: Option<String>,
+}
+
+impl Provenance {
+    pub fn of(transcription: &Transcription) -> Self {
+        let capture = transcription.capture.as_ref();
+        Self {
+            transcription_id: transcription.id.clone(),
+            source_name: transcription.source_name.clone(),
+            device: capture.and_then(|c| c.device.clone()),
+            recorded_at: transcription.created_at.clone(),
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            audio_sha256: capture.map(|c| c.audio_sha256.clone()),
+            content_sha256: None,
+            recording_sha256: transcription
+                .audio_path
+                .as_deref()
+                .map(Path::new)
+                .filter(|p| p.is_file())
+                .and_then(|p| hash_file(p).ok()),
+        }
+    }
+
+    /// Same provenance, sealing the document text it will follow
+    pub fn sealing(&self, content: &str) -> Self {
+        Self {
+            content_sha256: Some(content_digest(content)),
+            ..self.clone()
+        }
+    }
+
+    /// Line to append to a document
+    pub fn line(&self) -> Result<String> {
+        Ok(format!("{}{}", MARKER, serde_json::to_string(self)?))
+    }
+
+    /// Provenance line found in a document's text
+    fn find(text: &str) -> Option<Self> {
+        let start = text.rfind(MARKER)? + MARKER.len();
+        // Only the JSON object, whatever follows it
+        serde_json::Deserializer::from_str(&text[start..])
+            .into_iter::<Self>()
+            .next()?
+            .ok()
+    }
+}
+
+/// Capture info of a dictation, from the samples as recorded
+///
+/// For a dual-source recording, the system audio is hashed after the microphone.
+pub fn capture_of_samples(samples: &[f32], loopback: Option<&[f32]>, device: Option<String>) -> CaptureInfo {
+    let mut hasher = Sha256::new();
+    for sample in samples.iter().chain(loopback.unwrap_or_default()) {
+        hasher.update(sample.to_le_bytes());
+    }
+    CaptureInfo {
+        device,
+        audio_sha256: format!("{:x}", hasher.finalize()),
+    }
+}
+
+/// Capture info of a transcribed file
+pub fn capture_of_file(path: &Path) -> Result<CaptureInfo> {
+    Ok(CaptureInfo {
+        device: None,
+        audio_sha256: hash_file(path)?,
+    })
+}
+
+fn hash_file(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// SHA-256 of a document's words separated by single spaces: line breaks and
+/// paragraphs differ between formats, the words do not
+pub fn content_digest(text: &str) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    format!("{:x}", Sha256::digest(words.join(" ").as_bytes()))
+}
+
+/// Text before the provenance line vs the hash it carries (None: no hash)
+fn content_matches(text: &str, provenance: &Provenance) -> Option<bool> {
+    let expected = provenance.content_sha256.as_ref()?;
+    let end = text.rfind(MARKER)?;
+    Some(&content_digest(&text[..end]) == expected)
+}
+
+/// Outcome of `verify_export`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportVerification {
+    pub provenance: Provenance,
+    /// Document hash vs the one stored with the transcription (None: transcription deleted, or no hash)
+    pub stored_hash_matches: Option<bool>,
+    /// Document hash vs the source file hashed again (None: dictation, or file gone)
+    pub source_file_matches: Option<bool>,
+    /// Text of the document hashed again (None: exported before text hashes)
+    pub content_matches: Option<bool>,
+    /// Kept recording hashed again (None: not kept, or file gone)
+    pub recording_matches: Option<bool>,
+    /// A check passed and none failed
+    pub verified: bool,
+}
+
+/// Check the provenance embedded in an exported TXT, Markdown or DOCX file
+pub fn verify_export(path: &Path) -> Result<ExportVerification> {
+    let text = document_text(path)?;
+    let provenance = Provenance::find(&text)
+        .ok_or_else(|| AppError::NotFound("No provenance metadata in this file".into()))?;
+    let content_matches = content_matches(&text, &provenance);
+
+    let transcription = storage::with_db(|conn| storage::get_transcription(conn, &provenance.transcription_id))?;
+    let stored_hash_matches = provenance.audio_sha256.as_ref().and_then(|hash| {
+        let stored = transcription.as_ref()?.capture.as_ref()?;
+        Some(&stored.audio_sha256 == hash)
+    });
+    let (source_path, audio_path) = transcription.map_or((None, None), |t| (t.source_path, t.audio_path));
+    let source_file_matches = match (&provenance.audio_sha256, source_path) {
+        (Some(hash), Some(source)) if Path::new(&source).is_file() => Some(&hash_file(Path::new(&source))? == hash),
+        _ => None,
+    };
+    let recording_matches = match (&provenance.recording_sha256, audio_path) {
+        (Some(hash), Some(audio)) if Path::new(&audio).is_file() => Some(&hash_file(Path::new(&audio))? == hash),
+        _ => None,
+    };
+
+    let checks = [stored_hash_matches, source_file_matches, content_matches, recording_matches];
+    let verified = checks.contains(&Some(true)) && !checks.contains(&Some(false));
+    Ok(ExportVerification {
+        provenance,
+        stored_hash_matches,
+        source_file_matches,
+        content_matches,
+        recording_matches,
+        verified,
+    })
+}
+
+/// Text of an export; the text of its runs for a DOCX
+fn document_text(path: &Path) -> Result<String> {
+    let is_docx = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("docx"));
+    if is_docx {
+        docx_text(File::open(path)?)
+    } else {
+        Ok(fs::read_to_string(path)?)
+    }
+}
+
+/// Text of the runs of a DOCX (`<w:t>` elements), one per line
+pub fn docx_text(reader: impl Read + Seek) -> Result<String> {
+    let zip_err = |e: zip::result::ZipError| AppError::InvalidInput(format!("Invalid DOCX file: {}", e));
+    let mut archive = zip::ZipArchive::new(reader).map_err(zip_err)?;
+    let mut xml = String::new();
+    archive
+        .by_name("word/document.xml")
+        .map_err(zip_err)?
+        .read_to_string(&mut xml)?;
+    Ok(run_texts(&xml).join("\n"))
+}
+
+/// Unescaped contents of the `<w:t>` elements, in order
+fn run_texts(xml: &str) -> Vec<String> {
+    let mut texts = Vec::new();
+    let mut rest = xml;
+    while let Some(open) = rest.find("<w:t") {
+        rest = &rest[open + 4..];
+        // Not <w:tab/>, <w:tbl>...
+        if !rest.starts_with('>') && !rest.starts_with(' ') {
+            continue;
+        }
+        let Some(start) = rest.find('>') else { break };
+        if rest[..start].ends_with('/') {
+            rest = &rest[start + 1..];
+            continue;
+        }
+        let Some(end) = rest.find("</w:t>") else { break };
+        texts.push(unescape_xml(&rest[start + 1..end]));
+        rest = &rest[end..];
+    }
+    texts
+}
+
+fn unescape_xml(xml: &str) -> String {
+    xml.replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provenance() -> Provenance {
+        Provenance {
+            transcription_id: "abc".into(),
+            source_name: Some("reunion \"budget\".wav".into()),
+            device: None,
+            recorded_at: "2026-01-02T10:00:00+00:00".into(),
+            exported_at: "2026-01-03T10:00:00+00:00".into(),
+            app_version: "1.0.0".into(),
+            audio_sha256: Some("00ff".into()),
+            content_sha256: None,
+            recording_sha256: None,
+        }
+    }
+
+    #[test]
+    fn test_provenance_round_trip() {
+        let p = provenance();
+        let text = format!("Transcription\n\n---\n{}\n", p.line().unwrap());
+        assert_eq!(Provenance::find(&text), Some(p.clone()));
+
+        // DOCX body: escaped, followed by the closing tags
+        let xml = format!(
+            "<w:t>{}</w:t></w:r></w:p>",
+            p.line().unwrap().replace('&', "&amp;").replace('"', "&quot;")
+        );
+        assert_eq!(run_texts(&xml).len(), 1);
+        assert_eq!(Provenance::find(&run_texts(&xml)[0]), Some(p));
+        assert_eq!(Provenance::find("no metadata"), None);
+    }
+
+    #[test]
+    fn test_content_hash_detects_edits() {
+        let body = "Transcription WakaScribe\n\nBonjour a tous.\n\n---\n";
+        let p = provenance().sealing(body);
+        let text = format!("{}{}\n", body, p.line().unwrap());
+        assert_eq!(content_matches(&text, &p), Some(true));
+        assert_eq!(content_matches(&text.replace("tous", "toutes"), &p), Some(false));
+        assert_eq!(content_matches(&text, &provenance()), None);
+
+        // Same words from the runs of a DOCX
+        let xml = format!(
+            "<w:p><w:r><w:rPr><w:b/></w:rPr><w:t xml:space=\"preserve\">Transcription WakaScribe</w:t></w:r></w:p>\
+             <w:p><w:r><w:tab/><w:t>Bonjour a tous.</w:t></w:r></w:p><w:p><w:r><w:t>---</w:t></w:r></w:p>\
+             <w:p><w:r><w:t>{}</w:t></w:r></w:p>",
+            p.line().unwrap().replace('&', "&amp;").replace('"', "&quot;")
+        );
+        assert_eq!(content_matches(&run_texts(&xml).join("\n"), &p), Some(true));
+    }
+}
@@ -0,0 +1,110 @@
+//! Audio of recordings kept next to their transcription
+//!
+//! With the `keep_recordings` setting, `stop_recording` writes the audio it
+//! transcribes (16 kHz mono, both sources mixed with system audio) to a WAV
+//! file under app data, whose path is stored with the transcription so the
+//! UI can play it back in sync with the segments. Files no transcription
+//! refers to any more (deleted, purged from the trash) are removed by
+//! `remove_orphans`.
+
+use crate::audio::write_wav;
+use crate::error::{AppError, Result};
+use crate::storage;
+use parking_lot::Mutex;
+use rusqlite::Connection;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+fn recordings_dir() -> Result<PathBuf> {
+    crate::app_data_dir()
+        .map(|p| p.join("com.wakascribe.app").join("recordings"))
+        .ok_or_else(|| AppError::NotFound("App data directory not found".into()))
+}
+
+/// Recordings written whose transcription is not saved yet (not orphans)
+static PENDING: Mutex<Vec<PathBuf>> = parking_lot::const_mutex(Vec::new());
+
+/// Recording written before its transcription is saved: deleted on drop
+/// unless kept, so a failed transcription leaves no file behind
+pub struct PendingRecording {
+    path: PathBuf,
+    kept: bool,
+}
+
+impl PendingRecording {
+    /// Path to store with the transcription
+    pub fn path(&self) -> String {
+        self.path.to_string_lossy().into_owned()
+    }
+
+    /// The transcription referring to it is saved
+    pub fn keep(mut self) {
+        self.kept = true;
+    }
+}
+
+impl Drop for PendingRecording {
+    fn drop(&mut self) {
+        PENDING.lock().retain(|p| p != &self.path);
+        if !self.kept {
+            remove(&self.path);
+        }
+    }
+}
+
+/// Write 16 kHz mono `samples` to a new file of the recordings folder
+pub fn save(samples: &[f32]) -> Result<PendingRecording> {
+    let dir = recordings_dir()?;
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.wav", Uuid::new_v4()));
+    let part = path.with_extension("wav.part");
+    if let Err(e) = write_wav(samples, &part) {
+        remove(&part);
+        return Err(e);
+    }
+    PENDING.lock().push(path.clone());
+    let pending = PendingRecording { path, kept: false };
+    std::fs::rename(&part, &pending.path)?;
+    Ok(pending)
+}
+
+/// Delete a recording file; a file already gone is not an error
+pub fn remove(path: &Path) {
+    match std::fs::remove_file(path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => warn!("Failed to delete recording {:?}: {}", path, e),
+    }
+}
+
+/// Delete the recordings no transcription refers to (every profile counts);
+/// returns how many were removed
+pub fn remove_orphans(conn: &Connection) -> Result<usize> {
+    let Ok(dir) = recordings_dir() else {
+        return Ok(0);
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Ok(0);
+    };
+    let mut referenced: HashSet<PathBuf> = storage::recording_audio_paths(conn)?
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    referenced.extend(PENDING.lock().iter().cloned());
+
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let partial = path.extension().is_some_and(|e| e == "part");
+        if path.is_file() && !partial && !referenced.contains(&path) {
+            remove(&path);
+            removed += 1;
+        }
+    }
+    if removed > 0 {
+        info!("Removed {} recordings without transcription", removed);
+    }
+    Ok(removed)
+}
@@ -0,0 +1,69 @@
+//! Spoken feedback through the OS text-to-speech
+//!
+//! Short announcements ("recording started", "transcription ready") so the
+//! app can be used without watching the screen. Each platform has a speech
+//! command available out of the box; a new announcement cuts the previous
+//! one short rather than queueing behind it.
+
+use crate::error::{AppError, Result};
+use parking_lot::Mutex;
+use std::process::{Child, Command, Stdio};
+use tracing::debug;
+
+/// Announcement being spoken, killed when the next one starts
+static SPEAKING: Mutex<Option<Child>> = parking_lot::const_mutex(None);
+
+/// Speak `text` without waiting for the end of the announcement
+pub fn speak(text: &str) -> Result<()> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(());
+    }
+
+    let mut speaking = SPEAKING.lock();
+    if let Some(mut previous) = speaking.take() {
+        let _ = previous.kill();
+        let _ = previous.wait();
+    }
+
+    debug!("Announcing: {}", text);
+    let child = speech_command(text)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| AppError::Audio(format!("Text-to-speech unavailable: {}", e)))?;
+    *speaking = Some(child);
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn speech_command(text: &str) -> Command {
+    let mut command = Command::new("say");
+    command.arg("--").arg(text);
+    command
+}
+
+#[cfg(target_os = "windows")]
+fn speech_command(text: &str) -> Command {
+    // The text goes through the environment, never into the script itself
+    let mut command = Command::new("powershell");
+    command
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Add-Type -AssemblyName System.Speech; \
+             (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak($env:WAKASCRIBE_SPEECH)",
+        ])
+        .env("WAKASCRIBE_SPEECH", text);
+    command
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn speech_command(text: &str) -> Command {
+    // speech-dispatcher, installed with most desktop environments
+    let mut command = Command::new("spd-say");
+    command.arg("--").arg(text);
+    command
+}
@@ -0,0 +1,209 @@
+//! Backup of the history and settings to one portable file
+//!
+//! The backup is JSON: the settings and every transcription of the active
+//! profile (segments included), tagged with a format version. Files from an
+//! older version load as is, fields added since taking their defaults; files
+//! from a newer version are refused.
+//!
+//! On restore, a transcription already present with the same `updated_at` is
+//! skipped. One whose id is taken by a different transcription (edited since,
+//! or in another profile) is added as a copy under a new id, so nothing on
+//! either side is overwritten.
+
+use crate::error::{AppError, Result};
+use crate::storage::models::{BackupImport, Settings, Transcription};
+use crate::storage::preset::{clear_local_settings, keep_local_settings};
+use crate::storage::queries::{get_settings, get_transcription, insert_transcription, list_transcriptions, update_settings};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use tracing::info;
+use uuid::Uuid;
+
+/// Current backup file format version
+const BACKUP_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Backup {
+    version: u32,
+    exported_at: String,
+    settings: Settings,
+    transcriptions: Vec<Transcription>,
+}
+
+/// Write the settings and history of the active profile to `path`; returns
+/// the number of transcriptions saved
+///
+/// The local settings (input device, credentials, hooks) are left out, as
+/// in presets.
+pub fn export_database(conn: &Connection, path: &Path) -> Result<usize> {
+    let mut settings = get_settings(conn)?;
+    clear_local_settings(&mut settings);
+
+    let mut transcriptions = Vec::new();
+    for summary in list_transcriptions(conn, None, None)? {
+        if let Some(t) = get_transcription(conn, &summary.id)? {
+            transcriptions.push(t);
+        }
+    }
+
+    let backup = Backup {
+        version: BACKUP_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        settings,
+        transcriptions,
+    };
+    let json = serde_json::to_vec(&backup)?;
+    crate::export::write_atomic(path, |file| Ok(file.write_all(&json)?))?;
+
+    info!("Backed up {} transcriptions to {:?}", backup.transcriptions.len(), path);
+    Ok(backup.transcriptions.len())
+}
+
+/// Add the transcriptions of a backup to the active profile and take its
+/// settings (the local input device, credentials and hooks are kept)
+///
+/// All or nothing: a failure leaves the database as it was.
+pub fn import_database(conn: &Connection, path: &Path) -> Result<BackupImport> {
+    let content = std::fs::read(path)?;
+    let backup: Backup = serde_json::from_slice(&content)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid backup file: {}", e)))?;
+
+    if backup.version > BACKUP_VERSION {
+        return Err(AppError::InvalidInput(format!(
+            "Backup version {} is newer than supported version {}",
+            backup.version, BACKUP_VERSION
+        )));
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    let mut report = BackupImport::default();
+    for mut t in backup.transcriptions {
+        let existing: Option<String> = tx
+            .query_row("SELECT updated_at FROM transcriptions WHERE id = ?1", [&t.id], |row| row.get(0))
+            .optional()?;
+        match existing {
+            Some(updated_at) if updated_at == t.updated_at => {
+                report.skipped += 1;
+                continue;
+            }
+            Some(_) => {
+                t.id = Uuid::new_v4().to_string();
+                for segment in &mut t.segments {
+                    segment.id = Uuid::new_v4().to_string();
+                }
+                report.copied += 1;
+            }
+            None => report.imported += 1,
+        }
+        insert_transcription(&tx, &t)?;
+    }
+
+    let mut settings = backup.settings;
+    keep_local_settings(&mut settings, get_settings(&tx)?);
+    update_settings(&tx, &settings)?;
+    tx.commit()?;
+
+    info!(
+        "Restored backup {:?}: {} imported, {} copied, {} already present",
+        path, report.imported, report.copied, report.skipped
+    );
+    report.settings = settings;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::database::migrate;
+    use crate::storage::queries::update_transcription_text;
+
+    fn database() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn).unwrap();
+        conn
+    }
+
+    fn transcription(id: &str) -> Transcription {
+        serde_json::from_str(&format!(
+            r#"{{
+                "id": "{}", "createdAt": "2026-03-01T10:00:00+00:00", "updatedAt": "2026-03-01T10:00:00+00:00",
+                "sourceType": "meeting", "durationMs": 1500, "language": "fr",
+                "rawText": "Bonjour a tous", "isEdited": false,
+                "segments": [{{"id": "{}-s0", "startMs": 0, "endMs": 1500, "text": "Bonjour a tous", "confidence": 0.9}}]
+            }}"#,
+            id, id
+        ))
+        .unwrap()
+    }
+
+    fn backup_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("wakascribe-backup-{}-{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_backup_round_trip() {
+        let source = database();
+        insert_transcription(&source, &transcription("a")).unwrap();
+        insert_transcription(&source, &transcription("b")).unwrap();
+        let mut settings = get_settings(&source).unwrap();
+        settings.translation.api_url = "http://localhost:5000".into();
+        settings.translation.api_key = "secret-source".into();
+        update_settings(&source, &settings).unwrap();
+
+        let path = backup_path("round-trip");
+        assert_eq!(export_database(&source, &path).unwrap(), 2);
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("secret-source"));
+
+        let target = database();
+        let mut local = get_settings(&target).unwrap();
+        local.translation.api_key = "secret-target".into();
+        update_settings(&target, &local).unwrap();
+
+        let report = import_database(&target, &path).unwrap();
+        assert_eq!((report.imported, report.copied, report.skipped), (2, 0, 0));
+        let restored = get_transcription(&target, "a").unwrap().unwrap();
+        let original = get_transcription(&source, "a").unwrap().unwrap();
+        assert_eq!(restored.raw_text, original.raw_text);
+        assert_eq!(restored.updated_at, original.updated_at);
+        assert_eq!(restored.segments.len(), 1);
+        assert_eq!(restored.segments[0].text, original.segments[0].text);
+        // Shared settings taken, credentials kept local
+        assert_eq!(report.settings.translation.api_key, "secret-target");
+        assert_eq!(get_settings(&target).unwrap().translation.api_key, "secret-target");
+
+        // Restored again: unchanged ones skipped, edited ones kept next to the backup's
+        update_transcription_text(&target, "b", "revu", &[], None).unwrap();
+        let again = import_database(&target, &path).unwrap();
+        assert_eq!((again.imported, again.copied, again.skipped), (0, 1, 1));
+        assert_eq!(list_transcriptions(&target, None, None).unwrap().len(), 3);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_newer_backup_is_refused() {
+        let source = database();
+        insert_transcription(&source, &transcription("a")).unwrap();
+        let path = backup_path("newer");
+        export_database(&source, &path).unwrap();
+
+        let mut backup: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        backup["version"] = (BACKUP_VERSION + 1).into();
+        std::fs::write(&path, serde_json::to_vec(&backup).unwrap()).unwrap();
+
+        let target = database();
+        assert!(matches!(import_database(&target, &path), Err(AppError::InvalidInput(_))));
+        assert!(list_transcriptions(&target, None, None).unwrap().is_empty());
+
+        // Older files load, fields added since taking their defaults
+        backup["version"] = 0.into();
+        let first = backup["transcriptions"][0].as_object_mut().unwrap();
+        first.remove("confidence");
+        first.remove("audioPath");
+        std::fs::write(&path, serde_json::to_vec(&backup).unwrap()).unwrap();
+        assert_eq!(import_database(&target, &path).unwrap().imported, 1);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
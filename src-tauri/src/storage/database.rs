@@ -1,36 +1,449 @@
 use crate::error::{AppError, Result};
+use crate::storage::repo::Store;
+use crate::storage::sqlite::SqliteStore;
 use once_cell::sync::OnceCell;
 use parking_lot::Mutex;
-use rusqlite::Connection;
-use std::path::PathBuf;
-use tracing::info;
+use rusqlite::{params, Connection, OpenFlags};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{info, warn};
 
-static DB: OnceCell<Mutex<Connection>> = OnceCell::new();
+static DB: OnceCell<SqliteStore> = OnceCell::new();
 
-fn get_db_path() -> PathBuf {
+/// Set when the database was opened with `init_database_read_only`
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// File of the open database
+static DB_PATH: Mutex<Option<PathBuf>> = parking_lot::const_mutex(None);
+
+const DB_FILE_NAME: &str = "wakascribe.db";
+
+/// Holds the path of a database moved out of the app data directory
+///
+/// The settings live in the database itself, so its location has to be
+/// known before opening it.
+const LOCATION_FILE_NAME: &str = "database-location";
+
+fn app_dir() -> PathBuf {
     let app_dir = dirs::data_local_dir()
         .unwrap_or_else(|| PathBuf::from("."))
         .join("com.wakascribe.app");
 
     std::fs::create_dir_all(&app_dir).ok();
-    app_dir.join("wakascribe.db")
+    app_dir
+}
+
+fn default_db_path() -> PathBuf {
+    app_dir().join(DB_FILE_NAME)
+}
+
+fn get_db_path() -> PathBuf {
+    match fs::read_to_string(app_dir().join(LOCATION_FILE_NAME)) {
+        Ok(location) if !location.trim().is_empty() => PathBuf::from(location.trim()),
+        _ => default_db_path(),
+    }
 }
 
 pub fn init_database() -> Result<()> {
     let db_path = get_db_path();
     info!("Initializing database at {:?}", db_path);
 
+    if let Some(parent) = db_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
     let conn = Connection::open(&db_path)?;
 
-    // Run migrations
+    migrate(&conn)?;
+    enforce_foreign_keys(&conn)?;
+
+    DB.set(SqliteStore::new(conn))
+        .map_err(|_| AppError::InvalidState("Database already initialized".into()))?;
+    *DB_PATH.lock() = Some(db_path);
+
+    Ok(())
+}
+
+/// A numbered SQL file, applied once in a transaction (no BEGIN/COMMIT inside)
+struct Migration {
+    version: u32,
+    name: &'static str,
+    sql: &'static str,
+}
+
+/// Last version of the schema built by `apply_baseline`
+const BASELINE_VERSION: u32 = 8;
+
+/// Migrations after the baseline, in version order: new schema changes go here
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 9,
+        name: "trash_index",
+        sql: include_str!("../../migrations/009_trash_index.sql"),
+    },
+    Migration {
+        version: 10,
+        name: "recording_audio",
+        sql: include_str!("../../migrations/010_recording_audio.sql"),
+    },
+    Migration {
+        version: 11,
+        name: "transcription_revisions",
+        sql: include_str!("../../migrations/011_transcription_revisions.sql"),
+    },
+    Migration {
+        version: 12,
+        name: "segment_language",
+        sql: include_str!("../../migrations/012_segment_language.sql"),
+    },
+    Migration {
+        version: 13,
+        name: "transcription_translations",
+        sql: include_str!("../../migrations/013_transcription_translations.sql"),
+    },
+    Migration {
+        version: 14,
+        name: "transcription_notes",
+        sql: include_str!("../../migrations/014_transcription_notes.sql"),
+    },
+    Migration {
+        version: 15,
+        name: "tags",
+        sql: include_str!("../../migrations/015_tags.sql"),
+    },
+    Migration {
+        version: 16,
+        name: "transcription_title",
+        sql: include_str!("../../migrations/016_transcription_title.sql"),
+    },
+    Migration {
+        version: 17,
+        name: "transcription_pauses",
+        sql: include_str!("../../migrations/017_transcription_pauses.sql"),
+    },
+    Migration {
+        version: 18,
+        name: "pending_transcriptions",
+        sql: include_str!("../../migrations/018_pending_transcriptions.sql"),
+    },
+    Migration {
+        version: 19,
+        name: "transcription_drafts",
+        sql: include_str!("../../migrations/019_transcription_drafts.sql"),
+    },
+    Migration {
+        version: 20,
+        name: "deleted_transcriptions",
+        sql: include_str!("../../migrations/020_deleted_transcriptions.sql"),
+    },
+];
+
+/// Create the tables, or bring those of an older version up to date
+///
+/// Applied versions are recorded in `schema_migrations`. Databases from
+/// before the table existed (and new ones) first go through the baseline,
+/// which detects what is already there; the numbered migrations after it
+/// are then applied in order, each one once.
+pub(super) fn migrate(conn: &Connection) -> Result<()> {
+    // Rebuilding a table drops the old one: with foreign keys enforced, that
+    // would delete the segments and texts pointing to it (ON DELETE CASCADE)
+    let foreign_keys: bool = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0))?;
+    conn.execute_batch("PRAGMA foreign_keys = OFF")?;
+    let migrated = apply_migrations(conn, MIGRATIONS);
+    if foreign_keys {
+        conn.execute_batch("PRAGMA foreign_keys = ON")?;
+    }
+    migrated
+}
+
+/// Turn on the foreign keys of a migrated connection, so that deleting a
+/// transcription cascades to its segments and text (SQLite leaves them off
+/// on every new connection)
+pub(super) fn enforce_foreign_keys(conn: &Connection) -> Result<()> {
+    conn.execute_batch("PRAGMA foreign_keys = ON")?;
+    Ok(())
+}
+
+fn apply_migrations(conn: &Connection, migrations: &[Migration]) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        )",
+    )?;
+    let mut current = schema_version(conn)?;
+
+    if current < BASELINE_VERSION {
+        apply_baseline(conn)?;
+        record_migration(conn, BASELINE_VERSION, "baseline")?;
+        current = BASELINE_VERSION;
+    }
+
+    for migration in migrations {
+        if migration.version <= current {
+            continue;
+        }
+        info!("Applying migration {:03}_{}", migration.version, migration.name);
+        let tx = conn.unchecked_transaction()?;
+        tx.execute_batch(migration.sql)?;
+        record_migration(&tx, migration.version, migration.name)?;
+        tx.commit()?;
+        current = migration.version;
+    }
+
+    let latest = migrations.last().map_or(BASELINE_VERSION, |m| m.version);
+    if current > latest {
+        warn!(
+            "Database schema version {} is newer than this app's ({}): some data may not be shown",
+            current, latest
+        );
+    }
+    Ok(())
+}
+
+/// Highest migration applied (0 for a new database, or one from before `schema_migrations`)
+fn schema_version(conn: &Connection) -> Result<u32> {
+    Ok(conn.query_row("SELECT COALESCE(MAX(version), 0) FROM schema_migrations", [], |row| {
+        row.get(0)
+    })?)
+}
+
+fn record_migration(conn: &Connection, version: u32, name: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO schema_migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+        params![version, name, chrono::Utc::now().to_rfc3339()],
+    )?;
+    Ok(())
+}
+
+/// Schema up to `BASELINE_VERSION`, from scratch or from any earlier state
+///
+/// Every step checks what it changes, as these ran at each startup before
+/// versions were recorded.
+fn apply_baseline(conn: &Connection) -> Result<()> {
     conn.execute_batch(include_str!("../../migrations/001_init.sql"))?;
+    conn.execute_batch(include_str!("../../migrations/002_profiles.sql"))?;
+    add_column_if_missing(conn, "transcriptions", "profile_id", "TEXT")?;
+    conn.execute_batch(include_str!("../../migrations/003_chunk_cache.sql"))?;
+    if !column_exists(conn, "transcriptions", "source_path")? {
+        info!("Migrating transcriptions to typed sources");
+        conn.execute_batch(include_str!("../../migrations/004_transcription_source.sql"))?;
+    }
+    add_column_if_missing(conn, "transcriptions", "action_items", "TEXT")?;
+    add_column_if_missing(conn, "transcriptions", "markers", "TEXT")?;
+    conn.execute_batch(include_str!("../../migrations/005_job_errors.sql"))?;
+    if !column_exists(conn, "transcriptions", "preview")? {
+        info!("Moving transcription texts to transcription_content");
+        conn.execute_batch(include_str!("../../migrations/006_transcription_content.sql"))?;
+    }
+    add_column_if_missing(conn, "transcriptions", "is_draft", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "transcriptions", "capture", "TEXT")?;
+    add_column_if_missing(conn, "segments", "speaker", "TEXT")?;
+    add_column_if_missing(conn, "segments", "words", "TEXT")?;
+    add_column_if_missing(conn, "transcriptions", "confidence", "REAL")?;
+    add_column_if_missing(conn, "transcriptions", "deleted_at", "TEXT")?;
+    conn.execute_batch(include_str!("../../migrations/007_inference_speed.sql"))?;
+    if !table_exists(conn, "transcription_fts")? {
+        info!("Indexing transcriptions for full-text search");
+        conn.execute_batch(include_str!("../../migrations/008_search.sql"))?;
+    }
+
+    Ok(())
+}
+
+/// Open an existing database (e.g. a copied snapshot) without write access
+///
+/// Migrations are not run, so the snapshot must come from the same app version.
+pub fn init_database_read_only(db_path: &Path) -> Result<()> {
+    info!("Opening database read-only at {:?}", db_path);
+
+    if !db_path.exists() {
+        return Err(AppError::NotFound(format!("Database not found: {:?}", db_path)));
+    }
+
+    let conn = Connection::open_with_flags(
+        db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
 
-    DB.set(Mutex::new(conn))
+    DB.set(SqliteStore::new(conn))
         .map_err(|_| AppError::InvalidState("Database already initialized".into()))?;
+    READ_ONLY.store(true, Ordering::SeqCst);
+    *DB_PATH.lock() = Some(db_path.to_path_buf());
+
+    Ok(())
+}
+
+/// Whether the app runs in read-only review mode
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::SeqCst)
+}
+
+/// Reject modifications (recording, edits, settings) in read-only mode
+pub fn ensure_writable() -> Result<()> {
+    if is_read_only() {
+        return Err(AppError::InvalidState(
+            "WakaScribe is in read-only mode".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// File of the open database, if any
+pub fn database_path() -> Option<PathBuf> {
+    DB_PATH.lock().clone()
+}
+
+/// Move the database to `target` (a file, or a directory to put `wakascribe.db` in;
+/// `None` for the app data directory) and continue on the new file
+///
+/// The copy is made with `VACUUM INTO` while the connection is locked, so it
+/// is consistent and no write can slip in between; it is checked, recorded as
+/// the location to open at startup, and only then replaces the connection.
+/// The old file is removed last: a failure at any earlier step leaves the
+/// app on the old database. Moving back to the app data directory forgets
+/// the custom location.
+///
+/// SQLite relies on file locks, which some network drives don't honor: a
+/// shared database must not be opened by two machines at once.
+pub fn move_database(target: Option<&Path>) -> Result<PathBuf> {
+    ensure_writable()?;
+
+    let target = target.map(Path::to_path_buf).unwrap_or_else(default_db_path);
+    if !target.is_absolute() {
+        return Err(AppError::InvalidInput(format!(
+            "Database path must be absolute: {:?}",
+            target
+        )));
+    }
+    let target = if target.is_dir() {
+        target.join(DB_FILE_NAME)
+    } else {
+        target
+    };
+    let current =
+        database_path().ok_or_else(|| AppError::InvalidState("Database not initialized".into()))?;
+    if target == current {
+        return Ok(target);
+    }
+    if target.exists() {
+        return Err(AppError::InvalidInput(format!(
+            "A file already exists at {:?}",
+            target
+        )));
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let db = DB
+        .get()
+        .ok_or_else(|| AppError::InvalidState("Database not initialized".into()))?;
+    let mut conn = db.conn.lock();
+
+    info!("Moving database from {:?} to {:?}", current, target);
+    relocate(&mut conn, &current, &target, record_location)?;
+    *DB_PATH.lock() = Some(target.clone());
+    Ok(target)
+}
+
+/// Copy the database open in `conn` (at `current`) to `target`, record the
+/// new location and switch `conn` to it, then remove the old files. Until
+/// the switch any failure leaves `conn` and `current` as they were
+fn relocate(
+    conn: &mut Connection,
+    current: &Path,
+    target: &Path,
+    record: impl FnOnce(&Path) -> Result<()>,
+) -> Result<()> {
+    let mut copy = target.to_path_buf().into_os_string();
+    copy.push(".moving");
+    let copy = PathBuf::from(copy);
+    let _ = fs::remove_file(&copy);
+
+    let moved = copy_database(conn, &copy).and_then(|_| {
+        fs::rename(&copy, target)?;
+        let moved = Connection::open(target)?;
+        enforce_foreign_keys(&moved)?;
+        record(target)?;
+        Ok(moved)
+    });
+    let moved = match moved {
+        Ok(moved) => moved,
+        Err(e) => {
+            let _ = fs::remove_file(&copy);
+            let _ = fs::remove_file(target);
+            return Err(e);
+        }
+    };
+
+    // Close the old connection before removing its files
+    drop(std::mem::replace(conn, moved));
+
+    for suffix in ["", "-wal", "-shm", "-journal"] {
+        let mut file = current.as_os_str().to_owned();
+        file.push(suffix);
+        let file = PathBuf::from(file);
+        if file.exists() {
+            if let Err(e) = fs::remove_file(&file) {
+                warn!("Failed to remove old database file {:?}: {}", file, e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Write a consistent copy of the database to `path` and check it
+fn copy_database(conn: &Connection, path: &Path) -> Result<()> {
+    conn.execute("VACUUM INTO ?1", [path.to_string_lossy()])?;
+
+    let copy = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let check: String = copy.query_row("PRAGMA quick_check", [], |row| row.get(0))?;
+    if check != "ok" {
+        return Err(AppError::InvalidState(format!(
+            "Copied database failed its check: {}",
+            check
+        )));
+    }
+    Ok(())
+}
+
+/// Remember where the database is for the next start
+fn record_location(path: &Path) -> Result<()> {
+    let location_file = app_dir().join(LOCATION_FILE_NAME);
+    if path == default_db_path() {
+        if location_file.exists() {
+            fs::remove_file(&location_file)?;
+        }
+        return Ok(());
+    }
 
+    let temp = location_file.with_extension("tmp");
+    fs::write(&temp, path.to_string_lossy().as_bytes())?;
+    fs::rename(&temp, &location_file)?;
     Ok(())
 }
 
+/// Add a column to an existing table unless it is already there
+/// (SQLite has no `ADD COLUMN IF NOT EXISTS`)
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, decl: &str) -> Result<()> {
+    if !column_exists(conn, table, column)? {
+        info!("Adding column {}.{}", table, column);
+        conn.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, decl))?;
+    }
+
+    Ok(())
+}
+
+/// Storage of the transcriptions and settings
+pub fn store() -> Result<&'static dyn Store> {
+    DB.get()
+        .map(|db| db as &dyn Store)
+        .ok_or_else(|| AppError::InvalidState("Database not initialized".into()))
+}
+
 pub fn with_db<F, T>(f: F) -> Result<T>
 where
     F: FnOnce(&Connection) -> Result<T>,
@@ -38,7 +451,7 @@ where
     let db = DB
         .get()
         .ok_or_else(|| AppError::InvalidState("Database not initialized".into()))?;
-    let conn = db.lock();
+    let conn = db.conn.lock();
     f(&conn)
 }
 
@@ -49,10 +462,28 @@ where
     let db = DB
         .get()
         .ok_or_else(|| AppError::InvalidState("Database not initialized".into()))?;
-    let mut conn = db.lock();
+    let mut conn = db.conn.lock();
     f(&mut conn)
 }
 
+fn column_exists(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|name| name.ok())
+        .any(|name| name == column);
+    Ok(exists)
+}
+
+fn table_exists(conn: &Connection, table: &str) -> Result<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [table],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
 // Add dirs dependency for cross-platform paths
 mod dirs {
     use std::path::PathBuf;
@@ -82,3 +513,165 @@ mod dirs {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions(conn: &Connection) -> Vec<u32> {
+        let mut stmt = conn.prepare("SELECT version FROM schema_migrations ORDER BY version").unwrap();
+        let rows = stmt.query_map([], |row| row.get(0)).unwrap();
+        rows.map(|v| v.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        let mut previous = BASELINE_VERSION;
+        for migration in MIGRATIONS {
+            assert!(migration.version > previous, "{} out of order", migration.name);
+            previous = migration.version;
+        }
+    }
+
+    #[test]
+    fn test_migrate_records_versions_once() {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn).unwrap();
+        migrate(&conn).unwrap();
+        assert_eq!(versions(&conn), [8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20]);
+
+        let extra = [Migration {
+            version: 21,
+            name: "extra",
+            sql: "ALTER TABLE settings ADD COLUMN extra TEXT;",
+        }];
+        apply_migrations(&conn, &extra).unwrap();
+        // Already applied: the ALTER would fail if run again
+        apply_migrations(&conn, &extra).unwrap();
+        assert_eq!(versions(&conn), [8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21]);
+        assert!(column_exists(&conn, "settings", "extra").unwrap());
+    }
+
+    #[test]
+    fn test_migrate_upgrades_first_schema() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("../../migrations/001_init.sql")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO transcriptions (id, created_at, updated_at, source_type, raw_text)
+             VALUES ('a', '2025-01-01', '2025-01-01', 'dictation', 'ancien texte');
+             INSERT INTO segments (id, transcription_id, start_ms, end_ms, text)
+             VALUES ('s', 'a', 0, 1000, 'ancien texte');",
+        )
+        .unwrap();
+
+        migrate(&conn).unwrap();
+        assert_eq!(versions(&conn), [8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20]);
+        let (preview, raw_text): (String, String) = conn
+            .query_row(
+                "SELECT t.preview, c.raw_text FROM transcriptions t
+                 JOIN transcription_content c ON c.transcription_id = t.id WHERE t.id = 'a'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((preview.as_str(), raw_text.as_str()), ("ancien texte", "ancien texte"));
+        assert!(column_exists(&conn, "transcriptions", "deleted_at").unwrap());
+        let segments: i64 = conn.query_row("SELECT COUNT(*) FROM segments", [], |row| row.get(0)).unwrap();
+        assert_eq!(segments, 1);
+    }
+
+    #[test]
+    fn test_deleting_a_transcription_cascades() {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn).unwrap();
+        enforce_foreign_keys(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO transcriptions (id, created_at, updated_at, source_type)
+             VALUES ('a', '2025-01-01', '2025-01-01', 'dictation');
+             INSERT INTO transcription_content (transcription_id, raw_text) VALUES ('a', 'texte');
+             INSERT INTO segments (id, transcription_id, start_ms, end_ms, text)
+             VALUES ('s', 'a', 0, 1000, 'texte');",
+        )
+        .unwrap();
+        // Migrating again must not drop the rows through the cascade
+        migrate(&conn).unwrap();
+
+        conn.execute("DELETE FROM transcriptions WHERE id = 'a'", []).unwrap();
+        let count = |table: &str| -> i64 {
+            conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+                .unwrap()
+        };
+        assert_eq!((count("segments"), count("transcription_content")), (0, 0));
+        // An orphan segment is refused
+        assert!(conn
+            .execute(
+                "INSERT INTO segments (id, transcription_id, start_ms, end_ms, text)
+                 VALUES ('t', 'absent', 0, 1000, 'texte')",
+                [],
+            )
+            .is_err());
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("wakascribe-move-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn open_with_row(path: &Path) -> Connection {
+        let conn = Connection::open(path).unwrap();
+        conn.execute_batch("CREATE TABLE t (text TEXT); INSERT INTO t VALUES ('garde');").unwrap();
+        conn
+    }
+
+    fn row(conn: &Connection) -> String {
+        conn.query_row("SELECT text FROM t", [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_relocate_moves_and_switches() {
+        let dir = temp_dir("ok");
+        let current = dir.join(DB_FILE_NAME);
+        let target = dir.join("partage").join(DB_FILE_NAME);
+        fs::create_dir_all(target.parent().unwrap()).unwrap();
+        let mut conn = open_with_row(&current);
+
+        let mut recorded = None;
+        relocate(&mut conn, &current, &target, |path| {
+            recorded = Some(path.to_path_buf());
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(recorded.as_deref(), Some(target.as_path()));
+        assert_eq!(conn.path().map(PathBuf::from), Some(target.clone()));
+        assert_eq!(row(&conn), "garde");
+        assert!(!current.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_failed_relocate_keeps_the_database() {
+        let dir = temp_dir("failed");
+        let current = dir.join(DB_FILE_NAME);
+        let mut conn = open_with_row(&current);
+
+        // Copy fails: the target folder does not exist
+        let missing = dir.join("absent").join(DB_FILE_NAME);
+        assert!(relocate(&mut conn, &current, &missing, |_| Ok(())).is_err());
+
+        // Location not recorded: the copy is removed again
+        let target = dir.join("nouvelle.db");
+        let result = relocate(&mut conn, &current, &target, |_| Err(AppError::InvalidState("read-only".into())));
+        assert!(matches!(result, Err(AppError::InvalidState(_))));
+        assert!(!target.exists());
+        assert!(!dir.join("nouvelle.db.moving").exists());
+
+        // Still on the old database, which is intact
+        assert_eq!(conn.path().map(PathBuf::from), Some(current.clone()));
+        conn.execute("INSERT INTO t VALUES ('apres')", []).unwrap();
+        assert!(current.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
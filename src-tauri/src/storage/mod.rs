@@ -1,7 +1,19 @@
+pub mod backup;
 pub mod database;
 pub mod models;
+pub mod preset;
 pub mod queries;
+pub mod repo;
+pub mod search;
+pub mod sqlite;
 
-pub use database::{init_database, with_db};
+pub use backup::{export_database, import_database};
+pub use database::{
+    database_path, ensure_writable, init_database, init_database_read_only, is_read_only,
+    move_database, store, with_db,
+};
 pub use models::*;
+pub use preset::{export_preset, import_preset};
 pub use queries::*;
+pub use repo::{SettingsRepo, Store, TranscriptionRepo};
+pub use search::search_transcriptions;
@@ -1,4 +1,19 @@
+use crate::audio::ducking::DEFAULT_DUCK_LEVEL;
+use crate::audio::{Denoise, DualSourceMode, Normalization};
+use crate::engine::merger::{ChunkJoin, DEFAULT_SENTENCE_GAP_MS};
+use crate::engine::{
+    DecodingConfig, DecodingProfile, ExecutionProviderChoice, LanguageDecoding, OpenVinoPerformance,
+    TranscriptionLanguage,
+};
+use crate::error::ErrorCode;
+use crate::hooks::PostTranscriptionHook;
+use crate::postprocess::summary::LlmSettings;
+use crate::postprocess::translation::TranslationSettings;
+use crate::power::PowerMode;
+use crate::sync::SyncSettings;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -8,6 +23,25 @@ pub struct Segment {
     pub end_ms: i64,
     pub text: String,
     pub confidence: f64,
+    /// Who spoke, for dual-source recordings ("me" or "them")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+    /// Language identified in this segment ("fr", "en"), in Auto mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Words with their confidence, to point out the doubtful ones (empty without token scores)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<SegmentWord>,
+}
+
+/// Word of a segment, with the decoder's confidence in it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SegmentWord {
+    pub text: String,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub confidence: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,14 +50,458 @@ pub struct Transcription {
     pub id: String,
     pub created_at: String,
     pub updated_at: String,
-    pub source_type: String, // "dictation" or "file"
+    pub source_type: TranscriptionSource,
     pub source_name: Option<String>,
+    /// Given by the user (dictations have no source name)
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Full path of the transcribed file (file/import sources)
+    #[serde(default)]
+    pub source_path: Option<String>,
+    /// Sample rate of the audio before resampling to 16kHz
+    #[serde(default)]
+    pub original_sample_rate: Option<u32>,
     pub duration_ms: i64,
     pub language: String,
     pub segments: Vec<Segment>,
     pub raw_text: String,
     pub edited_text: Option<String>,
     pub is_edited: bool,
+    /// Partial text saved while recording, kept when the final transcription failed
+    #[serde(default)]
+    pub is_draft: bool,
+    /// Decoding issues detected for this transcription (not persisted)
+    #[serde(default)]
+    pub warnings: Vec<TranscriptionWarning>,
+    /// Gain applied by level normalization before transcription (not persisted)
+    #[serde(default)]
+    pub normalization_gain: Option<f32>,
+    /// Actions and decisions found in the text (minute-taker output)
+    #[serde(default)]
+    pub action_items: Vec<ActionItem>,
+    /// Moments flagged by the user while recording
+    #[serde(default)]
+    pub markers: Vec<RecordingMarker>,
+    /// Pauses of the recording, also "[pause]" segments
+    #[serde(default)]
+    pub pauses: Vec<RecordingPause>,
+    /// Device and audio fingerprint, for the provenance of exports
+    #[serde(default)]
+    pub capture: Option<CaptureInfo>,
+    /// Mean confidence of the decoded words (None without token scores)
+    #[serde(default)]
+    pub confidence: Option<f64>,
+    /// Audio file of the recording, when recordings are kept
+    #[serde(default)]
+    pub audio_path: Option<String>,
+    /// Text translated into other languages, one per target language
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub translations: Vec<Translation>,
+    /// Summaries generated by the LLM, one per style
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<TranscriptionNotes>,
+    /// Folder (client, project) it is filed in
+    #[serde(default)]
+    pub folder: Option<String>,
+    /// Names of its tags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// When it was moved to the trash (kept through sync and backups)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<String>,
+}
+
+/// Transcription metadata and text preview, for list views
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionSummary {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub source_type: TranscriptionSource,
+    pub source_name: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    pub source_path: Option<String>,
+    pub duration_ms: i64,
+    pub language: String,
+    /// Start of the edited (or raw) text
+    pub preview: String,
+    pub is_edited: bool,
+    pub is_draft: bool,
+    /// When it was moved to the trash (trash listing only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<String>,
+    #[serde(default)]
+    pub folder: Option<String>,
+    /// Names of its tags, sorted
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Label of the active profile's transcriptions, several per transcription
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tag {
+    pub id: String,
+    pub name: String,
+    /// CSS color of the badge
+    pub color: Option<String>,
+    /// Tagged transcriptions outside the trash
+    pub count: i64,
+}
+
+/// Folder in use and the number of transcriptions filed in it (trash excluded)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Folder {
+    pub name: String,
+    pub count: i64,
+}
+
+/// Position in the history list: the last transcription of the previous page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryCursor {
+    pub created_at: String,
+    pub id: String,
+}
+
+/// Restrictions of a history search (dates are local, YYYY-MM-DD, inclusive)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchFilters {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub source_type: Option<TranscriptionSource>,
+    pub language: Option<String>,
+    /// Id of a tag the transcriptions must have
+    pub tag: Option<String>,
+    pub folder: Option<String>,
+    /// Results returned (default 50)
+    pub limit: Option<usize>,
+}
+
+/// Transcription matching a history search
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    pub transcription: TranscriptionSummary,
+    /// Relevance, higher is better
+    pub score: f64,
+    /// Text around the best match, matched words in <mark></mark>
+    pub snippet: String,
+    /// Start of the best matching segment (None when the match is in the full text)
+    pub start_ms: Option<i64>,
+}
+
+/// Full text of a transcription
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionText {
+    pub raw_text: String,
+    pub edited_text: Option<String>,
+}
+
+/// Where a transcription's audio came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptionSource {
+    /// Microphone recording
+    Dictation,
+    /// Audio file picked by the user
+    File,
+    /// Live audio stream
+    Stream,
+    /// Transcript imported from elsewhere
+    Import,
+    /// Meeting recording
+    Meeting,
+}
+
+impl TranscriptionSource {
+    /// Value stored in the `source_type` column (same as the serialized form)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TranscriptionSource::Dictation => "dictation",
+            TranscriptionSource::File => "file",
+            TranscriptionSource::Stream => "stream",
+            TranscriptionSource::Import => "import",
+            TranscriptionSource::Meeting => "meeting",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "dictation" => Some(TranscriptionSource::Dictation),
+            "file" => Some(TranscriptionSource::File),
+            "stream" => Some(TranscriptionSource::Stream),
+            "import" => Some(TranscriptionSource::Import),
+            "meeting" => Some(TranscriptionSource::Meeting),
+            _ => None,
+        }
+    }
+}
+
+impl ToSql for TranscriptionSource {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for TranscriptionSource {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let s = value.as_str()?;
+        TranscriptionSource::parse(s)
+            .ok_or_else(|| FromSqlError::Other(format!("Unknown source type: {}", s).into()))
+    }
+}
+
+/// Kind of a minute-taker item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActionItemKind {
+    Action,
+    Decision,
+}
+
+/// Transcriptions of one day, for the activity heat map
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityDay {
+    /// Local date, YYYY-MM-DD
+    pub date: String,
+    pub count: i64,
+    pub minutes: f64,
+}
+
+/// Result of saving an edited text
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextUpdate {
+    pub action_items: Vec<ActionItem>,
+    /// To send as `expected_updated_at` with the next save
+    pub updated_at: String,
+}
+
+/// One saved version of a transcription's text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionRevision {
+    /// 1 for the first edit, and so on; 0 is the transcribed text
+    pub revision: i64,
+    pub text: String,
+    pub created_at: String,
+}
+
+/// Edited text not saved yet, kept by the editor as it is typed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextDraft {
+    pub transcription_id: String,
+    pub text: String,
+    /// `updated_at` of the transcription the edit started from
+    pub base_updated_at: String,
+    pub saved_at: String,
+}
+
+/// Transcription text translated into another language
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Translation {
+    /// Target language code ("en", "de", ...)
+    pub language: String,
+    pub text: String,
+    pub created_at: String,
+}
+
+/// Kind of summary asked of the LLM
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryStyle {
+    /// A few sentences
+    #[default]
+    Brief,
+    /// Section by section
+    Detailed,
+    /// Meeting minutes: decisions and actions first
+    Minutes,
+}
+
+impl SummaryStyle {
+    /// Value stored in the `style` column (same as the serialized form)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SummaryStyle::Brief => "brief",
+            SummaryStyle::Detailed => "detailed",
+            SummaryStyle::Minutes => "minutes",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "brief" => Some(SummaryStyle::Brief),
+            "detailed" => Some(SummaryStyle::Detailed),
+            "minutes" => Some(SummaryStyle::Minutes),
+            _ => None,
+        }
+    }
+}
+
+impl ToSql for SummaryStyle {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for SummaryStyle {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let s = value.as_str()?;
+        SummaryStyle::parse(s).ok_or_else(|| FromSqlError::Other(format!("Unknown summary style: {}", s).into()))
+    }
+}
+
+/// Summary, key points and actions generated by the LLM from a transcription
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionNotes {
+    pub style: SummaryStyle,
+    pub summary: String,
+    #[serde(default)]
+    pub key_points: Vec<String>,
+    #[serde(default)]
+    pub action_items: Vec<String>,
+    /// Model that wrote them, as reported by the endpoint
+    #[serde(default)]
+    pub model: Option<String>,
+    pub created_at: String,
+}
+
+/// Sentence of a transcription that records something to do or something decided
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionItem {
+    pub kind: ActionItemKind,
+    pub text: String,
+    /// Person the action is assigned to ("Marie va envoyer...")
+    pub owner: Option<String>,
+    /// Due date as said ("avant vendredi", "by Monday")
+    pub due: Option<String>,
+}
+
+/// Background job that can fail without a command waiting for it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    /// Model loading at startup
+    ModelLoad,
+    WatchFolder,
+    ScheduledRecording,
+    /// Second transcription pass improving a first result
+    Refinement,
+    /// User command run after a transcription
+    Hook,
+}
+
+impl JobKind {
+    /// Value stored in the `job` column (same as the serialized form)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobKind::ModelLoad => "model_load",
+            JobKind::WatchFolder => "watch_folder",
+            JobKind::ScheduledRecording => "scheduled_recording",
+            JobKind::Refinement => "refinement",
+            JobKind::Hook => "hook",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "model_load" => Some(JobKind::ModelLoad),
+            "watch_folder" => Some(JobKind::WatchFolder),
+            "scheduled_recording" => Some(JobKind::ScheduledRecording),
+            "refinement" => Some(JobKind::Refinement),
+            "hook" => Some(JobKind::Hook),
+            _ => None,
+        }
+    }
+}
+
+impl ToSql for JobKind {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for JobKind {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let s = value.as_str()?;
+        JobKind::parse(s).ok_or_else(|| FromSqlError::Other(format!("Unknown job kind: {}", s).into()))
+    }
+}
+
+/// Failure of a background job, persisted and sent as a `job-failed` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobError {
+    pub id: String,
+    pub job: JobKind,
+    pub code: ErrorCode,
+    pub message: String,
+    /// What the job was working on (file path, schedule name...)
+    pub context: Option<String>,
+    pub occurred_at: String,
+}
+
+/// Result of one chunk of a file transcription still in progress
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingChunk {
+    pub index: usize,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    /// Empty for silence
+    pub text: String,
+    /// Ends a word cut by the previous chunk
+    #[serde(default)]
+    pub continues_word: bool,
+}
+
+/// File transcription not saved yet: interrupted if the app stopped meanwhile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingTranscription {
+    pub id: String,
+    pub source_path: String,
+    pub source_name: Option<String>,
+    /// Options of the transcription, to resume it the same way
+    pub language: Option<TranscriptionLanguage>,
+    pub decoding_config: Option<DecodingConfig>,
+    pub normalization: Option<Normalization>,
+    /// 0 until the first chunk is done (and for audio short enough for one inference)
+    pub chunk_count: usize,
+    /// In chunk order
+    pub chunks: Vec<PendingChunk>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Measured speed of one transcription, for job time estimates
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InferenceSpeed {
+    pub beam_width: usize,
+    pub audio_ms: i64,
+    pub elapsed_ms: i64,
+}
+
+/// Non-fatal decoding issue (e.g. truncated output) reported with a transcription
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionWarning {
+    /// Stable identifier: "max_symbols_per_frame", "blank_collapse", "partial"
+    pub code: String,
+    pub message: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +513,233 @@ pub struct Settings {
     pub shortcuts: ShortcutSettings,
     /// Inference engine backend: "openvino", "onnxruntime", or "coreml" (macOS only)
     pub engine_backend: String,
+    /// Text replacements applied to every transcription
+    #[serde(default)]
+    pub substitution_rules: Vec<SubstitutionRule>,
+    /// Custom vocabulary (names, acronyms, jargon) to bias post-processing towards
+    #[serde(default)]
+    pub vocabulary: Vec<String>,
+    /// Languages whose downloaded post-processing pack is active
+    #[serde(default)]
+    pub enabled_language_packs: Vec<String>,
+    /// Language and decoding parameters used when a transcription command gets none
+    #[serde(default)]
+    pub transcription: TranscriptionDefaults,
+    /// Format transcripts as question/answer pairs (interviews)
+    #[serde(default)]
+    pub interview_mode: bool,
+    /// In Auto language mode, bias decoding towards the UI language
+    #[serde(default = "default_auto_language_prior")]
+    pub auto_language_prior: bool,
+    /// Pacing of long transcriptions on battery (low-power mode)
+    #[serde(default)]
+    pub power_mode: PowerMode,
+    /// Inserted between the texts of consecutive chunks
+    #[serde(default = "default_chunk_separator")]
+    pub chunk_separator: String,
+    /// Silence (ms) between chunks that ends a sentence when no punctuation was transcribed (0 = off)
+    #[serde(default = "default_sentence_gap_ms")]
+    pub sentence_gap_ms: i64,
+    /// Pick blank penalty and temperature from the language (forced, or favored in Auto mode)
+    #[serde(default = "default_per_language_decoding")]
+    pub per_language_decoding: bool,
+    /// User values replacing the built-in per-language defaults
+    #[serde(default)]
+    pub language_decoding_overrides: HashMap<TranscriptionLanguage, LanguageDecoding>,
+    /// Decoding profiles saved by the user (the built-in ones are not stored)
+    #[serde(default)]
+    pub decoding_profiles: Vec<DecodingProfile>,
+    /// Name of the decoding profile chosen for each backend, applied when switching to it
+    #[serde(default)]
+    pub selected_decoding_profiles: HashMap<String, String>,
+    /// Opt-in encrypted mirror of the history on a WebDAV or S3 endpoint
+    #[serde(default)]
+    pub sync: SyncSettings,
+    /// Service used by `translate_transcription` (LibreTranslate-compatible API)
+    #[serde(default)]
+    pub translation: TranslationSettings,
+    /// Endpoint used by `summarize_transcription` (OpenAI-compatible or llama.cpp server)
+    #[serde(default)]
+    pub llm: LlmSettings,
+    /// Announce recording and transcription state through the OS text-to-speech
+    #[serde(default)]
+    pub spoken_feedback: bool,
+    /// URL of the `models.json` catalog offered for download
+    #[serde(default)]
+    pub model_catalog_url: Option<String>,
+    /// Commands run after each transcription (text on stdin and in a file)
+    #[serde(default)]
+    pub post_transcription_hooks: Vec<PostTranscriptionHook>,
+    /// End TXT/DOCX exports with provenance metadata (device, dates, audio hash)
+    #[serde(default)]
+    pub export_provenance: bool,
+    /// Keep the confirmed live text in the clipboard during dictation (experimental)
+    #[serde(default)]
+    pub live_paste: bool,
+    /// Hardware the ONNX Runtime backend runs on (falls back to the CPU)
+    #[serde(default)]
+    pub onnx_execution_provider: ExecutionProviderChoice,
+    /// Run the OpenVINO backend in a separate worker process, so a driver crash only fails the job
+    #[serde(default)]
+    pub openvino_isolated: bool,
+    /// Threads and hints of the OpenVINO backend (applied when the model loads)
+    #[serde(default)]
+    pub openvino_performance: OpenVinoPerformance,
+    /// Run a short inference once the model is loaded at startup (faster first transcription)
+    #[serde(default = "default_warmup_engine")]
+    pub warmup_engine: bool,
+    /// Second engine instance for file transcriptions, so a dictation does not wait for them (twice the model memory)
+    #[serde(default)]
+    pub engine_pool: bool,
+    /// Lower the system output volume while recording (speaker bleed into the microphone)
+    #[serde(default)]
+    pub duck_output: bool,
+    /// Output volume while ducked, in percent of its level before recording
+    #[serde(default = "default_duck_output_level")]
+    pub duck_output_level: u8,
+    /// Raise the microphone level while recording, so quiet speech is not taken for silence
+    #[serde(default)]
+    pub capture_agc: bool,
+    /// Recording length after which audio is no longer captured, in minutes (0: no limit)
+    #[serde(default = "default_max_recording_minutes")]
+    pub max_recording_minutes: u32,
+    /// Silence after which a recording is stopped and transcribed, in seconds (0: never)
+    #[serde(default)]
+    pub auto_stop_silence_secs: u32,
+    /// System audio recorded along with the microphone (None: microphone only)
+    #[serde(default)]
+    pub loopback_device_id: Option<String>,
+    /// Capture format chosen per input device id ("" for the system default)
+    #[serde(default)]
+    pub input_configs: HashMap<String, InputConfig>,
+    /// Mix both sources, or transcribe them apart labelled me/them
+    #[serde(default)]
+    pub dual_source_mode: DualSourceMode,
+    /// Dev: milliseconds added to each inference to exercise the UI on a fast machine (0: off)
+    #[serde(default)]
+    pub simulated_inference_delay_ms: u64,
+    /// Write the model inputs and outputs of each transcription under app data (debugging)
+    #[serde(default)]
+    pub debug_dump: bool,
+    /// Keep the audio of each recording (WAV in app data) for playback with its transcription
+    #[serde(default)]
+    pub keep_recordings: bool,
+    /// Shorten pauses over 2s before transcribing files (timestamps stay those of the file)
+    #[serde(default)]
+    pub skip_silence: bool,
+    /// Days a transcription stays in the trash before it is purged at startup (0: kept until emptied)
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    /// Database file in use; reported by `get_settings`, changed with `move_database` only
+    #[serde(default)]
+    pub database_path: Option<String>,
+}
+
+fn default_auto_language_prior() -> bool {
+    true
+}
+
+fn default_per_language_decoding() -> bool {
+    true
+}
+
+fn default_warmup_engine() -> bool {
+    true
+}
+
+fn default_chunk_separator() -> String {
+    ChunkJoin::default().separator
+}
+
+fn default_sentence_gap_ms() -> i64 {
+    DEFAULT_SENTENCE_GAP_MS
+}
+
+fn default_duck_output_level() -> u8 {
+    DEFAULT_DUCK_LEVEL
+}
+
+fn default_max_recording_minutes() -> u32 {
+    180
+}
+
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
+/// Replace a word or phrase (case-insensitive, whole words) in transcribed text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubstitutionRule {
+    pub from: String,
+    pub to: String,
+}
+
+/// Default language and decoding parameters of transcriptions
+///
+/// Same shape as the frontend transcription settings, whose other fields
+/// (live captions) are ignored here.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TranscriptionDefaults {
+    pub language: TranscriptionLanguage,
+    pub beam_width: usize,
+    pub temperature: f32,
+    pub blank_penalty: f32,
+    /// Noise reduction before level normalization
+    pub denoise: Denoise,
+    /// Level normalization of recordings stopped without one (auto-stop)
+    pub normalization: Normalization,
+}
+
+impl TranscriptionDefaults {
+    /// Decoding config with these parameters, the rest left to its defaults
+    pub fn decoding_config(&self) -> DecodingConfig {
+        DecodingConfig {
+            beam_width: self.beam_width,
+            temperature: self.temperature,
+            blank_penalty: self.blank_penalty,
+            ..DecodingConfig::default()
+        }
+    }
+}
+
+impl Default for TranscriptionDefaults {
+    fn default() -> Self {
+        let config = DecodingConfig::default();
+        Self {
+            language: TranscriptionLanguage::default(),
+            beam_width: config.beam_width,
+            temperature: config.temperature,
+            blank_penalty: config.blank_penalty,
+            denoise: Denoise::default(),
+            normalization: Normalization::default(),
+        }
+    }
+}
+
+/// Outcome of restoring a backup
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupImport {
+    /// Transcriptions added as they were
+    pub imported: usize,
+    /// Added under a new id: the id was taken by a different transcription
+    pub copied: usize,
+    /// Already present, unchanged
+    pub skipped: usize,
+    /// Settings in effect after the restore
+    pub settings: Settings,
+}
+
+/// Portable bundle of settings, rules, vocabulary and shortcuts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Preset {
+    /// Preset format version
+    pub version: u32,
+    pub exported_at: String,
+    pub settings: Settings,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,6 +748,9 @@ pub struct ShortcutSettings {
     pub toggle_recording: String,
     pub pause: String,
     pub copy: String,
+    /// Held to dictate into the focused application (empty: disabled)
+    #[serde(default)]
+    pub push_to_talk: String,
 }
 
 impl Default for Settings {
@@ -55,12 +763,71 @@ impl Default for Settings {
                 toggle_recording: "CommandOrControl+Shift+R".to_string(),
                 pause: "CommandOrControl+Shift+P".to_string(),
                 copy: "CommandOrControl+Shift+C".to_string(),
+                push_to_talk: String::new(),
             },
             engine_backend: "openvino".to_string(), // Default to OpenVINO
+            substitution_rules: Vec::new(),
+            vocabulary: Vec::new(),
+            enabled_language_packs: Vec::new(),
+            transcription: TranscriptionDefaults::default(),
+            interview_mode: false,
+            auto_language_prior: default_auto_language_prior(),
+            power_mode: PowerMode::default(),
+            chunk_separator: default_chunk_separator(),
+            sentence_gap_ms: default_sentence_gap_ms(),
+            per_language_decoding: default_per_language_decoding(),
+            language_decoding_overrides: HashMap::new(),
+            decoding_profiles: Vec::new(),
+            selected_decoding_profiles: HashMap::new(),
+            sync: SyncSettings::default(),
+            translation: TranslationSettings::default(),
+            llm: LlmSettings::default(),
+            spoken_feedback: false,
+            model_catalog_url: None,
+            post_transcription_hooks: Vec::new(),
+            export_provenance: false,
+            live_paste: false,
+            onnx_execution_provider: ExecutionProviderChoice::default(),
+            openvino_isolated: false,
+            openvino_performance: OpenVinoPerformance::default(),
+            warmup_engine: default_warmup_engine(),
+            engine_pool: false,
+            duck_output: false,
+            duck_output_level: DEFAULT_DUCK_LEVEL,
+            capture_agc: false,
+            max_recording_minutes: default_max_recording_minutes(),
+            auto_stop_silence_secs: 0,
+            loopback_device_id: None,
+            input_configs: HashMap::new(),
+            dual_source_mode: DualSourceMode::default(),
+            simulated_inference_delay_ms: 0,
+            debug_dump: false,
+            keep_recordings: false,
+            skip_silence: false,
+            trash_retention_days: default_trash_retention_days(),
+            database_path: None,
         }
     }
 }
 
+/// User profile: a named settings bundle with its own history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+    pub is_active: bool,
+}
+
+/// How the app was started
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppMode {
+    /// Database opened read-only (`--read-only <db path>`): no recording, no edits
+    pub read_only: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AudioDevice {
@@ -69,18 +836,157 @@ pub struct AudioDevice {
     pub is_default: bool,
 }
 
+/// What an input device can record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceCapabilities {
+    pub id: String,
+    /// Usual rates within those the device accepts
+    pub sample_rates: Vec<u32>,
+    pub default_sample_rate: u32,
+    /// Most channels of a stream
+    pub max_channels: u16,
+    pub default_channels: u16,
+    /// "f32", "i16"... (only f32 and i16 are recorded)
+    pub sample_formats: Vec<String>,
+    /// Frames per callback accepted, when the driver tells
+    pub min_buffer_size: Option<u32>,
+    pub max_buffer_size: Option<u32>,
+}
+
+/// Capture format chosen for an input device (unset: its default)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InputConfig {
+    pub sample_rate: Option<u32>,
+    /// Only this channel is recorded (0: first, left)
+    pub channel: Option<u16>,
+    /// Frames per callback, within what the device accepts
+    pub buffer_size: Option<u32>,
+}
+
+/// Snapshot of the current recording (computed from the capture buffer)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingStatus {
+    pub is_recording: bool,
+    pub is_paused: bool,
+    /// Recorded audio duration (pauses excluded)
+    pub elapsed_ms: u64,
+    /// Interleaved samples in the buffer
+    pub buffered_samples: u64,
+    /// Estimated buffer memory use
+    pub memory_bytes: u64,
+    /// Start of a long recording, moved to a temporary file
+    #[serde(default)]
+    pub disk_bytes: u64,
+}
+
+/// Moment flagged while recording ("important", "to check")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingMarker {
+    /// Position in the recorded audio (pauses excluded)
+    pub at_ms: i64,
+    pub label: String,
+}
+
+/// Recording paused and resumed: `duration_ms` of wall time not in the audio
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingPause {
+    /// Position in the recorded audio where it resumed (pauses excluded)
+    pub at_ms: i64,
+    pub duration_ms: i64,
+}
+
+/// How the audio of a transcription was captured
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureInfo {
+    /// Input device of a dictation (None: system default, or a file)
+    pub device: Option<String>,
+    /// SHA-256 of the source file, or of the captured samples for a dictation
+    pub audio_sha256: String,
+}
+
+/// Notice sent when the audio thread died and was restarted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioReset {
+    pub reason: String,
+    /// A recording was in progress (its audio is lost)
+    pub was_recording: bool,
+    /// Recording resumed on the same device with an empty buffer
+    pub recording_restarted: bool,
+    pub device_id: Option<String>,
+}
+
+/// Recent microphone levels of the current recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioWaveform {
+    /// Level of the last captured block, as `get_audio_level`
+    pub level: f32,
+    /// Values per second in `levels`
+    pub rate_hz: u32,
+    /// Oldest first, one minute at most (0 to 1)
+    pub levels: Vec<f32>,
+}
+
+/// Sent as "recording-auto-stopped" when a silent recording was stopped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingAutoStopped {
+    /// Silence that triggered the stop
+    pub silence_ms: u64,
+    /// The saved transcription, unless it failed
+    pub transcription: Option<Transcription>,
+    pub error: Option<String>,
+}
+
+/// Notice sent when a recording nears, then reaches, its length limit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingLimit {
+    pub max_ms: u64,
+    pub elapsed_ms: u64,
+    /// Audio is no longer captured (else: a warning)
+    pub reached: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TranscriptionProgress {
     pub current_ms: i64,
     pub total_ms: i64,
+    /// Audio transcribed per wall-clock time (2.0 = twice faster than realtime)
     pub speed_factor: f64,
+    /// Chunks done so far, out of `chunk_count` (0 when the audio isn't chunked)
+    #[serde(default)]
+    pub chunk_index: usize,
+    #[serde(default)]
+    pub chunk_count: usize,
+    /// Slowed down by the low-resource simulation (dev setting)
+    #[serde(default)]
+    pub simulated: bool,
 }
 
+/// Partial caption of a live transcription
+///
+/// `text` is the source language. Captions decoded by the engine have no
+/// `translated_text`; with a live translation language, final ones are sent
+/// again as "transcription-caption" events carrying both texts
+/// (`postprocess::translation::LiveTranslator`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StreamingSegment {
     pub text: String,
     pub is_final: bool,
     pub confidence: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translated_text: Option<String>,
+    /// Language of `translated_text` ("en"...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_language: Option<String>,
 }
@@ -0,0 +1,155 @@
+//! Portable preset files (settings, substitution rules, vocabulary, shortcuts)
+
+use crate::error::{AppError, Result};
+use crate::storage::models::{Preset, Settings};
+use crate::storage::queries::{get_settings, update_settings};
+use rusqlite::Connection;
+use std::io::Write;
+use std::path::Path;
+use tracing::info;
+
+/// Current preset file format version
+const PRESET_VERSION: u32 = 1;
+
+/// Leave out what must not travel with a shared file: the input device
+/// (machine-specific), the sync configuration, API keys and LLM endpoint
+/// (credentials, private hosts) and the post-transcription hooks (commands
+/// run on the receiving machine)
+pub(crate) fn clear_local_settings(settings: &mut Settings) {
+    settings.input_device_id = None;
+    settings.sync = Default::default();
+    settings.translation.api_key = String::new();
+    settings.llm.api_url = String::new();
+    settings.llm.api_key = String::new();
+    settings.post_transcription_hooks = Vec::new();
+}
+
+/// Put back the local values of what `clear_local_settings` leaves out, so
+/// an imported file can never change them
+pub(crate) fn keep_local_settings(settings: &mut Settings, current: Settings) {
+    settings.input_device_id = current.input_device_id;
+    settings.sync = current.sync;
+    settings.translation.api_key = current.translation.api_key;
+    settings.llm.api_url = current.llm.api_url;
+    settings.llm.api_key = current.llm.api_key;
+    settings.post_transcription_hooks = current.post_transcription_hooks;
+}
+
+/// Write the current settings to a preset file (without the local settings,
+/// see `clear_local_settings`)
+pub fn export_preset(conn: &Connection, path: &Path) -> Result<()> {
+    let mut settings = get_settings(conn)?;
+    clear_local_settings(&mut settings);
+
+    let preset = Preset {
+        version: PRESET_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        settings,
+    };
+
+    let json = serde_json::to_string_pretty(&preset)?;
+    crate::export::write_atomic(path, |file| Ok(file.write_all(json.as_bytes())?))?;
+    info!("Exported preset to {:?}", path);
+    Ok(())
+}
+
+/// Replace the current settings with those of a preset file
+///
+/// The local input device, credentials and hooks are kept. Returns the
+/// resulting settings.
+pub fn import_preset(conn: &Connection, path: &Path) -> Result<Settings> {
+    let content = std::fs::read_to_string(path)?;
+    let preset: Preset = serde_json::from_str(&content)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid preset file: {}", e)))?;
+
+    if preset.version > PRESET_VERSION {
+        return Err(AppError::InvalidInput(format!(
+            "Preset version {} is newer than supported version {}",
+            preset.version, PRESET_VERSION
+        )));
+    }
+
+    let mut settings = preset.settings;
+    keep_local_settings(&mut settings, get_settings(conn)?);
+    update_settings(conn, &settings)?;
+
+    info!("Imported preset from {:?}", path);
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::PostTranscriptionHook;
+    use crate::storage::database::migrate;
+
+    fn hook(program: &str) -> PostTranscriptionHook {
+        PostTranscriptionHook {
+            name: program.to_string(),
+            program: program.to_string(),
+            args: Vec::new(),
+            enabled: true,
+            timeout_secs: 30,
+        }
+    }
+
+    #[test]
+    fn test_preset_never_carries_hooks() {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn).unwrap();
+        let mut settings = get_settings(&conn).unwrap();
+        settings.post_transcription_hooks = vec![hook("local-script")];
+        update_settings(&conn, &settings).unwrap();
+
+        let path = std::env::temp_dir().join(format!("wakascribe-preset-{}.json", std::process::id()));
+        export_preset(&conn, &path).unwrap();
+        let exported: Preset = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(exported.settings.post_transcription_hooks.is_empty());
+
+        // A preset edited to run a program is imported without it
+        let mut shared = exported;
+        shared.settings.post_transcription_hooks = vec![hook("/tmp/payload")];
+        std::fs::write(&path, serde_json::to_string(&shared).unwrap()).unwrap();
+        let imported = import_preset(&conn, &path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let programs: Vec<String> = imported.post_transcription_hooks.iter().map(|h| h.program.clone()).collect();
+        assert_eq!(programs, ["local-script"]);
+        let stored = get_settings(&conn).unwrap();
+        assert_eq!(stored.post_transcription_hooks.len(), 1);
+        assert_eq!(stored.post_transcription_hooks[0].program, "local-script");
+    }
+
+    #[test]
+    fn test_preset_keeps_api_keys_local() {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn).unwrap();
+        let mut settings = get_settings(&conn).unwrap();
+        settings.translation.api_url = "http://localhost:5000".to_string();
+        settings.translation.api_key = "cle-locale".to_string();
+        settings.llm.api_url = "http://llm.local:8080/v1".to_string();
+        settings.llm.api_key = "cle-llm".to_string();
+        settings.llm.model = "mistral".to_string();
+        update_settings(&conn, &settings).unwrap();
+
+        let path = std::env::temp_dir().join(format!("wakascribe-preset-keys-{}.json", std::process::id()));
+        export_preset(&conn, &path).unwrap();
+        let exported = std::fs::read_to_string(&path).unwrap();
+        assert!(!exported.contains("cle-locale"));
+        assert!(!exported.contains("cle-llm"));
+        assert!(!exported.contains("llm.local"));
+
+        let mut shared: Preset = serde_json::from_str(&exported).unwrap();
+        assert_eq!(shared.settings.translation.api_url, "http://localhost:5000");
+        assert_eq!(shared.settings.llm.model, "mistral");
+        shared.settings.translation.api_key = "cle-partagee".to_string();
+        shared.settings.llm.api_url = "http://ailleurs.example/v1".to_string();
+        std::fs::write(&path, serde_json::to_string(&shared).unwrap()).unwrap();
+        let imported = import_preset(&conn, &path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(imported.translation.api_key, "cle-locale");
+        assert_eq!(imported.llm.api_url, "http://llm.local:8080/v1");
+        assert_eq!(imported.llm.api_key, "cle-llm");
+    }
+}
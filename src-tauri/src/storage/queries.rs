@@ -1,14 +1,25 @@
-use crate::error::Result;
-use crate::storage::models::{Segment, Settings, Transcription};
-use rusqlite::{params, Connection};
+use crate::error::{AppError, Result};
+use crate::storage::models::{
+    ActionItem, ActivityDay, Folder, HistoryCursor, InferenceSpeed, JobError, PendingChunk, PendingTranscription,
+    Profile, Segment, Settings, Tag, TextDraft,
+    Transcription, TranscriptionNotes, TranscriptionRevision, TranscriptionSummary, TranscriptionText, Translation,
+};
+use rusqlite::{params, Connection, OptionalExtension};
 
 // Transcription queries
 
+/// Characters of text kept in the `preview` column for list views
+const PREVIEW_CHARS: usize = 200;
+
+fn preview(text: &str) -> String {
+    text.chars().take(PREVIEW_CHARS).collect()
+}
+
 pub fn insert_transcription(conn: &Connection, t: &Transcription) -> Result<()> {
     conn.execute(
         r#"
-        INSERT INTO transcriptions (id, created_at, updated_at, source_type, source_name, duration_ms, language, raw_text, edited_text, is_edited)
-        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+        INSERT INTO transcriptions (id, created_at, updated_at, source_type, source_name, duration_ms, language, preview, is_edited, profile_id, source_path, original_sample_rate, action_items, markers, is_draft, capture, confidence, audio_path, folder, title, pauses, deleted_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22)
         "#,
         params![
             t.id,
@@ -18,32 +29,82 @@ pub fn insert_transcription(conn: &Connection, t: &Transcription) -> Result<()>
             t.source_name,
             t.duration_ms,
             t.language,
-            t.raw_text,
-            t.edited_text,
-            t.is_edited as i32
+            preview(t.edited_text.as_deref().unwrap_or(&t.raw_text)),
+            t.is_edited as i32,
+            active_profile_id(conn)?,
+            t.source_path,
+            t.original_sample_rate,
+            serde_json::to_string(&t.action_items)?,
+            serde_json::to_string(&t.markers)?,
+            t.is_draft as i32,
+            t.capture.as_ref().map(serde_json::to_string).transpose()?,
+            t.confidence,
+            t.audio_path,
+            t.folder,
+            t.title,
+            serde_json::to_string(&t.pauses)?,
+            t.deleted_at
         ],
     )?;
+    conn.execute(
+        "INSERT INTO transcription_content (transcription_id, raw_text, edited_text) VALUES (?1, ?2, ?3)",
+        params![t.id, t.raw_text, t.edited_text],
+    )?;
 
     // Insert segments
     for seg in &t.segments {
         conn.execute(
             r#"
-            INSERT INTO segments (id, transcription_id, start_ms, end_ms, text, confidence)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            INSERT INTO segments (id, transcription_id, start_ms, end_ms, text, confidence, speaker, words, language)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
             "#,
-            params![seg.id, t.id, seg.start_ms, seg.end_ms, seg.text, seg.confidence],
+            params![
+                seg.id,
+                t.id,
+                seg.start_ms,
+                seg.end_ms,
+                seg.text,
+                seg.confidence,
+                seg.speaker,
+                (!seg.words.is_empty()).then(|| serde_json::to_string(&seg.words)).transpose()?,
+                seg.language
+            ],
         )?;
     }
 
+    for translation in &t.translations {
+        save_translation(conn, &t.id, translation)?;
+    }
+    for notes in &t.notes {
+        save_notes(conn, &t.id, notes)?;
+    }
+    for name in &t.tags {
+        let tag = add_tag(conn, name, None)?;
+        tag_transcription(conn, &t.id, &tag.id)?;
+    }
+
+    Ok(())
+}
+
+/// Insert `t`, replacing the stored transcription with the same id (its
+/// edit history is kept); it is in the trash when `t.deleted_at` is set, and
+/// no longer counts as deleted for good if it was
+pub fn upsert_transcription(conn: &Connection, t: &Transcription) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    delete_transcription_rows(&tx, &t.id)?;
+    insert_transcription(&tx, t)?;
+    tx.execute("DELETE FROM deleted_transcriptions WHERE id = ?1", [&t.id])?;
+    tx.commit()?;
     Ok(())
 }
 
 pub fn get_transcription(conn: &Connection, id: &str) -> Result<Option<Transcription>> {
     let mut stmt = conn.prepare(
         r#"
-        SELECT id, created_at, updated_at, source_type, source_name, duration_ms, language, raw_text, edited_text, is_edited
-        FROM transcriptions
-        WHERE id = ?1
+        SELECT t.id, t.created_at, t.updated_at, t.source_type, t.source_name, t.duration_ms, t.language, c.raw_text, c.edited_text, t.is_edited, t.source_path, t.original_sample_rate, t.action_items, t.markers, t.is_draft, t.capture, t.confidence, t.audio_path, t.folder, t.title, t.pauses, t.deleted_at
+        FROM transcriptions t
+        LEFT JOIN transcription_content c ON c.transcription_id = t.id
+        WHERE t.id = ?1
         "#,
     )?;
 
@@ -54,18 +115,40 @@ pub fn get_transcription(conn: &Connection, id: &str) -> Result<Option<Transcrip
             updated_at: row.get(2)?,
             source_type: row.get(3)?,
             source_name: row.get(4)?,
+            source_path: row.get(10)?,
+            original_sample_rate: row.get(11)?,
             duration_ms: row.get(5)?,
             language: row.get(6)?,
-            raw_text: row.get(7)?,
+            raw_text: row.get::<_, Option<String>>(7)?.unwrap_or_default(),
             edited_text: row.get(8)?,
             is_edited: row.get::<_, i32>(9)? != 0,
+            is_draft: row.get::<_, i32>(14)? != 0,
             segments: vec![],
+            warnings: Vec::new(),
+            normalization_gain: None,
+            action_items: parse_json_column(row.get(12)?),
+            markers: parse_json_column(row.get(13)?),
+            pauses: parse_json_column(row.get(20)?),
+            capture: row
+                .get::<_, Option<String>>(15)?
+                .and_then(|j| serde_json::from_str(&j).ok()),
+            confidence: row.get(16)?,
+            audio_path: row.get(17)?,
+            translations: Vec::new(),
+            notes: Vec::new(),
+            folder: row.get(18)?,
+            tags: Vec::new(),
+            title: row.get(19)?,
+            deleted_at: row.get(21)?,
         })
     });
 
     match transcription {
         Ok(mut t) => {
             t.segments = get_segments(conn, &t.id)?;
+            t.translations = get_translations(conn, &t.id)?;
+            t.notes = get_notes(conn, &t.id)?;
+            t.tags = get_tag_names(conn, &t.id)?;
             Ok(Some(t))
         }
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
@@ -73,45 +156,198 @@ pub fn get_transcription(conn: &Connection, id: &str) -> Result<Option<Transcrip
     }
 }
 
-pub fn list_transcriptions(conn: &Connection) -> Result<Vec<Transcription>> {
+/// Metadata and preview of the active profile's transcriptions (no full text, no segments),
+/// newest first
+///
+/// Paged by keyset: up to `limit` transcriptions after `after`, the last one
+/// of the previous page (all of them without a limit).
+pub fn list_transcriptions(
+    conn: &Connection,
+    limit: Option<usize>,
+    after: Option<&HistoryCursor>,
+) -> Result<Vec<TranscriptionSummary>> {
+    list_summaries(conn, limit, after, None, None)
+}
+
+/// Transcriptions with the tag `tag_id`, paged like `list_transcriptions`
+pub fn list_by_tag(
+    conn: &Connection,
+    tag_id: &str,
+    limit: Option<usize>,
+    after: Option<&HistoryCursor>,
+) -> Result<Vec<TranscriptionSummary>> {
+    list_summaries(conn, limit, after, Some(tag_id), None)
+}
+
+/// Transcriptions filed in `folder`, paged like `list_transcriptions`
+pub fn list_by_folder(
+    conn: &Connection,
+    folder: &str,
+    limit: Option<usize>,
+    after: Option<&HistoryCursor>,
+) -> Result<Vec<TranscriptionSummary>> {
+    list_summaries(conn, limit, after, None, Some(folder))
+}
+
+fn list_summaries(
+    conn: &Connection,
+    limit: Option<usize>,
+    after: Option<&HistoryCursor>,
+    tag_id: Option<&str>,
+    folder: Option<&str>,
+) -> Result<Vec<TranscriptionSummary>> {
     let mut stmt = conn.prepare(
         r#"
-        SELECT id, created_at, updated_at, source_type, source_name, duration_ms, language, raw_text, edited_text, is_edited
+        SELECT id, created_at, updated_at, source_type, source_name, duration_ms, language, preview, is_edited, source_path, is_draft, deleted_at, folder,
+               (SELECT group_concat(g.name, char(31)) FROM transcription_tags tt JOIN tags g ON g.id = tt.tag_id WHERE tt.transcription_id = transcriptions.id),
+               title
         FROM transcriptions
-        ORDER BY created_at DESC
+        WHERE COALESCE(profile_id, 'default') = ?1
+          AND deleted_at IS NULL
+          AND (?2 IS NULL OR (created_at, id) < (?2, ?3))
+          AND (?5 IS NULL OR id IN (SELECT transcription_id FROM transcription_tags WHERE tag_id = ?5))
+          AND (?6 IS NULL OR folder = ?6)
+        ORDER BY created_at DESC, id DESC
+        LIMIT ?4
         "#,
     )?;
 
-    let rows = stmt.query_map([], |row| {
-        Ok(Transcription {
-            id: row.get(0)?,
-            created_at: row.get(1)?,
-            updated_at: row.get(2)?,
-            source_type: row.get(3)?,
-            source_name: row.get(4)?,
-            duration_ms: row.get(5)?,
-            language: row.get(6)?,
-            raw_text: row.get(7)?,
-            edited_text: row.get(8)?,
-            is_edited: row.get::<_, i32>(9)? != 0,
-            segments: vec![],
+    let profile_id = active_profile_id(conn)?;
+    let limit = limit.map_or(-1, |l| l as i64);
+    let rows = stmt.query_map(
+        params![
+            profile_id,
+            after.map(|c| &c.created_at),
+            after.map(|c| &c.id),
+            limit,
+            tag_id,
+            folder
+        ],
+        summary_from_row,
+    )?;
+
+    Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+}
+
+/// Id and `updated_at` of every transcription of the active profile, trash
+/// included (what sync compares with the remote manifest)
+pub fn list_updated_at(conn: &Connection) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, updated_at FROM transcriptions WHERE COALESCE(profile_id, 'default') = ?1 ORDER BY id",
+    )?;
+    let rows = stmt.query_map([active_profile_id(conn)?], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+}
+
+/// Id and deletion time of the transcriptions of the active profile deleted
+/// for good (what sync sends as deletions)
+pub fn list_deleted(conn: &Connection) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare("SELECT id, deleted_at FROM deleted_transcriptions WHERE profile_id = ?1 ORDER BY id")?;
+    let rows = stmt.query_map([active_profile_id(conn)?], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+}
+
+/// Transcriptions of the active profile in the trash, last trashed first
+pub fn list_trash(conn: &Connection) -> Result<Vec<TranscriptionSummary>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, created_at, updated_at, source_type, source_name, duration_ms, language, preview, is_edited, source_path, is_draft, deleted_at, folder,
+               (SELECT group_concat(g.name, char(31)) FROM transcription_tags tt JOIN tags g ON g.id = tt.tag_id WHERE tt.transcription_id = transcriptions.id),
+               title
+        FROM transcriptions
+        WHERE COALESCE(profile_id, 'default') = ?1
+          AND deleted_at IS NOT NULL
+        ORDER BY deleted_at DESC
+        "#,
+    )?;
+    let rows = stmt.query_map([active_profile_id(conn)?], summary_from_row)?;
+    Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+}
+
+fn summary_from_row(row: &rusqlite::Row) -> rusqlite::Result<TranscriptionSummary> {
+    Ok(TranscriptionSummary {
+        id: row.get(0)?,
+        created_at: row.get(1)?,
+        updated_at: row.get(2)?,
+        source_type: row.get(3)?,
+        source_name: row.get(4)?,
+        source_path: row.get(9)?,
+        duration_ms: row.get(5)?,
+        language: row.get(6)?,
+        preview: row.get(7)?,
+        is_edited: row.get::<_, i32>(8)? != 0,
+        is_draft: row.get::<_, i32>(10)? != 0,
+        deleted_at: row.get(11)?,
+        folder: row.get(12)?,
+        tags: split_tags(row.get(13)?),
+        title: row.get(14)?,
+    })
+}
+
+/// Tag names concatenated by `group_concat(name, char(31))`, sorted
+pub(super) fn split_tags(names: Option<String>) -> Vec<String> {
+    let mut tags: Vec<String> = names
+        .map(|n| n.split('\u{1f}').map(str::to_string).collect())
+        .unwrap_or_default();
+    tags.sort_by_key(|t| t.to_lowercase());
+    tags
+}
+
+/// Per-day transcription count and audio minutes of the active profile in `year`
+///
+/// Days are in local time; days without activity are left out. Drafts don't count.
+pub fn get_activity_calendar(conn: &Connection, year: i32) -> Result<Vec<ActivityDay>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT date(created_at, 'localtime') AS day, COUNT(*), SUM(duration_ms) / 60000.0
+        FROM transcriptions
+        WHERE COALESCE(profile_id, 'default') = ?1
+          AND is_draft = 0
+          AND deleted_at IS NULL
+          AND strftime('%Y', created_at, 'localtime') = ?2
+        GROUP BY day
+        ORDER BY day
+        "#,
+    )?;
+
+    let profile_id = active_profile_id(conn)?;
+    let rows = stmt.query_map(params![profile_id, format!("{:04}", year)], |row| {
+        Ok(ActivityDay {
+            date: row.get(0)?,
+            count: row.get(1)?,
+            minutes: row.get(2)?,
         })
     })?;
 
-    let mut transcriptions = Vec::new();
-    for row in rows {
-        let mut t = row?;
-        t.segments = get_segments(conn, &t.id)?;
-        transcriptions.push(t);
-    }
+    Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+}
+
+/// Full raw and edited text of a transcription
+pub fn get_transcription_text(conn: &Connection, id: &str) -> Result<Option<TranscriptionText>> {
+    Ok(conn
+        .query_row(
+            "SELECT raw_text, edited_text FROM transcription_content WHERE transcription_id = ?1",
+            [id],
+            |row| {
+                Ok(TranscriptionText {
+                    raw_text: row.get(0)?,
+                    edited_text: row.get(1)?,
+                })
+            },
+        )
+        .optional()?)
+}
 
-    Ok(transcriptions)
+/// JSON list column (NULL for transcriptions made before the column existed)
+fn parse_json_column<T: serde::de::DeserializeOwned>(json: Option<String>) -> Vec<T> {
+    json.and_then(|j| serde_json::from_str(&j).ok()).unwrap_or_default()
 }
 
-fn get_segments(conn: &Connection, transcription_id: &str) -> Result<Vec<Segment>> {
+/// Segments of a transcription, in time order
+pub fn get_segments(conn: &Connection, transcription_id: &str) -> Result<Vec<Segment>> {
     let mut stmt = conn.prepare(
         r#"
-        SELECT id, start_ms, end_ms, text, confidence
+        SELECT id, start_ms, end_ms, text, confidence, speaker, words, language
         FROM segments
         WHERE transcription_id = ?1
         ORDER BY start_ms
@@ -125,34 +361,570 @@ fn get_segments(conn: &Connection, transcription_id: &str) -> Result<Vec<Segment
             end_ms: row.get(2)?,
             text: row.get(3)?,
             confidence: row.get(4)?,
+            speaker: row.get(5)?,
+            language: row.get(7)?,
+            words: parse_json_column(row.get(6)?),
         })
     })?;
 
     Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
 }
 
-pub fn update_transcription_text(conn: &Connection, id: &str, edited_text: &str) -> Result<()> {
+/// Save the edited text; returns the new `updated_at`
+///
+/// With `expected_updated_at`, fails with `AppError::Conflict` when the
+/// transcription was saved since (None overwrites unconditionally).
+pub fn update_transcription_text(
+    conn: &Connection,
+    id: &str,
+    edited_text: &str,
+    action_items: &[ActionItem],
+    expected_updated_at: Option<&str>,
+) -> Result<String> {
     let now = chrono::Utc::now().to_rfc3339();
-    conn.execute(
+    let tx = conn.unchecked_transaction()?;
+    let updated = tx.execute(
         r#"
         UPDATE transcriptions
-        SET edited_text = ?1, is_edited = 1, updated_at = ?2
-        WHERE id = ?3
+        SET preview = ?1, is_edited = 1, updated_at = ?2, action_items = ?3
+        WHERE id = ?4 AND (?5 IS NULL OR updated_at = ?5)
         "#,
-        params![edited_text, now, id],
+        params![preview(edited_text), now, serde_json::to_string(action_items)?, id, expected_updated_at],
+    )?;
+    if updated == 0 {
+        let current: Option<String> = tx
+            .query_row("SELECT updated_at FROM transcriptions WHERE id = ?1", [id], |row| row.get(0))
+            .optional()?;
+        return Err(match current {
+            Some(current_updated_at) => AppError::Conflict {
+                id: id.to_string(),
+                current_updated_at,
+            },
+            None => AppError::NotFound(format!("Transcription not found: {}", id)),
+        });
+    }
+    tx.execute(
+        "UPDATE transcription_content SET edited_text = ?1 WHERE transcription_id = ?2",
+        params![edited_text, id],
+    )?;
+    tx.execute(
+        "INSERT INTO transcription_revisions (transcription_id, revision, text, created_at)
+         VALUES (?1, (SELECT COALESCE(MAX(revision), 0) + 1 FROM transcription_revisions WHERE transcription_id = ?1), ?2, ?3)",
+        params![id, edited_text, now],
+    )?;
+    tx.execute("DELETE FROM transcription_drafts WHERE transcription_id = ?1", [id])?;
+    tx.commit()?;
+    Ok(now)
+}
+
+/// Keep the text being edited, replacing the previous draft
+pub fn save_text_draft(conn: &Connection, id: &str, text: &str, base_updated_at: &str) -> Result<TextDraft> {
+    let exists: bool = conn.query_row("SELECT EXISTS(SELECT 1 FROM transcriptions WHERE id = ?1)", [id], |row| row.get(0))?;
+    if !exists {
+        return Err(AppError::NotFound(format!("Transcription not found: {}", id)));
+    }
+    let draft = TextDraft {
+        transcription_id: id.to_string(),
+        text: text.to_string(),
+        base_updated_at: base_updated_at.to_string(),
+        saved_at: chrono::Utc::now().to_rfc3339(),
+    };
+    conn.execute(
+        "INSERT OR REPLACE INTO transcription_drafts (transcription_id, text, base_updated_at, saved_at)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![draft.transcription_id, draft.text, draft.base_updated_at, draft.saved_at],
+    )?;
+    Ok(draft)
+}
+
+pub fn get_text_draft(conn: &Connection, id: &str) -> Result<Option<TextDraft>> {
+    let draft = conn
+        .query_row(
+            "SELECT text, base_updated_at, saved_at FROM transcription_drafts WHERE transcription_id = ?1",
+            [id],
+            |row| {
+                Ok(TextDraft {
+                    transcription_id: id.to_string(),
+                    text: row.get(0)?,
+                    base_updated_at: row.get(1)?,
+                    saved_at: row.get(2)?,
+                })
+            },
+        )
+        .optional()?;
+    Ok(draft)
+}
+
+pub fn delete_text_draft(conn: &Connection, id: &str) -> Result<()> {
+    conn.execute("DELETE FROM transcription_drafts WHERE transcription_id = ?1", [id])?;
+    Ok(())
+}
+
+/// Saved texts of a transcription, newest first, ending with revision 0 (the
+/// transcribed text)
+pub fn get_revision_history(conn: &Connection, id: &str) -> Result<Vec<TranscriptionRevision>> {
+    let original: Option<TranscriptionRevision> = conn
+        .query_row(
+            "SELECT t.created_at, c.raw_text FROM transcriptions t
+             LEFT JOIN transcription_content c ON c.transcription_id = t.id
+             WHERE t.id = ?1",
+            [id],
+            |row| {
+                Ok(TranscriptionRevision {
+                    revision: 0,
+                    created_at: row.get(0)?,
+                    text: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                })
+            },
+        )
+        .optional()?;
+    let original = original.ok_or_else(|| AppError::NotFound(format!("Transcription not found: {}", id)))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT revision, text, created_at FROM transcription_revisions
+         WHERE transcription_id = ?1 ORDER BY revision DESC",
+    )?;
+    let mut revisions = stmt
+        .query_map([id], |row| {
+            Ok(TranscriptionRevision {
+                revision: row.get(0)?,
+                text: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    revisions.push(original);
+    Ok(revisions)
+}
+
+/// Store the translation of a transcription, replacing the one in the same language
+pub fn save_translation(conn: &Connection, transcription_id: &str, translation: &Translation) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO transcription_translations (transcription_id, language, text, created_at)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![transcription_id, translation.language, translation.text, translation.created_at],
+    )?;
+    Ok(())
+}
+
+/// Translations of a transcription, by language
+pub fn get_translations(conn: &Connection, transcription_id: &str) -> Result<Vec<Translation>> {
+    let mut stmt = conn.prepare(
+        "SELECT language, text, created_at FROM transcription_translations
+         WHERE transcription_id = ?1 ORDER BY language",
+    )?;
+    let translations = stmt
+        .query_map([transcription_id], |row| {
+            Ok(Translation {
+                language: row.get(0)?,
+                text: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(translations)
+}
+
+/// Store the LLM notes of a transcription, replacing those in the same style
+pub fn save_notes(conn: &Connection, transcription_id: &str, notes: &TranscriptionNotes) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO transcription_notes
+         (transcription_id, style, summary, key_points, action_items, model, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            transcription_id,
+            notes.style,
+            notes.summary,
+            serde_json::to_string(&notes.key_points)?,
+            serde_json::to_string(&notes.action_items)?,
+            notes.model,
+            notes.created_at
+        ],
     )?;
     Ok(())
 }
 
+/// LLM notes of a transcription, newest first
+pub fn get_notes(conn: &Connection, transcription_id: &str) -> Result<Vec<TranscriptionNotes>> {
+    let mut stmt = conn.prepare(
+        "SELECT style, summary, key_points, action_items, model, created_at FROM transcription_notes
+         WHERE transcription_id = ?1 ORDER BY created_at DESC",
+    )?;
+    let notes = stmt
+        .query_map([transcription_id], |row| {
+            Ok(TranscriptionNotes {
+                style: row.get(0)?,
+                summary: row.get(1)?,
+                key_points: parse_json_column(row.get(2)?),
+                action_items: parse_json_column(row.get(3)?),
+                model: row.get(4)?,
+                created_at: row.get(5)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(notes)
+}
+
+/// For good, with the edit history, the translations and the notes; the
+/// deletion is recorded for sync
 pub fn delete_transcription(conn: &Connection, id: &str) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "INSERT OR REPLACE INTO deleted_transcriptions (id, profile_id, deleted_at)
+         SELECT id, COALESCE(profile_id, 'default'), ?2 FROM transcriptions WHERE id = ?1",
+        params![id, chrono::Utc::now().to_rfc3339()],
+    )?;
+    delete_transcription_rows(&tx, id)?;
+    for table in [
+        "transcription_revisions",
+        "transcription_translations",
+        "transcription_notes",
+        "transcription_drafts",
+    ] {
+        tx.execute(&format!("DELETE FROM {} WHERE transcription_id = ?1", table), [id])?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Transcription, text, segments and tag links; the edit history, the
+/// translations, the notes and the draft are left to the caller
+fn delete_transcription_rows(conn: &Connection, id: &str) -> Result<()> {
     conn.execute("DELETE FROM segments WHERE transcription_id = ?1", [id])?;
+    conn.execute("DELETE FROM transcription_tags WHERE transcription_id = ?1", [id])?;
+    conn.execute("DELETE FROM transcription_content WHERE transcription_id = ?1", [id])?;
     conn.execute("DELETE FROM transcriptions WHERE id = ?1", [id])?;
     Ok(())
 }
 
+// Tags and folders
+
+/// Tags of the active profile by name, with the number of transcriptions of each
+pub fn list_tags(conn: &Connection) -> Result<Vec<Tag>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT g.id, g.name, g.color, COUNT(t.id)
+        FROM tags g
+        LEFT JOIN transcription_tags tt ON tt.tag_id = g.id
+        LEFT JOIN transcriptions t ON t.id = tt.transcription_id AND t.deleted_at IS NULL
+        WHERE g.profile_id = ?1
+        GROUP BY g.id
+        ORDER BY g.name
+        "#,
+    )?;
+    let tags = stmt
+        .query_map([active_profile_id(conn)?], |row| {
+            Ok(Tag {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                color: row.get(2)?,
+                count: row.get(3)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(tags)
+}
+
+/// Create a tag in the active profile, or return the one with the same name
+/// (case-insensitive; its color is updated when `color` is set)
+pub fn add_tag(conn: &Connection, name: &str, color: Option<&str>) -> Result<Tag> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput("Tag name is empty".to_string()));
+    }
+    let profile_id = active_profile_id(conn)?;
+    conn.execute(
+        "INSERT INTO tags (id, profile_id, name, color, created_at) VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT (profile_id, name) DO UPDATE SET color = COALESCE(excluded.color, color)",
+        params![
+            uuid::Uuid::new_v4().to_string(),
+            profile_id,
+            name,
+            color,
+            chrono::Utc::now().to_rfc3339()
+        ],
+    )?;
+    let id: String = conn.query_row(
+        "SELECT id FROM tags WHERE profile_id = ?1 AND name = ?2",
+        params![profile_id, name],
+        |row| row.get(0),
+    )?;
+    list_tags(conn)?
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| AppError::NotFound(format!("Tag not found: {}", id)))
+}
+
+/// Remove a tag from every transcription and delete it
+pub fn delete_tag(conn: &Connection, id: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE transcriptions SET updated_at = ?1
+         WHERE id IN (SELECT transcription_id FROM transcription_tags WHERE tag_id = ?2)",
+        params![chrono::Utc::now().to_rfc3339(), id],
+    )?;
+    conn.execute("DELETE FROM transcription_tags WHERE tag_id = ?1", [id])?;
+    if conn.execute("DELETE FROM tags WHERE id = ?1", [id])? == 0 {
+        return Err(AppError::NotFound(format!("Tag not found: {}", id)));
+    }
+    Ok(())
+}
+
+/// Returns the `updated_at` of the transcription, bumped if the tag is new to it
+pub fn tag_transcription(conn: &Connection, transcription_id: &str, tag_id: &str) -> Result<String> {
+    let tag_exists = conn
+        .query_row("SELECT 1 FROM tags WHERE id = ?1", [tag_id], |_| Ok(()))
+        .optional()?
+        .is_some();
+    if !tag_exists {
+        return Err(AppError::NotFound(format!("Tag not found: {}", tag_id)));
+    }
+    let transcription_exists = conn
+        .query_row("SELECT 1 FROM transcriptions WHERE id = ?1", [transcription_id], |_| Ok(()))
+        .optional()?
+        .is_some();
+    if !transcription_exists {
+        return Err(AppError::NotFound(format!("Transcription not found: {}", transcription_id)));
+    }
+    let added = conn.execute(
+        "INSERT OR IGNORE INTO transcription_tags (transcription_id, tag_id) VALUES (?1, ?2)",
+        params![transcription_id, tag_id],
+    )?;
+    if added > 0 {
+        touch(conn, transcription_id)?;
+    }
+    updated_at_of(conn, transcription_id)
+}
+
+/// Returns the `updated_at` of the transcription, bumped if it had the tag
+pub fn untag_transcription(conn: &Connection, transcription_id: &str, tag_id: &str) -> Result<String> {
+    let removed = conn.execute(
+        "DELETE FROM transcription_tags WHERE transcription_id = ?1 AND tag_id = ?2",
+        params![transcription_id, tag_id],
+    )?;
+    if removed > 0 {
+        touch(conn, transcription_id)?;
+    }
+    updated_at_of(conn, transcription_id)
+}
+
+/// Bump `updated_at` of a transcription whose tags changed: they travel
+/// with it, so sync carries the change
+fn touch(conn: &Connection, id: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE transcriptions SET updated_at = ?1 WHERE id = ?2",
+        params![chrono::Utc::now().to_rfc3339(), id],
+    )?;
+    Ok(())
+}
+
+fn updated_at_of(conn: &Connection, id: &str) -> Result<String> {
+    conn.query_row("SELECT updated_at FROM transcriptions WHERE id = ?1", [id], |row| row.get(0))
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Transcription not found: {}", id)))
+}
+
+/// Names of the tags of a transcription, sorted
+fn get_tag_names(conn: &Connection, transcription_id: &str) -> Result<Vec<String>> {
+    let names = conn.query_row(
+        "SELECT group_concat(g.name, char(31)) FROM transcription_tags tt
+         JOIN tags g ON g.id = tt.tag_id WHERE tt.transcription_id = ?1",
+        [transcription_id],
+        |row| row.get(0),
+    )?;
+    Ok(split_tags(names))
+}
+
+/// File a transcription in a folder (None or blank: out of any folder);
+/// bumps `updated_at` when the folder changes and returns it
+pub fn set_folder(conn: &Connection, id: &str, folder: Option<&str>) -> Result<String> {
+    let folder = folder.map(str::trim).filter(|f| !f.is_empty());
+    let updated = conn.execute(
+        "UPDATE transcriptions
+         SET updated_at = CASE WHEN folder IS ?1 THEN updated_at ELSE ?2 END, folder = ?1
+         WHERE id = ?3",
+        params![folder, chrono::Utc::now().to_rfc3339(), id],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!("Transcription not found: {}", id)));
+    }
+    updated_at_of(conn, id)
+}
+
+/// Folders of the active profile by name, with their number of transcriptions
+pub fn list_folders(conn: &Connection) -> Result<Vec<Folder>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT folder, COUNT(*)
+        FROM transcriptions
+        WHERE COALESCE(profile_id, 'default') = ?1
+          AND deleted_at IS NULL
+          AND folder IS NOT NULL
+        GROUP BY folder
+        ORDER BY folder COLLATE NOCASE
+        "#,
+    )?;
+    let folders = stmt
+        .query_map([active_profile_id(conn)?], |row| {
+            Ok(Folder {
+                name: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(folders)
+}
+
+/// Set the title, source name and language of a transcription (blank title
+/// or source name: none); its text is left as it is. Bumps `updated_at`, so
+/// sync carries the change; returns the new one
+pub fn update_transcription_metadata(
+    conn: &Connection,
+    id: &str,
+    title: Option<&str>,
+    source_name: Option<&str>,
+    language: &str,
+) -> Result<String> {
+    fn blank_to_none(value: Option<&str>) -> Option<&str> {
+        value.map(str::trim).filter(|v| !v.is_empty())
+    }
+    let now = chrono::Utc::now().to_rfc3339();
+    let updated = conn.execute(
+        "UPDATE transcriptions SET title = ?1, source_name = ?2, language = ?3, updated_at = ?4 WHERE id = ?5",
+        params![blank_to_none(title), blank_to_none(source_name), language, now, id],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!("Transcription not found: {}", id)));
+    }
+    Ok(now)
+}
+
+/// Set or clear the kept recording of a transcription
+pub fn set_audio_path(conn: &Connection, id: &str, audio_path: Option<&str>) -> Result<()> {
+    let updated = conn.execute(
+        "UPDATE transcriptions SET audio_path = ?1 WHERE id = ?2",
+        params![audio_path, id],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!("Transcription not found: {}", id)));
+    }
+    Ok(())
+}
+
+/// Kept recordings of every profile, trash included
+pub fn recording_audio_paths(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT audio_path FROM transcriptions WHERE audio_path IS NOT NULL")?;
+    let paths = stmt.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<_>>()?;
+    Ok(paths)
+}
+
+/// Move a transcription to the trash: out of the history and search until restored or purged
+pub fn trash_transcription(conn: &Connection, id: &str) -> Result<()> {
+    set_trashed(conn, id, true)
+}
+
+/// Bring a transcription back from the trash
+pub fn restore_transcription(conn: &Connection, id: &str) -> Result<()> {
+    set_trashed(conn, id, false)
+}
+
+/// Also bumps `updated_at`, so sync carries the change to other machines
+fn set_trashed(conn: &Connection, id: &str, trashed: bool) -> Result<()> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let updated = conn.execute(
+        "UPDATE transcriptions SET deleted_at = ?1, updated_at = ?2
+         WHERE id = ?3 AND COALESCE(profile_id, 'default') = ?4",
+        params![trashed.then_some(&now), now, id, active_profile_id(conn)?],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!("Transcription not found: {}", id)));
+    }
+    Ok(())
+}
+
+/// Empty the trash of the active profile; returns the number of transcriptions deleted
+pub fn purge_trash(conn: &Connection) -> Result<usize> {
+    purge_trashed(conn, Some(&active_profile_id(conn)?), None)
+}
+
+/// Delete the transcriptions trashed more than `retention_days` ago, in every profile
+/// (0: none)
+pub fn purge_expired_trash(conn: &Connection, retention_days: u32) -> Result<usize> {
+    if retention_days == 0 {
+        return Ok(0);
+    }
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(retention_days as i64);
+    purge_trashed(conn, None, Some(&cutoff.to_rfc3339()))
+}
+
+fn purge_trashed(conn: &Connection, profile_id: Option<&str>, before: Option<&str>) -> Result<usize> {
+    let trashed = "SELECT id FROM transcriptions
+        WHERE deleted_at IS NOT NULL
+          AND (?1 IS NULL OR COALESCE(profile_id, 'default') = ?1)
+          AND (?2 IS NULL OR deleted_at < ?2)";
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        &format!(
+            "INSERT OR REPLACE INTO deleted_transcriptions (id, profile_id, deleted_at)
+             SELECT id, COALESCE(profile_id, 'default'), ?3 FROM transcriptions WHERE id IN ({})",
+            trashed
+        ),
+        params![profile_id, before, chrono::Utc::now().to_rfc3339()],
+    )?;
+    for table in [
+        "segments",
+        "transcription_content",
+        "transcription_revisions",
+        "transcription_translations",
+        "transcription_notes",
+        "transcription_drafts",
+        "transcription_tags",
+    ] {
+        tx.execute(
+            &format!("DELETE FROM {} WHERE transcription_id IN ({})", table, trashed),
+            params![profile_id, before],
+        )?;
+    }
+    let purged = tx.execute(
+        &format!("DELETE FROM transcriptions WHERE id IN ({})", trashed),
+        params![profile_id, before],
+    )?;
+    tx.commit()?;
+    Ok(purged)
+}
+
+/// Delete every transcription of the active profile (recorded for sync)
 pub fn delete_all_transcriptions(conn: &Connection) -> Result<()> {
-    conn.execute("DELETE FROM segments", [])?;
-    conn.execute("DELETE FROM transcriptions", [])?;
+    let profile_id = active_profile_id(conn)?;
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "INSERT OR REPLACE INTO deleted_transcriptions (id, profile_id, deleted_at)
+         SELECT id, ?1, ?2 FROM transcriptions WHERE COALESCE(profile_id, 'default') = ?1",
+        params![profile_id, chrono::Utc::now().to_rfc3339()],
+    )?;
+    for table in [
+        "segments",
+        "transcription_content",
+        "transcription_revisions",
+        "transcription_translations",
+        "transcription_notes",
+        "transcription_drafts",
+        "transcription_tags",
+    ] {
+        tx.execute(
+            &format!(
+                "DELETE FROM {} WHERE transcription_id IN (
+                    SELECT id FROM transcriptions WHERE COALESCE(profile_id, 'default') = ?1
+                )",
+                table
+            ),
+            [&profile_id],
+        )?;
+    }
+    tx.execute(
+        "DELETE FROM transcriptions WHERE COALESCE(profile_id, 'default') = ?1",
+        [&profile_id],
+    )?;
+    tx.commit()?;
     Ok(())
 }
 
@@ -174,7 +946,94 @@ pub fn get_settings(conn: &Connection) -> Result<Settings> {
             "shortcut_toggle_recording" => settings.shortcuts.toggle_recording = value,
             "shortcut_pause" => settings.shortcuts.pause = value,
             "shortcut_copy" => settings.shortcuts.copy = value,
+            "shortcut_push_to_talk" => settings.shortcuts.push_to_talk = value,
             "engine_backend" => settings.engine_backend = value,
+            "substitution_rules" => {
+                settings.substitution_rules = serde_json::from_str(&value).unwrap_or_default()
+            }
+            "vocabulary" => settings.vocabulary = serde_json::from_str(&value).unwrap_or_default(),
+            "enabled_language_packs" => {
+                settings.enabled_language_packs = serde_json::from_str(&value).unwrap_or_default()
+            }
+            "transcription" => {
+                settings.transcription = serde_json::from_str(&value).unwrap_or_default()
+            }
+            "interview_mode" => settings.interview_mode = value == "true",
+            "auto_language_prior" => settings.auto_language_prior = value == "true",
+            "power_mode" => {
+                settings.power_mode = serde_json::from_str(&value).unwrap_or_default()
+            }
+            "chunk_separator" => settings.chunk_separator = value,
+            "per_language_decoding" => settings.per_language_decoding = value == "true",
+            "sync" => settings.sync = serde_json::from_str(&value).unwrap_or_default(),
+            "translation" => settings.translation = serde_json::from_str(&value).unwrap_or_default(),
+            "llm" => settings.llm = serde_json::from_str(&value).unwrap_or_default(),
+            "language_decoding_overrides" => {
+                settings.language_decoding_overrides =
+                    serde_json::from_str(&value).unwrap_or_default()
+            }
+            "decoding_profiles" => {
+                settings.decoding_profiles = serde_json::from_str(&value).unwrap_or_default()
+            }
+            "selected_decoding_profiles" => {
+                settings.selected_decoding_profiles = serde_json::from_str(&value).unwrap_or_default()
+            }
+            "sentence_gap_ms" => {
+                if let Ok(ms) = value.parse() {
+                    settings.sentence_gap_ms = ms;
+                }
+            }
+            "spoken_feedback" => settings.spoken_feedback = value == "true",
+            "export_provenance" => settings.export_provenance = value == "true",
+            "live_paste" => settings.live_paste = value == "true",
+            "onnx_execution_provider" => {
+                settings.onnx_execution_provider = serde_json::from_str(&value).unwrap_or_default()
+            }
+            "openvino_isolated" => settings.openvino_isolated = value == "true",
+            "openvino_performance" => {
+                settings.openvino_performance = serde_json::from_str(&value).unwrap_or_default()
+            }
+            "warmup_engine" => settings.warmup_engine = value == "true",
+            "engine_pool" => settings.engine_pool = value == "true",
+            "duck_output" => settings.duck_output = value == "true",
+            "duck_output_level" => {
+                if let Ok(level) = value.parse() {
+                    settings.duck_output_level = level;
+                }
+            }
+            "capture_agc" => settings.capture_agc = value == "true",
+            "max_recording_minutes" => {
+                if let Ok(minutes) = value.parse() {
+                    settings.max_recording_minutes = minutes;
+                }
+            }
+            "auto_stop_silence_secs" => {
+                if let Ok(secs) = value.parse() {
+                    settings.auto_stop_silence_secs = secs;
+                }
+            }
+            "loopback_device_id" => settings.loopback_device_id = Some(value),
+            "input_configs" => settings.input_configs = serde_json::from_str(&value).unwrap_or_default(),
+            "dual_source_mode" => {
+                settings.dual_source_mode = serde_json::from_str(&value).unwrap_or_default()
+            }
+            "simulated_inference_delay_ms" => {
+                if let Ok(ms) = value.parse() {
+                    settings.simulated_inference_delay_ms = ms;
+                }
+            }
+            "debug_dump" => settings.debug_dump = value == "true",
+            "keep_recordings" => settings.keep_recordings = value == "true",
+            "skip_silence" => settings.skip_silence = value == "true",
+            "trash_retention_days" => {
+                if let Ok(days) = value.parse() {
+                    settings.trash_retention_days = days;
+                }
+            }
+            "model_catalog_url" => settings.model_catalog_url = Some(value),
+            "post_transcription_hooks" => {
+                settings.post_transcription_hooks = serde_json::from_str(&value).unwrap_or_default()
+            }
             _ => {}
         }
     }
@@ -192,7 +1051,72 @@ pub fn update_settings(conn: &Connection, settings: &Settings) -> Result<()> {
         ),
         ("shortcut_pause", settings.shortcuts.pause.clone()),
         ("shortcut_copy", settings.shortcuts.copy.clone()),
+        ("shortcut_push_to_talk", settings.shortcuts.push_to_talk.clone()),
         ("engine_backend", settings.engine_backend.clone()),
+        (
+            "substitution_rules",
+            serde_json::to_string(&settings.substitution_rules)?,
+        ),
+        ("vocabulary", serde_json::to_string(&settings.vocabulary)?),
+        (
+            "enabled_language_packs",
+            serde_json::to_string(&settings.enabled_language_packs)?,
+        ),
+        ("transcription", serde_json::to_string(&settings.transcription)?),
+        ("interview_mode", settings.interview_mode.to_string()),
+        ("auto_language_prior", settings.auto_language_prior.to_string()),
+        ("power_mode", serde_json::to_string(&settings.power_mode)?),
+        ("chunk_separator", settings.chunk_separator.clone()),
+        ("sentence_gap_ms", settings.sentence_gap_ms.to_string()),
+        (
+            "per_language_decoding",
+            settings.per_language_decoding.to_string(),
+        ),
+        (
+            "language_decoding_overrides",
+            serde_json::to_string(&settings.language_decoding_overrides)?,
+        ),
+        (
+            "decoding_profiles",
+            serde_json::to_string(&settings.decoding_profiles)?,
+        ),
+        (
+            "selected_decoding_profiles",
+            serde_json::to_string(&settings.selected_decoding_profiles)?,
+        ),
+        ("input_configs", serde_json::to_string(&settings.input_configs)?),
+        ("sync", serde_json::to_string(&settings.sync)?),
+        ("translation", serde_json::to_string(&settings.translation)?),
+        ("llm", serde_json::to_string(&settings.llm)?),
+        ("spoken_feedback", settings.spoken_feedback.to_string()),
+        ("export_provenance", settings.export_provenance.to_string()),
+        ("live_paste", settings.live_paste.to_string()),
+        (
+            "onnx_execution_provider",
+            serde_json::to_string(&settings.onnx_execution_provider)?,
+        ),
+        ("openvino_isolated", settings.openvino_isolated.to_string()),
+        ("openvino_performance", serde_json::to_string(&settings.openvino_performance)?),
+        ("warmup_engine", settings.warmup_engine.to_string()),
+        ("engine_pool", settings.engine_pool.to_string()),
+        ("duck_output", settings.duck_output.to_string()),
+        ("duck_output_level", settings.duck_output_level.to_string()),
+        ("capture_agc", settings.capture_agc.to_string()),
+        ("max_recording_minutes", settings.max_recording_minutes.to_string()),
+        ("auto_stop_silence_secs", settings.auto_stop_silence_secs.to_string()),
+        ("dual_source_mode", serde_json::to_string(&settings.dual_source_mode)?),
+        (
+            "simulated_inference_delay_ms",
+            settings.simulated_inference_delay_ms.to_string(),
+        ),
+        ("debug_dump", settings.debug_dump.to_string()),
+        ("keep_recordings", settings.keep_recordings.to_string()),
+        ("skip_silence", settings.skip_silence.to_string()),
+        ("trash_retention_days", settings.trash_retention_days.to_string()),
+        (
+            "post_transcription_hooks",
+            serde_json::to_string(&settings.post_transcription_hooks)?,
+        ),
     ];
 
     for (key, value) in pairs {
@@ -209,5 +1133,348 @@ pub fn update_settings(conn: &Connection, settings: &Settings) -> Result<()> {
         )?;
     }
 
+    match settings.model_catalog_url {
+        Some(ref url) => conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES ('model_catalog_url', ?1)",
+            [url],
+        )?,
+        None => conn.execute("DELETE FROM settings WHERE key = 'model_catalog_url'", [])?,
+    };
+
+    match settings.loopback_device_id {
+        Some(ref device_id) => conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES ('loopback_device_id', ?1)",
+            [device_id],
+        )?,
+        None => conn.execute("DELETE FROM settings WHERE key = 'loopback_device_id'", [])?,
+    };
+
+    Ok(())
+}
+
+// Profile queries
+
+/// ID of the profile currently in use ("default" until another is selected)
+pub fn active_profile_id(conn: &Connection) -> Result<String> {
+    let id = conn
+        .query_row(
+            "SELECT value FROM settings WHERE key = 'active_profile_id'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?;
+    Ok(id.unwrap_or_else(|| "default".to_string()))
+}
+
+pub fn list_profiles(conn: &Connection) -> Result<Vec<Profile>> {
+    let active_id = active_profile_id(conn)?;
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, name, created_at
+        FROM profiles
+        ORDER BY created_at
+        "#,
+    )?;
+
+    let rows = stmt.query_map([], |row| {
+        let id: String = row.get(0)?;
+        Ok(Profile {
+            is_active: id == active_id,
+            id,
+            name: row.get(1)?,
+            created_at: row.get(2)?,
+        })
+    })?;
+
+    Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+}
+
+/// Create a new profile starting from default settings
+pub fn create_profile(conn: &Connection, name: &str) -> Result<Profile> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput("Profile name cannot be empty".into()));
+    }
+
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM profiles WHERE name = ?1)",
+        [name],
+        |row| row.get(0),
+    )?;
+    if exists {
+        return Err(AppError::InvalidInput(format!("Profile already exists: {}", name)));
+    }
+
+    let profile = Profile {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        is_active: false,
+    };
+
+    conn.execute(
+        "INSERT INTO profiles (id, name, created_at, settings_json) VALUES (?1, ?2, ?3, ?4)",
+        params![
+            profile.id,
+            profile.name,
+            profile.created_at,
+            serde_json::to_string(&Settings::default())?
+        ],
+    )?;
+
+    Ok(profile)
+}
+
+/// Make another profile active
+///
+/// The current settings are saved into the outgoing profile's bundle, then
+/// the target profile's bundle replaces them. Returns the new settings.
+pub fn switch_profile(conn: &Connection, id: &str) -> Result<Settings> {
+    let target_json: Option<Option<String>> = conn
+        .query_row(
+            "SELECT settings_json FROM profiles WHERE id = ?1",
+            [id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let target_json =
+        target_json.ok_or_else(|| AppError::NotFound(format!("Profile not found: {}", id)))?;
+
+    let current_id = active_profile_id(conn)?;
+    if current_id == id {
+        return get_settings(conn);
+    }
+
+    let tx = conn.unchecked_transaction()?;
+
+    // Save the outgoing profile's settings bundle
+    let current_settings = get_settings(&tx)?;
+    tx.execute(
+        "UPDATE profiles SET settings_json = ?1 WHERE id = ?2",
+        params![serde_json::to_string(&current_settings)?, current_id],
+    )?;
+
+    // Apply the incoming one (never-switched-to profiles start from defaults)
+    let settings = match target_json {
+        Some(json) => serde_json::from_str(&json)?,
+        None => Settings::default(),
+    };
+    tx.execute("DELETE FROM settings WHERE key = 'input_device_id'", [])?;
+    update_settings(&tx, &settings)?;
+
+    tx.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES ('active_profile_id', ?1)",
+        [id],
+    )?;
+    tx.commit()?;
+
+    Ok(settings)
+}
+
+// Chunk cache queries
+
+/// Cached text for a chunk key (refreshes its last use)
+pub fn get_cached_chunk(conn: &Connection, key: &str) -> Result<Option<String>> {
+    let text = conn
+        .query_row("SELECT text FROM chunk_cache WHERE key = ?1", [key], |row| {
+            row.get::<_, String>(0)
+        })
+        .optional()?;
+    if text.is_some() {
+        conn.execute(
+            "UPDATE chunk_cache SET last_used_at = datetime('now') WHERE key = ?1",
+            [key],
+        )?;
+    }
+    Ok(text)
+}
+
+/// Store a chunk result, keeping only the `max_entries` most recently used
+pub fn put_cached_chunk(conn: &Connection, key: &str, text: &str, max_entries: usize) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO chunk_cache (key, text, last_used_at) VALUES (?1, ?2, datetime('now'))",
+        params![key, text],
+    )?;
+    conn.execute(
+        "DELETE FROM chunk_cache WHERE key NOT IN
+         (SELECT key FROM chunk_cache ORDER BY last_used_at DESC LIMIT ?1)",
+        [max_entries as i64],
+    )?;
+    Ok(())
+}
+
+pub fn clear_chunk_cache(conn: &Connection) -> Result<usize> {
+    Ok(conn.execute("DELETE FROM chunk_cache", [])?)
+}
+
+// Pending transcription queries
+
+/// Start (or restart) a pending transcription with its saved chunks
+pub fn insert_pending_transcription(conn: &Connection, pending: &PendingTranscription) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO pending_transcriptions
+         (id, source_path, source_name, language, decoding_config, normalization, chunk_count, chunks, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            pending.id,
+            pending.source_path,
+            pending.source_name,
+            pending.language.as_ref().map(serde_json::to_string).transpose()?,
+            pending.decoding_config.as_ref().map(serde_json::to_string).transpose()?,
+            pending.normalization.as_ref().map(serde_json::to_string).transpose()?,
+            pending.chunk_count as i64,
+            serde_json::to_string(&pending.chunks)?,
+            pending.created_at,
+            pending.updated_at,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Save the result of a chunk (replacing an earlier result for the same index)
+pub fn append_pending_chunk(conn: &Connection, id: &str, chunk_count: usize, chunk: &PendingChunk) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    let json: String = tx
+        .query_row("SELECT chunks FROM pending_transcriptions WHERE id = ?1", [id], |row| row.get(0))
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Pending transcription not found: {}", id)))?;
+    let mut chunks: Vec<PendingChunk> = serde_json::from_str(&json)?;
+    chunks.retain(|c| c.index != chunk.index);
+    chunks.push(chunk.clone());
+    chunks.sort_by_key(|c| c.index);
+
+    tx.execute(
+        "UPDATE pending_transcriptions SET chunks = ?1, chunk_count = ?2, updated_at = ?3 WHERE id = ?4",
+        params![serde_json::to_string(&chunks)?, chunk_count as i64, chrono::Utc::now().to_rfc3339(), id],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Oldest first
+pub fn list_pending_transcriptions(conn: &Connection) -> Result<Vec<PendingTranscription>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, source_path, source_name, language, decoding_config, normalization,
+               chunk_count, chunks, created_at, updated_at
+        FROM pending_transcriptions
+        ORDER BY created_at
+        "#,
+    )?;
+
+    let rows = stmt.query_map([], pending_from_row)?;
+    Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+}
+
+pub fn get_pending_transcription(conn: &Connection, id: &str) -> Result<Option<PendingTranscription>> {
+    Ok(conn
+        .query_row(
+            "SELECT id, source_path, source_name, language, decoding_config, normalization,
+                    chunk_count, chunks, created_at, updated_at
+             FROM pending_transcriptions WHERE id = ?1",
+            [id],
+            pending_from_row,
+        )
+        .optional()?)
+}
+
+pub fn delete_pending_transcription(conn: &Connection, id: &str) -> Result<()> {
+    conn.execute("DELETE FROM pending_transcriptions WHERE id = ?1", [id])?;
+    Ok(())
+}
+
+fn pending_from_row(row: &rusqlite::Row) -> rusqlite::Result<PendingTranscription> {
+    let json = |i: usize| -> rusqlite::Result<Option<String>> { row.get(i) };
+    Ok(PendingTranscription {
+        id: row.get(0)?,
+        source_path: row.get(1)?,
+        source_name: row.get(2)?,
+        language: json(3)?.and_then(|j| serde_json::from_str(&j).ok()),
+        decoding_config: json(4)?.and_then(|j| serde_json::from_str(&j).ok()),
+        normalization: json(5)?.and_then(|j| serde_json::from_str(&j).ok()),
+        chunk_count: row.get::<_, i64>(6)? as usize,
+        chunks: parse_json_column(row.get(7)?),
+        created_at: row.get(8)?,
+        updated_at: row.get(9)?,
+    })
+}
+
+// Job error queries
+
+/// Record a background job failure, keeping only the `max_entries` most recent
+pub fn insert_job_error(conn: &Connection, error: &JobError, max_entries: usize) -> Result<()> {
+    conn.execute(
+        "INSERT INTO job_errors (id, job, code, message, context, occurred_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![error.id, error.job, error.code, error.message, error.context, error.occurred_at],
+    )?;
+    conn.execute(
+        "DELETE FROM job_errors WHERE id NOT IN
+         (SELECT id FROM job_errors ORDER BY occurred_at DESC LIMIT ?1)",
+        [max_entries as i64],
+    )?;
     Ok(())
 }
+
+/// Most recent job failures first
+pub fn list_recent_job_errors(conn: &Connection, limit: usize) -> Result<Vec<JobError>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, job, code, message, context, occurred_at
+        FROM job_errors
+        ORDER BY occurred_at DESC
+        LIMIT ?1
+        "#,
+    )?;
+
+    let rows = stmt.query_map([limit as i64], |row| {
+        Ok(JobError {
+            id: row.get(0)?,
+            job: row.get(1)?,
+            code: row.get(2)?,
+            message: row.get(3)?,
+            context: row.get(4)?,
+            occurred_at: row.get(5)?,
+        })
+    })?;
+
+    Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+}
+
+// Inference speed queries
+
+/// Record the speed of a transcription on `backend`, keeping the `max_entries` most recent
+pub fn insert_inference_speed(
+    conn: &Connection,
+    backend: &str,
+    speed: &InferenceSpeed,
+    max_entries: usize,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO inference_speed (backend, beam_width, audio_ms, elapsed_ms, recorded_at)
+         VALUES (?1, ?2, ?3, ?4, datetime('now'))",
+        params![backend, speed.beam_width as i64, speed.audio_ms, speed.elapsed_ms],
+    )?;
+    conn.execute(
+        "DELETE FROM inference_speed WHERE backend = ?1 AND id NOT IN
+         (SELECT id FROM inference_speed WHERE backend = ?1 ORDER BY id DESC LIMIT ?2)",
+        params![backend, max_entries as i64],
+    )?;
+    Ok(())
+}
+
+/// Recorded speeds of `backend`, most recent first
+pub fn list_inference_speeds(conn: &Connection, backend: &str) -> Result<Vec<InferenceSpeed>> {
+    let mut stmt = conn.prepare(
+        "SELECT beam_width, audio_ms, elapsed_ms FROM inference_speed WHERE backend = ?1 ORDER BY id DESC",
+    )?;
+
+    let rows = stmt.query_map([backend], |row| {
+        Ok(InferenceSpeed {
+            beam_width: row.get::<_, i64>(0)? as usize,
+            audio_ms: row.get(1)?,
+            elapsed_ms: row.get(2)?,
+        })
+    })?;
+
+    Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+}
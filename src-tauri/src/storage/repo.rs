@@ -0,0 +1,146 @@
+//! Storage of transcriptions and settings behind traits
+//!
+//! Commands go through `storage::store()` instead of a SQLite connection, so
+//! another backend (encrypted file store, server for the HTTP mode) only has
+//! to implement these traits. `SqliteStore` is the one in use. Caches and
+//! diagnostics (chunk cache, job errors, inference speeds), profiles and
+//! whole-database operations (move, backup) remain SQLite-only.
+
+use crate::error::Result;
+use crate::storage::models::{
+    ActionItem, ActivityDay, Folder, HistoryCursor, SearchFilters, SearchHit, Segment, Settings, Tag, TextDraft,
+    Transcription, TranscriptionNotes, TranscriptionRevision, TranscriptionSummary, TranscriptionText, Translation,
+};
+
+/// Transcriptions of the active profile
+pub trait TranscriptionRepo: Send + Sync {
+    fn insert_transcription(&self, t: &Transcription) -> Result<()>;
+
+    /// Insert `t`, replacing the stored transcription with the same id
+    fn upsert_transcription(&self, t: &Transcription) -> Result<()>;
+
+    /// With its segments
+    fn get_transcription(&self, id: &str) -> Result<Option<Transcription>>;
+
+    /// Newest first, paged by keyset: up to `limit` after `after` (trash excluded)
+    fn list_transcriptions(
+        &self,
+        limit: Option<usize>,
+        after: Option<&HistoryCursor>,
+    ) -> Result<Vec<TranscriptionSummary>>;
+
+    fn get_transcription_text(&self, id: &str) -> Result<Option<TranscriptionText>>;
+
+    /// In time order
+    fn get_segments(&self, transcription_id: &str) -> Result<Vec<Segment>>;
+
+    /// Save the edited text as a new revision; returns the new `updated_at`
+    ///
+    /// With `expected_updated_at`, fails with `AppError::Conflict` when the
+    /// transcription was saved since.
+    fn update_transcription_text(
+        &self,
+        id: &str,
+        edited_text: &str,
+        action_items: &[ActionItem],
+        expected_updated_at: Option<&str>,
+    ) -> Result<String>;
+
+    /// Saved texts, newest first, ending with revision 0 (the transcribed text)
+    fn get_revision_history(&self, id: &str) -> Result<Vec<TranscriptionRevision>>;
+
+    /// Keep the text being edited (no revision); replaces the previous draft
+    fn save_text_draft(&self, id: &str, text: &str, base_updated_at: &str) -> Result<TextDraft>;
+
+    /// Draft left since the text was last saved, if any
+    fn get_text_draft(&self, id: &str) -> Result<Option<TextDraft>>;
+
+    fn delete_text_draft(&self, id: &str) -> Result<()>;
+
+    /// Replaces the translation in the same language
+    fn save_translation(&self, transcription_id: &str, translation: &Translation) -> Result<()>;
+
+    /// Replaces the notes in the same style
+    fn save_notes(&self, transcription_id: &str, notes: &TranscriptionNotes) -> Result<()>;
+
+    /// Best matches first
+    fn search_transcriptions(&self, query: &str, filters: &SearchFilters) -> Result<Vec<SearchHit>>;
+
+    fn get_activity_calendar(&self, year: i32) -> Result<Vec<ActivityDay>>;
+
+    /// By name, with the number of transcriptions of each
+    fn list_tags(&self) -> Result<Vec<Tag>>;
+
+    /// Returns the tag with the same name (case-insensitive) when there is one
+    fn add_tag(&self, name: &str, color: Option<&str>) -> Result<Tag>;
+
+    /// Also removes it from its transcriptions
+    fn delete_tag(&self, id: &str) -> Result<()>;
+
+    /// Tag and untag return the transcription's `updated_at`, bumped on change
+    fn tag_transcription(&self, transcription_id: &str, tag_id: &str) -> Result<String>;
+
+    fn untag_transcription(&self, transcription_id: &str, tag_id: &str) -> Result<String>;
+
+    /// Paged like `list_transcriptions`
+    fn list_by_tag(
+        &self,
+        tag_id: &str,
+        limit: Option<usize>,
+        after: Option<&HistoryCursor>,
+    ) -> Result<Vec<TranscriptionSummary>>;
+
+    /// None or blank: out of any folder; returns `updated_at` like tagging
+    fn set_folder(&self, id: &str, folder: Option<&str>) -> Result<String>;
+
+    /// By name, with their number of transcriptions
+    fn list_folders(&self) -> Result<Vec<Folder>>;
+
+    /// Paged like `list_transcriptions`
+    fn list_by_folder(
+        &self,
+        folder: &str,
+        limit: Option<usize>,
+        after: Option<&HistoryCursor>,
+    ) -> Result<Vec<TranscriptionSummary>>;
+
+    /// Set or clear the kept recording
+    fn set_audio_path(&self, id: &str, audio_path: Option<&str>) -> Result<()>;
+
+    /// Title, source name and language, without touching the text; returns
+    /// the new `updated_at`
+    fn update_metadata(&self, id: &str, title: Option<&str>, source_name: Option<&str>, language: &str) -> Result<String>;
+
+    fn trash_transcription(&self, id: &str) -> Result<()>;
+
+    fn restore_transcription(&self, id: &str) -> Result<()>;
+
+    /// Id and `updated_at` of every transcription, trash included (for sync)
+    fn list_updated_at(&self) -> Result<Vec<(String, String)>>;
+
+    /// Id and deletion time of the transcriptions deleted for good (for sync)
+    fn list_deleted(&self) -> Result<Vec<(String, String)>>;
+
+    /// Last trashed first
+    fn list_trash(&self) -> Result<Vec<TranscriptionSummary>>;
+
+    /// Returns the number of transcriptions deleted
+    fn purge_trash(&self) -> Result<usize>;
+
+    /// For good, without going through the trash
+    fn delete_transcription(&self, id: &str) -> Result<()>;
+
+    fn delete_all_transcriptions(&self) -> Result<()>;
+}
+
+/// Settings of the active profile
+pub trait SettingsRepo: Send + Sync {
+    fn get_settings(&self) -> Result<Settings>;
+
+    fn update_settings(&self, settings: &Settings) -> Result<()>;
+}
+
+/// A complete storage backend
+pub trait Store: TranscriptionRepo + SettingsRepo {}
+
+impl<T: TranscriptionRepo + SettingsRepo> Store for T {}
@@ -0,0 +1,160 @@
+//! Full-text search over the history (FTS5)
+//!
+//! `transcription_fts` indexes the raw and edited texts, `segment_fts` each
+//! segment; both are kept in sync by triggers (migration 008). A transcription
+//! matches when its text or one of its segments does, and is ranked by its
+//! best bm25 score. Accents are ignored.
+
+use crate::error::Result;
+use crate::storage::models::{SearchFilters, SearchHit, TranscriptionSummary};
+use crate::storage::queries::{active_profile_id, split_tags};
+use rusqlite::{params, Connection};
+
+/// Results returned when the filters set no limit
+const DEFAULT_LIMIT: usize = 50;
+
+/// Marks around the matched words in snippets
+const MATCH_START: &str = "<mark>";
+const MATCH_END: &str = "</mark>";
+
+/// FTS5 query for what the user typed: "quoted phrases" stay phrases, other
+/// words must all appear, a trailing * matches a prefix (budg*)
+///
+/// Everything is quoted, so FTS5 operators and column filters typed by the
+/// user are searched as plain words. None when there is nothing to search.
+pub fn fts_query(input: &str) -> Option<String> {
+    fn quote(term: &str) -> String {
+        format!("\"{}\"", term.replace('"', "\"\""))
+    }
+
+    let mut terms = Vec::new();
+    for (i, part) in input.split('"').enumerate() {
+        if i % 2 == 1 {
+            if !part.trim().is_empty() {
+                terms.push(quote(part.trim()));
+            }
+            continue;
+        }
+        for word in part.split_whitespace() {
+            let (word, prefix) = match word.strip_suffix('*') {
+                Some(stem) => (stem, true),
+                None => (word, false),
+            };
+            if word.chars().any(char::is_alphanumeric) {
+                terms.push(if prefix { format!("{}*", quote(word)) } else { quote(word) });
+            }
+        }
+    }
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Transcriptions of the active profile matching `query`, best first
+pub fn search_transcriptions(
+    conn: &Connection,
+    query: &str,
+    filters: &SearchFilters,
+) -> Result<Vec<SearchHit>> {
+    let Some(fts) = fts_query(query) else {
+        return Ok(Vec::new());
+    };
+
+    // SQLite takes the other columns of an aggregate query from the row
+    // holding the MIN(): the snippet and segment are those of the best match
+    let mut stmt = conn.prepare(
+        r#"
+        WITH hits AS (
+            SELECT transcription_id, NULL AS segment_id, bm25(transcription_fts) AS score,
+                   snippet(transcription_fts, -1, ?2, ?3, '…', 12) AS snippet
+            FROM transcription_fts WHERE transcription_fts MATCH ?1
+            UNION ALL
+            SELECT transcription_id, segment_id, bm25(segment_fts),
+                   snippet(segment_fts, 2, ?2, ?3, '…', 12)
+            FROM segment_fts WHERE segment_fts MATCH ?1
+        )
+        SELECT t.id, t.created_at, t.updated_at, t.source_type, t.source_name, t.duration_ms, t.language,
+               t.preview, t.is_edited, t.source_path, t.is_draft,
+               MIN(h.score), h.snippet, s.start_ms, t.folder,
+               (SELECT group_concat(g.name, char(31)) FROM transcription_tags tt JOIN tags g ON g.id = tt.tag_id WHERE tt.transcription_id = t.id),
+               t.title
+        FROM hits h
+        JOIN transcriptions t ON t.id = h.transcription_id
+        LEFT JOIN segments s ON s.id = h.segment_id
+        WHERE COALESCE(t.profile_id, 'default') = ?4
+          AND t.deleted_at IS NULL
+          AND (?5 IS NULL OR date(t.created_at, 'localtime') >= ?5)
+          AND (?6 IS NULL OR date(t.created_at, 'localtime') <= ?6)
+          AND (?7 IS NULL OR t.source_type = ?7)
+          AND (?8 IS NULL OR t.language = ?8)
+          AND (?10 IS NULL OR t.id IN (SELECT transcription_id FROM transcription_tags WHERE tag_id = ?10))
+          AND (?11 IS NULL OR t.folder = ?11)
+        GROUP BY t.id
+        ORDER BY MIN(h.score)
+        LIMIT ?9
+        "#,
+    )?;
+
+    let rows = stmt.query_map(
+        params![
+            fts,
+            MATCH_START,
+            MATCH_END,
+            active_profile_id(conn)?,
+            filters.from,
+            filters.to,
+            filters.source_type,
+            filters.language,
+            filters.limit.unwrap_or(DEFAULT_LIMIT) as i64,
+            filters.tag,
+            filters.folder,
+        ],
+        |row| {
+            Ok(SearchHit {
+                transcription: TranscriptionSummary {
+                    id: row.get(0)?,
+                    created_at: row.get(1)?,
+                    updated_at: row.get(2)?,
+                    source_type: row.get(3)?,
+                    source_name: row.get(4)?,
+                    source_path: row.get(9)?,
+                    duration_ms: row.get(5)?,
+                    language: row.get(6)?,
+                    preview: row.get(7)?,
+                    is_edited: row.get::<_, i32>(8)? != 0,
+                    is_draft: row.get::<_, i32>(10)? != 0,
+                    deleted_at: None,
+                    folder: row.get(14)?,
+                    tags: split_tags(row.get(15)?),
+                    title: row.get(16)?,
+                },
+                // bm25 is lower for better matches
+                score: -row.get::<_, f64>(11)?,
+                snippet: row.get(12)?,
+                start_ms: row.get(13)?,
+            })
+        },
+    )?;
+
+    Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fts_query() {
+        assert_eq!(fts_query("budget ete").as_deref(), Some("\"budget\" \"ete\""));
+        assert_eq!(
+            fts_query("\"ordre du jour\" budg*").as_deref(),
+            Some("\"ordre du jour\" \"budg\"*")
+        );
+        // Operators and column filters are plain words
+        assert_eq!(
+            fts_query("raw_text:NEAR(a OR b) -").as_deref(),
+            Some("\"raw_text:NEAR(a\" \"OR\" \"b)\"")
+        );
+        // Unbalanced quote: the rest is a phrase
+        assert_eq!(fts_query("\"l'ete").as_deref(), Some("\"l'ete\""));
+        assert_eq!(fts_query("  \"\" * "), None);
+    }
+}
@@ -0,0 +1,709 @@
+//! SQLite implementation of the storage traits (the default backend)
+
+use crate::error::Result;
+use crate::storage::models::{
+    ActionItem, ActivityDay, Folder, HistoryCursor, SearchFilters, SearchHit, Segment, Settings, Tag, TextDraft,
+    Transcription, TranscriptionNotes, TranscriptionRevision, TranscriptionSummary, TranscriptionText, Translation,
+};
+use crate::storage::repo::{SettingsRepo, TranscriptionRepo};
+use crate::storage::{queries, search};
+use parking_lot::Mutex;
+use rusqlite::Connection;
+
+/// Store over one SQLite connection
+pub struct SqliteStore {
+    pub(super) conn: Mutex<Connection>,
+}
+
+impl SqliteStore {
+    pub fn new(conn: Connection) -> Self {
+        Self { conn: Mutex::new(conn) }
+    }
+
+    fn with<T>(&self, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+        f(&self.conn.lock())
+    }
+}
+
+impl TranscriptionRepo for SqliteStore {
+    fn insert_transcription(&self, t: &Transcription) -> Result<()> {
+        self.with(|conn| queries::insert_transcription(conn, t))
+    }
+
+    fn upsert_transcription(&self, t: &Transcription) -> Result<()> {
+        self.with(|conn| queries::upsert_transcription(conn, t))
+    }
+
+    fn get_transcription(&self, id: &str) -> Result<Option<Transcription>> {
+        self.with(|conn| queries::get_transcription(conn, id))
+    }
+
+    fn list_transcriptions(
+        &self,
+        limit: Option<usize>,
+        after: Option<&HistoryCursor>,
+    ) -> Result<Vec<TranscriptionSummary>> {
+        self.with(|conn| queries::list_transcriptions(conn, limit, after))
+    }
+
+    fn get_transcription_text(&self, id: &str) -> Result<Option<TranscriptionText>> {
+        self.with(|conn| queries::get_transcription_text(conn, id))
+    }
+
+    fn get_segments(&self, transcription_id: &str) -> Result<Vec<Segment>> {
+        self.with(|conn| queries::get_segments(conn, transcription_id))
+    }
+
+    fn update_transcription_text(
+        &self,
+        id: &str,
+        edited_text: &str,
+        action_items: &[ActionItem],
+        expected_updated_at: Option<&str>,
+    ) -> Result<String> {
+        self.with(|conn| {
+            queries::update_transcription_text(conn, id, edited_text, action_items, expected_updated_at)
+        })
+    }
+
+    fn get_revision_history(&self, id: &str) -> Result<Vec<TranscriptionRevision>> {
+        self.with(|conn| queries::get_revision_history(conn, id))
+    }
+
+    fn save_text_draft(&self, id: &str, text: &str, base_updated_at: &str) -> Result<TextDraft> {
+        self.with(|conn| queries::save_text_draft(conn, id, text, base_updated_at))
+    }
+
+    fn get_text_draft(&self, id: &str) -> Result<Option<TextDraft>> {
+        self.with(|conn| queries::get_text_draft(conn, id))
+    }
+
+    fn delete_text_draft(&self, id: &str) -> Result<()> {
+        self.with(|conn| queries::delete_text_draft(conn, id))
+    }
+
+    fn save_translation(&self, transcription_id: &str, translation: &Translation) -> Result<()> {
+        self.with(|conn| queries::save_translation(conn, transcription_id, translation))
+    }
+
+    fn save_notes(&self, transcription_id: &str, notes: &TranscriptionNotes) -> Result<()> {
+        self.with(|conn| queries::save_notes(conn, transcription_id, notes))
+    }
+
+    fn search_transcriptions(&self, query: &str, filters: &SearchFilters) -> Result<Vec<SearchHit>> {
+        self.with(|conn| search::search_transcriptions(conn, query, filters))
+    }
+
+    fn get_activity_calendar(&self, year: i32) -> Result<Vec<ActivityDay>> {
+        self.with(|conn| queries::get_activity_calendar(conn, year))
+    }
+
+    fn list_tags(&self) -> Result<Vec<Tag>> {
+        self.with(queries::list_tags)
+    }
+
+    fn add_tag(&self, name: &str, color: Option<&str>) -> Result<Tag> {
+        self.with(|conn| queries::add_tag(conn, name, color))
+    }
+
+    fn delete_tag(&self, id: &str) -> Result<()> {
+        self.with(|conn| queries::delete_tag(conn, id))
+    }
+
+    fn tag_transcription(&self, transcription_id: &str, tag_id: &str) -> Result<String> {
+        self.with(|conn| queries::tag_transcription(conn, transcription_id, tag_id))
+    }
+
+    fn untag_transcription(&self, transcription_id: &str, tag_id: &str) -> Result<String> {
+        self.with(|conn| queries::untag_transcription(conn, transcription_id, tag_id))
+    }
+
+    fn list_by_tag(
+        &self,
+        tag_id: &str,
+        limit: Option<usize>,
+        after: Option<&HistoryCursor>,
+    ) -> Result<Vec<TranscriptionSummary>> {
+        self.with(|conn| queries::list_by_tag(conn, tag_id, limit, after))
+    }
+
+    fn set_folder(&self, id: &str, folder: Option<&str>) -> Result<String> {
+        self.with(|conn| queries::set_folder(conn, id, folder))
+    }
+
+    fn list_folders(&self) -> Result<Vec<Folder>> {
+        self.with(queries::list_folders)
+    }
+
+    fn list_by_folder(
+        &self,
+        folder: &str,
+        limit: Option<usize>,
+        after: Option<&HistoryCursor>,
+    ) -> Result<Vec<TranscriptionSummary>> {
+        self.with(|conn| queries::list_by_folder(conn, folder, limit, after))
+    }
+
+    fn set_audio_path(&self, id: &str, audio_path: Option<&str>) -> Result<()> {
+        self.with(|conn| queries::set_audio_path(conn, id, audio_path))
+    }
+
+    fn update_metadata(&self, id: &str, title: Option<&str>, source_name: Option<&str>, language: &str) -> Result<String> {
+        self.with(|conn| queries::update_transcription_metadata(conn, id, title, source_name, language))
+    }
+
+    fn trash_transcription(&self, id: &str) -> Result<()> {
+        self.with(|conn| queries::trash_transcription(conn, id))
+    }
+
+    fn restore_transcription(&self, id: &str) -> Result<()> {
+        self.with(|conn| queries::restore_transcription(conn, id))
+    }
+
+    fn list_updated_at(&self) -> Result<Vec<(String, String)>> {
+        self.with(queries::list_updated_at)
+    }
+
+    fn list_deleted(&self) -> Result<Vec<(String, String)>> {
+        self.with(queries::list_deleted)
+    }
+
+    fn list_trash(&self) -> Result<Vec<TranscriptionSummary>> {
+        self.with(queries::list_trash)
+    }
+
+    fn purge_trash(&self) -> Result<usize> {
+        self.with(queries::purge_trash)
+    }
+
+    fn delete_transcription(&self, id: &str) -> Result<()> {
+        self.with(|conn| queries::delete_transcription(conn, id))
+    }
+
+    fn delete_all_transcriptions(&self) -> Result<()> {
+        self.with(queries::delete_all_transcriptions)
+    }
+}
+
+impl SettingsRepo for SqliteStore {
+    fn get_settings(&self) -> Result<Settings> {
+        self.with(queries::get_settings)
+    }
+
+    fn update_settings(&self, settings: &Settings) -> Result<()> {
+        self.with(|conn| queries::update_settings(conn, settings))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TranscriptionLanguage;
+    use crate::error::AppError;
+    use crate::storage::database::{enforce_foreign_keys, migrate};
+    use crate::storage::models::{PendingChunk, PendingTranscription, SegmentWord, SummaryStyle, TranscriptionSource};
+
+    fn store() -> SqliteStore {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn).unwrap();
+        enforce_foreign_keys(&conn).unwrap();
+        SqliteStore::new(conn)
+    }
+
+    fn transcription(id: &str, created_at: &str, text: &str) -> Transcription {
+        Transcription {
+            id: id.to_string(),
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+            source_type: TranscriptionSource::Dictation,
+            source_name: None,
+            source_path: None,
+            original_sample_rate: Some(48000),
+            duration_ms: 4000,
+            language: "fr".to_string(),
+            segments: vec![Segment {
+                id: format!("{}-s0", id),
+                start_ms: 0,
+                end_ms: 4000,
+                text: text.to_string(),
+                confidence: 0.9,
+                speaker: None,
+                language: None,
+                words: vec![SegmentWord {
+                    text: text.split(' ').next().unwrap_or_default().to_string(),
+                    start_ms: 0,
+                    end_ms: 500,
+                    confidence: 0.8,
+                }],
+            }],
+            raw_text: text.to_string(),
+            edited_text: None,
+            is_edited: false,
+            is_draft: false,
+            warnings: Vec::new(),
+            normalization_gain: None,
+            action_items: Vec::new(),
+            markers: Vec::new(),
+            pauses: Vec::new(),
+            capture: None,
+            confidence: Some(0.9),
+            audio_path: None,
+            translations: Vec::new(),
+            notes: Vec::new(),
+            folder: None,
+            tags: Vec::new(),
+            title: None,
+            deleted_at: None,
+        }
+    }
+
+    fn ids(summaries: &[TranscriptionSummary]) -> Vec<&str> {
+        summaries.iter().map(|s| s.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_insert_get_and_upsert() {
+        let store = store();
+        let t = transcription("a", "2026-03-01T10:00:00+00:00", "bonjour tout le monde");
+        store.insert_transcription(&t).unwrap();
+
+        let loaded = store.get_transcription("a").unwrap().unwrap();
+        assert_eq!(loaded.raw_text, "bonjour tout le monde");
+        assert_eq!(loaded.original_sample_rate, Some(48000));
+        assert_eq!(loaded.segments.len(), 1);
+        assert_eq!(loaded.segments[0].words[0].text, "bonjour");
+        assert_eq!(store.get_segments("a").unwrap()[0].id, "a-s0");
+        assert!(store.get_transcription("missing").unwrap().is_none());
+
+        store.set_audio_path("a", Some("/tmp/a.wav")).unwrap();
+        assert_eq!(store.get_transcription("a").unwrap().unwrap().audio_path.as_deref(), Some("/tmp/a.wav"));
+        store.set_audio_path("a", None).unwrap();
+        assert!(store.get_transcription("a").unwrap().unwrap().audio_path.is_none());
+
+        let mut replaced = t.clone();
+        replaced.raw_text = "au revoir".to_string();
+        replaced.segments.clear();
+        store.upsert_transcription(&replaced).unwrap();
+        let text = store.get_transcription_text("a").unwrap().unwrap();
+        assert_eq!(text.raw_text, "au revoir");
+        assert!(store.get_segments("a").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_list_pages_newest_first() {
+        let store = store();
+        for (id, day) in [("a", 1), ("b", 2), ("c", 3)] {
+            let created_at = format!("2026-03-0{}T10:00:00+00:00", day);
+            store.insert_transcription(&transcription(id, &created_at, "texte")).unwrap();
+        }
+
+        let first = store.list_transcriptions(Some(2), None).unwrap();
+        assert_eq!(ids(&first), ["c", "b"]);
+        let last = &first[1];
+        let cursor = HistoryCursor {
+            created_at: last.created_at.clone(),
+            id: last.id.clone(),
+        };
+        assert_eq!(ids(&store.list_transcriptions(Some(2), Some(&cursor)).unwrap()), ["a"]);
+        assert_eq!(ids(&store.list_transcriptions(None, None).unwrap()), ["c", "b", "a"]);
+    }
+
+    #[test]
+    fn test_update_text_detects_conflicts() {
+        let store = store();
+        let t = transcription("a", "2026-03-01T10:00:00+00:00", "premier jet");
+        store.insert_transcription(&t).unwrap();
+
+        let updated_at = store
+            .update_transcription_text("a", "version revue", &[], Some(&t.updated_at))
+            .unwrap();
+        let loaded = store.get_transcription("a").unwrap().unwrap();
+        assert_eq!(loaded.edited_text.as_deref(), Some("version revue"));
+        assert!(loaded.is_edited);
+        assert_eq!(loaded.updated_at, updated_at);
+
+        // Saved with the stale timestamp: conflict, unless forced
+        let stale = store.update_transcription_text("a", "autre version", &[], Some(&t.updated_at));
+        match stale {
+            Err(AppError::Conflict { id, current_updated_at }) => {
+                assert_eq!((id.as_str(), current_updated_at.as_str()), ("a", updated_at.as_str()))
+            }
+            other => panic!("expected a conflict, got {:?}", other),
+        }
+        // Nothing written: neither the text nor a revision
+        let kept = store.get_transcription("a").unwrap().unwrap();
+        assert_eq!((kept.edited_text.as_deref(), kept.updated_at.as_str()), (Some("version revue"), updated_at.as_str()));
+        assert_eq!(store.get_revision_history("a").unwrap().len(), 2);
+
+        // The other editor reloads and saves on the current version, or forces
+        let reloaded = store
+            .update_transcription_text("a", "version fusionnee", &[], Some(&updated_at))
+            .unwrap();
+        assert!(store.update_transcription_text("a", "x", &[], Some(&updated_at)).is_err());
+        store.update_transcription_text("a", "autre version", &[], None).unwrap();
+        let forced = store.get_transcription("a").unwrap().unwrap();
+        assert_eq!(forced.edited_text.as_deref(), Some("autre version"));
+        assert_ne!(forced.updated_at, reloaded);
+
+        let missing = store.update_transcription_text("missing", "x", &[], None);
+        assert!(matches!(missing, Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_revision_history() {
+        let store = store();
+        let t = transcription("a", "2026-03-01T10:00:00+00:00", "premier jet");
+        store.insert_transcription(&t).unwrap();
+        store.update_transcription_text("a", "version une", &[], None).unwrap();
+        store.update_transcription_text("a", "version deux", &[], None).unwrap();
+
+        let history = store.get_revision_history("a").unwrap();
+        let texts: Vec<(i64, &str)> = history.iter().map(|r| (r.revision, r.text.as_str())).collect();
+        assert_eq!(texts, [(2, "version deux"), (1, "version une"), (0, "premier jet")]);
+        assert_eq!(history[2].created_at, t.created_at);
+
+        // Replaced by sync: the history stays; deleted: it goes
+        store.upsert_transcription(&t).unwrap();
+        assert_eq!(store.get_revision_history("a").unwrap().len(), 3);
+        store.delete_transcription("a").unwrap();
+        assert!(matches!(store.get_revision_history("a"), Err(AppError::NotFound(_))));
+        store.insert_transcription(&t).unwrap();
+        assert_eq!(store.get_revision_history("a").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_text_draft_cleared_when_saved() {
+        let store = store();
+        let t = transcription("a", "2026-03-01T10:00:00+00:00", "premier jet");
+        store.insert_transcription(&t).unwrap();
+        store.save_text_draft("a", "premier", &t.updated_at).unwrap();
+        store.save_text_draft("a", "premier jet revu", &t.updated_at).unwrap();
+        let draft = store.get_text_draft("a").unwrap().unwrap();
+        assert_eq!((draft.text.as_str(), draft.base_updated_at.as_str()), ("premier jet revu", t.updated_at.as_str()));
+        assert!(matches!(store.save_text_draft("missing", "x", ""), Err(AppError::NotFound(_))));
+
+        store.update_transcription_text("a", "premier jet revu", &[], None).unwrap();
+        assert!(store.get_text_draft("a").unwrap().is_none());
+
+        store.save_text_draft("a", "encore", &t.updated_at).unwrap();
+        store.delete_transcription("a").unwrap();
+        assert!(store.get_text_draft("a").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_translations_follow_the_transcription() {
+        let store = store();
+        let t = transcription("a", "2026-03-01T10:00:00+00:00", "bonjour");
+        store.insert_transcription(&t).unwrap();
+        let translation = |text: &str| Translation {
+            language: "en".to_string(),
+            text: text.to_string(),
+            created_at: "2026-03-02T10:00:00+00:00".to_string(),
+        };
+        store.save_translation("a", &translation("hi")).unwrap();
+        store.save_translation("a", &translation("hello")).unwrap();
+        assert_eq!(store.get_transcription("a").unwrap().unwrap().translations, [translation("hello")]);
+
+        // Kept when replaced by sync, gone with the transcription
+        store.upsert_transcription(&t).unwrap();
+        assert_eq!(store.get_transcription("a").unwrap().unwrap().translations.len(), 1);
+        store.delete_transcription("a").unwrap();
+        store.insert_transcription(&t).unwrap();
+        assert!(store.get_transcription("a").unwrap().unwrap().translations.is_empty());
+    }
+
+    #[test]
+    fn test_notes_replaced_per_style() {
+        let store = store();
+        store.insert_transcription(&transcription("a", "2026-03-01T10:00:00+00:00", "bonjour")).unwrap();
+        let notes = |style: SummaryStyle, summary: &str, created_at: &str| TranscriptionNotes {
+            style,
+            summary: summary.to_string(),
+            key_points: vec!["budget".to_string()],
+            action_items: Vec::new(),
+            model: Some("llama".to_string()),
+            created_at: created_at.to_string(),
+        };
+        store.save_notes("a", &notes(SummaryStyle::Brief, "v1", "2026-03-02T10:00:00+00:00")).unwrap();
+        store.save_notes("a", &notes(SummaryStyle::Minutes, "cr", "2026-03-03T10:00:00+00:00")).unwrap();
+        store.save_notes("a", &notes(SummaryStyle::Brief, "v2", "2026-03-04T10:00:00+00:00")).unwrap();
+
+        let saved = store.get_transcription("a").unwrap().unwrap().notes;
+        let summaries: Vec<&str> = saved.iter().map(|n| n.summary.as_str()).collect();
+        assert_eq!(summaries, ["v2", "cr"]);
+        assert_eq!(saved[0].key_points, ["budget"]);
+
+        store.purge_trash().unwrap();
+        store.delete_all_transcriptions().unwrap();
+        store.insert_transcription(&transcription("a", "2026-03-01T10:00:00+00:00", "bonjour")).unwrap();
+        assert!(store.get_transcription("a").unwrap().unwrap().notes.is_empty());
+    }
+
+    #[test]
+    fn test_tags_and_folders() {
+        let store = store();
+        store.insert_transcription(&transcription("a", "2026-03-01T10:00:00+00:00", "client A")).unwrap();
+        store.insert_transcription(&transcription("b", "2026-03-02T10:00:00+00:00", "client B")).unwrap();
+
+        let client = store.add_tag("Client", Some("#f00")).unwrap();
+        assert_eq!(store.add_tag(" client ", None).unwrap().id, client.id);
+        let urgent = store.add_tag("urgent", None).unwrap();
+        store.tag_transcription("a", &client.id).unwrap();
+        store.tag_transcription("a", &urgent.id).unwrap();
+        store.tag_transcription("b", &client.id).unwrap();
+        assert!(matches!(store.tag_transcription("x", &client.id), Err(AppError::NotFound(_))));
+        // Tags are part of the transcription for sync
+        let tagged_at = store.tag_transcription("a", &urgent.id).unwrap();
+        assert!(tagged_at.as_str() > "2026-03-01T10:00:00+00:00");
+        assert_eq!(store.get_transcription("a").unwrap().unwrap().updated_at, tagged_at);
+
+        let ids = |list: Vec<TranscriptionSummary>| list.into_iter().map(|t| t.id).collect::<Vec<_>>();
+        assert_eq!(ids(store.list_by_tag(&urgent.id, None, None).unwrap()), ["a"]);
+        assert_eq!(ids(store.list_by_tag(&client.id, None, None).unwrap()), ["b", "a"]);
+        assert_eq!(store.list_transcriptions(None, None).unwrap()[1].tags, ["Client", "urgent"]);
+        let counts: Vec<(String, i64)> = store.list_tags().unwrap().into_iter().map(|t| (t.name, t.count)).collect();
+        assert_eq!(counts, [("Client".to_string(), 2), ("urgent".to_string(), 1)]);
+
+        let filed_at = store.set_folder("b", Some(" Projet X ")).unwrap();
+        assert!(filed_at.as_str() > "2026-03-02T10:00:00+00:00");
+        assert_eq!(store.set_folder("b", Some("Projet X")).unwrap(), filed_at);
+        assert_eq!(store.get_transcription("b").unwrap().unwrap().updated_at, filed_at);
+        assert_eq!(ids(store.list_by_folder("Projet X", None, None).unwrap()), ["b"]);
+        assert_eq!(store.list_folders().unwrap()[0].count, 1);
+
+        // Tags and folder travel with the transcription (sync)
+        let b = store.get_transcription("b").unwrap().unwrap();
+        assert_eq!((b.folder.as_deref(), b.tags.as_slice()), (Some("Projet X"), ["Client".to_string()].as_slice()));
+        store.upsert_transcription(&b).unwrap();
+        assert_eq!(store.get_transcription("b").unwrap().unwrap().tags, ["Client"]);
+
+        store.untag_transcription("a", &urgent.id).unwrap();
+        store.delete_tag(&client.id).unwrap();
+        assert!(store.get_transcription("a").unwrap().unwrap().tags.is_empty());
+        assert_eq!(store.list_tags().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_update_metadata() {
+        let store = store();
+        store.insert_transcription(&transcription("a", "2026-03-01T10:00:00+00:00", "bonjour")).unwrap();
+        let before = store.get_transcription("a").unwrap().unwrap();
+
+        let updated_at = store.update_metadata("a", Some(" Reunion budget "), Some("audio.wav"), "en").unwrap();
+        let after = store.get_transcription("a").unwrap().unwrap();
+        assert_eq!(after.updated_at, updated_at);
+        assert_eq!(after.title.as_deref(), Some("Reunion budget"));
+        assert_eq!(after.source_name.as_deref(), Some("audio.wav"));
+        assert_eq!(after.language, "en");
+        assert!(after.updated_at > before.updated_at);
+        assert_eq!(after.raw_text, before.raw_text);
+        assert_eq!(store.list_transcriptions(None, None).unwrap()[0].title.as_deref(), Some("Reunion budget"));
+
+        store.update_metadata("a", Some("  "), None, "en").unwrap();
+        assert!(store.get_transcription("a").unwrap().unwrap().title.is_none());
+        assert!(matches!(store.update_metadata("x", None, None, "fr"), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_trash_restore_and_purge() {
+        let store = store();
+        store.insert_transcription(&transcription("a", "2026-03-01T10:00:00+00:00", "garder")).unwrap();
+        store.insert_transcription(&transcription("b", "2026-03-02T10:00:00+00:00", "jeter")).unwrap();
+
+        store.trash_transcription("b").unwrap();
+        assert_eq!(ids(&store.list_transcriptions(None, None).unwrap()), ["a"]);
+        let trash = store.list_trash().unwrap();
+        assert_eq!(ids(&trash), ["b"]);
+        assert!(trash[0].deleted_at.is_some());
+        assert!(store.search_transcriptions("jeter", &SearchFilters::default()).unwrap().is_empty());
+
+        store.restore_transcription("b").unwrap();
+        assert_eq!(ids(&store.list_transcriptions(None, None).unwrap()), ["b", "a"]);
+
+        store.trash_transcription("b").unwrap();
+        assert_eq!(store.purge_trash().unwrap(), 1);
+        assert!(store.get_transcription("b").unwrap().is_none());
+        assert!(store.list_trash().unwrap().is_empty());
+        assert!(matches!(store.trash_transcription("b"), Err(AppError::NotFound(_))));
+
+        store.delete_transcription("a").unwrap();
+        assert!(store.list_transcriptions(None, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_trash_is_a_synced_change() {
+        let store = store();
+        let t = transcription("a", "2026-03-01T10:00:00+00:00", "garder");
+        store.insert_transcription(&t).unwrap();
+
+        store.trash_transcription("a").unwrap();
+        let trashed = store.get_transcription("a").unwrap().unwrap();
+        assert!(trashed.deleted_at.is_some());
+        assert!(trashed.updated_at > t.updated_at);
+        assert_eq!(store.list_updated_at().unwrap(), [("a".to_string(), trashed.updated_at.clone())]);
+
+        // Received from another machine: the trash state comes with it
+        store.upsert_transcription(&trashed).unwrap();
+        assert_eq!(ids(&store.list_trash().unwrap()), ["a"]);
+        store.upsert_transcription(&Transcription { deleted_at: None, ..trashed.clone() }).unwrap();
+        assert_eq!(ids(&store.list_transcriptions(None, None).unwrap()), ["a"]);
+
+        store.trash_transcription("a").unwrap();
+        store.restore_transcription("a").unwrap();
+        let restored = store.get_transcription("a").unwrap().unwrap();
+        assert!(restored.deleted_at.is_none());
+        assert!(restored.updated_at > trashed.updated_at);
+
+        // Out of reach from another profile
+        store
+            .with(|conn| {
+                let other = queries::create_profile(conn, "Autre")?;
+                queries::switch_profile(conn, &other.id)
+            })
+            .unwrap();
+        assert!(matches!(store.trash_transcription("a"), Err(AppError::NotFound(_))));
+        assert!(store.list_updated_at().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_deletions_are_recorded_for_sync() {
+        let store = store();
+        let deleted_ids = |store: &SqliteStore| -> Vec<String> {
+            store.list_deleted().unwrap().into_iter().map(|(id, _)| id).collect()
+        };
+        store.insert_transcription(&transcription("a", "2026-03-01T10:00:00+00:00", "un")).unwrap();
+        store.insert_transcription(&transcription("b", "2026-03-02T10:00:00+00:00", "deux")).unwrap();
+        store.insert_transcription(&transcription("c", "2026-03-03T10:00:00+00:00", "trois")).unwrap();
+
+        store.delete_transcription("a").unwrap();
+        store.trash_transcription("b").unwrap();
+        store.purge_trash().unwrap();
+        assert_eq!(deleted_ids(&store), ["a", "b"]);
+        assert!(store.list_deleted().unwrap().iter().all(|(_, deleted_at)| deleted_at.as_str() > "2026-03-03"));
+        assert_eq!(store.list_updated_at().unwrap().len(), 1);
+
+        // Received again (sync, backup): no longer deleted
+        store.upsert_transcription(&transcription("a", "2026-03-01T10:00:00+00:00", "un")).unwrap();
+        assert_eq!(deleted_ids(&store), ["b"]);
+
+        store.delete_all_transcriptions().unwrap();
+        assert_eq!(deleted_ids(&store), ["a", "b", "c"]);
+
+        // Each profile syncs its own deletions
+        store
+            .with(|conn| {
+                let other = queries::create_profile(conn, "Autre")?;
+                queries::switch_profile(conn, &other.id)
+            })
+            .unwrap();
+        assert!(deleted_ids(&store).is_empty());
+    }
+
+    #[test]
+    fn test_failed_delete_leaves_everything() {
+        let store = store();
+        store.insert_transcription(&transcription("a", "2026-03-01T10:00:00+00:00", "un")).unwrap();
+        store
+            .with(|conn| {
+                conn.execute_batch(
+                    "CREATE TRIGGER refuse BEFORE DELETE ON transcriptions
+                     BEGIN SELECT RAISE(ABORT, 'refus'); END;",
+                )?;
+                Ok(())
+            })
+            .unwrap();
+
+        assert!(store.delete_transcription("a").is_err());
+        assert!(store.delete_all_transcriptions().is_err());
+        // Nothing half-deleted, and no deletion sent to the other devices
+        assert_eq!(store.get_segments("a").unwrap().len(), 1);
+        assert!(store.get_transcription_text("a").unwrap().is_some());
+        assert!(store.list_deleted().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_and_delete_all() {
+        let store = store();
+        store
+            .insert_transcription(&transcription("a", "2026-03-01T10:00:00+00:00", "le budget de l'ete"))
+            .unwrap();
+        store
+            .insert_transcription(&transcription("b", "2026-03-02T10:00:00+00:00", "le planning"))
+            .unwrap();
+
+        // Accents are ignored
+        let hits = store.search_transcriptions("été", &SearchFilters::default()).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].transcription.id, "a");
+        assert!(hits[0].snippet.contains("<mark>ete</mark>"));
+
+        let days = store.get_activity_calendar(2026).unwrap();
+        assert_eq!(days.iter().map(|d| d.count).sum::<i64>(), 2);
+
+        store.delete_all_transcriptions().unwrap();
+        assert!(store.list_transcriptions(None, None).unwrap().is_empty());
+        assert!(store.search_transcriptions("planning", &SearchFilters::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_settings_roundtrip() {
+        let store = store();
+        let mut settings = store.get_settings().unwrap();
+        assert_eq!(settings.trash_retention_days, 30);
+
+        settings.theme = "dark".to_string();
+        settings.vocabulary = vec!["WakaScribe".to_string()];
+        settings.trash_retention_days = 7;
+        settings.transcription.language = TranscriptionLanguage::French;
+        settings.transcription.beam_width = 5;
+        settings.openvino_performance.num_threads = Some(2);
+        store.update_settings(&settings).unwrap();
+
+        let loaded = store.get_settings().unwrap();
+        assert_eq!(loaded.theme, "dark");
+        assert_eq!(loaded.vocabulary, ["WakaScribe"]);
+        assert_eq!(loaded.trash_retention_days, 7);
+        assert_eq!(loaded.transcription, settings.transcription);
+        assert_eq!(loaded.transcription.decoding_config().beam_width, 5);
+        assert_eq!(loaded.openvino_performance, settings.openvino_performance);
+    }
+
+    #[test]
+    fn test_pending_transcription_chunks() {
+        let store = store();
+        let conn = store.conn.lock();
+        let pending = PendingTranscription {
+            id: "p".to_string(),
+            source_path: "/tmp/reunion.wav".to_string(),
+            source_name: Some("reunion.wav".to_string()),
+            language: Some(TranscriptionLanguage::French),
+            decoding_config: None,
+            normalization: None,
+            chunk_count: 0,
+            chunks: Vec::new(),
+            created_at: "2026-03-01T10:00:00+00:00".to_string(),
+            updated_at: "2026-03-01T10:00:00+00:00".to_string(),
+        };
+        queries::insert_pending_transcription(&conn, &pending).unwrap();
+
+        let chunk = |index: usize, text: &str| PendingChunk {
+            index,
+            start_ms: index as i64 * 10_000,
+            end_ms: (index as i64 + 1) * 10_000,
+            text: text.to_string(),
+            continues_word: false,
+        };
+        queries::append_pending_chunk(&conn, "p", 3, &chunk(1, "deux")).unwrap();
+        queries::append_pending_chunk(&conn, "p", 3, &chunk(0, "un")).unwrap();
+        queries::append_pending_chunk(&conn, "p", 3, &chunk(1, "deux bis")).unwrap();
+
+        let loaded = queries::get_pending_transcription(&conn, "p").unwrap().unwrap();
+        assert_eq!(loaded.chunk_count, 3);
+        assert_eq!(loaded.language, Some(TranscriptionLanguage::French));
+        assert_eq!(loaded.chunks, [chunk(0, "un"), chunk(1, "deux bis")]);
+        assert!(queries::append_pending_chunk(&conn, "x", 3, &chunk(0, "")).is_err());
+
+        queries::delete_pending_transcription(&conn, "p").unwrap();
+        assert!(queries::list_pending_transcriptions(&conn).unwrap().is_empty());
+    }
+}
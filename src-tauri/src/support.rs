@@ -0,0 +1,464 @@
+//! Support bundle: one zip file users can attach to a bug report
+//!
+//! Contains the tail of the log file and the recent log events, app/engine
+//! status, system information, redacted settings, the model files manifest,
+//! the diagnostics of the last failed transcription and the recent background
+//! job failures. Vocabulary, substitution rules and anything that looks like
+//! a credential are redacted from the settings, log events quoting what was
+//! dictated (`logs::TRANSCRIPT_TARGET`) are left out, and the home directory
+//! is replaced by `~` in every file so paths don't carry the user name.
+
+use crate::engine::{DecodeTelemetry, DecodingConfig, DynamicEngine, EngineStatus, TranscriptionLanguage};
+use crate::error::{AppError, Result};
+use crate::logs::{self, LogLevel};
+use crate::storage::{self, Settings, TranscriptionSource};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use tracing::info;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Only the end of the log file goes into the bundle
+const MAX_LOG_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Structured log events in the bundle (the file tail has the rest)
+const RECENT_LOG_ENTRIES: usize = 500;
+
+/// Setting keys whose value is replaced (matched case-insensitively)
+const SECRET_KEY_PARTS: &[&str] = &[
+    "token",
+    "secret",
+    "password",
+    "passphrase",
+    "apikey",
+    "api_key",
+    "accesskey",
+];
+
+/// Diagnostics of a transcription that failed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedJob {
+    pub failed_at: String,
+    pub source_type: TranscriptionSource,
+    pub source_name: Option<String>,
+    pub error: String,
+    pub backend: String,
+    pub language: TranscriptionLanguage,
+    pub decoding_config: Option<DecodingConfig>,
+    pub audio_seconds: f32,
+    pub telemetry: Option<DecodeTelemetry>,
+}
+
+impl FailedJob {
+    pub fn new(
+        source_type: TranscriptionSource,
+        source_name: Option<&str>,
+        error: &AppError,
+        engine: &DynamicEngine,
+        language: TranscriptionLanguage,
+        decoding_config: Option<&DecodingConfig>,
+        samples: usize,
+    ) -> Self {
+        Self {
+            failed_at: chrono::Utc::now().to_rfc3339(),
+            source_type,
+            source_name: source_name.map(String::from),
+            error: error.to_string(),
+            backend: engine.backend().display_name().to_string(),
+            language,
+            decoding_config: decoding_config.cloned(),
+            audio_seconds: samples as f32 / 16000.0,
+            telemetry: engine.last_telemetry(),
+        }
+    }
+}
+
+static LAST_FAILURE: Lazy<Mutex<Option<FailedJob>>> = Lazy::new(|| Mutex::new(None));
+
+/// Remember a failed transcription for the next support bundle
+pub fn record_failure(job: FailedJob) {
+    *LAST_FAILURE.lock() = Some(job);
+}
+
+pub fn last_failure() -> Option<FailedJob> {
+    LAST_FAILURE.lock().clone()
+}
+
+/// App and engine state at bundle creation
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppStatus {
+    pub created_at: String,
+    pub app_version: &'static str,
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub read_only: bool,
+    pub engine_backend: String,
+    pub engine_status: EngineStatus,
+    pub engine_loaded: bool,
+    pub last_telemetry: Option<DecodeTelemetry>,
+}
+
+/// Machine the app runs on
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemInfo {
+    pub os: &'static str,
+    pub os_version: Option<String>,
+    pub arch: &'static str,
+    pub cpu_count: usize,
+    pub total_memory_bytes: Option<u64>,
+}
+
+impl SystemInfo {
+    pub fn collect() -> Self {
+        Self {
+            os: std::env::consts::OS,
+            os_version: os_version(),
+            arch: std::env::consts::ARCH,
+            cpu_count: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            total_memory_bytes: total_memory_bytes(),
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn os_version() -> Option<String> {
+    command_output("sw_vers", &["-productVersion"]).map(|v| format!("macOS {}", v))
+}
+
+#[cfg(target_os = "linux")]
+fn os_version() -> Option<String> {
+    let release = fs::read_to_string("/etc/os-release").ok()?;
+    let name = release.lines().find_map(|l| l.strip_prefix("PRETTY_NAME="))?;
+    Some(name.trim_matches('"').to_string())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn os_version() -> Option<String> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn total_memory_bytes() -> Option<u64> {
+    command_output("sysctl", &["-n", "hw.memsize"])?.parse().ok()
+}
+
+#[cfg(target_os = "linux")]
+fn total_memory_bytes() -> Option<u64> {
+    let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo.lines().find(|l| l.starts_with("MemTotal:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn total_memory_bytes() -> Option<u64> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// One file of the model directory
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelFileInfo {
+    pub name: String,
+    pub size: u64,
+    pub modified: Option<u64>,
+}
+
+/// Model directory listing, with whether the engine managed to load it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelManifest {
+    pub model_dir: String,
+    pub exists: bool,
+    pub loaded: bool,
+    pub files: Vec<ModelFileInfo>,
+}
+
+impl ModelManifest {
+    pub fn scan(model_dir: &Path, loaded: bool) -> Self {
+        let mut files: Vec<ModelFileInfo> = fs::read_dir(model_dir)
+            .map(|dir| {
+                dir.filter_map(|e| e.ok())
+                    .filter_map(|e| {
+                        let meta = e.metadata().ok()?;
+                        if !meta.is_file() {
+                            return None;
+                        }
+                        Some(ModelFileInfo {
+                            name: e.file_name().to_string_lossy().into_owned(),
+                            size: meta.len(),
+                            modified: meta
+                                .modified()
+                                .ok()
+                                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                                .map(|d| d.as_secs()),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            model_dir: model_dir.to_string_lossy().into_owned(),
+            exists: model_dir.exists(),
+            loaded,
+            files,
+        }
+    }
+}
+
+/// Settings as JSON, without user content or credentials
+pub fn redact_settings(settings: &Settings) -> Result<Value> {
+    let mut value = serde_json::to_value(settings)?;
+    redact_secrets(&mut value);
+
+    if let Value::Object(map) = &mut value {
+        // User content (hook arguments may carry tokens): keep the size, not the words
+        for key in ["vocabulary", "substitutionRules", "postTranscriptionHooks"] {
+            if let Some(Value::Array(items)) = map.get(key) {
+                let count = items.len();
+                map.insert(key.to_string(), Value::String(format!("[{} redacted]", count)));
+            }
+        }
+    }
+    Ok(value)
+}
+
+fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                let key = key.to_lowercase();
+                if SECRET_KEY_PARTS.iter().any(|part| key.contains(part)) {
+                    *v = Value::String("[redacted]".into());
+                } else {
+                    redact_secrets(v);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+/// Home directory of the user, `None` when unknown
+fn home_dir() -> Option<String> {
+    let home = std::env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" })?;
+    let home = home.to_string_lossy().trim_end_matches(['/', '\\']).to_string();
+    // "/" or an empty value would turn every path into "~"
+    (home.len() > 1).then_some(home)
+}
+
+/// `text` with `home` replaced by `~`, also where JSON escaped its backslashes
+fn redact_home(text: &str, home: &str) -> String {
+    text.replace(&home.replace('\\', "\\\\"), "~").replace(home, "~")
+}
+
+/// Last `MAX_LOG_BYTES` of the log file, without the lines quoting transcripts
+fn read_log_tail(log_path: &Path) -> Result<String> {
+    let mut file = File::open(log_path)?;
+    let len = file.metadata()?.len();
+    file.seek(SeekFrom::Start(len.saturating_sub(MAX_LOG_BYTES)))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    Ok(String::from_utf8_lossy(&bytes)
+        .lines()
+        .filter(|line| !logs::is_transcript_line(line))
+        .map(|line| format!("{}\n", line))
+        .collect())
+}
+
+/// Write the support bundle zip to `path`
+pub fn create_bundle(
+    path: &Path,
+    status: &AppStatus,
+    settings: Option<&Settings>,
+    manifest: &ModelManifest,
+) -> Result<()> {
+    crate::export::write_atomic(path, |file| {
+        write_bundle(file, status, settings, manifest, &crate::log_path())
+    })?;
+    info!("Support bundle written to {:?}", path);
+    Ok(())
+}
+
+fn write_bundle(
+    file: &mut File,
+    status: &AppStatus,
+    settings: Option<&Settings>,
+    manifest: &ModelManifest,
+    log_path: &Path,
+) -> Result<()> {
+    let zip_err = |e: zip::result::ZipError| AppError::Export(e.to_string());
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let home = home_dir();
+    let mut add = |name: &str, bytes: &[u8]| -> Result<()> {
+        zip.start_file(name, options).map_err(zip_err)?;
+        match &home {
+            Some(home) => zip.write_all(redact_home(&String::from_utf8_lossy(bytes), home).as_bytes())?,
+            None => zip.write_all(bytes)?,
+        }
+        Ok(())
+    };
+
+    add("status.json", &serde_json::to_vec_pretty(status)?)?;
+    add("system.json", &serde_json::to_vec_pretty(&SystemInfo::collect())?)?;
+    add("model_manifest.json", &serde_json::to_vec_pretty(manifest)?)?;
+
+    match settings {
+        Some(settings) => add("settings.json", &serde_json::to_vec_pretty(&redact_settings(settings)?)?)?,
+        None => add("settings.json", b"null")?,
+    }
+
+    match last_failure() {
+        Some(job) => add("last_failure.json", &serde_json::to_vec_pretty(&job)?)?,
+        None => add("last_failure.json", b"null")?,
+    }
+
+    let job_errors = storage::with_db(|conn| storage::list_recent_job_errors(conn, 50)).unwrap_or_default();
+    add("job_errors.json", &serde_json::to_vec_pretty(&job_errors)?)?;
+
+    match read_log_tail(log_path) {
+        Ok(log) => add("wakascribe.log", log.as_bytes())?,
+        Err(e) => add("wakascribe.log", format!("Log file unavailable: {}", e).as_bytes())?,
+    }
+    let mut recent_logs = logs::recent(LogLevel::Trace, RECENT_LOG_ENTRIES);
+    recent_logs.retain(|e| e.target != logs::TRANSCRIPT_TARGET);
+    add("recent_logs.json", &serde_json::to_vec_pretty(&recent_logs)?)?;
+
+    zip.finish().map_err(zip_err)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::PostTranscriptionHook;
+    use crate::sync::SyncRemote;
+    use tracing_subscriber::prelude::*;
+
+    #[test]
+    fn test_redact_settings_hides_secrets_and_user_content() {
+        let mut settings = Settings::default();
+        settings.translation.api_url = "http://localhost:5000".into();
+        settings.translation.api_key = "translate-key".into();
+        settings.llm.api_key = "llm-key".into();
+        settings.sync.passphrase = "sync-passphrase".into();
+        settings.sync.remote = Some(SyncRemote::S3 {
+            endpoint: "https://s3.example.com".into(),
+            region: "eu-west-3".into(),
+            bucket: "notes".into(),
+            access_key_id: "AKIAEXAMPLE".into(),
+            secret_access_key: "s3-secret".into(),
+        });
+        settings.vocabulary = vec!["Kubernetes".into(), "Wakascribe".into()];
+        settings.post_transcription_hooks = vec![PostTranscriptionHook {
+            name: "notify".into(),
+            program: "curl".into(),
+            args: vec!["--token".into(), "hook-token".into()],
+            enabled: true,
+            timeout_secs: 30,
+        }];
+
+        let value = redact_settings(&settings).unwrap();
+        let json = value.to_string();
+        for secret in [
+            "translate-key",
+            "llm-key",
+            "sync-passphrase",
+            "AKIAEXAMPLE",
+            "s3-secret",
+            "Kubernetes",
+            "hook-token",
+        ] {
+            assert!(!json.contains(secret), "{} leaked", secret);
+        }
+        assert_eq!(value["translation"]["apiKey"], "[redacted]");
+        assert_eq!(value["sync"]["remote"]["secretAccessKey"], "[redacted]");
+        assert_eq!(value["vocabulary"], "[2 redacted]");
+        assert_eq!(value["postTranscriptionHooks"], "[1 redacted]");
+        // Non-secret settings stay readable
+        assert_eq!(value["translation"]["apiUrl"], "http://localhost:5000");
+        assert_eq!(value["sync"]["remote"]["bucket"], "notes");
+    }
+
+    #[test]
+    fn test_redact_home_from_paths() {
+        let mut settings = Settings::default();
+        settings.database_path = Some("/home/alice/Documents/wakascribe.db".into());
+        let json = serde_json::to_string(&redact_settings(&settings).unwrap()).unwrap();
+        let redacted = redact_home(&json, "/home/alice");
+        assert!(redacted.contains("\"~/Documents/wakascribe.db\""));
+        assert!(!redacted.contains("alice"));
+
+        // Windows paths are escaped in JSON, and not in the log file
+        let home = r"C:\Users\alice";
+        let json = serde_json::to_string(r"C:\Users\alice\AppData\models").unwrap();
+        assert_eq!(redact_home(&json, home), r#""~\\AppData\\models""#);
+        let log = r"Loading model from C:\Users\alice\AppData\models";
+        assert_eq!(redact_home(log, home), r"Loading model from ~\AppData\models");
+    }
+
+    #[test]
+    fn test_bundle_leaves_transcripts_out() {
+        let dir = std::env::temp_dir().join(format!("wakascribe-support-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let log_path = dir.join("wakascribe.log");
+        let log_file = File::create(&log_path).unwrap();
+        let subscriber = tracing_subscriber::registry()
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(std::sync::Mutex::new(log_file)),
+            )
+            .with(logs::RecentLogs);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("Transcribing 3 chunks");
+            tracing::debug!(target: logs::TRANSCRIPT_TARGET, "Final transcription: {:?}", "Rendez-vous chez le notaire\nà 9h");
+        });
+
+        let status = AppStatus {
+            created_at: chrono::Utc::now().to_rfc3339(),
+            app_version: env!("CARGO_PKG_VERSION"),
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            read_only: false,
+            engine_backend: "OpenVINO".into(),
+            engine_status: EngineStatus::Ready,
+            engine_loaded: true,
+            last_telemetry: None,
+        };
+        let manifest = ModelManifest::scan(&dir.join("models"), false);
+        let bundle = dir.join("bundle.zip");
+        write_bundle(&mut File::create(&bundle).unwrap(), &status, None, &manifest, &log_path).unwrap();
+
+        let mut zip = zip::ZipArchive::new(File::open(&bundle).unwrap()).unwrap();
+        for name in ["wakascribe.log", "recent_logs.json"] {
+            let mut content = String::new();
+            zip.by_name(name).unwrap().read_to_string(&mut content).unwrap();
+            assert!(content.contains("Transcribing 3 chunks"), "{}", name);
+            assert!(!content.contains("notaire"), "{} quotes the transcript", name);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,100 @@
+//! Encryption of synced objects (ChaCha20-Poly1305, key from PBKDF2-SHA256)
+//!
+//! Each object is bound to its remote key through the associated data, so a
+//! server swapping two objects (or serving an old manifest under another
+//! profile) makes decryption fail instead of importing the wrong content.
+
+use crate::error::{AppError, Result};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use sha2::Sha256;
+
+/// PBKDF2 rounds: slow enough against brute force, done once per sync
+const KDF_ROUNDS: u32 = 600_000;
+
+const NONCE_LEN: usize = 12;
+
+pub struct Cipher(ChaCha20Poly1305);
+
+impl Cipher {
+    pub fn new(passphrase: &str, salt: &[u8]) -> Self {
+        let mut key = [0u8; 32];
+        pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, KDF_ROUNDS, &mut key);
+        Self::from_key(&key)
+    }
+
+    fn from_key(key: &[u8; 32]) -> Self {
+        Self(ChaCha20Poly1305::new(Key::from_slice(key)))
+    }
+
+    /// Random nonce followed by the ciphertext of the object stored at `key`
+    pub fn encrypt(&self, key: &str, plain: &[u8]) -> Result<Vec<u8>> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let payload = Payload {
+            msg: plain,
+            aad: key.as_bytes(),
+        };
+        let cipher = self
+            .0
+            .encrypt(&nonce, payload)
+            .map_err(|_| AppError::InvalidState("Encryption failed".to_string()))?;
+
+        let mut out = nonce.to_vec();
+        out.extend_from_slice(&cipher);
+        Ok(out)
+    }
+
+    /// Fails unless `data` was encrypted for the same `key`
+    pub fn decrypt(&self, key: &str, data: &[u8]) -> Result<Vec<u8>> {
+        if data.len() < NONCE_LEN {
+            return Err(AppError::InvalidInput("Synced object is truncated".to_string()));
+        }
+        let (nonce, cipher) = data.split_at(NONCE_LEN);
+        let payload = Payload {
+            msg: cipher,
+            aad: key.as_bytes(),
+        };
+        self.0.decrypt(Nonce::from_slice(nonce), payload).map_err(|_| {
+            AppError::InvalidInput(format!(
+                "Cannot decrypt synced object {}: wrong passphrase, corrupted or misplaced object",
+                key
+            ))
+        })
+    }
+}
+
+pub fn random_salt() -> [u8; 16] {
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    salt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_bound_to_key() {
+        let cipher = Cipher::from_key(&[7u8; 32]);
+        let sealed = cipher.encrypt("wakascribe/default/a", b"bonjour").unwrap();
+        assert_eq!(cipher.decrypt("wakascribe/default/a", &sealed).unwrap(), b"bonjour");
+
+        // Served under another id: rejected
+        assert!(cipher.decrypt("wakascribe/default/b", &sealed).is_err());
+        assert!(Cipher::from_key(&[8u8; 32]).decrypt("wakascribe/default/a", &sealed).is_err());
+
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(cipher.decrypt("wakascribe/default/a", &tampered).is_err());
+        assert!(cipher.decrypt("wakascribe/default/a", &sealed[..NONCE_LEN - 1]).is_err());
+    }
+
+    #[test]
+    fn test_fresh_nonce_per_object() {
+        let cipher = Cipher::from_key(&[7u8; 32]);
+        let a = cipher.encrypt("k", b"meme texte").unwrap();
+        let b = cipher.encrypt("k", b"meme texte").unwrap();
+        assert_ne!(a, b);
+    }
+}
@@ -0,0 +1,484 @@
+//! Opt-in sync of the history with a user-provided WebDAV or S3 endpoint
+//!
+//! Each transcription is stored remotely as one object encrypted with a key
+//! derived from the user's passphrase, so the server never sees the text.
+//! An encrypted manifest maps every transcription id to its `updated_at`. A
+//! sync compares the manifest with the local history and copies each
+//! transcription from the side where it is most recent. Moving to the trash
+//! and restoring count as changes and are synced. Deletions for good are
+//! kept in the manifest as tombstones (id → deletion time), resolved against
+//! `updated_at` like edits: a transcription edited after it was deleted
+//! elsewhere comes back, otherwise it is deleted everywhere.
+
+mod crypto;
+mod s3;
+mod webdav;
+
+use crate::error::{AppError, Result};
+use crate::storage::{self, with_db, Transcription};
+use crypto::Cipher;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::io::Read;
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+/// Root of the app's objects on the remote
+const REMOTE_ROOT: &str = "wakascribe";
+
+/// Largest object accepted from the remote
+const MAX_OBJECT_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Sync configuration (part of the settings)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncSettings {
+    pub enabled: bool,
+    pub remote: Option<SyncRemote>,
+    /// Encryption passphrase, identical on every synced machine
+    pub passphrase: String,
+}
+
+/// Endpoint the history is mirrored to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SyncRemote {
+    #[serde(rename_all = "camelCase")]
+    Webdav {
+        /// Collection URL, e.g. https://cloud.example.com/remote.php/dav/files/me
+        url: String,
+        username: String,
+        password: String,
+    },
+    /// S3-compatible bucket (path-style requests)
+    #[serde(rename_all = "camelCase")]
+    S3 {
+        /// e.g. https://s3.eu-west-3.amazonaws.com, or https://example.com/s3 behind a reverse proxy
+        endpoint: String,
+        region: String,
+        bucket: String,
+        access_key_id: String,
+        secret_access_key: String,
+    },
+}
+
+/// Object storage the history is synced with
+pub trait RemoteStore {
+    /// Content of `key`, `None` when it doesn't exist
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<()>;
+
+    /// Remove `key` (no error when it doesn't exist)
+    fn delete(&self, key: &str) -> Result<()>;
+}
+
+/// Content of the remote manifest
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    /// `updated_at` of each transcription stored remotely
+    transcriptions: BTreeMap<String, String>,
+    /// Deletion time of each transcription deleted for good
+    #[serde(default)]
+    deleted: BTreeMap<String, String>,
+}
+
+/// Manifests written before deletions were synced are a bare id → `updated_at` map
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredManifest {
+    Current(Manifest),
+    Legacy(BTreeMap<String, String>),
+}
+
+impl From<StoredManifest> for Manifest {
+    fn from(stored: StoredManifest) -> Self {
+        match stored {
+            StoredManifest::Current(manifest) => manifest,
+            StoredManifest::Legacy(transcriptions) => Manifest {
+                transcriptions,
+                deleted: BTreeMap::new(),
+            },
+        }
+    }
+}
+
+fn open_remote(remote: &SyncRemote) -> Box<dyn RemoteStore> {
+    match remote {
+        SyncRemote::Webdav { url, username, password } => {
+            Box::new(webdav::WebDav::new(url, username, password))
+        }
+        SyncRemote::S3 {
+            endpoint,
+            region,
+            bucket,
+            access_key_id,
+            secret_access_key,
+        } => Box::new(s3::S3::new(endpoint, region, bucket, access_key_id, secret_access_key)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncState {
+    #[default]
+    Idle,
+    Running,
+    Failed,
+}
+
+/// Progress and outcome of the last sync, sent with the `sync-status` event
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStatus {
+    pub state: SyncState,
+    pub last_sync_at: Option<String>,
+    /// Transcriptions sent (or deleted remotely) during the last sync
+    pub uploaded: usize,
+    /// Transcriptions received (or deleted locally) during the last sync
+    pub downloaded: usize,
+    pub error: Option<String>,
+}
+
+static STATUS: Lazy<Mutex<SyncStatus>> = Lazy::new(|| Mutex::new(SyncStatus::default()));
+
+/// Held while a sync runs
+static RUNNING: Mutex<()> = parking_lot::const_mutex(());
+
+/// Status of the current or last sync
+pub fn status() -> SyncStatus {
+    STATUS.lock().clone()
+}
+
+fn set_status(app: &AppHandle, status: SyncStatus) {
+    *STATUS.lock() = status.clone();
+    let _ = app.emit("sync-status", &status);
+}
+
+/// Sync the active profile's history with the configured remote
+pub fn sync_now(app: &AppHandle) -> Result<SyncStatus> {
+    let _running = RUNNING
+        .try_lock()
+        .ok_or_else(|| AppError::InvalidState("A sync is already running".to_string()))?;
+    storage::ensure_writable()?;
+
+    let settings = storage::store()?.get_settings()?.sync;
+    if !settings.enabled {
+        return Err(AppError::InvalidState("Sync is disabled".to_string()));
+    }
+    let remote = settings
+        .remote
+        .ok_or_else(|| AppError::InvalidInput("No sync endpoint configured".to_string()))?;
+    if settings.passphrase.is_empty() {
+        return Err(AppError::InvalidInput("Sync passphrase is empty".to_string()));
+    }
+
+    let last_sync_at = status().last_sync_at;
+    set_status(
+        app,
+        SyncStatus {
+            state: SyncState::Running,
+            last_sync_at: last_sync_at.clone(),
+            ..Default::default()
+        },
+    );
+
+    match run(open_remote(&remote).as_ref(), &settings.passphrase) {
+        Ok((uploaded, downloaded)) => {
+            info!("Sync done: {} uploaded, {} downloaded", uploaded, downloaded);
+            let status = SyncStatus {
+                state: SyncState::Idle,
+                last_sync_at: Some(chrono::Utc::now().to_rfc3339()),
+                uploaded,
+                downloaded,
+                error: None,
+            };
+            set_status(app, status.clone());
+            Ok(status)
+        }
+        Err(e) => {
+            warn!("Sync failed: {}", e);
+            set_status(
+                app,
+                SyncStatus {
+                    state: SyncState::Failed,
+                    last_sync_at,
+                    error: Some(e.to_string()),
+                    ..Default::default()
+                },
+            );
+            Err(e)
+        }
+    }
+}
+
+/// Returns the number of changes uploaded and downloaded
+fn run(store: &dyn RemoteStore, passphrase: &str) -> Result<(usize, usize)> {
+    // The salt is shared by all machines, so they derive the same key
+    let salt_key = format!("{}/salt", REMOTE_ROOT);
+    let salt = match store.get(&salt_key)? {
+        Some(salt) => salt,
+        None => {
+            let salt = crypto::random_salt().to_vec();
+            store.put(&salt_key, &salt)?;
+            salt
+        }
+    };
+    let cipher = Cipher::new(passphrase, &salt);
+
+    let prefix = format!("{}/{}", REMOTE_ROOT, with_db(|conn| storage::active_profile_id(conn))?);
+    let manifest_key = format!("{}/manifest", prefix);
+    let mut manifest: Manifest = match store.get(&manifest_key)? {
+        Some(bytes) => serde_json::from_slice::<StoredManifest>(&cipher.decrypt(&manifest_key, &bytes)?)?.into(),
+        None => Manifest::default(),
+    };
+
+    // Trash included: a trashed transcription is still known locally
+    let local = storage::store()?.list_updated_at()?;
+    let deleted = storage::store()?.list_deleted()?;
+    let (mut uploaded, mut downloaded) = (0, 0);
+
+    for step in plan(&local, &deleted, &manifest) {
+        match step {
+            Step::Upload(id, updated_at) => {
+                let key = format!("{}/{}", prefix, id);
+                let transcription = storage::store()?
+                    .get_transcription(&id)?
+                    .ok_or_else(|| AppError::NotFound(format!("Transcription not found: {}", id)))?;
+                store.put(&key, &cipher.encrypt(&key, &serde_json::to_vec(&transcription)?)?)?;
+                manifest.deleted.remove(&id);
+                manifest.transcriptions.insert(id, updated_at);
+                uploaded += 1;
+            }
+            Step::Download(id) => {
+                download(store, &cipher, &format!("{}/{}", prefix, id))?;
+                downloaded += 1;
+            }
+            Step::DeleteRemote(id, deleted_at) => {
+                store.delete(&format!("{}/{}", prefix, id))?;
+                manifest.transcriptions.remove(&id);
+                manifest.deleted.insert(id, deleted_at);
+                uploaded += 1;
+            }
+            Step::DeleteLocal(id) => {
+                storage::store()?.delete_transcription(&id)?;
+                downloaded += 1;
+            }
+        }
+    }
+    if downloaded > 0 {
+        // Recordings of the transcriptions deleted by the other machines
+        with_db(crate::recordings::remove_orphans)?;
+    }
+
+    if uploaded > 0 {
+        store.put(&manifest_key, &cipher.encrypt(&manifest_key, &serde_json::to_vec(&manifest)?)?)?;
+    }
+
+    Ok((uploaded, downloaded))
+}
+
+fn download(store: &dyn RemoteStore, cipher: &Cipher, key: &str) -> Result<()> {
+    let bytes = store
+        .get(key)?
+        .ok_or_else(|| AppError::NotFound(format!("Remote object missing: {}", key)))?;
+    let transcription: Transcription = serde_json::from_slice(&cipher.decrypt(key, &bytes)?)?;
+    storage::store()?.upsert_transcription(&transcription)
+}
+
+/// What a sync does with one transcription
+#[derive(Debug, PartialEq)]
+enum Step {
+    /// Local copy newer or missing remotely: sent, with its `updated_at`
+    Upload(String, String),
+    /// Remote copy newer or missing locally
+    Download(String),
+    /// Deleted here after its last remote change: removed remotely, the
+    /// deletion time kept in the manifest
+    DeleteRemote(String, String),
+    /// Deleted on another machine after its last local change
+    DeleteLocal(String),
+}
+
+/// Steps bringing both sides to the most recent copy of each transcription,
+/// from the local (id, `updated_at`) pairs, the local (id, deletion time)
+/// pairs and the remote manifest; a deletion wins over a change made at the
+/// same instant
+fn plan(local: &[(String, String)], deleted: &[(String, String)], manifest: &Manifest) -> Vec<Step> {
+    let mut steps = Vec::new();
+    for (id, updated_at) in local {
+        if let Some(deleted_at) = manifest.deleted.get(id) {
+            match compare_times(updated_at, deleted_at) {
+                Ordering::Greater => steps.push(Step::Upload(id.clone(), updated_at.clone())),
+                _ => steps.push(Step::DeleteLocal(id.clone())),
+            }
+            continue;
+        }
+        match manifest.transcriptions.get(id).map(|remote| compare_times(updated_at, remote)) {
+            Some(Ordering::Equal) => {}
+            Some(Ordering::Less) => steps.push(Step::Download(id.clone())),
+            None | Some(Ordering::Greater) => steps.push(Step::Upload(id.clone(), updated_at.clone())),
+        }
+    }
+    for (id, deleted_at) in deleted {
+        // Never synced, or the deletion is already known remotely: nothing to do
+        if let Some(remote) = manifest.transcriptions.get(id) {
+            match compare_times(remote, deleted_at) {
+                Ordering::Greater => steps.push(Step::Download(id.clone())),
+                _ => steps.push(Step::DeleteRemote(id.clone(), deleted_at.clone())),
+            }
+        }
+    }
+    // Made on another machine
+    let known = |id: &String| local.iter().chain(deleted).any(|(known_id, _)| known_id == id);
+    for id in manifest.transcriptions.keys().filter(|id| !known(id)) {
+        steps.push(Step::Download(id.clone()));
+    }
+    steps
+}
+
+/// Order of two RFC 3339 timestamps (text order if one doesn't parse)
+fn compare_times(a: &str, b: &str) -> Ordering {
+    match (
+        chrono::DateTime::parse_from_rfc3339(a),
+        chrono::DateTime::parse_from_rfc3339(b),
+    ) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
+/// Body of a successful response, bounded by `MAX_OBJECT_BYTES`
+fn read_body(response: ureq::Response, what: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_OBJECT_BYTES + 1)
+        .read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > MAX_OBJECT_BYTES {
+        return Err(AppError::Network(format!("{}: object too large", what)));
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(entries: &[(&str, &str)]) -> Vec<(String, String)> {
+        entries.iter().map(|(id, t)| (id.to_string(), t.to_string())).collect()
+    }
+
+    fn remote(transcriptions: &[(&str, &str)], deleted: &[(&str, &str)]) -> Manifest {
+        Manifest {
+            transcriptions: pairs(transcriptions).into_iter().collect(),
+            deleted: pairs(deleted).into_iter().collect(),
+        }
+    }
+
+    #[test]
+    fn test_plan_keeps_the_most_recent_side() {
+        let local = pairs(&[
+            ("same", "2026-03-01T10:00:00+00:00"),
+            ("edited_here", "2026-03-02T10:00:00+00:00"),
+            ("edited_there", "2026-03-01T10:00:00+00:00"),
+            ("new_here", "2026-03-01T10:00:00+00:00"),
+        ]);
+        let manifest = remote(
+            &[
+                ("same", "2026-03-01T10:00:00+00:00"),
+                ("edited_here", "2026-03-01T10:00:00+00:00"),
+                ("edited_there", "2026-03-03T10:00:00+00:00"),
+                ("new_there", "2026-03-01T10:00:00+00:00"),
+            ],
+            &[],
+        );
+
+        assert_eq!(
+            plan(&local, &[], &manifest),
+            [
+                Step::Upload("edited_here".to_string(), "2026-03-02T10:00:00+00:00".to_string()),
+                Step::Download("edited_there".to_string()),
+                Step::Upload("new_here".to_string(), "2026-03-01T10:00:00+00:00".to_string()),
+                Step::Download("new_there".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_compares_instants_across_offsets() {
+        // Same instant written by machines in different time zones: in sync
+        let local = pairs(&[("a", "2026-03-01T12:00:00+02:00")]);
+        let manifest = remote(&[("a", "2026-03-01T10:00:00+00:00")], &[]);
+        assert!(plan(&local, &[], &manifest).is_empty());
+
+        // 11:30+02:00 is 09:30 UTC: older than the local copy though it sorts after as text
+        let local = pairs(&[("a", "2026-03-01T10:30:00+00:00")]);
+        let manifest = remote(&[("a", "2026-03-01T11:30:00+02:00")], &[]);
+        assert_eq!(
+            plan(&local, &[], &manifest),
+            [Step::Upload("a".to_string(), "2026-03-01T10:30:00+00:00".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_plan_on_empty_remote_uploads_everything() {
+        let local = pairs(&[("a", "2026-03-01T10:00:00+00:00"), ("b", "2026-03-02T10:00:00+00:00")]);
+        let steps = plan(&local, &[], &Manifest::default());
+        assert!(steps.iter().all(|s| matches!(s, Step::Upload(..))));
+        assert_eq!(steps.len(), 2);
+    }
+
+    #[test]
+    fn test_plan_propagates_deletions() {
+        let local = pairs(&[
+            ("deleted_there", "2026-03-01T10:00:00+00:00"),
+            ("edited_after_deletion_there", "2026-03-05T10:00:00+00:00"),
+        ]);
+        let deleted = pairs(&[
+            ("deleted_here", "2026-03-04T10:00:00+00:00"),
+            ("edited_there_after_deletion", "2026-03-02T10:00:00+00:00"),
+            ("already_deleted", "2026-03-02T10:00:00+00:00"),
+            ("never_synced", "2026-03-02T10:00:00+00:00"),
+        ]);
+        let manifest = remote(
+            &[
+                ("deleted_here", "2026-03-01T10:00:00+00:00"),
+                ("edited_there_after_deletion", "2026-03-03T10:00:00+00:00"),
+            ],
+            &[
+                ("deleted_there", "2026-03-02T10:00:00+00:00"),
+                ("edited_after_deletion_there", "2026-03-02T10:00:00+00:00"),
+                ("already_deleted", "2026-03-02T10:00:00+00:00"),
+            ],
+        );
+
+        assert_eq!(
+            plan(&local, &deleted, &manifest),
+            [
+                Step::DeleteLocal("deleted_there".to_string()),
+                Step::Upload(
+                    "edited_after_deletion_there".to_string(),
+                    "2026-03-05T10:00:00+00:00".to_string()
+                ),
+                Step::DeleteRemote("deleted_here".to_string(), "2026-03-04T10:00:00+00:00".to_string()),
+                Step::Download("edited_there_after_deletion".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_legacy_manifest_is_read() {
+        let legacy = br#"{"a": "2026-03-01T10:00:00+00:00"}"#;
+        let manifest: Manifest = serde_json::from_slice::<StoredManifest>(legacy).unwrap().into();
+        assert_eq!(manifest.transcriptions.len(), 1);
+        assert!(manifest.deleted.is_empty());
+
+        let current = serde_json::to_vec(&manifest).unwrap();
+        let manifest: Manifest = serde_json::from_slice::<StoredManifest>(&current).unwrap().into();
+        assert_eq!(manifest.transcriptions.get("a").map(String::as_str), Some("2026-03-01T10:00:00+00:00"));
+    }
+}
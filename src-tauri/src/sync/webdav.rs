@@ -0,0 +1,88 @@
+//! WebDAV remote (Nextcloud, ownCloud, any RFC 4918 server)
+
+use super::{read_body, RemoteStore};
+use crate::error::{AppError, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+pub struct WebDav {
+    base_url: String,
+    authorization: String,
+    /// Collections already created during this sync
+    collections: RefCell<HashSet<String>>,
+}
+
+impl WebDav {
+    pub fn new(url: &str, username: &str, password: &str) -> Self {
+        Self {
+            base_url: url.trim_end_matches('/').to_string(),
+            authorization: format!("Basic {}", STANDARD.encode(format!("{}:{}", username, password))),
+            collections: RefCell::new(HashSet::new()),
+        }
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!("{}/{}", self.base_url, key)
+    }
+
+    /// Create the parent collections of `key` (PUT doesn't create them)
+    fn ensure_collections(&self, key: &str) -> Result<()> {
+        let parts: Vec<&str> = key.split('/').collect();
+        for depth in 1..parts.len() {
+            let collection = parts[..depth].join("/");
+            if self.collections.borrow().contains(&collection) {
+                continue;
+            }
+
+            let url = self.url(&collection);
+            match ureq::request("MKCOL", &url)
+                .set("Authorization", &self.authorization)
+                .call()
+            {
+                // 405: the collection already exists
+                Ok(_) | Err(ureq::Error::Status(405, _)) => {}
+                Err(e) => return Err(AppError::Network(format!("{}: {}", url, e))),
+            }
+            self.collections.borrow_mut().insert(collection);
+        }
+        Ok(())
+    }
+}
+
+impl RemoteStore for WebDav {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let url = self.url(key);
+        match ureq::get(&url)
+            .set("Authorization", &self.authorization)
+            .call()
+        {
+            Ok(response) => Ok(Some(read_body(response, &url)?)),
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(e) => Err(AppError::Network(format!("{}: {}", url, e))),
+        }
+    }
+
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<()> {
+        self.ensure_collections(key)?;
+        let url = self.url(key);
+        ureq::put(&url)
+            .set("Authorization", &self.authorization)
+            .set("Content-Type", "application/octet-stream")
+            .send_bytes(bytes)
+            .map_err(|e| AppError::Network(format!("{}: {}", url, e)))?;
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        let url = self.url(key);
+        match ureq::delete(&url)
+            .set("Authorization", &self.authorization)
+            .call()
+        {
+            Ok(_) | Err(ureq::Error::Status(404, _)) => Ok(()),
+            Err(e) => Err(AppError::Network(format!("{}: {}", url, e))),
+        }
+    }
+}
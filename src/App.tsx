@@ -42,9 +42,15 @@ function App() {
       </div>
 
       {/* Content */}
-      <div className="flex-1 overflow-hidden">
-        {mode === "dictation" ? <Recorder /> : <FileTranscribe />}
+      {/* The recorder stays mounted: push-to-talk works from either tab */}
+      <div className={`flex-1 overflow-hidden ${mode === "dictation" ? "" : "hidden"}`}>
+        <Recorder />
       </div>
+      {mode === "file" && (
+        <div className="flex-1 overflow-hidden">
+          <FileTranscribe />
+        </div>
+      )}
     </Layout>
   );
 }
@@ -0,0 +1,27 @@
+import { RefObject } from "react";
+
+interface AudioPlayerProps {
+  src: string;
+  audioRef: RefObject<HTMLAudioElement>;
+  onTimeUpdate: (ms: number) => void;
+  onDelete: () => void;
+}
+
+// Source audio of a recording, played back in sync with the segments
+export function AudioPlayer({ src, audioRef, onTimeUpdate, onDelete }: AudioPlayerProps) {
+  return (
+    <div className="px-4 py-2 border-b border-[var(--color-border)] flex items-center gap-3">
+      <audio
+        ref={audioRef}
+        src={src}
+        controls
+        preload="metadata"
+        onTimeUpdate={(e) => onTimeUpdate(e.currentTarget.currentTime * 1000)}
+        className="flex-1 h-8"
+      />
+      <button onClick={onDelete} className="text-xs text-red-500 hover:underline shrink-0">
+        Supprimer l'audio
+      </button>
+    </div>
+  );
+}
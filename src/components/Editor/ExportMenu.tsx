@@ -1,17 +1,30 @@
 import { useState, useRef, useEffect } from "react";
 import { save } from "@tauri-apps/plugin-dialog";
+import type { TranscriptFormat } from "../../lib/types";
 
 interface ExportMenuProps {
   onExportTxt: (path: string) => void;
   onExportDocx: (path: string) => void;
+  onExportSrt: (path: string) => void;
+  onExportVtt: (path: string) => void;
+  onExportMarkdown: (path: string) => void;
+  onExportFullJson: (path: string) => void;
+  onExportJson: (path: string, format: TranscriptFormat) => void;
   onCopy: () => void;
+  fileName?: string; // without extension
   disabled?: boolean;
 }
 
 export function ExportMenu({
   onExportTxt,
   onExportDocx,
+  onExportSrt,
+  onExportVtt,
+  onExportMarkdown,
+  onExportFullJson,
+  onExportJson,
   onCopy,
+  fileName = "transcription",
   disabled,
 }: ExportMenuProps) {
   const [isOpen, setIsOpen] = useState(false);
@@ -32,7 +45,7 @@ export function ExportMenu({
     setIsOpen(false);
     const path = await save({
       filters: [{ name: "Text", extensions: ["txt"] }],
-      defaultPath: "transcription.txt",
+      defaultPath: `${fileName}.txt`,
     });
     if (path) {
       onExportTxt(path);
@@ -43,13 +56,68 @@ export function ExportMenu({
     setIsOpen(false);
     const path = await save({
       filters: [{ name: "Word Document", extensions: ["docx"] }],
-      defaultPath: "transcription.docx",
+      defaultPath: `${fileName}.docx`,
     });
     if (path) {
       onExportDocx(path);
     }
   };
 
+  const handleExportSrt = async () => {
+    setIsOpen(false);
+    const path = await save({
+      filters: [{ name: "SubRip", extensions: ["srt"] }],
+      defaultPath: `${fileName}.srt`,
+    });
+    if (path) {
+      onExportSrt(path);
+    }
+  };
+
+  const handleExportVtt = async () => {
+    setIsOpen(false);
+    const path = await save({
+      filters: [{ name: "WebVTT", extensions: ["vtt"] }],
+      defaultPath: `${fileName}.vtt`,
+    });
+    if (path) {
+      onExportVtt(path);
+    }
+  };
+
+  const handleExportMarkdown = async () => {
+    setIsOpen(false);
+    const path = await save({
+      filters: [{ name: "Markdown", extensions: ["md"] }],
+      defaultPath: `${fileName}.md`,
+    });
+    if (path) {
+      onExportMarkdown(path);
+    }
+  };
+
+  const handleExportFullJson = async () => {
+    setIsOpen(false);
+    const path = await save({
+      filters: [{ name: "JSON", extensions: ["json"] }],
+      defaultPath: `${fileName}.json`,
+    });
+    if (path) {
+      onExportFullJson(path);
+    }
+  };
+
+  const handleExportJson = async (format: TranscriptFormat) => {
+    setIsOpen(false);
+    const path = await save({
+      filters: [{ name: "JSON", extensions: ["json"] }],
+      defaultPath: `${fileName}.json`,
+    });
+    if (path) {
+      onExportJson(path, format);
+    }
+  };
+
   const handleCopy = () => {
     setIsOpen(false);
     onCopy();
@@ -125,6 +193,75 @@ export function ExportMenu({
             </svg>
             Exporter en .docx
           </button>
+          <button
+            onClick={handleExportSrt}
+            className="w-full px-4 py-2 text-left text-sm hover:bg-[var(--color-bg-secondary)] flex items-center gap-2"
+          >
+            <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+              <path
+                strokeLinecap="round"
+                strokeLinejoin="round"
+                strokeWidth={2}
+                d="M7 4v16M17 4v16M3 8h4m10 0h4M3 12h18M3 16h4m10 0h4M4 20h16a1 1 0 001-1V5a1 1 0 00-1-1H4a1 1 0 00-1 1v14a1 1 0 001 1z"
+              />
+            </svg>
+            Sous-titres .srt
+          </button>
+          <button
+            onClick={handleExportVtt}
+            className="w-full px-4 py-2 text-left text-sm hover:bg-[var(--color-bg-secondary)] flex items-center gap-2"
+          >
+            <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+              <path
+                strokeLinecap="round"
+                strokeLinejoin="round"
+                strokeWidth={2}
+                d="M7 4v16M17 4v16M3 8h4m10 0h4M3 12h18M3 16h4m10 0h4M4 20h16a1 1 0 001-1V5a1 1 0 00-1-1H4a1 1 0 00-1 1v14a1 1 0 001 1z"
+              />
+            </svg>
+            Sous-titres .vtt
+          </button>
+          <button
+            onClick={handleExportMarkdown}
+            className="w-full px-4 py-2 text-left text-sm hover:bg-[var(--color-bg-secondary)] flex items-center gap-2"
+          >
+            <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+              <path
+                strokeLinecap="round"
+                strokeLinejoin="round"
+                strokeWidth={2}
+                d="M9 12h6m-6 4h6m2 5H7a2 2 0 01-2-2V5a2 2 0 012-2h5.586a1 1 0 01.707.293l5.414 5.414a1 1 0 01.293.707V19a2 2 0 01-2 2z"
+              />
+            </svg>
+            Exporter en .md
+          </button>
+          <button
+            onClick={handleExportFullJson}
+            className="w-full px-4 py-2 text-left text-sm hover:bg-[var(--color-bg-secondary)] flex items-center gap-2"
+          >
+            <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+              <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M10 20l4-16m4 4l4 4-4 4M6 16l-4-4 4-4" />
+            </svg>
+            JSON complet
+          </button>
+          <button
+            onClick={() => handleExportJson("whisper_verbose")}
+            className="w-full px-4 py-2 text-left text-sm hover:bg-[var(--color-bg-secondary)] flex items-center gap-2"
+          >
+            <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+              <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M10 20l4-16m4 4l4 4-4 4M6 16l-4-4 4-4" />
+            </svg>
+            JSON Whisper
+          </button>
+          <button
+            onClick={() => handleExportJson("word_level")}
+            className="w-full px-4 py-2 text-left text-sm hover:bg-[var(--color-bg-secondary)] flex items-center gap-2"
+          >
+            <svg className="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+              <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M10 20l4-16m4 4l4 4-4 4M6 16l-4-4 4-4" />
+            </svg>
+            JSON mots horodates
+          </button>
         </div>
       )}
     </div>
@@ -0,0 +1,43 @@
+import { useEffect, useState } from "react";
+import { getRevisionHistory } from "../../lib/tauri";
+import type { TranscriptionRevision } from "../../lib/types";
+
+interface RevisionHistoryProps {
+  transcriptionId: string;
+  updatedAt: string; // reloads after each save
+  onRevert: (revision: TranscriptionRevision) => void;
+}
+
+export function RevisionHistory({ transcriptionId, updatedAt, onRevert }: RevisionHistoryProps) {
+  const [revisions, setRevisions] = useState<TranscriptionRevision[]>([]);
+
+  useEffect(() => {
+    getRevisionHistory(transcriptionId)
+      .then(setRevisions)
+      .catch((error) => console.error("Failed to load revision history:", error));
+  }, [transcriptionId, updatedAt]);
+
+  return (
+    <div className="space-y-2">
+      {revisions.map((r, i) => (
+        <div key={r.revision} className="p-3 rounded-lg bg-[var(--color-bg-tertiary)]">
+          <div className="flex items-center justify-between gap-2 mb-1">
+            <span className="text-xs text-[var(--color-text-muted)]">
+              {r.revision === 0 ? "Transcription d'origine" : `Version ${r.revision}`} -{" "}
+              {new Date(r.createdAt).toLocaleString("fr-FR")}
+            </span>
+            {i > 0 && (
+              <button
+                onClick={() => onRevert(r)}
+                className="text-xs text-[var(--color-accent)] hover:underline"
+              >
+                Restaurer
+              </button>
+            )}
+          </div>
+          <p className="text-sm text-[var(--color-text-secondary)] line-clamp-3 whitespace-pre-wrap">{r.text}</p>
+        </div>
+      ))}
+    </div>
+  );
+}
@@ -1,11 +1,16 @@
-import type { Segment } from "../../lib/types";
+import type { RecordingPause, Segment } from "../../lib/types";
 import { ConfidenceIndicator } from "../Recorder/ConfidenceIndicator";
+import { SegmentText } from "../Recorder/SegmentText";
 
 interface SegmentListProps {
   segments: Segment[];
   onSegmentClick?: (segment: Segment) => void;
+  activeSegmentId?: string; // segment under the playback position
+  pauses?: RecordingPause[]; // durations of the "[pause]" segments
 }
 
+const PAUSE_MARKER = "[pause]";
+
 function formatTimestamp(ms: number): string {
   const totalSeconds = Math.floor(ms / 1000);
   const minutes = Math.floor(totalSeconds / 60);
@@ -13,28 +18,50 @@ function formatTimestamp(ms: number): string {
   return `${minutes.toString().padStart(2, "0")}:${seconds.toString().padStart(2, "0")}`;
 }
 
-export function SegmentList({ segments, onSegmentClick }: SegmentListProps) {
+function pauseLabel(segment: Segment, pauses?: RecordingPause[]): string {
+  const pause = pauses?.find((p) => p.atMs === segment.startMs);
+  if (!pause) return "Pause";
+  const minutes = Math.floor(pause.durationMs / 60000);
+  const seconds = Math.floor((pause.durationMs % 60000) / 1000);
+  return minutes > 0 ? `Pause de ${minutes} min ${seconds} s` : `Pause de ${seconds} s`;
+}
+
+export function SegmentList({ segments, onSegmentClick, activeSegmentId, pauses }: SegmentListProps) {
   return (
     <div className="space-y-3">
-      {segments.map((segment) => (
-        <div
-          key={segment.id}
-          onClick={() => onSegmentClick?.(segment)}
-          className={`p-3 rounded-lg bg-[var(--color-bg-tertiary)] ${
-            onSegmentClick ? "cursor-pointer hover:bg-[var(--color-border)]" : ""
-          }`}
-        >
-          <div className="flex items-start gap-3">
-            <span className="text-xs text-[var(--color-text-muted)] font-mono shrink-0">
-              [{formatTimestamp(segment.startMs)}]
-            </span>
-            <p className="flex-1 text-[var(--color-text-primary)]">{segment.text}</p>
+      {segments.map((segment) =>
+        segment.text === PAUSE_MARKER && segment.startMs === segment.endMs ? (
+          <div
+            key={segment.id}
+            className="flex items-center gap-3 text-xs text-[var(--color-text-muted)]"
+          >
+            <span className="font-mono">[{formatTimestamp(segment.startMs)}]</span>
+            <span className="flex-1 border-t border-dashed border-[var(--color-border)]" />
+            <span>{pauseLabel(segment, pauses)}</span>
+            <span className="flex-1 border-t border-dashed border-[var(--color-border)]" />
           </div>
-          <div className="mt-2 pl-14">
-            <ConfidenceIndicator confidence={segment.confidence} />
+        ) : (
+          <div
+            key={segment.id}
+            onClick={() => onSegmentClick?.(segment)}
+            className={`p-3 rounded-lg bg-[var(--color-bg-tertiary)] ${
+              onSegmentClick ? "cursor-pointer hover:bg-[var(--color-border)]" : ""
+            } ${segment.id === activeSegmentId ? "ring-2 ring-[var(--color-accent)]" : ""}`}
+          >
+            <div className="flex items-start gap-3">
+              <span className="text-xs text-[var(--color-text-muted)] font-mono shrink-0">
+                [{formatTimestamp(segment.startMs)}]
+              </span>
+              <p className="flex-1 text-[var(--color-text-primary)]">
+                <SegmentText segment={segment} />
+              </p>
+            </div>
+            <div className="mt-2 pl-14">
+              <ConfidenceIndicator confidence={segment.confidence} />
+            </div>
           </div>
-        </div>
-      ))}
+        )
+      )}
     </div>
   );
 }
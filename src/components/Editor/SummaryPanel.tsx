@@ -0,0 +1,96 @@
+import { useEffect, useState } from "react";
+import { summarizeTranscription } from "../../lib/tauri";
+import { SUMMARY_STYLES } from "../../lib/types";
+import type { SummaryStyle, TranscriptionNotes } from "../../lib/types";
+
+interface SummaryPanelProps {
+  transcriptionId: string;
+  notes: TranscriptionNotes[];
+}
+
+function styleLabel(style: SummaryStyle): string {
+  return SUMMARY_STYLES.find((s) => s.value === style)?.label ?? style;
+}
+
+export function SummaryPanel({ transcriptionId, notes }: SummaryPanelProps) {
+  const [style, setStyle] = useState<SummaryStyle>("brief");
+  const [items, setItems] = useState<TranscriptionNotes[]>(notes);
+  const [summarizing, setSummarizing] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setItems(notes);
+    setError(null);
+  }, [transcriptionId, notes]);
+
+  const handleSummarize = async () => {
+    setSummarizing(true);
+    setError(null);
+    try {
+      const generated = await summarizeTranscription(transcriptionId, style);
+      setItems([generated, ...items.filter((n) => n.style !== generated.style)]);
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setSummarizing(false);
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      <div className="flex items-center gap-2">
+        <select
+          value={style}
+          onChange={(e) => setStyle(e.target.value as SummaryStyle)}
+          className="px-2 py-1.5 rounded bg-[var(--color-bg-tertiary)] border border-[var(--color-border)] text-sm"
+        >
+          {SUMMARY_STYLES.map((s) => (
+            <option key={s.value} value={s.value}>
+              {s.label}
+            </option>
+          ))}
+        </select>
+        <button
+          onClick={handleSummarize}
+          disabled={summarizing}
+          className="px-3 py-1.5 rounded text-sm bg-[var(--color-accent)] text-white
+                     hover:bg-[var(--color-accent-hover)] disabled:opacity-50 transition-colors"
+        >
+          {summarizing ? "Generation..." : "Generer"}
+        </button>
+      </div>
+
+      {error && <p className="text-xs text-red-500">{error}</p>}
+
+      {items.map((n) => (
+        <div key={n.style} className="p-3 rounded-lg bg-[var(--color-bg-tertiary)] space-y-2">
+          <div className="text-xs text-[var(--color-text-muted)]">
+            {styleLabel(n.style)} - {new Date(n.createdAt).toLocaleString("fr-FR")}
+            {n.model && ` (${n.model})`}
+          </div>
+          <p className="text-sm text-[var(--color-text-secondary)] whitespace-pre-wrap">{n.summary}</p>
+          {n.keyPoints.length > 0 && (
+            <div>
+              <div className="text-xs font-medium text-[var(--color-text-primary)]">Points cles</div>
+              <ul className="list-disc pl-5 text-sm text-[var(--color-text-secondary)]">
+                {n.keyPoints.map((p, i) => (
+                  <li key={i}>{p}</li>
+                ))}
+              </ul>
+            </div>
+          )}
+          {n.actionItems.length > 0 && (
+            <div>
+              <div className="text-xs font-medium text-[var(--color-text-primary)]">Actions</div>
+              <ul className="list-disc pl-5 text-sm text-[var(--color-text-secondary)]">
+                {n.actionItems.map((a, i) => (
+                  <li key={i}>{a}</li>
+                ))}
+              </ul>
+            </div>
+          )}
+        </div>
+      ))}
+    </div>
+  );
+}
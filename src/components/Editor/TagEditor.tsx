@@ -0,0 +1,123 @@
+import { useEffect, useState } from "react";
+import {
+  addTag,
+  listFolders,
+  listTags,
+  setTranscriptionFolder,
+  tagTranscription,
+  untagTranscription,
+} from "../../lib/tauri";
+import type { Folder, Tag } from "../../lib/types";
+
+interface TagEditorProps {
+  transcriptionId: string;
+  tags: string[];
+  folder?: string;
+  onUpdated: (updatedAt: string) => void; // tags and folder are part of the transcription
+}
+
+export function TagEditor({ transcriptionId, tags, folder, onUpdated }: TagEditorProps) {
+  const [names, setNames] = useState(tags);
+  const [folderName, setFolderName] = useState(folder ?? "");
+  const [newTag, setNewTag] = useState("");
+  const [allTags, setAllTags] = useState<Tag[]>([]);
+  const [folders, setFolders] = useState<Folder[]>([]);
+
+  useEffect(() => {
+    setNames(tags);
+    setFolderName(folder ?? "");
+  }, [transcriptionId, tags, folder]);
+
+  useEffect(() => {
+    listTags()
+      .then(setAllTags)
+      .catch((e) => console.error("Failed to list tags:", e));
+    listFolders()
+      .then(setFolders)
+      .catch((e) => console.error("Failed to list folders:", e));
+  }, [transcriptionId]);
+
+  const handleAdd = async () => {
+    if (!newTag.trim()) return;
+    try {
+      const tag = await addTag(newTag);
+      onUpdated(await tagTranscription(transcriptionId, tag.id));
+      setAllTags([...allTags.filter((t) => t.id !== tag.id), tag]);
+      if (!names.some((n) => n.toLowerCase() === tag.name.toLowerCase())) {
+        setNames([...names, tag.name].sort((a, b) => a.localeCompare(b)));
+      }
+      setNewTag("");
+    } catch (e) {
+      console.error("Failed to tag transcription:", e);
+    }
+  };
+
+  const handleRemove = async (name: string) => {
+    const tag = allTags.find((t) => t.name.toLowerCase() === name.toLowerCase());
+    if (!tag) return;
+    try {
+      onUpdated(await untagTranscription(transcriptionId, tag.id));
+      setNames(names.filter((n) => n !== name));
+    } catch (e) {
+      console.error("Failed to untag transcription:", e);
+    }
+  };
+
+  // Saved when leaving the field
+  const handleFolderBlur = async () => {
+    if (folderName.trim() === (folder ?? "")) return;
+    try {
+      onUpdated(await setTranscriptionFolder(transcriptionId, folderName.trim() || null));
+    } catch (e) {
+      console.error("Failed to set folder:", e);
+    }
+  };
+
+  return (
+    <div className="px-4 py-2 border-b border-[var(--color-border)] flex flex-wrap items-center gap-2 text-xs">
+      <input
+        list="wk-folders"
+        value={folderName}
+        onChange={(e) => setFolderName(e.target.value)}
+        onBlur={handleFolderBlur}
+        placeholder="Dossier"
+        className="w-32 px-2 py-1 rounded bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]"
+      />
+      <datalist id="wk-folders">
+        {folders.map((f) => (
+          <option key={f.name} value={f.name} />
+        ))}
+      </datalist>
+
+      {names.map((name) => {
+        const color = allTags.find((t) => t.name.toLowerCase() === name.toLowerCase())?.color;
+        return (
+          <span
+            key={name}
+            className="flex items-center gap-1 px-1.5 py-0.5 rounded bg-[var(--color-accent)]/15 text-[var(--color-accent)]"
+            style={color ? { color } : undefined}
+          >
+            {name}
+            <button onClick={() => handleRemove(name)} title="Retirer l'etiquette" className="hover:opacity-70">
+              x
+            </button>
+          </span>
+        );
+      })}
+
+      <input
+        list="wk-tags"
+        value={newTag}
+        onChange={(e) => setNewTag(e.target.value)}
+        onKeyDown={(e) => e.key === "Enter" && handleAdd()}
+        placeholder="+ Etiquette"
+        className="w-28 px-2 py-1 rounded bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]"
+      />
+      <datalist id="wk-tags">
+        {allTags.map((t) => (
+          <option key={t.id} value={t.name} />
+        ))}
+      </datalist>
+    </div>
+  );
+}
@@ -0,0 +1,81 @@
+import { useEffect, useState } from "react";
+import { listSupportedLanguages, translateTranscription } from "../../lib/tauri";
+import type { SupportedLanguage, Translation } from "../../lib/types";
+
+interface TranslationPanelProps {
+  transcriptionId: string;
+  sourceLanguage: string; // code of the transcription, not offered as a target
+  translations: Translation[];
+}
+
+export function TranslationPanel({ transcriptionId, sourceLanguage, translations }: TranslationPanelProps) {
+  const [languages, setLanguages] = useState<SupportedLanguage[]>([]);
+  const [target, setTarget] = useState(sourceLanguage === "en" ? "fr" : "en");
+  const [items, setItems] = useState<Translation[]>(translations);
+  const [translating, setTranslating] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    listSupportedLanguages()
+      .then(setLanguages)
+      .catch((e) => console.error("Failed to list languages:", e));
+  }, []);
+
+  useEffect(() => {
+    setItems(translations);
+    setError(null);
+  }, [transcriptionId, translations]);
+
+  const handleTranslate = async () => {
+    setTranslating(true);
+    setError(null);
+    try {
+      const translation = await translateTranscription(transcriptionId, target);
+      setItems([...items.filter((t) => t.language !== translation.language), translation]);
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setTranslating(false);
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      <div className="flex items-center gap-2">
+        <select
+          value={target}
+          onChange={(e) => setTarget(e.target.value)}
+          className="px-2 py-1.5 rounded bg-[var(--color-bg-tertiary)] border border-[var(--color-border)] text-sm"
+        >
+          {languages
+            .filter((l) => l.code !== sourceLanguage)
+            .map((l) => (
+              <option key={l.code} value={l.code}>
+                {l.name}
+              </option>
+            ))}
+        </select>
+        <button
+          onClick={handleTranslate}
+          disabled={translating}
+          className="px-3 py-1.5 rounded text-sm bg-[var(--color-accent)] text-white
+                     hover:bg-[var(--color-accent-hover)] disabled:opacity-50 transition-colors"
+        >
+          {translating ? "Traduction..." : "Traduire"}
+        </button>
+      </div>
+
+      {error && <p className="text-xs text-red-500">{error}</p>}
+
+      {items.map((t) => (
+        <div key={t.language} className="p-3 rounded-lg bg-[var(--color-bg-tertiary)]">
+          <div className="text-xs text-[var(--color-text-muted)] mb-1">
+            <span className="uppercase">{t.language}</span> -{" "}
+            {new Date(t.createdAt).toLocaleString("fr-FR")}
+          </div>
+          <p className="text-sm text-[var(--color-text-secondary)] whitespace-pre-wrap">{t.text}</p>
+        </div>
+      ))}
+    </div>
+  );
+}
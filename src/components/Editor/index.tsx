@@ -1,8 +1,43 @@
-import { useState, useEffect } from "react";
+import { useState, useEffect, useRef } from "react";
+import { convertFileSrc } from "@tauri-apps/api/core";
 import { SegmentList } from "./SegmentList";
 import { ExportMenu } from "./ExportMenu";
+import { AudioPlayer } from "./AudioPlayer";
+import { RevisionHistory } from "./RevisionHistory";
+import { TranslationPanel } from "./TranslationPanel";
+import { SummaryPanel } from "./SummaryPanel";
+import { TagEditor } from "./TagEditor";
 import { useTranscription } from "../../hooks/useTranscription";
-import type { Transcription } from "../../lib/types";
+import {
+  deleteRecordingAudio,
+  discardTextDraft,
+  getRecordingAudioPath,
+  getTextDraft,
+  getTranscription,
+  isConflictError,
+  revertToRevision,
+  saveDraft,
+  updateTranscriptionMetadata,
+} from "../../lib/tauri";
+import type { Segment, Transcription, TranscriptionRevision, TranscriptFormat } from "../../lib/types";
+
+type Panel = "history" | "segments" | "translation" | "summary";
+
+// Draft of unsaved edits written this long after the last change
+const DRAFT_DELAY_MS = 2000;
+
+const PANELS: { value: Panel; label: string }[] = [
+  { value: "history", label: "Historique" },
+  { value: "segments", label: "Segments" },
+  { value: "translation", label: "Traduction" },
+  { value: "summary", label: "Resume" },
+];
+
+// Default name of exported files: title, else source name, else date
+function exportFileName(t: Transcription, title: string): string {
+  const name = title.trim() || t.sourceName?.replace(/\.[^.]+$/, "") || `transcription-${t.createdAt.slice(0, 10)}`;
+  return name.replace(/[\\/:*?"<>|]+/g, "-").slice(0, 100);
+}
 
 interface EditorProps {
   transcription: Transcription;
@@ -13,15 +48,114 @@ export function Editor({ transcription, onClose }: EditorProps) {
   const [editedText, setEditedText] = useState(
     transcription.editedText || transcription.rawText
   );
-  const [showSegments, setShowSegments] = useState(false);
-  const { updateText, exportTxt, exportDocx, copyText } = useTranscription();
+  // Shown instead of the text editor
+  const [panel, setPanel] = useState<Panel | null>(null);
+  // Text and updated_at as last loaded or saved here
+  const [savedText, setSavedText] = useState(transcription.editedText || transcription.rawText);
+  const [baseUpdatedAt, setBaseUpdatedAt] = useState(transcription.updatedAt);
+  const [conflict, setConflict] = useState(false);
+  // Unsaved edits of an earlier session, put back in the editor
+  const [draftRestored, setDraftRestored] = useState(false);
+  const [title, setTitle] = useState(transcription.title ?? "");
+  const { updateText, exportTxt, exportDocx, exportSrt, exportVtt, exportMarkdown, exportFullJson, exportJson, copyText } = useTranscription();
+  // Kept recording, if any, and the playback position
+  const [audioSrc, setAudioSrc] = useState<string | null>(null);
+  const [playbackMs, setPlaybackMs] = useState<number | null>(null);
+  const audioRef = useRef<HTMLAudioElement>(null);
 
   useEffect(() => {
-    setEditedText(transcription.editedText || transcription.rawText);
+    setTitle(transcription.title ?? "");
+  }, [transcription.id, transcription.title]);
+
+  // Saved when leaving the field
+  const handleTitleBlur = async () => {
+    if (title.trim() === (transcription.title ?? "")) return;
+    try {
+      // The text is unchanged: the next save is based on the renamed version
+      const updatedAt = await updateTranscriptionMetadata(
+        transcription.id,
+        title.trim() || null,
+        transcription.sourceName ?? null,
+        transcription.language
+      );
+      setBaseUpdatedAt(updatedAt);
+    } catch (e) {
+      console.error("Failed to rename transcription:", e);
+    }
+  };
+
+  useEffect(() => {
+    setAudioSrc(null);
+    setPlaybackMs(null);
+    getRecordingAudioPath(transcription.id)
+      .then((path) => setAudioSrc(path ? convertFileSrc(path) : null))
+      .catch((error) => console.error("Failed to get recording audio:", error));
+  }, [transcription.id]);
+
+  useEffect(() => {
+    const text = transcription.editedText || transcription.rawText;
+    setEditedText(text);
+    setSavedText(text);
+    setBaseUpdatedAt(transcription.updatedAt);
+    setConflict(false);
+    setDraftRestored(false);
+
+    // Restored from its own base: saved since, the save shows the conflict
+    let cancelled = false;
+    getTextDraft(transcription.id)
+      .then((draft) => {
+        if (cancelled || !draft) return;
+        setEditedText(draft.text);
+        setBaseUpdatedAt(draft.baseUpdatedAt);
+        setDraftRestored(true);
+      })
+      .catch((error) => console.error("Failed to load draft:", error));
+    return () => {
+      cancelled = true;
+    };
   }, [transcription]);
 
-  const handleSave = async () => {
-    await updateText(transcription.id, editedText);
+  // Unsaved edits survive a crash or power loss
+  useEffect(() => {
+    if (editedText === savedText) return;
+    const timer = setTimeout(() => {
+      saveDraft(transcription.id, editedText, baseUpdatedAt).catch((error) =>
+        console.error("Failed to save draft:", error)
+      );
+    }, DRAFT_DELAY_MS);
+    return () => clearTimeout(timer);
+  }, [transcription.id, editedText, savedText, baseUpdatedAt]);
+
+  const save = async (force: boolean) => {
+    try {
+      const update = await updateText(transcription.id, editedText, baseUpdatedAt, force);
+      if (update) {
+        setSavedText(editedText);
+        setBaseUpdatedAt(update.updatedAt);
+        setConflict(false);
+        setDraftRestored(false);
+      }
+    } catch (error) {
+      if (isConflictError(error)) setConflict(true);
+    }
+  };
+
+  const handleSave = () => save(false);
+
+  // Drop local changes for the version saved elsewhere
+  const handleReload = async () => {
+    try {
+      const latest = await getTranscription(transcription.id);
+      const text = latest.editedText || latest.rawText;
+      setEditedText(text);
+      setSavedText(text);
+      setBaseUpdatedAt(latest.updatedAt);
+      setConflict(false);
+      setDraftRestored(false);
+      await discardTextDraft(transcription.id);
+    } catch (error) {
+      console.error("Failed to reload transcription:", error);
+    }
   };
 
   const handleExportTxt = (path: string) => {
@@ -32,35 +166,103 @@ export function Editor({ transcription, onClose }: EditorProps) {
     exportDocx(transcription.id, path);
   };
 
+  const handleExportSrt = (path: string) => {
+    exportSrt(transcription.id, path);
+  };
+
+  const handleExportVtt = (path: string) => {
+    exportVtt(transcription.id, path);
+  };
+
+  const handleExportMarkdown = (path: string) => {
+    exportMarkdown(transcription.id, path);
+  };
+
+  const handleExportFullJson = (path: string) => {
+    exportFullJson(transcription.id, path);
+  };
+
+  const handleExportJson = (path: string, format: TranscriptFormat) => {
+    exportJson(transcription.id, path, format);
+  };
+
   const handleCopy = () => {
     copyText(editedText);
   };
 
-  const hasChanges = editedText !== (transcription.editedText || transcription.rawText);
+  // Saved as a new version: the revert can be undone from the history too
+  const handleRevert = async (revision: TranscriptionRevision) => {
+    if (editedText !== savedText && !confirm("Abandonner les modifications non enregistrees ?")) return;
+    try {
+      const update = await revertToRevision(transcription.id, revision.revision);
+      setEditedText(revision.text);
+      setSavedText(revision.text);
+      setBaseUpdatedAt(update.updatedAt);
+      setConflict(false);
+      setDraftRestored(false);
+      setPanel(null);
+    } catch (error) {
+      console.error("Failed to revert transcription:", error);
+    }
+  };
+
+  const handleSegmentClick = (segment: Segment) => {
+    const audio = audioRef.current;
+    if (!audio) return;
+    audio.currentTime = segment.startMs / 1000;
+    audio.play().catch((error) => console.error("Failed to play recording:", error));
+  };
+
+  const handleDeleteAudio = async () => {
+    if (!confirm("Supprimer l'audio de cet enregistrement ? Le texte est conserve.")) return;
+    try {
+      await deleteRecordingAudio(transcription.id);
+      setAudioSrc(null);
+    } catch (error) {
+      console.error("Failed to delete recording audio:", error);
+    }
+  };
+
+  const activeSegmentId =
+    playbackMs === null
+      ? undefined
+      : transcription.segments.find((s) => playbackMs >= s.startMs && playbackMs < s.endMs)?.id;
+
+  const hasChanges = editedText !== savedText;
 
   return (
     <div className="h-full flex flex-col">
       {/* Header */}
       <div className="p-4 border-b border-[var(--color-border)] flex items-center justify-between">
         <div>
-          <h2 className="font-semibold text-[var(--color-text-primary)]">
-            {transcription.sourceName || "Dictee"}
-          </h2>
+          <input
+            value={title}
+            onChange={(e) => setTitle(e.target.value)}
+            onBlur={handleTitleBlur}
+            onKeyDown={(e) => e.key === "Enter" && e.currentTarget.blur()}
+            placeholder={transcription.sourceName || "Dictee"}
+            title="Titre"
+            className="font-semibold bg-transparent text-[var(--color-text-primary)] focus:outline-none
+                       placeholder:text-[var(--color-text-primary)]"
+          />
           <p className="text-xs text-[var(--color-text-muted)]">
             {new Date(transcription.createdAt).toLocaleString("fr-FR")}
           </p>
         </div>
         <div className="flex items-center gap-2">
-          <button
-            onClick={() => setShowSegments(!showSegments)}
-            className={`px-3 py-1.5 rounded text-sm transition-colors ${
-              showSegments
-                ? "bg-[var(--color-accent)] text-white"
-                : "bg-[var(--color-bg-tertiary)] hover:bg-[var(--color-border)]"
-            }`}
-          >
-            Segments
-          </button>
+          {PANELS.map((p) => (
+            <button
+              key={p.value}
+              onClick={() => setPanel(panel === p.value ? null : p.value)}
+              className={`px-3 py-1.5 rounded text-sm transition-colors ${
+                panel === p.value
+                  ? "bg-[var(--color-accent)] text-white"
+                  : "bg-[var(--color-bg-tertiary)] hover:bg-[var(--color-border)]"
+              }`}
+            >
+              {p.label}
+            </button>
+          ))}
           {onClose && (
             <button
               onClick={onClose}
@@ -84,10 +286,45 @@ export function Editor({ transcription, onClose }: EditorProps) {
         </div>
       </div>
 
+      <TagEditor
+        transcriptionId={transcription.id}
+        tags={transcription.tags ?? []}
+        folder={transcription.folder}
+        onUpdated={setBaseUpdatedAt}
+      />
+
+      {audioSrc && (
+        <AudioPlayer
+          src={audioSrc}
+          audioRef={audioRef}
+          onTimeUpdate={setPlaybackMs}
+          onDelete={handleDeleteAudio}
+        />
+      )}
+
       {/* Content */}
       <div className="flex-1 overflow-auto p-4">
-        {showSegments ? (
-          <SegmentList segments={transcription.segments} />
+        {panel === "history" ? (
+          <RevisionHistory
+            transcriptionId={transcription.id}
+            updatedAt={baseUpdatedAt}
+            onRevert={handleRevert}
+          />
+        ) : panel === "translation" ? (
+          <TranslationPanel
+            transcriptionId={transcription.id}
+            sourceLanguage={transcription.language}
+            translations={transcription.translations ?? []}
+          />
+        ) : panel === "summary" ? (
+          <SummaryPanel transcriptionId={transcription.id} notes={transcription.notes ?? []} />
+        ) : panel === "segments" ? (
+          <SegmentList
+            segments={transcription.segments}
+            onSegmentClick={audioSrc ? handleSegmentClick : undefined}
+            activeSegmentId={activeSegmentId}
+            pauses={transcription.pauses}
+          />
         ) : (
           <textarea
             value={editedText}
@@ -98,6 +335,42 @@ export function Editor({ transcription, onClose }: EditorProps) {
         )}
       </div>
 
+      {draftRestored && !conflict && (
+        <div className="mx-4 mb-2 p-2 rounded bg-[var(--color-bg-secondary)] border border-[var(--color-border)] text-xs flex items-center justify-between gap-2">
+          <span className="text-[var(--color-text-muted)]">
+            Modifications non enregistrees de la derniere session restaurees.
+          </span>
+          <button
+            onClick={handleReload}
+            className="px-2 py-1 rounded bg-[var(--color-bg-tertiary)] hover:bg-[var(--color-border)]"
+          >
+            Ignorer
+          </button>
+        </div>
+      )}
+
+      {conflict && (
+        <div className="mx-4 mb-2 p-2 rounded bg-red-500/10 border border-red-500/30 text-xs flex items-center justify-between gap-2">
+          <span className="text-red-500">
+            Cette transcription a ete modifiee ailleurs depuis son ouverture.
+          </span>
+          <div className="flex gap-2">
+            <button
+              onClick={handleReload}
+              className="px-2 py-1 rounded bg-[var(--color-bg-tertiary)] hover:bg-[var(--color-border)]"
+            >
+              Recharger
+            </button>
+            <button
+              onClick={() => save(true)}
+              className="px-2 py-1 rounded bg-red-500/20 hover:bg-red-500/30 text-red-500"
+            >
+              Ecraser
+            </button>
+          </div>
+        </div>
+      )}
+
       {/* Footer */}
       <div className="p-4 border-t border-[var(--color-border)] flex items-center justify-between">
         <div>
@@ -119,7 +392,13 @@ export function Editor({ transcription, onClose }: EditorProps) {
           <ExportMenu
             onExportTxt={handleExportTxt}
             onExportDocx={handleExportDocx}
+            onExportSrt={handleExportSrt}
+            onExportVtt={handleExportVtt}
+            onExportMarkdown={handleExportMarkdown}
+            onExportFullJson={handleExportFullJson}
+            onExportJson={handleExportJson}
             onCopy={handleCopy}
+            fileName={exportFileName(transcription, title)}
           />
         </div>
       </div>
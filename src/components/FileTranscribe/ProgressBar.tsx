@@ -69,11 +69,17 @@ export function ProgressBar({ fileName, progress, onCancel }: ProgressBarProps)
 
       {/* Stats */}
       <div className="flex items-center justify-between mt-2 text-xs text-[var(--color-text-muted)]">
-        <span>{percentage}%</span>
+        <span>
+          {percentage}%
+          {progress.chunkCount ? ` - segment ${Math.min((progress.chunkIndex ?? 0) + 1, progress.chunkCount)}/${progress.chunkCount}` : ""}
+        </span>
         <span>
           {formatTime(progress.currentMs)} / {formatTime(progress.totalMs)}
         </span>
-        <span>{progress.speedFactor.toFixed(1)}x temps reel</span>
+        <span>
+          {progress.speedFactor > 0 ? `${progress.speedFactor.toFixed(1)}x temps reel` : "-"}
+          {progress.simulated && " (ralenti simule)"}
+        </span>
       </div>
     </div>
   );
@@ -3,17 +3,91 @@ import { DropZone } from "./DropZone";
 import { ProgressBar } from "./ProgressBar";
 import { useTranscription } from "../../hooks/useTranscription";
 import { useAppStore } from "../../stores/appStore";
-import { checkTestAudio, startRecording, stopRecordingToWav } from "../../lib/tauri";
-import type { Transcription, TranscriptionProgress } from "../../lib/types";
+import {
+  checkTestAudio,
+  discardPendingTranscription,
+  estimateJob,
+  finalizePendingTranscription,
+  recoverPendingTranscriptions,
+  startRecording,
+  stopRecordingToWav,
+} from "../../lib/tauri";
+import type { PendingTranscription, Transcription, TranscriptionProgress } from "../../lib/types";
 import { TRANSCRIPTION_LANGUAGES } from "../../lib/types";
 
+// Jobs predicted to take longer than this are confirmed before running
+const LONG_JOB_MS = 60_000;
+
+function formatEstimate(ms: number): string {
+  const minutes = Math.round(ms / 60_000);
+  if (minutes < 60) return `environ ${minutes} minute${minutes > 1 ? "s" : ""}`;
+  const hours = Math.floor(minutes / 60);
+  const rest = minutes % 60;
+  return `environ ${hours} h${rest > 0 ? ` ${String(rest).padStart(2, "0")}` : ""}`;
+}
+
 export function FileTranscribe() {
   const [isProcessing, setIsProcessing] = useState(false);
   const [currentFile, setCurrentFile] = useState<string | null>(null);
   const [progress, setProgress] = useState<TranscriptionProgress | null>(null);
   const [result, setResult] = useState<Transcription | null>(null);
-  const { transcribeFile, copyText, transcriptionSettings } = useTranscription();
-  const { toggleSettings } = useAppStore();
+  const { transcribeFile, resumeFile, copyText, transcriptionSettings, getDecodingConfig } = useTranscription();
+  const { toggleSettings, addTranscription } = useAppStore();
+
+  // File transcriptions interrupted by a crash, with their chunks done
+  const [pending, setPending] = useState<PendingTranscription[]>([]);
+  const [pendingError, setPendingError] = useState<string | null>(null);
+  useEffect(() => {
+    recoverPendingTranscriptions()
+      .then(setPending)
+      .catch((e) => console.warn("Failed to list interrupted transcriptions:", e));
+  }, []);
+
+  const forgetPending = (id: string) => setPending((list) => list.filter((p) => p.id !== id));
+
+  const handleResume = async (item: PendingTranscription) => {
+    setPendingError(null);
+    forgetPending(item.id);
+    setCurrentFile(item.sourcePath);
+    setIsProcessing(true);
+    setProgress({ currentMs: 0, totalMs: 1, speedFactor: 0 });
+    setResult(null);
+
+    const transcription = await resumeFile(item.id, (p) => setProgress(p));
+
+    setIsProcessing(false);
+    setProgress(null);
+    if (transcription) {
+      setResult(transcription);
+    } else {
+      // Still recoverable: the chunks done are kept
+      recoverPendingTranscriptions().then(setPending).catch(() => {});
+    }
+  };
+
+  const handleFinalize = async (item: PendingTranscription) => {
+    setPendingError(null);
+    try {
+      const transcription = await finalizePendingTranscription(item.id);
+      forgetPending(item.id);
+      addTranscription(transcription);
+      setCurrentFile(item.sourcePath);
+      setResult(transcription);
+    } catch (e) {
+      setPendingError(String(e));
+    }
+  };
+
+  const handleDiscard = async (item: PendingTranscription) => {
+    if (!confirm(`Abandonner la transcription de ${item.sourceName ?? item.sourcePath} ?`)) return;
+    setPendingError(null);
+    try {
+      await discardPendingTranscription(item.id);
+      forgetPending(item.id);
+    } catch (e) {
+      setPendingError(String(e));
+    }
+  };
 
   // Recording state for test audio
   const [isRecording, setIsRecording] = useState(false);
@@ -55,6 +129,18 @@ export function FileTranscribe() {
   // Fin du bloc test
 
   const handleFileSelect = async (path: string) => {
+    const estimate = await estimateJob(path, undefined, getDecodingConfig()).catch((e) => {
+      console.warn("Failed to estimate job:", e);
+      return null;
+    });
+    if (
+      estimate &&
+      estimate.processingMs >= LONG_JOB_MS &&
+      !confirm(`Duree estimee : ${formatEstimate(estimate.processingMs)}. Lancer la transcription ?`)
+    ) {
+      return;
+    }
+
     setCurrentFile(path);
     setIsProcessing(true);
     setProgress({ currentMs: 0, totalMs: 1, speedFactor: 0 });
@@ -149,6 +235,52 @@ export function FileTranscribe() {
         </button>
       )}
 
+      {/* Interrupted transcriptions */}
+      {!isProcessing && !result && pending.length > 0 && (
+        <div className="px-4 py-3 rounded-lg border border-amber-500/50 bg-amber-500/5 space-y-2">
+          <p className="text-sm font-medium text-[var(--color-text-primary)]">
+            Transcriptions interrompues
+          </p>
+          {pending.map((item) => (
+            <div key={item.id} className="flex items-center justify-between gap-3">
+              <div className="min-w-0">
+                <p className="text-sm text-[var(--color-text-primary)] truncate">
+                  {item.sourceName ?? item.sourcePath}
+                </p>
+                <p className="text-xs text-[var(--color-text-muted)]">
+                  {item.chunkCount > 0
+                    ? `${item.chunks.length} / ${item.chunkCount} segments transcrits`
+                    : "Aucun segment transcrit"}
+                </p>
+              </div>
+              <div className="flex items-center gap-2 shrink-0">
+                <button
+                  onClick={() => handleResume(item)}
+                  className="px-3 py-1 text-sm rounded bg-[var(--color-accent)] text-white hover:opacity-90"
+                >
+                  Reprendre
+                </button>
+                {item.chunks.some((c) => c.text) && (
+                  <button
+                    onClick={() => handleFinalize(item)}
+                    className="px-3 py-1 text-sm rounded bg-[var(--color-bg-tertiary)] hover:bg-[var(--color-border)]"
+                  >
+                    Finaliser
+                  </button>
+                )}
+                <button
+                  onClick={() => handleDiscard(item)}
+                  className="px-3 py-1 text-sm rounded text-red-500 hover:bg-red-500/10"
+                >
+                  Abandonner
+                </button>
+              </div>
+            </div>
+          ))}
+          {pendingError && <p className="text-xs text-red-500">{pendingError}</p>}
+        </div>
+      )}
+
       {/* Drop zone or progress */}
       {!isProcessing && !result ? (
         <div className="space-y-4">
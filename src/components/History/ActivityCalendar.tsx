@@ -0,0 +1,101 @@
+import { useEffect, useMemo, useState } from "react";
+import { getActivityCalendar } from "../../lib/tauri";
+import type { ActivityDay } from "../../lib/types";
+
+interface ActivityCalendarProps {
+  refreshKey?: number; // reload when the history changes
+}
+
+const LEVELS = [
+  "bg-[var(--color-bg-tertiary)]",
+  "bg-[var(--color-accent)]/25",
+  "bg-[var(--color-accent)]/50",
+  "bg-[var(--color-accent)]/75",
+  "bg-[var(--color-accent)]",
+];
+
+function localDate(date: Date): string {
+  const month = String(date.getMonth() + 1).padStart(2, "0");
+  const day = String(date.getDate()).padStart(2, "0");
+  return `${date.getFullYear()}-${month}-${day}`;
+}
+
+// Weeks of the year (Monday first), each with its 7 days; null outside the year
+function weeksOf(year: number): (Date | null)[][] {
+  const first = new Date(year, 0, 1);
+  const offset = (first.getDay() + 6) % 7;
+  const weeks: (Date | null)[][] = [];
+  const day = new Date(year, 0, 1 - offset);
+  while (day.getFullYear() <= year) {
+    const week: (Date | null)[] = [];
+    for (let i = 0; i < 7; i++) {
+      week.push(day.getFullYear() === year ? new Date(day) : null);
+      day.setDate(day.getDate() + 1);
+    }
+    weeks.push(week);
+  }
+  return weeks;
+}
+
+export function ActivityCalendar({ refreshKey }: ActivityCalendarProps) {
+  const [year, setYear] = useState(new Date().getFullYear());
+  const [days, setDays] = useState<ActivityDay[]>([]);
+
+  useEffect(() => {
+    getActivityCalendar(year)
+      .then(setDays)
+      .catch((e) => console.error("Failed to load activity calendar:", e));
+  }, [year, refreshKey]);
+
+  const byDate = useMemo(() => new Map(days.map((d) => [d.date, d])), [days]);
+  const maxMinutes = useMemo(() => Math.max(0, ...days.map((d) => d.minutes)), [days]);
+  const weeks = useMemo(() => weeksOf(year), [year]);
+  const total = days.reduce((sum, d) => sum + d.count, 0);
+
+  const level = (minutes: number) =>
+    minutes <= 0 || maxMinutes === 0 ? 0 : Math.min(4, Math.ceil((minutes / maxMinutes) * 4));
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center justify-between text-xs text-[var(--color-text-muted)]">
+        <span>
+          {total} transcription{total > 1 ? "s" : ""} en {year}
+        </span>
+        <div className="flex items-center gap-1">
+          <button onClick={() => setYear(year - 1)} className="px-1 hover:text-[var(--color-text-primary)]">
+            &lt;
+          </button>
+          <button
+            onClick={() => setYear(year + 1)}
+            disabled={year >= new Date().getFullYear()}
+            className="px-1 hover:text-[var(--color-text-primary)] disabled:opacity-30"
+          >
+            &gt;
+          </button>
+        </div>
+      </div>
+      <div className="flex gap-[2px] overflow-x-auto">
+        {weeks.map((week, i) => (
+          <div key={i} className="flex flex-col gap-[2px]">
+            {week.map((date, j) => {
+              if (!date) return <div key={j} className="w-2.5 h-2.5" />;
+              const activity = byDate.get(localDate(date));
+              const label = date.toLocaleDateString("fr-FR", { day: "numeric", month: "long" });
+              return (
+                <div
+                  key={j}
+                  className={`w-2.5 h-2.5 rounded-sm ${LEVELS[level(activity?.minutes ?? 0)]}`}
+                  title={
+                    activity
+                      ? `${label} : ${activity.count} transcription${activity.count > 1 ? "s" : ""}, ${Math.round(activity.minutes)} min`
+                      : `${label} : aucune activite`
+                  }
+                />
+              );
+            })}
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
@@ -1,9 +1,27 @@
-import type { Transcription } from "../../lib/types";
+import type { TranscriptionSummary } from "../../lib/types";
 
 interface TranscriptionCardProps {
-  transcription: Transcription;
+  transcription: TranscriptionSummary;
   onOpen: () => void;
   onDelete: () => void;
+  snippet?: string; // search result excerpt, matches in <mark></mark>
+}
+
+// Excerpt with the matched words highlighted
+function Snippet({ text }: { text: string }) {
+  return (
+    <>
+      {text.split(/<\/?mark>/).map((part, i) =>
+        i % 2 === 1 ? (
+          <mark key={i} className="bg-[var(--color-accent)]/20 text-inherit rounded">
+            {part}
+          </mark>
+        ) : (
+          part
+        )
+      )}
+    </>
+  );
 }
 
 function formatDuration(ms: number): string {
@@ -17,10 +35,11 @@ export function TranscriptionCard({
   transcription,
   onOpen,
   onDelete,
+  snippet,
 }: TranscriptionCardProps) {
   const preview =
-    transcription.rawText.slice(0, 100) +
-    (transcription.rawText.length > 100 ? "..." : "");
+    transcription.preview.slice(0, 100) +
+    (transcription.preview.length > 100 ? "..." : "");
 
   const date = new Date(transcription.createdAt);
   const time = date.toLocaleTimeString("fr-FR", {
@@ -62,18 +81,37 @@ export function TranscriptionCard({
             </svg>
           )}
           <span className="text-sm font-medium text-[var(--color-text-primary)] truncate">
-            {transcription.sourceName || "Dictee"}
+            {transcription.title || transcription.sourceName || "Dictee"}
           </span>
+          {transcription.isDraft && (
+            <span className="text-xs px-1.5 rounded bg-[var(--color-bg-tertiary)] text-[var(--color-text-muted)]">
+              Brouillon
+            </span>
+          )}
         </div>
         <div className="flex items-center gap-1 text-xs text-[var(--color-text-muted)]">
           <span>{time}</span>
           <span>-</span>
           <span>{formatDuration(transcription.durationMs)}</span>
+          <span className="uppercase">{transcription.language}</span>
         </div>
       </div>
 
+      {(transcription.folder || (transcription.tags?.length ?? 0) > 0) && (
+        <div className="mt-1 flex flex-wrap items-center gap-1 text-xs">
+          {transcription.folder && (
+            <span className="text-[var(--color-text-muted)]">{transcription.folder}</span>
+          )}
+          {transcription.tags?.map((tag) => (
+            <span key={tag} className="px-1.5 rounded bg-[var(--color-accent)]/15 text-[var(--color-accent)]">
+              {tag}
+            </span>
+          ))}
+        </div>
+      )}
+
       <p className="mt-2 text-xs text-[var(--color-text-secondary)] line-clamp-2">
-        {preview || "Transcription vide"}
+        {snippet ? <Snippet text={snippet} /> : preview || "Transcription vide"}
       </p>
 
       <div className="mt-3 flex items-center gap-2">
@@ -0,0 +1,102 @@
+import { useCallback, useEffect, useState } from "react";
+import { deleteTranscription, listTrash, purgeTrash, restoreTranscription } from "../../lib/tauri";
+import { useAppStore } from "../../stores/appStore";
+import type { TranscriptionSummary } from "../../lib/types";
+
+interface TrashProps {
+  onRestored: () => void; // reload the history
+}
+
+export function Trash({ onRestored }: TrashProps) {
+  const { settings } = useAppStore();
+  const [items, setItems] = useState<TranscriptionSummary[]>([]);
+
+  const reload = useCallback(() => {
+    listTrash()
+      .then(setItems)
+      .catch((e) => console.error("Failed to list the trash:", e));
+  }, []);
+
+  useEffect(reload, [reload]);
+
+  const handleRestore = async (id: string) => {
+    try {
+      await restoreTranscription(id);
+      reload();
+      onRestored();
+    } catch (e) {
+      console.error("Failed to restore transcription:", e);
+    }
+  };
+
+  const handleDelete = async (id: string) => {
+    if (!confirm("Supprimer definitivement cette transcription ?")) return;
+    try {
+      await deleteTranscription(id);
+      reload();
+    } catch (e) {
+      console.error("Failed to delete transcription:", e);
+    }
+  };
+
+  const handlePurge = async () => {
+    if (!confirm(`Vider la corbeille (${items.length} transcriptions) ?`)) return;
+    try {
+      await purgeTrash();
+      reload();
+    } catch (e) {
+      console.error("Failed to empty the trash:", e);
+    }
+  };
+
+  const retention = settings.trashRetentionDays ?? 30;
+
+  return (
+    <div className="flex-1 overflow-auto p-4 space-y-3">
+      <div className="flex items-center justify-between">
+        <p className="text-xs text-[var(--color-text-muted)]">
+          {retention > 0
+            ? `Supprimees definitivement ${retention} jours apres leur mise a la corbeille`
+            : "Conservees jusqu'a ce que la corbeille soit videe"}
+        </p>
+        {items.length > 0 && (
+          <button onClick={handlePurge} className="text-xs text-red-500 hover:underline">
+            Vider la corbeille
+          </button>
+        )}
+      </div>
+
+      {items.length === 0 ? (
+        <div className="text-center py-8 text-[var(--color-text-muted)]">La corbeille est vide</div>
+      ) : (
+        <div className="space-y-2">
+          {items.map((t) => (
+            <div
+              key={t.id}
+              className="bg-[var(--color-bg-tertiary)] rounded-lg p-3 flex items-start justify-between gap-2"
+            >
+              <div className="min-w-0">
+                <p className="text-xs text-[var(--color-text-muted)]">
+                  {new Date(t.createdAt).toLocaleString("fr-FR")}
+                  {t.deletedAt && ` - supprimee le ${new Date(t.deletedAt).toLocaleDateString("fr-FR")}`}
+                </p>
+                <p className="text-sm text-[var(--color-text-secondary)] truncate">{t.preview}</p>
+              </div>
+              <div className="flex gap-2 shrink-0 text-xs">
+                <button
+                  onClick={() => handleRestore(t.id)}
+                  className="text-[var(--color-accent)] hover:underline"
+                >
+                  Restaurer
+                </button>
+                <button onClick={() => handleDelete(t.id)} className="text-red-500 hover:underline">
+                  Supprimer
+                </button>
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -1,16 +1,35 @@
 import { useState, useEffect, useMemo } from "react";
+import { open } from "@tauri-apps/plugin-dialog";
+import { ActivityCalendar } from "./ActivityCalendar";
 import { SearchBar } from "./SearchBar";
 import { TranscriptionCard } from "./TranscriptionCard";
-import { useTranscription } from "../../hooks/useTranscription";
-import type { Transcription } from "../../lib/types";
+import { Trash } from "./Trash";
+import { HISTORY_PAGE_SIZE, useTranscription } from "../../hooks/useTranscription";
+import {
+  exportArchiveHtml,
+  importTranscriptJson,
+  listByFolder,
+  listByTag,
+  listFolders,
+  listTags,
+  searchTranscriptions,
+} from "../../lib/tauri";
+import type {
+  Folder,
+  HistoryCursor,
+  SearchHit,
+  Tag,
+  TranscriptionSource,
+  TranscriptionSummary,
+} from "../../lib/types";
 
 interface HistoryProps {
   onClose: () => void;
-  onSelectTranscription?: (transcription: Transcription) => void;
+  onSelectTranscription?: (transcription: TranscriptionSummary) => void;
 }
 
-function groupByDate(transcriptions: Transcription[]) {
-  const groups: { [key: string]: Transcription[] } = {};
+function groupByDate(transcriptions: TranscriptionSummary[]) {
+  const groups: { [key: string]: TranscriptionSummary[] } = {};
   const today = new Date();
   today.setHours(0, 0, 0, 0);
   const yesterday = new Date(today);
@@ -42,34 +61,108 @@ function groupByDate(transcriptions: Transcription[]) {
   return groups;
 }
 
+// "folder:<name>" or "tag:<id>" ("" = everything)
+function listShelf(shelf: string, after?: HistoryCursor): Promise<TranscriptionSummary[]> {
+  const value = shelf.slice(shelf.indexOf(":") + 1);
+  return shelf.startsWith("tag:")
+    ? listByTag(value, HISTORY_PAGE_SIZE, after)
+    : listByFolder(value, HISTORY_PAGE_SIZE, after);
+}
+
 export function History({ onClose, onSelectTranscription }: HistoryProps) {
   const [searchQuery, setSearchQuery] = useState("");
-  const { transcriptions, loadTranscriptions, deleteTranscription, deleteAllTranscriptions } =
-    useTranscription();
+  const [sourceFilter, setSourceFilter] = useState<TranscriptionSource | "">("");
+  const [fromDate, setFromDate] = useState("");
+  const [toDate, setToDate] = useState("");
+  const [hits, setHits] = useState<SearchHit[] | null>(null);
+  const [hasMore, setHasMore] = useState(false);
+  const [showTrash, setShowTrash] = useState(false);
+  // Folder or tag the list is restricted to, and its transcriptions
+  const [shelf, setShelf] = useState("");
+  const [shelfItems, setShelfItems] = useState<TranscriptionSummary[]>([]);
+  const [shelfHasMore, setShelfHasMore] = useState(false);
+  const [tags, setTags] = useState<Tag[]>([]);
+  const [folders, setFolders] = useState<Folder[]>([]);
+  const {
+    transcriptions,
+    loadTranscriptions,
+    loadMoreTranscriptions,
+    deleteTranscription,
+    deleteAllTranscriptions,
+  } = useTranscription();
 
   useEffect(() => {
-    loadTranscriptions();
+    loadTranscriptions().then(setHasMore);
   }, [loadTranscriptions]);
 
-  const filteredTranscriptions = useMemo(() => {
-    if (!searchQuery) return transcriptions;
-    const query = searchQuery.toLowerCase();
-    return transcriptions.filter(
-      (t) =>
-        t.rawText.toLowerCase().includes(query) ||
-        t.sourceName?.toLowerCase().includes(query)
-    );
-  }, [transcriptions, searchQuery]);
-
-  const groupedTranscriptions = useMemo(
-    () => groupByDate(filteredTranscriptions),
-    [filteredTranscriptions]
+  useEffect(() => {
+    listTags()
+      .then(setTags)
+      .catch((e) => console.error("Failed to list tags:", e));
+    listFolders()
+      .then(setFolders)
+      .catch((e) => console.error("Failed to list folders:", e));
+  }, [transcriptions]);
+
+  useEffect(() => {
+    if (!shelf) return;
+    listShelf(shelf)
+      .then((list) => {
+        setShelfItems(list);
+        setShelfHasMore(list.length === HISTORY_PAGE_SIZE);
+      })
+      .catch((e) => console.error("Failed to list transcriptions:", e));
+  }, [shelf, transcriptions]);
+
+  const loadMoreShelf = async () => {
+    const last = shelfItems[shelfItems.length - 1];
+    if (!last) return;
+    try {
+      const list = await listShelf(shelf, { createdAt: last.createdAt, id: last.id });
+      setShelfItems([...shelfItems, ...list]);
+      setShelfHasMore(list.length === HISTORY_PAGE_SIZE);
+    } catch (e) {
+      console.error("Failed to load more transcriptions:", e);
+    }
+  };
+
+  // Full-text search (server side), ranked: shown as one group in rank order
+  useEffect(() => {
+    if (!searchQuery.trim()) {
+      setHits(null);
+      return;
+    }
+    const timer = setTimeout(() => {
+      searchTranscriptions(searchQuery, {
+        sourceType: sourceFilter || undefined,
+        from: fromDate || undefined,
+        to: toDate || undefined,
+        tag: shelf.startsWith("tag:") ? shelf.slice(4) : undefined,
+        folder: shelf.startsWith("folder:") ? shelf.slice(7) : undefined,
+      })
+        .then(setHits)
+        .catch((e) => {
+          console.error("Search failed:", e);
+          setHits([]);
+        });
+    }, 250);
+    return () => clearTimeout(timer);
+  }, [searchQuery, sourceFilter, fromDate, toDate, shelf, transcriptions]);
+
+  const snippets = useMemo(
+    () => new Map((hits ?? []).map((hit) => [hit.transcription.id, hit.snippet])),
+    [hits]
   );
 
+  const groupedTranscriptions = useMemo(() => {
+    if (!hits) return groupByDate(shelf ? shelfItems : transcriptions);
+    if (hits.length === 0) return {};
+    return { [`Resultats (${hits.length})`]: hits.map((hit) => hit.transcription) };
+  }, [hits, shelf, shelfItems, transcriptions]);
+
+  // Recoverable from the trash: no confirmation
   const handleDelete = async (id: string) => {
-    if (confirm("Supprimer cette transcription ?")) {
-      await deleteTranscription(id);
-    }
+    await deleteTranscription(id);
   };
 
   const handleDeleteAll = async () => {
@@ -79,12 +172,85 @@ export function History({ onClose, onSelectTranscription }: HistoryProps) {
     }
   };
 
+  const handleExportArchive = async () => {
+    const dir = await open({ directory: true, title: "Dossier de l'archive HTML" });
+    if (typeof dir !== "string") return;
+    try {
+      const count = await exportArchiveHtml(dir);
+      alert(`Archive creee (${count} transcriptions)`);
+    } catch (e) {
+      console.error("Failed to export archive:", e);
+      alert(`Echec de l'export : ${e instanceof Error ? e.message : String(e)}`);
+    }
+  };
+
+  const handleImportJson = async () => {
+    const path = await open({
+      filters: [{ name: "JSON", extensions: ["json"] }],
+      title: "Importer une transcription (Whisper ou mots horodates)",
+    });
+    if (typeof path !== "string") return;
+    try {
+      await importTranscriptJson(path);
+      setHasMore(await loadTranscriptions());
+    } catch (e) {
+      console.error("Failed to import transcript:", e);
+      alert(`Echec de l'import : ${e instanceof Error ? e.message : String(e)}`);
+    }
+  };
+
   return (
     <div className="h-full flex flex-col">
       {/* Header */}
       <div className="p-4 border-b border-[var(--color-border)] flex items-center justify-between">
-        <h2 className="font-semibold text-[var(--color-text-primary)]">Historique</h2>
+        <h2 className="font-semibold text-[var(--color-text-primary)]">
+          {showTrash ? "Corbeille" : "Historique"}
+        </h2>
         <div className="flex items-center gap-2">
+          <button
+            onClick={() => setShowTrash(!showTrash)}
+            className={`px-2 py-1 rounded text-xs hover:bg-[var(--color-bg-tertiary)] ${
+              showTrash ? "text-[var(--color-accent)]" : "text-[var(--color-text-muted)]"
+            }`}
+            title={showTrash ? "Revenir a l'historique" : "Transcriptions supprimees"}
+          >
+            Corbeille
+          </button>
+          <button
+            onClick={handleImportJson}
+            className="p-1 rounded hover:bg-[var(--color-bg-tertiary)] text-[var(--color-text-muted)]"
+            title="Importer une transcription JSON"
+          >
+            <svg className="w-5 h-5" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+              <path
+                strokeLinecap="round"
+                strokeLinejoin="round"
+                strokeWidth={2}
+                d="M4 16v1a3 3 0 003 3h10a3 3 0 003-3v-1m-4-8l-4-4m0 0L8 8m4-4v12"
+              />
+            </svg>
+          </button>
+          {transcriptions.length > 0 && (
+            <button
+              onClick={handleExportArchive}
+              className="p-1 rounded hover:bg-[var(--color-bg-tertiary)] text-[var(--color-text-muted)]"
+              title="Exporter l'historique en archive HTML"
+            >
+              <svg
+                className="w-5 h-5"
+                fill="none"
+                stroke="currentColor"
+                viewBox="0 0 24 24"
+              >
+                <path
+                  strokeLinecap="round"
+                  strokeLinejoin="round"
+                  strokeWidth={2}
+                  d="M5 8h14M5 8a2 2 0 110-4h14a2 2 0 110 4M5 8v10a2 2 0 002 2h10a2 2 0 002-2V8m-9 4h4"
+                />
+              </svg>
+            </button>
+          )}
           {transcriptions.length > 0 && (
             <button
               onClick={handleDeleteAll}
@@ -127,17 +293,83 @@ export function History({ onClose, onSelectTranscription }: HistoryProps) {
         </div>
       </div>
 
+      {showTrash && <Trash onRestored={() => loadTranscriptions().then(setHasMore)} />}
+
+      {/* Activity */}
+      {!showTrash && transcriptions.length > 0 && (
+        <div className="px-4 pt-4">
+          <ActivityCalendar refreshKey={transcriptions.length} />
+        </div>
+      )}
+
       {/* Search */}
-      <div className="p-4">
+      <div className={showTrash ? "hidden" : "p-4"}>
         <SearchBar
           value={searchQuery}
           onChange={setSearchQuery}
-          placeholder="Rechercher dans l'historique..."
+          placeholder={`Rechercher dans l'historique ("expression exacte", prefixe*)`}
         />
+        {(folders.length > 0 || tags.length > 0) && (
+          <select
+            value={shelf}
+            onChange={(e) => setShelf(e.target.value)}
+            className="mt-2 w-full px-2 py-1 rounded text-xs bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]"
+          >
+            <option value="">Tous les dossiers et etiquettes</option>
+            {folders.length > 0 && (
+              <optgroup label="Dossiers">
+                {folders.map((f) => (
+                  <option key={f.name} value={`folder:${f.name}`}>
+                    {f.name} ({f.count})
+                  </option>
+                ))}
+              </optgroup>
+            )}
+            {tags.length > 0 && (
+              <optgroup label="Etiquettes">
+                {tags.map((t) => (
+                  <option key={t.id} value={`tag:${t.id}`}>
+                    {t.name} ({t.count})
+                  </option>
+                ))}
+              </optgroup>
+            )}
+          </select>
+        )}
+        {searchQuery && (
+          <div className="mt-2 flex items-center gap-2 text-xs">
+            <select
+              value={sourceFilter}
+              onChange={(e) => setSourceFilter(e.target.value as TranscriptionSource | "")}
+              className="px-2 py-1 rounded bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]"
+            >
+              <option value="">Toutes les sources</option>
+              <option value="dictation">Dictees</option>
+              <option value="file">Fichiers</option>
+              <option value="meeting">Reunions</option>
+              <option value="stream">Flux</option>
+              <option value="import">Imports</option>
+            </select>
+            <input
+              type="date"
+              value={fromDate}
+              onChange={(e) => setFromDate(e.target.value)}
+              title="Depuis le"
+              className="px-2 py-1 rounded bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]"
+            />
+            <input
+              type="date"
+              value={toDate}
+              onChange={(e) => setToDate(e.target.value)}
+              title="Jusqu'au"
+              className="px-2 py-1 rounded bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]"
+            />
+          </div>
+        )}
       </div>
 
       {/* List */}
-      <div className="flex-1 overflow-auto px-4 pb-4">
+      <div className={showTrash ? "hidden" : "flex-1 overflow-auto px-4 pb-4"}>
         {Object.keys(groupedTranscriptions).length === 0 ? (
           <div className="text-center py-8 text-[var(--color-text-muted)]">
             {searchQuery ? "Aucun resultat" : "Aucune transcription"}
@@ -156,11 +388,20 @@ export function History({ onClose, onSelectTranscription }: HistoryProps) {
                       transcription={transcription}
                       onOpen={() => onSelectTranscription?.(transcription)}
                       onDelete={() => handleDelete(transcription.id)}
+                      snippet={snippets.get(transcription.id)}
                     />
                   ))}
                 </div>
               </div>
             ))}
+            {!hits && (shelf ? shelfHasMore : hasMore) && (
+              <button
+                onClick={() => (shelf ? loadMoreShelf() : loadMoreTranscriptions().then(setHasMore))}
+                className="w-full py-2 text-sm text-[var(--color-accent)] hover:underline"
+              >
+                Charger plus
+              </button>
+            )}
           </div>
         )}
       </div>
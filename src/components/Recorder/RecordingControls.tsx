@@ -1,4 +1,6 @@
+import { useState } from "react";
 import { useRecording } from "../../hooks/useRecording";
+import { usePushToTalk } from "../../hooks/usePushToTalk";
 
 function formatTime(ms: number): string {
   const totalSeconds = Math.floor(ms / 1000);
@@ -8,103 +10,172 @@ function formatTime(ms: number): string {
 }
 
 export function RecordingControls() {
-  const { recordingState, elapsedMs, toggleRecording, togglePause } =
-    useRecording();
+  const {
+    recordingState,
+    elapsedMs,
+    start,
+    stop,
+    toggleRecording,
+    togglePause,
+    addMarker,
+    permissionIssue,
+    recordingLimit,
+  } = useRecording();
+  usePushToTalk(start, stop);
+  const [markerCount, setMarkerCount] = useState(0);
 
   const isRecording = recordingState === "recording";
   const isPaused = recordingState === "paused";
   const isProcessing = recordingState === "processing";
   const isActive = isRecording || isPaused;
 
-  return (
-    <div className="flex items-center justify-center gap-4">
-      {/* Timer */}
-      <div className="w-24 text-center">
-        {isActive && (
-          <div className="flex items-center gap-2">
-            <span
-              className={`w-2 h-2 rounded-full ${
-                isRecording ? "bg-red-500 animate-pulse" : "bg-yellow-500"
-              }`}
-            />
-            <span className="font-mono text-lg text-[var(--color-text-primary)]">
-              {formatTime(elapsedMs)}
-            </span>
-          </div>
-        )}
-      </div>
+  const handleToggleRecording = async () => {
+    if (!isActive) setMarkerCount(0);
+    await toggleRecording();
+  };
 
-      {/* Main record/stop button */}
-      <button
-        onClick={toggleRecording}
-        disabled={isProcessing}
-        className={`w-16 h-16 rounded-full flex items-center justify-center transition-all ${
-          isProcessing
-            ? "bg-gray-400 cursor-not-allowed"
-            : isActive
-              ? "bg-red-500 hover:bg-red-600"
-              : "bg-[var(--color-accent)] hover:bg-[var(--color-accent-hover)]"
-        }`}
-      >
-        {isProcessing ? (
-          <svg
-            className="w-6 h-6 text-white animate-spin"
-            fill="none"
-            viewBox="0 0 24 24"
-          >
-            <circle
-              className="opacity-25"
-              cx="12"
-              cy="12"
-              r="10"
-              stroke="currentColor"
-              strokeWidth="4"
-            />
-            <path
-              className="opacity-75"
-              fill="currentColor"
-              d="M4 12a8 8 0 018-8V0C5.373 0 0 5.373 0 12h4zm2 5.291A7.962 7.962 0 014 12H0c0 3.042 1.135 5.824 3 7.938l3-2.647z"
-            />
-          </svg>
-        ) : isActive ? (
-          <svg className="w-6 h-6 text-white" fill="currentColor" viewBox="0 0 24 24">
-            <rect x="6" y="6" width="12" height="12" rx="2" />
-          </svg>
-        ) : (
-          <svg className="w-6 h-6 text-white" fill="currentColor" viewBox="0 0 24 24">
-            <circle cx="12" cy="12" r="6" />
-          </svg>
-        )}
-      </button>
+  const handleAddMarker = async () => {
+    if (await addMarker()) setMarkerCount((count) => count + 1);
+  };
 
-      {/* Pause button */}
-      <div className="w-24">
-        {isActive && (
-          <button
-            onClick={togglePause}
-            className="p-3 rounded-full bg-[var(--color-bg-tertiary)] hover:bg-[var(--color-border)] transition-colors"
-          >
-            {isPaused ? (
-              <svg
-                className="w-5 h-5 text-[var(--color-text-primary)]"
+  return (
+    <div className="space-y-3">
+      <div className="flex items-center justify-center gap-4">
+        {/* Timer */}
+        <div className="w-24 text-center">
+          {isActive && (
+            <div className="flex items-center gap-2">
+              <span
+                className={`w-2 h-2 rounded-full ${
+                  isRecording ? "bg-red-500 animate-pulse" : "bg-yellow-500"
+                }`}
+              />
+              <span className="font-mono text-lg text-[var(--color-text-primary)]">
+                {formatTime(elapsedMs)}
+              </span>
+            </div>
+          )}
+        </div>
+
+        {/* Main record/stop button */}
+        <button
+          onClick={handleToggleRecording}
+          disabled={isProcessing}
+          className={`w-16 h-16 rounded-full flex items-center justify-center transition-all ${
+            isProcessing
+              ? "bg-gray-400 cursor-not-allowed"
+              : isActive
+                ? "bg-red-500 hover:bg-red-600"
+                : "bg-[var(--color-accent)] hover:bg-[var(--color-accent-hover)]"
+          }`}
+        >
+          {isProcessing ? (
+            <svg
+              className="w-6 h-6 text-white animate-spin"
+              fill="none"
+              viewBox="0 0 24 24"
+            >
+              <circle
+                className="opacity-25"
+                cx="12"
+                cy="12"
+                r="10"
+                stroke="currentColor"
+                strokeWidth="4"
+              />
+              <path
+                className="opacity-75"
                 fill="currentColor"
-                viewBox="0 0 24 24"
-              >
-                <path d="M8 5v14l11-7z" />
-              </svg>
-            ) : (
+                d="M4 12a8 8 0 018-8V0C5.373 0 0 5.373 0 12h4zm2 5.291A7.962 7.962 0 014 12H0c0 3.042 1.135 5.824 3 7.938l3-2.647z"
+              />
+            </svg>
+          ) : isActive ? (
+            <svg className="w-6 h-6 text-white" fill="currentColor" viewBox="0 0 24 24">
+              <rect x="6" y="6" width="12" height="12" rx="2" />
+            </svg>
+          ) : (
+            <svg className="w-6 h-6 text-white" fill="currentColor" viewBox="0 0 24 24">
+              <circle cx="12" cy="12" r="6" />
+            </svg>
+          )}
+        </button>
+
+        {/* Pause and marker buttons */}
+        <div className="w-24 flex items-center gap-2">
+          {isActive && (
+            <button
+              onClick={togglePause}
+              className="p-3 rounded-full bg-[var(--color-bg-tertiary)] hover:bg-[var(--color-border)] transition-colors"
+            >
+              {isPaused ? (
+                <svg
+                  className="w-5 h-5 text-[var(--color-text-primary)]"
+                  fill="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <path d="M8 5v14l11-7z" />
+                </svg>
+              ) : (
+                <svg
+                  className="w-5 h-5 text-[var(--color-text-primary)]"
+                  fill="currentColor"
+                  viewBox="0 0 24 24"
+                >
+                  <rect x="6" y="4" width="4" height="16" rx="1" />
+                  <rect x="14" y="4" width="4" height="16" rx="1" />
+                </svg>
+              )}
+            </button>
+          )}
+          {isRecording && (
+            <button
+              onClick={handleAddMarker}
+              title="Marquer ce moment"
+              className="relative p-3 rounded-full bg-[var(--color-bg-tertiary)] hover:bg-[var(--color-border)] transition-colors"
+            >
               <svg
                 className="w-5 h-5 text-[var(--color-text-primary)]"
-                fill="currentColor"
+                fill="none"
+                stroke="currentColor"
                 viewBox="0 0 24 24"
               >
-                <rect x="6" y="4" width="4" height="16" rx="1" />
-                <rect x="14" y="4" width="4" height="16" rx="1" />
+                <path
+                  strokeLinecap="round"
+                  strokeLinejoin="round"
+                  strokeWidth={2}
+                  d="M3 21v-4m0 0V5a2 2 0 012-2h6.5l1 1H21l-3 6 3 6h-8.5l-1-1H5a2 2 0 00-2 2z"
+                />
               </svg>
-            )}
-          </button>
-        )}
+              {markerCount > 0 && (
+                <span className="absolute -top-1 -right-1 text-[10px] leading-4 min-w-4 px-1 rounded-full bg-[var(--color-accent)] text-white">
+                  {markerCount}
+                </span>
+              )}
+            </button>
+          )}
+        </div>
       </div>
+
+      {/* Length limit near or reached: the rest is not recorded */}
+      {recordingLimit && isActive && (
+        <div className="mx-auto max-w-md px-3 py-2 rounded-lg text-xs bg-amber-500/10 text-amber-600 dark:text-amber-400">
+          {recordingLimit.reached
+            ? `Duree maximale atteinte (${formatTime(recordingLimit.maxMs)}) : la suite n'est plus enregistree. Arretez pour transcrire.`
+            : `L'enregistrement s'arretera de capter a ${formatTime(recordingLimit.maxMs)}.`}
+        </div>
+      )}
+
+      {/* Microphone access missing: explain how to grant it */}
+      {permissionIssue && (
+        <div className="mx-auto max-w-md px-3 py-2 rounded-lg text-xs bg-red-500/10 text-red-600 dark:text-red-400">
+          <p className="font-medium">
+            {permissionIssue.state === "denied"
+              ? "Acces au microphone refuse"
+              : "Acces au microphone non accorde"}
+          </p>
+          {permissionIssue.instructions && <p>{permissionIssue.instructions}</p>}
+        </div>
+      )}
     </div>
   );
 }
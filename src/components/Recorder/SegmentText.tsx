@@ -0,0 +1,32 @@
+import type { Segment } from "../../lib/types";
+
+// Below this, a word is shown as doubtful (to review)
+export const LOW_CONFIDENCE = 0.6;
+
+interface SegmentTextProps {
+  segment: Segment;
+}
+
+export function SegmentText({ segment }: SegmentTextProps) {
+  if (!segment.words?.length) return <>{segment.text}</>;
+
+  return (
+    <>
+      {segment.words.map((word, i) => (
+        <span key={i}>
+          {i > 0 && " "}
+          {word.confidence < LOW_CONFIDENCE ? (
+            <span
+              className="underline decoration-dotted decoration-yellow-500 bg-yellow-500/10 rounded-sm"
+              title={`Confiance ${Math.round(word.confidence * 100)}% - a verifier`}
+            >
+              {word.text}
+            </span>
+          ) : (
+            word.text
+          )}
+        </span>
+      ))}
+    </>
+  );
+}
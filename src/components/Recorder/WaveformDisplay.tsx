@@ -1,10 +1,13 @@
 import { useEffect, useRef } from "react";
 import { useAppStore } from "../../stores/appStore";
 
+// Last 10 seconds of the level history (10 values per second)
+const BAR_COUNT = 100;
+
 export function WaveformDisplay() {
   const canvasRef = useRef<HTMLCanvasElement>(null);
-  const { audioLevel, recordingState } = useAppStore();
-  const barsRef = useRef<number[]>(Array(64).fill(0));
+  const { waveform, recordingState } = useAppStore();
+  const barsRef = useRef<number[]>(Array(BAR_COUNT).fill(0));
 
   useEffect(() => {
     const canvas = canvasRef.current;
@@ -24,15 +27,10 @@ export function WaveformDisplay() {
       // Clear canvas
       ctx.clearRect(0, 0, width, height);
 
-      // Update bars based on audio level
+      // Newest levels on the right, scrolling as the history grows
       if (recordingState === "recording") {
-        // Shift bars left
-        for (let i = 0; i < barCount - 1; i++) {
-          barsRef.current[i] = barsRef.current[i + 1];
-        }
-        // Add new bar with some randomness for visual effect
-        barsRef.current[barCount - 1] =
-          audioLevel * (0.8 + Math.random() * 0.4);
+        const recent = waveform.slice(-barCount);
+        barsRef.current = [...Array(barCount - recent.length).fill(0), ...recent];
       } else if (recordingState === "idle") {
         // Decay bars when not recording
         for (let i = 0; i < barCount; i++) {
@@ -66,7 +64,7 @@ export function WaveformDisplay() {
     return () => {
       cancelAnimationFrame(animationId);
     };
-  }, [audioLevel, recordingState]);
+  }, [waveform, recordingState]);
 
   // Handle canvas resize
   useEffect(() => {
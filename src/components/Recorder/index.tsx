@@ -1,6 +1,7 @@
 import { WaveformDisplay } from "./WaveformDisplay";
 import { RecordingControls } from "./RecordingControls";
 import { ConfidenceIndicator } from "./ConfidenceIndicator";
+import { SegmentText } from "./SegmentText";
 import { useRecording } from "../../hooks/useRecording";
 import { useAudioDevices } from "../../hooks/useAudioDevices";
 import { useTranscription } from "../../hooks/useTranscription";
@@ -13,7 +14,7 @@ function formatTimestamp(ms: number): string {
 }
 
 export function Recorder() {
-  const { segments, pendingText } = useRecording();
+  const { segments, pendingText, translatedCaptions } = useRecording();
   const { devices, selectedDeviceId, selectDevice } = useAudioDevices();
   const { copyText } = useTranscription();
 
@@ -79,7 +80,7 @@ export function Recorder() {
                       [{formatTimestamp(segment.startMs)}]
                     </span>
                     <p className="flex-1 text-[var(--color-text-primary)]">
-                      {segment.text}
+                      <SegmentText segment={segment} />
                     </p>
                   </div>
                   <div className="pl-16">
@@ -104,6 +105,20 @@ export function Recorder() {
         </div>
       </div>
 
+      {/* Translated live captions */}
+      {translatedCaptions.length > 0 && (
+        <div className="max-h-32 overflow-auto bg-[var(--color-bg-secondary)] rounded-lg border border-[var(--color-border)] p-4 space-y-1">
+          {translatedCaptions.map((caption, i) => (
+            <p key={i} className="text-sm text-[var(--color-text-primary)]">
+              <span className="text-xs text-[var(--color-text-muted)] font-mono mr-2">
+                {caption.targetLanguage?.toUpperCase()}
+              </span>
+              {caption.translatedText}
+            </p>
+          ))}
+        </div>
+      )}
+
       {/* Action buttons */}
       <div className="flex items-center justify-end gap-3">
         <button
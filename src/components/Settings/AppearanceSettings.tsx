@@ -1,7 +1,20 @@
 import { useTheme } from "../../hooks/useTheme";
+import { useAppStore } from "../../stores/appStore";
+import { updateSettings as saveSettings } from "../../lib/tauri";
 
 export function AppearanceSettings() {
   const { theme, setTheme } = useTheme();
+  const { settings, setSettings } = useAppStore();
+
+  const handleSpokenFeedbackChange = async (spokenFeedback: boolean) => {
+    const newSettings = { ...settings, spokenFeedback };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (error) {
+      console.error("Failed to save spoken feedback:", error);
+    }
+  };
 
   return (
     <div className="space-y-4">
@@ -57,6 +70,22 @@ export function AppearanceSettings() {
           </button>
         </div>
       </div>
+
+      {/* Accessibility */}
+      <div className="space-y-2">
+        <label className="flex items-center gap-2 text-sm text-[var(--color-text-secondary)]">
+          <input
+            type="checkbox"
+            checked={settings.spokenFeedback ?? false}
+            onChange={(e) => handleSpokenFeedbackChange(e.target.checked)}
+            className="accent-[var(--color-accent)]"
+          />
+          Annonces vocales
+        </label>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          La synthese vocale du systeme annonce le debut de l'enregistrement et la fin de la transcription
+        </p>
+      </div>
     </div>
   );
 }
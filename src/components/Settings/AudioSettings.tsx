@@ -1,9 +1,47 @@
+import { useEffect, useState } from "react";
 import { useAudioDevices } from "../../hooks/useAudioDevices";
 import { useAppStore } from "../../stores/appStore";
+import { getDeviceCapabilities, listLoopbackDevices, updateSettings as saveSettings } from "../../lib/tauri";
+import type { AudioDevice, DeviceCapabilities, InputConfig, Settings } from "../../lib/types";
 
 export function AudioSettings() {
   const { devices, selectedDeviceId, selectDevice } = useAudioDevices();
-  const { audioLevel } = useAppStore();
+  const { audioLevel, settings, setSettings } = useAppStore();
+  const [loopbackDevices, setLoopbackDevices] = useState<AudioDevice[]>([]);
+  const [capabilities, setCapabilities] = useState<DeviceCapabilities | null>(null);
+  const deviceKey = selectedDeviceId ?? "";
+  const inputConfig = settings.inputConfigs?.[deviceKey] ?? {};
+
+  useEffect(() => {
+    listLoopbackDevices()
+      .then(setLoopbackDevices)
+      .catch((e) => console.error("Failed to load loopback devices:", e));
+  }, []);
+
+  useEffect(() => {
+    setCapabilities(null);
+    getDeviceCapabilities(selectedDeviceId ?? undefined)
+      .then(setCapabilities)
+      .catch((e) => console.error("Failed to load device capabilities:", e));
+  }, [selectedDeviceId]);
+
+  const update = async (patch: Partial<Settings>) => {
+    const newSettings = { ...settings, ...patch };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (e) {
+      console.error("Failed to save audio settings:", e);
+    }
+  };
+
+  const updateInput = (patch: Partial<InputConfig>) => {
+    update({
+      inputConfigs: { ...settings.inputConfigs, [deviceKey]: { ...inputConfig, ...patch } },
+    });
+  };
+
+  const optionalNumber = (value: string) => (value === "" ? undefined : Number(value));
 
   return (
     <div className="space-y-4">
@@ -40,6 +78,172 @@ export function AudioSettings() {
               </option>
             ))}
           </select>
+          {capabilities && (
+            <div className="mt-2 grid grid-cols-3 gap-2">
+              <select
+                value={inputConfig.sampleRate ?? ""}
+                onChange={(e) => updateInput({ sampleRate: optionalNumber(e.target.value) })}
+                title="Frequence d'echantillonnage"
+                className="px-2 py-2 bg-[var(--color-bg-tertiary)] border border-[var(--color-border)] rounded-lg text-sm text-[var(--color-text-primary)] focus:outline-none focus:border-[var(--color-accent)]"
+              >
+                <option value="">{capabilities.defaultSampleRate} Hz (defaut)</option>
+                {capabilities.sampleRates
+                  .filter((rate) => rate !== capabilities.defaultSampleRate)
+                  .map((rate) => (
+                    <option key={rate} value={rate}>
+                      {rate} Hz
+                    </option>
+                  ))}
+              </select>
+              <select
+                value={inputConfig.channel ?? ""}
+                onChange={(e) => updateInput({ channel: optionalNumber(e.target.value) })}
+                title="Canal enregistre"
+                className="px-2 py-2 bg-[var(--color-bg-tertiary)] border border-[var(--color-border)] rounded-lg text-sm text-[var(--color-text-primary)] focus:outline-none focus:border-[var(--color-accent)]"
+              >
+                <option value="">Tous les canaux</option>
+                {Array.from({ length: capabilities.maxChannels }, (_, channel) => (
+                  <option key={channel} value={channel}>
+                    Canal {channel + 1}
+                  </option>
+                ))}
+              </select>
+              <select
+                value={inputConfig.bufferSize ?? ""}
+                onChange={(e) => updateInput({ bufferSize: optionalNumber(e.target.value) })}
+                title="Taille du tampon"
+                className="px-2 py-2 bg-[var(--color-bg-tertiary)] border border-[var(--color-border)] rounded-lg text-sm text-[var(--color-text-primary)] focus:outline-none focus:border-[var(--color-accent)]"
+              >
+                <option value="">Tampon par defaut</option>
+                {[256, 512, 1024, 2048, 4096]
+                  .filter(
+                    (frames) =>
+                      (capabilities.minBufferSize ?? 0) <= frames &&
+                      frames <= (capabilities.maxBufferSize ?? Infinity)
+                  )
+                  .map((frames) => (
+                    <option key={frames} value={frames}>
+                      {frames} trames
+                    </option>
+                  ))}
+              </select>
+            </div>
+          )}
+          {capabilities && (
+            <p className="text-xs text-[var(--color-text-muted)] mt-1">
+              Formats : {capabilities.sampleFormats.join(", ")}. Applique au prochain enregistrement ; un seul canal evite de melanger deux micros d'une interface.
+            </p>
+          )}
+        </div>
+
+        <div>
+          <label className="text-xs text-[var(--color-text-muted)] block mb-1">
+            Audio systeme (appels)
+          </label>
+          <select
+            value={settings.loopbackDeviceId ?? ""}
+            onChange={(e) => update({ loopbackDeviceId: e.target.value || undefined })}
+            className="w-full px-3 py-2 bg-[var(--color-bg-tertiary)] border border-[var(--color-border)] rounded-lg text-sm text-[var(--color-text-primary)] focus:outline-none focus:border-[var(--color-accent)]"
+          >
+            <option value="">Aucun (microphone seul)</option>
+            {loopbackDevices.map((device) => (
+              <option key={device.id} value={device.id}>
+                {device.name}
+              </option>
+            ))}
+          </select>
+          {settings.loopbackDeviceId && (
+            <select
+              value={settings.dualSourceMode ?? "mix"}
+              onChange={(e) => update({ dualSourceMode: e.target.value as Settings["dualSourceMode"] })}
+              className="mt-2 w-full px-3 py-2 bg-[var(--color-bg-tertiary)] border border-[var(--color-border)] rounded-lg text-sm text-[var(--color-text-primary)] focus:outline-none focus:border-[var(--color-accent)]"
+            >
+              <option value="mix">Mixer les deux sources</option>
+              <option value="separate">Transcrire separement (Moi / Eux)</option>
+            </select>
+          )}
+        </div>
+
+        <div>
+          <label className="flex items-center gap-2 text-sm text-[var(--color-text-secondary)]">
+            <input
+              type="checkbox"
+              checked={settings.duckOutput ?? false}
+              onChange={(e) => update({ duckOutput: e.target.checked })}
+              className="accent-[var(--color-accent)]"
+            />
+            Baisser le son des haut-parleurs pendant l'enregistrement
+          </label>
+          {settings.duckOutput && (
+            <select
+              value={settings.duckOutputLevel ?? 30}
+              onChange={(e) => update({ duckOutputLevel: Number(e.target.value) })}
+              className="mt-2 w-full px-3 py-2 bg-[var(--color-bg-tertiary)] border border-[var(--color-border)] rounded-lg text-sm text-[var(--color-text-primary)] focus:outline-none focus:border-[var(--color-accent)]"
+            >
+              <option value={0}>Couper le son</option>
+              <option value={15}>15 % du volume</option>
+              <option value={30}>30 % du volume</option>
+              <option value={50}>50 % du volume</option>
+            </select>
+          )}
+          <p className="text-xs text-[var(--color-text-muted)] mt-1">
+            Volume restaure a l'arret ou en pause. Sans effet avec l'audio systeme (appels) ni sous Windows.
+          </p>
+        </div>
+
+        <div>
+          <label className="flex items-center gap-2 text-sm text-[var(--color-text-secondary)]">
+            <input
+              type="checkbox"
+              checked={settings.captureAgc ?? false}
+              onChange={(e) => update({ captureAgc: e.target.checked })}
+              className="accent-[var(--color-accent)]"
+            />
+            Controle automatique du gain du micro
+          </label>
+          <p className="text-xs text-[var(--color-text-muted)] mt-1">
+            Remonte le niveau des voix faibles pendant l'enregistrement, pour qu'elles ne soient pas prises pour des silences. Applique au prochain enregistrement.
+          </p>
+        </div>
+
+        <div>
+          <label className="text-xs text-[var(--color-text-muted)] block mb-1">
+            Duree maximale d'un enregistrement
+          </label>
+          <select
+            value={settings.maxRecordingMinutes ?? 180}
+            onChange={(e) => update({ maxRecordingMinutes: Number(e.target.value) })}
+            className="w-full px-3 py-2 bg-[var(--color-bg-tertiary)] border border-[var(--color-border)] rounded-lg text-sm text-[var(--color-text-primary)] focus:outline-none focus:border-[var(--color-accent)]"
+          >
+            <option value={30}>30 minutes</option>
+            <option value={60}>1 heure</option>
+            <option value={120}>2 heures</option>
+            <option value={180}>3 heures</option>
+            <option value={0}>Illimitee</option>
+          </select>
+          <p className="text-xs text-[var(--color-text-muted)] mt-1">
+            Un avertissement est affiche 5 minutes avant. Au-dela, le son n'est plus capte : un enregistrement oublie ne remplit pas le disque.
+          </p>
+        </div>
+
+        <div>
+          <label className="text-xs text-[var(--color-text-muted)] block mb-1">
+            Arret automatique apres un silence
+          </label>
+          <select
+            value={settings.autoStopSilenceSecs ?? 0}
+            onChange={(e) => update({ autoStopSilenceSecs: Number(e.target.value) })}
+            className="w-full px-3 py-2 bg-[var(--color-bg-tertiary)] border border-[var(--color-border)] rounded-lg text-sm text-[var(--color-text-primary)] focus:outline-none focus:border-[var(--color-accent)]"
+          >
+            <option value={0}>Jamais</option>
+            <option value={30}>30 secondes</option>
+            <option value={60}>1 minute</option>
+            <option value={120}>2 minutes</option>
+            <option value={300}>5 minutes</option>
+          </select>
+          <p className="text-xs text-[var(--color-text-muted)] mt-1">
+            Sans parole pendant ce temps, l'enregistrement est arrete et transcrit, meme fenetre fermee.
+          </p>
         </div>
 
         <div>
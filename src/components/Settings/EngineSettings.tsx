@@ -1,12 +1,152 @@
-import { useState } from "react";
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
 import { useAppStore } from "../../stores/appStore";
-import { ENGINE_BACKENDS, EngineBackend } from "../../lib/types";
-import { updateSettings as saveSettings, switchEngineBackend } from "../../lib/tauri";
+import {
+  ENGINE_BACKENDS,
+  EXECUTION_PROVIDER_LABELS,
+  EngineBackend,
+  EngineStatusReport,
+  ExecutionProvider,
+  ExecutionProviderInfo,
+  DeferredSwitch,
+  ModelCacheInfo,
+  OpenVinoPerformance,
+} from "../../lib/types";
+import {
+  clearModelCache,
+  getModelCacheInfo,
+  getEngineStatus,
+  getExecutionProviders,
+  reloadEngine,
+  updateSettings as saveSettings,
+  switchEngineBackend,
+  unloadEngine,
+  warmupEngine,
+} from "../../lib/tauri";
+
+function formatSeconds(ms: number): string {
+  return `${(ms / 1000).toFixed(1)} s`;
+}
+
+// Select value of an optional setting: "" is Auto
+function autoBoolValue(value: boolean | null | undefined): string {
+  return value == null ? "" : value ? "yes" : "no";
+}
+
+function parseAutoBool(value: string): boolean | null {
+  return value === "" ? null : value === "yes";
+}
+
+function formatSize(bytes: number): string {
+  if (bytes >= 1024 ** 3) return `${(bytes / 1024 ** 3).toFixed(1)} Go`;
+  return `${Math.round(bytes / 1024 ** 2)} Mo`;
+}
 
 export function EngineSettings() {
   const { settings, setSettings } = useAppStore();
   const [switching, setSwitching] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [providers, setProviders] = useState<ExecutionProviderInfo[]>([]);
+  const [deferred, setDeferred] = useState<string | null>(null);
+  const [engineStatus, setEngineStatus] = useState<EngineStatusReport | null>(null);
+  const [warming, setWarming] = useState(false);
+  const [modelCache, setModelCache] = useState<ModelCacheInfo | null>(null);
+  const performance = settings.openvinoPerformance ?? {};
+  const threadChoices = Array.from({ length: navigator.hardwareConcurrency || 8 }, (_, i) => i + 1);
+
+  const refreshProviders = () =>
+    getExecutionProviders()
+      .then(setProviders)
+      .catch((e) => console.error("Failed to list execution providers:", e));
+
+  useEffect(() => {
+    refreshProviders();
+  }, [settings.engineBackend]);
+
+  useEffect(() => {
+    if (switching) return;
+    getEngineStatus()
+      .then(setEngineStatus)
+      .catch((e) => console.error("Failed to get engine status:", e));
+  }, [switching, deferred]);
+
+  // Filled by each OpenVINO model load
+  useEffect(() => {
+    if (switching) return;
+    getModelCacheInfo()
+      .then(setModelCache)
+      .catch((e) => console.error("Failed to get model cache info:", e));
+  }, [switching]);
+
+  const handleClearModelCache = async () => {
+    setError(null);
+    try {
+      await clearModelCache();
+      setModelCache(await getModelCacheInfo());
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    }
+  };
+
+  const handleWarmup = async () => {
+    setWarming(true);
+    setError(null);
+    try {
+      setEngineStatus(await warmupEngine());
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setWarming(false);
+    }
+  };
+
+  // Free the model memory, or load it back
+  const handleUnloadToggle = async () => {
+    setSwitching(true);
+    setError(null);
+    try {
+      if (engineStatus?.status === "unloaded") {
+        await reloadEngine();
+      } else {
+        await unloadEngine();
+      }
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setSwitching(false);
+    }
+  };
+
+  const handleWarmupChange = async (warmupEngine: boolean) => {
+    const newSettings = { ...settings, warmupEngine };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (e) {
+      console.error("Failed to save engine warm-up:", e);
+    }
+  };
+
+  const handlePoolChange = async (enginePool: boolean) => {
+    const newSettings = { ...settings, enginePool };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (e) {
+      console.error("Failed to save engine pool:", e);
+    }
+  };
+
+  useEffect(() => {
+    const unlisten = listen<DeferredSwitch>("engine-backend-switched", (event) => {
+      setDeferred(null);
+      if (event.payload.error) setError(event.payload.error);
+      refreshProviders();
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
 
   const handleBackendChange = async (backend: EngineBackend) => {
     if (backend === settings.engineBackend) return;
@@ -15,11 +155,22 @@ export function EngineSettings() {
     setError(null);
 
     try {
-      // Switch the backend in the Rust engine
-      await switchEngineBackend(backend);
+      // Switch the backend in the Rust engine (after the recording or transcription in progress)
+      const result = await switchEngineBackend(backend);
+      setDeferred(result.deferred ? result.backend : null);
 
-      // Update local and persisted settings
-      const newSettings = { ...settings, engineBackend: backend };
+      // Update local and persisted settings, with the decoding profile chosen for this backend
+      const profile = result.decodingProfile;
+      const newSettings = {
+        ...settings,
+        engineBackend: backend,
+        transcription: {
+          ...settings.transcription,
+          beamWidth: profile.beamWidth,
+          temperature: profile.temperature,
+          blankPenalty: profile.blankPenalty,
+        },
+      };
       setSettings(newSettings);
       await saveSettings(newSettings);
     } catch (e) {
@@ -30,6 +181,85 @@ export function EngineSettings() {
     }
   };
 
+  const handleProviderChange = async (onnxExecutionProvider: ExecutionProvider) => {
+    const newSettings = { ...settings, onnxExecutionProvider };
+    setSettings(newSettings);
+    setError(null);
+    try {
+      await saveSettings(newSettings);
+      // Sessions are created at load time: reload to apply
+      if (settings.engineBackend === "onnxruntime") {
+        setSwitching(true);
+        await reloadEngine();
+      }
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+      console.error("Failed to change execution provider:", e);
+    } finally {
+      setSwitching(false);
+      refreshProviders();
+    }
+  };
+
+  const handleIsolatedChange = async (openvinoIsolated: boolean) => {
+    const newSettings = { ...settings, openvinoIsolated };
+    setSettings(newSettings);
+    setError(null);
+    try {
+      await saveSettings(newSettings);
+      // The worker is started when the model loads
+      if (settings.engineBackend === "openvino") {
+        setSwitching(true);
+        await reloadEngine();
+      }
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+      console.error("Failed to change OpenVINO isolation:", e);
+    } finally {
+      setSwitching(false);
+    }
+  };
+
+  // Compiled with the model: reloaded when OpenVINO is the current backend
+  const handlePerformanceChange = async (change: Partial<OpenVinoPerformance>) => {
+    const openvinoPerformance = { ...settings.openvinoPerformance, ...change };
+    const newSettings = { ...settings, openvinoPerformance };
+    setSettings(newSettings);
+    setError(null);
+    try {
+      await saveSettings(newSettings);
+      if (settings.engineBackend === "openvino") {
+        setSwitching(true);
+        await reloadEngine();
+      }
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+      console.error("Failed to change OpenVINO performance:", e);
+    } finally {
+      setSwitching(false);
+    }
+  };
+
+  const handleDebugDumpChange = async (debugDump: boolean) => {
+    const newSettings = { ...settings, debugDump };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (e) {
+      console.error("Failed to save debug dump:", e);
+    }
+  };
+
+  const handleSimulatedDelayChange = async (simulatedInferenceDelayMs: number) => {
+    const newSettings = { ...settings, simulatedInferenceDelayMs };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (e) {
+      console.error("Failed to save simulated delay:", e);
+    }
+  };
+
   const currentBackend = ENGINE_BACKENDS.find(b => b.value === settings.engineBackend) || ENGINE_BACKENDS[0];
 
   return (
@@ -94,6 +324,12 @@ export function EngineSettings() {
           ))}
         </div>
 
+        {deferred && (
+          <div className="p-2 rounded bg-[var(--color-bg-tertiary)] text-[var(--color-text-muted)] text-xs">
+            Passage a {deferred} a la fin de l'enregistrement ou de la transcription en cours
+          </div>
+        )}
+
         {/* Error message */}
         {error && (
           <div className="p-2 rounded bg-red-500/10 border border-red-500/30 text-red-500 text-xs">
@@ -102,6 +338,137 @@ export function EngineSettings() {
         )}
       </div>
 
+      {/* ONNX Runtime execution provider */}
+      <div className="space-y-1">
+        <label className="text-sm text-[var(--color-text-muted)]">
+          Materiel (ONNX Runtime)
+        </label>
+        <select
+          value={settings.onnxExecutionProvider ?? "cpu"}
+          onChange={(e) => handleProviderChange(e.target.value as ExecutionProvider)}
+          disabled={switching}
+          className="w-full px-3 py-2 rounded-lg bg-[var(--color-bg-tertiary)] border border-[var(--color-border)] text-sm text-[var(--color-text-primary)]"
+        >
+          {providers.map((p) => (
+            <option key={p.provider} value={p.provider}>
+              {EXECUTION_PROVIDER_LABELS[p.provider]}
+              {p.available === false ? " - indisponible" : ""}
+              {p.active ? " (actif)" : ""}
+            </option>
+          ))}
+        </select>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Un fournisseur GPU indisponible ou en echec bascule sur le CPU.
+        </p>
+      </div>
+
+      {/* OpenVINO process isolation */}
+      <div className="space-y-1">
+        <label className="flex items-center gap-2 text-sm text-[var(--color-text-secondary)]">
+          <input
+            type="checkbox"
+            checked={settings.openvinoIsolated ?? false}
+            onChange={(e) => handleIsolatedChange(e.target.checked)}
+            disabled={switching}
+            className="accent-[var(--color-accent)]"
+          />
+          Executer OpenVINO dans un processus separe
+        </label>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Si le pilote GPU plante, seule la transcription en cours echoue ; le moteur redemarre a la suivante.
+        </p>
+      </div>
+
+      {/* OpenVINO threading */}
+      <div className="space-y-2">
+        <label className="block text-sm text-[var(--color-text-secondary)]">
+          Performances OpenVINO (CPU)
+        </label>
+        <div className="grid grid-cols-2 gap-2 text-sm">
+          <label className="flex items-center justify-between gap-2 text-[var(--color-text-muted)]">
+            Threads
+            <select
+              value={performance.numThreads ?? 0}
+              onChange={(e) => handlePerformanceChange({ numThreads: Number(e.target.value) || null })}
+              disabled={switching}
+              className="px-2 py-1 rounded bg-[var(--color-bg-tertiary)] text-[var(--color-text-primary)]"
+            >
+              <option value={0}>Auto</option>
+              {threadChoices.map((n) => (
+                <option key={n} value={n}>
+                  {n}
+                </option>
+              ))}
+            </select>
+          </label>
+          <label className="flex items-center justify-between gap-2 text-[var(--color-text-muted)]">
+            Priorite
+            <select
+              value={performance.hint ?? ""}
+              onChange={(e) =>
+                handlePerformanceChange({ hint: (e.target.value || null) as OpenVinoPerformance["hint"] })
+              }
+              disabled={switching}
+              className="px-2 py-1 rounded bg-[var(--color-bg-tertiary)] text-[var(--color-text-primary)]"
+            >
+              <option value="">Auto</option>
+              <option value="latency">Latence</option>
+              <option value="throughput">Debit</option>
+            </select>
+          </label>
+          <label className="flex items-center justify-between gap-2 text-[var(--color-text-muted)]">
+            Epinglage des coeurs
+            <select
+              value={autoBoolValue(performance.cpuPinning)}
+              onChange={(e) => handlePerformanceChange({ cpuPinning: parseAutoBool(e.target.value) })}
+              disabled={switching}
+              className="px-2 py-1 rounded bg-[var(--color-bg-tertiary)] text-[var(--color-text-primary)]"
+            >
+              <option value="">Auto</option>
+              <option value="yes">Oui</option>
+              <option value="no">Non</option>
+            </select>
+          </label>
+          <label className="flex items-center justify-between gap-2 text-[var(--color-text-muted)]">
+            Hyper-threading
+            <select
+              value={autoBoolValue(performance.hyperThreading)}
+              onChange={(e) => handlePerformanceChange({ hyperThreading: parseAutoBool(e.target.value) })}
+              disabled={switching}
+              className="px-2 py-1 rounded bg-[var(--color-bg-tertiary)] text-[var(--color-text-primary)]"
+            >
+              <option value="">Auto</option>
+              <option value="yes">Oui</option>
+              <option value="no">Non</option>
+            </select>
+          </label>
+        </div>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Moins de threads laissent de la place aux autres applications pendant la transcription.
+        </p>
+      </div>
+
+      {/* OpenVINO compiled-model cache */}
+      {modelCache?.path && (
+        <div className="space-y-1">
+          <div className="flex items-center justify-between text-sm text-[var(--color-text-secondary)]">
+            <span>
+              Cache des modeles compiles : {formatSize(modelCache.sizeBytes)}
+            </span>
+            <button
+              onClick={handleClearModelCache}
+              disabled={switching || modelCache.sizeBytes === 0}
+              className="px-3 py-1 rounded text-sm bg-[var(--color-bg-tertiary)] hover:bg-[var(--color-border)] disabled:opacity-50"
+            >
+              Vider
+            </button>
+          </div>
+          <p className="text-xs text-[var(--color-text-muted)]">
+            Evite de recompiler les modeles OpenVINO a chaque demarrage. Vide automatiquement quand les modeles changent.
+          </p>
+        </div>
+      )}
+
       {/* Current engine info */}
       <div className="bg-[var(--color-bg-tertiary)] rounded-lg p-3 space-y-2 text-sm">
         <div className="flex justify-between">
@@ -123,6 +490,11 @@ export function EngineSettings() {
               <span className="w-2 h-2 bg-yellow-500 rounded-full animate-pulse" />
               Chargement...
             </span>
+          ) : engineStatus?.status === "unloaded" ? (
+            <span className="text-[var(--color-text-muted)] flex items-center gap-1">
+              <span className="w-2 h-2 bg-[var(--color-text-muted)] rounded-full" />
+              Decharge
+            </span>
           ) : (
             <span className="text-green-500 flex items-center gap-1">
               <span className="w-2 h-2 bg-green-500 rounded-full" />
@@ -130,12 +502,120 @@ export function EngineSettings() {
             </span>
           )}
         </div>
+        {(engineStatus?.status === "ready" || engineStatus?.status === "unloaded") && (
+          <div className="flex justify-between items-center">
+            <span className="text-[var(--color-text-muted)]">Memoire</span>
+            <button
+              onClick={handleUnloadToggle}
+              disabled={switching || warming}
+              className="text-xs text-[var(--color-accent)] hover:underline disabled:opacity-50"
+            >
+              {engineStatus.status === "unloaded" ? "Recharger le modele" : "Liberer (recharge a la prochaine dictee)"}
+            </button>
+          </div>
+        )}
+        {engineStatus?.loadTimeMs != null && (
+          <div className="flex justify-between">
+            <span className="text-[var(--color-text-muted)]">Temps de chargement</span>
+            <span className="text-[var(--color-text-primary)]">{formatSeconds(engineStatus.loadTimeMs)}</span>
+          </div>
+        )}
+        {engineStatus?.modelBytes != null && (
+          <div className="flex justify-between">
+            <span className="text-[var(--color-text-muted)]">Memoire du modele</span>
+            <span className="text-[var(--color-text-primary)]">
+              {formatSize(engineStatus.modelBytes)}
+              {engineStatus.processBytes != null && ` (application : ${formatSize(engineStatus.processBytes)})`}
+            </span>
+          </div>
+        )}
+        {engineStatus?.status === "ready" && (
+          <div className="flex justify-between items-center">
+            <span className="text-[var(--color-text-muted)]">Prechauffage</span>
+            <span className="text-[var(--color-text-primary)] flex items-center gap-2">
+              {engineStatus.warmupMs != null ? formatSeconds(engineStatus.warmupMs) : "Non fait"}
+              <button
+                onClick={handleWarmup}
+                disabled={warming || switching}
+                className="text-xs text-[var(--color-accent)] hover:underline disabled:opacity-50"
+              >
+                {warming ? "En cours..." : "Prechauffer"}
+              </button>
+            </span>
+          </div>
+        )}
+      </div>
+
+      <div className="space-y-1">
+        <label className="flex items-center gap-2 text-sm text-[var(--color-text-secondary)]">
+          <input
+            type="checkbox"
+            checked={settings.warmupEngine ?? true}
+            onChange={(e) => handleWarmupChange(e.target.checked)}
+            className="accent-[var(--color-accent)]"
+          />
+          Prechauffer le modele au demarrage
+        </label>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Une courte transcription a vide apres le chargement : la premiere dictee demarre a pleine vitesse.
+        </p>
+      </div>
+
+      <div className="space-y-1">
+        <label className="flex items-center gap-2 text-sm text-[var(--color-text-secondary)]">
+          <input
+            type="checkbox"
+            checked={settings.enginePool ?? false}
+            onChange={(e) => handlePoolChange(e.target.checked)}
+            className="accent-[var(--color-accent)]"
+          />
+          Transcriptions simultanees
+        </label>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Un second moteur transcrit les fichiers : une dictee n'attend plus la fin d'un fichier. Double la memoire utilisee par le modele.
+        </p>
       </div>
 
       {/* Note */}
       <p className="text-xs text-[var(--color-text-muted)] italic">
         Le changement de backend charge le nouveau modele a chaud.
       </p>
+
+      {/* Debug */}
+      <div className="space-y-1">
+        <label className="flex items-center gap-2 text-sm text-[var(--color-text-secondary)]">
+          <input
+            type="checkbox"
+            checked={settings.debugDump ?? false}
+            onChange={(e) => handleDebugDumpChange(e.target.checked)}
+            className="accent-[var(--color-accent)]"
+          />
+          Enregistrer les entrees/sorties du modele
+        </label>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Debogage : audio normalise, mel, longueurs de l'encodeur et tokens de chaque transcription
+          dans debug_dumps (donnees de l'application). Prend de la place.
+        </p>
+      </div>
+
+      <div className="space-y-1">
+        <label className="flex items-center justify-between gap-2 text-sm text-[var(--color-text-secondary)]">
+          Simuler une machine lente
+          <select
+            value={settings.simulatedInferenceDelayMs ?? 0}
+            onChange={(e) => handleSimulatedDelayChange(Number(e.target.value))}
+            className="px-2 py-1 bg-[var(--color-bg-tertiary)] border border-[var(--color-border)] rounded text-sm text-[var(--color-text-primary)]"
+          >
+            <option value={0}>Desactive</option>
+            <option value={500}>+0,5 s par segment</option>
+            <option value={2000}>+2 s par segment</option>
+            <option value={5000}>+5 s par segment</option>
+          </select>
+        </label>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Developpement : ralentit chaque inference pour tester la progression et l'attente.
+        </p>
+      </div>
     </div>
   );
 }
@@ -0,0 +1,100 @@
+import { useAppStore } from "../../stores/appStore";
+import { updateSettings as saveSettings } from "../../lib/tauri";
+import type { PostTranscriptionHook } from "../../lib/types";
+
+const inputClass = `w-full px-3 py-2 rounded-lg bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]
+  text-[var(--color-text-primary)] text-sm
+  focus:outline-none focus:ring-2 focus:ring-[var(--color-accent)] focus:border-transparent`;
+
+const NEW_HOOK: PostTranscriptionHook = {
+  name: "Nouveau script",
+  program: "",
+  args: ["{text_file}"],
+  enabled: true,
+  timeoutSecs: 30,
+};
+
+export function HookSettings() {
+  const { settings, setSettings } = useAppStore();
+  const hooks = settings.postTranscriptionHooks ?? [];
+
+  const save = async (postTranscriptionHooks: PostTranscriptionHook[]) => {
+    const newSettings = { ...settings, postTranscriptionHooks };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (e) {
+      console.error("Failed to save hooks:", e);
+    }
+  };
+
+  const updateHook = (index: number, change: Partial<PostTranscriptionHook>) =>
+    save(hooks.map((h, i) => (i === index ? { ...h, ...change } : h)));
+
+  return (
+    <div className="space-y-4">
+      <h3 className="text-sm font-medium text-[var(--color-text-primary)]">
+        Scripts apres transcription
+      </h3>
+      <p className="text-xs text-[var(--color-text-muted)]">
+        Programmes lances apres chaque transcription, sans shell. Le texte arrive sur l'entree
+        standard ; {"{text_file}"}, {"{id}"}, {"{source_path}"} et {"{language}"} sont remplaces
+        dans les arguments (un par ligne).
+      </p>
+
+      {hooks.map((hook, index) => (
+        <div key={index} className="p-3 rounded-lg border border-[var(--color-border)] space-y-2">
+          <div className="flex items-center gap-2">
+            <input
+              type="checkbox"
+              checked={hook.enabled}
+              onChange={(e) => updateHook(index, { enabled: e.target.checked })}
+              className="accent-[var(--color-accent)]"
+            />
+            <input
+              className={inputClass}
+              value={hook.name}
+              onChange={(e) => updateHook(index, { name: e.target.value })}
+            />
+            <button
+              onClick={() => save(hooks.filter((_, i) => i !== index))}
+              className="text-xs text-[var(--color-text-muted)] hover:text-red-500"
+            >
+              Supprimer
+            </button>
+          </div>
+          <input
+            className={inputClass}
+            placeholder="Programme (/usr/local/bin/mon-script)"
+            value={hook.program}
+            onChange={(e) => updateHook(index, { program: e.target.value })}
+          />
+          <textarea
+            className={inputClass}
+            rows={2}
+            placeholder="Arguments, un par ligne"
+            value={hook.args.join("\n")}
+            onChange={(e) => updateHook(index, { args: e.target.value.split("\n") })}
+          />
+          <label className="flex items-center gap-2 text-xs text-[var(--color-text-muted)]">
+            Delai maximum (s)
+            <input
+              type="number"
+              min={1}
+              className={`${inputClass} w-20`}
+              value={hook.timeoutSecs}
+              onChange={(e) => updateHook(index, { timeoutSecs: Math.max(1, Number(e.target.value)) })}
+            />
+          </label>
+        </div>
+      ))}
+
+      <button
+        onClick={() => save([...hooks, NEW_HOOK])}
+        className="text-sm text-[var(--color-accent)] hover:underline"
+      >
+        Ajouter un script
+      </button>
+    </div>
+  );
+}
@@ -0,0 +1,175 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { useAppStore } from "../../stores/appStore";
+import {
+  cancelDownload,
+  downloadModel,
+  listAvailableModels,
+  reloadEngine,
+  updateSettings as saveSettings,
+} from "../../lib/tauri";
+import type { AvailableModel, ModelStatus } from "../../lib/types";
+
+const inputClass = `w-full px-3 py-2 rounded-lg bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]
+  text-[var(--color-text-primary)] text-sm
+  focus:outline-none focus:ring-2 focus:ring-[var(--color-accent)] focus:border-transparent`;
+
+function formatSize(bytes: number): string {
+  if (bytes >= 1024 ** 3) return `${(bytes / 1024 ** 3).toFixed(1)} Go`;
+  return `${Math.round(bytes / 1024 ** 2)} Mo`;
+}
+
+const STATE_LABELS: Record<ModelStatus["state"], string> = {
+  not_installed: "Non installe",
+  downloading: "Telechargement...",
+  paused: "En pause",
+  installed: "Installe",
+  update_available: "Mise a jour disponible",
+  failed: "Echec",
+};
+
+export function ModelDownloads() {
+  const { settings, setSettings } = useAppStore();
+  const [catalogUrl, setCatalogUrl] = useState(settings.modelCatalogUrl ?? "");
+  const [models, setModels] = useState<AvailableModel[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setCatalogUrl(settings.modelCatalogUrl ?? "");
+  }, [settings.modelCatalogUrl]);
+
+  useEffect(() => {
+    const unlisten = listen<ModelStatus>("model-download-progress", (event) => {
+      const status = event.payload;
+      setModels((current) =>
+        current.map((m) => (m.id === status.modelId ? { ...m, status } : m))
+      );
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const handleRefresh = async () => {
+    setError(null);
+    try {
+      if (catalogUrl !== (settings.modelCatalogUrl ?? "")) {
+        const newSettings = { ...settings, modelCatalogUrl: catalogUrl || undefined };
+        setSettings(newSettings);
+        await saveSettings(newSettings);
+      }
+      setModels(await listAvailableModels());
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    }
+  };
+
+  const handleDownload = async (model: AvailableModel) => {
+    setError(null);
+    try {
+      const status = await downloadModel(model.id);
+      if (status.state === "installed" && model.backend === settings.engineBackend) {
+        await reloadEngine();
+      }
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    }
+  };
+
+  const handleCancel = async (model: AvailableModel) => {
+    try {
+      await cancelDownload(model.id);
+    } catch (e) {
+      console.error("Failed to cancel download:", e);
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <h3 className="text-sm font-medium text-[var(--color-text-primary)]">
+        Telechargement des modeles
+      </h3>
+
+      <div className="flex gap-2">
+        <input
+          className={inputClass}
+          placeholder="URL du catalogue (models.json)"
+          value={catalogUrl}
+          onChange={(e) => setCatalogUrl(e.target.value)}
+        />
+        <button
+          onClick={handleRefresh}
+          disabled={!catalogUrl}
+          className="px-3 py-2 rounded-lg text-sm bg-[var(--color-bg-tertiary)] hover:bg-[var(--color-border)]
+                     text-[var(--color-text-primary)] disabled:opacity-50"
+        >
+          Actualiser
+        </button>
+      </div>
+
+      {models.map((model) => {
+        const { status } = model;
+        const percent = status.totalBytes
+          ? Math.round((status.downloadedBytes / status.totalBytes) * 100)
+          : 0;
+        return (
+          <div
+            key={model.id}
+            className="p-3 rounded-lg border border-[var(--color-border)] space-y-2 text-sm"
+          >
+            <div className="flex justify-between gap-2">
+              <div>
+                <div className="font-medium text-[var(--color-text-primary)]">{model.name}</div>
+                <div className="text-xs text-[var(--color-text-muted)]">
+                  {model.backend} - v{model.version} - {formatSize(model.size)}
+                </div>
+              </div>
+              <span className="text-xs text-[var(--color-text-muted)]">
+                {STATE_LABELS[status.state]}
+              </span>
+            </div>
+
+            {(status.state === "downloading" || status.state === "paused") && (
+              <div className="h-1.5 rounded bg-[var(--color-bg-tertiary)] overflow-hidden">
+                <div
+                  className="h-full bg-[var(--color-accent)] transition-all"
+                  style={{ width: `${percent}%` }}
+                />
+              </div>
+            )}
+
+            {status.error && <p className="text-xs text-red-500">{status.error}</p>}
+
+            {status.state === "downloading" ? (
+              <button
+                onClick={() => handleCancel(model)}
+                className="text-xs text-[var(--color-text-muted)] hover:text-red-500"
+              >
+                Annuler
+              </button>
+            ) : (
+              status.state !== "installed" && (
+                <button
+                  onClick={() => handleDownload(model)}
+                  className="text-xs text-[var(--color-accent)] hover:underline"
+                >
+                  {status.state === "paused"
+                    ? `Reprendre (${percent}%)`
+                    : status.state === "update_available"
+                      ? "Mettre a jour"
+                      : "Telecharger"}
+                </button>
+              )
+            )}
+          </div>
+        );
+      })}
+
+      {error && (
+        <div className="p-2 rounded bg-red-500/10 border border-red-500/30 text-red-500 text-xs">
+          Erreur: {error}
+        </div>
+      )}
+    </div>
+  );
+}
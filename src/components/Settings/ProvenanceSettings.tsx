@@ -0,0 +1,105 @@
+import { useState } from "react";
+import { open } from "@tauri-apps/plugin-dialog";
+import { useAppStore } from "../../stores/appStore";
+import { updateSettings as saveSettings, verifyExport } from "../../lib/tauri";
+import type { ExportVerification } from "../../lib/types";
+
+function checkLabel(matches: boolean | null): string {
+  if (matches === null) return "non verifiable";
+  return matches ? "identique" : "different";
+}
+
+export function ProvenanceSettings() {
+  const { settings, setSettings } = useAppStore();
+  const [result, setResult] = useState<ExportVerification | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleToggle = async (exportProvenance: boolean) => {
+    const newSettings = { ...settings, exportProvenance };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (e) {
+      console.error("Failed to save export provenance:", e);
+    }
+  };
+
+  const handleVerify = async () => {
+    const path = await open({
+      title: "Export a verifier",
+      filters: [{ name: "Documents", extensions: ["txt", "md", "docx"] }],
+    });
+    if (typeof path !== "string") return;
+
+    setResult(null);
+    setError(null);
+    try {
+      setResult(await verifyExport(path));
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <h3 className="text-sm font-medium text-[var(--color-text-primary)]">
+        Provenance des exports
+      </h3>
+
+      <div className="space-y-1">
+        <label className="flex items-center gap-2 text-sm text-[var(--color-text-secondary)]">
+          <input
+            type="checkbox"
+            checked={settings.exportProvenance ?? false}
+            onChange={(e) => handleToggle(e.target.checked)}
+            className="accent-[var(--color-accent)]"
+          />
+          Ajouter les metadonnees de capture aux exports TXT et DOCX
+        </label>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Peripherique, dates d'enregistrement et d'export, version de l'application et empreinte
+          SHA-256 de l'audio.
+        </p>
+      </div>
+
+      <button
+        onClick={handleVerify}
+        className="px-3 py-2 rounded-lg text-sm bg-[var(--color-bg-tertiary)] hover:bg-[var(--color-border)]
+                   text-[var(--color-text-primary)]"
+      >
+        Verifier un export...
+      </button>
+
+      {result && (
+        <div
+          className={`p-2 rounded border text-xs space-y-1 ${
+            result.verified
+              ? "bg-green-500/10 border-green-500/30"
+              : "bg-red-500/10 border-red-500/30"
+          }`}
+        >
+          <div className={result.verified ? "text-green-500" : "text-red-500"}>
+            {result.verified ? "Document et audio verifies" : "Verification impossible ou echouee"}
+          </div>
+          <div className="text-[var(--color-text-secondary)]">
+            Enregistre le {new Date(result.provenance.recordedAt).toLocaleString("fr-FR")}
+            {result.provenance.device && ` sur ${result.provenance.device}`}, exporte le{" "}
+            {new Date(result.provenance.exportedAt).toLocaleString("fr-FR")} (v
+            {result.provenance.appVersion})
+          </div>
+          <div className="text-[var(--color-text-muted)]">
+            Texte : {checkLabel(result.contentMatches)} - empreinte en base :{" "}
+            {checkLabel(result.storedHashMatches)} - fichier source : {checkLabel(result.sourceFileMatches)} -
+            enregistrement : {checkLabel(result.recordingMatches)}
+          </div>
+        </div>
+      )}
+
+      {error && (
+        <div className="p-2 rounded bg-red-500/10 border border-red-500/30 text-red-500 text-xs">
+          Erreur: {error}
+        </div>
+      )}
+    </div>
+  );
+}
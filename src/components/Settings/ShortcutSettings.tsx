@@ -21,8 +21,14 @@ function ShortcutItem({ label, shortcut }: ShortcutItemProps) {
   );
 }
 
+// Keys offered for push-to-talk (held while speaking)
+const PUSH_TO_TALK_SHORTCUTS = ["CommandOrControl+Shift+Space", "CommandOrControl+Alt+D", "F8", "F9"];
+
 export function ShortcutSettings() {
-  const { settings } = useSettings();
+  const { settings, updateSettings } = useSettings();
+
+  const handlePushToTalkChange = (pushToTalk: string) =>
+    updateSettings({ ...settings, shortcuts: { ...settings.shortcuts, pushToTalk } });
 
   return (
     <div className="space-y-4">
@@ -58,6 +64,27 @@ export function ShortcutSettings() {
       <p className="text-xs text-[var(--color-text-muted)]">
         Les raccourcis fonctionnent depuis n'importe quelle application.
       </p>
+
+      <div className="space-y-1">
+        <label className="text-sm text-[var(--color-text-secondary)] block">
+          Dictee par appui maintenu
+        </label>
+        <select
+          value={settings.shortcuts.pushToTalk ?? ""}
+          onChange={(e) => handlePushToTalkChange(e.target.value)}
+          className="w-full px-3 py-2 bg-[var(--color-bg-tertiary)] border border-[var(--color-border)] rounded-lg text-sm text-[var(--color-text-primary)] focus:outline-none focus:border-[var(--color-accent)]"
+        >
+          <option value="">Desactivee</option>
+          {PUSH_TO_TALK_SHORTCUTS.map((shortcut) => (
+            <option key={shortcut} value={shortcut}>
+              {shortcut.replace("CommandOrControl", "Cmd").split("+").join(" + ")}
+            </option>
+          ))}
+        </select>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Maintenir la touche pour enregistrer ; au relachement, le texte est tape dans l'application active.
+        </p>
+      </div>
     </div>
   );
 }
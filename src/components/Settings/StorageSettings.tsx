@@ -0,0 +1,187 @@
+import { useState } from "react";
+import { open, save } from "@tauri-apps/plugin-dialog";
+import { useAppStore } from "../../stores/appStore";
+import {
+  exportDatabase,
+  importDatabase,
+  moveDatabase,
+  updateSettings as saveSettings,
+} from "../../lib/tauri";
+
+export function StorageSettings() {
+  const { settings, setSettings } = useAppStore();
+  const [isMoving, setIsMoving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const move = async (path?: string) => {
+    setIsMoving(true);
+    setError(null);
+    try {
+      const databasePath = await moveDatabase(path);
+      setSettings({ ...settings, databasePath });
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setIsMoving(false);
+    }
+  };
+
+  const handleRetentionChange = async (trashRetentionDays: number) => {
+    const newSettings = { ...settings, trashRetentionDays };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (e) {
+      console.error("Failed to save trash retention:", e);
+    }
+  };
+
+  const handleKeepRecordingsChange = async (keepRecordings: boolean) => {
+    const newSettings = { ...settings, keepRecordings };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (e) {
+      console.error("Failed to save keep recordings:", e);
+    }
+  };
+
+  const handleBackup = async () => {
+    const path = await save({
+      filters: [{ name: "Sauvegarde WakaScribe", extensions: ["json"] }],
+      defaultPath: `wakascribe-${new Date().toISOString().slice(0, 10)}.json`,
+    });
+    if (!path) return;
+    setError(null);
+    try {
+      const count = await exportDatabase(path);
+      alert(`Sauvegarde creee (${count} transcriptions)`);
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    }
+  };
+
+  const handleRestore = async () => {
+    const path = await open({
+      filters: [{ name: "Sauvegarde WakaScribe", extensions: ["json"] }],
+      title: "Restaurer une sauvegarde",
+    });
+    if (typeof path !== "string") return;
+    if (!confirm("Les transcriptions de la sauvegarde seront ajoutees et ses reglages remplaceront les reglages actuels. Continuer ?")) {
+      return;
+    }
+    setError(null);
+    try {
+      const report = await importDatabase(path);
+      setSettings({ ...report.settings, databasePath: settings.databasePath });
+      alert(
+        `Sauvegarde restauree : ${report.imported} ajoutees, ${report.copied} copiees (identifiant deja pris), ${report.skipped} deja presentes`
+      );
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    }
+  };
+
+  const handleChoose = async () => {
+    const dir = await open({ directory: true, title: "Dossier de la base de donnees" });
+    if (typeof dir === "string") {
+      await move(dir);
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <h3 className="text-sm font-medium text-[var(--color-text-primary)]">
+        Emplacement des donnees
+      </h3>
+      <p className="text-xs text-[var(--color-text-muted)]">
+        La base (historique et reglages) peut etre placee sur un disque partage ou synchronise.
+        Elle ne doit pas etre ouverte par deux machines en meme temps.
+      </p>
+
+      <p className="text-xs font-mono break-all text-[var(--color-text-secondary)]">
+        {settings.databasePath ?? "-"}
+      </p>
+
+      <div className="flex gap-2">
+        <button
+          onClick={handleChoose}
+          disabled={isMoving}
+          className="px-3 py-2 rounded-lg text-sm bg-[var(--color-bg-tertiary)] hover:bg-[var(--color-border)]
+                     text-[var(--color-text-primary)] disabled:opacity-50"
+        >
+          {isMoving ? "Deplacement..." : "Deplacer..."}
+        </button>
+        <button
+          onClick={() => move()}
+          disabled={isMoving}
+          className="px-3 py-2 rounded-lg text-sm text-[var(--color-text-muted)] hover:text-[var(--color-text-primary)]
+                     disabled:opacity-50"
+        >
+          Emplacement par defaut
+        </button>
+      </div>
+
+      {error && (
+        <div className="p-2 rounded bg-red-500/10 border border-red-500/30 text-red-500 text-xs">
+          Erreur: {error}
+        </div>
+      )}
+
+      <div className="space-y-2">
+        <h3 className="text-sm font-medium text-[var(--color-text-primary)]">Sauvegarde</h3>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Historique et reglages dans un seul fichier, a restaurer sur cette machine ou une autre
+        </p>
+        <div className="flex gap-2">
+          <button
+            onClick={handleBackup}
+            className="px-3 py-2 rounded-lg text-sm bg-[var(--color-bg-tertiary)] hover:bg-[var(--color-border)]
+                       text-[var(--color-text-primary)]"
+          >
+            Sauvegarder...
+          </button>
+          <button
+            onClick={handleRestore}
+            className="px-3 py-2 rounded-lg text-sm text-[var(--color-text-muted)] hover:text-[var(--color-text-primary)]"
+          >
+            Restaurer...
+          </button>
+        </div>
+      </div>
+
+      <div className="space-y-1">
+        <label className="flex items-center gap-2 text-sm text-[var(--color-text-secondary)]">
+          <input
+            type="checkbox"
+            checked={settings.keepRecordings ?? false}
+            onChange={(e) => handleKeepRecordingsChange(e.target.checked)}
+            className="accent-[var(--color-accent)]"
+          />
+          Conserver l'audio des enregistrements
+        </label>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Fichier WAV par dictee, pour la reecoute depuis les segments
+        </p>
+      </div>
+
+      <div className="space-y-2">
+        <label className="text-sm text-[var(--color-text-secondary)]">
+          Conservation dans la corbeille (jours)
+        </label>
+        <input
+          type="number"
+          min={0}
+          max={3650}
+          value={settings.trashRetentionDays ?? 30}
+          onChange={(e) => handleRetentionChange(Math.max(0, Number(e.target.value) || 0))}
+          className="w-24 px-3 py-2 rounded-lg bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]
+                     text-[var(--color-text-primary)] text-sm"
+        />
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Les transcriptions supprimees sont purgees au demarrage passe ce delai (0 : jamais)
+        </p>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,71 @@
+import { useEffect, useState } from "react";
+import { useAppStore } from "../../stores/appStore";
+import { updateSettings as saveSettings } from "../../lib/tauri";
+import type { LlmSettings as LlmConfig } from "../../lib/types";
+
+const EMPTY: LlmConfig = { apiUrl: "", apiKey: "", model: "" };
+
+const inputClass = `w-full px-3 py-2 rounded-lg bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]
+  text-[var(--color-text-primary)] text-sm
+  focus:outline-none focus:ring-2 focus:ring-[var(--color-accent)] focus:border-transparent`;
+
+export function SummarySettings() {
+  const { settings, setSettings } = useAppStore();
+  const [draft, setDraft] = useState<LlmConfig>(settings.llm ?? EMPTY);
+
+  useEffect(() => {
+    setDraft(settings.llm ?? EMPTY);
+  }, [settings.llm]);
+
+  const handleSave = async () => {
+    const newSettings = { ...settings, llm: draft };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (e) {
+      console.error("Failed to save LLM settings:", e);
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <h3 className="text-sm font-medium text-[var(--color-text-primary)]">
+        Resumes (LLM)
+      </h3>
+
+      <div className="space-y-2">
+        <input
+          className={inputClass}
+          placeholder="URL de l'API (ex. http://localhost:8080/v1)"
+          value={draft.apiUrl}
+          onChange={(e) => setDraft({ ...draft, apiUrl: e.target.value })}
+        />
+        <input
+          className={inputClass}
+          type="password"
+          placeholder="Cle d'API (si le service en demande une)"
+          value={draft.apiKey}
+          onChange={(e) => setDraft({ ...draft, apiKey: e.target.value })}
+        />
+        <input
+          className={inputClass}
+          placeholder="Modele (ex. gpt-4o-mini, ignore par llama.cpp)"
+          value={draft.model}
+          onChange={(e) => setDraft({ ...draft, model: e.target.value })}
+        />
+        <p className="text-xs text-[var(--color-text-muted)]">
+          API compatible OpenAI. Un serveur llama.cpp lance sur cette machine garde les textes
+          en local.
+        </p>
+      </div>
+
+      <button
+        onClick={handleSave}
+        className="w-full px-3 py-2 rounded-lg text-sm font-medium bg-[var(--color-bg-tertiary)]
+                   text-[var(--color-text-secondary)] hover:bg-[var(--color-border)] transition-colors"
+      >
+        Enregistrer
+      </button>
+    </div>
+  );
+}
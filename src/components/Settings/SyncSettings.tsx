@@ -0,0 +1,160 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { useAppStore } from "../../stores/appStore";
+import { getSyncStatus, syncNow, updateSettings as saveSettings } from "../../lib/tauri";
+import type { SyncRemote, SyncSettings as SyncConfig, SyncStatus } from "../../lib/types";
+
+const EMPTY_WEBDAV: SyncRemote = { kind: "webdav", url: "", username: "", password: "" };
+const EMPTY_S3: SyncRemote = {
+  kind: "s3",
+  endpoint: "",
+  region: "",
+  bucket: "",
+  accessKeyId: "",
+  secretAccessKey: "",
+};
+
+const inputClass = `w-full px-3 py-2 rounded-lg bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]
+  text-[var(--color-text-primary)] text-sm
+  focus:outline-none focus:ring-2 focus:ring-[var(--color-accent)] focus:border-transparent`;
+
+export function SyncSettings() {
+  const { settings, setSettings } = useAppStore();
+  const [draft, setDraft] = useState<SyncConfig>(
+    settings.sync ?? { enabled: false, passphrase: "" }
+  );
+  const [status, setStatus] = useState<SyncStatus | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setDraft(settings.sync ?? { enabled: false, passphrase: "" });
+  }, [settings.sync]);
+
+  useEffect(() => {
+    getSyncStatus()
+      .then(setStatus)
+      .catch((e) => console.error("Failed to get sync status:", e));
+    const unlisten = listen<SyncStatus>("sync-status", (event) => setStatus(event.payload));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const remote = draft.remote ?? EMPTY_WEBDAV;
+  const setRemoteField = (field: string, value: string) =>
+    setDraft({ ...draft, remote: { ...remote, [field]: value } as SyncRemote });
+
+  const handleSave = async () => {
+    const newSettings = { ...settings, sync: { ...draft, remote } };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (e) {
+      console.error("Failed to save sync settings:", e);
+    }
+  };
+
+  const handleSyncNow = async () => {
+    setError(null);
+    try {
+      await syncNow();
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <h3 className="text-sm font-medium text-[var(--color-text-primary)]">
+        Synchronisation
+      </h3>
+
+      <label className="flex items-center gap-2 text-sm text-[var(--color-text-secondary)]">
+        <input
+          type="checkbox"
+          checked={draft.enabled}
+          onChange={(e) => setDraft({ ...draft, enabled: e.target.checked })}
+          className="accent-[var(--color-accent)]"
+        />
+        Synchroniser l'historique (chiffre) avec un serveur
+      </label>
+
+      {draft.enabled && (
+        <div className="space-y-2">
+          <select
+            value={remote.kind}
+            onChange={(e) =>
+              setDraft({ ...draft, remote: e.target.value === "s3" ? EMPTY_S3 : EMPTY_WEBDAV })
+            }
+            className={inputClass}
+          >
+            <option value="webdav">WebDAV (Nextcloud...)</option>
+            <option value="s3">S3 compatible</option>
+          </select>
+
+          {remote.kind === "webdav" ? (
+            <>
+              <input className={inputClass} placeholder="URL" value={remote.url}
+                onChange={(e) => setRemoteField("url", e.target.value)} />
+              <input className={inputClass} placeholder="Utilisateur" value={remote.username}
+                onChange={(e) => setRemoteField("username", e.target.value)} />
+              <input className={inputClass} type="password" placeholder="Mot de passe" value={remote.password}
+                onChange={(e) => setRemoteField("password", e.target.value)} />
+            </>
+          ) : (
+            <>
+              <input className={inputClass} placeholder="Endpoint (https://...)" value={remote.endpoint}
+                onChange={(e) => setRemoteField("endpoint", e.target.value)} />
+              <input className={inputClass} placeholder="Region" value={remote.region}
+                onChange={(e) => setRemoteField("region", e.target.value)} />
+              <input className={inputClass} placeholder="Bucket" value={remote.bucket}
+                onChange={(e) => setRemoteField("bucket", e.target.value)} />
+              <input className={inputClass} placeholder="Access key" value={remote.accessKeyId}
+                onChange={(e) => setRemoteField("accessKeyId", e.target.value)} />
+              <input className={inputClass} type="password" placeholder="Secret key" value={remote.secretAccessKey}
+                onChange={(e) => setRemoteField("secretAccessKey", e.target.value)} />
+            </>
+          )}
+
+          <input
+            className={inputClass}
+            type="password"
+            placeholder="Phrase de chiffrement (la meme sur chaque machine)"
+            value={draft.passphrase}
+            onChange={(e) => setDraft({ ...draft, passphrase: e.target.value })}
+          />
+        </div>
+      )}
+
+      <div className="flex gap-2">
+        <button
+          onClick={handleSave}
+          className="flex-1 px-3 py-2 rounded-lg text-sm font-medium bg-[var(--color-bg-tertiary)]
+                     text-[var(--color-text-secondary)] hover:bg-[var(--color-border)] transition-colors"
+        >
+          Enregistrer
+        </button>
+        {settings.sync?.enabled && (
+          <button
+            onClick={handleSyncNow}
+            disabled={status?.state === "running"}
+            className="flex-1 px-3 py-2 rounded-lg text-sm font-medium bg-[var(--color-accent)] text-white
+                       hover:bg-[var(--color-accent-hover)] disabled:opacity-50 transition-colors"
+          >
+            {status?.state === "running" ? "Synchronisation..." : "Synchroniser maintenant"}
+          </button>
+        )}
+      </div>
+
+      {status?.lastSyncAt && (
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Derniere synchronisation : {new Date(status.lastSyncAt).toLocaleString("fr-FR")}
+          {" "}({status.uploaded} envoyee(s), {status.downloaded} recue(s))
+        </p>
+      )}
+      {(error || status?.error) && (
+        <p className="text-xs text-red-500">{error ?? status?.error}</p>
+      )}
+    </div>
+  );
+}
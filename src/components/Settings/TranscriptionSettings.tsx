@@ -1,44 +1,275 @@
 import { useAppStore } from "../../stores/appStore";
 import { TRANSCRIPTION_LANGUAGES } from "../../lib/types";
-import type { TranscriptionLanguage } from "../../lib/types";
+import { useEffect, useState } from "react";
+import type {
+  DecodingProfile,
+  Denoise,
+  Normalization,
+  PowerMode,
+  PowerSource,
+  Settings,
+  TranscriptionLanguage,
+} from "../../lib/types";
+import {
+  getPowerSource,
+  listDecodingProfiles,
+  listSupportedLanguages,
+  saveDecodingProfile,
+  updateSettings as saveSettings,
+} from "../../lib/tauri";
+
+const NORMALIZATION_OPTIONS: { value: Normalization["mode"]; label: string }[] = [
+  { value: "rms", label: "RMS (fichier entier)" },
+  { value: "lufs", label: "LUFS (ignore les pauses)" },
+  { value: "per_chunk", label: "Par segment de 5s" },
+  { value: "off", label: "Desactivee" },
+];
+
+const DENOISE_OPTIONS: { value: Denoise; label: string }[] = [
+  { value: "off", label: "Desactivee" },
+  { value: "high_pass", label: "Filtre passe-haut (ventilateur, ronflement)" },
+  { value: "spectral_gate", label: "Passe-haut et suppression du bruit de fond" },
+];
+
+const POWER_MODE_OPTIONS: { value: PowerMode; label: string }[] = [
+  { value: "performance", label: "Pleine vitesse" },
+  { value: "battery_saver", label: "Economie (pauses entre segments sur batterie)" },
+  { value: "wait_for_ac", label: "Attendre le secteur (sur batterie)" },
+];
+
+const CHUNK_SEPARATOR_OPTIONS: { value: string; label: string }[] = [
+  { value: " ", label: "Espace" },
+  { value: "\n", label: "Retour a la ligne" },
+  { value: "\n\n", label: "Paragraphe" },
+];
+
+const BUILT_IN_PROFILE_LABELS: Record<string, string> = {
+  fast: "Rapide",
+  accurate: "Precis",
+};
+
+const SENTENCE_GAP_OPTIONS: { value: number; label: string }[] = [
+  { value: 0, label: "Jamais" },
+  { value: 700, label: "Apres 0,7 s de silence" },
+  { value: 1000, label: "Apres 1 s de silence" },
+  { value: 1500, label: "Apres 1,5 s de silence" },
+  { value: 2500, label: "Apres 2,5 s de silence" },
+];
 
 export function TranscriptionSettings() {
   const { settings, setSettings } = useAppStore();
   const { transcription, engineBackend } = settings;
 
+  const [powerSource, setPowerSource] = useState<PowerSource>("unknown");
+  const [profiles, setProfiles] = useState<DecodingProfile[]>([]);
+  const [languages, setLanguages] = useState(TRANSCRIPTION_LANGUAGES);
+
   // CoreML doesn't support beam search
   const isCoreML = engineBackend === "coreml";
 
-  const handleLanguageChange = (language: TranscriptionLanguage) => {
-    setSettings({
-      transcription: { ...transcription, language },
+  useEffect(() => {
+    listDecodingProfiles(engineBackend)
+      .then(setProfiles)
+      .catch((error) => console.error("Failed to list decoding profiles:", error));
+  }, [engineBackend, settings.decodingProfiles]);
+
+  useEffect(() => {
+    listSupportedLanguages()
+      .then((supported) =>
+        setLanguages([
+          TRANSCRIPTION_LANGUAGES[0],
+          ...supported.map((lang) => ({ value: lang.id, label: lang.name })),
+        ])
+      )
+      .catch((error) => console.error("Failed to list supported languages:", error));
+  }, []);
+
+  useEffect(() => {
+    getPowerSource()
+      .then(setPowerSource)
+      .catch((error) => console.error("Failed to get power source:", error));
+  }, []);
+
+  // Language and decoding parameters are the backend defaults too: saved
+  const saveTranscription = async (patch: Partial<typeof transcription>) => {
+    const newSettings = { ...settings, transcription: { ...transcription, ...patch } };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (error) {
+      console.error("Failed to save transcription defaults:", error);
+    }
+  };
+
+  // Selecting or saving a profile makes its parameters the defaults
+  const applyProfile = async (profile: DecodingProfile) => {
+    try {
+      const saved: Settings = await saveDecodingProfile(profile);
+      setSettings({ ...saved, transcription: { ...transcription, ...saved.transcription } });
+    } catch (error) {
+      console.error("Failed to save decoding profile:", error);
+    }
+  };
+
+  const handleProfileSelect = (name: string) => {
+    const profile = profiles.find((p) => p.name === name);
+    if (profile) applyProfile(profile);
+  };
+
+  const handleProfileSave = () => {
+    const name = prompt("Nom du profil de decodage", selectedProfile ?? "custom");
+    if (!name?.trim()) return;
+    applyProfile({
+      name: name.trim(),
+      backend: engineBackend,
+      beamWidth: transcription.beamWidth,
+      temperature: transcription.temperature,
+      blankPenalty: transcription.blankPenalty,
     });
   };
 
+  const handleLanguageChange = (language: TranscriptionLanguage) => {
+    saveTranscription({ language });
+  };
+
   const handleBeamWidthChange = (beamWidth: number) => {
     // Ignore beam width changes for CoreML (always greedy)
     if (isCoreML) return;
+    saveTranscription({ beamWidth });
+  };
+
+  const handleTemperatureChange = (temperature: number) => {
+    saveTranscription({ temperature });
+  };
+
+  const handleBlankPenaltyChange = (blankPenalty: number) => {
+    saveTranscription({ blankPenalty });
+  };
+
+  const handleLiveTranscriptionChange = (liveTranscription: boolean) => {
     setSettings({
-      transcription: { ...transcription, beamWidth },
+      transcription: { ...transcription, liveTranscription },
     });
   };
 
-  const handleTemperatureChange = (temperature: number) => {
+  const handleRollingTranscriptionChange = (rollingTranscription: boolean) => {
     setSettings({
-      transcription: { ...transcription, temperature },
+      transcription: { ...transcription, rollingTranscription },
     });
   };
 
-  const handleBlankPenaltyChange = (blankPenalty: number) => {
+  const handleLivePasteChange = async (livePaste: boolean) => {
+    const newSettings = { ...settings, livePaste };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (error) {
+      console.error("Failed to save live paste:", error);
+    }
+  };
+
+  const handleNormalizationChange = (mode: Normalization["mode"]) => {
     setSettings({
-      transcription: { ...transcription, blankPenalty },
+      transcription: { ...transcription, normalization: { mode } },
     });
   };
 
+  const handleDenoiseChange = (denoise: Denoise) => {
+    saveTranscription({ denoise });
+  };
+
+  const handleAutoLanguagePriorChange = async (autoLanguagePrior: boolean) => {
+    const newSettings = { ...settings, autoLanguagePrior };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (error) {
+      console.error("Failed to save language prior:", error);
+    }
+  };
+
+  const handleSkipSilenceChange = async (skipSilence: boolean) => {
+    const newSettings = { ...settings, skipSilence };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (error) {
+      console.error("Failed to save skip silence:", error);
+    }
+  };
+
+  const handlePowerModeChange = async (powerMode: PowerMode) => {
+    const newSettings = { ...settings, powerMode };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (error) {
+      console.error("Failed to save power mode:", error);
+    }
+  };
+
+  const handleInterviewModeChange = async (interviewMode: boolean) => {
+    const newSettings = { ...settings, interviewMode };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (error) {
+      console.error("Failed to save interview mode:", error);
+    }
+  };
+
+  const handlePerLanguageDecodingChange = async (perLanguageDecoding: boolean) => {
+    const newSettings = { ...settings, perLanguageDecoding };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (error) {
+      console.error("Failed to save per-language decoding:", error);
+    }
+  };
+
+  // Keep the current slider values as the defaults of the forced language
+  const handleSaveLanguageDecoding = async () => {
+    const newSettings = {
+      ...settings,
+      languageDecodingOverrides: {
+        ...settings.languageDecodingOverrides,
+        [transcription.language]: {
+          blankPenalty: transcription.blankPenalty,
+          temperature: transcription.temperature,
+        },
+      },
+    };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (error) {
+      console.error("Failed to save language decoding override:", error);
+    }
+  };
+
+  const handleChunkJoinChange = async (chunkJoin: { chunkSeparator?: string; sentenceGapMs?: number }) => {
+    const newSettings = { ...settings, ...chunkJoin };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (error) {
+      console.error("Failed to save chunk joining:", error);
+    }
+  };
+
   // Decode mode: Simple (greedy) vs Precise (beam search)
   // CoreML only supports greedy decoding
   const isBeamSearch = !isCoreML && transcription.beamWidth > 1;
 
+  // Profile whose parameters are in use, if any
+  const selectedProfile = profiles.find(
+    (p) =>
+      p.beamWidth === transcription.beamWidth &&
+      p.temperature === transcription.temperature &&
+      p.blankPenalty === transcription.blankPenalty
+  )?.name;
+
   return (
     <div className="space-y-4">
       <h3 className="text-sm font-medium text-[var(--color-text-primary)] flex items-center gap-2">
@@ -70,12 +301,254 @@ export function TranscriptionSettings() {
                      text-[var(--color-text-primary)] text-sm
                      focus:outline-none focus:ring-2 focus:ring-[var(--color-accent)] focus:border-transparent"
         >
-          {TRANSCRIPTION_LANGUAGES.map((lang) => (
+          {languages.map((lang) => (
             <option key={lang.value} value={lang.value}>
               {lang.label}
             </option>
           ))}
         </select>
+        {transcription.language === "auto" && (
+          <label className="flex items-center gap-2 text-xs text-[var(--color-text-muted)]">
+            <input
+              type="checkbox"
+              checked={settings.autoLanguagePrior ?? true}
+              onChange={(e) => handleAutoLanguagePriorChange(e.target.checked)}
+              className="accent-[var(--color-accent)]"
+            />
+            Privilegier la langue de l'interface ({settings.language})
+          </label>
+        )}
+      </div>
+
+      {/* Skip long pauses of files */}
+      <div className="space-y-2">
+        <label className="flex items-center gap-2 text-sm text-[var(--color-text-secondary)]">
+          <input
+            type="checkbox"
+            checked={settings.skipSilence ?? false}
+            onChange={(e) => handleSkipSilenceChange(e.target.checked)}
+            className="accent-[var(--color-accent)]"
+          />
+          Ignorer les longs silences des fichiers
+        </label>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Pauses de plus de 2 s raccourcies avant la transcription ; les horodatages restent ceux du fichier
+        </p>
+      </div>
+
+      {/* Live transcription */}
+      <div className="space-y-2">
+        <label className="flex items-center gap-2 text-sm text-[var(--color-text-secondary)]">
+          <input
+            type="checkbox"
+            checked={transcription.liveTranscription ?? false}
+            onChange={(e) => handleLiveTranscriptionChange(e.target.checked)}
+            className="accent-[var(--color-accent)]"
+          />
+          Transcription en direct pendant l'enregistrement
+        </label>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Texte provisoire affiche au fil de la dictee ; le texte final est recalcule a l'arret
+        </p>
+        {transcription.liveTranscription && (
+          <label className="flex items-center gap-2 text-xs text-[var(--color-text-muted)]">
+            <input
+              type="checkbox"
+              checked={settings.livePaste ?? false}
+              onChange={(e) => handleLivePasteChange(e.target.checked)}
+              className="accent-[var(--color-accent)]"
+            />
+            Copier le texte confirme dans le presse-papiers au fil de la dictee (experimental)
+          </label>
+        )}
+        <label className="flex items-center gap-2 text-sm text-[var(--color-text-secondary)]">
+          <input
+            type="checkbox"
+            checked={transcription.rollingTranscription ?? false}
+            onChange={(e) => handleRollingTranscriptionChange(e.target.checked)}
+            className="accent-[var(--color-accent)]"
+          />
+          Transcrire les longs enregistrements par blocs pendant l'enregistrement
+        </label>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Blocs de 30 a 60 s coupes aux pauses ; a l'arret seule la fin reste a transcrire (sans audio systeme)
+        </p>
+      </div>
+
+      {/* Noise reduction */}
+      <div className="space-y-2">
+        <label className="text-sm text-[var(--color-text-secondary)]">
+          Reduction du bruit
+        </label>
+        <select
+          value={transcription.denoise ?? "off"}
+          onChange={(e) => handleDenoiseChange(e.target.value as Denoise)}
+          className="w-full px-3 py-2 rounded-lg bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]
+                     text-[var(--color-text-primary)] text-sm
+                     focus:outline-none focus:ring-2 focus:ring-[var(--color-accent)] focus:border-transparent"
+        >
+          {DENOISE_OPTIONS.map((option) => (
+            <option key={option.value} value={option.value}>
+              {option.label}
+            </option>
+          ))}
+        </select>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Appliquee avant la normalisation ; utile pour le micro d'un portable pres du ventilateur
+        </p>
+      </div>
+
+      {/* Audio level normalization */}
+      <div className="space-y-2">
+        <label className="text-sm text-[var(--color-text-secondary)]">
+          Normalisation du volume
+        </label>
+        <select
+          value={transcription.normalization?.mode ?? "rms"}
+          onChange={(e) => handleNormalizationChange(e.target.value as Normalization["mode"])}
+          className="w-full px-3 py-2 rounded-lg bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]
+                     text-[var(--color-text-primary)] text-sm
+                     focus:outline-none focus:ring-2 focus:ring-[var(--color-accent)] focus:border-transparent"
+        >
+          {NORMALIZATION_OPTIONS.map((option) => (
+            <option key={option.value} value={option.value}>
+              {option.label}
+            </option>
+          ))}
+        </select>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Enregistrements calmes avec souffle ou bruit de fond : preferer LUFS ou desactiver
+        </p>
+      </div>
+
+      {/* Low-power mode */}
+      <div className="space-y-2">
+        <label className="text-sm text-[var(--color-text-secondary)]">
+          Economie d'energie
+        </label>
+        <select
+          value={settings.powerMode ?? "performance"}
+          onChange={(e) => handlePowerModeChange(e.target.value as PowerMode)}
+          className="w-full px-3 py-2 rounded-lg bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]
+                     text-[var(--color-text-primary)] text-sm
+                     focus:outline-none focus:ring-2 focus:ring-[var(--color-accent)] focus:border-transparent"
+        >
+          {POWER_MODE_OPTIONS.map((option) => (
+            <option key={option.value} value={option.value}>
+              {option.label}
+            </option>
+          ))}
+        </select>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          {powerSource === "battery"
+            ? "Sur batterie actuellement"
+            : powerSource === "ac"
+              ? "Sur secteur actuellement"
+              : "Source d'alimentation inconnue (traitee comme secteur)"}
+        </p>
+      </div>
+
+      {/* Output layout */}
+      <div className="space-y-2">
+        <label className="text-sm text-[var(--color-text-secondary)]">
+          Mise en forme
+        </label>
+        <div className="flex gap-2">
+          <button
+            onClick={() => handleInterviewModeChange(false)}
+            className={`flex-1 px-3 py-2 rounded-lg text-sm font-medium transition-colors
+              ${!settings.interviewMode
+                ? "bg-[var(--color-accent)] text-white"
+                : "bg-[var(--color-bg-tertiary)] text-[var(--color-text-secondary)] hover:bg-[var(--color-border)]"
+              }`}
+          >
+            Texte continu
+          </button>
+          <button
+            onClick={() => handleInterviewModeChange(true)}
+            className={`flex-1 px-3 py-2 rounded-lg text-sm font-medium transition-colors
+              ${settings.interviewMode
+                ? "bg-[var(--color-accent)] text-white"
+                : "bg-[var(--color-bg-tertiary)] text-[var(--color-text-secondary)] hover:bg-[var(--color-border)]"
+              }`}
+          >
+            Interview
+          </button>
+        </div>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          {settings.interviewMode
+            ? "Questions et reponses separees (Q: / A:)"
+            : "Transcription en un seul bloc"}
+        </p>
+      </div>
+
+      {/* Joining of long-audio chunks */}
+      <div className="space-y-2">
+        <label className="text-sm text-[var(--color-text-secondary)]">
+          Jonction des segments
+        </label>
+        <select
+          value={settings.chunkSeparator ?? " "}
+          onChange={(e) => handleChunkJoinChange({ chunkSeparator: e.target.value })}
+          className="w-full px-3 py-2 rounded-lg bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]
+                     text-[var(--color-text-primary)] text-sm
+                     focus:outline-none focus:ring-2 focus:ring-[var(--color-accent)] focus:border-transparent"
+        >
+          {CHUNK_SEPARATOR_OPTIONS.map((option) => (
+            <option key={option.label} value={option.value}>
+              {option.label}
+            </option>
+          ))}
+        </select>
+        <select
+          value={settings.sentenceGapMs ?? 1000}
+          onChange={(e) => handleChunkJoinChange({ sentenceGapMs: Number(e.target.value) })}
+          className="w-full px-3 py-2 rounded-lg bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]
+                     text-[var(--color-text-primary)] text-sm
+                     focus:outline-none focus:ring-2 focus:ring-[var(--color-accent)] focus:border-transparent"
+        >
+          {SENTENCE_GAP_OPTIONS.map((option) => (
+            <option key={option.value} value={option.value}>
+              {option.label}
+            </option>
+          ))}
+        </select>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Fin de phrase (point et majuscule) ajoutee apres un long silence entre deux segments
+        </p>
+      </div>
+
+      {/* Decoding profile of the current backend */}
+      <div className="space-y-2">
+        <label className="text-sm text-[var(--color-text-secondary)]">
+          Profil de decodage
+        </label>
+        <div className="flex gap-2">
+          <select
+            value={selectedProfile ?? ""}
+            onChange={(e) => handleProfileSelect(e.target.value)}
+            className="flex-1 px-3 py-2 rounded-lg bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]
+                       text-[var(--color-text-primary)] text-sm
+                       focus:outline-none focus:ring-2 focus:ring-[var(--color-accent)] focus:border-transparent"
+          >
+            {!selectedProfile && <option value="">Personnalise</option>}
+            {profiles.map((p) => (
+              <option key={p.name} value={p.name}>
+                {(p.builtIn && BUILT_IN_PROFILE_LABELS[p.name]) || p.name}
+              </option>
+            ))}
+          </select>
+          <button
+            onClick={handleProfileSave}
+            className="px-3 py-2 rounded-lg text-sm bg-[var(--color-bg-tertiary)] text-[var(--color-text-secondary)]
+                       hover:bg-[var(--color-border)]"
+          >
+            Enregistrer
+          </button>
+        </div>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Propre a chaque moteur, repris au changement de moteur
+        </p>
       </div>
 
       {/* Decoding mode */}
@@ -141,6 +614,33 @@ export function TranscriptionSettings() {
         </div>
       )}
 
+      {/* Per-language blank penalty and temperature */}
+      <div className="space-y-2">
+        <label className="flex items-center gap-2 text-sm text-[var(--color-text-secondary)]">
+          <input
+            type="checkbox"
+            checked={settings.perLanguageDecoding ?? true}
+            onChange={(e) => handlePerLanguageDecodingChange(e.target.checked)}
+            className="accent-[var(--color-accent)]"
+          />
+          Reglages adaptes a la langue
+        </label>
+        <p className="text-xs text-[var(--color-text-muted)]">
+          {(settings.perLanguageDecoding ?? true)
+            ? "Temperature et blank penalty choisis selon la langue (forcee ou privilegiee en Auto) ; les curseurs ci-dessous servent sinon"
+            : "Les curseurs ci-dessous s'appliquent a toutes les langues"}
+        </p>
+        {(settings.perLanguageDecoding ?? true) && transcription.language !== "auto" && (
+          <button
+            onClick={handleSaveLanguageDecoding}
+            className="px-3 py-1.5 rounded-lg text-xs font-medium bg-[var(--color-bg-tertiary)]
+                       text-[var(--color-text-secondary)] hover:bg-[var(--color-border)] transition-colors"
+          >
+            Utiliser les curseurs pour cette langue
+          </button>
+        )}
+      </div>
+
       {/* Temperature slider */}
       <div className="space-y-2">
         <div className="flex justify-between">
@@ -0,0 +1,71 @@
+import { useEffect, useState } from "react";
+import { useAppStore } from "../../stores/appStore";
+import { updateSettings as saveSettings } from "../../lib/tauri";
+import type { TranslationSettings as TranslationConfig } from "../../lib/types";
+
+const EMPTY: TranslationConfig = { apiUrl: "", apiKey: "" };
+
+const inputClass = `w-full px-3 py-2 rounded-lg bg-[var(--color-bg-tertiary)] border border-[var(--color-border)]
+  text-[var(--color-text-primary)] text-sm
+  focus:outline-none focus:ring-2 focus:ring-[var(--color-accent)] focus:border-transparent`;
+
+export function TranslationSettings() {
+  const { settings, setSettings } = useAppStore();
+  const [draft, setDraft] = useState<TranslationConfig>(settings.translation ?? EMPTY);
+
+  useEffect(() => {
+    setDraft(settings.translation ?? EMPTY);
+  }, [settings.translation]);
+
+  const handleSave = async () => {
+    const newSettings = { ...settings, translation: draft };
+    setSettings(newSettings);
+    try {
+      await saveSettings(newSettings);
+    } catch (e) {
+      console.error("Failed to save translation settings:", e);
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <h3 className="text-sm font-medium text-[var(--color-text-primary)]">
+        Traduction
+      </h3>
+
+      <div className="space-y-2">
+        <input
+          className={inputClass}
+          placeholder="URL du service (ex. http://localhost:5000)"
+          value={draft.apiUrl}
+          onChange={(e) => setDraft({ ...draft, apiUrl: e.target.value })}
+        />
+        <input
+          className={inputClass}
+          type="password"
+          placeholder="Cle d'API (si le service en demande une)"
+          value={draft.apiKey}
+          onChange={(e) => setDraft({ ...draft, apiKey: e.target.value })}
+        />
+        <input
+          className={inputClass}
+          placeholder="Sous-titres en direct traduits en (ex. en, vide = non)"
+          value={draft.liveTarget ?? ""}
+          onChange={(e) => setDraft({ ...draft, liveTarget: e.target.value })}
+        />
+        <p className="text-xs text-[var(--color-text-muted)]">
+          Service compatible LibreTranslate. Un serveur LibreTranslate lance sur cette machine
+          garde les textes en local.
+        </p>
+      </div>
+
+      <button
+        onClick={handleSave}
+        className="w-full px-3 py-2 rounded-lg text-sm font-medium bg-[var(--color-bg-tertiary)]
+                   text-[var(--color-text-secondary)] hover:bg-[var(--color-border)] transition-colors"
+      >
+        Enregistrer
+      </button>
+    </div>
+  );
+}
@@ -3,8 +3,15 @@ import { ShortcutSettings } from "./ShortcutSettings";
 import { AppearanceSettings } from "./AppearanceSettings";
 import { TranscriptionSettings } from "./TranscriptionSettings";
 import { EngineSettings } from "./EngineSettings";
+import { SyncSettings } from "./SyncSettings";
+import { TranslationSettings } from "./TranslationSettings";
+import { SummarySettings } from "./SummarySettings";
+import { ModelDownloads } from "./ModelDownloads";
+import { HookSettings } from "./HookSettings";
+import { StorageSettings } from "./StorageSettings";
+import { ProvenanceSettings } from "./ProvenanceSettings";
 // Test button - commenter pour désactiver
-import { TestButton } from "../TestButton";
+import { TestButton, AudioLoopbackTest } from "../TestButton";
 
 interface SettingsProps {
   onClose: () => void;
@@ -45,14 +52,29 @@ export function Settings({ onClose }: SettingsProps) {
         <AppearanceSettings />
         <div className="border-t border-[var(--color-border)]" />
         <ShortcutSettings />
+        <div className="border-t border-[var(--color-border)]" />
+        <SyncSettings />
+        <div className="border-t border-[var(--color-border)]" />
+        <TranslationSettings />
+        <div className="border-t border-[var(--color-border)]" />
+        <SummarySettings />
+        <div className="border-t border-[var(--color-border)]" />
+        <HookSettings />
+        <div className="border-t border-[var(--color-border)]" />
+        <StorageSettings />
+        <div className="border-t border-[var(--color-border)]" />
+        <ProvenanceSettings />
 
         {/* Engine settings */}
         <div className="border-t border-[var(--color-border)]" />
         <EngineSettings />
+        <div className="border-t border-[var(--color-border)]" />
+        <ModelDownloads />
 
         {/* Test button - commenter pour désactiver */}
         <div className="border-t border-[var(--color-border)]" />
         <TestButton className="mt-4" />
+        <AudioLoopbackTest />
       </div>
 
       {/* Footer */}
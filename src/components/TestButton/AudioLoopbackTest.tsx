@@ -0,0 +1,111 @@
+/**
+ * Autotest de la chaine audio
+ *
+ * Joue un bip sur la sortie par defaut pendant l'enregistrement du micro
+ * selectionne, puis affiche la latence et les niveaux mesures.
+ * Diagnostic pour les enregistrements silencieux.
+ *
+ * Pour désactiver ce composant:
+ * 1. Supprimer l'import et l'utilisation dans Settings/index.tsx
+ * 2. Ou simplement ne pas rendre ce composant
+ */
+
+import { useState } from "react";
+import { useAppStore } from "../../stores/appStore";
+import { testAudioLoopback, type AudioLoopbackResult } from "../../lib/tauri";
+
+interface AudioLoopbackTestProps {
+  className?: string;
+}
+
+export function AudioLoopbackTest({ className = "" }: AudioLoopbackTestProps) {
+  const { selectedDeviceId, recordingState } = useAppStore();
+  const [isLoading, setIsLoading] = useState(false);
+  const [result, setResult] = useState<AudioLoopbackResult | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleTest = async () => {
+    setIsLoading(true);
+    setError(null);
+    setResult(null);
+
+    try {
+      setResult(await testAudioLoopback(selectedDeviceId ?? undefined));
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const busy = isLoading || recordingState !== "idle";
+
+  return (
+    <div className={`space-y-3 ${className}`}>
+      <button
+        onClick={handleTest}
+        disabled={busy}
+        className={`
+          w-full px-4 py-2 rounded-lg font-medium text-sm transition-all
+          ${busy
+            ? "bg-gray-400 cursor-not-allowed"
+            : "bg-blue-600 hover:bg-blue-700 text-white"
+          }
+        `}
+      >
+        {isLoading ? "Bip en cours..." : "Test micro (bip)"}
+      </button>
+
+      {/* Erreur */}
+      {error && (
+        <div className="p-3 bg-red-500/10 border border-red-500/30 rounded-lg">
+          <p className="text-red-600 dark:text-red-400 text-sm">{error}</p>
+        </div>
+      )}
+
+      {/* Résultats */}
+      {result && (
+        <div className="p-3 bg-[var(--color-bg-secondary)] border border-[var(--color-border)] rounded-lg space-y-3">
+          <p
+            className={`text-sm ${result.advice ? "text-yellow-600 dark:text-yellow-400" : "text-green-600 dark:text-green-400"}`}
+          >
+            {result.advice ?? "Chaine audio OK"}
+          </p>
+
+          <div className="grid grid-cols-2 gap-2 text-xs">
+            <div className="bg-[var(--color-bg-tertiary)] p-2 rounded">
+              <p className="text-[var(--color-text-muted)]">Latence</p>
+              <p className="text-[var(--color-text-primary)] font-mono">
+                {result.latency_ms !== null ? `${result.latency_ms} ms` : "-"}
+              </p>
+            </div>
+            <div className="bg-[var(--color-bg-tertiary)] p-2 rounded">
+              <p className="text-[var(--color-text-muted)]">Niveau (RMS / crete)</p>
+              <p className="text-[var(--color-text-primary)] font-mono">
+                {result.captured_rms.toFixed(4)} / {result.captured_peak.toFixed(3)}
+              </p>
+            </div>
+            <div className="bg-[var(--color-bg-tertiary)] p-2 rounded">
+              <p className="text-[var(--color-text-muted)]">Normalisation</p>
+              <p className="text-[var(--color-text-primary)] font-mono">
+                {result.normalization_gain.toFixed(1)}x
+              </p>
+            </div>
+            <div className="bg-[var(--color-bg-tertiary)] p-2 rounded">
+              <p className="text-[var(--color-text-muted)]">Frequence</p>
+              <p className="text-[var(--color-text-primary)] font-mono">
+                {result.measured_frequency_hz !== null
+                  ? `${Math.round(result.measured_frequency_hz)} Hz`
+                  : "-"}
+              </p>
+            </div>
+          </div>
+
+          <p className="text-xs text-[var(--color-text-muted)] truncate">
+            {result.output_device} - entree {result.input_sample_rate} Hz
+          </p>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -1,3 +1,4 @@
 // Export du composant TestButton
 // Pour désactiver: commenter cette ligne et les imports dans les composants parents
 export { TestButton } from "./TestButton";
+export { AudioLoopbackTest } from "./AudioLoopbackTest";
@@ -0,0 +1,43 @@
+import { useEffect, useRef } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { useAppStore } from "../stores/appStore";
+import { typeDictation } from "../lib/tauri";
+import type { PushToTalk, Transcription } from "../lib/types";
+
+// Record while the push-to-talk shortcut is held, then type the text into the focused application
+export function usePushToTalk(
+  start: () => Promise<void>,
+  stop: () => Promise<Transcription | null>
+) {
+  // Latest callbacks for the event handler, registered once
+  const latest = useRef({ start, stop });
+  latest.current = { start, stop };
+  // Set while a push-to-talk recording is starting or running
+  const starting = useRef<Promise<void> | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<PushToTalk>("push-to-talk", async (event) => {
+      if (event.payload.pressed) {
+        // Key repeat, or a recording started from the window: leave it alone
+        if (useAppStore.getState().recordingState !== "idle" || starting.current) return;
+        starting.current = latest.current.start();
+        return;
+      }
+
+      const pending = starting.current;
+      if (!pending) return;
+      await pending;
+      starting.current = null;
+      // Refused (microphone permission): nothing to stop
+      if (useAppStore.getState().recordingState !== "recording") return;
+      const transcription = await latest.current.stop();
+      const text = transcription?.editedText ?? transcription?.rawText;
+      if (text) {
+        typeDictation(text).catch((e) => console.error("Failed to type dictation:", e));
+      }
+    });
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+}
@@ -1,14 +1,30 @@
-import { useCallback, useEffect, useRef } from "react";
+import { useCallback, useEffect, useRef, useState } from "react";
 import { listen, UnlistenFn } from "@tauri-apps/api/event";
 import { useAppStore } from "../stores/appStore";
 import {
   startRecording as tauriStartRecording,
   stopRecording as tauriStopRecording,
+  startStreamingTranscription,
+  startRollingTranscription,
   pauseRecording as tauriPauseRecording,
   resumeRecording as tauriResumeRecording,
-  getAudioLevel as tauriGetAudioLevel,
+  getAudioWaveform,
+  getRecordingStatus as tauriGetRecordingStatus,
+  addRecordingMarker as tauriAddRecordingMarker,
+  listTranscriptions,
+  checkMicrophonePermission,
+  requestMicrophonePermission,
 } from "../lib/tauri";
-import type { Segment, StreamingSegment, DecodingConfig } from "../lib/types";
+import { speak, transcriptionReady } from "../lib/announcements";
+import type {
+  Segment,
+  StreamingSegment,
+  DecodingConfig,
+  AudioReset,
+  MicrophonePermission,
+  RecordingAutoStopped,
+  RecordingLimit,
+} from "../lib/types";
 
 export function useRecording() {
   const {
@@ -17,14 +33,18 @@ export function useRecording() {
     elapsedMs,
     currentSegments,
     pendingText,
+    translatedCaptions,
     settings,
     setRecordingState,
     setElapsedMs,
     addSegment,
     setPendingText,
+    addTranslatedCaption,
     setAudioLevel,
+    setWaveform,
     clearCurrentTranscription,
     addTranscription,
+    setTranscriptions,
   } = useAppStore();
 
   // Build DecodingConfig from settings
@@ -34,6 +54,11 @@ export function useRecording() {
     blank_penalty: settings.transcription.blankPenalty,
   }), [settings.transcription]);
 
+  // Set when recording was refused because the microphone is not accessible
+  const [permissionIssue, setPermissionIssue] = useState<MicrophonePermission | null>(null);
+  // Last length limit notice of the current recording
+  const [recordingLimit, setRecordingLimit] = useState<RecordingLimit | null>(null);
+
   const timerRef = useRef<ReturnType<typeof setInterval> | null>(null);
   const unlistenRefs = useRef<UnlistenFn[]>([]);
 
@@ -60,7 +85,47 @@ export function useRecording() {
         }
       );
 
-      unlistenRefs.current = [unlistenSegment];
+      // Final captions with their translation (live translation language set)
+      const unlistenCaption = await listen<StreamingSegment>("transcription-caption", (event) => {
+        addTranslatedCaption(event.payload);
+      });
+
+      // Audio thread restarted by the backend watchdog: earlier audio is lost
+      const unlistenReset = await listen<AudioReset>("audio-reset", (event) => {
+        console.warn("Audio capture was reset:", event.payload.reason);
+        clearCurrentTranscription();
+        if (!event.payload.recordingRestarted) {
+          setRecordingState("idle");
+        }
+      });
+
+      const unlistenLimit = await listen<RecordingLimit>("recording-limit", (event) => {
+        setRecordingLimit(event.payload);
+        speak(
+          settings,
+          event.payload.reached
+            ? "Duree maximale atteinte, l'enregistrement ne capte plus"
+            : "Duree maximale bientot atteinte"
+        );
+      });
+
+      // Stopped and transcribed by the backend after a long silence (auto-stop setting)
+      const unlistenAutoStop = await listen<RecordingAutoStopped>("recording-auto-stopped", (event) => {
+        const { transcription, error } = event.payload;
+        setRecordingState("idle");
+        if (transcription) {
+          addTranscription(transcription);
+          speak(settings, transcriptionReady(transcription));
+        } else {
+          console.error("Auto-stopped recording not transcribed:", error);
+          speak(settings, "Echec de la transcription");
+          listTranscriptions()
+            .then(setTranscriptions)
+            .catch((e) => console.error("Failed to reload history:", e));
+        }
+      });
+
+      unlistenRefs.current = [unlistenSegment, unlistenCaption, unlistenReset, unlistenLimit, unlistenAutoStop];
     }
 
     setupListeners();
@@ -68,21 +133,32 @@ export function useRecording() {
     return () => {
       unlistenRefs.current.forEach((unlisten) => unlisten());
     };
-  }, [elapsedMs, addSegment, setPendingText]);
+  }, [
+    elapsedMs,
+    addSegment,
+    setPendingText,
+    addTranslatedCaption,
+    clearCurrentTranscription,
+    setRecordingState,
+    addTranscription,
+    setTranscriptions,
+    settings,
+  ]);
 
-  // Poll audio level when recording
+  // Poll the level history when recording (one call for the level and the waveform)
   const audioLevelRef = useRef<ReturnType<typeof setInterval> | null>(null);
 
   useEffect(() => {
     if (recordingState === "recording") {
       audioLevelRef.current = setInterval(async () => {
         try {
-          const level = await tauriGetAudioLevel();
-          setAudioLevel(level);
+          const waveform = await getAudioWaveform();
+          setAudioLevel(waveform.level);
+          setWaveform(waveform.levels);
         } catch (e) {
-          console.error("Failed to get audio level:", e);
+          console.error("Failed to get audio waveform:", e);
         }
-      }, 50); // Poll every 50ms for smooth visualization
+      }, 100); // The history gets 10 values per second
     } else {
       if (audioLevelRef.current) {
         clearInterval(audioLevelRef.current);
@@ -96,13 +172,18 @@ export function useRecording() {
         clearInterval(audioLevelRef.current);
       }
     };
-  }, [recordingState, setAudioLevel]);
+  }, [recordingState, setAudioLevel, setWaveform]);
 
-  // Timer for elapsed time
+  // Timer for elapsed time (read from the capture buffer, not a local clock)
   useEffect(() => {
     if (recordingState === "recording") {
-      timerRef.current = setInterval(() => {
-        setElapsedMs(elapsedMs + 100);
+      timerRef.current = setInterval(async () => {
+        try {
+          const status = await tauriGetRecordingStatus();
+          setElapsedMs(status.elapsedMs);
+        } catch (e) {
+          console.error("Failed to get recording status:", e);
+        }
       }, 100);
     } else if (timerRef.current) {
       clearInterval(timerRef.current);
@@ -114,53 +195,106 @@ export function useRecording() {
         clearInterval(timerRef.current);
       }
     };
-  }, [recordingState, elapsedMs, setElapsedMs]);
+  }, [recordingState, setElapsedMs]);
 
   const start = useCallback(async () => {
     try {
+      // Denied access records silence without error on macOS: check first
+      let permission = await checkMicrophonePermission();
+      if (permission.state === "undetermined") {
+        permission = await requestMicrophonePermission();
+      }
+      if (permission.state !== "granted") {
+        setPermissionIssue(permission);
+        return;
+      }
+      setPermissionIssue(null);
+      setRecordingLimit(null);
+
       clearCurrentTranscription();
-      await tauriStartRecording(selectedDeviceId ?? undefined);
+      await tauriStartRecording(selectedDeviceId ?? undefined, settings.loopbackDeviceId);
       setRecordingState("recording");
+      speak(settings, "Enregistrement demarre");
+
+      // Captions arrive as "transcription-segment" events until the recording stops
+      if (settings.transcription.liveTranscription) {
+        startStreamingTranscription(
+          settings.transcription.language,
+          getDecodingConfig(),
+          settings.transcription.normalization
+        ).catch((e) => console.error("Failed to start live transcription:", e));
+      }
+      // Blocks transcribed in the background: only the end is left at stop
+      if (settings.transcription.rollingTranscription && !settings.loopbackDeviceId) {
+        startRollingTranscription(
+          settings.transcription.language,
+          getDecodingConfig(),
+          settings.transcription.normalization
+        ).catch((e) => console.error("Failed to start rolling transcription:", e));
+      }
     } catch (error) {
       console.error("Failed to start recording:", error);
       setRecordingState("idle");
+      speak(settings, "Echec du demarrage de l'enregistrement");
     }
-  }, [selectedDeviceId, clearCurrentTranscription, setRecordingState]);
+  }, [selectedDeviceId, clearCurrentTranscription, setRecordingState, settings, getDecodingConfig]);
 
   const stop = useCallback(async () => {
     try {
       setRecordingState("processing");
+      speak(settings, "Enregistrement termine, transcription en cours");
       // Use global settings for language and decoding config
       const language = settings.transcription.language;
       const decodingConfig = getDecodingConfig();
-      const transcription = await tauriStopRecording(language, decodingConfig);
+      const transcription = await tauriStopRecording(
+        language,
+        decodingConfig,
+        settings.transcription.normalization
+      );
       addTranscription(transcription);
       setRecordingState("idle");
+      speak(settings, transcriptionReady(transcription));
       return transcription;
     } catch (error) {
       console.error("Failed to stop recording:", error);
       setRecordingState("idle");
+      speak(settings, "Echec de la transcription");
+      // The live draft, if any, is kept in the history
+      listTranscriptions()
+        .then(setTranscriptions)
+        .catch((e) => console.error("Failed to reload history:", e));
       return null;
     }
-  }, [setRecordingState, addTranscription, settings.transcription, getDecodingConfig]);
+  }, [setRecordingState, addTranscription, setTranscriptions, settings, getDecodingConfig]);
 
   const pause = useCallback(async () => {
     try {
       await tauriPauseRecording();
       setRecordingState("paused");
+      speak(settings, "Enregistrement en pause");
     } catch (error) {
       console.error("Failed to pause recording:", error);
     }
-  }, [setRecordingState]);
+  }, [setRecordingState, settings]);
 
   const resume = useCallback(async () => {
     try {
       await tauriResumeRecording();
       setRecordingState("recording");
+      speak(settings, "Enregistrement repris");
     } catch (error) {
       console.error("Failed to resume recording:", error);
     }
-  }, [setRecordingState]);
+  }, [setRecordingState, settings]);
+
+  const addMarker = useCallback(async (label = "Important") => {
+    try {
+      return await tauriAddRecordingMarker(label);
+    } catch (error) {
+      console.error("Failed to add marker:", error);
+      return null;
+    }
+  }, []);
 
   const toggleRecording = useCallback(async () => {
     if (recordingState === "idle") {
@@ -183,10 +317,14 @@ export function useRecording() {
     elapsedMs,
     segments: currentSegments,
     pendingText,
+    translatedCaptions,
     start,
     stop,
     pause,
     resume,
+    addMarker,
+    permissionIssue,
+    recordingLimit,
     toggleRecording,
     togglePause,
   };
@@ -11,7 +11,11 @@ export function useSettings() {
     async function loadSettings() {
       try {
         const savedSettings = await getSettings();
-        setSettings(savedSettings);
+        // The backend keeps the language and decoding parameters only
+        setSettings({
+          ...savedSettings,
+          transcription: { ...useAppStore.getState().settings.transcription, ...savedSettings.transcription },
+        });
       } catch (error) {
         console.error("Failed to load settings:", error);
       }
@@ -4,15 +4,26 @@ import { useAppStore } from "../stores/appStore";
 import {
   listTranscriptions,
   getTranscription,
-  deleteTranscription as tauriDeleteTranscription,
+  trashTranscription,
   deleteAllTranscriptions as tauriDeleteAllTranscriptions,
   updateTranscriptionText,
+  isConflictError,
   transcribeFile as tauriTranscribeFile,
+  resumePendingTranscription,
   exportToTxt,
   exportToDocx,
+  exportToSrt,
+  exportToVtt,
+  exportToMarkdown,
+  exportToJson,
+  exportTranscriptJson,
   copyToClipboard,
 } from "../lib/tauri";
-import type { Transcription, TranscriptionProgress, DecodingConfig } from "../lib/types";
+import { speak, transcriptionReady } from "../lib/announcements";
+import type { Transcription, TranscriptionProgress, DecodingConfig, TranscriptFormat } from "../lib/types";
+
+// Transcriptions loaded per page in the history list
+export const HISTORY_PAGE_SIZE = 100;
 
 export function useTranscription() {
   const { transcriptions, setTranscriptions, addTranscription, settings } = useAppStore();
@@ -24,12 +35,30 @@ export function useTranscription() {
     blank_penalty: settings.transcription.blankPenalty,
   }), [settings.transcription]);
 
+  // First page; returns whether there are more
   const loadTranscriptions = useCallback(async () => {
     try {
-      const list = await listTranscriptions();
+      const list = await listTranscriptions(HISTORY_PAGE_SIZE);
       setTranscriptions(list);
+      return list.length === HISTORY_PAGE_SIZE;
     } catch (error) {
       console.error("Failed to load transcriptions:", error);
+      return false;
+    }
+  }, [setTranscriptions]);
+
+  // Next page, after the last loaded transcription; returns whether there are more
+  const loadMoreTranscriptions = useCallback(async () => {
+    const loaded = useAppStore.getState().transcriptions;
+    const last = loaded[loaded.length - 1];
+    if (!last) return false;
+    try {
+      const list = await listTranscriptions(HISTORY_PAGE_SIZE, { createdAt: last.createdAt, id: last.id });
+      setTranscriptions([...loaded, ...list]);
+      return list.length === HISTORY_PAGE_SIZE;
+    } catch (error) {
+      console.error("Failed to load more transcriptions:", error);
+      return false;
     }
   }, [setTranscriptions]);
 
@@ -52,10 +81,18 @@ export function useTranscription() {
 
         // Use global settings for language and decoding config
         const language = settings.transcription.language;
-        const decodingConfig = getDecodingConfig();
+        // Files are often re-run after small edits: reuse unchanged chunks
+        const decodingConfig = { ...getDecodingConfig(), chunk_cache: true };
 
-        const transcription = await tauriTranscribeFile(filePath, language, decodingConfig);
+        const transcription = await tauriTranscribeFile(
+          filePath,
+          language,
+          decodingConfig,
+          undefined,
+          settings.transcription.normalization
+        );
         addTranscription(transcription);
+        speak(settings, transcriptionReady(transcription));
 
         if (unlisten) {
           unlisten();
@@ -64,19 +101,46 @@ export function useTranscription() {
         return transcription;
       } catch (error) {
         console.error("Failed to transcribe file:", error);
+        speak(settings, "Echec de la transcription");
         return null;
       }
     },
-    [addTranscription, settings.transcription, getDecodingConfig]
+    [addTranscription, settings, getDecodingConfig]
   );
 
+  // Interrupted file transcription: the saved chunks are reused
+  const resumeFile = useCallback(
+    async (
+      pendingId: string,
+      onProgress?: (progress: TranscriptionProgress) => void
+    ): Promise<Transcription | null> => {
+      const unlisten = onProgress
+        ? await listen<TranscriptionProgress>("transcription-progress", (event) => onProgress(event.payload))
+        : null;
+      try {
+        const transcription = await resumePendingTranscription(pendingId);
+        addTranscription(transcription);
+        speak(settings, transcriptionReady(transcription));
+        return transcription;
+      } catch (error) {
+        console.error("Failed to resume transcription:", error);
+        speak(settings, "Echec de la transcription");
+        return null;
+      } finally {
+        unlisten?.();
+      }
+    },
+    [addTranscription, settings]
+  );
+
+  // To the trash: restored from the history's trash view
   const deleteTranscription = useCallback(
     async (id: string) => {
       try {
-        await tauriDeleteTranscription(id);
+        await trashTranscription(id);
         setTranscriptions(transcriptions.filter((t) => t.id !== id));
       } catch (error) {
-        console.error("Failed to delete transcription:", error);
+        console.error("Failed to move transcription to the trash:", error);
       }
     },
     [transcriptions, setTranscriptions]
@@ -91,13 +155,19 @@ export function useTranscription() {
     }
   }, [setTranscriptions]);
 
-  const updateText = useCallback(async (id: string, editedText: string) => {
-    try {
-      await updateTranscriptionText(id, editedText);
-    } catch (error) {
-      console.error("Failed to update transcription:", error);
-    }
-  }, []);
+  // Conflicts are rethrown: the editor asks whether to overwrite
+  const updateText = useCallback(
+    async (id: string, editedText: string, expectedUpdatedAt: string, force = false) => {
+      try {
+        return await updateTranscriptionText(id, editedText, expectedUpdatedAt, force);
+      } catch (error) {
+        if (isConflictError(error)) throw error;
+        console.error("Failed to update transcription:", error);
+        return null;
+      }
+    },
+    []
+  );
 
   const exportTxt = useCallback(async (id: string, path: string) => {
     try {
@@ -115,6 +185,46 @@ export function useTranscription() {
     }
   }, []);
 
+  const exportSrt = useCallback(async (id: string, path: string) => {
+    try {
+      await exportToSrt(id, path);
+    } catch (error) {
+      console.error("Failed to export to srt:", error);
+    }
+  }, []);
+
+  const exportVtt = useCallback(async (id: string, path: string) => {
+    try {
+      await exportToVtt(id, path);
+    } catch (error) {
+      console.error("Failed to export to vtt:", error);
+    }
+  }, []);
+
+  const exportMarkdown = useCallback(async (id: string, path: string) => {
+    try {
+      await exportToMarkdown(id, path);
+    } catch (error) {
+      console.error("Failed to export to markdown:", error);
+    }
+  }, []);
+
+  const exportFullJson = useCallback(async (id: string, path: string) => {
+    try {
+      await exportToJson(id, path);
+    } catch (error) {
+      console.error("Failed to export to full json:", error);
+    }
+  }, []);
+
+  const exportJson = useCallback(async (id: string, path: string, format: TranscriptFormat) => {
+    try {
+      await exportTranscriptJson(id, path, format);
+    } catch (error) {
+      console.error("Failed to export to json:", error);
+    }
+  }, []);
+
   const copyText = useCallback(async (text: string) => {
     try {
       await copyToClipboard(text);
@@ -126,13 +236,20 @@ export function useTranscription() {
   return {
     transcriptions,
     loadTranscriptions,
+    loadMoreTranscriptions,
     getTranscription,
     transcribeFile,
+    resumeFile,
     deleteTranscription,
     deleteAllTranscriptions,
     updateText,
     exportTxt,
     exportDocx,
+    exportSrt,
+    exportVtt,
+    exportMarkdown,
+    exportFullJson,
+    exportJson,
     copyText,
     // Expose transcription settings for components that need them
     transcriptionSettings: settings.transcription,
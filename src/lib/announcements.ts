@@ -0,0 +1,17 @@
+// Spoken feedback (Settings.spokenFeedback) for use without watching the screen
+import { announce } from "./tauri";
+import type { Settings, Transcription } from "./types";
+
+export function speak(settings: Settings, text: string) {
+  if (!settings.spokenFeedback) return;
+  announce(text).catch((e) => console.error("Failed to announce:", e));
+}
+
+export function transcriptionReady(transcription: Transcription): string {
+  const words = (transcription.editedText ?? transcription.rawText)
+    .split(/\s+/)
+    .filter(Boolean).length;
+  return words === 0
+    ? "Transcription prete, aucun mot reconnu"
+    : `Transcription prete, ${words} mot${words > 1 ? "s" : ""}`;
+}
@@ -1,11 +1,61 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import type {
+  AccuracyEvaluation,
   AudioDevice,
+  AudioWaveform,
+  DeviceCapabilities,
   Transcription,
+  TranscriptionSummary,
+  TranscriptionText,
   Settings,
+  Profile,
+  AppMode,
+  BackupImport,
+  DecodingProfile,
   TranscriptionProgress,
+  TranscriptionJob,
+  PendingTranscription,
+  TextNormalization,
   TranscriptionLanguage,
+  SupportedLanguage,
   DecodingConfig,
+  EngineStatusReport,
+  ExecutionProviderInfo,
+  ExportVerification,
+  DecodeTelemetry,
+  RecordingStatus,
+  LanguagePackInfo,
+  AvailableModel,
+  ModelStatus,
+  ModelCacheInfo,
+  SubtitleOptions,
+  TranscriptFormat,
+  HistoryCursor,
+  Segment,
+  BackendSwitch,
+  SearchFilters,
+  SearchHit,
+  JobEstimate,
+  EngineBackend,
+  Normalization,
+  StreamingConfig,
+  RecordingMarker,
+  PowerSource,
+  JobError,
+  LogEntry,
+  LogLevel,
+  MicrophonePermission,
+  SyncStatus,
+  TextUpdate,
+  TranscriptionRevision,
+  TextDraft,
+  Translation,
+  SummaryStyle,
+  TranscriptionNotes,
+  ActivityDay,
+  Tag,
+  Folder,
 } from "./types";
 
 // Audio commands
@@ -13,15 +63,66 @@ export async function listAudioDevices(): Promise<AudioDevice[]> {
   return invoke("list_audio_devices");
 }
 
-export async function startRecording(deviceId?: string): Promise<void> {
-  return invoke("start_recording", { deviceId });
+export async function getDeviceCapabilities(id?: string): Promise<DeviceCapabilities> {
+  return invoke("get_device_capabilities", { id });
+}
+
+export async function checkMicrophonePermission(): Promise<MicrophonePermission> {
+  return invoke("check_microphone_permission");
+}
+
+export async function requestMicrophonePermission(): Promise<MicrophonePermission> {
+  return invoke("request_microphone_permission");
+}
+
+export async function announce(text: string): Promise<void> {
+  return invoke("announce", { text });
+}
+
+export async function typeDictation(text: string): Promise<void> {
+  return invoke("type_dictation", { text });
+}
+
+export async function listLoopbackDevices(): Promise<AudioDevice[]> {
+  return invoke("list_loopback_devices");
+}
+
+export async function startRecording(deviceId?: string, loopbackDeviceId?: string): Promise<void> {
+  return invoke("start_recording", { deviceId, loopbackDeviceId });
 }
 
 export async function stopRecording(
   language?: TranscriptionLanguage,
-  decodingConfig?: DecodingConfig
+  decodingConfig?: DecodingConfig,
+  normalization?: Normalization
 ): Promise<Transcription> {
-  return invoke("stop_recording", { language, decodingConfig });
+  return awaitTranscriptionJob(() => invoke("stop_recording", { language, decodingConfig, normalization }));
+}
+
+export async function startStreamingTranscription(
+  language?: TranscriptionLanguage,
+  decodingConfig?: DecodingConfig,
+  normalization?: Normalization,
+  streamingConfig?: StreamingConfig
+): Promise<void> {
+  return invoke("start_streaming_transcription", {
+    language,
+    decodingConfig,
+    normalization,
+    streamingConfig,
+  });
+}
+
+export async function startRollingTranscription(
+  language?: TranscriptionLanguage,
+  decodingConfig?: DecodingConfig,
+  normalization?: Normalization
+): Promise<void> {
+  return invoke("start_rolling_transcription", {
+    language,
+    decodingConfig,
+    normalization,
+  });
 }
 
 export async function stopRecordingToWav(): Promise<string> {
@@ -40,20 +141,152 @@ export async function getAudioLevel(): Promise<number> {
   return invoke("get_audio_level");
 }
 
+export async function getAudioWaveform(): Promise<AudioWaveform> {
+  return invoke("get_audio_waveform");
+}
+
+export async function getRecordingStatus(): Promise<RecordingStatus> {
+  return invoke("get_recording_status");
+}
+
+export async function addRecordingMarker(label: string): Promise<RecordingMarker> {
+  return invoke("add_recording_marker", { label });
+}
+
 // File transcription commands
+export async function estimateJob(
+  input: string | number,
+  backend?: EngineBackend,
+  config?: DecodingConfig
+): Promise<JobEstimate> {
+  return invoke("estimate_job", { input, backend, config });
+}
+
 export async function transcribeFile(
   filePath: string,
   language?: TranscriptionLanguage,
   decodingConfig?: DecodingConfig,
-  _onProgress?: (progress: TranscriptionProgress) => void
+  _onProgress?: (progress: TranscriptionProgress) => void,
+  normalization?: Normalization
 ): Promise<Transcription> {
   // Progress updates come through Tauri events (handled via listen())
-  return invoke("transcribe_file", { filePath, language, decodingConfig });
+  return awaitTranscriptionJob(() => invoke("transcribe_file", { filePath, language, decodingConfig, normalization }));
+}
+
+export async function getJobStatus(id: string): Promise<TranscriptionJob> {
+  return invoke("get_job_status", { id });
+}
+
+export async function recoverPendingTranscriptions(): Promise<PendingTranscription[]> {
+  return invoke("recover_pending_transcriptions");
+}
+
+export async function resumePendingTranscription(id: string): Promise<Transcription> {
+  return awaitTranscriptionJob(() => invoke("resume_pending_transcription", { id }));
+}
+
+export async function finalizePendingTranscription(id: string): Promise<Transcription> {
+  return invoke("finalize_pending_transcription", { id });
+}
+
+export async function discardPendingTranscription(id: string): Promise<void> {
+  return invoke("discard_pending_transcription", { id });
+}
+
+// Queue a job and wait for its "transcription-job" event (listening first: a short job may end before the id is back)
+async function awaitTranscriptionJob(start: () => Promise<string>): Promise<Transcription> {
+  const finished = new Map<string, TranscriptionJob>();
+  let settle: ((job: TranscriptionJob) => void) | undefined;
+  const unlisten = await listen<TranscriptionJob>("transcription-job", (event) => {
+    const job = event.payload;
+    if (job.state !== "done" && job.state !== "failed") return;
+    finished.set(job.id, job);
+    settle?.(job);
+  });
+  try {
+    const id = await start();
+    const job =
+      finished.get(id) ??
+      (await new Promise<TranscriptionJob>((resolve) => {
+        settle = (job) => {
+          if (job.id === id) resolve(job);
+        };
+      }));
+    if (job.state === "failed" || !job.transcription) {
+      throw job.error ?? "Transcription failed";
+    }
+    return job.transcription;
+  } finally {
+    unlisten();
+  }
 }
 
 // History commands
-export async function listTranscriptions(): Promise<Transcription[]> {
-  return invoke("list_transcriptions");
+export async function listTranscriptions(limit?: number, after?: HistoryCursor): Promise<TranscriptionSummary[]> {
+  return invoke("list_transcriptions", { limit, after });
+}
+
+// Tags and folders
+export async function listTags(): Promise<Tag[]> {
+  return invoke("list_tags");
+}
+
+export async function addTag(name: string, color?: string): Promise<Tag> {
+  return invoke("add_tag", { name, color });
+}
+
+export async function deleteTag(id: string): Promise<void> {
+  return invoke("delete_tag", { id });
+}
+
+// Tagging, untagging and filing return the new updatedAt of the transcription
+export async function tagTranscription(id: string, tagId: string): Promise<string> {
+  return invoke("tag_transcription", { id, tagId });
+}
+
+export async function untagTranscription(id: string, tagId: string): Promise<string> {
+  return invoke("untag_transcription", { id, tagId });
+}
+
+export async function listByTag(tagId: string, limit?: number, after?: HistoryCursor): Promise<TranscriptionSummary[]> {
+  return invoke("list_by_tag", { tagId, limit, after });
+}
+
+export async function updateTranscriptionMetadata(
+  id: string,
+  title: string | null,
+  sourceName: string | null,
+  language: string
+): Promise<string> {
+  return invoke("update_transcription_metadata", { id, title, sourceName, language });
+}
+
+export async function setTranscriptionFolder(id: string, folder: string | null): Promise<string> {
+  return invoke("set_transcription_folder", { id, folder });
+}
+
+export async function listFolders(): Promise<Folder[]> {
+  return invoke("list_folders");
+}
+
+export async function listByFolder(folder: string, limit?: number, after?: HistoryCursor): Promise<TranscriptionSummary[]> {
+  return invoke("list_by_folder", { folder, limit, after });
+}
+
+export async function getTranscriptionSegments(id: string): Promise<Segment[]> {
+  return invoke("get_transcription_segments", { id });
+}
+
+export async function searchTranscriptions(query: string, filters?: SearchFilters): Promise<SearchHit[]> {
+  return invoke("search_transcriptions", { query, filters });
+}
+
+export async function getActivityCalendar(year: number): Promise<ActivityDay[]> {
+  return invoke("get_activity_calendar", { year });
+}
+
+export async function getTranscriptionText(id: string): Promise<TranscriptionText> {
+  return invoke("get_transcription_text", { id });
 }
 
 export async function getTranscription(id: string): Promise<Transcription> {
@@ -64,15 +297,87 @@ export async function deleteTranscription(id: string): Promise<void> {
   return invoke("delete_transcription", { id });
 }
 
+export async function getRecordingAudioPath(id: string): Promise<string | null> {
+  return invoke("get_recording_audio_path", { id });
+}
+
+export async function deleteRecordingAudio(id: string): Promise<void> {
+  return invoke("delete_recording_audio", { id });
+}
+
+export async function trashTranscription(id: string): Promise<void> {
+  return invoke("trash_transcription", { id });
+}
+
+export async function restoreTranscription(id: string): Promise<void> {
+  return invoke("restore_transcription", { id });
+}
+
+export async function listTrash(): Promise<TranscriptionSummary[]> {
+  return invoke("list_trash");
+}
+
+export async function purgeTrash(): Promise<number> {
+  return invoke("purge_trash");
+}
+
 export async function deleteAllTranscriptions(): Promise<void> {
   return invoke("delete_all_transcriptions");
 }
 
 export async function updateTranscriptionText(
   id: string,
-  editedText: string
-): Promise<void> {
-  return invoke("update_transcription_text", { id, editedText });
+  editedText: string,
+  expectedUpdatedAt: string,
+  force = false
+): Promise<TextUpdate> {
+  return invoke("update_transcription_text", { id, editedText, expectedUpdatedAt, force });
+}
+
+export async function getRevisionHistory(id: string): Promise<TranscriptionRevision[]> {
+  return invoke("get_revision_history", { id });
+}
+
+export async function revertToRevision(id: string, revision: number): Promise<TextUpdate> {
+  return invoke("revert_to_revision", { id, revision });
+}
+
+export async function saveDraft(id: string, text: string, baseUpdatedAt: string): Promise<TextDraft> {
+  return invoke("save_draft", { id, text, baseUpdatedAt });
+}
+
+export async function getTextDraft(id: string): Promise<TextDraft | null> {
+  return invoke("get_text_draft", { id });
+}
+
+export async function discardTextDraft(id: string): Promise<void> {
+  return invoke("discard_text_draft", { id });
+}
+
+export async function translateTranscription(id: string, targetLang: string): Promise<Translation> {
+  return invoke("translate_transcription", { id, targetLang });
+}
+
+export async function summarizeTranscription(id: string, style: SummaryStyle): Promise<TranscriptionNotes> {
+  return invoke("summarize_transcription", { id, style });
+}
+
+// The transcription was saved elsewhere since it was loaded
+export function isConflictError(error: unknown): boolean {
+  return String(error).startsWith("Conflict:");
+}
+
+// Profile commands
+export async function listProfiles(): Promise<Profile[]> {
+  return invoke("list_profiles");
+}
+
+export async function createProfile(name: string): Promise<Profile> {
+  return invoke("create_profile", { name });
+}
+
+export async function switchProfile(id: string): Promise<Settings> {
+  return invoke("switch_profile", { id });
 }
 
 // Settings commands
@@ -84,8 +389,109 @@ export async function updateSettings(settings: Partial<Settings>): Promise<void>
   return invoke("update_settings", { settings });
 }
 
+export async function getAppMode(): Promise<AppMode> {
+  return invoke("get_app_mode");
+}
+
+export async function moveDatabase(path?: string): Promise<string> {
+  return invoke("move_database", { path });
+}
+
+export async function getPowerSource(): Promise<PowerSource> {
+  return invoke("get_power_source");
+}
+
+export async function exportPreset(path: string): Promise<void> {
+  return invoke("export_preset", { path });
+}
+
+export async function importPreset(path: string): Promise<Settings> {
+  return invoke("import_preset", { path });
+}
+
+export async function exportDatabase(path: string): Promise<number> {
+  return invoke("export_database", { path });
+}
+
+export async function importDatabase(path: string): Promise<BackupImport> {
+  return invoke("import_database", { path });
+}
+
+export async function listDecodingProfiles(backend?: EngineBackend): Promise<DecodingProfile[]> {
+  return invoke("list_decoding_profiles", { backend });
+}
+
+export async function saveDecodingProfile(profile: DecodingProfile): Promise<Settings> {
+  return invoke("save_decoding_profile", { profile });
+}
+
+// Language pack commands
+export async function listLanguagePacks(): Promise<LanguagePackInfo[]> {
+  return invoke("list_language_packs");
+}
+
+export async function downloadLanguagePack(url: string): Promise<LanguagePackInfo> {
+  return invoke("download_language_pack", { url });
+}
+
+export async function setLanguagePackEnabled(language: string, enabled: boolean): Promise<void> {
+  return invoke("set_language_pack_enabled", { language, enabled });
+}
+
+// Model download commands
+export async function listAvailableModels(): Promise<AvailableModel[]> {
+  return invoke("list_available_models");
+}
+
+export async function downloadModel(modelId: string): Promise<ModelStatus> {
+  return invoke("download_model", { modelId });
+}
+
+export async function cancelDownload(modelId: string): Promise<void> {
+  return invoke("cancel_download", { modelId });
+}
+
+export async function getModelStatus(modelId: string): Promise<ModelStatus> {
+  return invoke("get_model_status", { modelId });
+}
+
+export async function getModelCacheInfo(): Promise<ModelCacheInfo> {
+  return invoke("get_model_cache_info");
+}
+
+export async function clearModelCache(): Promise<number> {
+  return invoke("clear_model_cache");
+}
+
+// Support commands
+export async function createSupportBundle(path: string): Promise<string> {
+  return invoke("create_support_bundle", { path });
+}
+
+export async function exportDiagnosticsBundle(path?: string): Promise<string> {
+  return invoke("export_diagnostics_bundle", { path });
+}
+
+export async function getRecentLogs(level?: LogLevel, limit?: number): Promise<LogEntry[]> {
+  return invoke("get_recent_logs", { level, limit });
+}
+
+// Background job commands
+export async function getRecentErrors(limit?: number): Promise<JobError[]> {
+  return invoke("get_recent_errors", { limit });
+}
+
+// Sync commands
+export async function syncNow(): Promise<SyncStatus> {
+  return invoke("sync_now");
+}
+
+export async function getSyncStatus(): Promise<SyncStatus> {
+  return invoke("get_sync_status");
+}
+
 // Engine commands
-export async function switchEngineBackend(backend: string): Promise<string> {
+export async function switchEngineBackend(backend: string): Promise<BackendSwitch> {
   return invoke("switch_engine_backend", { backend });
 }
 
@@ -93,6 +499,30 @@ export async function getEngineBackend(): Promise<string> {
   return invoke("get_engine_backend");
 }
 
+export async function getEngineStatus(): Promise<EngineStatusReport> {
+  return invoke("get_engine_status");
+}
+
+export async function warmupEngine(): Promise<EngineStatusReport> {
+  return invoke("warmup_engine");
+}
+
+export async function listSupportedLanguages(): Promise<SupportedLanguage[]> {
+  return invoke("list_supported_languages");
+}
+
+export async function reloadEngine(): Promise<string> {
+  return invoke("reload_engine");
+}
+
+export async function unloadEngine(): Promise<void> {
+  return invoke("unload_engine");
+}
+
+export async function getExecutionProviders(): Promise<ExecutionProviderInfo[]> {
+  return invoke("get_execution_providers");
+}
+
 // Export commands
 export async function exportToTxt(id: string, path: string): Promise<void> {
   return invoke("export_to_txt", { id, path });
@@ -102,6 +532,38 @@ export async function exportToDocx(id: string, path: string): Promise<void> {
   return invoke("export_to_docx", { id, path });
 }
 
+export async function exportToSrt(id: string, path: string, options?: SubtitleOptions): Promise<void> {
+  return invoke("export_to_srt", { id, path, options });
+}
+
+export async function exportToVtt(id: string, path: string, options?: SubtitleOptions): Promise<void> {
+  return invoke("export_to_vtt", { id, path, options });
+}
+
+export async function exportToMarkdown(id: string, path: string): Promise<void> {
+  return invoke("export_to_markdown", { id, path });
+}
+
+export async function exportToJson(id: string, path: string): Promise<void> {
+  return invoke("export_to_json", { id, path });
+}
+
+export async function exportTranscriptJson(id: string, path: string, format: TranscriptFormat): Promise<void> {
+  return invoke("export_transcript_json", { id, path, format });
+}
+
+export async function importTranscriptJson(path: string, format?: TranscriptFormat): Promise<Transcription> {
+  return invoke("import_transcript_json", { path, format });
+}
+
+export async function verifyExport(path: string): Promise<ExportVerification> {
+  return invoke("verify_export", { path });
+}
+
+export async function exportArchiveHtml(dir: string): Promise<number> {
+  return invoke("export_archive_html", { dir });
+}
+
 export async function copyToClipboard(text: string): Promise<void> {
   return invoke("copy_to_clipboard", { text });
 }
@@ -118,6 +580,8 @@ export interface TestTranscriptionResult {
     audio_samples: number;
     original_sample_rate: number;
     tokens_count: number;
+    decode_telemetry: DecodeTelemetry | null;
+    blank_collapse: boolean;
   };
 }
 
@@ -128,3 +592,31 @@ export async function testTranscription(): Promise<TestTranscriptionResult> {
 export async function checkTestAudio(): Promise<string> {
   return invoke("check_test_audio");
 }
+
+export async function evaluateAccuracy(
+  audioPath: string,
+  referenceText: string,
+  config?: DecodingConfig,
+  language?: TranscriptionLanguage,
+  normalization?: TextNormalization
+): Promise<AccuracyEvaluation> {
+  return invoke("evaluate_accuracy", { audioPath, referenceText, config, language, normalization });
+}
+
+export interface AudioLoopbackResult {
+  output_device: string;
+  input_sample_rate: number;
+  tone_detected: boolean;
+  latency_ms: number | null;
+  noise_rms: number;
+  captured_rms: number;
+  captured_peak: number;
+  normalized_rms: number;
+  normalization_gain: number;
+  measured_frequency_hz: number | null;
+  advice: string | null;
+}
+
+export async function testAudioLoopback(deviceId?: string): Promise<AudioLoopbackResult> {
+  return invoke("test_audio_loopback", { deviceId });
+}
@@ -1,29 +1,135 @@
 // Core domain types
 
 // Language selection for transcription
-export type TranscriptionLanguage = "auto" | "french" | "english";
+export type TranscriptionLanguage =
+  | "auto"
+  | "bulgarian"
+  | "croatian"
+  | "czech"
+  | "danish"
+  | "dutch"
+  | "english"
+  | "estonian"
+  | "finnish"
+  | "french"
+  | "german"
+  | "greek"
+  | "hungarian"
+  | "italian"
+  | "latvian"
+  | "lithuanian"
+  | "maltese"
+  | "polish"
+  | "portuguese"
+  | "romanian"
+  | "russian"
+  | "slovak"
+  | "slovenian"
+  | "spanish"
+  | "swedish"
+  | "ukrainian";
 
 export const TRANSCRIPTION_LANGUAGES: { value: TranscriptionLanguage; label: string }[] = [
   { value: "auto", label: "Auto" },
-  { value: "french", label: "Français" },
+  { value: "bulgarian", label: "Български" },
+  { value: "croatian", label: "Hrvatski" },
+  { value: "czech", label: "Čeština" },
+  { value: "danish", label: "Dansk" },
+  { value: "dutch", label: "Nederlands" },
   { value: "english", label: "English" },
+  { value: "estonian", label: "Eesti" },
+  { value: "finnish", label: "Suomi" },
+  { value: "french", label: "Français" },
+  { value: "german", label: "Deutsch" },
+  { value: "greek", label: "Ελληνικά" },
+  { value: "hungarian", label: "Magyar" },
+  { value: "italian", label: "Italiano" },
+  { value: "latvian", label: "Latviešu" },
+  { value: "lithuanian", label: "Lietuvių" },
+  { value: "maltese", label: "Malti" },
+  { value: "polish", label: "Polski" },
+  { value: "portuguese", label: "Português" },
+  { value: "romanian", label: "Română" },
+  { value: "russian", label: "Русский" },
+  { value: "slovak", label: "Slovenčina" },
+  { value: "slovenian", label: "Slovenščina" },
+  { value: "spanish", label: "Español" },
+  { value: "swedish", label: "Svenska" },
+  { value: "ukrainian", label: "Українська" },
 ];
 
+// Language the model can be forced to (list_supported_languages)
+export interface SupportedLanguage {
+  id: TranscriptionLanguage;
+  code: string; // ISO 639-1
+  name: string; // native name
+}
+
 // Decoding configuration for beam search and temperature
+// Blank penalty and temperature for one language
+export interface LanguageDecoding {
+  blankPenalty: number;
+  temperature: number;
+}
+
+// Beam width, temperature and blank penalty saved under a name for one backend
+export interface DecodingProfile {
+  name: string; // built-in: "fast", "accurate"
+  backend: EngineBackend;
+  beamWidth: number;
+  temperature: number;
+  blankPenalty: number;
+  builtIn?: boolean;
+}
+
+// How chunk transcriptions are concatenated
+export interface ChunkJoin {
+  separator: string;
+  sentence_gap_ms: number; // silence that ends a sentence when no punctuation was transcribed (0 = off)
+}
+
 export interface DecodingConfig {
   beam_width: number;
   temperature: number;
   blank_penalty: number;
+  max_symbols_per_frame?: number; // default 10
+  boundary_similarity?: number;   // 0-1, default 0.8 (0 = no chunk boundary dedup)
+  skip_non_speech?: boolean;      // replace music/noise chunks with [music]/[noise]
+  chunk_cache?: boolean;          // reuse cached results of unchanged chunks (file re-runs)
+  carry_decoder_state?: boolean;  // greedy only: resume each chunk from the previous decoder state (no chunk cache)
+  language_prior?: TranscriptionLanguage; // favored language in Auto mode (set from settings)
+  power_mode?: PowerMode;                 // chunk pacing on battery (set from settings)
+  chunk_join?: ChunkJoin;                 // joining of chunk texts (set from settings)
+  initial_prompt?: string;                // context/spellings for prompt-driven backends, ignored by Parakeet
 }
 
+// Low-power mode: how long transcriptions behave on battery
+export type PowerMode = "performance" | "battery_saver" | "wait_for_ac";
+
+export type PowerSource = "ac" | "battery" | "unknown";
+
 // Transcription settings (stored in app settings)
 export interface TranscriptionSettings {
   language: TranscriptionLanguage;
   beamWidth: number;      // 1 = greedy (fast), 5 = beam search (quality)
   temperature: number;    // 0.1-1.5, default 1.0
   blankPenalty: number;   // 0-15, default 6.0
+  normalization?: Normalization; // default: whole-file RMS
+  denoise?: Denoise; // before normalization, default "off"
+  liveTranscription?: boolean; // partial captions while recording, default false
+  rollingTranscription?: boolean; // long recordings transcribed in blocks while recording, default false
 }
 
+// Noise reduction before transcription
+export type Denoise = "off" | "high_pass" | "spectral_gate";
+
+// Level normalization before transcription (target optional)
+export type Normalization =
+  | { mode: "off" }
+  | { mode: "rms"; target?: number }       // linear RMS, default 0.15
+  | { mode: "lufs"; target?: number }      // LUFS, default -20
+  | { mode: "per_chunk"; target?: number }; // RMS per 5s window, default 0.15
+
 export const DEFAULT_TRANSCRIPTION_SETTINGS: TranscriptionSettings = {
   language: "auto",
   beamWidth: 1,
@@ -37,20 +143,277 @@ export interface Segment {
   endMs: number;
   text: string;
   confidence: number;
+  speaker?: Speaker; // dual-source recordings only
+  language?: string; // identified in Auto mode ("fr", "en")
+  words?: SegmentWord[]; // with the decoder's confidence, when the backend scores tokens
 }
 
+export interface SegmentWord {
+  text: string;
+  startMs: number;
+  endMs: number;
+  confidence: number;
+}
+
+export type Speaker = "me" | "them";
+
+export type TranscriptionSource = "dictation" | "file" | "stream" | "import" | "meeting";
+
 export interface Transcription {
   id: string;
   createdAt: string;
   updatedAt: string;
-  sourceType: "dictation" | "file";
+  sourceType: TranscriptionSource;
   sourceName?: string;
+  title?: string; // given by the user, shown instead of the source name
+  sourcePath?: string;
+  originalSampleRate?: number; // Hz, before resampling to 16kHz
   durationMs: number;
-  language: string;
+  language: string; // "fr" or "en": the forced language, or the one identified in Auto mode
   segments: Segment[];
   rawText: string;
   editedText?: string;
   isEdited: boolean;
+  isDraft?: boolean;                 // partial live text, final transcription missing
+  warnings?: TranscriptionWarning[]; // decoding issues, not persisted
+  normalizationGain?: number;        // gain applied before transcription, not persisted
+  actionItems?: ActionItem[];
+  markers?: RecordingMarker[];
+  pauses?: RecordingPause[]; // also "[pause]" segments at atMs
+  capture?: CaptureInfo;
+  confidence?: number; // mean word confidence (absent without token scores)
+  audioPath?: string; // kept recording (keepRecordings setting)
+  translations?: Translation[]; // one per target language
+  notes?: TranscriptionNotes[]; // LLM summaries, one per style, newest first
+  folder?: string; // client or project it is filed in
+  tags?: string[]; // tag names
+  deletedAt?: string; // in the trash since
+}
+
+// How the audio was captured (provenance of exports)
+export interface CaptureInfo {
+  device?: string;     // dictation input device (none: system default, or a file)
+  audioSha256: string; // source file, or captured samples of a dictation
+}
+
+// Metadata at the end of TXT/DOCX exports when exportProvenance is on
+export interface Provenance {
+  transcriptionId: string;
+  sourceName?: string;
+  device?: string;
+  recordedAt: string;
+  exportedAt: string;
+  appVersion: string;
+  audioSha256?: string;
+  contentSha256?: string; // document text before the provenance line
+  recordingSha256?: string; // WAV kept with the transcription
+}
+
+export interface ExportVerification {
+  provenance: Provenance;
+  storedHashMatches: boolean | null; // null: transcription deleted, or no hash
+  sourceFileMatches: boolean | null; // null: dictation, or source file gone
+  contentMatches: boolean | null; // null: exported before text hashes
+  recordingMatches: boolean | null; // null: recording not kept, or gone
+  verified: boolean;
+}
+
+// History list entry: metadata and start of the text (full text via getTranscriptionText)
+export interface TranscriptionSummary {
+  id: string;
+  createdAt: string;
+  updatedAt: string;
+  sourceType: TranscriptionSource;
+  sourceName?: string;
+  title?: string;
+  sourcePath?: string;
+  durationMs: number;
+  language: string;
+  preview: string;
+  isEdited: boolean;
+  isDraft?: boolean;
+  deletedAt?: string; // set in the trash listing
+  folder?: string;
+  tags?: string[]; // names, sorted
+}
+
+// Label of transcriptions (several per transcription)
+export interface Tag {
+  id: string;
+  name: string;
+  color?: string; // CSS color of the badge
+  count: number; // tagged transcriptions outside the trash
+}
+
+// Folder (client, project) in use
+export interface Folder {
+  name: string;
+  count: number;
+}
+
+// Activity of one day (local date "YYYY-MM-DD"), for the history heat map
+export interface ActivityDay {
+  date: string;
+  count: number;
+  minutes: number;
+}
+
+export interface TranscriptionText {
+  rawText: string;
+  editedText?: string;
+}
+
+// Opt-in encrypted mirror of the history
+export type SyncRemote =
+  | { kind: "webdav"; url: string; username: string; password: string }
+  | {
+      kind: "s3";
+      endpoint: string;
+      region: string;
+      bucket: string;
+      accessKeyId: string;
+      secretAccessKey: string;
+    };
+
+export interface SyncSettings {
+  enabled: boolean;
+  remote?: SyncRemote;
+  passphrase: string; // encryption passphrase, the same on every machine
+}
+
+// LibreTranslate-compatible service used by translateTranscription
+export interface TranslationSettings {
+  apiUrl: string; // e.g. http://localhost:5000 (empty = translation off)
+  apiKey: string;
+  liveTarget?: string; // language live captions are translated into ("en"...; empty = off)
+}
+
+// OpenAI-compatible or llama.cpp endpoint used by summarizeTranscription
+export interface LlmSettings {
+  apiUrl: string; // e.g. http://localhost:8080/v1 (empty = summaries off)
+  apiKey: string;
+  model: string;
+}
+
+export type SyncState = "idle" | "running" | "failed";
+
+// Payload of the "sync-status" event
+export interface SyncStatus {
+  state: SyncState;
+  lastSyncAt?: string;
+  uploaded: number;
+  downloaded: number;
+  error?: string;
+}
+
+// Microphone access as decided by the OS; instructions explain how to grant it
+export type PermissionState = "granted" | "denied" | "undetermined";
+
+export interface MicrophonePermission {
+  state: PermissionState;
+  instructions?: string;
+}
+
+// Moment flagged by the user while recording
+export interface RecordingMarker {
+  atMs: number; // recorded audio position, pauses excluded
+  label: string;
+}
+
+// Recording paused, then resumed at atMs
+export interface RecordingPause {
+  atMs: number;       // recorded audio position, pauses excluded
+  durationMs: number; // wall time not recorded
+}
+
+export interface ActionItem {
+  kind: "action" | "decision";
+  text: string;
+  owner?: string;
+  due?: string;
+}
+
+export interface TranscriptionWarning {
+  code: "max_symbols_per_frame" | "blank_collapse" | "partial" | string;
+  message: string;
+}
+
+export interface RecordingStatus {
+  isRecording: boolean;
+  isPaused: boolean;
+  elapsedMs: number;
+  bufferedSamples: number;
+  memoryBytes: number;
+  diskBytes?: number; // start of a long recording, moved to a temporary file
+}
+
+// Recent microphone levels of the current recording (getAudioWaveform)
+export interface AudioWaveform {
+  level: number;    // last captured block, as getAudioLevel
+  rateHz: number;   // values per second in levels
+  levels: number[]; // oldest first, one minute at most (0 to 1)
+}
+
+/** Emitted as "recording-auto-stopped" when a silent recording was stopped and transcribed */
+export interface RecordingAutoStopped {
+  silenceMs: number;
+  transcription?: Transcription; // absent when the transcription failed
+  error?: string;
+}
+
+/** Transcription queued by stopRecording / transcribeFile, sent as "transcription-job" on each change */
+// OpenVINO compiled-model cache (skips model compilation at startup)
+export interface ModelCacheInfo {
+  path?: string;
+  sizeBytes: number;
+  blobCount: number;
+}
+
+export interface TranscriptionJob {
+  id: string;
+  source: TranscriptionSource;
+  sourceName?: string; // file name of a file job
+  state: "queued" | "running" | "done" | "failed";
+  createdAt: string;
+  transcription?: Transcription; // once done
+  error?: string;
+}
+
+export interface PendingChunk {
+  index: number;
+  startMs: number;
+  endMs: number;
+  text: string;
+  continuesWord?: boolean; // glued to the previous chunk (cut inside a word)
+}
+
+// File transcription interrupted by a crash or a failure, with its chunks done
+export interface PendingTranscription {
+  id: string;
+  sourcePath: string;
+  sourceName?: string;
+  language?: TranscriptionLanguage;
+  decodingConfig?: DecodingConfig;
+  normalization?: Normalization;
+  chunkCount: number; // 0 until the first chunk is saved
+  chunks: PendingChunk[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+/** Emitted as "recording-limit" a few minutes before the length limit, then when it is reached */
+export interface RecordingLimit {
+  maxMs: number;
+  elapsedMs: number;
+  reached: boolean; // audio is no longer captured
+}
+
+/** Emitted as "audio-reset" when the audio thread died and was restarted */
+export interface AudioReset {
+  reason: string;
+  wasRecording: boolean;
+  recordingRestarted: boolean; // recording resumed on the same device, earlier audio lost
+  deviceId?: string;
 }
 
 export interface AudioDevice {
@@ -59,9 +422,66 @@ export interface AudioDevice {
   isDefault: boolean;
 }
 
+// Formats an input device accepts (getDeviceCapabilities)
+export interface DeviceCapabilities {
+  id: string;
+  sampleRates: number[];
+  defaultSampleRate: number;
+  maxChannels: number;
+  defaultChannels: number;
+  sampleFormats: string[]; // "f32", "i16", ...
+  minBufferSize?: number;  // frames, absent when the driver does not tell
+  maxBufferSize?: number;
+}
+
+// Stream of one input device; unset fields keep the device default
+export interface InputConfig {
+  sampleRate?: number;
+  channel?: number;    // only this channel is recorded (0 = first)
+  bufferSize?: number; // frames
+}
+
 // Available inference engine backends
 export type EngineBackend = "openvino" | "onnxruntime" | "coreml";
 
+/** Per-inference decoder statistics (blank-collapse detection) */
+export interface DecodeTelemetry {
+  steps: number;
+  blankSteps: number;
+  blankRatio: number;
+  avgBlankMargin: number;
+  tokens: number;
+  audioSeconds: number;
+  tokensPerSecond: number;
+  maxSymbolsGuardHits: number;
+}
+
+// Result of switch_engine_backend; deferred while recording or transcribing
+export interface BackendSwitch {
+  backend: string; // display name
+  deferred: boolean; // applied when the job ends ("engine-backend-switched" event)
+  decodingProfile: DecodingProfile; // chosen for this backend, its parameters become the defaults
+}
+
+// "engine-backend-switched" event: a deferred switch was applied (or failed)
+export interface DeferredSwitch {
+  backend: string;
+  error?: string;
+}
+
+/** Model loading state, emitted as "engine-ready" when loading finishes ("unloaded": released by unloadEngine) */
+export type EngineStatus = "loading" | "ready" | "unavailable" | "unloaded";
+
+// getEngineStatus: status with the cost of the loaded model
+export interface EngineStatusReport {
+  status: EngineStatus;
+  backend?: string;      // display name, once loaded
+  loadTimeMs?: number;
+  warmupMs?: number;     // absent until warmed up
+  modelBytes?: number;   // model files, held in memory
+  processBytes?: number; // resident memory of the app (Linux only)
+}
+
 export const ENGINE_BACKENDS: { value: EngineBackend; label: string; description: string }[] = [
   { value: "openvino", label: "OpenVINO", description: "Intel OpenVINO (default, optimized for Intel CPUs)" },
   { value: "onnxruntime", label: "ONNX Runtime", description: "Microsoft ONNX Runtime (cross-platform)" },
@@ -76,19 +496,222 @@ export interface Settings {
     toggleRecording: string;
     pause: string;
     copy: string;
+    pushToTalk?: string; // held to dictate into the focused application (empty: disabled)
   };
   transcription: TranscriptionSettings;
   engineBackend: EngineBackend;
+  substitutionRules?: SubstitutionRule[];
+  vocabulary?: string[];
+  enabledLanguagePacks?: string[];
+  interviewMode?: boolean; // format transcripts as Q:/A: pairs
+  autoLanguagePrior?: boolean; // Auto language favors the UI language (default true)
+  powerMode?: PowerMode;
+  chunkSeparator?: string; // inserted between chunk texts (default " ")
+  sentenceGapMs?: number;  // silence between chunks that ends a sentence (default 1000, 0 = off)
+  perLanguageDecoding?: boolean; // blank penalty/temperature from the language (default true)
+  languageDecodingOverrides?: Partial<Record<TranscriptionLanguage, LanguageDecoding>>;
+  decodingProfiles?: DecodingProfile[]; // saved by the user (built-in ones are not stored)
+  selectedDecodingProfiles?: Partial<Record<EngineBackend, string>>; // applied when switching backend
+  sync?: SyncSettings;
+  translation?: TranslationSettings;
+  llm?: LlmSettings;
+  spokenFeedback?: boolean; // announce recording/transcription state via OS text-to-speech
+  modelCatalogUrl?: string; // models.json listing downloadable models
+  postTranscriptionHooks?: PostTranscriptionHook[];
+  exportProvenance?: boolean; // end TXT/DOCX exports with device, dates and audio hash
+  livePaste?: boolean; // keep the confirmed live text in the clipboard while dictating (experimental)
+  onnxExecutionProvider?: ExecutionProvider; // ONNX Runtime hardware (default "cpu")
+  openvinoIsolated?: boolean; // OpenVINO in a worker process, a driver crash only fails the job
+  openvinoPerformance?: OpenVinoPerformance; // applied when the model loads
+  warmupEngine?: boolean; // short inference after the startup load (default true)
+  enginePool?: boolean; // second engine for file transcriptions, dictations don't wait for them
+  loopbackDeviceId?: string; // system audio recorded along with the microphone
+  dualSourceMode?: DualSourceMode; // default "mix"
+  duckOutput?: boolean; // lower the speakers while recording
+  duckOutputLevel?: number; // percent of the volume while ducked (default 30)
+  captureAgc?: boolean; // raise quiet microphone levels while recording
+  maxRecordingMinutes?: number; // audio no longer captured past it (0: no limit, default 180)
+  autoStopSilenceSecs?: number; // stop and transcribe after this much silence (0: never)
+  inputConfigs?: Record<string, InputConfig>; // by device id ("" for the system default)
+  simulatedInferenceDelayMs?: number; // dev: added to each inference to mimic a slow machine (0 = off)
+  debugDump?: boolean; // write model I/O of each transcription under app data
+  keepRecordings?: boolean; // save the audio of each recording for playback, default false
+  skipSilence?: boolean; // shorten pauses over 2s before transcribing files, default false
+  trashRetentionDays?: number; // purged from the trash at startup after this, 0 = never, default 30
+  databasePath?: string; // reported by getSettings, changed with moveDatabase only
+}
+
+export type DualSourceMode = "mix" | "separate";
+
+export type ExecutionProvider = "cpu" | "auto" | "cuda" | "directml" | "coreml";
+
+export const EXECUTION_PROVIDER_LABELS: Record<ExecutionProvider, string> = {
+  cpu: "CPU",
+  auto: "Automatique (GPU si disponible)",
+  cuda: "CUDA (NVIDIA)",
+  directml: "DirectML (Windows)",
+  coreml: "CoreML (Apple)",
+};
+
+export type PerformanceHint = "latency" | "throughput";
+
+// OpenVINO CPU use; unset fields are left to OpenVINO
+export interface OpenVinoPerformance {
+  numThreads?: number | null;
+  hint?: PerformanceHint | null;
+  cpuPinning?: boolean | null;
+  hyperThreading?: boolean | null;
+}
+
+export interface ExecutionProviderInfo {
+  provider: ExecutionProvider;
+  available: boolean | null; // unknown until the ONNX Runtime backend is loaded
+  active: boolean;
+}
+
+// Program run after each transcription (text on stdin and in {text_file}), no shell
+export interface PostTranscriptionHook {
+  name: string;
+  program: string;
+  args: string[]; // {id}, {text_file}, {source_path}, {language} are replaced
+  enabled: boolean;
+  timeoutSecs: number;
+}
+
+// Case-insensitive whole-word replacement applied after transcription
+export interface SubstitutionRule {
+  from: string;
+  to: string;
+}
+
+// Downloadable per-language post-processing resources
+// How much punctuation an output keeps
+export type PunctuationMode = "full" | "minimal";
+
+// Cue layout of SRT/VTT exports
+export interface SubtitleOptions {
+  maxLineChars?: number; // default 42
+  maxCueMs?: number; // default 6000
+  punctuation?: PunctuationMode; // default "minimal" (no commas, no closing period)
+}
+
+// Position in the history list: the last transcription of the previous page
+export interface HistoryCursor {
+  createdAt: string;
+  id: string;
+}
+
+// Restrictions of a history search (local dates, YYYY-MM-DD, inclusive)
+export interface SearchFilters {
+  from?: string;
+  to?: string;
+  sourceType?: TranscriptionSource;
+  language?: string;
+  tag?: string; // tag id
+  folder?: string;
+  limit?: number; // default 50
+}
+
+// Transcription matching a history search, best first
+export interface SearchHit {
+  transcription: TranscriptionSummary;
+  score: number; // higher is better
+  snippet: string; // matched words in <mark></mark>
+  startMs?: number; // best matching segment (absent when the match is in the full text)
+}
+
+// Transcript JSON shared with other tools: Whisper verbose_json, or a flat word list
+export type TranscriptFormat = "whisper_verbose" | "word_level";
+
+// Downloadable model from the catalog (models.json)
+export interface ModelFile {
+  name: string;
+  url: string;
+  sha256: string;
+  size: number;
+}
+
+export type ModelState =
+  | "not_installed"
+  | "downloading"
+  | "paused"
+  | "installed"
+  | "update_available"
+  | "failed";
+
+// Also the payload of "model-download-progress" events
+export interface ModelStatus {
+  modelId: string;
+  state: ModelState;
+  downloadedBytes: number;
+  totalBytes: number;
+  installedVersion: number | null;
+  error: string | null;
+}
+
+export interface AvailableModel {
+  id: string;
+  name: string;
+  backend: string;
+  version: number;
+  files: ModelFile[];
+  size: number;
+  status: ModelStatus;
+}
+
+export interface LanguagePackInfo {
+  language: string;
+  name: string;
+  version: number;
+  assets: string[];
+  enabled: boolean;
+}
+
+export interface Profile {
+  id: string;
+  name: string;
+  createdAt: string;
+  isActive: boolean;
+}
+
+// Outcome of restoring a backup
+export interface BackupImport {
+  imported: number;
+  copied: number; // id taken by a different transcription: added under a new id
+  skipped: number; // already present
+  settings: Settings;
+}
+
+// Read-only review mode (started with `--read-only <db path>`)
+export interface AppMode {
+  readOnly: boolean;
 }
 
 export type RecordingState = "idle" | "recording" | "paused" | "processing";
 
+// Push-to-talk shortcut went down or up ("push-to-talk" event)
+export interface PushToTalk {
+  pressed: boolean;
+}
+
 export type TranscriptionMode = "dictation" | "file";
 
+// Predicted cost of a job (estimate_job), from the measured speed of past runs
+export interface JobEstimate {
+  audioMs: number;
+  processingMs: number;
+  realtimeFactor: number; // audio time per wall-clock time
+  memoryBytes: number;
+  basedOnRuns: number; // 0: default speed, no run recorded on this backend yet
+}
+
 export interface TranscriptionProgress {
   currentMs: number;
   totalMs: number;
-  speedFactor: number;
+  speedFactor: number; // measured, audio time per wall-clock time
+  chunkIndex?: number; // chunks done, out of chunkCount (0 when not chunked)
+  chunkCount?: number;
+  simulated?: boolean; // slowed down by the low-resource simulation (dev setting)
 }
 
 // Tauri command return types
@@ -96,8 +719,128 @@ export interface TranscribeResult {
   transcription: Transcription;
 }
 
+// Latency bounds of live transcription (all optional, in ms)
+export interface StreamingConfig {
+  stepMs?: number;          // delay between two decodes, default 1000
+  minWindowMs?: number;     // default 1000
+  maxWindowMs?: number;     // default 12000
+  commitSilenceMs?: number; // pause that finalizes a caption, default 600
+}
+
 export interface StreamingSegment {
   text: string;
   isFinal: boolean;
   confidence?: number;
+  translatedText?: string; // only in "transcription-caption" events
+  targetLanguage?: string;
+}
+
+// Failure of a background job (stored, and sent as a "job-failed" event)
+export type JobKind = "model_load" | "watch_folder" | "scheduled_recording" | "refinement" | "hook";
+
+export type ErrorCode =
+  | "database"
+  | "audio"
+  | "transcription"
+  | "engine_crashed"
+  | "export"
+  | "io"
+  | "network"
+  | "serialization"
+  | "not_found"
+  | "invalid_state"
+  | "invalid_input"
+  | "conflict";
+
+// Result of saving an edited text
+export interface TextUpdate {
+  actionItems: ActionItem[];
+  updatedAt: string; // expected by the next save
+}
+
+// One saved version of a transcription's text
+export interface TranscriptionRevision {
+  revision: number; // 0 = transcribed text
+  text: string;
+  createdAt: string;
+}
+
+// Edited text not saved yet, kept while typing
+export interface TextDraft {
+  transcriptionId: string;
+  text: string;
+  baseUpdatedAt: string; // updatedAt of the transcription the edit started from
+  savedAt: string;
+}
+
+export type SummaryStyle = "brief" | "detailed" | "minutes";
+
+export const SUMMARY_STYLES: { value: SummaryStyle; label: string }[] = [
+  { value: "brief", label: "Resume court" },
+  { value: "detailed", label: "Resume detaille" },
+  { value: "minutes", label: "Compte rendu" },
+];
+
+// Summary, key points and actions written by the LLM
+export interface TranscriptionNotes {
+  style: SummaryStyle;
+  summary: string;
+  keyPoints: string[];
+  actionItems: string[];
+  model?: string; // as reported by the endpoint
+  createdAt: string;
+}
+
+// Transcription text translated into another language
+export interface Translation {
+  language: string; // target language code ("en", "de", ...)
+  text: string;
+  createdAt: string;
+}
+
+export interface JobError {
+  id: string;
+  job: JobKind;
+  code: ErrorCode;
+  message: string;
+  context?: string;
+  occurredAt: string;
+}
+
+/** What evaluateAccuracy ignores when comparing texts */
+export interface TextNormalization {
+  lowercase?: boolean; // default true
+  stripPunctuation?: boolean; // default true
+  stripAccents?: boolean; // default false
+}
+
+export interface EditCounts {
+  substitutions: number;
+  deletions: number;
+  insertions: number;
+  referenceLen: number;
+}
+
+/** Engine output compared to a reference transcript */
+export interface AccuracyEvaluation {
+  wer: number; // word errors / reference words
+  cer: number; // character errors / reference characters
+  words: EditCounts;
+  chars: EditCounts;
+  reference: string; // as compared, after normalization
+  hypothesis: string;
+  text: string; // raw engine output
+  audioDurationMs: number;
+  transcriptionTimeMs: number;
+}
+
+export type LogLevel = "error" | "warn" | "info" | "debug" | "trace";
+
+/** Log event kept in memory (getRecentLogs) */
+export interface LogEntry {
+  timestamp: string;
+  level: LogLevel;
+  target: string; // Rust module of the event
+  message: string;
+  fields: Record<string, string>;
 }
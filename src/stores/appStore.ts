@@ -3,8 +3,10 @@ import type {
   RecordingState,
   TranscriptionMode,
   Transcription,
+  TranscriptionSummary,
   Segment,
   Settings,
+  StreamingSegment,
   AudioDevice,
 } from "../lib/types";
 import { DEFAULT_TRANSCRIPTION_SETTINGS } from "../lib/types";
@@ -18,17 +20,19 @@ interface AppState {
   // Current transcription
   currentSegments: Segment[];
   pendingText: string;
+  translatedCaptions: StreamingSegment[]; // final live captions with their translation
 
   // Audio
   audioDevices: AudioDevice[];
   selectedDeviceId: string | null;
   audioLevel: number;
+  waveform: number[]; // level history of the recording, oldest first (10 per second)
 
   // Settings
   settings: Settings;
 
   // History
-  transcriptions: Transcription[];
+  transcriptions: TranscriptionSummary[];
 
   // UI
   showSettings: boolean;
@@ -40,12 +44,14 @@ interface AppState {
   setElapsedMs: (ms: number) => void;
   addSegment: (segment: Segment) => void;
   setPendingText: (text: string) => void;
+  addTranslatedCaption: (caption: StreamingSegment) => void;
   clearCurrentTranscription: () => void;
   setAudioDevices: (devices: AudioDevice[]) => void;
   setSelectedDeviceId: (id: string | null) => void;
   setAudioLevel: (level: number) => void;
+  setWaveform: (levels: number[]) => void;
   setSettings: (settings: Partial<Settings>) => void;
-  setTranscriptions: (transcriptions: Transcription[]) => void;
+  setTranscriptions: (transcriptions: TranscriptionSummary[]) => void;
   addTranscription: (transcription: Transcription) => void;
   toggleSettings: () => void;
   toggleHistory: () => void;
@@ -63,6 +69,22 @@ const defaultSettings: Settings = {
   engineBackend: "openvino",
 };
 
+function toSummary(t: Transcription): TranscriptionSummary {
+  return {
+    id: t.id,
+    createdAt: t.createdAt,
+    updatedAt: t.updatedAt,
+    sourceType: t.sourceType,
+    sourceName: t.sourceName,
+    sourcePath: t.sourcePath,
+    durationMs: t.durationMs,
+    language: t.language,
+    preview: (t.editedText ?? t.rawText).slice(0, 200),
+    isEdited: t.isEdited,
+    isDraft: t.isDraft,
+  };
+}
+
 export const useAppStore = create<AppState>((set) => ({
   // Initial state
   recordingState: "idle",
@@ -70,9 +92,11 @@ export const useAppStore = create<AppState>((set) => ({
   elapsedMs: 0,
   currentSegments: [],
   pendingText: "",
+  translatedCaptions: [],
   audioDevices: [],
   selectedDeviceId: null,
   audioLevel: 0,
+  waveform: [],
   settings: defaultSettings,
   transcriptions: [],
   showSettings: false,
@@ -90,16 +114,24 @@ export const useAppStore = create<AppState>((set) => ({
 
   setPendingText: (pendingText) => set({ pendingText }),
 
+  addTranslatedCaption: (caption) =>
+    set((state) => ({
+      translatedCaptions: [...state.translatedCaptions, caption],
+    })),
+
   clearCurrentTranscription: () =>
     set({
       currentSegments: [],
       pendingText: "",
+      translatedCaptions: [],
       elapsedMs: 0,
+      waveform: [],
     }),
 
   setAudioDevices: (audioDevices) => set({ audioDevices }),
   setSelectedDeviceId: (selectedDeviceId) => set({ selectedDeviceId }),
   setAudioLevel: (audioLevel) => set({ audioLevel }),
+  setWaveform: (waveform) => set({ waveform }),
 
   setSettings: (newSettings) =>
     set((state) => ({
@@ -110,7 +142,7 @@ export const useAppStore = create<AppState>((set) => ({
 
   addTranscription: (transcription) =>
     set((state) => ({
-      transcriptions: [transcription, ...state.transcriptions],
+      transcriptions: [toSummary(transcription), ...state.transcriptions],
       currentSegments: transcription.segments || [],
     })),
 